@@ -1,3 +1,4 @@
 mod routing;
+mod headers;
 
-criterion::criterion_main!(routing::routing);
+criterion::criterion_main!(routing::routing, headers::headers);
@@ -0,0 +1,51 @@
+use criterion::{criterion_group, Criterion};
+
+use rocket::http::{ContentType, Header, HeaderMap};
+
+/// Headers typically set on the response to a simple route.
+fn response_headers(map: &mut HeaderMap<'static>) {
+    map.replace(ContentType::JSON);
+    map.replace(Header::new("Server", "Rocket"));
+    map.replace(Header::new("X-Content-Type-Options", "nosniff"));
+    map.replace(Header::new("X-Frame-Options", "SAMEORIGIN"));
+    map.replace(Header::new("Permissions-Policy", "interest-cohort=()"));
+    map.add(Header::new("Vary", "Accept"));
+}
+
+/// Headers typically sent by a browser, borrowed from `raw`.
+fn request_headers<'a>(map: &mut HeaderMap<'a>, raw: &'a [(&'a str, &'a str)]) {
+    for &(name, value) in raw {
+        map.add(Header::new(name, value));
+    }
+}
+
+pub fn bench_response_headers(c: &mut Criterion) {
+    c.bench_function("headers.response", |b| b.iter(|| {
+        let mut map = HeaderMap::new();
+        response_headers(&mut map);
+        assert_eq!(map.iter().count(), 6);
+    }));
+}
+
+pub fn bench_request_headers(c: &mut Criterion) {
+    let raw = [
+        ("host", "localhost:8000"),
+        ("user-agent", "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0"),
+        ("accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+        ("accept-language", "en-US,en;q=0.5"),
+        ("accept-encoding", "gzip, deflate, br, zstd"),
+        ("connection", "keep-alive"),
+        ("upgrade-insecure-requests", "1"),
+        ("sec-fetch-dest", "document"),
+        ("sec-fetch-mode", "navigate"),
+        ("sec-fetch-site", "none"),
+    ];
+
+    c.bench_function("headers.request", |b| b.iter(|| {
+        let mut map = HeaderMap::new();
+        request_headers(&mut map, &raw);
+        assert_eq!(map.get_one("Accept-Language"), Some("en-US,en;q=0.5"));
+    }));
+}
+
+criterion_group!(headers, bench_response_headers, bench_request_headers);
@@ -124,4 +124,21 @@ pub fn bench_bitwarden_routes(c: &mut Criterion) {
     }));
 }
 
-criterion_group!(routing, bench_rust_lang_routes, bench_bitwarden_routes);
+pub fn bench_many_routes(c: &mut Criterion) {
+    let table: String = (0..2500)
+        .map(|i| format!("GET /api/resource{i}/<id>\nPOST /api/resource{i}/<id>/items/<item>\n"))
+        .collect();
+
+    let routes = parse_routes_table(&table);
+    let client = client(routes.clone());
+    let requests = generate_matching_requests(&client, &routes);
+    let requests: Vec<_> = requests.into_iter().step_by(97).collect();
+    c.bench_function("many.routes", |b| b.iter(|| {
+        for request in requests.clone() {
+            let response = request.dispatch();
+            assert_eq!(response.status(), Status::Ok);
+        }
+    }));
+}
+
+criterion_group!(routing, bench_rust_lang_routes, bench_bitwarden_routes, bench_many_routes);
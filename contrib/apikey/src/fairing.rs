@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rocket::{Rocket, Build};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::serde::Deserialize;
+
+use crate::{ApiKeyError, KeyHash, KeyInfo, KeyStore, StaticStore};
+use crate::store::ConfiguredKey;
+
+/// The default name of the header API keys are read from.
+const DEFAULT_HEADER: &str = "X-Api-Key";
+
+/// The API key configuration and store, available as managed state.
+///
+/// `ApiKeys` is placed in managed state by the fairing returned from
+/// [`ApiKeys::fairing()`] and is used by the [`ApiKey`](crate::ApiKey) request
+/// guard. It can also be used directly to resolve keys presented outside of a
+/// request header, for instance, in a WebSocket message. Cloning an `ApiKeys`
+/// is cheap.
+#[derive(Clone)]
+pub struct ApiKeys {
+    store: Arc<dyn KeyStore>,
+    header: Arc<str>,
+}
+
+/// The fairing returned by [`ApiKeys::fairing()`].
+///
+/// On ignition, the fairing reads the `api_keys` table of the application's
+/// configuration and places [`ApiKeys`] in managed state. The table's `header`
+/// names the header keys are read from and defaults to `X-Api-Key`. Unless a
+/// custom store is configured via [`ApiKeysFairing::store()`], keys are read
+/// from the table's `keys` table, keyed by key ID:
+///
+/// ```toml
+/// [default.api_keys]
+/// header = "X-Api-Key"
+///
+/// [default.api_keys.keys.ci]
+/// hash = "sha256:2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+/// owner = "ops@example.com"
+/// scopes = ["deploy", "read"]
+/// expires = "2030-01-01T00:00:00Z"
+/// ```
+///
+/// Only `hash` is required. Hashes are generated with [`KeyHash::generate()`]
+/// or, for an existing key, with `printf %s "$KEY" | sha256sum`. A missing
+/// `api_keys` table is treated as an empty one. Ignition fails if the table is
+/// invalid.
+pub struct ApiKeysFairing {
+    store: Option<Arc<dyn KeyStore>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+struct Config {
+    header: String,
+    keys: HashMap<String, ConfiguredKey>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { header: DEFAULT_HEADER.into(), keys: HashMap::new() }
+    }
+}
+
+impl ApiKeys {
+    /// Returns a fairing that places `ApiKeys` in managed state. See
+    /// [`ApiKeysFairing`] for details and options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_apikey::ApiKeys;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(ApiKeys::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> ApiKeysFairing {
+        ApiKeysFairing { store: None }
+    }
+
+    /// Creates `ApiKeys` that resolve keys presented in the header named
+    /// `header` against `store`.
+    pub fn new<S: KeyStore>(store: S, header: impl Into<String>) -> Self {
+        ApiKeys { store: Arc::new(store), header: header.into().into() }
+    }
+
+    /// Returns the name of the header keys are read from.
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Resolves the presented key `key` to its metadata.
+    ///
+    /// Fails with [`ApiKeyError::Invalid`] if the key is unknown,
+    /// [`ApiKeyError::Expired`] if it has expired, and [`ApiKeyError::Store`]
+    /// if the store fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_apikey::{ApiKeys, ApiKeyError, KeyHash, KeyInfo, StaticStore};
+    ///
+    /// let store = StaticStore::new().with(KeyHash::of("secret"), KeyInfo::new("ci"));
+    /// let keys = ApiKeys::new(store, "X-Api-Key");
+    ///
+    /// # rocket::async_test(async {
+    /// assert_eq!(keys.resolve("secret").await.unwrap().id, "ci");
+    /// assert_eq!(keys.resolve("guess").await, Err(ApiKeyError::Invalid));
+    /// # });
+    /// ```
+    pub async fn resolve(&self, key: &str) -> Result<KeyInfo, ApiKeyError> {
+        let info = match self.store.find(&KeyHash::of(key)).await {
+            Ok(Some(info)) => info,
+            Ok(None) => return Err(ApiKeyError::Invalid),
+            Err(e) => {
+                error!("API key store failed: {e}");
+                return Err(ApiKeyError::Store);
+            }
+        };
+
+        if info.is_expired() {
+            info!(key = %info.id, "rejected expired API key");
+            return Err(ApiKeyError::Expired);
+        }
+
+        Ok(info)
+    }
+}
+
+impl ApiKeysFairing {
+    /// Resolves keys against `store` instead of the configured keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_apikey::{ApiKeys, StaticStore, KeyHash, KeyInfo};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let store = StaticStore::new().with(KeyHash::of("secret"), KeyInfo::new("ci"));
+    ///     rocket::build().attach(ApiKeys::fairing().store(store))
+    /// }
+    /// ```
+    pub fn store<S: KeyStore>(mut self, store: S) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ApiKeysFairing {
+    fn info(&self) -> Info {
+        Info { name: "API Keys", kind: Kind::Ignite }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let config = match rocket.figment().extract_inner::<Config>("api_keys") {
+            Ok(config) => config,
+            Err(e) if e.missing() => Config::default(),
+            Err(e) => {
+                error!("invalid API key configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let store = match &self.store {
+            Some(store) => {
+                if !config.keys.is_empty() {
+                    warn!("configured API keys are ignored in favor of a custom store");
+                }
+
+                store.clone()
+            }
+            None => {
+                let store = config.keys.into_iter()
+                    .map(|(id, key)| key.into_entry(id))
+                    .fold(StaticStore::new(), |store, (hash, info)| store.with(hash, info));
+
+                info!(header = %config.header, keys = store.len(), "loaded configured API keys");
+                Arc::new(store)
+            }
+        };
+
+        Ok(rocket.manage(ApiKeys { store, header: config.header.into() }))
+    }
+}
+
+impl std::fmt::Debug for ApiKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeys").field("header", &self.header).finish_non_exhaustive()
+    }
+}
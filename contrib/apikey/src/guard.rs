@@ -0,0 +1,240 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use rocket::http::Status;
+use rocket::outcome::try_outcome;
+use rocket::request::{self, FromRequest, Outcome, Request};
+
+use crate::{ApiKeys, KeyInfo};
+
+/// A request guard for a request made with a valid API key.
+///
+/// The key is read from the header configured for [`ApiKeys`], `X-Api-Key` by
+/// default. If the header is `Authorization`, the key is read from a `Bearer`
+/// credential. The key is hashed and resolved via the configured
+/// [`KeyStore`](crate::KeyStore). On success, the guard dereferences to the
+/// key's [`KeyInfo`].
+///
+/// The guard fails with `401 Unauthorized` if the key is missing, unknown, or
+/// expired and with `500 Internal Server Error` if the store fails or
+/// [`ApiKeys::fairing()`] isn't attached. The key is resolved at most once per
+/// request, so the guard can be used in any number of places.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_apikey::ApiKey;
+///
+/// #[get("/whoami")]
+/// fn whoami(key: ApiKey) -> String {
+///     match &key.owner {
+///         Some(owner) => format!("{} ({owner})", key.id),
+///         None => key.id.clone(),
+///     }
+/// }
+/// ```
+///
+/// To make a key optional, use `Option<ApiKey>`. To distinguish failures, use
+/// `Result<ApiKey, ApiKeyError>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey(KeyInfo);
+
+/// A scope that can be named in a type, for use with [`Scoped`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_apikey::Scope;
+///
+/// struct Deploy;
+///
+/// impl Scope for Deploy {
+///     const NAME: &'static str = "deploy";
+/// }
+/// ```
+pub trait Scope: Send + Sync + 'static {
+    /// The name of the scope.
+    const NAME: &'static str;
+}
+
+/// A request guard for a request made with a valid API key that has the scope
+/// `S`.
+///
+/// The guard fails like [`ApiKey`] and, if the key lacks the scope, with `403
+/// Forbidden`. It dereferences to the [`ApiKey`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_apikey::{Scope, Scoped};
+///
+/// struct Deploy;
+///
+/// impl Scope for Deploy {
+///     const NAME: &'static str = "deploy";
+/// }
+///
+/// #[post("/deploy")]
+/// fn deploy(key: Scoped<Deploy>) -> String {
+///     format!("deploy started by {}", key.id)
+/// }
+/// ```
+pub struct Scoped<S: Scope>(ApiKey, PhantomData<S>);
+
+/// The outcome of resolving a request's key, cached per request.
+struct Resolved(Result<KeyInfo, ApiKeyError>);
+
+/// The reason an [`ApiKey`] or [`Scoped`] guard failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyError {
+    /// The request has no key.
+    Missing,
+    /// The key is unknown.
+    Invalid,
+    /// The key has expired.
+    Expired,
+    /// The key lacks the required scope.
+    Forbidden(&'static str),
+    /// The key store failed or isn't configured.
+    Store,
+}
+
+impl ApiKey {
+    /// Returns the key's metadata.
+    pub fn info(&self) -> &KeyInfo {
+        &self.0
+    }
+
+    /// Returns the key's metadata, consuming `self`.
+    pub fn into_info(self) -> KeyInfo {
+        self.0
+    }
+
+    /// Returns `Ok(())` if the key has the scope `scope` and
+    /// `Err(Status::Forbidden)` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Status;
+    /// use rocket_apikey::ApiKey;
+    ///
+    /// #[delete("/reports/<id>")]
+    /// fn delete(id: u64, key: ApiKey) -> Result<(), Status> {
+    ///     key.require(&format!("reports:{id}:delete")).or_else(|_| key.require("admin"))
+    /// }
+    /// ```
+    pub fn require(&self, scope: &str) -> Result<(), Status> {
+        match self.has_scope(scope) {
+            true => Ok(()),
+            false => Err(Status::Forbidden),
+        }
+    }
+}
+
+impl ApiKeyError {
+    /// Returns the status a failure of this kind is reported with.
+    pub fn status(&self) -> Status {
+        match self {
+            ApiKeyError::Missing | ApiKeyError::Invalid | ApiKeyError::Expired => {
+                Status::Unauthorized
+            }
+            ApiKeyError::Forbidden(_) => Status::Forbidden,
+            ApiKeyError::Store => Status::InternalServerError,
+        }
+    }
+}
+
+impl Deref for ApiKey {
+    type Target = KeyInfo;
+
+    fn deref(&self) -> &KeyInfo {
+        &self.0
+    }
+}
+
+impl<S: Scope> Deref for Scoped<S> {
+    type Target = ApiKey;
+
+    fn deref(&self) -> &ApiKey {
+        &self.0
+    }
+}
+
+/// Extracts the presented key from the request's headers.
+fn presented<'r>(req: &'r Request<'_>, header: &str) -> Option<&'r str> {
+    let value = req.headers().get_one(header)?.trim();
+    let key = match header.eq_ignore_ascii_case("authorization") {
+        true => {
+            let (scheme, credential) = value.split_once(' ')?;
+            scheme.eq_ignore_ascii_case("bearer").then(|| credential.trim())?
+        }
+        false => value,
+    };
+
+    (!key.is_empty()).then_some(key)
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ApiKey {
+    type Error = ApiKeyError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ApiKeyError> {
+        let resolved = req.local_cache_async(async {
+            let Some(keys) = req.rocket().state::<ApiKeys>() else {
+                error!("`ApiKey` guard used without attaching `ApiKeys::fairing()`");
+                return Resolved(Err(ApiKeyError::Store));
+            };
+
+            match presented(req, keys.header()) {
+                Some(key) => Resolved(keys.resolve(key).await),
+                None => Resolved(Err(ApiKeyError::Missing)),
+            }
+        }).await;
+
+        match &resolved.0 {
+            Ok(info) => Outcome::Success(ApiKey(info.clone())),
+            Err(e) => Outcome::Error((e.status(), *e)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, S: Scope> FromRequest<'r> for Scoped<S> {
+    type Error = ApiKeyError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ApiKeyError> {
+        let key = try_outcome!(ApiKey::from_request(req).await);
+        if !key.has_scope(S::NAME) {
+            info!(key = %key.id, scope = S::NAME, "API key lacks required scope");
+            let error = ApiKeyError::Forbidden(S::NAME);
+            return Outcome::Error((error.status(), error));
+        }
+
+        Outcome::Success(Scoped(key, PhantomData))
+    }
+}
+
+impl fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeyError::Missing => write!(f, "missing API key"),
+            ApiKeyError::Invalid => write!(f, "invalid API key"),
+            ApiKeyError::Expired => write!(f, "expired API key"),
+            ApiKeyError::Forbidden(scope) => write!(f, "API key lacks scope `{scope}`"),
+            ApiKeyError::Store => write!(f, "API key store unavailable"),
+        }
+    }
+}
+
+impl std::error::Error for ApiKeyError { }
+
+impl<S: Scope> fmt::Debug for Scoped<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Scoped").field(&S::NAME).field(&self.0).finish()
+    }
+}
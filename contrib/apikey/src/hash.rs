@@ -0,0 +1,151 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::RngCore;
+use rocket::serde::{Deserialize, Deserializer, de::Error as _};
+use sha2::{Digest, Sha256};
+
+/// The SHA-256 hash of an API key.
+///
+/// Keys are never stored, only their hashes. A `KeyHash` is computed from a
+/// presented key via [`KeyHash::of()`] and parsed from its textual form, 64
+/// hexadecimal digits optionally prefixed with `sha256:`, via [`FromStr`] or
+/// when deserialized. It displays in the prefixed form.
+///
+/// Hashes are compared in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_apikey::KeyHash;
+///
+/// let hash = KeyHash::of("secret");
+/// let text = "sha256:2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b";
+/// assert_eq!(hash.to_string(), text);
+/// assert_eq!(text.parse::<KeyHash>().unwrap(), hash);
+/// ```
+#[derive(Clone, Copy, Eq)]
+pub struct KeyHash([u8; 32]);
+
+/// The error returned when parsing an invalid [`KeyHash`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidHash;
+
+impl KeyHash {
+    const PREFIX: &'static str = "sha256:";
+
+    /// Computes the hash of `key`.
+    pub fn of(key: &str) -> KeyHash {
+        KeyHash(Sha256::digest(key.as_bytes()).into())
+    }
+
+    /// Generates a new random key, returning the key, to be given to its
+    /// holder, and its hash, to be stored.
+    ///
+    /// Keys are 256 bits from a cryptographically secure random number
+    /// generator, hex-encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_apikey::KeyHash;
+    ///
+    /// let (key, hash) = KeyHash::generate();
+    /// assert_eq!(key.len(), 64);
+    /// assert_eq!(KeyHash::of(&key), hash);
+    /// ```
+    pub fn generate() -> (String, KeyHash) {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let key = hex(&bytes);
+        let hash = KeyHash::of(&key);
+        (key, hash)
+    }
+
+    /// Returns the raw digest.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the digest as 64 lowercase hexadecimal digits, without a
+    /// prefix. Useful as the lookup key in a database.
+    pub fn to_hex(&self) -> String {
+        hex(&self.0)
+    }
+}
+
+impl PartialEq for KeyHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+impl FromStr for KeyHash {
+    type Err = InvalidHash;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix(KeyHash::PREFIX).unwrap_or(s).as_bytes();
+        if digits.len() != 64 || !digits.iter().all(u8::is_ascii_hexdigit) {
+            return Err(InvalidHash);
+        }
+
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+            let hex = std::str::from_utf8(pair).map_err(|_| InvalidHash)?;
+            *byte = u8::from_str_radix(hex, 16).map_err(|_| InvalidHash)?;
+        }
+
+        Ok(KeyHash(bytes))
+    }
+}
+
+impl fmt::Display for KeyHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", KeyHash::PREFIX, self.to_hex())
+    }
+}
+
+impl fmt::Debug for KeyHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyHash({self})")
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyHash {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let string = <std::borrow::Cow<'de, str>>::deserialize(de)?;
+        string.parse().map_err(|_| D::Error::custom("expected a SHA-256 hash of 64 hex digits"))
+    }
+}
+
+impl fmt::Display for InvalidHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key hash: expected 64 hex digits, optionally prefixed by `sha256:`")
+    }
+}
+
+impl std::error::Error for InvalidHash { }
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyHash;
+
+    #[test]
+    fn test_parse() {
+        let hex = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b";
+        assert_eq!(hex.parse::<KeyHash>(), Ok(KeyHash::of("secret")));
+        assert_eq!(format!("sha256:{hex}").parse::<KeyHash>(), Ok(KeyHash::of("secret")));
+        assert_eq!(hex.to_uppercase().parse::<KeyHash>(), Ok(KeyHash::of("secret")));
+
+        assert!("".parse::<KeyHash>().is_err());
+        assert!("sha256:".parse::<KeyHash>().is_err());
+        assert!(hex[1..].parse::<KeyHash>().is_err());
+        assert!(format!("md5:{hex}").parse::<KeyHash>().is_err());
+        assert!(format!("{}zz", &hex[2..]).parse::<KeyHash>().is_err());
+        assert!(format!("{}+f", &hex[2..]).parse::<KeyHash>().is_err());
+    }
+}
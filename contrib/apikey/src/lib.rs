@@ -0,0 +1,81 @@
+//! API key authentication.
+//!
+//! This crate authenticates requests made with API keys. Keys are never
+//! stored, only their SHA-256 [`KeyHash`]es, and are looked up in a
+//! [`KeyStore`]: either a [`StaticStore`] of keys listed in the application's
+//! configuration or a custom store, such as one backed by a database. Each key
+//! has [`KeyInfo`] metadata: an ID, an optional owner, scopes, and an optional
+//! expiration time.
+//!
+//! The [`ApiKey`] request guard validates the key presented with a request and
+//! exposes its metadata to the handler. [`Scoped`] additionally requires that
+//! the key have a given scope.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_apikey = "0.1.0"
+//! ```
+//!
+//! Then configure keys by hash, attach the fairing returned by
+//! [`ApiKeys::fairing()`], and use the guards:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_apikey::{ApiKey, ApiKeys, Scope, Scoped};
+//!
+//! struct Deploy;
+//!
+//! impl Scope for Deploy {
+//!     const NAME: &'static str = "deploy";
+//! }
+//!
+//! #[get("/status")]
+//! fn status(key: ApiKey) -> String {
+//!     format!("hello, {}", key.id)
+//! }
+//!
+//! #[post("/deploy")]
+//! fn deploy(key: Scoped<Deploy>) -> String {
+//!     format!("deploy started by {}", key.id)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![status, deploy])
+//!         .attach(ApiKeys::fairing())
+//! }
+//! ```
+//!
+//! With a `Rocket.toml` of:
+//!
+//! ```toml
+//! [default.api_keys.keys.ci]
+//! hash = "sha256:2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+//! scopes = ["deploy"]
+//! expires = "2030-01-01T00:00:00Z"
+//! ```
+//!
+//! A request to `/deploy` with the header `X-Api-Key: secret` succeeds. Without
+//! the header, or with another key, it fails with `401 Unauthorized`. See
+//! [`ApiKeysFairing`] for configuration details.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_apikey")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod guard;
+mod hash;
+mod store;
+
+pub use fairing::{ApiKeys, ApiKeysFairing};
+pub use guard::{ApiKey, ApiKeyError, Scope, Scoped};
+pub use hash::{KeyHash, InvalidHash};
+pub use store::{KeyStore, KeyInfo, StaticStore, Error};
@@ -0,0 +1,209 @@
+use std::collections::BTreeSet;
+
+use rocket::serde::{Deserialize, Deserializer, de::Error as _};
+use rocket::time::OffsetDateTime;
+use rocket::time::format_description::well_known::Rfc3339;
+
+use crate::KeyHash;
+
+/// The error type returned by a [`KeyStore`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Metadata about an API key, resolved from a [`KeyStore`].
+///
+/// Handlers access the metadata of the key a request was made with via the
+/// [`ApiKey`](crate::ApiKey) request guard.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::time::macros::datetime;
+/// use rocket_apikey::KeyInfo;
+///
+/// let info = KeyInfo::new("ci")
+///     .owner("ops@example.com")
+///     .scope("deploy")
+///     .expires(datetime!(2030-01-01 0:00 UTC));
+///
+/// assert!(info.has_scope("deploy"));
+/// assert!(!info.has_scope("admin"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyInfo {
+    /// The key's identifier. Unlike the key itself, the identifier is safe to
+    /// log and display.
+    pub id: String,
+    /// The key's owner, if known.
+    pub owner: Option<String>,
+    /// The key's scopes. A key with the scope `*` has every scope.
+    pub scopes: BTreeSet<String>,
+    /// When the key expires, if ever.
+    pub expires: Option<OffsetDateTime>,
+}
+
+impl KeyInfo {
+    /// Creates metadata for the key identified by `id` with no owner, no
+    /// scopes, and no expiration.
+    pub fn new(id: impl Into<String>) -> Self {
+        KeyInfo { id: id.into(), owner: None, scopes: BTreeSet::new(), expires: None }
+    }
+
+    /// Sets the key's owner to `owner`.
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Adds `scope` to the key's scopes.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.insert(scope.into());
+        self
+    }
+
+    /// Sets the key to expire at `expires`.
+    pub fn expires(mut self, expires: OffsetDateTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Returns `true` if the key has the scope `scope` or the scope `*`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.contains(scope) || self.scopes.contains("*")
+    }
+
+    /// Returns `true` if the key has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires.map_or(false, |expires| expires <= OffsetDateTime::now_utc())
+    }
+}
+
+/// A source of API keys, such as a database.
+///
+/// A store resolves the [`KeyHash`] of a presented key to the key's
+/// [`KeyInfo`]. By default, [`ApiKeys::fairing()`](crate::ApiKeys::fairing())
+/// uses a [`StaticStore`] of keys from the application's configuration. A
+/// custom store is configured via [`ApiKeysFairing::store()`].
+///
+/// Stores index keys by hash, never by the key itself. Because looking up a
+/// hash reveals nothing useful about the keys that hash to nearby values,
+/// stores may look hashes up directly, for instance, with an indexed database
+/// query on [`KeyHash::to_hex()`].
+///
+/// Expiration is checked by the caller; stores may return expired keys.
+///
+/// [`ApiKeysFairing::store()`]: crate::ApiKeysFairing::store()
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_apikey::{KeyStore, KeyHash, KeyInfo, Error};
+///
+/// # struct Pool;
+/// # impl Pool {
+/// #     async fn find_key(&self, _: &str) -> Result<Option<(String, Vec<String>)>, Error> {
+/// #         Ok(None)
+/// #     }
+/// # }
+/// struct DbStore { pool: Pool }
+///
+/// #[rocket::async_trait]
+/// impl KeyStore for DbStore {
+///     async fn find(&self, hash: &KeyHash) -> Result<Option<KeyInfo>, Error> {
+///         // SELECT id, scopes FROM api_keys WHERE hash = ?
+///         let row = self.pool.find_key(&hash.to_hex()).await?;
+///         Ok(row.map(|(id, scopes)| {
+///             scopes.into_iter().fold(KeyInfo::new(id), |info, s| info.scope(s))
+///         }))
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait KeyStore: Send + Sync + 'static {
+    /// Returns the metadata of the key whose hash is `hash`, if there is one.
+    async fn find(&self, hash: &KeyHash) -> Result<Option<KeyInfo>, Error>;
+}
+
+/// A fixed set of keys held in memory.
+///
+/// Lookups compare the presented hash against every key's hash, in constant
+/// time, without stopping at a match.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_apikey::{StaticStore, KeyHash, KeyInfo};
+///
+/// let store = StaticStore::new()
+///     .with(KeyHash::of("secret"), KeyInfo::new("ci").scope("deploy"));
+///
+/// assert_eq!(store.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StaticStore {
+    keys: Vec<(KeyHash, KeyInfo)>,
+}
+
+impl StaticStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        StaticStore::default()
+    }
+
+    /// Adds the key with hash `hash` and metadata `info`.
+    pub fn with(mut self, hash: KeyHash, info: KeyInfo) -> Self {
+        self.keys.push((hash, info));
+        self
+    }
+
+    /// Returns the number of keys in the store.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the store has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[rocket::async_trait]
+impl KeyStore for StaticStore {
+    async fn find(&self, hash: &KeyHash) -> Result<Option<KeyInfo>, Error> {
+        let mut found = None;
+        for (key, info) in &self.keys {
+            if key == hash && found.is_none() {
+                found = Some(info);
+            }
+        }
+
+        Ok(found.cloned())
+    }
+}
+
+/// A key as configured in the `api_keys.keys` table.
+#[derive(Debug, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub(crate) struct ConfiguredKey {
+    hash: KeyHash,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    scopes: BTreeSet<String>,
+    #[serde(default, deserialize_with = "rfc3339")]
+    expires: Option<OffsetDateTime>,
+}
+
+impl ConfiguredKey {
+    pub(crate) fn into_entry(self, id: String) -> (KeyHash, KeyInfo) {
+        let info = KeyInfo { id, owner: self.owner, scopes: self.scopes, expires: self.expires };
+        (self.hash, info)
+    }
+}
+
+fn rfc3339<'de, D: Deserializer<'de>>(de: D) -> Result<Option<OffsetDateTime>, D::Error> {
+    let string = <std::borrow::Cow<'de, str>>::deserialize(de)?;
+    OffsetDateTime::parse(&string, &Rfc3339)
+        .map(Some)
+        .map_err(|e| D::Error::custom(format!("invalid RFC 3339 date-time: {e}")))
+}
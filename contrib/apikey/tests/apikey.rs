@@ -0,0 +1,131 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket_apikey::*;
+
+struct Deploy;
+
+impl Scope for Deploy {
+    const NAME: &'static str = "deploy";
+}
+
+#[get("/whoami")]
+fn whoami(key: ApiKey) -> String {
+    format!("{}:{}", key.id, key.owner.as_deref().unwrap_or("-"))
+}
+
+#[post("/deploy")]
+fn deploy(key: Scoped<Deploy>) -> String {
+    key.id.clone()
+}
+
+#[get("/error")]
+fn error(key: Result<ApiKey, ApiKeyError>) -> String {
+    match key {
+        Ok(key) => key.id.clone(),
+        Err(e) => e.to_string(),
+    }
+}
+
+const SECRET: &str = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b";
+
+fn client(figment: rocket::figment::Figment, fairing: ApiKeysFairing) -> Client {
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![whoami, deploy, error])
+        .attach(fairing);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn configured() -> Client {
+    let figment = rocket::Config::figment()
+        .merge(("api_keys.keys.ci.hash", format!("sha256:{SECRET}")))
+        .merge(("api_keys.keys.ci.owner", "ops"))
+        .merge(("api_keys.keys.ci.scopes", ["deploy"]))
+        .merge(("api_keys.keys.old.hash", KeyHash::of("old").to_string()))
+        .merge(("api_keys.keys.old.expires", "2000-01-01T00:00:00Z"))
+        .merge(("api_keys.keys.reader.hash", KeyHash::of("reader").to_hex()));
+
+    client(figment, ApiKeys::fairing())
+}
+
+fn get(client: &Client, path: &str, key: Option<&str>) -> (Status, Option<String>) {
+    let mut request = client.get(path);
+    if let Some(key) = key {
+        request = request.header(Header::new("X-Api-Key", key.to_string()));
+    }
+
+    let response = request.dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn configured_keys_authenticate() {
+    let client = configured();
+    assert_eq!(get(&client, "/whoami", Some("secret")), (Status::Ok, Some("ci:ops".into())));
+    assert_eq!(get(&client, "/whoami", Some("reader")).1, Some("reader:-".into()));
+
+    assert_eq!(get(&client, "/whoami", None).0, Status::Unauthorized);
+    assert_eq!(get(&client, "/whoami", Some("")).0, Status::Unauthorized);
+    assert_eq!(get(&client, "/whoami", Some("guess")).0, Status::Unauthorized);
+    assert_eq!(get(&client, "/whoami", Some("old")).0, Status::Unauthorized);
+
+    assert_eq!(get(&client, "/error", None).1, Some("missing API key".into()));
+    assert_eq!(get(&client, "/error", Some("guess")).1, Some("invalid API key".into()));
+    assert_eq!(get(&client, "/error", Some("old")).1, Some("expired API key".into()));
+}
+
+#[test]
+fn scopes_are_enforced() {
+    let client = configured();
+    let response = client.post("/deploy").header(Header::new("X-Api-Key", "secret")).dispatch();
+    assert_eq!(response.into_string(), Some("ci".into()));
+
+    let response = client.post("/deploy").header(Header::new("X-Api-Key", "reader")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.post("/deploy").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn bearer_keys_and_custom_stores() {
+    let (key, hash) = KeyHash::generate();
+    let store = StaticStore::new().with(hash, KeyInfo::new("gen").scope("*"));
+    let figment = rocket::Config::figment().merge(("api_keys.header", "Authorization"));
+    let client = client(figment, ApiKeys::fairing().store(store));
+
+    let bearer = Header::new("Authorization", format!("Bearer {key}"));
+    let response = client.post("/deploy").header(bearer).dispatch();
+    assert_eq!(response.into_string(), Some("gen".into()));
+
+    let basic = Header::new("Authorization", format!("Basic {key}"));
+    let response = client.get("/whoami").header(basic).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let wrong_header = Header::new("X-Api-Key", key);
+    let response = client.get("/whoami").header(wrong_header).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn invalid_configuration_fails_ignition() {
+    let figment = rocket::Config::figment().merge(("api_keys.keys.ci.hash", "not-a-hash"));
+    let rocket = rocket::custom(figment).attach(ApiKeys::fairing());
+    assert!(Client::debug(rocket).is_err());
+
+    let figment = rocket::Config::figment()
+        .merge(("api_keys.keys.ci.hash", SECRET))
+        .merge(("api_keys.keys.ci.expires", "tomorrow"));
+
+    let rocket = rocket::custom(figment).attach(ApiKeys::fairing());
+    assert!(Client::debug(rocket).is_err());
+}
+
+#[test]
+fn guard_fails_without_fairing() {
+    let client = Client::debug_with(routes![whoami]).unwrap();
+    let response = client.get("/whoami").header(Header::new("X-Api-Key", "secret")).dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use rocket::{Rocket, Build, Request, Data};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::fs::NamedFile;
+use rocket::http::{Method, Status, uri::{Segments, fmt::Path as UriPath}};
+use rocket::response::Cached;
+use rocket::route::{Route, Handler, Outcome};
+use rocket::tokio::task::spawn_blocking;
+
+use crate::map::{self, AssetMap, Inner};
+
+/// The default asset directory, relative to the current working directory.
+const DEFAULT_DIR: &str = "static";
+
+/// The default path assets are served at.
+const DEFAULT_PATH: &str = "/static";
+
+/// How long fingerprinted assets are cached for: one year, in seconds.
+const IMMUTABLE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+/// A fairing that fingerprints and serves static assets.
+///
+/// At ignition, the fairing hashes the contents of every visible file in the
+/// asset directory, by default `static`, and records a _fingerprinted_ name
+/// for it that includes the hash: `app.css` becomes, say,
+/// `app.4c1f0e9a2b7d3c55.css`. The fingerprinted URLs are available through
+/// the [`AssetMap`], which is managed and can be retrieved as a request guard
+/// or via [`Assets::map()`].
+///
+/// The fairing mounts a handler at the asset path, by default `/static`, for
+/// `GET` requests which serves:
+///
+///   * fingerprinted names, like `/static/app.4c1f0e9a2b7d3c55.css`, with a
+///     `Cache-Control: public, max-age=31536000, immutable` header. Because a
+///     file's URL changes whenever its contents do, clients can cache these
+///     responses indefinitely without ever using a stale asset;
+///   * plain names, like `/static/app.css`, with a `Cache-Control: no-cache`
+///     header, so that references to assets that bypass the map still work.
+///
+/// Any other request is forwarded with a `404`. Only files present at
+/// ignition are served. The handler has a rank of `10`, like
+/// [`FileServer`](rocket::fs::FileServer).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::RawHtml;
+/// use rocket_assets::{Assets, AssetMap};
+///
+/// #[get("/")]
+/// fn index(assets: &AssetMap) -> RawHtml<String> {
+///     let css = assets.asset_url("app.css").unwrap_or_default();
+///     RawHtml(format!(r#"<link rel="stylesheet" href="{css}">"#))
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .attach(Assets::fairing().dir("assets/dist").path("/assets"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Assets {
+    dir: PathBuf,
+    path: String,
+    rank: isize,
+    map: AssetMap,
+}
+
+impl Assets {
+    /// The default rank of the mounted route.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Returns a fairing that serves the assets in `static` at `/static`.
+    pub fn fairing() -> Self {
+        Assets {
+            dir: DEFAULT_DIR.into(),
+            path: DEFAULT_PATH.into(),
+            rank: Self::DEFAULT_RANK,
+            map: AssetMap::default(),
+        }
+    }
+
+    /// Serves the assets in `dir`, relative to the current working directory,
+    /// instead of `static`.
+    pub fn dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Serves assets at `path` instead of `/static`.
+    ///
+    /// # Panics
+    ///
+    /// Ignition panics if `path` is not a valid mount point.
+    pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the rank of the mounted route to `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Returns a handle to the map this fairing populates at ignition.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_assets::Assets;
+    ///
+    /// let assets = Assets::fairing();
+    /// let map = assets.map();
+    /// assert!(map.asset_url("app.css").is_none());
+    /// ```
+    pub fn map(&self) -> AssetMap {
+        self.map.clone()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Assets {
+    fn info(&self) -> Info {
+        Info { name: "Assets", kind: Kind::Ignite }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if rocket.state::<AssetMap>().is_some() {
+            error!("an `AssetMap` is already being managed");
+            return Err(rocket);
+        }
+
+        let (dir, path) = (self.dir.clone(), self.path.clone());
+        let inner = match spawn_blocking(move || Inner::hash_dir(&dir, &path)).await {
+            Ok(Ok(inner)) => inner,
+            Ok(Err(e)) => {
+                error!(dir = %self.dir.display(), "failed to fingerprint assets: {e}");
+                return Err(rocket);
+            }
+            Err(e) => {
+                error!(dir = %self.dir.display(), "asset fingerprinting panicked: {e}");
+                return Err(rocket);
+            }
+        };
+
+        if !self.map.set(inner) {
+            warn!("`Assets` fairing ignited more than once: keeping existing fingerprints");
+        }
+
+        span_info!("assets", dir = %self.dir.display(), path = %self.path => {
+            info!(assets = self.map.iter().count(), "fingerprinted assets");
+        });
+
+        let server = Server { dir: self.dir.clone(), map: self.map.clone() };
+        let mut route = Route::ranked(self.rank, Method::Get, "/<path..>", server);
+        route.name = Some("Assets".into());
+
+        Ok(rocket.manage(self.map.clone()).mount(self.path.clone(), vec![route]))
+    }
+}
+
+/// The handler mounted by [`Assets`].
+#[derive(Clone)]
+struct Server {
+    dir: PathBuf,
+    map: AssetMap,
+}
+
+#[rocket::async_trait]
+impl Handler for Server {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let name = req.segments::<Segments<'_, UriPath>>(0..).ok()
+            .and_then(|segments| segments.to_path_buf(false).ok())
+            .and_then(|path| map::asset_name(&path));
+
+        let Some(name) = name else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+
+        if let Some(asset) = self.map.resolve(&name) {
+            let file = NamedFile::open(self.dir.join(asset)).await.ok();
+            let response = Cached::new(file).public().max_age(IMMUTABLE_MAX_AGE).immutable();
+            return Outcome::from(req, response);
+        }
+
+        if self.map.contains(&name) {
+            let file = NamedFile::open(self.dir.join(&name)).await.ok();
+            return Outcome::from(req, Cached::new(file).no_cache());
+        }
+
+        Outcome::forward(data, Status::NotFound)
+    }
+}
@@ -0,0 +1,58 @@
+use rocket_dyn_templates::Engines;
+
+use crate::AssetMap;
+
+/// Registers the `asset_url` helper with every enabled engine in `engines`.
+pub fn register(map: &AssetMap, engines: &mut Engines) {
+    #[cfg(feature = "tera")] {
+        use std::collections::HashMap;
+        use rocket_dyn_templates::tera::{self, Value};
+
+        let map = map.clone();
+        engines.tera.register_function("asset_url", move |args: &HashMap<String, Value>| {
+            let name = args.get("name")
+                .and_then(|name| name.as_str())
+                .ok_or_else(|| tera::Error::msg("`asset_url` expects a string `name` argument"))?;
+
+            map.asset_url(name)
+                .map(Value::from)
+                .ok_or_else(|| tera::Error::msg(format!("unknown asset `{name}`")))
+        });
+    }
+
+    #[cfg(feature = "handlebars")] {
+        use rocket_dyn_templates::handlebars::{self, RenderErrorReason};
+
+        let map = map.clone();
+        let helper = move |
+            h: &handlebars::Helper<'_>,
+            _: &handlebars::Handlebars<'_>,
+            _: &handlebars::Context,
+            _: &mut handlebars::RenderContext<'_, '_>,
+            out: &mut dyn handlebars::Output
+        | -> handlebars::HelperResult {
+            let name = h.param(0)
+                .and_then(|param| param.value().as_str())
+                .ok_or(RenderErrorReason::ParamNotFoundForIndex("asset_url", 0))?;
+
+            let url = map.asset_url(name)
+                .ok_or_else(|| RenderErrorReason::Other(format!("unknown asset `{name}`")))?;
+
+            out.write(url)?;
+            Ok(())
+        };
+
+        engines.handlebars.register_helper("asset_url", Box::new(helper));
+    }
+
+    #[cfg(feature = "minijinja")] {
+        use rocket_dyn_templates::minijinja::{Error, ErrorKind};
+
+        let map = map.clone();
+        engines.minijinja.add_function("asset_url", move |name: &str| {
+            map.asset_url(name)
+                .map(String::from)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidOperation, "unknown asset"))
+        });
+    }
+}
@@ -0,0 +1,64 @@
+//! Fingerprinted static assets for Rocket.
+//!
+//! Browsers and proxies cache static assets like stylesheets and scripts. If
+//! an asset keeps its URL across deploys, clients may keep using a stale copy
+//! after it changes, unless caching is limited, making every page load slower.
+//! The standard remedy is _cache busting_: include a hash of an asset's
+//! contents in its URL, so that the URL changes whenever the contents do, and
+//! let clients cache each URL forever.
+//!
+//! The [`Assets`] fairing implements cache busting without a build step: it
+//! hashes the files in the asset directory at ignition, serves them at their
+//! fingerprinted URLs with immutable caching headers, and exposes the URLs
+//! through the [`AssetMap`], both in Rust via [`AssetMap::asset_url()`] and,
+//! with one of the `tera`, `handlebars`, or `minijinja` features, in templates
+//! via an `asset_url` helper.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_assets = "0.1.0"
+//! ```
+//!
+//! Then attach the fairing and use fingerprinted URLs in responses:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::response::content::RawHtml;
+//! use rocket_assets::{Assets, AssetMap};
+//!
+//! #[get("/")]
+//! fn index(assets: &AssetMap) -> RawHtml<String> {
+//!     let css = assets.asset_url("app.css").unwrap_or_default();
+//!     RawHtml(format!(r#"<link rel="stylesheet" href="{css}">"#))
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index])
+//!         .attach(Assets::fairing())
+//! }
+//! ```
+//!
+//! If `static/app.css` exists, `index` links to a URL like
+//! `/static/app.4c1f0e9a2b7d3c55.css`, which is served with a `Cache-Control:
+//! public, max-age=31536000, immutable` header.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_assets")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod map;
+
+#[cfg(any(feature = "tera", feature = "handlebars", feature = "minijinja"))]
+mod helpers;
+
+pub use fairing::Assets;
+pub use map::AssetMap;
@@ -0,0 +1,223 @@
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::collections::HashMap;
+
+use rocket::http::Status;
+use rocket::request::{self, Request, FromRequest};
+use sha2::{Digest, Sha256};
+
+/// The number of bytes of a file's SHA-256 digest used in its fingerprint.
+const FINGERPRINT_LEN: usize = 8;
+
+/// A map from asset names to fingerprinted URLs.
+///
+/// An `AssetMap` is a cheaply cloneable handle to the fingerprints computed by
+/// the [`Assets`](crate::Assets) fairing at ignition. Before ignition, the map
+/// is empty. An asset's name is its path relative to the asset directory,
+/// using `/` as the separator: `app.css` or `img/logo.svg`.
+///
+/// # Request Guard
+///
+/// `&AssetMap` is a request guard that retrieves the map of the attached
+/// `Assets` fairing. It fails with `500 Internal Server Error` if the fairing
+/// isn't attached.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::RawHtml;
+/// use rocket_assets::AssetMap;
+///
+/// #[get("/")]
+/// fn index(assets: &AssetMap) -> RawHtml<String> {
+///     let css = assets.asset_url("app.css").unwrap_or("/static/app.css");
+///     RawHtml(format!(r#"<link rel="stylesheet" href="{css}">"#))
+/// }
+/// ```
+///
+/// # Template Helper
+///
+/// With the `tera`, `handlebars`, or `minijinja` features enabled,
+/// [`AssetMap::register()`] registers an `asset_url` helper with the
+/// respective engine of `rocket_dyn_templates`. Because the helper only reads
+/// the map when a template is rendered, it can be registered before the
+/// fairing ignites:
+///
+/// ```rust
+/// # #[cfg(feature = "tera")] mod example {
+/// use rocket::{Rocket, Build};
+/// use rocket_dyn_templates::Template;
+/// use rocket_assets::Assets;
+///
+/// fn rocket() -> Rocket<Build> {
+///     let assets = Assets::fairing();
+///     let map = assets.map();
+///     rocket::build()
+///         .attach(assets)
+///         .attach(Template::custom(move |engines| map.register(engines)))
+/// }
+/// # }
+/// ```
+///
+/// In a Tera template, the helper is called as `{{ asset_url(name="app.css")
+/// }}`, in a Handlebars template as `{{asset_url "app.css"}}`, and in a
+/// minijinja template as `{{ asset_url("app.css") }}`. Rendering fails if
+/// there is no such asset.
+#[derive(Debug, Clone, Default)]
+pub struct AssetMap {
+    inner: Arc<OnceLock<Inner>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Inner {
+    /// Asset name to fingerprinted URL.
+    urls: HashMap<String, String>,
+    /// Fingerprinted name to asset name.
+    names: HashMap<String, String>,
+}
+
+impl AssetMap {
+    /// Returns the fingerprinted URL of the asset `name`, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_assets::AssetMap;
+    ///
+    /// let map = AssetMap::default();
+    /// assert!(map.asset_url("app.css").is_none());
+    /// ```
+    pub fn asset_url(&self, name: &str) -> Option<&str> {
+        self.inner.get()?.urls.get(name).map(|url| url.as_str())
+    }
+
+    /// Returns an iterator over the names and fingerprinted URLs of all
+    /// assets, in an unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.inner.get()
+            .into_iter()
+            .flat_map(|inner| inner.urls.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+    }
+
+    /// Returns the name of the asset whose fingerprinted name is `name`.
+    pub(crate) fn resolve(&self, name: &str) -> Option<&str> {
+        self.inner.get()?.names.get(name).map(|name| name.as_str())
+    }
+
+    /// Returns `true` if there is an asset named `name`.
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.inner.get().map_or(false, |inner| inner.urls.contains_key(name))
+    }
+
+    /// Sets the fingerprints to `inner`. Returns `false` if they were already
+    /// set.
+    pub(crate) fn set(&self, inner: Inner) -> bool {
+        self.inner.set(inner).is_ok()
+    }
+
+    /// Registers an `asset_url` helper which resolves asset names to
+    /// fingerprinted URLs with each enabled template engine.
+    #[cfg(any(feature = "tera", feature = "handlebars", feature = "minijinja"))]
+    pub fn register(&self, engines: &mut rocket_dyn_templates::Engines) {
+        crate::helpers::register(self, engines)
+    }
+}
+
+impl Inner {
+    /// Fingerprints every visible file in `root`, recursively, generating URLs
+    /// rooted at `base`.
+    pub(crate) fn hash_dir(root: &Path, base: &str) -> io::Result<Inner> {
+        let base = base.trim_end_matches('/');
+        let mut inner = Inner::default();
+        for entry in walkdir::WalkDir::new(root).follow_links(true).min_depth(1) {
+            let entry = entry.map_err(io::Error::from)?;
+            let hidden = entry.file_name().to_str().map_or(true, |name| name.starts_with('.'));
+            if hidden || !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry.path().strip_prefix(root).map_err(io::Error::other)?;
+            let Some(name) = asset_name(relative) else {
+                continue;
+            };
+
+            let fingerprinted = fingerprinted_name(&name, &hash_file(entry.path())?);
+            inner.urls.insert(name.clone(), format!("{base}/{fingerprinted}"));
+            inner.names.insert(fingerprinted, name);
+        }
+
+        Ok(inner)
+    }
+}
+
+/// Returns the `/`-separated name of the asset at `relative`, if it is valid
+/// UTF-8 and contains no hidden components.
+pub(crate) fn asset_name(relative: &Path) -> Option<String> {
+    let components = relative.iter()
+        .map(|component| component.to_str().filter(|c| !c.starts_with('.')))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(components.join("/"))
+}
+
+/// Returns the hex-encoded, truncated SHA-256 digest of the file at `path`.
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0; 8192];
+    loop {
+        match file.read(&mut buf)? {
+            0 => break,
+            n => hasher.update(&buf[..n]),
+        }
+    }
+
+    let digest = hasher.finalize();
+    Ok(digest[..FINGERPRINT_LEN].iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Inserts `hash` before the extension in the file name of `name`:
+/// `css/app.css` becomes `css/app.{hash}.css`.
+fn fingerprinted_name(name: &str, hash: &str) -> String {
+    let (dir, file) = match name.rsplit_once('/') {
+        Some((dir, file)) => (Some(dir), file),
+        None => (None, name),
+    };
+
+    let file = match file.rsplit_once('.') {
+        Some((stem, ext)) => format!("{stem}.{hash}.{ext}"),
+        None => format!("{file}.{hash}"),
+    };
+
+    match dir {
+        Some(dir) => format!("{dir}/{file}"),
+        None => file,
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r AssetMap {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.rocket().state::<AssetMap>() {
+            Some(map) => request::Outcome::Success(map),
+            None => {
+                error!("`AssetMap` guard used without attaching the `Assets` fairing");
+                request::Outcome::Error((Status::InternalServerError, ()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fingerprinted_name;
+
+    #[test]
+    fn test_fingerprinted_name() {
+        assert_eq!(fingerprinted_name("app.css", "abc"), "app.abc.css");
+        assert_eq!(fingerprinted_name("js/app.min.js", "abc"), "js/app.min.abc.js");
+        assert_eq!(fingerprinted_name("a/b/LICENSE", "abc"), "a/b/LICENSE.abc");
+    }
+}
@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use std::fs;
+use std::path::Path;
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket_assets::{Assets, AssetMap};
+
+const CSS: &str = "/static/app.5de625c36355cce7.css";
+const JS: &str = "/static/js/main.ea1a0f9102305e02.js";
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+#[get("/url/<name..>")]
+fn url(name: std::path::PathBuf, assets: &AssetMap) -> Option<String> {
+    assets.asset_url(name.to_str()?).map(String::from)
+}
+
+fn build(root: &Path) {
+    fs::create_dir_all(root.join("js")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join("app.css"), "body { color: red; }").unwrap();
+    fs::write(root.join("js/main.js"), "main()").unwrap();
+    fs::write(root.join(".secret"), "secret").unwrap();
+    fs::write(root.join(".git/config"), "config").unwrap();
+}
+
+fn client(assets: Assets) -> Client {
+    let rocket = rocket::build().mount("/", routes![url]).attach(assets);
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn fingerprinted_urls() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+
+    let assets = Assets::fairing().dir(dir.path());
+    let map = assets.map();
+    assert!(map.asset_url("app.css").is_none());
+
+    let client = client(assets);
+    assert_eq!(map.asset_url("app.css"), Some(CSS));
+    assert_eq!(map.asset_url("js/main.js"), Some(JS));
+    assert_eq!(map.iter().count(), 2);
+
+    let response = client.get("/url/js/main.js").dispatch();
+    assert_eq!(response.into_string().unwrap(), JS);
+    assert_eq!(client.get("/url/.secret").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn serves_assets() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+    let client = client(Assets::fairing().dir(dir.path()));
+
+    let response = client.get(CSS).dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(IMMUTABLE));
+    assert_eq!(response.into_string().unwrap(), "body { color: red; }");
+
+    let response = client.get(JS).dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(IMMUTABLE));
+    assert_eq!(response.into_string().unwrap(), "main()");
+
+    let response = client.get("/static/app.css").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+    assert_eq!(response.into_string().unwrap(), "body { color: red; }");
+
+    let response = client.get("/static/app.0000000000000000.css").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(client.get("/static/.secret").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/static/.git/config").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn custom_path() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+    let client = client(Assets::fairing().dir(dir.path()).path("/assets"));
+
+    let response = client.get("/url/app.css").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/assets/app.5de625c36355cce7.css");
+
+    let response = client.get("/assets/app.5de625c36355cce7.css").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(IMMUTABLE));
+    assert_eq!(client.get(CSS).dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn missing_dir_fails_ignition() {
+    let dir = tempfile::tempdir().unwrap();
+    let rocket = rocket::build().attach(Assets::fairing().dir(dir.path().join("missing")));
+    assert!(Client::debug(rocket).is_err());
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use rocket::{Request, Data, Route};
+use rocket::data::FromData;
+use rocket::futures::future::join_all;
+use rocket::http::{Header, Method, Status, uri::Origin};
+use rocket::outcome::Outcome;
+use rocket::route::{self, Handler};
+use rocket::serde::{Serialize, Deserialize, json::Json};
+
+/// A handler that dispatches a batch of sub-requests.
+///
+/// `Batch` handles `POST` requests to the path it is mounted at. See the
+/// [crate level docs](crate) for the protocol. By default, at most 32
+/// sub-requests are accepted per batch and sub-requests are dispatched
+/// concurrently.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_batch::Batch;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().mount("/batch", Batch::new().sequential())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Batch {
+    max_requests: usize,
+    sequential: bool,
+}
+
+/// A sub-request in a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct BatchRequest {
+    /// An identifier echoed in the corresponding [`BatchResponse`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The method of the sub-request.
+    pub method: Method,
+    /// The origin URI of the sub-request, such as `/users/1?fields=name`.
+    pub uri: String,
+    /// Headers of the sub-request, in addition to those inherited.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// The body of the sub-request.
+    #[serde(default)]
+    pub body: String,
+}
+
+/// The response to a sub-request in a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct BatchResponse {
+    /// The `id` of the corresponding [`BatchRequest`], if it had one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response: each name maps to all of its values.
+    pub headers: BTreeMap<String, Vec<String>>,
+    /// The body of the response. Bytes that are not valid UTF-8 are replaced
+    /// with `U+FFFD`.
+    pub body: String,
+}
+
+impl Batch {
+    /// The default maximum number of sub-requests in a batch.
+    pub const MAX_REQUESTS: usize = 32;
+
+    /// Returns a `Batch` handler with the default configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_batch::Batch;
+    ///
+    /// let batch = Batch::new();
+    /// ```
+    pub fn new() -> Self {
+        Batch { max_requests: Self::MAX_REQUESTS, sequential: false }
+    }
+
+    /// Sets the maximum number of sub-requests in a batch to `n`. Batches with
+    /// more sub-requests are rejected with `413 Payload Too Large`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_batch::Batch;
+    ///
+    /// let batch = Batch::new().max_requests(8);
+    /// ```
+    pub fn max_requests(mut self, n: usize) -> Self {
+        self.max_requests = n;
+        self
+    }
+
+    /// Dispatches sub-requests one at a time, in order, instead of
+    /// concurrently. Useful when later sub-requests depend on the effects of
+    /// earlier ones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_batch::Batch;
+    ///
+    /// let batch = Batch::new().sequential();
+    /// ```
+    pub fn sequential(mut self) -> Self {
+        self.sequential = true;
+        self
+    }
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Batch::new()
+    }
+}
+
+async fn dispatch(req: &Request<'_>, sub: BatchRequest) -> BatchResponse {
+    let uri = match Origin::parse_owned(sub.uri) {
+        Ok(uri) => uri,
+        Err(e) => {
+            let (status, body) = (Status::BadRequest.code, format!("invalid URI: {e}"));
+            return BatchResponse { id: sub.id, status, headers: BTreeMap::new(), body };
+        }
+    };
+
+    let mut internal = req.internal(sub.method, uri);
+    for (name, value) in sub.headers {
+        internal = internal.header(Header::new(name, value));
+    }
+
+    let response = internal.inherit_headers().body(sub.body).dispatch().await;
+    let mut headers = BTreeMap::<String, Vec<String>>::new();
+    for header in response.headers().iter() {
+        let values = headers.entry(header.name().to_string()).or_default();
+        values.push(header.value().to_string());
+    }
+
+    BatchResponse {
+        id: sub.id,
+        status: response.status().code,
+        headers,
+        body: String::from_utf8_lossy(response.body()).into_owned(),
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Batch {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        let batch = match Json::<Vec<BatchRequest>>::from_data(req, data).await {
+            Outcome::Success(Json(batch)) => batch,
+            Outcome::Error((status, e)) => {
+                info!(%status, "rejecting malformed batch: {e}");
+                return Outcome::Error(status);
+            }
+            Outcome::Forward(forward) => return Outcome::Forward(forward),
+        };
+
+        if batch.len() > self.max_requests {
+            info!(len = batch.len(), max = self.max_requests, "rejecting oversized batch");
+            return Outcome::Error(Status::PayloadTooLarge);
+        }
+
+        let responses = match self.sequential {
+            true => {
+                let mut responses = Vec::with_capacity(batch.len());
+                for sub in batch {
+                    responses.push(dispatch(req, sub).await);
+                }
+
+                responses
+            }
+            false => join_all(batch.into_iter().map(|sub| dispatch(req, sub))).await,
+        };
+
+        route::Outcome::from(req, Json(responses))
+    }
+}
+
+impl From<Batch> for Vec<Route> {
+    fn from(batch: Batch) -> Self {
+        let mut route = Route::new(Method::Post, "/", batch);
+        route.name = Some("Batch".into());
+        vec![route]
+    }
+}
@@ -0,0 +1,81 @@
+//! Batch request endpoint for Rocket.
+//!
+//! Chatty clients, like dashboards that render many small widgets, often issue
+//! a burst of independent requests, each paying for a network round trip. The
+//! [`Batch`] handler lets such clients send a single request containing many
+//! sub-requests. Each sub-request is dispatched internally, via
+//! [`Request::internal()`](rocket::Request::internal()), to the same instance
+//! of Rocket: fairings, routing, request guards, and catchers apply exactly as
+//! they would to a request received over the network. The responses are
+//! returned together, in order, each with its own status.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_batch = "0.1.0"
+//! ```
+//!
+//! Then mount a [`Batch`] handler at the path of your choosing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_batch::Batch;
+//!
+//! #[get("/users/<id>")]
+//! fn user(id: usize) -> String {
+//!     format!("user #{id}")
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![user])
+//!         .mount("/batch", Batch::new().max_requests(16))
+//! }
+//! ```
+//!
+//! # Protocol
+//!
+//! A batch is a `POST` request with a JSON array of [`BatchRequest`]s:
+//!
+//! ```json
+//! [
+//!   { "id": "a", "method": "GET", "uri": "/users/1" },
+//!   { "method": "POST", "uri": "/notes", "headers": { "Content-Type": "text/plain" },
+//!     "body": "remember the milk" }
+//! ]
+//! ```
+//!
+//! The response is a `200 OK` with a JSON array of [`BatchResponse`]s, one per
+//! sub-request and in the same order:
+//!
+//! ```json
+//! [
+//!   { "id": "a", "status": 200, "body": "user #1",
+//!     "headers": { "Content-Type": ["text/plain; charset=utf-8"], ... } },
+//!   { "status": 201, "body": "", "headers": { ... } }
+//! ]
+//! ```
+//!
+//! Sub-requests inherit the headers of the batch request, such as
+//! `Authorization` and `Cookie`, except those describing its body. Headers in a
+//! sub-request take precedence over inherited ones.
+//!
+//! A batch that is not a valid JSON array of sub-requests is rejected as the
+//! [`Json`](rocket::serde::json::Json) data guard would reject it. A batch with
+//! more sub-requests than [allowed](Batch::max_requests()) is rejected with
+//! `413 Payload Too Large`. A sub-request with an invalid URI receives a `400
+//! Bad Request` response without being dispatched.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_batch")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod batch;
+
+pub use batch::{Batch, BatchRequest, BatchResponse};
@@ -0,0 +1,128 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+
+use rocket::{Request, State};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{FromRequest, Outcome};
+use rocket::serde::json::{json, Value};
+use rocket_batch::{Batch, BatchResponse};
+
+struct User<'r>(&'r str);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, ()> {
+        match req.headers().get_one("Authorization") {
+            Some(user) => Outcome::Success(User(user)),
+            None => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[get("/whoami")]
+fn whoami(user: User<'_>) -> String {
+    user.0.to_string()
+}
+
+#[get("/items/<id>")]
+fn item(id: usize) -> Value {
+    json!({ "id": id })
+}
+
+#[post("/notes", data = "<note>")]
+fn add_note(note: String, notes: &State<Mutex<Vec<String>>>) -> (Status, String) {
+    let mut notes = notes.lock().unwrap();
+    notes.push(note);
+    (Status::Created, notes.len().to_string())
+}
+
+fn client(batch: Batch) -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![whoami, item, add_note])
+        .mount("/batch", batch)
+        .manage(Mutex::new(Vec::<String>::new()));
+
+    Client::debug(rocket).unwrap()
+}
+
+fn send(client: &Client, batch: Value) -> Vec<BatchResponse> {
+    let response = client.post("/batch")
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", "alice"))
+        .body(batch.to_string())
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    response.into_json().unwrap()
+}
+
+#[test]
+fn dispatches_sub_requests_in_order() {
+    let client = client(Batch::new());
+    let responses = send(&client, json!([
+        { "id": "me", "method": "GET", "uri": "/whoami" },
+        { "method": "GET", "uri": "/whoami", "headers": { "Authorization": "bob" } },
+        { "method": "GET", "uri": "/items/7" },
+        { "method": "GET", "uri": "/missing" },
+        { "method": "GET", "uri": "not a uri" },
+    ]));
+
+    assert_eq!(responses.len(), 5);
+    assert_eq!(responses[0].id.as_deref(), Some("me"));
+    assert_eq!(responses[0].status, 200);
+    assert_eq!(responses[0].body, "alice");
+    assert_eq!(responses[1].id, None);
+    assert_eq!(responses[1].body, "bob");
+    assert_eq!(responses[2].headers["Content-Type"], ["application/json"]);
+    assert_eq!(responses[2].body, "{\"id\":7}");
+    assert_eq!(responses[3].status, 404);
+    assert_eq!(responses[4].status, 400);
+}
+
+#[test]
+fn sequential_batches_observe_earlier_effects() {
+    let client = client(Batch::new().sequential());
+    let responses = send(&client, json!([
+        { "method": "POST", "uri": "/notes", "body": "one" },
+        { "method": "POST", "uri": "/notes", "body": "two" },
+    ]));
+
+    let statuses: Vec<_> = responses.iter().map(|r| r.status).collect();
+    let bodies: Vec<_> = responses.iter().map(|r| r.body.as_str()).collect();
+    assert_eq!(statuses, [201, 201]);
+    assert_eq!(bodies, ["1", "2"]);
+}
+
+#[test]
+fn guards_apply_to_sub_requests() {
+    let client = client(Batch::new());
+    let response = client.post("/batch")
+        .header(ContentType::JSON)
+        .body(json!([{ "method": "GET", "uri": "/whoami" }]).to_string())
+        .dispatch();
+
+    let responses: Vec<BatchResponse> = response.into_json().unwrap();
+    assert_eq!(responses[0].status, 401);
+}
+
+#[test]
+fn rejects_malformed_and_oversized_batches() {
+    let client = client(Batch::new().max_requests(1));
+    let response = client.post("/batch").body("{ not json").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.post("/batch").body(json!([{ "uri": "/" }]).to_string()).dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let batch = json!([
+        { "method": "GET", "uri": "/items/1" },
+        { "method": "GET", "uri": "/items/2" },
+    ]);
+
+    let response = client.post("/batch").body(batch.to_string()).dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+}
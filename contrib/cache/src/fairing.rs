@@ -0,0 +1,459 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use rocket::{Rocket, Build, Request, Response, Data, Route};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::http::{Header, Method, Status, uri::Origin};
+use rocket::response::{self, Responder};
+use rocket::route::{Handler, Outcome};
+
+use crate::{CacheHandle, Entry, Memory, Store};
+
+/// A fairing that caches full responses of selected routes.
+///
+/// At ignition, the fairing manages a [`CacheHandle`] and mounts an internal
+/// route, named `Cache`, that answers `GET` and `HEAD` requests from the cache.
+/// Requests for responses that are not cached are forwarded to the
+/// application's routes as usual.
+///
+/// A successful (`200 OK`) response to a `GET` request handled by one of the
+/// configured [routes](Cache::routes()) or, if [enabled](Cache::public()),
+/// explicitly marked as `public`, is cached, keyed by the request's method and
+/// URI, unless:
+///
+///   * the request has a `Cache-Control: no-store` header,
+///   * the request has a `Cookie` header,
+///   * the request has an `Authorization` header and the response's
+///     `Cache-Control` header has neither a `public` nor an `s-maxage`
+///     directive,
+///   * the response has a `Cache-Control` header with a `no-store`,
+///     `no-cache`, or `private` directive, or a `max-age` of `0`,
+///   * the response sets a cookie or has a `Vary: *` header,
+///   * or the response body is larger than the
+///     [maximum entry size](Cache::max_entry_size()).
+///
+/// Cached responses expire after the `s-maxage` or `max-age` in the response's
+/// `Cache-Control` header or, if there is neither, the [default
+/// TTL](Cache::ttl()). Responses with a `Vary` header are cached separately
+/// for every distinct set of values of the named request headers.
+///
+/// Every cached response has an `ETag`: if the response doesn't have one, a
+/// strong entity tag is computed from its body. Requests with a matching
+/// `If-None-Match` header are answered with `304 Not Modified`.
+///
+/// Requests with a `Cache-Control: no-cache` or `no-store` header or with a
+/// `Cookie` header bypass the cache. Requests with an `Authorization` header
+/// are only answered with cached responses that are explicitly `public` or
+/// have an `s-maxage`. Because cached responses are served before any of the
+/// application's request guards run, responses that depend on credentials
+/// must not be marked `public`.
+///
+/// See the [crate docs](crate) for an example.
+pub struct Cache {
+    routes: Vec<Target>,
+    public: bool,
+    store: Arc<dyn Store>,
+    ttl: Duration,
+    max_entry_size: ByteUnit,
+}
+
+/// Identifies a configured route.
+struct Target {
+    name: Option<String>,
+    method: Option<Method>,
+    uri: Origin<'static>,
+}
+
+/// The internal route serving cached responses.
+#[derive(Clone)]
+struct Lookup {
+    store: Arc<dyn Store>,
+}
+
+/// A cached response being served.
+struct Hit(Entry);
+
+/// Returns the cache key for a `GET` request to `uri`.
+pub(crate) fn key(uri: &str) -> String {
+    format!("GET {uri}")
+}
+
+impl Cache {
+    /// Creates a new cache fairing with no configured routes, an in-memory
+    /// [`Memory`] store with a capacity of 64MiB, a default TTL of one minute,
+    /// and a maximum entry size of 1MiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_cache::Cache;
+    ///
+    /// let cache = Cache::new();
+    /// ```
+    pub fn new() -> Self {
+        Cache {
+            routes: vec![],
+            public: false,
+            store: Arc::new(Memory::default()),
+            ttl: Duration::from_secs(60),
+            max_entry_size: 1.mebibytes(),
+        }
+    }
+
+    /// Caches responses from `routes`, typically the output of `routes![]`,
+    /// in addition to any previously configured routes.
+    ///
+    /// Routes are identified by their name, method, and unmounted URI, so the
+    /// routes passed here need not be mounted at the same base.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_cache::Cache;
+    ///
+    /// #[get("/")]
+    /// fn index() -> &'static str { "Hello, world!" }
+    ///
+    /// let cache = Cache::new().routes(routes![index]);
+    /// ```
+    pub fn routes<R: Into<Vec<Route>>>(mut self, routes: R) -> Self {
+        self.routes.extend(routes.into().into_iter().map(|route| Target {
+            name: route.name.map(|name| name.into_owned()),
+            method: route.method,
+            uri: route.uri.unmounted().clone().into_owned(),
+        }));
+
+        self
+    }
+
+    /// Additionally caches responses from any route whose `Cache-Control`
+    /// header has a `public` or `s-maxage` directive, such as those of routes
+    /// declared cacheable with `#[cache(public)]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_cache::Cache;
+    ///
+    /// #[get("/")]
+    /// #[cache(max_age = 300, public)]
+    /// fn index() -> &'static str { "Hello, world!" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount("/", routes![index])
+    ///         .attach(Cache::new().public())
+    /// }
+    /// ```
+    pub fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+
+    /// Stores cached responses in `store` instead of the current store.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::ToByteUnit;
+    /// use rocket_cache::{Cache, Memory};
+    ///
+    /// let cache = Cache::new().store(Memory::new(256.mebibytes()));
+    /// ```
+    pub fn store<S: Store>(mut self, store: S) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Sets how long responses without a `max-age` or `s-maxage`
+    /// `Cache-Control` directive are cached. Defaults to one minute.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the size of the largest response body that is cached. Defaults to
+    /// 1MiB.
+    pub fn max_entry_size(mut self, size: ByteUnit) -> Self {
+        self.max_entry_size = size;
+        self
+    }
+
+    fn is_target(&self, route: &Route) -> bool {
+        self.routes.iter().any(|target| {
+            target.method == route.method
+                && target.name.as_deref() == route.name.as_deref()
+                && &target.uri == route.uri.unmounted()
+        })
+    }
+
+    /// Returns the time-to-live of `res` or `None` if it must not be cached.
+    fn ttl_of(&self, req: &Request<'_>, res: &Response<'_>) -> Option<Duration> {
+        if req.headers().get("Cache-Control").flat_map(directives).any(|(d, _)| d == "no-store") {
+            return None;
+        }
+
+        // A shared cache must not store responses to requests with credentials
+        // unless they are explicitly shareable. See RFC 9111 § 3.5.
+        let (headers, shared) = (req.headers(), is_shared(res.headers().get("Cache-Control")));
+        if has_cookies(req) || (headers.contains("Authorization") && !shared) {
+            return None;
+        }
+
+        if res.headers().contains("Set-Cookie") || vary(res).any(|name| name == "*") {
+            return None;
+        }
+
+        let mut ttl = self.ttl;
+        let mut shared_ttl = None;
+        for (directive, value) in res.headers().get("Cache-Control").flat_map(directives) {
+            let seconds = || value.and_then(|v| v.parse().ok()).map(Duration::from_secs);
+            match &*directive {
+                "no-store" | "no-cache" | "private" => return None,
+                "max-age" => ttl = seconds()?,
+                "s-maxage" => shared_ttl = Some(seconds()?),
+                _ => {}
+            }
+        }
+
+        Some(shared_ttl.unwrap_or(ttl)).filter(|ttl| !ttl.is_zero())
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache::new()
+    }
+}
+
+/// Returns the lowercased directives in a `Cache-Control` header `value`.
+fn directives(value: &str) -> impl Iterator<Item = (String, Option<&str>)> {
+    value.split(',')
+        .map(|directive| directive.trim())
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((name, value)) => (name.trim().to_ascii_lowercase(), Some(value.trim())),
+            None => (directive.to_ascii_lowercase(), None),
+        })
+}
+
+/// Returns `true` if the `Cache-Control` header `values` explicitly allow
+/// shared caches to store the response, that is, have a `public` or
+/// `s-maxage` directive.
+fn is_shared<'a>(values: impl Iterator<Item = &'a str>) -> bool {
+    values.flat_map(directives).any(|(d, _)| d == "public" || d == "s-maxage")
+}
+
+/// Returns `true` if `req` carries cookies.
+fn has_cookies(req: &Request<'_>) -> bool {
+    req.headers().contains("Cookie") || req.cookies().iter().next().is_some()
+}
+
+/// Returns the names of the request headers named in `res`'s `Vary` header.
+fn vary<'a>(res: &'a Response<'_>) -> impl Iterator<Item = &'a str> {
+    res.headers().get("Vary")
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+}
+
+/// Returns `true` if `req` has an `If-None-Match` header matching `etag`.
+fn is_not_modified(req: &Request<'_>, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    let etag = opaque(etag);
+    req.headers().get("If-None-Match")
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// Returns a strong entity tag for `body`.
+///
+/// The tag is computed with 64-bit FNV-1a, which, unlike `DefaultHasher`, is
+/// stable across builds, so that instances sharing a store agree on tags.
+fn etag_of(body: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let hash = body.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+
+    format!("\"{:016x}{:08x}\"", hash, body.len())
+}
+
+impl Entry {
+    /// Returns `true` if `req` matches the request headers `self` varies on.
+    fn matches(&self, req: &Request<'_>) -> bool {
+        self.vary.iter().all(|(name, value)| req.headers().get_one(name) == value.as_deref())
+    }
+
+    /// Returns `true` if the entry is explicitly shareable. See `is_shared()`.
+    fn is_shared(&self) -> bool {
+        is_shared(self.headers.iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("Cache-Control"))
+            .map(|(_, value)| value.as_str()))
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Cache {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Response, name: "Cache" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if rocket.state::<CacheHandle>().is_some() {
+            error!("a response cache is already being managed");
+            return Err(rocket);
+        }
+
+        let lookup = Lookup { store: self.store.clone() };
+        let mut route = Route::ranked(isize::MIN, Method::Get, "/<_..>", lookup);
+        route.name = Some("Cache".into());
+
+        Ok(rocket.manage(CacheHandle::new(self.store.clone())).mount("/", vec![route]))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if req.method() != Method::Get || res.status() != Status::Ok {
+            return;
+        }
+
+        let is_target = req.route().map_or(false, |route| self.is_target(route));
+        let is_public = self.public && is_shared(res.headers().get("Cache-Control"));
+
+        if !is_target && !is_public {
+            return;
+        }
+
+        let Some(ttl) = self.ttl_of(req, res) else {
+            return;
+        };
+
+        match res.body_mut().size().await {
+            Some(size) if size.bytes() <= self.max_entry_size => {}
+            _ => return,
+        }
+
+        let body = match res.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(uri = %req.uri(), "failed to read response body for caching: {e}");
+                return;
+            }
+        };
+
+        res.set_sized_body(body.len(), Cursor::new(body.clone()));
+        let etag = match res.headers().get_one("ETag") {
+            Some(etag) => etag.to_owned(),
+            None => {
+                let etag = etag_of(&body);
+                res.set_header(Header::new("ETag", etag.clone()));
+                etag
+            }
+        };
+
+        let now = SystemTime::now();
+        let entry = Entry {
+            status: res.status().code,
+            headers: res.headers().iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect(),
+            body,
+            vary: vary(res)
+                .map(|name| (name.to_owned(), req.headers().get_one(name).map(|v| v.to_owned())))
+                .collect(),
+            etag,
+            stored: now,
+            expires: now + ttl,
+        };
+
+        let key = key(&req.uri().to_string());
+        let result = match self.store.get(&key).await {
+            Ok(mut entries) => {
+                entries.retain(|e| e.is_fresh(now) && e.vary != entry.vary);
+                if is_not_modified(req, &entry.etag) {
+                    res.set_status(Status::NotModified);
+                }
+
+                entries.push(entry);
+                self.store.put(&key, entries).await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            warn!(%key, "failed to cache response: {e}");
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Lookup {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let bypass = req.headers().get("Cache-Control")
+            .flat_map(directives)
+            .any(|(directive, _)| directive == "no-cache" || directive == "no-store");
+
+        if bypass || has_cookies(req) {
+            return Outcome::forward(data, Status::NotFound);
+        }
+
+        let key = key(&req.uri().to_string());
+        let entries = match self.store.get(&key).await {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!(%key, "failed to read from response cache: {e}");
+                return Outcome::forward(data, Status::NotFound);
+            }
+        };
+
+        let now = SystemTime::now();
+        let authorized = req.headers().contains("Authorization");
+        let usable = |e: &Entry| {
+            e.is_fresh(now) && e.matches(req) && (!authorized || e.is_shared())
+        };
+
+        match entries.into_iter().find(usable) {
+            Some(entry) => Outcome::from(req, Hit(entry)),
+            None => Outcome::forward(data, Status::NotFound),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Hit {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let Hit(entry) = self;
+        let age = SystemTime::now().duration_since(entry.stored).unwrap_or_default();
+        let status = match is_not_modified(req, &entry.etag) {
+            true => Status::NotModified,
+            false => Status::new(entry.status),
+        };
+
+        let mut response = Response::build();
+        response.status(status);
+        for (name, value) in entry.headers {
+            response.raw_header_adjoin(name, value);
+        }
+
+        response.raw_header("Age", age.as_secs().to_string())
+            .sized_body(entry.body.len(), Cursor::new(entry.body))
+            .ok()
+    }
+}
+
+impl std::fmt::Debug for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cache")
+            .field("routes", &self.routes.iter().map(|r| &r.name).collect::<Vec<_>>())
+            .field("public", &self.public)
+            .field("ttl", &self.ttl)
+            .field("max_entry_size", &self.max_entry_size)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use rocket::request::{self, FromRequest, Request};
+use rocket::http::Status;
+use rocket::outcome::IntoOutcome;
+
+use crate::store::{Store, Error};
+
+/// A handle to the response cache, available as managed state.
+///
+/// A `CacheHandle` is managed by the [`Cache`](crate::Cache) fairing and can
+/// be retrieved in handlers as a request guard, or via `&State<CacheHandle>`,
+/// to explicitly invalidate cached responses, typically after a request
+/// changes the underlying resource. Cloning a `CacheHandle` is cheap.
+///
+/// URIs passed to the handle's methods are matched against the request URIs,
+/// including the query string, of cached responses.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_cache::CacheHandle;
+///
+/// #[post("/posts/<id>", data = "<body>")]
+/// async fn update(id: usize, body: String, cache: CacheHandle) {
+///     /* update the post... */
+///     let _ = cache.invalidate(&format!("/posts/{id}")).await;
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CacheHandle {
+    store: Arc<dyn Store>,
+}
+
+impl CacheHandle {
+    pub(crate) fn new(store: Arc<dyn Store>) -> Self {
+        CacheHandle { store }
+    }
+
+    /// Removes the cached response, in all of its variants, for `uri`.
+    pub async fn invalidate(&self, uri: &str) -> Result<(), Error> {
+        self.store.remove(&crate::fairing::key(uri)).await
+    }
+
+    /// Removes all cached responses for URIs starting with `prefix`.
+    pub async fn invalidate_prefix(&self, prefix: &str) -> Result<(), Error> {
+        self.store.remove_prefix(&crate::fairing::key(prefix)).await
+    }
+
+    /// Removes all cached responses.
+    pub async fn clear(&self) -> Result<(), Error> {
+        self.store.clear().await
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &dyn Store {
+        &*self.store
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CacheHandle {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.rocket().state::<CacheHandle>()
+            .cloned()
+            .or_error((Status::InternalServerError, ()))
+    }
+}
+
+impl std::fmt::Debug for CacheHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheHandle").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,83 @@
+//! Server-side response caching for Rocket.
+//!
+//! This crate provides the [`Cache`] fairing, which caches full responses of
+//! selected routes in a pluggable [`Store`], serves subsequent requests for the
+//! same resource from the cache without running the route's handler, and
+//! answers conditional requests with `304 Not Modified` using `ETag`s.
+//! Caching respects `Cache-Control` directives in requests and responses and
+//! the `Vary` header in responses. Cached responses can be invalidated
+//! explicitly via a [`CacheHandle`].
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_cache = "0.1.0"
+//! ```
+//!
+//! Then attach a [`Cache`] fairing configured with the routes whose responses
+//! should be cached. Below, an article is cached for five minutes, as set by
+//! its `Cache-Control` header, and evicted from the cache when it is edited:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::http::Header;
+//! use rocket_cache::{Cache, CacheHandle};
+//!
+//! #[derive(Responder)]
+//! struct Article {
+//!     body: String,
+//!     cache_control: Header<'static>,
+//! }
+//!
+//! #[get("/articles/<slug>")]
+//! async fn article(slug: &str) -> Article {
+//!     /* render the article... */
+//!     Article {
+//!         body: format!("all about {slug}"),
+//!         cache_control: Header::new("Cache-Control", "max-age=300"),
+//!     }
+//! }
+//!
+//! #[post("/articles/<slug>", data = "<body>")]
+//! async fn edit(slug: &str, body: String, cache: CacheHandle) {
+//!     /* save the article... */
+//!     if let Err(e) = cache.invalidate(&format!("/articles/{slug}")).await {
+//!         error!("failed to invalidate cached article: {e}");
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![article, edit])
+//!         .attach(Cache::new().routes(routes![article]))
+//! }
+//! ```
+//!
+//! Alternatively, [`Cache::public()`] caches responses from any route that
+//! declares them cacheable by shared caches, for instance with Rocket's
+//! `#[cache(max_age = 300, public)]` route attribute.
+//!
+//! # Stores
+//!
+//! Responses are cached in the in-memory, least-recently-used [`Memory`] store
+//! by default. To share a cache between several instances of an application,
+//! implement [`Store`] for an external cache like Redis and configure it via
+//! [`Cache::store()`].
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_cache")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod handle;
+mod store;
+
+pub use fairing::Cache;
+pub use handle::CacheHandle;
+pub use store::{Store, Memory, Entry, Error};
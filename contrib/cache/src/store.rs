@@ -0,0 +1,255 @@
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use indexmap::IndexMap;
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::serde::{Serialize, Deserialize};
+
+/// An error produced by a [`Store`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A cached response.
+///
+/// A [`Store`] maps every cache key to all of the cached _variants_ of the
+/// response for that key: one `Entry` per distinct set of values of the
+/// request headers named in the response's `Vary` header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Entry {
+    /// The response's status code.
+    pub status: u16,
+    /// The response's headers, including its `ETag`.
+    pub headers: Vec<(String, String)>,
+    /// The response's body.
+    pub body: Vec<u8>,
+    /// The response's entity tag, including quotes and any `W/` prefix.
+    pub etag: String,
+    /// The names of the request headers the response varies on and the values
+    /// they had in the request that produced the response.
+    pub vary: Vec<(String, Option<String>)>,
+    /// When the response was cached.
+    pub stored: SystemTime,
+    /// When the response becomes stale and must no longer be served.
+    pub expires: SystemTime,
+}
+
+impl Entry {
+    /// Returns `true` if the entry has not yet expired at `now`.
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        now < self.expires
+    }
+
+    /// An estimate of the memory used by the entry in bytes.
+    pub fn size(&self) -> usize {
+        let headers = self.headers.iter().map(|(n, v)| n.len() + v.len());
+        let vary = self.vary.iter().map(|(n, v)| n.len() + v.as_ref().map_or(0, |v| v.len()));
+        self.body.len() + self.etag.len() + headers.sum::<usize>() + vary.sum::<usize>()
+    }
+}
+
+/// Storage for cached responses.
+///
+/// A store maps cache keys, of the form `GET /path?query`, to the cached
+/// variants of the response for that key. The [`Memory`] store is used by
+/// default. Implementing this trait allows responses to be cached elsewhere,
+/// say in Redis, and shared by several instances of an application. [`Entry`]
+/// implements `Serialize` and `Deserialize` to ease storing it externally.
+///
+/// Stores need not evict expired entries: expired entries are never served
+/// and are replaced when the response is next cached.
+#[rocket::async_trait]
+pub trait Store: Send + Sync + 'static {
+    /// Returns all cached variants for `key`.
+    async fn get(&self, key: &str) -> Result<Vec<Entry>, Error>;
+
+    /// Replaces all cached variants for `key` with `entries`.
+    async fn put(&self, key: &str, entries: Vec<Entry>) -> Result<(), Error>;
+
+    /// Removes all cached variants for `key`.
+    async fn remove(&self, key: &str) -> Result<(), Error>;
+
+    /// Removes all cached variants for all keys starting with `prefix`.
+    async fn remove_prefix(&self, prefix: &str) -> Result<(), Error>;
+
+    /// Removes all cached responses.
+    async fn clear(&self) -> Result<(), Error>;
+}
+
+/// An in-memory, least-recently-used [`Store`], the default.
+///
+/// The store holds cached responses up to a total size of `capacity`, evicting
+/// the least recently used key when the capacity is exceeded.
+#[derive(Debug)]
+pub struct Memory {
+    capacity: ByteUnit,
+    entries: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    map: IndexMap<String, Vec<Entry>>,
+    size: usize,
+}
+
+impl Memory {
+    /// Creates a new, empty store that holds up to `capacity` bytes of
+    /// responses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::ToByteUnit;
+    /// use rocket_cache::Memory;
+    ///
+    /// let store = Memory::new(16.mebibytes());
+    /// ```
+    pub fn new(capacity: ByteUnit) -> Self {
+        Memory { capacity, entries: Mutex::new(Lru::default()) }
+    }
+
+    /// Returns the number of keys in the store.
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("cache lock").map.len()
+    }
+
+    /// Returns `true` if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for Memory {
+    /// Creates a store with a capacity of 64MiB.
+    fn default() -> Self {
+        Memory::new(64.mebibytes())
+    }
+}
+
+impl Lru {
+    fn size_of(entries: &[Entry]) -> usize {
+        entries.iter().map(|e| e.size()).sum()
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entries) = self.map.shift_remove(key) {
+            self.size -= Lru::size_of(&entries);
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Store for Memory {
+    async fn get(&self, key: &str) -> Result<Vec<Entry>, Error> {
+        let mut lru = self.entries.lock().expect("cache lock");
+        let Some(i) = lru.map.get_index_of(key) else {
+            return Ok(vec![]);
+        };
+
+        let last = lru.map.len() - 1;
+        lru.map.move_index(i, last);
+        Ok(lru.map[last].clone())
+    }
+
+    async fn put(&self, key: &str, entries: Vec<Entry>) -> Result<(), Error> {
+        let capacity = self.capacity.as_u64().try_into().unwrap_or(usize::MAX);
+        let mut lru = self.entries.lock().expect("cache lock");
+        lru.remove(key);
+
+        let size = Lru::size_of(&entries);
+        if size > capacity {
+            return Ok(());
+        }
+
+        while lru.size + size > capacity {
+            match lru.map.shift_remove_index(0) {
+                Some((_, evicted)) => lru.size -= Lru::size_of(&evicted),
+                None => break,
+            }
+        }
+
+        lru.size += size;
+        lru.map.insert(key.to_owned(), entries);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Error> {
+        self.entries.lock().expect("cache lock").remove(key);
+        Ok(())
+    }
+
+    async fn remove_prefix(&self, prefix: &str) -> Result<(), Error> {
+        let mut lru = self.entries.lock().expect("cache lock");
+        let mut removed = 0;
+        lru.map.retain(|key, entries| {
+            let keep = !key.starts_with(prefix);
+            if !keep { removed += Lru::size_of(entries); }
+            keep
+        });
+
+        lru.size -= removed;
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), Error> {
+        *self.entries.lock().expect("cache lock") = Lru::default();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use rocket::data::ToByteUnit;
+
+    use super::{Entry, Memory, Store};
+
+    fn entry(body: usize) -> Vec<Entry> {
+        let now = SystemTime::now();
+        vec![Entry {
+            status: 200,
+            headers: vec![],
+            body: vec![0; body],
+            etag: String::new(),
+            vary: vec![],
+            stored: now,
+            expires: now + Duration::from_secs(60),
+        }]
+    }
+
+    #[rocket::async_test]
+    async fn evicts_least_recently_used() {
+        let store = Memory::new(100.bytes());
+        store.put("a", entry(40)).await.unwrap();
+        store.put("b", entry(40)).await.unwrap();
+        assert_eq!(store.get("a").await.unwrap().len(), 1);
+
+        store.put("c", entry(40)).await.unwrap();
+        assert_eq!(store.len(), 2);
+        assert!(store.get("b").await.unwrap().is_empty());
+        assert_eq!(store.get("a").await.unwrap().len(), 1);
+        assert_eq!(store.get("c").await.unwrap().len(), 1);
+    }
+
+    #[rocket::async_test]
+    async fn skips_oversized_entries() {
+        let store = Memory::new(100.bytes());
+        store.put("a", entry(40)).await.unwrap();
+        store.put("b", entry(101)).await.unwrap();
+        assert_eq!(store.len(), 1);
+        assert!(store.get("b").await.unwrap().is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn removes_by_prefix() {
+        let store = Memory::new(1.kibibytes());
+        store.put("GET /a/1", entry(1)).await.unwrap();
+        store.put("GET /a/2", entry(1)).await.unwrap();
+        store.put("GET /b", entry(1)).await.unwrap();
+        store.remove_prefix("GET /a/").await.unwrap();
+        assert_eq!(store.len(), 1);
+
+        store.clear().await.unwrap();
+        assert!(store.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::request::{self, FromRequest, Request};
+use rocket::local::blocking::Client;
+use rocket_cache::{Cache, CacheHandle};
+
+#[derive(Default)]
+struct Hits(AtomicUsize);
+
+impl Hits {
+    fn count(&self) -> usize {
+        self.0.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+#[derive(Responder)]
+struct WithHeader(String, Header<'static>);
+
+#[get("/counter")]
+fn counter(hits: &State<Hits>) -> String {
+    hits.count().to_string()
+}
+
+#[get("/private")]
+fn private(hits: &State<Hits>) -> WithHeader {
+    WithHeader(hits.count().to_string(), Header::new("Cache-Control", "no-store"))
+}
+
+struct Lang<'r>(Option<&'r str>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Lang<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Lang(req.headers().get_one("Accept-Language")))
+    }
+}
+
+#[get("/greeting")]
+fn greeting(hits: &State<Hits>, lang: Lang<'_>) -> WithHeader {
+    let _ = hits.count();
+    let body = match lang.0 {
+        Some("fr") => "bonjour",
+        _ => "hello",
+    };
+
+    WithHeader(body.into(), Header::new("Vary", "Accept-Language"))
+}
+
+#[get("/uncached")]
+fn uncached(hits: &State<Hits>) -> String {
+    hits.count().to_string()
+}
+
+#[get("/declared")]
+#[cache(max_age = 60, public)]
+fn declared(hits: &State<Hits>) -> String {
+    hits.count().to_string()
+}
+
+#[post("/invalidate")]
+async fn invalidate(cache: CacheHandle) {
+    cache.invalidate("/counter").await.unwrap();
+}
+
+fn client_with(cache: Cache) -> Client {
+    let rocket = rocket::build()
+        .manage(Hits::default())
+        .mount("/", routes![counter, private, greeting, uncached, declared, invalidate])
+        .attach(cache.routes(routes![counter, private, greeting]));
+
+    Client::tracked(rocket).unwrap()
+}
+
+fn client() -> Client {
+    client_with(Cache::new())
+}
+
+#[test]
+fn cache_hits_skip_the_handler() {
+    let client = client();
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "1");
+
+    let response = client.get("/counter?x=1").dispatch();
+    assert_eq!(response.into_string().unwrap(), "2");
+
+    assert_eq!(client.get("/uncached").dispatch().into_string().unwrap(), "3");
+    assert_eq!(client.get("/uncached").dispatch().into_string().unwrap(), "4");
+
+    let response = client.get("/counter").header(Header::new("Cache-Control", "no-cache"));
+    assert_eq!(response.dispatch().into_string().unwrap(), "5");
+}
+
+#[test]
+fn no_store_responses_are_not_cached() {
+    let client = client();
+    assert_eq!(client.get("/private").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/private").dispatch().into_string().unwrap(), "2");
+}
+
+#[test]
+fn etag_and_if_none_match() {
+    let client = client();
+    let response = client.get("/counter").dispatch();
+    let etag = response.headers().get_one("ETag").unwrap().to_owned();
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+    let response = client.get("/counter").dispatch();
+    assert_eq!(response.headers().get_one("ETag"), Some(&*etag));
+    assert!(response.headers().get_one("Age").is_some());
+
+    let response = client.get("/counter").header(Header::new("If-None-Match", etag)).dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+    assert!(response.into_string().is_none());
+
+    let response = client.get("/counter").header(Header::new("If-None-Match", "\"nope\""));
+    assert_eq!(response.dispatch().status(), Status::Ok);
+}
+
+#[test]
+fn explicit_invalidation() {
+    let client = client();
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "1");
+
+    assert_eq!(client.post("/invalidate").dispatch().status(), Status::Ok);
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "2");
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "2");
+}
+
+#[test]
+fn vary_caches_variants_separately() {
+    let client = client();
+    let get = |lang: &str| {
+        client.get("/greeting")
+            .header(Header::new("Accept-Language", lang.to_string()))
+            .dispatch()
+            .into_string()
+            .unwrap()
+    };
+
+    assert_eq!(get("en"), "hello");
+    assert_eq!(get("fr"), "bonjour");
+    assert_eq!(get("en"), "hello");
+    assert_eq!(get("fr"), "bonjour");
+    assert_eq!(client.rocket().state::<Hits>().unwrap().0.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn public_responses_are_cached_when_enabled() {
+    let client = client();
+    assert_eq!(client.get("/declared").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/declared").dispatch().into_string().unwrap(), "2");
+
+    let client = client_with(Cache::new().public());
+    let response = client.get("/declared").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=60, public"));
+    assert_eq!(response.into_string().unwrap(), "1");
+    assert_eq!(client.get("/declared").dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/uncached").dispatch().into_string().unwrap(), "2");
+    assert_eq!(client.get("/uncached").dispatch().into_string().unwrap(), "3");
+}
+
+#[test]
+fn requests_with_credentials_are_not_shared() {
+    let client = client_with(Cache::new().public());
+    let authorized = || client.get("/counter").header(Header::new("Authorization", "Bearer a"));
+    assert_eq!(authorized().dispatch().into_string().unwrap(), "1");
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "2");
+    assert_eq!(authorized().dispatch().into_string().unwrap(), "3");
+    assert_eq!(client.get("/counter").dispatch().into_string().unwrap(), "2");
+
+    let with_cookie = || client.get("/counter").cookie(("session", "a"));
+    assert_eq!(with_cookie().dispatch().into_string().unwrap(), "4");
+    assert_eq!(with_cookie().dispatch().into_string().unwrap(), "5");
+
+    // Explicitly public responses are shared even with authorized requests.
+    let declared = || client.get("/declared").header(Header::new("Authorization", "Bearer a"));
+    assert_eq!(declared().dispatch().into_string().unwrap(), "6");
+    assert_eq!(declared().dispatch().into_string().unwrap(), "6");
+    assert_eq!(client.get("/declared").dispatch().into_string().unwrap(), "6");
+}
+
+#[test]
+fn generated_etags_are_stable() {
+    let client = client();
+    let response = client.get("/counter").dispatch();
+    assert_eq!(response.headers().get_one("ETag"), Some("\"af63ac4c86019afc00000001\""));
+}
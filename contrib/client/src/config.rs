@@ -0,0 +1,52 @@
+use rocket::serde::{Deserialize, Serialize};
+
+/// The configuration of an [`Http`](crate::Http) client.
+///
+/// The fairing returned by [`Http::fairing()`](crate::Http::fairing()) reads
+/// the configuration from the `http_client` table of the application's
+/// configuration. Missing values take their defaults:
+///
+/// ```toml
+/// [default.http_client]
+/// timeout = 30
+/// connect_timeout = 5
+/// user_agent = "my-app/1.0"
+/// propagate = ["traceparent", "tracestate", "baggage"]
+/// request_id_header = "X-Request-Id"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct HttpConfig {
+    /// The maximum number of seconds any outbound request may take. Requests
+    /// made on behalf of an incoming request are further bounded by its
+    /// [`Deadline`](rocket::request::Deadline). `0` disables the timeout.
+    /// Defaults to `30`.
+    pub timeout: f64,
+    /// The maximum number of seconds to wait for a connection to be
+    /// established. `0` disables the timeout. Defaults to `5`.
+    pub connect_timeout: f64,
+    /// The `User-Agent` sent with every request. Defaults to
+    /// `rocket_client/<version>`.
+    pub user_agent: String,
+    /// The names of incoming request headers forwarded, when present, to
+    /// outbound requests. Defaults to the W3C trace context headers,
+    /// `traceparent` and `tracestate`.
+    pub propagate: Vec<String>,
+    /// The name of the header carrying the request ID. The incoming value is
+    /// forwarded if present; otherwise, a random ID is generated for the
+    /// request and sent. Set to an empty string to disable. Defaults to
+    /// `X-Request-Id`.
+    pub request_id_header: String,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            timeout: 30.0,
+            connect_timeout: 5.0,
+            user_agent: concat!("rocket_client/", env!("CARGO_PKG_VERSION")).into(),
+            propagate: vec!["traceparent".into(), "tracestate".into()],
+            request_id_header: "X-Request-Id".into(),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use rocket::fairing::{AdHoc, Fairing};
+
+use crate::HttpConfig;
+
+/// A configured outbound HTTP client, available as managed state.
+///
+/// An `Http` wraps a [`reqwest::Client`] built from an [`HttpConfig`]. The
+/// fairing returned by [`Http::fairing()`] builds one from the application's
+/// configuration and places it in managed state. Cloning an `Http` is cheap
+/// and yields a handle to the same connection pool.
+///
+/// Requests made on behalf of an incoming request should be made via the
+/// [`Outbound`](crate::Outbound) request guard, which propagates the incoming
+/// request's context. Background tasks, which have no incoming request, can
+/// use the underlying client, via [`Http::client()`], directly.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket_client::Http;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(Http::fairing())
+///         .attach(rocket::fairing::AdHoc::on_liftoff("Warmup", |rocket| Box::pin(async {
+///             let http = rocket.state::<Http>().unwrap().clone();
+///             rocket::tokio::spawn(async move {
+///                 let _ = http.client().get("https://example.com/warmup").send().await;
+///             });
+///         })))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Http {
+    client: reqwest::Client,
+    config: HttpConfig,
+}
+
+impl Http {
+    /// Returns a fairing that reads an [`HttpConfig`] from the `http_client`
+    /// table of the application's configuration and places an `Http` built
+    /// from it in managed state. A missing `http_client` table is treated as
+    /// an empty one. Ignition fails if the table is invalid or the client
+    /// cannot be built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_client::Http;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Http::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("HTTP Client", |rocket| async move {
+            let config = match rocket.figment().extract_inner("http_client") {
+                Ok(config) => config,
+                Err(e) if e.missing() => HttpConfig::default(),
+                Err(e) => {
+                    error!("invalid HTTP client configuration: {e}");
+                    return Err(rocket);
+                }
+            };
+
+            match Http::new(config) {
+                Ok(http) => Ok(rocket.manage(http)),
+                Err(e) => {
+                    error!("failed to build HTTP client: {e}");
+                    Err(rocket)
+                }
+            }
+        })
+    }
+
+    /// Builds a client configured by `config`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_client::{Http, HttpConfig};
+    ///
+    /// let config = HttpConfig { timeout: 5.0, ..Default::default() };
+    /// let http = Http::new(config).unwrap();
+    /// assert_eq!(http.config().timeout, 5.0);
+    /// ```
+    pub fn new(config: HttpConfig) -> reqwest::Result<Self> {
+        let mut builder = reqwest::Client::builder().user_agent(&config.user_agent);
+        if let Some(timeout) = secs(config.timeout) {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = secs(config.connect_timeout) {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        Ok(Http { client: builder.build()?, config })
+    }
+
+    /// Returns the underlying client. Requests made with it directly do not
+    /// propagate any request context.
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Returns the client's configuration.
+    pub fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    /// The client-wide timeout, if any.
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        secs(self.config.timeout)
+    }
+}
+
+/// Converts a number of seconds into a `Duration`. Zero, negative, and
+/// unrepresentable values mean "no timeout".
+fn secs(secs: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(secs).ok().filter(|d| !d.is_zero())
+}
@@ -0,0 +1,82 @@
+//! Outbound HTTP requests that carry the context of the request they're made
+//! on behalf of.
+//!
+//! Handlers frequently call other HTTP services. To trace a request across
+//! services and to avoid doing work nobody will wait for, these calls should
+//! carry the incoming request's ID and trace context and should not outlive
+//! its time budget. This crate provides a configured, shared HTTP client that
+//! does both:
+//!
+//!   * [`Http`] is a [`reqwest`] client, configured from `Rocket.toml` and
+//!     placed in managed state by [`Http::fairing()`].
+//!   * [`Outbound`] is a request guard that builds requests with that client,
+//!     copying trace context headers and the request ID from the incoming
+//!     request and bounding each request's timeout by the incoming request's
+//!     [`Deadline`](rocket::request::Deadline). Requests sent via
+//!     [`Outbound::send()`] are logged via Rocket's tracing.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_client = "0.1.0"
+//! ```
+//!
+//! Then attach the fairing and use the guard:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::http::Status;
+//! use rocket_client::{Http, Outbound};
+//!
+//! #[get("/weather/<city>")]
+//! async fn weather(city: &str, http: Outbound<'_>) -> Result<String, Status> {
+//!     let request = http.get(format!("https://weather.internal/{city}"));
+//!     let response = http.send(request).await.map_err(|_| Status::BadGateway)?;
+//!     response.text().await.map_err(|_| Status::BadGateway)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![weather])
+//!         .attach(Http::fairing())
+//! }
+//! ```
+//!
+//! The client is configured in the `http_client` table; see [`HttpConfig`].
+//! Deadlines are configured via Rocket's `request_timeout` and
+//! `timeout_header` parameters:
+//!
+//! ```toml
+//! [default]
+//! request_timeout = 30
+//! timeout_header = "X-Request-Timeout"
+//!
+//! [default.http_client]
+//! timeout = 10
+//! propagate = ["traceparent", "tracestate", "baggage"]
+//! ```
+//!
+//! With this configuration, a request to `/weather/paris` received with a
+//! `traceparent` header is forwarded with the same `traceparent`, an
+//! `X-Request-Id`, and an `X-Request-Timeout` of the milliseconds remaining
+//! in its 30 second budget, and times out after at most 10 seconds.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_client")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod config;
+mod http;
+mod outbound;
+
+pub use config::HttpConfig;
+pub use http::Http;
+pub use outbound::Outbound;
+
+pub use reqwest;
@@ -0,0 +1,220 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, RequestBuilder, Response, Url};
+use rocket::http::Status;
+use rocket::outcome::{try_outcome, IntoOutcome};
+use rocket::request::{self, Deadline, FromRequest, Outcome, Request};
+
+use crate::Http;
+
+/// A request guard for making outbound requests on behalf of a request.
+///
+/// Requests built via an `Outbound` carry the incoming request's context:
+///
+///   * Headers named in [`HttpConfig::propagate`](crate::HttpConfig::propagate),
+///     by default the W3C trace context headers, are copied from the incoming
+///     request.
+///   * The request ID, from the incoming header named
+///     [`HttpConfig::request_id_header`](crate::HttpConfig::request_id_header)
+///     or, if it has none, generated once per incoming request, is sent in a
+///     header of the same name.
+///   * The request's timeout is the smaller of the client's timeout and the
+///     time remaining until the incoming request's [`Deadline`]. If the
+///     application configures a [`timeout_header`](rocket::Config::timeout_header),
+///     the remaining budget is also sent in that header, in milliseconds, so
+///     that a downstream Rocket application inherits the deadline.
+///
+/// Requests sent via [`Outbound::send()`] are additionally logged, along with
+/// the incoming request's ID, and fail immediately if the deadline has already
+/// passed.
+///
+/// Requires the fairing returned by [`Http::fairing()`]. Without it, the guard
+/// fails with `500 Internal Server Error`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket_client::Outbound;
+///
+/// #[get("/user/<id>")]
+/// async fn user(id: u64, http: Outbound<'_>) -> Result<String, Status> {
+///     let request = http.get(format!("https://users.internal/{id}"));
+///     let response = http.send(request).await.map_err(|_| Status::BadGateway)?;
+///     response.text().await.map_err(|_| Status::BadGateway)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Outbound<'r> {
+    http: &'r Http,
+    headers: HeaderMap,
+    request_id: Option<&'r str>,
+    deadline: Deadline,
+    timeout_header: Option<&'r str>,
+}
+
+/// The request ID generated for a request without one.
+struct RequestId(String);
+
+impl<'r> Outbound<'r> {
+    /// Returns a builder for a request with method `method` to `url` carrying
+    /// the incoming request's context.
+    ///
+    /// The builder's timeout and headers may be further modified before the
+    /// request is sent.
+    pub fn request<U: reqwest::IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
+        let mut headers = self.headers.clone();
+        let timeout = self.timeout();
+        if let (Some(name), Some(budget)) = (self.timeout_header, self.deadline.remaining()) {
+            let value = format!("{}m", budget.as_millis());
+            if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), value.try_into()) {
+                headers.insert(name, value);
+            }
+        }
+
+        let builder = self.http.client().request(method, url).headers(headers);
+        match timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+    }
+
+    /// Returns a builder for a `GET` request to `url`. See
+    /// [`Outbound::request()`].
+    pub fn get<U: reqwest::IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    /// Returns a builder for a `POST` request to `url`. See
+    /// [`Outbound::request()`].
+    pub fn post<U: reqwest::IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    /// Returns a builder for a `PUT` request to `url`. See
+    /// [`Outbound::request()`].
+    pub fn put<U: reqwest::IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    /// Returns a builder for a `DELETE` request to `url`. See
+    /// [`Outbound::request()`].
+    pub fn delete<U: reqwest::IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+
+    /// Sends the request built by `builder`, logging its outcome.
+    ///
+    /// Completed requests are logged at the `debug` level and failed ones at
+    /// the `warn` level, with the method, URL, status, elapsed time, and the
+    /// incoming request's ID. If the incoming request's deadline has already
+    /// passed, the request is given a zero timeout and so fails with a timeout
+    /// error.
+    pub async fn send(&self, builder: RequestBuilder) -> reqwest::Result<Response> {
+        let request = builder.build()?;
+        let (method, url) = (request.method().clone(), redacted(request.url()));
+        let request_id = self.request_id.unwrap_or("-");
+        if self.deadline.is_expired() {
+            warn!(%method, %url, request_id, "deadline passed before outbound request");
+        }
+
+        let start = Instant::now();
+        let result = self.http.client().execute(request).await;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        match &result {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                debug!(%method, %url, status, elapsed_ms, request_id, "outbound request");
+            }
+            Err(e) => {
+                let timeout = e.is_timeout();
+                warn!(%method, %url, elapsed_ms, request_id, timeout,
+                    "outbound request failed: {e}");
+            }
+        }
+
+        result
+    }
+
+    /// Returns the request ID sent with outbound requests, if request IDs are
+    /// enabled.
+    pub fn request_id(&self) -> Option<&'r str> {
+        self.request_id
+    }
+
+    /// Returns the incoming request's deadline.
+    pub fn deadline(&self) -> Deadline {
+        self.deadline
+    }
+
+    /// Returns the timeout applied to outbound requests: the smaller of the
+    /// client's timeout and the time remaining until the deadline, if either.
+    pub fn timeout(&self) -> Option<Duration> {
+        match (self.http.timeout(), self.deadline.remaining()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Returns the underlying [`Http`] client handle.
+    pub fn http(&self) -> &'r Http {
+        self.http
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Outbound<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let http = try_outcome!(req.rocket().state::<Http>()
+            .or_error((Status::InternalServerError, ())));
+
+        let config = http.config();
+        let mut headers = HeaderMap::new();
+        for name in &config.propagate {
+            let Ok(header) = HeaderName::try_from(name.as_str()) else { continue };
+            for value in req.headers().get(name) {
+                if let Ok(value) = HeaderValue::from_str(value) {
+                    headers.append(header.clone(), value);
+                }
+            }
+        }
+
+        let request_id = match config.request_id_header.as_str() {
+            "" => None,
+            name => {
+                let id = req.headers().get_one(name)
+                    .unwrap_or_else(|| req.local_cache(|| RequestId(generate_id())).0.as_str());
+
+                if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), id.try_into()) {
+                    headers.insert(name, value);
+                }
+
+                Some(id)
+            }
+        };
+
+        let deadline = req.guard::<Deadline>().await.succeeded().unwrap_or(Deadline::none());
+        let timeout_header = req.rocket().config().timeout_header.as_ref().map(|h| h.as_str());
+        Outcome::Success(Outbound { http, headers, request_id, deadline, timeout_header })
+    }
+}
+
+/// Generates a random, 128-bit, hex-encoded request ID.
+fn generate_id() -> String {
+    format!("{:032x}", rand::thread_rng().gen::<u128>())
+}
+
+/// The URL without credentials, query, or fragment, for logging.
+fn redacted(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.set_query(None);
+    url.set_fragment(None);
+    url.to_string()
+}
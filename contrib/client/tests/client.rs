@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket_client::{Http, HttpConfig, Outbound};
+
+async fn client(figment: rocket::figment::Figment) -> Client {
+    let rocket = rocket::custom(figment).attach(Http::fairing());
+    Client::untracked(rocket).await.unwrap()
+}
+
+fn header(request: &rocket_client::reqwest::Request, name: &str) -> Option<String> {
+    request.headers().get(name).map(|value| value.to_str().unwrap().to_string())
+}
+
+#[rocket::async_test]
+async fn propagates_context_headers() {
+    let client = client(rocket::Config::figment()).await;
+    let request = client.get("/")
+        .header(Header::new("traceparent", "00-abc-def-01"))
+        .header(Header::new("tracestate", "vendor=1"))
+        .header(Header::new("X-Request-Id", "req-123"))
+        .header(Header::new("X-Secret", "hunter2"));
+
+    let outbound = request.guard::<Outbound<'_>>().await.unwrap();
+    assert_eq!(outbound.request_id(), Some("req-123"));
+
+    let built = outbound.get("http://upstream.test/").build().unwrap();
+    assert_eq!(header(&built, "traceparent").as_deref(), Some("00-abc-def-01"));
+    assert_eq!(header(&built, "tracestate").as_deref(), Some("vendor=1"));
+    assert_eq!(header(&built, "x-request-id").as_deref(), Some("req-123"));
+    assert_eq!(header(&built, "x-secret"), None);
+}
+
+#[rocket::async_test]
+async fn generates_one_request_id_per_request() {
+    let client = client(rocket::Config::figment()).await;
+    let request = client.get("/");
+    let a = request.guard::<Outbound<'_>>().await.unwrap();
+    let b = request.guard::<Outbound<'_>>().await.unwrap();
+
+    let id = a.request_id().unwrap();
+    assert_eq!(id.len(), 32);
+    assert_eq!(b.request_id(), Some(id));
+
+    let built = a.post("http://upstream.test/").build().unwrap();
+    assert_eq!(header(&built, "x-request-id").as_deref(), Some(id));
+
+    let other = client.get("/");
+    let c = other.guard::<Outbound<'_>>().await.unwrap();
+    assert_ne!(c.request_id(), Some(id));
+}
+
+#[rocket::async_test]
+async fn request_ids_can_be_disabled() {
+    let figment = rocket::Config::figment().merge(("http_client.request_id_header", ""));
+    let client = client(figment).await;
+    let request = client.get("/").header(Header::new("X-Request-Id", "req-123"));
+    let outbound = request.guard::<Outbound<'_>>().await.unwrap();
+    assert_eq!(outbound.request_id(), None);
+
+    let built = outbound.get("http://upstream.test/").build().unwrap();
+    assert_eq!(header(&built, "x-request-id"), None);
+}
+
+#[rocket::async_test]
+async fn timeouts_are_bounded_by_the_deadline() {
+    let figment = rocket::Config::figment()
+        .merge(("timeout_header", "X-Budget"))
+        .merge(("http_client.timeout", 60));
+
+    let client = client(figment).await;
+    let request = client.get("/").header(Header::new("X-Budget", "2"));
+    let outbound = request.guard::<Outbound<'_>>().await.unwrap();
+
+    let built = outbound.get("http://upstream.test/").build().unwrap();
+    let timeout = built.timeout().copied().unwrap();
+    assert!(timeout > Duration::ZERO && timeout <= Duration::from_secs(2));
+
+    let budget = header(&built, "x-budget").unwrap();
+    let millis: u64 = budget.strip_suffix('m').unwrap().parse().unwrap();
+    assert!(millis <= 2000);
+
+    // Without a deadline, only the client's timeout applies.
+    let request = client.get("/");
+    let outbound = request.guard::<Outbound<'_>>().await.unwrap();
+    assert_eq!(outbound.timeout(), Some(Duration::from_secs(60)));
+
+    let built = outbound.get("http://upstream.test/").build().unwrap();
+    assert_eq!(header(&built, "x-budget"), None);
+}
+
+#[rocket::async_test]
+async fn guard_fails_without_fairing() {
+    let client = Client::untracked(rocket::build()).await.unwrap();
+    let request = client.get("/");
+    let outcome = request.guard::<Outbound<'_>>().await;
+    assert_eq!(outcome.failed().map(|(status, _)| status), Some(Status::InternalServerError));
+}
+
+#[test]
+fn invalid_configuration_fails_ignition() {
+    let figment = rocket::Config::figment().merge(("http_client.timeout", "soon"));
+    let rocket = rocket::custom(figment).attach(Http::fairing());
+    assert!(rocket::local::blocking::Client::untracked(rocket).is_err());
+}
+
+#[rocket::async_test]
+async fn zero_timeouts_are_disabled() {
+    let client = client(rocket::Config::figment().merge(("http_client.timeout", 0))).await;
+    let request = client.get("/");
+    let outbound = request.guard::<Outbound<'_>>().await.unwrap();
+    assert_eq!(outbound.timeout(), None);
+
+    let http = Http::new(HttpConfig { timeout: 0.0, ..Default::default() }).unwrap();
+    assert_eq!(http.config().timeout, 0.0);
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rocket::{Rocket, Build, Request, Response, Data, Route};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::http::{Method, Status, uncased::Uncased};
+use rocket::response::{self, Responder};
+use rocket::route::{Handler, Outcome};
+use rocket::tokio::{sync::watch, time::timeout};
+
+/// A fairing that coalesces concurrent, identical `GET` requests.
+///
+/// At ignition, the fairing mounts an internal route, named `Coalesce`, that
+/// intercepts `GET` requests before any application route. For each request
+/// that is [eligible](#eligibility):
+///
+///   * If a response to an identical request was stored within the [TTL], it
+///     is sent without calling any application route.
+///   * If an identical request is in flight, the request waits, for at most
+///     [`max_wait`](Coalesce::max_wait()), for its response. If the response
+///     is [shareable](#sharing), a copy is sent. Otherwise, or if the wait
+///     times out, the request is routed as usual.
+///   * Otherwise, the request becomes the leader for identical requests and is
+///     routed as usual.
+///
+/// Coalesced responses include an `X-Coalesced: true` header.
+///
+/// # Eligibility
+///
+/// A `GET` request is eligible if its path starts with one of the configured
+/// [paths](Coalesce::path()), if any are configured, and unless:
+///
+///   * it has a `Cache-Control: no-cache` or `no-store` directive, or
+///   * it has an `Authorization` or `Cookie` header and the fairing does not
+///     [vary](Coalesce::vary()) on that header.
+///
+/// Two eligible requests are identical if their normalized URIs, including
+/// their query strings in any order, and the values of the headers the
+/// fairing varies on are equal. By default, these are `Accept`,
+/// `Accept-Encoding`, and `Accept-Language`.
+///
+/// # Sharing
+///
+/// A response is shared with waiting requests if it has no `Set-Cookie`
+/// header, no `Cache-Control: private` or `no-store` directive, and a body no
+/// larger than the [maximum response size](Coalesce::max_response_size()).
+/// Additionally, a shared response is stored for the TTL only if it has a
+/// `2xx` status and no `Cache-Control: no-cache` directive. A `max-age` or
+/// `s-maxage` directive shorter than the TTL shortens it.
+///
+/// # Interaction with `rocket_cache`
+///
+/// The internal route is ranked just after that of `rocket_cache`, so both
+/// fairings can be attached: cached responses are served first, and cache
+/// misses are coalesced.
+///
+/// [TTL]: Coalesce::ttl()
+pub struct Coalesce {
+    flights: Arc<Flights>,
+    ttl: Duration,
+    max_wait: Duration,
+    max_response_size: ByteUnit,
+    vary: Vec<Uncased<'static>>,
+    paths: Vec<String>,
+}
+
+/// The in-flight and stored responses, keyed by request identity.
+#[derive(Default)]
+struct Flights(Mutex<HashMap<String, Flight>>);
+
+enum Flight {
+    /// A leader is in flight. Resolves to `Some(shared)` when it completes.
+    InFlight(watch::Receiver<Option<Option<Arc<Shared>>>>),
+    /// A shared response, stored until the instant.
+    Stored(Arc<Shared>, Instant),
+}
+
+/// A copy of a shareable response.
+struct Shared {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+/// The internal route intercepting `GET` requests.
+#[derive(Clone)]
+struct Lookup {
+    flights: Arc<Flights>,
+    max_wait: Duration,
+    vary: Arc<[Uncased<'static>]>,
+    paths: Arc<[String]>,
+}
+
+/// The key and sender of a leader request, cached in the request.
+struct Leader(Mutex<Option<(String, watch::Sender<Option<Option<Arc<Shared>>>>)>>);
+
+/// A shared response being sent.
+struct Coalesced(Arc<Shared>);
+
+impl Coalesce {
+    /// Creates a new fairing that coalesces all eligible `GET` requests,
+    /// varies on `Accept`, `Accept-Encoding`, and `Accept-Language`, does not
+    /// store responses (a TTL of zero), waits for at most 30 seconds, and
+    /// shares responses of up to 1MiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_coalesce::Coalesce;
+    ///
+    /// let coalesce = Coalesce::new();
+    /// ```
+    pub fn new() -> Self {
+        Coalesce {
+            flights: Arc::new(Flights::default()),
+            ttl: Duration::ZERO,
+            max_wait: Duration::from_secs(30),
+            max_response_size: 1.mebibytes(),
+            vary: vec!["Accept".into(), "Accept-Encoding".into(), "Accept-Language".into()],
+            paths: vec![],
+        }
+    }
+
+    /// Only coalesces requests whose path starts with `prefix`. May be called
+    /// multiple times to coalesce several paths. By default, all paths are
+    /// coalesced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_coalesce::Coalesce;
+    ///
+    /// let coalesce = Coalesce::new().path("/dashboard").path("/reports");
+    /// ```
+    pub fn path<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.paths.push(prefix.into());
+        self
+    }
+
+    /// Additionally distinguishes requests by the value of the header `name`.
+    ///
+    /// Varying on `Authorization` or `Cookie` allows requests with those
+    /// headers to be coalesced with requests with the same credentials.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_coalesce::Coalesce;
+    ///
+    /// let coalesce = Coalesce::new().vary("Authorization");
+    /// ```
+    pub fn vary<N: Into<String>>(mut self, name: N) -> Self {
+        self.vary.push(Uncased::from(name.into()));
+        self
+    }
+
+    /// Sets how long a shared response is reused for identical requests after
+    /// the leader completes. Defaults to zero: only concurrent requests are
+    /// coalesced.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long a request waits for an in-flight identical request before
+    /// it is routed as usual. Defaults to 30 seconds.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Sets the size of the largest response body that is shared. Defaults to
+    /// 1MiB.
+    pub fn max_response_size(mut self, size: ByteUnit) -> Self {
+        self.max_response_size = size;
+        self
+    }
+
+    /// Returns a copy of `res` if it can be shared with other requests.
+    async fn share(&self, res: &mut Response<'_>) -> Option<Arc<Shared>> {
+        let private = directives(res.headers().get("Cache-Control"))
+            .any(|(name, _)| name == "private" || name == "no-store");
+
+        if private || res.headers().contains("Set-Cookie") {
+            return None;
+        }
+
+        match res.body_mut().size().await {
+            Some(size) if size.bytes() <= self.max_response_size => {}
+            _ => return None,
+        }
+
+        let body = match res.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("failed to read response to coalesce: {e}");
+                return None;
+            }
+        };
+
+        res.set_sized_body(body.len(), Cursor::new(body.clone()));
+        Some(Arc::new(Shared {
+            status: res.status().code,
+            headers: res.headers().iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect(),
+            body,
+        }))
+    }
+
+    /// Returns how long `res`, which is shareable, may be stored, if at all.
+    fn ttl_of(&self, res: &Response<'_>) -> Option<Duration> {
+        if self.ttl.is_zero() || !res.status().class().is_success() {
+            return None;
+        }
+
+        let mut ttl = self.ttl;
+        for (name, value) in directives(res.headers().get("Cache-Control")) {
+            match name.as_str() {
+                "no-cache" => return None,
+                "max-age" | "s-maxage" => {
+                    let max_age = value.and_then(|v| v.parse().ok()).unwrap_or(0);
+                    ttl = ttl.min(Duration::from_secs(max_age));
+                }
+                _ => {}
+            }
+        }
+
+        (!ttl.is_zero()).then_some(ttl)
+    }
+}
+
+impl Default for Coalesce {
+    fn default() -> Self {
+        Coalesce::new()
+    }
+}
+
+/// Iterates over the lowercased directives in `Cache-Control` header values.
+fn directives<'a, I>(values: I) -> impl Iterator<Item = (String, Option<&'a str>)>
+    where I: Iterator<Item = &'a str>
+{
+    values.flat_map(|v| v.split(','))
+        .map(|directive| match directive.split_once('=') {
+            Some((name, value)) => (name, Some(value.trim().trim_matches('"'))),
+            None => (directive, None),
+        })
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value))
+}
+
+impl Lookup {
+    /// Returns the identity of `req` if it is eligible for coalescing.
+    fn key(&self, req: &Request<'_>) -> Option<String> {
+        let path = req.uri().path().as_str();
+        if !self.paths.is_empty() && !self.paths.iter().any(|p| path.starts_with(p.as_str())) {
+            return None;
+        }
+
+        let no_cache = directives(req.headers().get("Cache-Control"))
+            .any(|(name, _)| name == "no-cache" || name == "no-store");
+
+        let credentialed = ["Authorization", "Cookie"].into_iter()
+            .filter(|name| req.headers().contains(name))
+            .any(|name| !self.vary.iter().any(|v| v.as_str().eq_ignore_ascii_case(name)));
+
+        if no_cache || credentialed {
+            return None;
+        }
+
+        let uri = req.uri().clone().into_normalized();
+        let mut query: Vec<_> = uri.query().map(|q| q.as_str().split('&').collect())
+            .unwrap_or_default();
+
+        query.sort_unstable();
+        let mut key = format!("{}?{}", uri.path(), query.join("&"));
+        for name in self.vary.iter() {
+            let values: Vec<_> = req.headers().get(name.as_str()).collect();
+            key.push_str(&format!("\n{}: {}", name, values.join(", ")));
+        }
+
+        Some(key)
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Coalesce {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Response, name: "Coalesce" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let lookup = Lookup {
+            flights: self.flights.clone(),
+            max_wait: self.max_wait,
+            vary: self.vary.clone().into(),
+            paths: self.paths.clone().into(),
+        };
+
+        let mut route = Route::ranked(isize::MIN + 1, Method::Get, "/<_..>", lookup);
+        route.name = Some("Coalesce".into());
+        Ok(rocket.mount("/", vec![route]))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let leader = req.local_cache(|| Leader(Mutex::new(None)));
+        let Some((key, tx)) = leader.0.lock().expect("leader lock").take() else {
+            return;
+        };
+
+        let shared = self.share(res).await;
+        let ttl = shared.as_ref().and_then(|_| self.ttl_of(res));
+        {
+            let mut flights = self.flights.0.lock().expect("flights lock");
+            match (&shared, ttl) {
+                (Some(shared), Some(ttl)) => {
+                    let flight = Flight::Stored(shared.clone(), Instant::now() + ttl);
+                    flights.insert(key, flight);
+                }
+                _ => { flights.remove(&key); }
+            }
+        }
+
+        let _ = tx.send(Some(shared));
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Lookup {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let Some(key) = self.key(req) else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+
+        let mut rx = {
+            let mut flights = self.flights.0.lock().expect("flights lock");
+            let now = Instant::now();
+            match flights.get(&key) {
+                Some(Flight::Stored(shared, expires)) if *expires > now => {
+                    return Outcome::from(req, Coalesced(shared.clone()));
+                }
+                // A leader whose sender is gone was dropped without a response.
+                Some(Flight::InFlight(rx)) if rx.has_changed().is_ok() => rx.clone(),
+                _ => {
+                    flights.retain(|_, flight| match flight {
+                        Flight::Stored(_, expires) => *expires > now,
+                        Flight::InFlight(rx) => rx.has_changed().is_ok(),
+                    });
+
+                    let (tx, rx) = watch::channel(None);
+                    flights.insert(key.clone(), Flight::InFlight(rx));
+                    req.local_cache(|| Leader(Mutex::new(Some((key, tx)))));
+                    return Outcome::forward(data, Status::NotFound);
+                }
+            }
+        };
+
+        let shared = match timeout(self.max_wait, rx.wait_for(Option::is_some)).await {
+            Ok(Ok(value)) => value.clone().flatten(),
+            Ok(Err(_)) | Err(_) => None,
+        };
+
+        match shared {
+            Some(shared) => Outcome::from(req, Coalesced(shared)),
+            None => Outcome::forward(data, Status::NotFound),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Coalesced {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let Coalesced(shared) = self;
+        let mut response = Response::build();
+        response.status(Status::new(shared.status));
+        for (name, value) in &shared.headers {
+            response.raw_header_adjoin(name.clone(), value.clone());
+        }
+
+        response.raw_header("X-Coalesced", "true")
+            .sized_body(shared.body.len(), Cursor::new(shared.body.clone()))
+            .ok()
+    }
+}
+
+impl std::fmt::Debug for Coalesce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Coalesce")
+            .field("ttl", &self.ttl)
+            .field("max_wait", &self.max_wait)
+            .field("max_response_size", &self.max_response_size)
+            .field("vary", &self.vary)
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
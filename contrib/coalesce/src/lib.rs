@@ -0,0 +1,57 @@
+//! Request coalescing (single-flight) for Rocket.
+//!
+//! When an expensive endpoint, like a dashboard that aggregates many queries,
+//! becomes popular or its cache expires, many identical requests may arrive at
+//! once, each repeating the same work: a _thundering herd_. The [`Coalesce`]
+//! fairing deduplicates concurrent, identical `GET` requests: the first
+//! request, the _leader_, runs its handler as usual while every identical
+//! request that arrives before it completes waits for the leader's response
+//! and receives a copy of it. Optionally, the response is then reused for
+//! identical requests for a short [TTL](Coalesce::ttl()).
+//!
+//! Requests are identical if their normalized URIs and the values of the
+//! headers the fairing [varies on](Coalesce::vary()) are equal. Responses are
+//! only shared if they are safe to share: see [`Coalesce`] for details.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_coalesce = "0.1.0"
+//! ```
+//!
+//! Then attach the [`Coalesce`] fairing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::time::Duration;
+//! use rocket_coalesce::Coalesce;
+//!
+//! # async fn expensive_report() -> String { String::new() }
+//! #[get("/dashboard")]
+//! async fn dashboard() -> String {
+//!     expensive_report().await
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![dashboard])
+//!         .attach(Coalesce::new().path("/dashboard").ttl(Duration::from_secs(1)))
+//! }
+//! ```
+//!
+//! Responses sent to waiting requests, or reused within the TTL, include an
+//! `X-Coalesced: true` header.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_coalesce")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+
+pub use fairing::Coalesce;
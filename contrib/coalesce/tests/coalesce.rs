@@ -0,0 +1,120 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rocket::State;
+use rocket::http::{CookieJar, Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket_coalesce::Coalesce;
+
+#[get("/slow")]
+async fn slow(count: &State<AtomicUsize>) -> String {
+    let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+    rocket::tokio::time::sleep(Duration::from_millis(100)).await;
+    format!("report #{n}")
+}
+
+#[get("/fast?<q>")]
+fn fast(q: Option<&str>, count: &State<AtomicUsize>) -> String {
+    let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("{q:?} #{n}")
+}
+
+#[get("/private")]
+fn private(jar: &CookieJar<'_>, count: &State<AtomicUsize>) -> String {
+    jar.add(("visited", "yes"));
+    format!("#{}", count.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+#[get("/failing")]
+fn failing(count: &State<AtomicUsize>) -> Status {
+    count.fetch_add(1, Ordering::SeqCst);
+    Status::ServiceUnavailable
+}
+
+async fn client(coalesce: Coalesce) -> Client {
+    let rocket = rocket::build()
+        .manage(AtomicUsize::new(0))
+        .mount("/", routes![slow, fast, private, failing])
+        .attach(coalesce);
+
+    Client::tracked(rocket).await.unwrap()
+}
+
+fn count(client: &Client) -> usize {
+    client.rocket().state::<AtomicUsize>().unwrap().load(Ordering::SeqCst)
+}
+
+#[rocket::async_test]
+async fn concurrent_requests_are_coalesced() {
+    let client = client(Coalesce::new()).await;
+    let first = client.get("/slow").dispatch();
+    let second = async {
+        rocket::tokio::task::yield_now().await;
+        client.get("/slow").dispatch().await
+    };
+
+    let (first, second) = rocket::tokio::join!(first, second);
+    assert!(first.headers().get_one("X-Coalesced").is_none());
+    assert_eq!(second.headers().get_one("X-Coalesced"), Some("true"));
+    assert_eq!(first.into_string().await.unwrap(), "report #1");
+    assert_eq!(second.into_string().await.unwrap(), "report #1");
+    assert_eq!(count(&client), 1);
+
+    // Without a TTL, later requests run the handler again.
+    let third = client.get("/slow").dispatch().await;
+    assert_eq!(third.into_string().await.unwrap(), "report #2");
+}
+
+#[rocket::async_test]
+async fn responses_are_reused_within_ttl() {
+    let client = client(Coalesce::new().ttl(Duration::from_secs(60))).await;
+    let response = client.get("/fast?q=a&x=1").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Some(\"a\") #1");
+
+    // Query parameters are normalized.
+    let response = client.get("/fast?x=1&q=a").dispatch().await;
+    assert_eq!(response.headers().get_one("X-Coalesced"), Some("true"));
+    assert_eq!(response.into_string().await.unwrap(), "Some(\"a\") #1");
+
+    // Varied headers, credentials, and `no-cache` requests are distinguished.
+    let response = client.get("/fast?q=a&x=1").header(Header::new("Accept", "text/html"))
+        .dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Some(\"a\") #2");
+
+    let response = client.get("/fast?q=a&x=1").header(Header::new("Authorization", "Bearer x"))
+        .dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Some(\"a\") #3");
+
+    let response = client.get("/fast?q=a&x=1").header(Header::new("Cache-Control", "no-cache"))
+        .dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Some(\"a\") #4");
+}
+
+#[rocket::async_test]
+async fn unshareable_responses_are_not_reused() {
+    let client = client(Coalesce::new().ttl(Duration::from_secs(60))).await;
+    for n in 1..=2 {
+        let response = client.get("/private").dispatch().await;
+        assert!(response.headers().get_one("X-Coalesced").is_none());
+        assert_eq!(response.into_string().await.unwrap(), format!("#{n}"));
+    }
+
+    for _ in 0..2 {
+        let response = client.get("/failing").dispatch().await;
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert!(response.headers().get_one("X-Coalesced").is_none());
+    }
+
+    assert_eq!(count(&client), 4);
+}
+
+#[rocket::async_test]
+async fn only_configured_paths_are_coalesced() {
+    let client = client(Coalesce::new().path("/slow").ttl(Duration::from_secs(60))).await;
+    client.get("/fast").dispatch().await;
+    let response = client.get("/fast").dispatch().await;
+    assert!(response.headers().get_one("X-Coalesced").is_none());
+    assert_eq!(count(&client), 2);
+}
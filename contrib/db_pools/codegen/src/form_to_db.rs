@@ -0,0 +1,110 @@
+use proc_macro::TokenStream;
+
+use devise::{DeriveGenerator, FromMeta, MapperBuild, Support, ValidatorBuild};
+use devise::proc_macro2_diagnostics::SpanDiagnosticExt;
+use devise::syn::{self, ext::IdentExt, spanned::Spanned};
+
+const ONE_TABLE_ATTR: &str = "missing `#[form_to_db(table = \"name\")]` attribute";
+const NO_COLUMNS: &str = "struct must have at least one field that isn't skipped";
+
+#[derive(Debug, FromMeta)]
+struct TableAttribute {
+    table: String,
+}
+
+#[derive(Debug, Default, FromMeta)]
+struct FieldAttribute {
+    column: Option<String>,
+    skip: bool,
+}
+
+/// Returns the non-skipped fields of `fields` along with their column names.
+fn columns(fields: &syn::Fields) -> devise::Result<Vec<(&syn::Field, String)>> {
+    let mut columns = vec![];
+    for field in fields.iter() {
+        let attr = FieldAttribute::one_from_attrs("form_to_db", &field.attrs)?
+            .unwrap_or_default();
+
+        if attr.skip {
+            continue;
+        }
+
+        let column = match attr.column {
+            Some(column) => column,
+            None => field.ident.as_ref().expect("named field").unraw().to_string(),
+        };
+
+        columns.push((field, column));
+    }
+
+    Ok(columns)
+}
+
+pub fn derive_form_to_db(input: TokenStream) -> TokenStream {
+    let impl_tokens = quote! {
+        impl<'q, __DB: rocket_db_pools::sqlx::Database> rocket_db_pools::FormToDb<'q, __DB>
+    };
+
+    DeriveGenerator::build_for(input, impl_tokens)
+        .support(Support::NamedStruct | Support::Type | Support::Lifetime)
+        .validator(ValidatorBuild::new()
+            .struct_validate(|_, s| {
+                match columns(&s.fields)?.is_empty() {
+                    true => Err(s.span().error(NO_COLUMNS)),
+                    false => Ok(())
+                }
+            })
+        )
+        .type_bound_mapper(MapperBuild::new()
+            .try_struct_map(|_, s| {
+                let bounds = columns(&s.fields)?.into_iter().map(|(field, _)| {
+                    let ty = &field.ty;
+                    quote_spanned! { ty.span() =>
+                        #ty: 'q
+                            + rocket_db_pools::sqlx::Encode<'q, __DB>
+                            + rocket_db_pools::sqlx::Type<__DB>
+                    }
+                });
+
+                Ok(quote!(#(#bounds,)*))
+            })
+        )
+        .inner_mapper(MapperBuild::new()
+            .try_struct_map(|_, s| {
+                let table = TableAttribute::one_from_attrs("form_to_db", &s.attrs)?
+                    .map(|attr| attr.table)
+                    .ok_or_else(|| s.span().error(ONE_TABLE_ATTR))?;
+
+                let columns = columns(&s.fields)?;
+                let names: Vec<_> = columns.iter().map(|(_, column)| column.as_str()).collect();
+                let positional = vec!["?".to_string(); names.len()];
+                let numbered: Vec<_> = (1..=names.len()).map(|i| format!("${}", i)).collect();
+                let insert = |placeholders: &[String]| format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    table, names.join(", "), placeholders.join(", ")
+                );
+
+                let (insert, insert_numbered) = (insert(&positional), insert(&numbered));
+                let binds = columns.iter().map(|(field, _)| {
+                    let ident = field.ident.as_ref().expect("named field");
+                    quote_spanned!(field.span() => .bind(self.#ident))
+                });
+
+                let query = quote!(rocket_db_pools::form_to_db::Query<'q, __DB>);
+                Ok(quote! {
+                    const TABLE: &'static str = #table;
+
+                    const COLUMNS: &'static [&'static str] = &[#(#names),*];
+
+                    const INSERT: &'static str = #insert;
+
+                    const INSERT_NUMBERED: &'static str = #insert_numbered;
+
+                    fn bind(self, query: #query) -> #query {
+                        query #(#binds)*
+                    }
+                })
+            })
+        )
+        .to_tokens()
+}
@@ -10,6 +10,7 @@
 #[macro_use] extern crate quote;
 
 mod database;
+mod form_to_db;
 
 /// Automatic derive for the [`Database`] trait.
 ///
@@ -59,3 +60,51 @@ mod database;
 pub fn derive_database(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     crate::database::derive_database(input)
 }
+
+/// Automatic derive for the [`FormToDb`] trait.
+///
+/// ```rust
+/// # #[cfg(feature = "sqlx")] mod _inner {
+/// use rocket_db_pools::FormToDb;
+///
+/// #[derive(FormToDb)]
+/// #[form_to_db(table = "tasks")]
+/// struct Todo {
+///     description: String,
+///     #[form_to_db(column = "completed")]
+///     done: bool,
+///     #[form_to_db(skip)]
+///     confirm: bool,
+/// }
+/// # }
+/// ```
+///
+/// The derive generates an implementation of [`FormToDb`] for every `sqlx`
+/// database that can encode the types of all non-skipped fields:
+///
+/// * [`FormToDb::TABLE`] is set to the value of `table` in the
+///   `#[form_to_db(table = "name")]` attribute.
+///
+/// * [`FormToDb::COLUMNS`] lists the name of the column of every field not
+///   marked `#[form_to_db(skip)]`, in field order. A column's name is the
+///   field's name unless overridden with `#[form_to_db(column = "name")]`.
+///
+/// * [`FormToDb::INSERT`] and [`FormToDb::INSERT_NUMBERED`] are set to
+///   `INSERT` statements for the table and columns.
+///
+/// * [`FormToDb::bind()`] binds the value of every column's field.
+///
+/// The derive can only be applied to structures with named fields, at least
+/// one of which is not skipped. See the [`form_to_db`] module for details.
+///
+/// [`FormToDb`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html
+/// [`FormToDb::TABLE`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html#associatedconstant.TABLE
+/// [`FormToDb::COLUMNS`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html#associatedconstant.COLUMNS
+/// [`FormToDb::INSERT`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html#associatedconstant.INSERT
+/// [`FormToDb::INSERT_NUMBERED`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html#associatedconstant.INSERT_NUMBERED
+/// [`FormToDb::bind()`]: ../rocket_db_pools/form_to_db/trait.FormToDb.html#tymethod.bind
+/// [`form_to_db`]: ../rocket_db_pools/form_to_db/index.html
+#[proc_macro_derive(FormToDb, attributes(form_to_db))]
+pub fn derive_form_to_db(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    crate::form_to_db::derive_form_to_db(input)
+}
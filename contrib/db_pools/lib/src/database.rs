@@ -1,11 +1,14 @@
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::time::Instant;
 
-use rocket::{error, Build, Ignite, Phase, Rocket, Sentinel, Orbit};
+use rocket::{error, debug, warn, Build, Ignite, Phase, Rocket, Sentinel, Orbit};
 use rocket::fairing::{self, Fairing, Info, Kind};
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::figment::providers::Serialized;
 use rocket::http::Status;
+use rocket::timing::{Metric, Recorder, ServerTiming};
+use rocket::tracing::{self, Instrument};
 
 use crate::Pool;
 
@@ -167,6 +170,23 @@ pub struct Initializer<D: Database>(Option<&'static str>, PhantomData<fn() -> D>
 ///   another error occurs, the guard _fails_ with status `ServiceUnavailable`
 ///   and the error is returned in `Some`.
 ///
+/// ## Instrumentation
+///
+/// Connections are acquired in a `database` span, nested in the request's
+/// span, that records how long acquisition took and, once the connection is
+/// dropped, how long it was held. Additionally, two [`Server-Timing`] metrics,
+/// described with the database's name, are recorded:
+///
+///   * `db-acquire`: the time spent acquiring the connection from the pool.
+///   * `db`: the time from acquisition until the connection was dropped or
+///     converted into its inner value.
+///
+/// The metrics are only sent to clients when the
+/// [`ServerTiming::fairing()`] is attached. Statements logged by drivers, such
+/// as `sqlx`, are nested in the span of the request that issued them.
+///
+/// [`Server-Timing`]: rocket::timing
+///
 /// ## Deref
 ///
 /// A type of `Connection<Db>` dereferences, mutably and immutably, to the
@@ -192,7 +212,29 @@ pub struct Initializer<D: Database>(Option<&'static str>, PhantomData<fn() -> D>
 /// }
 /// # }
 /// ```
-pub struct Connection<D: Database>(<D::Pool as Pool>::Connection);
+pub struct Connection<D: Database> {
+    connection: <D::Pool as Pool>::Connection,
+    _usage: Usage,
+}
+
+/// Records how long a [`Connection`] was held when dropped.
+struct Usage {
+    database: &'static str,
+    acquired: Instant,
+    recorder: Recorder,
+    span: tracing::Span,
+}
+
+impl Drop for Usage {
+    fn drop(&mut self) {
+        let held = self.acquired.elapsed();
+        self.span.in_scope(|| {
+            debug!(held_ms = held.as_secs_f64() * 1000.0, "database connection released");
+        });
+
+        self.recorder.push(Metric::new("db").with_duration(held).with_description(self.database));
+    }
+}
 
 impl<D: Database> Initializer<D> {
     /// Returns a database initializer fairing for `D`.
@@ -239,7 +281,7 @@ impl<D: Database> Connection<D> {
     /// # }
     /// ```
     pub fn into_inner(self) -> <D::Pool as Pool>::Connection {
-        self.0
+        self.connection
     }
 }
 
@@ -283,12 +325,35 @@ impl<'r, D: Database> FromRequest<'r> for Connection<D> {
     type Error = Option<<D::Pool as Pool>::Error>;
 
     async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        match D::fetch(req.rocket()) {
-            Some(db) => match db.get().await {
-                Ok(conn) => Outcome::Success(Connection(conn)),
-                Err(e) => Outcome::Error((Status::ServiceUnavailable, Some(e))),
+        let Some(db) = D::fetch(req.rocket()) else {
+            return Outcome::Error((Status::InternalServerError, None));
+        };
+
+        let timing = req.guard::<&ServerTiming>().await.expect("infallible guard");
+        let span = tracing::info_span!("database", name = D::NAME);
+        let start = Instant::now();
+        let result = db.get().instrument(span.clone()).await;
+        let elapsed = start.elapsed();
+        timing.push(Metric::new("db-acquire").with_duration(elapsed).with_description(D::NAME));
+
+        let _entered = span.enter();
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        match result {
+            Ok(connection) => {
+                debug!(elapsed_ms, "database connection acquired");
+                let usage = Usage {
+                    database: D::NAME,
+                    acquired: Instant::now(),
+                    recorder: timing.recorder(),
+                    span: span.clone(),
+                };
+
+                Outcome::Success(Connection { connection, _usage: usage })
             },
-            None => Outcome::Error((Status::InternalServerError, None)),
+            Err(e) => {
+                warn!(elapsed_ms, "failed to acquire database connection");
+                Outcome::Error((Status::ServiceUnavailable, Some(e)))
+            }
         }
     }
 }
@@ -303,12 +368,12 @@ impl<D: Database> Deref for Connection<D> {
     type Target = <D::Pool as Pool>::Connection;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.connection
     }
 }
 
 impl<D: Database> DerefMut for Connection<D> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.connection
     }
 }
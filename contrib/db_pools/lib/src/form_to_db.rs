@@ -0,0 +1,97 @@
+//! Mapping of form structures to database rows.
+//!
+//! Structures that collect a row's worth of form fields usually mirror the
+//! table the row is inserted into. Instead of defining a second, row-shaped
+//! structure and converting between the two, the form structure can derive
+//! both [`FromForm`](rocket::form::FromForm) and [`FormToDb`], then be
+//! inserted directly with [`FormToDb::insert()`]:
+//!
+//! ```rust
+//! # #[cfg(feature = "sqlx_sqlite")] mod _inner {
+//! # use rocket::{post, FromForm};
+//! # use rocket_db_pools::{sqlx, Database};
+//! # #[derive(Database)]
+//! # #[database("todos")]
+//! # struct Db(sqlx::SqlitePool);
+//! use rocket::form::Form;
+//! use rocket::response::Debug;
+//! use rocket_db_pools::{Connection, FormToDb};
+//!
+//! #[derive(FromForm, FormToDb)]
+//! #[form_to_db(table = "tasks")]
+//! struct Todo {
+//!     #[field(validate = len(1..))]
+//!     description: String,
+//!     #[form_to_db(column = "completed")]
+//!     done: bool,
+//!     #[form_to_db(skip)]
+//!     confirm: bool,
+//! }
+//!
+//! #[post("/", data = "<todo>")]
+//! async fn new(mut db: Connection<Db>, todo: Form<Todo>) -> Result<(), Debug<sqlx::Error>> {
+//!     // INSERT INTO tasks (description, completed) VALUES (?, ?)
+//!     todo.into_inner().insert().execute(&mut **db).await?;
+//!     Ok(())
+//! }
+//! # }
+//! ```
+//!
+//! The derive accepts the following attributes:
+//!
+//!   * `#[form_to_db(table = "name")]`, on the structure, _required_
+//!
+//!     The name of the table rows are inserted into.
+//!
+//!   * `#[form_to_db(column = "name")]`, on a field
+//!
+//!     The name of the column the field is inserted into. Defaults to the
+//!     field's name.
+//!
+//!   * `#[form_to_db(skip)]`, on a field
+//!
+//!     Excludes the field, for instance one that is only used for validation,
+//!     from inserted rows. Columns without a field take on their default value.
+//!
+//! Table and column names are used verbatim in the generated SQL. Every field
+//! that isn't skipped must be encodable by the database driver.
+//!
+//! Only the `sqlx` drivers are supported. With `diesel`, derive `diesel`'s
+//! own `Insertable` alongside `FromForm` instead.
+
+/// The type of query returned by [`FormToDb::insert()`].
+pub type Query<'q, DB> = sqlx::query::Query<'q, DB, <DB as sqlx::Database>::Arguments<'q>>;
+
+/// Trait implemented by structures that map to a row in a database table.
+///
+/// This trait should be derived. See the [module level docs](self) for
+/// details.
+pub trait FormToDb<'q, DB: sqlx::Database>: Sized {
+    /// The name of the table.
+    const TABLE: &'static str;
+
+    /// The names of the columns values are inserted into, in field order.
+    const COLUMNS: &'static [&'static str];
+
+    /// An `INSERT` statement for the columns with `?` placeholders.
+    const INSERT: &'static str;
+
+    /// An `INSERT` statement for the columns with `$N` placeholders.
+    const INSERT_NUMBERED: &'static str;
+
+    /// Binds the value of each column, in field order, to `query`.
+    fn bind(self, query: Query<'q, DB>) -> Query<'q, DB>;
+
+    /// Returns a query that inserts `self` as a row into [`Self::TABLE`].
+    ///
+    /// The query uses [`Self::INSERT_NUMBERED`] for PostgreSQL and
+    /// [`Self::INSERT`] otherwise.
+    fn insert(self) -> Query<'q, DB> {
+        let sql = match DB::NAME {
+            "PostgreSQL" => Self::INSERT_NUMBERED,
+            _ => Self::INSERT,
+        };
+
+        self.bind(sqlx::query(sql))
+    }
+}
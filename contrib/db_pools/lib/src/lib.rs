@@ -96,6 +96,45 @@
 //!    # }
 //!    ```
 //!
+//! # Pagination
+//!
+//! Rocket's [`Pagination`] request guard and [`Page`] responder, available
+//! with Rocket's `json` feature, pair naturally with database queries:
+//! [`Pagination::limit_offset()`] returns the `LIMIT` and `OFFSET` of the
+//! requested page as `i64`s, suitable for binding as query parameters.
+//!
+//! ```rust
+//! # #[cfg(feature = "sqlx_sqlite")] mod _inner {
+//! # use rocket::get;
+//! # use rocket_db_pools::{sqlx, Database};
+//! # #[derive(Database)]
+//! # #[database("sqlite_logs")]
+//! # struct Logs(sqlx::SqlitePool);
+//! use rocket::pagination::{Page, Pagination};
+//! use rocket_db_pools::Connection;
+//!
+//! #[get("/logs")]
+//! async fn logs(mut db: Connection<Logs>, page: Pagination) -> Option<Page<String>> {
+//!     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM logs")
+//!         .fetch_one(&mut **db).await
+//!         .ok()?;
+//!
+//!     let (limit, offset) = page.limit_offset();
+//!     let logs = sqlx::query_scalar("SELECT content FROM logs LIMIT ? OFFSET ?")
+//!         .bind(limit)
+//!         .bind(offset)
+//!         .fetch_all(&mut **db).await
+//!         .ok()?;
+//!
+//!     Some(page.page(logs, total as u64))
+//! }
+//! # }
+//! ```
+//!
+//! [`Pagination`]: rocket::pagination::Pagination
+//! [`Page`]: rocket::pagination::Page
+//! [`Pagination::limit_offset()`]: rocket::pagination::Pagination::limit_offset()
+//!
 //! # Supported Drivers
 //!
 //! At present, this crate supports _four_ drivers: [`deadpool`], [`sqlx`],
@@ -248,6 +287,7 @@ pub use rocket::figment;
 #[cfg(feature = "deadpool_redis")] pub use deadpool_redis;
 #[cfg(feature = "mongodb")] pub use mongodb;
 #[cfg(feature = "sqlx")] pub use sqlx;
+#[cfg(feature = "sqlx")] pub mod form_to_db;
 
 mod database;
 mod error;
@@ -258,5 +298,6 @@ pub use self::database::{Connection, Database, Initializer};
 pub use self::error::Error;
 pub use self::pool::Pool;
 pub use self::config::Config;
+#[cfg(feature = "sqlx")] pub use self::form_to_db::FormToDb;
 
 pub use rocket_db_pools_codegen::*;
@@ -0,0 +1,71 @@
+#![cfg(feature = "sqlx_sqlite")]
+
+use rocket::{post, routes, FromForm};
+use rocket::form::Form;
+use rocket::http::ContentType;
+use rocket::local::asynchronous::Client;
+use rocket_db_pools::{sqlx, Connection, Database, FormToDb};
+
+#[derive(Database)]
+#[database("todos")]
+struct Db(sqlx::SqlitePool);
+
+#[derive(Debug, FromForm, FormToDb)]
+#[form_to_db(table = "tasks")]
+struct Todo {
+    description: String,
+    #[form_to_db(column = "completed")]
+    done: bool,
+    #[form_to_db(skip)]
+    #[allow(dead_code)]
+    confirm: bool,
+}
+
+#[post("/", data = "<todo>")]
+async fn new(mut db: Connection<Db>, todo: Form<Todo>) -> String {
+    sqlx::query("CREATE TABLE tasks (id INTEGER PRIMARY KEY, description TEXT, completed BOOL)")
+        .execute(&mut **db).await
+        .unwrap();
+
+    todo.into_inner().insert().execute(&mut **db).await.unwrap();
+    let (description, completed): (String, bool) =
+        sqlx::query_as("SELECT description, completed FROM tasks WHERE id = 1")
+            .fetch_one(&mut **db).await
+            .unwrap();
+
+    format!("{description}: {completed}")
+}
+
+#[test]
+fn derived_constants() {
+    type Sqlite = sqlx::Sqlite;
+    assert_eq!(<Todo as FormToDb<'_, Sqlite>>::TABLE, "tasks");
+    assert_eq!(<Todo as FormToDb<'_, Sqlite>>::COLUMNS, ["description", "completed"]);
+    assert_eq!(
+        <Todo as FormToDb<'_, Sqlite>>::INSERT,
+        "INSERT INTO tasks (description, completed) VALUES (?, ?)"
+    );
+    assert_eq!(
+        <Todo as FormToDb<'_, Sqlite>>::INSERT_NUMBERED,
+        "INSERT INTO tasks (description, completed) VALUES ($1, $2)"
+    );
+}
+
+#[rocket::async_test]
+async fn form_is_inserted() {
+    let figment = rocket::Config::figment()
+        .merge(("databases.todos.url", "sqlite::memory:"))
+        .merge(("databases.todos.max_connections", 1));
+
+    let rocket = rocket::custom(figment)
+        .attach(Db::init())
+        .mount("/", routes![new]);
+
+    let client = Client::debug(rocket).await.unwrap();
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body("description=write+tests&done=true&confirm=on")
+        .dispatch().await;
+
+    assert_eq!(response.into_string().await.unwrap(), "write tests: true");
+}
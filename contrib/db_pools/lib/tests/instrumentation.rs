@@ -0,0 +1,35 @@
+#![cfg(feature = "sqlx_sqlite")]
+
+use rocket::{get, routes};
+use rocket::local::asynchronous::Client;
+use rocket::timing::ServerTiming;
+use rocket_db_pools::{sqlx, Connection, Database};
+
+#[derive(Database)]
+#[database("db")]
+struct Db(sqlx::SqlitePool);
+
+#[get("/")]
+async fn query(mut db: Connection<Db>) -> String {
+    let (value,): (i64,) = sqlx::query_as("SELECT 42").fetch_one(&mut **db).await.unwrap();
+    value.to_string()
+}
+
+#[rocket::async_test]
+async fn connection_metrics_are_recorded() {
+    let figment = rocket::Config::figment().merge(("databases.db.url", "sqlite::memory:"));
+    let rocket = rocket::custom(figment)
+        .attach(Db::init())
+        .attach(ServerTiming::fairing())
+        .mount("/", routes![query]);
+
+    let client = Client::debug(rocket).await.unwrap();
+    let response = client.get("/").dispatch().await;
+    let header = response.headers().get_one("Server-Timing").unwrap().to_string();
+    let metrics: Vec<_> = header.split(", ").collect();
+    assert_eq!(metrics.len(), 3);
+    assert!(metrics[0].starts_with("db-acquire;dur=") && metrics[0].ends_with(r#";desc="db""#));
+    assert!(metrics[1].starts_with("db;dur=") && metrics[1].ends_with(r#";desc="db""#));
+    assert!(metrics[2].starts_with("total;dur="));
+    assert_eq!(response.into_string().await.unwrap(), "42");
+}
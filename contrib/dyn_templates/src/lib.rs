@@ -175,6 +175,23 @@
 //! metadata, such as whether a template is known to exist
 //! ([`Metadata::contains_template()`]), and to render templates to `String`
 //! ([`Metadata::render()`]).
+//!
+//! ### Streaming
+//!
+//! [`StreamedTemplate`] sends a rendered _shell_ template, such as a page's
+//! head and layout, immediately, then renders and streams deferred sections as
+//! the futures producing them resolve:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_dyn_templates::{Template, StreamedTemplate, context};
+//!
+//! #[get("/")]
+//! fn index() -> StreamedTemplate {
+//!     StreamedTemplate::shell("head", context! { title: "Home" })
+//!         .then(async { Template::render("body", context! { items: [1, 2, 3] }) })
+//! }
+//! ```
 
 #![doc(html_root_url = "https://api.rocket.rs/master/rocket_dyn_templates")]
 #![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
@@ -205,7 +222,9 @@ mod fairing;
 mod context;
 mod metadata;
 mod template;
+mod stream;
 
 pub use engine::Engines;
 pub use metadata::Metadata;
 pub use template::Template;
+pub use stream::StreamedTemplate;
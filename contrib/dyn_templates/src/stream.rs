@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+use std::future::Future;
+use std::io::Cursor;
+
+use rocket::{Rocket, Ignite, Sentinel};
+use rocket::request::Request;
+use rocket::response::{self, Response, Responder};
+use rocket::response::stream::ReaderStream;
+use rocket::http::Status;
+use rocket::futures::future::BoxFuture;
+use rocket::futures::stream::{self, StreamExt};
+use rocket::serde::Serialize;
+
+use crate::Template;
+use crate::context::ContextManager;
+
+/// Responder that streams a template shell followed by deferred sections.
+///
+/// A `StreamedTemplate` renders and sends a _shell_ template, typically the
+/// document head and page layout, as soon as the response begins. Each section
+/// added via [`StreamedTemplate::then()`] is a future resolving to a
+/// [`Template`]; once the shell is flushed, the futures are awaited in order
+/// and each resulting template is rendered and sent as it becomes available.
+/// This allows the client to begin fetching stylesheets and scripts referenced
+/// in the shell while slower data is still being resolved, improving
+/// time-to-first-byte for data-heavy pages.
+///
+/// The response `Content-Type` is derived from the shell template's extension.
+/// The body is [unsized](rocket::response::Body#unsized) and is thus sent using
+/// chunked transfer encoding over HTTP/1.1.
+///
+/// # Errors
+///
+/// If the shell fails to render, the responder fails with
+/// `Status::InternalServerError`. Because the status and headers have already
+/// been sent by the time a deferred section is rendered, a section that fails
+/// to render is logged and the response ends early.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_dyn_templates::{Template, StreamedTemplate, context};
+///
+/// # async fn load_items() -> Vec<String> { vec![] }
+/// #[get("/items")]
+/// fn items() -> StreamedTemplate {
+///     StreamedTemplate::shell("layout/head", context! { title: "Items" })
+///         .then(async {
+///             let items = load_items().await;
+///             Template::render("items", context! { items })
+///         })
+///         .then(async { Template::render("layout/tail", context! {}) })
+/// }
+/// ```
+pub struct StreamedTemplate {
+    shell: Template,
+    sections: Vec<BoxFuture<'static, Template>>,
+}
+
+impl StreamedTemplate {
+    /// Creates a `StreamedTemplate` that first sends the template named `name`
+    /// rendered with `context`. See [`Template::render()`] for details on
+    /// `context`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_dyn_templates::{StreamedTemplate, context};
+    ///
+    /// let streamed = StreamedTemplate::shell("layout/head", context! {
+    ///     title: "Hello, world!",
+    /// });
+    /// ```
+    pub fn shell<S, C>(name: S, context: C) -> StreamedTemplate
+        where S: Into<Cow<'static, str>>, C: Serialize
+    {
+        StreamedTemplate { shell: Template::render(name, context), sections: vec![] }
+    }
+
+    /// Appends a deferred section to the stream. The future `section` is
+    /// awaited only after the shell and all previously added sections have been
+    /// sent; the template it resolves to is then rendered and sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_dyn_templates::{Template, StreamedTemplate, context};
+    ///
+    /// let streamed = StreamedTemplate::shell("layout/head", context! {})
+    ///     .then(async { Template::render("body", context! { rows: 3 }) });
+    /// ```
+    pub fn then<F>(mut self, section: F) -> StreamedTemplate
+        where F: Future<Output = Template> + Send + 'static
+    {
+        self.sections.push(Box::pin(section));
+        self
+    }
+}
+
+/// Returns a response with the Content-Type derived from the shell template's
+/// extension and an unsized body that yields the rendered shell followed by
+/// each rendered section. If the shell fails to render, an `Err` of
+/// `Status::InternalServerError` is returned.
+impl<'r> Responder<'r, 'r> for StreamedTemplate {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'r> {
+        let manager = req.rocket()
+            .state::<ContextManager>()
+            .ok_or_else(|| {
+                error!(
+                    "uninitialized template context: missing `Template::fairing()`.\n\
+                    To use templates, you must attach `Template::fairing()`."
+                );
+
+                Status::InternalServerError
+            })?;
+
+        let (content_type, shell) = self.shell.finalize(&manager.context())?;
+        let sections = stream::iter(self.sections)
+            .then(|section| section)
+            .map(|template| template.finalize(&manager.context()).map(|(_, s)| s))
+            .take_while(|rendered| std::future::ready(rendered.is_ok()))
+            .filter_map(|rendered| std::future::ready(rendered.ok()));
+
+        let body = stream::once(std::future::ready(shell))
+            .chain(sections)
+            .map(Cursor::new);
+
+        Response::build()
+            .header(content_type)
+            .streamed_body(ReaderStream::from(body))
+            .ok()
+    }
+}
+
+impl Sentinel for StreamedTemplate {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        <Template as Sentinel>::abort(rocket)
+    }
+}
+
+impl std::fmt::Debug for StreamedTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamedTemplate")
+            .field("shell", &self.shell)
+            .field("sections", &self.sections.len())
+            .finish()
+    }
+}
@@ -281,6 +281,41 @@ mod tera_tests {
         let response = client.get("/hbs/txt_test").dispatch();
         assert_eq!(response.status(), Status::NotFound);
     }
+
+    #[test]
+    fn test_streamed_template() {
+        use rocket::local::blocking::Client;
+        use rocket::tokio::sync::oneshot;
+        use rocket_dyn_templates::StreamedTemplate;
+
+        #[get("/stream")]
+        fn stream() -> StreamedTemplate {
+            let (tx, rx) = oneshot::channel();
+            std::thread::spawn(move || tx.send("<data>"));
+            StreamedTemplate::shell("tera/stream_shell", context! { title: "_test_" })
+                .then(async move {
+                    let content = rx.await.unwrap();
+                    Template::render("tera/stream_body", context! { content })
+                })
+                .then(async { Template::render("tera/missing", context! {}) })
+                .then(async { Template::render("tera/stream_body", context! { content: 1 }) })
+        }
+
+        #[get("/broken")]
+        fn broken() -> StreamedTemplate {
+            StreamedTemplate::shell("tera/missing", context! {})
+        }
+
+        let client = Client::debug(rocket().mount("/", routes![stream, broken])).unwrap();
+        let response = client.get("/stream").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        assert_eq!(response.into_string().unwrap(),
+            "<head><title>_test_</title></head>\n<main>&lt;data&gt;</main>\n");
+
+        let response = client.get("/broken").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+    }
 }
 
 #[cfg(feature = "handlebars")]
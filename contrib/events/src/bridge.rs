@@ -0,0 +1,103 @@
+use rocket::futures::stream::BoxStream;
+
+use crate::Error;
+
+/// A message broker that bridges events between instances of an application.
+///
+/// A bridge publishes encoded events to named topics and subscribes to them.
+/// Every instance subscribed to a topic receives every message published to
+/// it, including those published by the instance itself, which are ignored.
+/// Implementations are provided for NATS via `Nats` with the `nats` feature
+/// and Redis via `Redis` with the `redis` feature.
+#[rocket::async_trait]
+pub trait Bridge: Send + Sync + 'static {
+    /// Publishes `payload` to `topic`.
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<(), Error>;
+
+    /// Subscribes to `topic`, returning a stream of the payloads published
+    /// to it.
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, Vec<u8>>, Error>;
+}
+
+/// A bridge to a NATS server, selected by `nats://` URLs.
+#[cfg(feature = "nats")]
+#[derive(Debug, Clone)]
+pub struct Nats {
+    client: async_nats::Client,
+}
+
+#[cfg(feature = "nats")]
+impl Nats {
+    /// Connects to the NATS server at `url`.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        Ok(Nats { client: async_nats::connect(url).await? })
+    }
+
+    /// Creates a bridge from a connected NATS client.
+    pub fn new(client: async_nats::Client) -> Self {
+        Nats { client }
+    }
+}
+
+#[cfg(feature = "nats")]
+#[rocket::async_trait]
+impl Bridge for Nats {
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<(), Error> {
+        self.client.publish(topic.to_string(), payload.into()).await?;
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, Vec<u8>>, Error> {
+        use rocket::futures::StreamExt;
+
+        let subscriber = self.client.subscribe(topic.to_string()).await?;
+        Ok(subscriber.map(|message| message.payload.to_vec()).boxed())
+    }
+}
+
+/// A bridge to a Redis server's pub/sub channels, selected by `redis://` and
+/// `rediss://` URLs.
+#[cfg(feature = "redis")]
+#[derive(Clone)]
+pub struct Redis {
+    client: redis::Client,
+    connection: redis::aio::MultiplexedConnection,
+}
+
+#[cfg(feature = "redis")]
+impl Redis {
+    /// Connects to the Redis server at `url`.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        let client = redis::Client::open(url)?;
+        let connection = client.get_multiplexed_tokio_connection().await?;
+        Ok(Redis { client, connection })
+    }
+}
+
+#[cfg(feature = "redis")]
+#[rocket::async_trait]
+impl Bridge for Redis {
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<(), Error> {
+        use redis::AsyncCommands;
+
+        let mut connection = self.connection.clone();
+        connection.publish::<_, _, ()>(topic, payload).await?;
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, Vec<u8>>, Error> {
+        use rocket::futures::StreamExt;
+
+        let mut pubsub = self.client.get_async_pubsub().await?;
+        pubsub.subscribe(topic).await?;
+        let payloads = pubsub.into_on_message().map(|msg| msg.get_payload_bytes().to_vec());
+        Ok(payloads.boxed())
+    }
+}
+
+#[cfg(feature = "redis")]
+impl std::fmt::Debug for Redis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Redis").field("client", &self.client).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,237 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::collections::hash_map::RandomState;
+use std::sync::{Arc, RwLock};
+
+use rocket::futures::stream::{self, Stream};
+use rocket::http::Status;
+use rocket::outcome::IntoOutcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::tokio::sync::{broadcast, mpsc};
+
+use crate::Error;
+
+/// The default number of events buffered per event type.
+pub(crate) const DEFAULT_CAPACITY: usize = 1024;
+
+/// Serializes an event, given the bus's origin, for publishing to a bridge.
+pub(crate) type Encoder = Arc<dyn Fn(u64, &dyn Any) -> Result<Vec<u8>, Error> + Send + Sync>;
+
+/// An event encoded for the topic it is published to.
+pub(crate) type Outbound = (String, Vec<u8>);
+
+/// A process-local, typed event bus, available as managed state.
+///
+/// Events are values of any type that is `Clone + Send + Sync + 'static`. An
+/// event [published](Bus::publish()) to the bus is delivered to every current
+/// [subscriber](Bus::subscribe()) to events of its type. Each subscriber
+/// buffers up to a fixed number of events; a subscriber that falls behind
+/// misses the oldest events.
+///
+/// A `Bus` is typically managed by the [`Events`](crate::Events) fairing,
+/// which can additionally bridge selected event types to a message broker so
+/// that events are delivered to the subscribers of every instance of an
+/// application. It can be retrieved in handlers as a request guard or via
+/// `&State<Bus>`. Cloning a `Bus` is cheap.
+///
+/// # Example
+///
+/// ```rust
+/// # rocket::async_test(async {
+/// use rocket_events::Bus;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct UserCreated(u64);
+///
+/// let bus = Bus::new();
+/// let mut subscription = bus.subscribe::<UserCreated>();
+/// assert_eq!(bus.publish(UserCreated(7)), 1);
+/// assert_eq!(subscription.recv().await, Some(UserCreated(7)));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Bus {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    origin: u64,
+    capacity: usize,
+    channels: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+    encoders: HashMap<TypeId, (String, Encoder)>,
+    outbound: Option<mpsc::UnboundedSender<Outbound>>,
+}
+
+/// A subscription to events of type `E` on a [`Bus`].
+///
+/// Created by [`Bus::subscribe()`]. Receives events published after it was
+/// created until it is dropped.
+pub struct Subscription<E> {
+    receiver: broadcast::Receiver<E>,
+}
+
+impl Bus {
+    /// Creates a new, process-local bus buffering up to 1024 events per
+    /// subscriber.
+    pub fn new() -> Self {
+        Bus::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Creates a new, process-local bus buffering up to `capacity` events per
+    /// subscriber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Bus::bridged(capacity, HashMap::new(), None)
+    }
+
+    pub(crate) fn bridged(
+        capacity: usize,
+        encoders: HashMap<TypeId, (String, Encoder)>,
+        outbound: Option<mpsc::UnboundedSender<Outbound>>,
+    ) -> Self {
+        assert!(capacity > 0, "event bus capacity must be non-zero");
+        let origin = RandomState::new().build_hasher().finish();
+        let channels = RwLock::new(HashMap::new());
+        Bus { inner: Arc::new(Inner { origin, capacity, channels, encoders, outbound }) }
+    }
+
+    /// A random identifier for this bus, used to ignore bridged events it
+    /// published itself.
+    pub(crate) fn origin(&self) -> u64 {
+        self.inner.origin
+    }
+
+    fn sender<E: Clone + Send + Sync + 'static>(&self) -> broadcast::Sender<E> {
+        let id = TypeId::of::<E>();
+        let channels = self.inner.channels.read().expect("channels lock");
+        if let Some(sender) = channels.get(&id).and_then(|s| s.downcast_ref()) {
+            return broadcast::Sender::clone(sender);
+        }
+
+        drop(channels);
+        let mut channels = self.inner.channels.write().expect("channels lock");
+        channels.entry(id)
+            .or_insert_with(|| Box::new(broadcast::channel::<E>(self.inner.capacity).0))
+            .downcast_ref::<broadcast::Sender<E>>()
+            .expect("channel type")
+            .clone()
+    }
+
+    /// Publishes `event` to all subscribers to events of type `E`, returning
+    /// the number of local subscribers it was delivered to.
+    ///
+    /// If `E` is bridged by the [`Events`](crate::Events) fairing, `event` is
+    /// also published to the bridge, in the background, for delivery to the
+    /// subscribers of other instances.
+    pub fn publish<E: Clone + Send + Sync + 'static>(&self, event: E) -> usize {
+        let encoder = self.inner.encoders.get(&TypeId::of::<E>());
+        if let (Some((topic, encode)), Some(outbound)) = (encoder, &self.inner.outbound) {
+            match encode(self.inner.origin, &event) {
+                Ok(payload) => { let _ = outbound.send((topic.clone(), payload)); }
+                Err(e) => warn!(%topic, "failed to encode bridged event: {e}"),
+            }
+        }
+
+        self.deliver(event)
+    }
+
+    /// Delivers `event` to local subscribers only.
+    pub(crate) fn deliver<E: Clone + Send + Sync + 'static>(&self, event: E) -> usize {
+        self.sender::<E>().send(event).unwrap_or(0)
+    }
+
+    /// Subscribes to events of type `E`.
+    pub fn subscribe<E: Clone + Send + Sync + 'static>(&self) -> Subscription<E> {
+        Subscription { receiver: self.sender::<E>().subscribe() }
+    }
+
+    /// Returns the number of local subscribers to events of type `E`.
+    pub fn subscribers<E: Clone + Send + Sync + 'static>(&self) -> usize {
+        self.sender::<E>().receiver_count()
+    }
+}
+
+impl<E: Clone + Send + 'static> Subscription<E> {
+    /// Waits for and returns the next event. Events missed because the
+    /// subscription fell behind are skipped. Returns `None` once the bus has
+    /// been dropped.
+    pub async fn recv(&mut self) -> Option<E> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!(skipped = n, "event subscription fell behind");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Converts the subscription into a stream of events, as returned by
+    /// [`Subscription::recv()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::stream::{Event, EventStream};
+    /// use rocket::serde::Serialize;
+    /// use rocket_events::Bus;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// #[serde(crate = "rocket::serde")]
+    /// struct Message(String);
+    ///
+    /// #[get("/messages")]
+    /// fn messages(bus: Bus) -> EventStream![] {
+    ///     let events = bus.subscribe::<Message>().into_stream();
+    ///     EventStream! {
+    ///         for await message in events {
+    ///             yield Event::json(&message);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn into_stream(self) -> impl Stream<Item = E> + Send {
+        stream::unfold(self, |mut subscription| async move {
+            let event = subscription.recv().await?;
+            Some((event, subscription))
+        })
+    }
+}
+
+impl Default for Bus {
+    fn default() -> Self {
+        Bus::new()
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Bus {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.rocket().state::<Bus>()
+            .cloned()
+            .or_error((Status::InternalServerError, ()))
+    }
+}
+
+impl std::fmt::Debug for Bus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bus")
+            .field("capacity", &self.inner.capacity)
+            .field("bridged", &self.inner.encoders.values().map(|(t, _)| t).collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> std::fmt::Debug for Subscription<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription").finish_non_exhaustive()
+    }
+}
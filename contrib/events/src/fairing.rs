@@ -0,0 +1,276 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rocket::{Rocket, Build, Orbit};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::futures::stream::{self, StreamExt};
+use rocket::serde::{Serialize, Deserialize, DeserializeOwned};
+use rocket::serde::json::{self, serde_json};
+use rocket::tokio::{self, sync::mpsc, task::JoinHandle};
+
+use crate::{Bus, Bridge, Error};
+use crate::bus::{Encoder, Outbound, DEFAULT_CAPACITY};
+
+/// Deserializes a bridged event and delivers it to local subscribers if it
+/// was published by another instance.
+type Decoder = Arc<dyn Fn(&Bus, &[u8]) -> Result<(), Error> + Send + Sync>;
+
+/// An event as published to a bridge.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Envelope<E> {
+    origin: u64,
+    event: E,
+}
+
+struct Bridged {
+    topic: String,
+    type_id: TypeId,
+    encode: Encoder,
+    decode: Decoder,
+}
+
+/// A fairing that manages an event [`Bus`] and optionally bridges events to a
+/// message broker.
+///
+/// On ignition, the fairing places a [`Bus`] in managed state. Event types
+/// registered via [`Events::bridge()`] are additionally published to a topic
+/// of a [`Bridge`] as JSON, and events received from the bridge are delivered
+/// to local subscribers, so that subscribers of every instance of an
+/// application receive every event. The bridge is either set explicitly via
+/// [`Events::with_bridge()`] or connected to on ignition using the `url` in
+/// the `events` table of the application's configuration:
+///
+/// ```toml
+/// [default.events]
+/// url = "nats://localhost:4222"
+/// capacity = 1024
+/// ```
+///
+/// URLs with a `nats` scheme require the `nats` feature; URLs with a `redis`
+/// or `rediss` scheme require the `redis` feature. Without a bridge, events
+/// are only delivered locally. Bridging runs from liftoff until shutdown.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::{Serialize, Deserialize};
+/// use rocket_events::{Events, Bus};
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct ChatMessage {
+///     room: String,
+///     text: String,
+/// }
+///
+/// #[post("/rooms/<room>", data = "<text>")]
+/// fn post(room: &str, text: String, bus: Bus) {
+///     bus.publish(ChatMessage { room: room.into(), text });
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![post])
+///         .attach(Events::new().bridge::<ChatMessage>("chat"))
+/// }
+/// ```
+#[derive(Default)]
+pub struct Events {
+    capacity: Option<usize>,
+    bridge: Option<Arc<dyn Bridge>>,
+    bridged: Vec<Bridged>,
+    pending: Mutex<Option<(Arc<dyn Bridge>, mpsc::UnboundedReceiver<Outbound>)>>,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+/// The `events` configuration table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Config {
+    url: Option<String>,
+    capacity: Option<usize>,
+}
+
+impl Events {
+    /// Creates a new fairing with no bridged event types.
+    pub fn new() -> Self {
+        Events::default()
+    }
+
+    /// Sets the number of events buffered per subscriber to `capacity`,
+    /// overriding the configured `capacity`. The default is `1024`.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Uses `bridge` instead of connecting to the configured `url`.
+    pub fn with_bridge<B: Bridge>(mut self, bridge: B) -> Self {
+        self.bridge = Some(Arc::new(bridge));
+        self
+    }
+
+    /// Bridges events of type `E` via the topic `topic`. Each bridged type
+    /// must have its own topic.
+    pub fn bridge<E>(mut self, topic: &str) -> Self
+        where E: Serialize + DeserializeOwned + Clone + Send + Sync + 'static
+    {
+        let encode: Encoder = Arc::new(|origin: u64, event: &dyn Any| -> Result<Vec<u8>, Error> {
+            let event = event.downcast_ref::<E>().expect("bridged event type");
+            Ok(serde_json::to_vec(&Envelope { origin, event })?)
+        });
+
+        let decode: Decoder = Arc::new(|bus: &Bus, payload: &[u8]| -> Result<(), Error> {
+            let envelope: Envelope<E> = json::from_slice(payload)?;
+            if envelope.origin != bus.origin() {
+                bus.deliver(envelope.event);
+            }
+
+            Ok(())
+        });
+
+        self.bridged.push(Bridged {
+            topic: topic.into(),
+            type_id: TypeId::of::<E>(),
+            encode,
+            decode,
+        });
+
+        self
+    }
+
+    async fn connect(&self, url: &str) -> Result<Arc<dyn Bridge>, Error> {
+        let scheme = url.split_once("://").map(|(scheme, _)| scheme);
+        match scheme {
+            #[cfg(feature = "nats")]
+            Some("nats" | "tls") => Ok(Arc::new(crate::Nats::connect(url).await?)),
+            #[cfg(feature = "redis")]
+            Some("redis" | "rediss") => Ok(Arc::new(crate::Redis::connect(url).await?)),
+            _ => Err(format!("unsupported event bridge url: {url}").into()),
+        }
+    }
+
+    async fn resolve(&self, config: &Config) -> Result<Option<Arc<dyn Bridge>>, Error> {
+        match (&self.bridge, &config.url) {
+            (Some(bridge), _) => Ok(Some(bridge.clone())),
+            (None, Some(url)) => self.connect(url).await.map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Events {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Liftoff | Kind::Shutdown, name: "Events" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        for (i, bridged) in self.bridged.iter().enumerate() {
+            if self.bridged[..i].iter().any(|b| b.topic == bridged.topic) {
+                error!(topic = %bridged.topic, "duplicate event bridge topic");
+                return Err(rocket);
+            }
+        }
+
+        let config = match rocket.figment().extract_inner::<Config>("events") {
+            Ok(config) => config,
+            Err(e) if e.missing() => Config::default(),
+            Err(e) => {
+                error!("invalid events configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let bridge = match self.resolve(&config).await {
+            Ok(bridge) => bridge,
+            Err(e) => {
+                error!("failed to connect event bridge: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let capacity = self.capacity.or(config.capacity).unwrap_or(DEFAULT_CAPACITY);
+        if capacity == 0 {
+            error!("event bus capacity must be non-zero");
+            return Err(rocket);
+        }
+
+        let bus = match bridge {
+            Some(bridge) => {
+                let (sender, receiver) = mpsc::unbounded_channel();
+                *self.pending.lock().expect("pending lock") = Some((bridge, receiver));
+                let encoders = self.bridged.iter()
+                    .map(|b| (b.type_id, (b.topic.clone(), b.encode.clone())))
+                    .collect::<HashMap<_, _>>();
+
+                Bus::bridged(capacity, encoders, Some(sender))
+            }
+            None => Bus::with_capacity(capacity),
+        };
+
+        Ok(rocket.manage(bus))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let Some(bus) = rocket.state::<Bus>() else { return };
+        let Some((bridge, mut outbound)) = self.pending.lock().expect("pending lock").take() else {
+            return;
+        };
+
+        let mut tasks = Vec::with_capacity(self.bridged.len() + 1);
+        let (publisher, shutdown) = (bridge.clone(), rocket.shutdown());
+        tasks.push(tokio::spawn(async move {
+            let events = stream::poll_fn(|cx| outbound.poll_recv(cx)).take_until(shutdown);
+            let mut events = std::pin::pin!(events);
+            while let Some((topic, payload)) = events.next().await {
+                if let Err(e) = publisher.publish(&topic, payload).await {
+                    warn!(%topic, "failed to publish bridged event: {e}");
+                }
+            }
+        }));
+
+        for bridged in &self.bridged {
+            let payloads = match bridge.subscribe(&bridged.topic).await {
+                Ok(payloads) => payloads,
+                Err(e) => {
+                    error!(topic = %bridged.topic, "failed to subscribe to event bridge: {e}");
+                    continue;
+                }
+            };
+
+            let (bus, decode, topic) = (bus.clone(), bridged.decode.clone(), bridged.topic.clone());
+            let shutdown = rocket.shutdown();
+            tasks.push(tokio::spawn(async move {
+                let mut payloads = std::pin::pin!(payloads.take_until(shutdown));
+                while let Some(payload) = payloads.next().await {
+                    if let Err(e) = decode(&bus, &payload) {
+                        warn!(%topic, "failed to decode bridged event: {e}");
+                    }
+                }
+            }));
+        }
+
+        self.tasks.lock().expect("tasks lock").extend(tasks);
+    }
+
+    async fn on_shutdown(&self, _: &Rocket<Orbit>) {
+        let tasks = std::mem::take(&mut *self.tasks.lock().expect("tasks lock"));
+        for task in tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for Events {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Events")
+            .field("capacity", &self.capacity)
+            .field("bridged", &self.bridged.iter().map(|b| &b.topic).collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,91 @@
+//! Typed publish/subscribe events for Rocket.
+//!
+//! This crate provides a process-local, typed event [`Bus`]: values of any
+//! `Clone + Send + Sync + 'static` type are [published](Bus::publish()) to the
+//! bus and delivered to every [subscriber](Bus::subscribe()) to that type. The
+//! [`Events`] fairing places a `Bus` in managed state and can bridge selected
+//! event types to a message broker, such as NATS or Redis, so that events
+//! published by one instance of an application are delivered to the
+//! subscribers of every instance, for instance, to fan chat messages out to
+//! WebSocket clients connected to different servers.
+//!
+//! # Usage
+//!
+//! Depend on the crate, enabling the `nats` or `redis` feature to bridge
+//! events to a broker:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_events = { version = "0.1.0", features = ["nats"] }
+//! ```
+//!
+//! Then attach the [`Events`] fairing, registering the event types to bridge,
+//! and configure the broker's URL in the `events` table of `Rocket.toml`:
+//!
+//! ```toml
+//! [default.events]
+//! url = "nats://localhost:4222"
+//! ```
+//!
+//! Finally, publish and subscribe to events via the [`Bus`] request guard:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::response::stream::{Event, EventStream};
+//! use rocket::serde::{Serialize, Deserialize};
+//! use rocket_events::{Events, Bus};
+//!
+//! #[derive(Debug, Clone, Serialize, Deserialize)]
+//! #[serde(crate = "rocket::serde")]
+//! struct Message {
+//!     room: String,
+//!     text: String,
+//! }
+//!
+//! #[post("/rooms/<room>", data = "<text>")]
+//! fn post(room: &str, text: String, bus: Bus) {
+//!     bus.publish(Message { room: room.into(), text });
+//! }
+//!
+//! #[get("/rooms/<room>")]
+//! fn listen(room: &str, bus: Bus) -> EventStream![Event + '_] {
+//!     let mut messages = bus.subscribe::<Message>();
+//!     EventStream! {
+//!         while let Some(message) = messages.recv().await {
+//!             if message.room == room {
+//!                 yield Event::json(&message);
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![post, listen])
+//!         .attach(Events::new().bridge::<Message>("messages"))
+//! }
+//! ```
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_events")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod bridge;
+mod bus;
+mod fairing;
+
+pub use bus::{Bus, Subscription};
+pub use bridge::Bridge;
+pub use fairing::Events;
+
+#[cfg(feature = "nats")]
+pub use bridge::Nats;
+
+#[cfg(feature = "redis")]
+pub use bridge::Redis;
+
+/// An error from a [`Bridge`] or while encoding or decoding bridged events.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::{Rocket, Build};
+use rocket::futures::stream::{self, BoxStream, StreamExt};
+use rocket::local::asynchronous::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::tokio::{sync::broadcast, time::timeout};
+use rocket_events::{Bus, Bridge, Events, Error};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Ping(u32);
+
+#[derive(Debug, Clone, PartialEq)]
+struct Local(u32);
+
+/// A bridge delivering messages between instances in the same process.
+#[derive(Clone, Default)]
+struct Loopback {
+    topics: Arc<Mutex<HashMap<String, broadcast::Sender<Vec<u8>>>>>,
+}
+
+impl Loopback {
+    fn topic(&self, topic: &str) -> broadcast::Sender<Vec<u8>> {
+        self.topics.lock().unwrap()
+            .entry(topic.into())
+            .or_insert_with(|| broadcast::channel(16).0)
+            .clone()
+    }
+}
+
+#[rocket::async_trait]
+impl Bridge for Loopback {
+    async fn publish(&self, topic: &str, payload: Vec<u8>) -> Result<(), Error> {
+        let _ = self.topic(topic).send(payload);
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str) -> Result<BoxStream<'static, Vec<u8>>, Error> {
+        let receiver = self.topic(topic).subscribe();
+        let payloads = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.ok().map(|payload| (payload, receiver))
+        });
+
+        Ok(payloads.boxed())
+    }
+}
+
+fn rocket(bridge: Loopback) -> Rocket<Build> {
+    rocket::build().attach(Events::new().with_bridge(bridge).bridge::<Ping>("ping"))
+}
+
+#[rocket::async_test]
+async fn local_events_are_delivered() {
+    let bus = Bus::new();
+    let (mut a, mut b) = (bus.subscribe::<Local>(), bus.subscribe::<Local>());
+    let mut pings = bus.subscribe::<Ping>();
+    assert_eq!(bus.subscribers::<Local>(), 2);
+
+    assert_eq!(bus.publish(Local(1)), 2);
+    assert_eq!(a.recv().await, Some(Local(1)));
+    assert_eq!(b.recv().await, Some(Local(1)));
+
+    bus.publish(Ping(2));
+    assert_eq!(pings.recv().await, Some(Ping(2)));
+
+    drop(a);
+    assert_eq!(bus.publish(Local(3)), 1);
+}
+
+#[rocket::async_test]
+async fn lagging_subscribers_skip_events() {
+    let bus = Bus::with_capacity(2);
+    let mut subscription = bus.subscribe::<Local>();
+    for i in 0..5 {
+        bus.publish(Local(i));
+    }
+
+    assert_eq!(subscription.recv().await, Some(Local(3)));
+    assert_eq!(subscription.recv().await, Some(Local(4)));
+}
+
+#[rocket::async_test]
+async fn bridged_events_reach_other_instances() {
+    let bridge = Loopback::default();
+    let a = Client::tracked(rocket(bridge.clone())).await.unwrap();
+    let b = Client::tracked(rocket(bridge.clone())).await.unwrap();
+    let (bus_a, bus_b) = (a.rocket().state::<Bus>().unwrap(), b.rocket().state::<Bus>().unwrap());
+
+    let (mut pings_a, mut pings_b) = (bus_a.subscribe::<Ping>(), bus_b.subscribe::<Ping>());
+    let mut locals_b = bus_b.subscribe::<Local>();
+
+    bus_a.publish(Ping(1));
+    bus_a.publish(Local(1));
+    assert_eq!(pings_a.recv().await, Some(Ping(1)));
+    let ping = timeout(Duration::from_secs(5), pings_b.recv()).await;
+    assert_eq!(ping.unwrap(), Some(Ping(1)));
+
+    // Neither the publisher nor unbridged types see duplicates.
+    assert!(timeout(Duration::from_millis(100), pings_a.recv()).await.is_err());
+    assert!(timeout(Duration::from_millis(100), locals_b.recv()).await.is_err());
+}
+
+#[rocket::async_test]
+async fn invalid_bridges_abort_ignition() {
+    let rocket = rocket::build().attach(Events::new());
+    assert!(rocket.ignite().await.unwrap().state::<Bus>().is_some());
+
+    let figment = rocket::Config::figment().merge(("events.url", "carrier-pigeon://coop"));
+    let rocket = rocket::custom(figment).attach(Events::new());
+    assert!(rocket.ignite().await.is_err());
+
+    let figment = rocket::Config::figment().merge(("events.capacity", 0));
+    let rocket = rocket::custom(figment).attach(Events::new());
+    assert!(rocket.ignite().await.is_err());
+
+    let rocket = rocket::build()
+        .attach(Events::new().bridge::<Ping>("ping").bridge::<Ping>("ping"));
+    assert!(rocket.ignite().await.is_err());
+}
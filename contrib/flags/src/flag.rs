@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use rocket::http::HeaderMap;
+use rocket::serde::{Deserialize, Serialize};
+
+/// The definition of a feature flag.
+///
+/// A flag is evaluated for a _subject_: the user a request is made by, if
+/// known, and the request's headers. See [`Flag::evaluate()`] for the rules.
+///
+/// Flags are typically deserialized from the application's configuration:
+///
+/// ```toml
+/// [default.flags.new_checkout]
+/// enabled = true
+/// rollout = 25
+/// users = ["alice", "bob"]
+///
+/// [default.flags.new_checkout.headers]
+/// X-Beta = ["1", "true"]
+/// ```
+///
+/// They can also be built programmatically:
+///
+/// ```rust
+/// use rocket_flags::Flag;
+///
+/// let flag = Flag::on()
+///     .with_rollout(25.0)
+///     .with_user("alice")
+///     .with_header("X-Beta", "1");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Flag {
+    /// Whether the flag is enabled at all. A disabled flag is off for every
+    /// subject. Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// The percentage, from `0` to `100`, of users the flag is on for.
+    #[serde(default)]
+    pub rollout: Option<f64>,
+    /// The IDs of users the flag is always on for.
+    #[serde(default)]
+    pub users: Vec<String>,
+    /// Header names mapped to values. The flag is always on for requests with
+    /// any of the values in the named header.
+    #[serde(default)]
+    pub headers: BTreeMap<String, Vec<String>>,
+}
+
+impl Flag {
+    /// Returns an enabled flag that is on for every subject.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::HeaderMap;
+    /// use rocket_flags::Flag;
+    ///
+    /// assert!(Flag::on().evaluate("flag", None, &HeaderMap::new()));
+    /// ```
+    pub fn on() -> Flag {
+        Flag { enabled: true, ..Flag::default() }
+    }
+
+    /// Returns a disabled flag that is off for every subject.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::HeaderMap;
+    /// use rocket_flags::Flag;
+    ///
+    /// assert!(!Flag::off().evaluate("flag", Some("alice"), &HeaderMap::new()));
+    /// ```
+    pub fn off() -> Flag {
+        Flag::default()
+    }
+
+    /// Sets the percentage of users the flag is on for to `percent`.
+    pub fn with_rollout(mut self, percent: f64) -> Self {
+        self.rollout = Some(percent);
+        self
+    }
+
+    /// Adds `id` to the users the flag is always on for.
+    pub fn with_user(mut self, id: impl Into<String>) -> Self {
+        self.users.push(id.into());
+        self
+    }
+
+    /// Turns the flag on for requests with a `name` header of `value`.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.entry(name.into()).or_default().push(value.into());
+        self
+    }
+
+    /// Evaluates the flag named `name` for the user with ID `user`, if any,
+    /// making a request with `headers`. Returns `true` if the flag is on.
+    ///
+    /// The rules are, in order:
+    ///
+    ///   1. If the flag is not `enabled`, it is off.
+    ///   2. If `user` is in `users`, the flag is on.
+    ///   3. If any of `headers` has a value in `headers`, the flag is on.
+    ///   4. If a `rollout` is set, the flag is on if the user falls in the
+    ///      rollout percentage. Users are assigned to a percentile by hashing
+    ///      `name` and `user`, so a given user is consistently in or out of a
+    ///      rollout, and increasing the rollout only ever adds users. Without a
+    ///      `user`, the flag is on only if the rollout is `100`.
+    ///   5. Otherwise, the flag is on if it has no targeting, that is, no
+    ///      `users` and no `headers`, and off if it does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Header, HeaderMap};
+    /// use rocket_flags::Flag;
+    ///
+    /// let flag = Flag::on().with_user("alice").with_header("X-Beta", "1");
+    /// let mut headers = HeaderMap::new();
+    /// assert!(flag.evaluate("beta", Some("alice"), &headers));
+    /// assert!(!flag.evaluate("beta", Some("bob"), &headers));
+    ///
+    /// headers.add(Header::new("X-Beta", "1"));
+    /// assert!(flag.evaluate("beta", Some("bob"), &headers));
+    /// ```
+    pub fn evaluate(&self, name: &str, user: Option<&str>, headers: &HeaderMap<'_>) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        if user.map_or(false, |user| self.users.iter().any(|id| id == user)) {
+            return true;
+        }
+
+        let header_match = self.headers.iter().any(|(header, values)| {
+            headers.get(header).any(|value| values.iter().any(|v| v == value))
+        });
+
+        if header_match {
+            return true;
+        }
+
+        match self.rollout {
+            Some(percent) => match user {
+                Some(user) => percentile(name, user) < percent,
+                None => percent >= 100.0,
+            },
+            None => self.users.is_empty() && self.headers.is_empty(),
+        }
+    }
+}
+
+/// Returns the stable percentile, in `[0, 100)`, of `user` for flag `name`.
+fn percentile(name: &str, user: &str) -> f64 {
+    // FNV-1a: stable across processes and releases, unlike `DefaultHasher`.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in name.bytes().chain([0]).chain(user.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    (hash % 10_000) as f64 / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollouts_are_stable_and_monotonic() {
+        let users: Vec<String> = (0..1000).map(|i| format!("user-{i}")).collect();
+        let on = |percent: f64| -> Vec<&str> {
+            let flag = Flag::on().with_rollout(percent);
+            users.iter()
+                .map(|u| u.as_str())
+                .filter(|u| flag.evaluate("flag", Some(*u), &HeaderMap::new()))
+                .collect()
+        };
+
+        let (ten, fifty) = (on(10.0), on(50.0));
+        assert!(ten.iter().all(|u| fifty.contains(u)));
+        assert!((50..150).contains(&ten.len()), "{}", ten.len());
+        assert!((400..600).contains(&fifty.len()), "{}", fifty.len());
+        assert_eq!(on(10.0), ten);
+        assert!(on(0.0).is_empty());
+        assert_eq!(on(100.0).len(), users.len());
+    }
+
+    #[test]
+    fn targeting_limits_flags_to_targets() {
+        let flag = Flag::on().with_user("alice");
+        assert!(flag.evaluate("flag", Some("alice"), &HeaderMap::new()));
+        assert!(!flag.evaluate("flag", Some("bob"), &HeaderMap::new()));
+        assert!(!flag.evaluate("flag", None, &HeaderMap::new()));
+        assert!(!Flag::off().with_user("alice").evaluate("f", Some("alice"), &HeaderMap::new()));
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use rocket::http::{HeaderMap, Status};
+use rocket::outcome::{try_outcome, IntoOutcome};
+use rocket::request::{self, FromRequest, Outcome, Request};
+use rocket::serde::{Serialize, Serializer};
+
+use crate::{Definitions, FlagsFairing, Registry};
+
+/// The user a request is made by, for the purposes of flag evaluation.
+///
+/// An authenticating fairing or guard inserts a `User` into the request's
+/// [extensions](rocket::Request::extensions()) to identify the user flags are
+/// evaluated for. Without one, the user is identified by the header configured
+/// via [`FlagsFairing::user_header()`], if any.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::fairing::AdHoc;
+/// use rocket_flags::User;
+///
+/// # fn authenticate(_: &rocket::Request<'_>) -> Option<String> { None }
+/// let auth = AdHoc::on_request("Authenticate", |req, _| Box::pin(async move {
+///     if let Some(id) = authenticate(req) {
+///         req.extensions().insert(User(id));
+///     }
+/// }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User(pub String);
+
+/// A request guard for evaluating feature flags.
+///
+/// `Flags` evaluates flags, as described in [`Flag::evaluate()`], for the
+/// request's [`User`] and headers. All `Flags` guards in one request evaluate
+/// flags against the same definitions, even if definitions change mid-request.
+/// The guard fails with `500 Internal Server Error` if the fairing returned by
+/// [`Flags::fairing()`] is not attached.
+///
+/// [`Flag::evaluate()`]: crate::Flag::evaluate()
+///
+/// # Templates
+///
+/// `Flags` serializes as a map from the name of every defined flag to whether
+/// it is on, so it can be passed to templates directly. With
+/// `rocket_dyn_templates`, for instance, a template rendered with
+/// `context! { flags }` can check `{% if flags.new_checkout %}`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::Serialize;
+/// use rocket_flags::Flags;
+///
+/// #[derive(Serialize)]
+/// #[serde(crate = "rocket::serde")]
+/// struct Context<'r> {
+///     flags: Flags<'r>,
+/// }
+///
+/// #[get("/")]
+/// fn index(flags: Flags<'_>) -> String {
+///     let context = Context { flags };
+///     /* render a template with `context` */
+///     # format!("{:?}", context.flags)
+/// }
+/// ```
+pub struct Flags<'r> {
+    definitions: &'r Definitions,
+    user: Option<&'r str>,
+    headers: &'r HeaderMap<'r>,
+}
+
+/// The definitions a request evaluates flags against.
+struct Snapshot(Arc<Definitions>);
+
+/// A flag that can be named in a type, for use with [`Enabled`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_flags::FlagName;
+///
+/// struct NewCheckout;
+///
+/// impl FlagName for NewCheckout {
+///     const NAME: &'static str = "new_checkout";
+/// }
+/// ```
+pub trait FlagName: Send + Sync + 'static {
+    /// The name of the flag.
+    const NAME: &'static str;
+}
+
+/// A request guard that succeeds only if the flag `F` is on.
+///
+/// If the flag is off, the guard forwards with `404 Not Found`. This allows
+/// gating an entire route on a flag and falling back to a lower-ranked route:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_flags::{Enabled, FlagName};
+///
+/// struct NewCheckout;
+///
+/// impl FlagName for NewCheckout {
+///     const NAME: &'static str = "new_checkout";
+/// }
+///
+/// #[get("/checkout")]
+/// fn new_checkout(_flag: Enabled<NewCheckout>) -> &'static str {
+///     "the new checkout"
+/// }
+///
+/// #[get("/checkout", rank = 2)]
+/// fn checkout() -> &'static str {
+///     "the old checkout"
+/// }
+/// ```
+pub struct Enabled<F: FlagName>(PhantomData<F>);
+
+impl Flags<'_> {
+    /// Returns a fairing that places a [`Registry`] of flag definitions in
+    /// managed state. See [`FlagsFairing`] for details and options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_flags::Flags;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Flags::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> FlagsFairing {
+        FlagsFairing::new()
+    }
+}
+
+impl<'r> Flags<'r> {
+    /// Returns `true` if the flag named `name` is on for this request. A flag
+    /// without a definition is off.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.definitions.get(name)
+            .map_or(false, |flag| flag.evaluate(name, self.user, self.headers))
+    }
+
+    /// Returns the names of the flags that are on for this request, in no
+    /// particular order.
+    pub fn enabled(&self) -> impl Iterator<Item = &'r str> + '_ {
+        self.definitions.keys()
+            .map(|name| name.as_str())
+            .filter(|name| self.is_enabled(name))
+    }
+
+    /// Returns the ID of the user flags are evaluated for, if known.
+    pub fn user(&self) -> Option<&'r str> {
+        self.user
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Flags<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let registry = try_outcome!(req.rocket().state::<Registry>()
+            .or_error((Status::InternalServerError, ())));
+
+        let snapshot = req.local_cache(|| Snapshot(registry.snapshot()));
+        let user = req.extensions().get::<User>()
+            .map(|user| user.0.as_str())
+            .or_else(|| req.headers().get_one(registry.inner.user_header.as_deref()?));
+
+        Outcome::Success(Flags { definitions: &snapshot.0, user, headers: req.headers() })
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, F: FlagName> FromRequest<'r> for Enabled<F> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let flags = try_outcome!(Flags::from_request(req).await);
+        match flags.is_enabled(F::NAME) {
+            true => Outcome::Success(Enabled(PhantomData)),
+            false => Outcome::Forward(Status::NotFound),
+        }
+    }
+}
+
+impl Serialize for Flags<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let flags: BTreeMap<&str, bool> = self.definitions.keys()
+            .map(|name| (name.as_str(), self.is_enabled(name)))
+            .collect();
+
+        flags.serialize(serializer)
+    }
+}
+
+impl std::fmt::Debug for Flags<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flags")
+            .field("user", &self.user)
+            .field("enabled", &self.enabled().collect::<Vec<_>>())
+            .finish()
+    }
+}
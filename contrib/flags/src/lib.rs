@@ -0,0 +1,85 @@
+//! Feature flags for Rocket.
+//!
+//! This crate provides feature flags: named switches, defined outside of the
+//! application's code, that gate application behavior per request. Flags can
+//! be rolled out to a percentage of users and targeted at specific users or
+//! requests with specific headers, making them suitable both for gradual
+//! rollouts and for experiments.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_flags = "0.1.0"
+//! ```
+//!
+//! Then define flags in the `flags` table of `Rocket.toml`. Each flag is a
+//! [`Flag`]. Below, `new_checkout` is on for 25% of users, for `alice`, and
+//! for all requests with an `X-Beta: 1` header:
+//!
+//! ```toml
+//! [default.flags.new_checkout]
+//! enabled = true
+//! rollout = 25
+//! users = ["alice"]
+//!
+//! [default.flags.new_checkout.headers]
+//! X-Beta = ["1"]
+//! ```
+//!
+//! Finally, attach the fairing returned by [`Flags::fairing()`] and evaluate
+//! flags via the [`Flags`] request guard or gate entire routes via the
+//! [`Enabled`] request guard:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_flags::Flags;
+//!
+//! #[get("/checkout")]
+//! fn checkout(flags: Flags<'_>) -> &'static str {
+//!     if flags.is_enabled("new_checkout") {
+//!         "the new checkout"
+//!     } else {
+//!         "the old checkout"
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![checkout])
+//!         .attach(Flags::fairing())
+//! }
+//! ```
+//!
+//! # Users
+//!
+//! Percentage rollouts and user targeting require knowing the user a request
+//! is made by. An authenticating fairing identifies the user by inserting a
+//! [`User`] into the request's [extensions](rocket::Request::extensions()).
+//! Alternatively, when an authenticating proxy sets a header with the user's
+//! ID, configure the header via [`FlagsFairing::user_header()`].
+//!
+//! # Providers
+//!
+//! To load definitions from somewhere other than the configuration, such as a
+//! feature flag service, implement [`Provider`] and configure it via
+//! [`FlagsFairing::provider()`]. Definitions can be periodically reloaded via
+//! [`FlagsFairing::refresh()`] and changed at runtime via the [`Registry`] in
+//! managed state.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_flags")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod flag;
+mod guard;
+mod registry;
+
+pub use flag::Flag;
+pub use guard::{Flags, User, Enabled, FlagName};
+pub use registry::{Registry, FlagsFairing, Provider, Definitions, Error};
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Orbit};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::tokio;
+
+use crate::Flag;
+
+/// Flag definitions, keyed by flag name.
+pub type Definitions = HashMap<String, Flag>;
+
+/// The error type returned by a [`Provider`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A source of flag definitions, such as a feature flag service.
+///
+/// By default, [`Flags::fairing()`](crate::Flags::fairing()) reads definitions
+/// from the `flags` table of the application's configuration. A provider
+/// instead loads them from elsewhere, for instance, from a feature flag
+/// service's API. Providers are configured via [`FlagsFairing::provider()`].
+/// Definitions are loaded once at ignition and then, optionally, periodically
+/// via [`FlagsFairing::refresh()`] or on demand via [`Registry::reload()`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_flags::{Provider, Definitions, Error, Flag};
+///
+/// struct Service { url: String }
+///
+/// #[rocket::async_trait]
+/// impl Provider for Service {
+///     async fn load(&self) -> Result<Definitions, Error> {
+///         /* fetch definitions from `self.url` */
+///         # let _ = &self.url;
+///         let mut definitions = Definitions::new();
+///         definitions.insert("new_checkout".into(), Flag::on().with_rollout(10.0));
+///         Ok(definitions)
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait Provider: Send + Sync + 'static {
+    /// Loads the current flag definitions.
+    async fn load(&self) -> Result<Definitions, Error>;
+}
+
+/// The flag definitions in use by an application, available as managed state.
+///
+/// The `Registry` is placed in managed state by the fairing returned from
+/// [`Flags::fairing()`](crate::Flags::fairing()). Requests evaluate flags
+/// against a snapshot of the definitions taken when the request first
+/// evaluates a flag, so concurrent updates never change flags mid-request.
+/// Cloning a `Registry` is cheap.
+///
+/// Definitions can be changed at runtime via [`Registry::set()`] and
+/// [`Registry::remove()`], for instance, from an admin route or in tests.
+#[derive(Clone)]
+pub struct Registry {
+    pub(crate) inner: Arc<Inner>,
+}
+
+pub(crate) struct Inner {
+    flags: RwLock<Arc<Definitions>>,
+    provider: Option<Arc<dyn Provider>>,
+    pub(crate) user_header: Option<String>,
+}
+
+/// The fairing returned by [`Flags::fairing()`](crate::Flags::fairing()).
+///
+/// On ignition, the fairing loads flag definitions, from the configured
+/// [`Provider`] if there is one and from the `flags` table of the
+/// application's configuration otherwise, and places a [`Registry`] in managed
+/// state. Ignition fails if the definitions fail to load. A missing `flags`
+/// table is treated as an empty set of definitions.
+pub struct FlagsFairing {
+    provider: Option<Arc<dyn Provider>>,
+    user_header: Option<String>,
+    refresh: Option<Duration>,
+}
+
+impl Registry {
+    /// Returns the current definitions.
+    pub fn snapshot(&self) -> Arc<Definitions> {
+        self.inner.flags.read().expect("flags lock").clone()
+    }
+
+    /// Returns the current definition of the flag named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Flag> {
+        self.snapshot().get(name).cloned()
+    }
+
+    /// Sets the definition of the flag named `name` to `flag`.
+    ///
+    /// The change is seen by requests that have not yet evaluated a flag. It
+    /// is overwritten when definitions are next reloaded from a provider.
+    pub fn set(&self, name: impl Into<String>, flag: Flag) {
+        self.update(|definitions| { definitions.insert(name.into(), flag); })
+    }
+
+    /// Removes the definition of the flag named `name`, returning it if there
+    /// was one. A flag without a definition is off.
+    pub fn remove(&self, name: &str) -> Option<Flag> {
+        let mut removed = None;
+        self.update(|definitions| removed = definitions.remove(name));
+        removed
+    }
+
+    /// Reloads the definitions from the configured [`Provider`], if any.
+    /// Without a provider, this method does nothing and succeeds.
+    ///
+    /// If loading fails, the current definitions are left unchanged.
+    pub async fn reload(&self) -> Result<(), Error> {
+        if let Some(provider) = &self.inner.provider {
+            let definitions = provider.load().await?;
+            *self.inner.flags.write().expect("flags lock") = Arc::new(definitions);
+        }
+
+        Ok(())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Definitions)) {
+        let mut flags = self.inner.flags.write().expect("flags lock");
+        f(Arc::make_mut(&mut flags));
+    }
+}
+
+impl FlagsFairing {
+    pub(crate) fn new() -> Self {
+        FlagsFairing { provider: None, user_header: None, refresh: None }
+    }
+
+    /// Loads definitions from `provider` instead of the configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket_flags::{Provider, Definitions, Error};
+    /// # struct Service;
+    /// # #[rocket::async_trait]
+    /// # impl Provider for Service {
+    /// #     async fn load(&self) -> Result<Definitions, Error> { Ok(Definitions::new()) }
+    /// # }
+    /// use rocket_flags::Flags;
+    ///
+    /// let fairing = Flags::fairing().provider(Service);
+    /// ```
+    pub fn provider<P: Provider>(mut self, provider: P) -> Self {
+        self.provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Identifies users by the value of the header named `name` when no
+    /// [`User`](crate::User) was inserted into the request's extensions.
+    ///
+    /// Only use this option when the header is set by a trusted party, such
+    /// as an authenticating proxy: clients can otherwise choose their flags.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_flags::Flags;
+    ///
+    /// let fairing = Flags::fairing().user_header("X-User-Id");
+    /// ```
+    pub fn user_header(mut self, name: impl Into<String>) -> Self {
+        self.user_header = Some(name.into());
+        self
+    }
+
+    /// Reloads definitions from the configured provider every `period` after
+    /// liftoff, until shutdown. Has no effect without a provider. Failures to
+    /// reload are logged and the previous definitions are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket_flags::{Provider, Definitions, Error};
+    /// # struct Service;
+    /// # #[rocket::async_trait]
+    /// # impl Provider for Service {
+    /// #     async fn load(&self) -> Result<Definitions, Error> { Ok(Definitions::new()) }
+    /// # }
+    /// use std::time::Duration;
+    /// use rocket_flags::Flags;
+    ///
+    /// let fairing = Flags::fairing()
+    ///     .provider(Service)
+    ///     .refresh(Duration::from_secs(30));
+    /// ```
+    pub fn refresh(mut self, period: Duration) -> Self {
+        self.refresh = Some(period);
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for FlagsFairing {
+    fn info(&self) -> Info {
+        Info { name: "Feature Flags", kind: Kind::Ignite | Kind::Liftoff }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let definitions = match &self.provider {
+            Some(provider) => match provider.load().await {
+                Ok(definitions) => definitions,
+                Err(e) => {
+                    error!("failed to load feature flags: {e}");
+                    return Err(rocket);
+                }
+            },
+            None => match rocket.figment().extract_inner::<Definitions>("flags") {
+                Ok(definitions) => definitions,
+                Err(e) if e.missing() => Definitions::new(),
+                Err(e) => {
+                    error!("invalid feature flag configuration: {e}");
+                    return Err(rocket);
+                }
+            },
+        };
+
+        let registry = Registry {
+            inner: Arc::new(Inner {
+                flags: RwLock::new(Arc::new(definitions)),
+                provider: self.provider.clone(),
+                user_header: self.user_header.clone(),
+            })
+        };
+
+        Ok(rocket.manage(registry))
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let (Some(period), Some(_)) = (self.refresh, &self.provider) else { return };
+        let Some(registry) = rocket.state::<Registry>().cloned() else { return };
+        let shutdown = rocket.shutdown();
+        rocket.tasks().spawn("feature flag refresh", async move {
+            while tokio::time::timeout(period, shutdown.clone()).await.is_err() {
+                if let Err(e) = registry.reload().await {
+                    warn!("failed to reload feature flags: {e}");
+                }
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for Registry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Registry")
+            .field("flags", &self.snapshot())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,123 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rocket::{Build, Rocket};
+use rocket::fairing::AdHoc;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket_flags::{Definitions, Enabled, Error, Flag, FlagName, Flags, Provider, Registry, User};
+
+struct Beta;
+
+impl FlagName for Beta {
+    const NAME: &'static str = "beta";
+}
+
+#[get("/flag/<name>")]
+fn flag(name: &str, flags: Flags<'_>) -> &'static str {
+    if flags.is_enabled(name) { "on" } else { "off" }
+}
+
+#[get("/page")]
+fn beta_page(_beta: Enabled<Beta>) -> &'static str {
+    "beta page"
+}
+
+#[get("/page", rank = 2)]
+fn page() -> &'static str {
+    "page"
+}
+
+fn rocket() -> Rocket<Build> {
+    let figment = rocket::Config::figment()
+        .merge(("flags.beta", Flag::on().with_user("alice").with_header("X-Beta", "1")))
+        .merge(("flags.everyone", Flag::on()))
+        .merge(("flags.nobody", Flag::off()));
+
+    rocket::custom(figment)
+        .mount("/", routes![flag, beta_page, page])
+        .attach(Flags::fairing().user_header("X-User-Id"))
+        .attach(AdHoc::on_request("Authenticate", |req, _| Box::pin(async move {
+            if req.headers().get_one("Authorization") == Some("alice") {
+                req.extensions().insert(User("alice".into()));
+            }
+        })))
+}
+
+async fn get(client: &Client, uri: &str, headers: &[(&'static str, &'static str)]) -> String {
+    let mut request = client.get(uri.to_string());
+    for (name, value) in headers {
+        request.add_header(Header::new(*name, *value));
+    }
+
+    request.dispatch().await.into_string().await.unwrap()
+}
+
+#[rocket::async_test]
+async fn flags_are_targeted() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    assert_eq!(get(&client, "/flag/everyone", &[]).await, "on");
+    assert_eq!(get(&client, "/flag/nobody", &[("Authorization", "alice")]).await, "off");
+    assert_eq!(get(&client, "/flag/undefined", &[]).await, "off");
+
+    assert_eq!(get(&client, "/flag/beta", &[]).await, "off");
+    assert_eq!(get(&client, "/flag/beta", &[("Authorization", "alice")]).await, "on");
+    assert_eq!(get(&client, "/flag/beta", &[("X-User-Id", "alice")]).await, "on");
+    assert_eq!(get(&client, "/flag/beta", &[("X-User-Id", "bob")]).await, "off");
+    assert_eq!(get(&client, "/flag/beta", &[("X-Beta", "1")]).await, "on");
+}
+
+#[rocket::async_test]
+async fn enabled_guard_forwards() {
+    let client = Client::tracked(rocket()).await.unwrap();
+    assert_eq!(get(&client, "/page", &[]).await, "page");
+    assert_eq!(get(&client, "/page", &[("X-Beta", "1")]).await, "beta page");
+
+    let registry = client.rocket().state::<Registry>().unwrap();
+    registry.set("beta", Flag::on());
+    assert_eq!(get(&client, "/page", &[]).await, "beta page");
+
+    assert!(registry.remove("beta").is_some());
+    assert_eq!(get(&client, "/page", &[("X-Beta", "1")]).await, "page");
+}
+
+#[derive(Clone, Default)]
+struct Toggle(Arc<AtomicBool>);
+
+#[rocket::async_trait]
+impl Provider for Toggle {
+    async fn load(&self) -> Result<Definitions, Error> {
+        let flag = match self.0.load(Ordering::SeqCst) {
+            true => Flag::on(),
+            false => Flag::off(),
+        };
+
+        Ok(Definitions::from([("toggle".to_string(), flag)]))
+    }
+}
+
+#[rocket::async_test]
+async fn providers_are_reloaded() {
+    let toggle = Toggle::default();
+    let rocket = rocket::build()
+        .mount("/", routes![flag])
+        .attach(Flags::fairing().provider(toggle.clone()));
+
+    let client = Client::tracked(rocket).await.unwrap();
+    assert_eq!(get(&client, "/flag/toggle", &[]).await, "off");
+
+    toggle.0.store(true, Ordering::SeqCst);
+    assert_eq!(get(&client, "/flag/toggle", &[]).await, "off");
+
+    client.rocket().state::<Registry>().unwrap().reload().await.unwrap();
+    assert_eq!(get(&client, "/flag/toggle", &[]).await, "on");
+}
+
+#[rocket::async_test]
+async fn missing_fairing_is_an_error() {
+    let client = Client::tracked(rocket::build().mount("/", routes![flag])).await.unwrap();
+    let response = client.get("/flag/beta").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+}
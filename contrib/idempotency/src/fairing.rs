@@ -0,0 +1,237 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Request, Response, Data, Route};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::http::{Method, Status};
+use rocket::response::{self, Responder};
+use rocket::route::{Handler, Outcome};
+
+use crate::{IdempotencyKey, Memory, Record, Store, Stored, HEADER};
+
+/// A fairing implementing idempotency keys for unsafe requests.
+///
+/// At ignition, the fairing mounts internal routes, named `Idempotency`, that
+/// intercept `POST`, `PUT`, `PATCH`, and `DELETE` requests with an
+/// `Idempotency-Key` header before any application route. For each such
+/// request:
+///
+///   * If no request with the key has been seen within the [TTL], the request
+///     is recorded as in flight and forwarded to the application's routes as
+///     usual. Once a response is produced, it is stored for the TTL.
+///   * If the response to a request with the key is stored, it is replayed
+///     without calling any application route, with an additional
+///     `Idempotent-Replayed: true` header.
+///   * If a request with the key is still in flight, the request is rejected
+///     with `409 Conflict`.
+///   * If the key was used for a request with a different method or URI, the
+///     request is rejected with `422 Unprocessable Entity`.
+///   * If the key is invalid, the request is rejected with `400 Bad Request`.
+///
+/// Requests without an `Idempotency-Key` header are unaffected. Use the
+/// [`IdempotencyKey`] guard to require a key.
+///
+/// Responses with a `5xx` status are not stored, so requests that failed due
+/// to a server error can be retried. Neither are responses larger than the
+/// [maximum response size](Idempotency::max_response_size()). Keys are global:
+/// applications serving several clients should ensure that clients cannot
+/// guess each others' keys, for instance, by requiring random UUIDs.
+///
+/// [TTL]: Idempotency::ttl()
+pub struct Idempotency {
+    store: Arc<dyn Store>,
+    ttl: Duration,
+    lock_ttl: Duration,
+    max_response_size: ByteUnit,
+}
+
+/// The internal route intercepting requests with keys.
+#[derive(Clone)]
+struct Lookup {
+    store: Arc<dyn Store>,
+    lock_ttl: Duration,
+}
+
+/// The key of a request recorded as in flight, cached in the request.
+struct Pending(Option<String>);
+
+/// A stored response being replayed.
+struct Replay(Stored);
+
+impl Idempotency {
+    /// Creates a new fairing with an in-memory [`Memory`] store, a TTL of one
+    /// day, an in-flight TTL of one minute, and a maximum response size of
+    /// 1MiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_idempotency::Idempotency;
+    ///
+    /// let idempotency = Idempotency::new();
+    /// ```
+    pub fn new() -> Self {
+        Idempotency {
+            store: Arc::new(Memory::new()),
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lock_ttl: Duration::from_secs(60),
+            max_response_size: 1.mebibytes(),
+        }
+    }
+
+    /// Stores records in `store` instead of the current store.
+    pub fn store<S: Store>(mut self, store: S) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Sets how long responses are stored and replayed. Defaults to one day.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long a request is considered in flight if no response is
+    /// stored for it, for instance, because the process crashed. Defaults to
+    /// one minute.
+    pub fn in_flight_ttl(mut self, ttl: Duration) -> Self {
+        self.lock_ttl = ttl;
+        self
+    }
+
+    /// Sets the size of the largest response body that is stored. Defaults to
+    /// 1MiB.
+    pub fn max_response_size(mut self, size: ByteUnit) -> Self {
+        self.max_response_size = size;
+        self
+    }
+
+    async fn abort(&self, key: &str) {
+        if let Err(e) = self.store.abort(key).await {
+            warn!(%key, "failed to remove idempotency record: {e}");
+        }
+    }
+}
+
+impl Default for Idempotency {
+    fn default() -> Self {
+        Idempotency::new()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Idempotency {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Response, name: "Idempotency" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let lookup = Lookup { store: self.store.clone(), lock_ttl: self.lock_ttl };
+        let routes: Vec<_> = [Method::Post, Method::Put, Method::Patch, Method::Delete]
+            .into_iter()
+            .map(|method| {
+                let mut route = Route::ranked(isize::MIN, method, "/<_..>", lookup.clone());
+                route.name = Some("Idempotency".into());
+                route
+            })
+            .collect();
+
+        Ok(rocket.mount("/", routes))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let Pending(Some(key)) = req.local_cache(|| Pending(None)) else {
+            return;
+        };
+
+        if res.status().class().is_server_error() {
+            return self.abort(key).await;
+        }
+
+        match res.body_mut().size().await {
+            Some(size) if size.bytes() <= self.max_response_size => {}
+            _ => {
+                warn!(%key, "response too large to store for idempotency key");
+                return self.abort(key).await;
+            }
+        }
+
+        let body = match res.body_mut().to_bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(%key, "failed to read response for idempotency key: {e}");
+                return self.abort(key).await;
+            }
+        };
+
+        res.set_sized_body(body.len(), Cursor::new(body.clone()));
+        let stored = Stored {
+            status: res.status().code,
+            headers: res.headers().iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect(),
+            body,
+        };
+
+        if let Err(e) = self.store.complete(key, stored, self.ttl).await {
+            warn!(%key, "failed to store response for idempotency key: {e}");
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Lookup {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let Some(value) = req.headers().get_one(HEADER) else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+
+        let Ok(key) = IdempotencyKey::parse(value) else {
+            return Outcome::Error(Status::BadRequest);
+        };
+
+        let fingerprint = format!("{} {}", req.method(), req.uri());
+        match self.store.begin(key.as_str(), &fingerprint, self.lock_ttl).await {
+            Ok(None) => {
+                req.local_cache(|| Pending(Some(key.as_str().to_owned())));
+                Outcome::forward(data, Status::NotFound)
+            }
+            Ok(Some(record)) if record.fingerprint() != fingerprint => {
+                Outcome::Error(Status::UnprocessableEntity)
+            }
+            Ok(Some(Record::InFlight { .. })) => Outcome::Error(Status::Conflict),
+            Ok(Some(Record::Completed { response, .. })) => Outcome::from(req, Replay(response)),
+            Err(e) => {
+                error!(%key, "failed to read idempotency record: {e}");
+                Outcome::Error(Status::ServiceUnavailable)
+            }
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Replay {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let Replay(stored) = self;
+        let mut response = Response::build();
+        response.status(Status::new(stored.status));
+        for (name, value) in stored.headers {
+            response.raw_header_adjoin(name, value);
+        }
+
+        response.raw_header("Idempotent-Replayed", "true")
+            .sized_body(stored.body.len(), Cursor::new(stored.body))
+            .ok()
+    }
+}
+
+impl std::fmt::Debug for Idempotency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Idempotency")
+            .field("ttl", &self.ttl)
+            .field("in_flight_ttl", &self.lock_ttl)
+            .field("max_response_size", &self.max_response_size)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use rocket::request::{self, FromRequest, Request};
+use rocket::http::Status;
+
+/// The name of the header carrying idempotency keys.
+pub const HEADER: &str = "Idempotency-Key";
+
+/// A request guard for a valid `Idempotency-Key` header.
+///
+/// Use this guard in handlers that must only be called with an idempotency
+/// key. The guard fails with `400 Bad Request` if the request has no key or an
+/// invalid one. A valid key is between 1 and 255 visible ASCII characters.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_idempotency::IdempotencyKey;
+///
+/// #[post("/charges")]
+/// fn charge(key: IdempotencyKey<'_>) -> String {
+///     format!("charged once for {}", key.as_str())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdempotencyKey<'r>(&'r str);
+
+/// An error returned by the [`IdempotencyKey`] guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// The request has no `Idempotency-Key` header.
+    Missing,
+    /// The request's `Idempotency-Key` header is not a valid key.
+    Invalid,
+}
+
+impl<'r> IdempotencyKey<'r> {
+    /// Returns the key as a string.
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+
+    pub(crate) fn parse(value: &'r str) -> Result<Self, KeyError> {
+        let valid = (1..=255).contains(&value.len())
+            && value.bytes().all(|b| b.is_ascii_graphic());
+
+        match valid {
+            true => Ok(IdempotencyKey(value)),
+            false => Err(KeyError::Invalid),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey<'r> {
+    type Error = KeyError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, KeyError> {
+        let result = req.headers().get_one(HEADER)
+            .ok_or(KeyError::Missing)
+            .and_then(IdempotencyKey::parse);
+
+        match result {
+            Ok(key) => request::Outcome::Success(key),
+            Err(e) => request::Outcome::Error((Status::BadRequest, e)),
+        }
+    }
+}
+
+impl fmt::Display for IdempotencyKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::Missing => write!(f, "missing `{HEADER}` header"),
+            KeyError::Invalid => write!(f, "invalid `{HEADER}` header"),
+        }
+    }
+}
+
+impl std::error::Error for KeyError { }
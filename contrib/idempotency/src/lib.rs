@@ -0,0 +1,69 @@
+//! Idempotency keys for Rocket.
+//!
+//! Clients retry requests that time out or fail due to network errors. For
+//! unsafe requests, like a payment or the provisioning of a server, a retry
+//! must not repeat the request's effects. The common solution is for clients
+//! to send a unique `Idempotency-Key` header with every such request and reuse
+//! it for retries, and for the server to remember the response to the first
+//! request with a key and replay it for subsequent requests with the same key.
+//!
+//! This crate provides the [`Idempotency`] fairing, which implements this
+//! pattern for all `POST`, `PUT`, `PATCH`, and `DELETE` requests with an
+//! `Idempotency-Key` header, storing responses in a pluggable [`Store`], and
+//! the [`IdempotencyKey`] request guard, which requires requests to have a key.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_idempotency = "0.1.0"
+//! ```
+//!
+//! Then attach the [`Idempotency`] fairing:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! use rocket::State;
+//! use rocket_idempotency::{Idempotency, IdempotencyKey};
+//!
+//! #[post("/charges")]
+//! fn charge(key: IdempotencyKey<'_>, charges: &State<AtomicUsize>) -> String {
+//!     let n = charges.fetch_add(1, Ordering::Relaxed);
+//!     format!("charge #{n} for key {key}")
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .manage(AtomicUsize::new(0))
+//!         .mount("/", routes![charge])
+//!         .attach(Idempotency::new())
+//! }
+//! ```
+//!
+//! A client retrying `POST /charges` with the same `Idempotency-Key` receives
+//! the same response and is charged once. See [`Idempotency`] for details.
+//!
+//! # Stores
+//!
+//! Records are kept in the in-memory [`Memory`] store by default. To recognize
+//! retries across several instances of an application, implement [`Store`] for
+//! a shared store like Redis and configure it via [`Idempotency::store()`].
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_idempotency")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod key;
+mod store;
+
+pub use fairing::Idempotency;
+pub use key::{IdempotencyKey, KeyError, HEADER};
+pub use store::{Store, Memory, Record, Stored, Error};
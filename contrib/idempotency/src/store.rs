@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rocket::serde::{Serialize, Deserialize};
+
+/// An error produced by a [`Store`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A stored response to a request with an idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Stored {
+    /// The response's status code.
+    pub status: u16,
+    /// The response's headers.
+    pub headers: Vec<(String, String)>,
+    /// The response's body.
+    pub body: Vec<u8>,
+}
+
+/// The state of a request with an idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub enum Record {
+    /// The first request with the key is still being processed.
+    InFlight {
+        /// The fingerprint of the request.
+        fingerprint: String,
+    },
+    /// The first request with the key completed with `response`.
+    Completed {
+        /// The fingerprint of the request.
+        fingerprint: String,
+        /// The response to the request.
+        response: Stored,
+    },
+}
+
+impl Record {
+    /// The fingerprint of the request that created the record.
+    pub fn fingerprint(&self) -> &str {
+        match self {
+            Record::InFlight { fingerprint } => fingerprint,
+            Record::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+/// Storage for idempotency records.
+///
+/// A store maps idempotency keys to [`Record`]s, each of which expires after a
+/// time-to-live. The [`Memory`] store is used by default. Implementing this
+/// trait allows records to be stored elsewhere, say in Redis, so that retries
+/// are recognized across several instances of an application. [`Record`]
+/// implements `Serialize` and `Deserialize` to ease storing it externally.
+#[rocket::async_trait]
+pub trait Store: Send + Sync + 'static {
+    /// Atomically records that a request with `key` and `fingerprint` is in
+    /// flight, expiring after `ttl`, if there is no unexpired record for `key`.
+    ///
+    /// Returns `None` if the in-flight record was created and the existing
+    /// record otherwise.
+    async fn begin(&self, key: &str, fingerprint: &str, ttl: Duration)
+        -> Result<Option<Record>, Error>;
+
+    /// Replaces the record for `key` with a completed record for `response`,
+    /// expiring after `ttl`.
+    async fn complete(&self, key: &str, response: Stored, ttl: Duration) -> Result<(), Error>;
+
+    /// Removes the record for `key`, allowing the request to be retried.
+    async fn abort(&self, key: &str) -> Result<(), Error>;
+}
+
+/// An in-memory [`Store`], the default.
+///
+/// Records are lost when the process exits and are not shared between
+/// processes. Expired records are purged periodically.
+#[derive(Debug, Default)]
+pub struct Memory {
+    records: Mutex<Records>,
+}
+
+#[derive(Debug, Default)]
+struct Records {
+    map: HashMap<String, (Record, Instant)>,
+    inserts: usize,
+}
+
+impl Memory {
+    /// Creates a new, empty in-memory store.
+    pub fn new() -> Self {
+        Memory::default()
+    }
+
+    /// Returns the number of records in the store, including expired records
+    /// that have not yet been purged.
+    pub fn len(&self) -> usize {
+        self.records.lock().expect("records lock").map.len()
+    }
+
+    /// Returns `true` if the store has no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[rocket::async_trait]
+impl Store for Memory {
+    async fn begin(&self, key: &str, fingerprint: &str, ttl: Duration)
+        -> Result<Option<Record>, Error>
+    {
+        let mut records = self.records.lock().expect("records lock");
+        let now = Instant::now();
+        if let Some((record, expires)) = records.map.get(key) {
+            if *expires > now {
+                return Ok(Some(record.clone()));
+            }
+        }
+
+        records.inserts += 1;
+        if records.inserts % 64 == 0 {
+            records.map.retain(|_, (_, expires)| *expires > now);
+        }
+
+        let record = Record::InFlight { fingerprint: fingerprint.into() };
+        records.map.insert(key.into(), (record, now + ttl));
+        Ok(None)
+    }
+
+    async fn complete(&self, key: &str, response: Stored, ttl: Duration) -> Result<(), Error> {
+        let mut records = self.records.lock().expect("records lock");
+        if let Some((record, expires)) = records.map.get_mut(key) {
+            let fingerprint = record.fingerprint().to_owned();
+            *record = Record::Completed { fingerprint, response };
+            *expires = Instant::now() + ttl;
+        }
+
+        Ok(())
+    }
+
+    async fn abort(&self, key: &str) -> Result<(), Error> {
+        self.records.lock().expect("records lock").map.remove(key);
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::tokio::sync::Notify;
+use rocket_idempotency::{Idempotency, IdempotencyKey};
+
+#[post("/charges", data = "<amount>")]
+fn charge(key: IdempotencyKey<'_>, amount: &str, count: &State<AtomicUsize>) -> String {
+    let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("{key}: charge #{n} of {amount}")
+}
+
+#[put("/flaky")]
+fn flaky(count: &State<AtomicUsize>) -> Status {
+    match count.fetch_add(1, Ordering::SeqCst) {
+        0 => Status::InternalServerError,
+        _ => Status::Created,
+    }
+}
+
+#[post("/slow")]
+async fn slow(notify: &State<Notify>) -> &'static str {
+    notify.notified().await;
+    "done"
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(AtomicUsize::new(0))
+        .manage(Notify::new())
+        .mount("/", routes![charge, flaky, slow])
+        .attach(Idempotency::new());
+
+    Client::tracked(rocket).await.unwrap()
+}
+
+fn key(value: &str) -> Header<'static> {
+    Header::new("Idempotency-Key", value.to_string())
+}
+
+#[rocket::async_test]
+async fn responses_are_replayed() {
+    let client = client().await;
+    let response = client.post("/charges").header(key("abc")).body("10").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("Idempotent-Replayed").is_none());
+    assert_eq!(response.into_string().await.unwrap(), "abc: charge #1 of 10");
+
+    let response = client.post("/charges").header(key("abc")).body("10").dispatch().await;
+    assert_eq!(response.headers().get_one("Idempotent-Replayed"), Some("true"));
+    assert_eq!(response.into_string().await.unwrap(), "abc: charge #1 of 10");
+
+    let response = client.post("/charges").header(key("def")).body("20").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "def: charge #2 of 20");
+
+    let response = client.post("/charges").body("20").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[rocket::async_test]
+async fn key_reuse_for_other_request_is_rejected() {
+    let client = client().await;
+    let response = client.post("/charges").header(key("abc")).body("10").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.put("/flaky").header(key("abc")).dispatch().await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+
+    let response = client.put("/flaky").header(key("bad key")).dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[rocket::async_test]
+async fn server_errors_are_not_stored() {
+    let client = client().await;
+    let response = client.put("/flaky").header(key("abc")).dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+
+    let response = client.put("/flaky").header(key("abc")).dispatch().await;
+    assert_eq!(response.status(), Status::Created);
+
+    let response = client.put("/flaky").header(key("abc")).dispatch().await;
+    assert_eq!(response.status(), Status::Created);
+    assert_eq!(response.headers().get_one("Idempotent-Replayed"), Some("true"));
+}
+
+#[rocket::async_test]
+async fn concurrent_duplicates_conflict() {
+    let client = client().await;
+    let first = client.post("/slow").header(key("abc")).dispatch();
+    let second = async {
+        rocket::tokio::task::yield_now().await;
+        let response = client.post("/slow").header(key("abc")).dispatch().await;
+        client.rocket().state::<Notify>().unwrap().notify_one();
+        response
+    };
+
+    let (first, second) = rocket::tokio::join!(first, second);
+    assert_eq!(first.status(), Status::Ok);
+    assert_eq!(second.status(), Status::Conflict);
+}
@@ -0,0 +1,200 @@
+//! Run Rocket applications on AWS Lambda.
+//!
+//! This crate runs a Rocket application as an AWS Lambda function invoked by
+//! API Gateway (REST, HTTP, and WebSocket APIs), an Application Load Balancer,
+//! or a Lambda function URL. Each invocation's event is translated into a
+//! request, dispatched to the application via
+//! [`Rocket::into_service()`](rocket::Rocket::into_service()), and the
+//! application's response is translated back. Routes, catchers, fairings, and
+//! managed state work exactly as they do on Rocket's own server.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_lambda = "0.1.0"
+//! ```
+//!
+//! Then, instead of launching the application, pass it to [`run()`] from
+//! `main`:
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! #[get("/")]
+//! fn hello() -> &'static str {
+//!     "Hello, Lambda!"
+//! }
+//!
+//! #[rocket::main]
+//! async fn main() -> Result<(), rocket_lambda::Error> {
+//!     rocket_lambda::run(rocket::build().mount("/", routes![hello])).await
+//! }
+//! ```
+//!
+//! The application is ignited, and liftoff fairings are run, once per
+//! execution environment, before the first event is processed. Rocket does not
+//! bind to any network interface, so the `address`, `port`, and `tls`
+//! configuration parameters have no effect.
+//!
+//! # Buffered and Streaming Responses
+//!
+//! [`run()`] buffers each response in its entirety before returning it to
+//! Lambda, as required by API Gateway and Application Load Balancers.
+//! Responses with a textual `Content-Type` (`text/*`, JSON, XML, JavaScript)
+//! and no `Content-Encoding` are returned as text; all others are returned
+//! base64-encoded as binary.
+//!
+//! [`run_streaming()`] instead streams response bodies as they are produced.
+//! Streaming is only supported by function URLs configured with the
+//! `RESPONSE_STREAM` invoke mode.
+//!
+//! # Paths and Remote Addresses
+//!
+//! By default, API Gateway REST APIs include the deployment stage as the first
+//! segment of the request path. To omit it, set the environment variable
+//! `AWS_LAMBDA_HTTP_IGNORE_STAGE_IN_PATH` to `true`.
+//!
+//! The address of the client, as reported by API Gateway, is made available
+//! via [`Request::remote()`](rocket::Request::remote()); the reported port is
+//! always `0`. Application Load Balancers do not report the client's address.
+//! Instead, set Rocket's `ip_header` configuration parameter to a header the
+//! load balancer is configured to set.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_lambda")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+use std::net::{IpAddr, SocketAddr};
+
+use http_body_util::BodyExt;
+use lambda_http::http::HeaderMap;
+use lambda_http::http::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use lambda_http::request::RequestContext;
+use lambda_http::{service_fn, Body, Request, RequestExt, Response};
+use rocket::service::{ResponseBody, RocketService};
+use rocket::{Phase, Rocket};
+
+pub use lambda_http;
+
+/// The error type returned by [`run()`] and [`run_streaming()`].
+pub use lambda_http::Error;
+
+/// Runs `rocket` as a Lambda function, buffering responses.
+///
+/// Returns an error if `rocket` fails to ignite or the Lambda runtime fails.
+/// See the [crate level docs](crate) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// #[rocket::main]
+/// async fn main() -> Result<(), rocket_lambda::Error> {
+///     rocket_lambda::run(rocket::build()).await
+/// }
+/// ```
+pub async fn run<P: Phase>(rocket: Rocket<P>) -> Result<(), Error> {
+    let service = rocket.into_service().await?;
+    lambda_http::run(service_fn(move |request| {
+        let service = service.clone();
+        async move { handle(&service, request).await }
+    })).await
+}
+
+/// Runs `rocket` as a Lambda function, streaming responses.
+///
+/// Returns an error if `rocket` fails to ignite or the Lambda runtime fails.
+/// See the [crate level docs](crate) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// #[rocket::main]
+/// async fn main() -> Result<(), rocket_lambda::Error> {
+///     rocket_lambda::run_streaming(rocket::build()).await
+/// }
+/// ```
+pub async fn run_streaming<P: Phase>(rocket: Rocket<P>) -> Result<(), Error> {
+    let service = rocket.into_service().await?;
+    lambda_http::run_with_streaming_response(service_fn(move |request| {
+        let service = service.clone();
+        async move { Ok::<_, Error>(dispatch(&service, request).await) }
+    })).await
+}
+
+/// Dispatches the Lambda `request` to `service` and buffers its response.
+///
+/// This is the handler used by [`run()`]. It can be used directly to run
+/// Rocket inside of a custom Lambda handler, for instance, one that handles
+/// events other than HTTP requests.
+///
+/// Returns an error if reading the response body fails.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_lambda::lambda_http::{Body, Request};
+///
+/// # rocket::async_test(async {
+/// let service = rocket::build().into_service().await.unwrap();
+/// let request = Request::new(Body::Empty);
+/// let response = rocket_lambda::handle(&service, request).await.unwrap();
+/// assert_eq!(response.status(), 404);
+/// # });
+/// ```
+pub async fn handle(service: &RocketService, request: Request) -> Result<Response<Body>, Error> {
+    let (parts, body) = dispatch(service, request).await.into_parts();
+    let bytes = body.collect().await?.to_bytes();
+    let body = if bytes.is_empty() {
+        Body::Empty
+    } else if is_text(&parts.headers) {
+        match String::from_utf8(bytes.into()) {
+            Ok(string) => Body::Text(string),
+            Err(e) => Body::Binary(e.into_bytes()),
+        }
+    } else {
+        Body::Binary(bytes.into())
+    };
+
+    Ok(Response::from_parts(parts, body))
+}
+
+async fn dispatch(service: &RocketService, mut request: Request) -> Response<ResponseBody> {
+    if let Some(ip) = source_ip(&request) {
+        request.extensions_mut().insert(SocketAddr::new(ip, 0));
+    }
+
+    service.handle(request).await
+}
+
+/// Returns the client's IP address as reported by API Gateway, if any.
+fn source_ip(request: &Request) -> Option<IpAddr> {
+    let source_ip = match request.request_context_ref()? {
+        RequestContext::ApiGatewayV2(context) => context.http.source_ip.as_deref(),
+        RequestContext::ApiGatewayV1(context) => context.identity.source_ip.as_deref(),
+        RequestContext::WebSocket(context) => context.identity.source_ip.as_deref(),
+        _ => None,
+    };
+
+    source_ip?.parse().ok()
+}
+
+/// Returns `true` if a response with `headers` can be returned as text.
+fn is_text(headers: &HeaderMap) -> bool {
+    const TEXT: [&str; 3] = ["application/json", "application/xml", "application/javascript"];
+
+    if headers.contains_key(CONTENT_ENCODING) {
+        return false;
+    }
+
+    let Some(content_type) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    let media_type = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    media_type.starts_with("text/")
+        || TEXT.contains(&media_type.as_str())
+        || media_type.ends_with("+json")
+        || media_type.ends_with("+xml")
+}
@@ -0,0 +1,81 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use lambda_http::aws_lambda_events::apigw::ApiGatewayV2httpRequestContext;
+use lambda_http::request::RequestContext;
+use lambda_http::{Body, Request, RequestExt};
+use rocket::http::ContentType;
+use rocket::service::RocketService;
+
+#[get("/text")]
+fn text() -> &'static str {
+    "Hello, Lambda!"
+}
+
+#[get("/binary")]
+fn binary() -> (ContentType, Vec<u8>) {
+    (ContentType::PNG, vec![0x89, 0x50, 0x4E, 0x47])
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[get("/remote")]
+fn remote(remote: Option<SocketAddr>) -> String {
+    remote.map(|addr| addr.ip().to_string()).unwrap_or_else(|| "unknown".into())
+}
+
+async fn service() -> RocketService {
+    rocket::build()
+        .mount("/", routes![text, binary, echo, remote])
+        .into_service()
+        .await
+        .unwrap()
+}
+
+fn request(method: &str, uri: &str, body: Body) -> Request {
+    let mut request = Request::new(body);
+    *request.method_mut() = method.parse().unwrap();
+    *request.uri_mut() = uri.parse().unwrap();
+    request
+}
+
+#[rocket::async_test]
+async fn responses_are_text_or_binary() {
+    let service = service().await;
+
+    let response = rocket_lambda::handle(&service, request("GET", "/text", Body::Empty)).await;
+    let response = response.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body(), &Body::Text("Hello, Lambda!".into()));
+
+    let response = rocket_lambda::handle(&service, request("GET", "/binary", Body::Empty)).await;
+    let response = response.unwrap();
+    assert_eq!(response.body(), &Body::Binary(vec![0x89, 0x50, 0x4E, 0x47]));
+
+    let request = request("POST", "/echo", Body::Text("hi".into()));
+    let response = rocket_lambda::handle(&service, request).await.unwrap();
+    assert_eq!(response.body(), &Body::Text("hi".into()));
+
+    let response = rocket_lambda::handle(&service, self::request("GET", "/nope", Body::Empty));
+    assert_eq!(response.await.unwrap().status(), 404);
+}
+
+#[rocket::async_test]
+async fn source_ip_is_remote_address() {
+    let service = service().await;
+
+    let response = rocket_lambda::handle(&service, request("GET", "/remote", Body::Empty));
+    assert_eq!(response.await.unwrap().body(), &Body::Text("unknown".into()));
+
+    let mut context = ApiGatewayV2httpRequestContext::default();
+    context.http.source_ip = Some("192.0.2.7".into());
+    let request = request("GET", "/remote", Body::Empty)
+        .with_request_context(RequestContext::ApiGatewayV2(context));
+
+    let response = rocket_lambda::handle(&service, request).await.unwrap();
+    assert_eq!(response.body(), &Body::Text("192.0.2.7".into()));
+}
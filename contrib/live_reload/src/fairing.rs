@@ -0,0 +1,237 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Request, Response, State, Shutdown};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::figment::value::magic::RelativePathBuf;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::tokio::time::sleep;
+use rocket_ws::{WebSocket, Channel};
+
+use crate::Reloader;
+
+/// The template directory used when `template_dir` isn't configured. Matches
+/// the default of `rocket_dyn_templates`.
+const DEFAULT_TEMPLATE_DIR: &str = "templates";
+
+/// The default static file directory.
+const DEFAULT_STATIC_DIR: &str = "static";
+
+/// The default path of the live reload WebSocket endpoint.
+const DEFAULT_PATH: &str = "/__live_reload";
+
+/// How long to wait for a burst of file changes to settle before notifying.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A fairing that reloads browsers when watched files change.
+///
+/// When active, the fairing:
+///
+///   * watches the template directory, configured via `template_dir` and
+///     defaulting to `templates`, the `static` directory, and any directories
+///     added via [`LiveReload::watch()`], recursively, for changes;
+///   * mounts a WebSocket endpoint, by default at `/__live_reload`, that
+///     notifies connected browsers when a change is detected;
+///   * injects a script that connects to the endpoint and reloads the page
+///     when notified into every HTML response, before its closing `</body>`
+///     tag or, if it has none, at its end. Encoded responses, such as those
+///     that are compressed, are left untouched.
+///
+/// The script also reloads the page when it reconnects after losing its
+/// connection, so pages reload when the application is restarted, for
+/// instance by a tool like `cargo watch`.
+///
+/// The fairing is active in the `debug` profile and inactive otherwise. This
+/// can be overridden by setting the `live_reload` configuration parameter to
+/// `true` or `false`. When inactive, the fairing does nothing.
+///
+/// Pages served with a `Content-Security-Policy` that forbids inline scripts
+/// or WebSocket connections to the application will not reload.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_live_reload::LiveReload;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(LiveReload::fairing().watch("assets/dist"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LiveReload {
+    dirs: Vec<PathBuf>,
+    path: String,
+}
+
+impl LiveReload {
+    /// Returns a fairing that watches the template and static directories
+    /// and serves its WebSocket endpoint at `/__live_reload`.
+    pub fn fairing() -> Self {
+        LiveReload { dirs: vec![DEFAULT_STATIC_DIR.into()], path: DEFAULT_PATH.into() }
+    }
+
+    /// Additionally watches `dir`, relative to the current working directory.
+    pub fn watch<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.dirs.push(dir.into());
+        self
+    }
+
+    /// Serves the WebSocket endpoint at `path` instead of `/__live_reload`.
+    ///
+    /// # Panics
+    ///
+    /// Ignition panics if `path` is not a valid mount point.
+    pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Returns the script injected into HTML responses.
+    fn script(&self) -> String {
+        format!("<script>(function() {{\
+            var url = (location.protocol === 'https:' ? 'wss://' : 'ws://') \
+                + location.host + '{}';\
+            var reconnecting = false;\
+            function connect() {{\
+                var socket = new WebSocket(url);\
+                socket.onopen = function() {{ if (reconnecting) location.reload(); }};\
+                socket.onmessage = function() {{ location.reload(); }};\
+                socket.onclose = function() {{\
+                    reconnecting = true;\
+                    setTimeout(connect, 1000);\
+                }};\
+            }}\
+            connect();\
+        }})();</script>", self.path)
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for LiveReload {
+    fn info(&self) -> Info {
+        Info { name: "Live Reload", kind: Kind::Ignite | Kind::Response }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let profile = rocket.figment().profile().clone();
+        let enabled = match rocket.figment().extract_inner::<bool>("live_reload") {
+            Ok(enabled) => enabled,
+            Err(e) if e.missing() => profile == rocket::Config::DEBUG_PROFILE,
+            Err(e) => {
+                error!("invalid `live_reload` configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        if !enabled {
+            debug!(%profile, "live reload is disabled");
+            return Ok(rocket);
+        }
+
+        let template_dir = match rocket.figment().extract_inner::<RelativePathBuf>("template_dir") {
+            Ok(dir) => dir.relative(),
+            Err(e) if e.missing() => DEFAULT_TEMPLATE_DIR.into(),
+            Err(e) => {
+                error!("invalid `template_dir` configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let mut dirs = vec![template_dir];
+        dirs.extend(self.dirs.iter().cloned());
+        let reloader = Reloader::new(dirs);
+        span_info!("live_reload", endpoint = %self.path => {
+            reloader.watched().for_each(|dir| info!(directory = %dir.display()));
+        });
+
+        Ok(rocket.manage(reloader).mount(self.path.clone(), routes![socket]))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if req.rocket().state::<Reloader>().is_none()
+            || !res.content_type().map_or(false, |ct| ct.is_html())
+            || res.headers().contains("Content-Encoding")
+        {
+            return;
+        }
+
+        let html = match res.body_mut().to_string().await {
+            Ok(html) => html,
+            Err(e) => {
+                warn!("failed to read HTML response for live reload injection: {e}");
+                return;
+            }
+        };
+
+        let html = inject(html, &self.script());
+        res.set_sized_body(html.len(), Cursor::new(html));
+    }
+}
+
+/// Inserts `script` before the last `</body>` in `html` or, if there is none,
+/// appends it.
+fn inject(mut html: String, script: &str) -> String {
+    match html.to_ascii_lowercase().rfind("</body>") {
+        Some(i) => html.insert_str(i, script),
+        None => html.push_str(script),
+    }
+
+    html
+}
+
+enum Event {
+    Changed,
+    Message,
+    Done,
+}
+
+#[get("/")]
+fn socket(ws: WebSocket, reloader: &State<Reloader>, mut shutdown: Shutdown) -> Channel<'static> {
+    let mut rx = reloader.subscribe();
+    ws.channel(move |mut stream| Box::pin(async move {
+        loop {
+            let event = rocket::tokio::select! {
+                message = stream.next() => match message {
+                    Some(Ok(_)) => Event::Message,
+                    _ => Event::Done,
+                },
+                result = rx.recv() => match result {
+                    Ok(()) | Err(RecvError::Lagged(_)) => Event::Changed,
+                    Err(RecvError::Closed) => Event::Done,
+                },
+                _ = &mut shutdown => Event::Done,
+            };
+
+            match event {
+                Event::Message => continue,
+                Event::Done => break,
+                Event::Changed => {
+                    // Coalesce the burst of events a single save generates.
+                    sleep(DEBOUNCE).await;
+                    rx = rx.resubscribe();
+                    stream.send("reload".into()).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inject;
+
+    #[test]
+    fn test_inject() {
+        let script = "<script></script>";
+        assert_eq!(inject("<body>hi</body>".into(), script), "<body>hi<script></script></body>");
+        assert_eq!(inject("<BODY>hi</BODY>".into(), script), "<BODY>hi<script></script></BODY>");
+        assert_eq!(inject("hi".into(), script), "hi<script></script>");
+    }
+}
@@ -0,0 +1,57 @@
+//! Browser live reloading for development.
+//!
+//! When developing a server-rendered application, every change to a template
+//! or stylesheet is followed by a trip to the browser to refresh the page.
+//! This crate removes that step: in the `debug` profile, the [`LiveReload`]
+//! fairing watches the template and static directories and, when a file in
+//! them changes, tells every open page to reload.
+//!
+//! The fairing injects a small script into HTML responses. The script connects
+//! to a WebSocket endpoint, served via [`rocket_ws`], that sends a message when
+//! a change is detected, upon which the page reloads. Combined with the
+//! automatic template reloading of `rocket_dyn_templates` in debug builds, an
+//! edit to a template is visible as soon as the file is saved.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_live_reload = "0.1.0"
+//! ```
+//!
+//! Then attach the fairing returned by [`LiveReload::fairing()`]:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::response::content::RawHtml;
+//! use rocket_live_reload::LiveReload;
+//!
+//! #[get("/")]
+//! fn index() -> RawHtml<&'static str> {
+//!     RawHtml("<html><body>Hello, world!</body></html>")
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index])
+//!         .attach(LiveReload::fairing())
+//! }
+//! ```
+//!
+//! The fairing is inactive outside of the `debug` profile, so it can be left
+//! attached in production. See [`LiveReload`] for details.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_live_reload")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod reloader;
+
+pub use fairing::LiveReload;
+pub use reloader::Reloader;
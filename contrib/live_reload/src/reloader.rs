@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rocket::tokio::sync::broadcast;
+
+/// A handle to the live reload notifier, available as managed state.
+///
+/// While live reloading is active, [`LiveReload`](crate::LiveReload) places a
+/// `Reloader` in managed state. Browsers are notified to reload whenever a
+/// watched file changes or [`Reloader::reload()`] is called. The latter is
+/// useful to reload after changes the watcher can't see, like the completion
+/// of an external asset build.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket_live_reload::Reloader;
+///
+/// #[post("/__assets_built")]
+/// fn assets_built(reloader: Option<&State<Reloader>>) {
+///     if let Some(reloader) = reloader {
+///         reloader.reload();
+///     }
+/// }
+/// ```
+pub struct Reloader {
+    tx: broadcast::Sender<()>,
+    watched: Vec<PathBuf>,
+    _watcher: Option<Mutex<RecommendedWatcher>>,
+}
+
+impl Reloader {
+    /// Creates a reloader that watches `dirs`, recursively, for changes.
+    /// Directories that don't exist are skipped.
+    pub(crate) fn new(dirs: Vec<PathBuf>) -> Self {
+        let (tx, _) = broadcast::channel(16);
+        let watched: Vec<_> = dirs.into_iter()
+            .filter(|dir| dir.is_dir())
+            .filter_map(|dir| dir.canonicalize().ok())
+            .collect();
+
+        let sender = tx.clone();
+        let watcher = recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)
+                    | EventKind::Remove(_))
+                {
+                    let _ = sender.send(());
+                }
+            }
+        });
+
+        let watcher = watcher.and_then(|mut watcher| {
+            for dir in &watched {
+                watcher.watch(dir, RecursiveMode::Recursive)?;
+            }
+
+            Ok(watcher)
+        });
+
+        let watcher = match watcher {
+            Ok(watcher) => Some(Mutex::new(watcher)),
+            Err(e) => {
+                warn!("live reload file watcher initialization failed: {e}\n\
+                    browsers will only reload via `Reloader::reload()`");
+                None
+            }
+        };
+
+        Reloader { tx, watched, _watcher: watcher }
+    }
+
+    /// Notifies all connected browsers to reload.
+    pub fn reload(&self) {
+        debug!("notifying browsers to reload");
+        let _ = self.tx.send(());
+    }
+
+    /// Returns a receiver that is notified every time browsers are notified.
+    pub fn subscribe(&self) -> broadcast::Receiver<()> {
+        self.tx.subscribe()
+    }
+
+    /// Returns the canonicalized directories being watched.
+    pub fn watched(&self) -> impl Iterator<Item = &Path> {
+        self.watched.iter().map(|dir| dir.as_path())
+    }
+}
+
+impl std::fmt::Debug for Reloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reloader")
+            .field("watched", &self.watched)
+            .field("watching", &self._watcher.is_some())
+            .finish()
+    }
+}
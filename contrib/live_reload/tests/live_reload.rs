@@ -0,0 +1,85 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::futures::StreamExt;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::response::content::RawHtml;
+use rocket::tokio::time::timeout;
+use rocket_live_reload::{LiveReload, Reloader};
+use rocket_ws as ws;
+
+#[get("/")]
+fn index() -> RawHtml<&'static str> {
+    RawHtml("<html><body>Hello</body></html>")
+}
+
+#[get("/text")]
+fn text() -> &'static str {
+    "</body>"
+}
+
+async fn client(figment: rocket::figment::Figment, fairing: LiveReload) -> Client {
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![index, text])
+        .attach(fairing);
+
+    Client::tracked(rocket).await.unwrap()
+}
+
+async fn next_message(socket: &mut ws::local::LocalWebSocket<'_>) -> ws::Message {
+    timeout(Duration::from_secs(5), socket.next()).await
+        .expect("reload notification")
+        .expect("open socket")
+        .expect("valid message")
+}
+
+#[rocket::async_test]
+async fn injects_script_into_html() {
+    let client = client(rocket::Config::figment(), LiveReload::fairing()).await;
+    let html = client.get("/").dispatch().await.into_string().await.unwrap();
+    assert!(html.starts_with("<html><body>Hello<script>"));
+    assert!(html.ends_with("</script></body></html>"));
+    assert!(html.contains("'/__live_reload'"));
+
+    let text = client.get("/text").dispatch().await.into_string().await.unwrap();
+    assert_eq!(text, "</body>");
+}
+
+#[rocket::async_test]
+async fn disabled_when_configured() {
+    let figment = rocket::Config::figment().merge(("live_reload", false));
+    let client = client(figment, LiveReload::fairing()).await;
+    assert!(client.rocket().state::<Reloader>().is_none());
+
+    let html = client.get("/").dispatch().await.into_string().await.unwrap();
+    assert_eq!(html, "<html><body>Hello</body></html>");
+    assert_eq!(client.get("/__live_reload").dispatch().await.status(), Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn notifies_on_manual_reload() {
+    let fairing = LiveReload::fairing().path("/_dev/reload");
+    let client = client(rocket::Config::figment(), fairing).await;
+    let html = client.get("/").dispatch().await.into_string().await.unwrap();
+    assert!(html.contains("'/_dev/reload'"));
+
+    let mut socket = ws::local::connect(client.get("/_dev/reload")).await.unwrap();
+    client.rocket().state::<Reloader>().unwrap().reload();
+    assert_eq!(next_message(&mut socket).await, ws::Message::from("reload"));
+}
+
+#[rocket::async_test]
+async fn notifies_on_file_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(rocket::Config::figment(), LiveReload::fairing().watch(dir.path())).await;
+
+    let reloader = client.rocket().state::<Reloader>().unwrap();
+    let watched = dir.path().canonicalize().unwrap();
+    assert!(reloader.watched().any(|dir| dir == watched));
+
+    let mut socket = ws::local::connect(client.get("/__live_reload")).await.unwrap();
+    std::fs::write(dir.path().join("style.css"), "body { color: red; }").unwrap();
+    assert_eq!(next_message(&mut socket).await, ws::Message::from("reload"));
+}
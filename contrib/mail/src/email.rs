@@ -0,0 +1,209 @@
+use lettre::Message;
+use lettre::message::{header::ContentType, Mailbox, MultiPart};
+
+use crate::Error;
+
+/// An email to be sent via a [`Mailer`](crate::Mailer).
+///
+/// An email has a subject, a plain text body, an HTML body, or both, and is
+/// addressed with [`Mailbox`]es, which parse from strings like
+/// `"Jane Doe <jane@example.com>"` or `"jane@example.com"`. If an email has no
+/// sender, the mailer's default sender, if any, is used.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_mail::Email;
+///
+/// # fn f() -> Result<Email, rocket_mail::Error> {
+/// let email = Email::new()
+///     .to("Jane Doe <jane@example.com>".parse()?)
+///     .subject("Welcome!")
+///     .text("Thanks for signing up.")
+///     .html("<p>Thanks for signing up.</p>");
+/// # Ok(email)
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Email {
+    /// The sender.
+    pub from: Option<Mailbox>,
+    /// The `Reply-To` address.
+    pub reply_to: Option<Mailbox>,
+    /// The recipients.
+    pub to: Vec<Mailbox>,
+    /// The carbon copy recipients.
+    pub cc: Vec<Mailbox>,
+    /// The blind carbon copy recipients.
+    pub bcc: Vec<Mailbox>,
+    /// The subject.
+    pub subject: String,
+    /// The plain text body.
+    pub text: Option<String>,
+    /// The HTML body.
+    pub html: Option<String>,
+}
+
+impl Email {
+    /// Creates a new, empty email.
+    pub fn new() -> Self {
+        Email::default()
+    }
+
+    /// Sets the sender to `mailbox`.
+    pub fn from(mut self, mailbox: Mailbox) -> Self {
+        self.from = Some(mailbox);
+        self
+    }
+
+    /// Sets the `Reply-To` address to `mailbox`.
+    pub fn reply_to(mut self, mailbox: Mailbox) -> Self {
+        self.reply_to = Some(mailbox);
+        self
+    }
+
+    /// Adds `mailbox` as a recipient.
+    pub fn to(mut self, mailbox: Mailbox) -> Self {
+        self.to.push(mailbox);
+        self
+    }
+
+    /// Adds `mailbox` as a carbon copy recipient.
+    pub fn cc(mut self, mailbox: Mailbox) -> Self {
+        self.cc.push(mailbox);
+        self
+    }
+
+    /// Adds `mailbox` as a blind carbon copy recipient.
+    pub fn bcc(mut self, mailbox: Mailbox) -> Self {
+        self.bcc.push(mailbox);
+        self
+    }
+
+    /// Sets the subject to `subject`.
+    pub fn subject<S: Into<String>>(mut self, subject: S) -> Self {
+        self.subject = subject.into();
+        self
+    }
+
+    /// Sets the plain text body to `text`.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Sets the HTML body to `html`.
+    pub fn html<S: Into<String>>(mut self, html: S) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Renders the template named `name` with `context` and sets it as the
+    /// HTML body if the template is an HTML template, as determined by its
+    /// extension, or as the plain text body otherwise. Call this method twice,
+    /// for instance with `welcome.html` and `welcome.txt` templates, to send
+    /// both an HTML and a plain text body.
+    ///
+    /// Fails with [`Error::Template`] if the template does not exist or fails
+    /// to render. Requires the `templates` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_dyn_templates::{Metadata, context};
+    /// use rocket_mail::{Email, Mailer, Error};
+    ///
+    /// #[post("/signup", data = "<address>")]
+    /// async fn signup(
+    ///     address: &str,
+    ///     mailer: Mailer,
+    ///     templates: Metadata<'_>,
+    /// ) -> Result<(), Error> {
+    ///     let context = context! { address };
+    ///     let email = Email::new()
+    ///         .to(address.parse()?)
+    ///         .subject("Welcome!")
+    ///         .template(&templates, "emails/welcome.html", &context)?
+    ///         .template(&templates, "emails/welcome.txt", &context)?;
+    ///
+    ///     mailer.send(email).await
+    /// }
+    /// ```
+    #[cfg(feature = "templates")]
+    pub fn template<C: rocket::serde::Serialize>(
+        self,
+        templates: &rocket_dyn_templates::Metadata<'_>,
+        name: &str,
+        context: C,
+    ) -> Result<Self, Error> {
+        let (content_type, body) = templates.render(name.to_string(), context)
+            .ok_or_else(|| Error::Template(name.into()))?;
+
+        match content_type == rocket::http::ContentType::HTML {
+            true => Ok(self.html(body)),
+            false => Ok(self.text(body)),
+        }
+    }
+
+    /// Sets the sender to `mailbox` if no sender is set.
+    pub(crate) fn default_from(&mut self, mailbox: Option<&Mailbox>) {
+        if self.from.is_none() {
+            self.from = mailbox.cloned();
+        }
+    }
+
+    /// Composes the email into a MIME message. Fails if the email has no
+    /// sender or no recipients.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_mail::Email;
+    ///
+    /// # fn f() -> Result<(), rocket_mail::Error> {
+    /// let email = Email::new()
+    ///     .from("app@example.com".parse()?)
+    ///     .to("jane@example.com".parse()?)
+    ///     .text("Hello!");
+    ///
+    /// let message = email.to_message()?;
+    /// assert!(String::from_utf8(message.formatted()).unwrap().contains("Hello!"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_message(&self) -> Result<Message, Error> {
+        let mut builder = Message::builder().subject(self.subject.clone());
+        if let Some(from) = &self.from {
+            builder = builder.from(from.clone());
+        }
+
+        if let Some(reply_to) = &self.reply_to {
+            builder = builder.reply_to(reply_to.clone());
+        }
+
+        for mailbox in &self.to {
+            builder = builder.to(mailbox.clone());
+        }
+
+        for mailbox in &self.cc {
+            builder = builder.cc(mailbox.clone());
+        }
+
+        for mailbox in &self.bcc {
+            builder = builder.bcc(mailbox.clone());
+        }
+
+        let message = match (&self.text, &self.html) {
+            (Some(text), Some(html)) => {
+                let body = MultiPart::alternative_plain_html(text.clone(), html.clone());
+                builder.multipart(body)?
+            }
+            (Some(text), None) => builder.header(ContentType::TEXT_PLAIN).body(text.clone())?,
+            (None, Some(html)) => builder.header(ContentType::TEXT_HTML).body(html.clone())?,
+            (None, None) => builder.header(ContentType::TEXT_PLAIN).body(String::new())?,
+        };
+
+        Ok(message)
+    }
+}
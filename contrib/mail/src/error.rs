@@ -0,0 +1,79 @@
+use std::fmt;
+
+use lettre::address::AddressError;
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+/// An error composing or sending an email.
+///
+/// As a [`Responder`], an error responds with `400 Bad Request` if an address
+/// failed to parse and `500 Internal Server Error` otherwise.
+#[derive(Debug)]
+pub enum Error {
+    /// An email address failed to parse.
+    Address(AddressError),
+    /// The email could not be composed, usually because it has no sender or
+    /// recipients.
+    Message(lettre::error::Error),
+    /// The SMTP transport failed to connect or deliver the email.
+    Smtp(lettre::transport::smtp::Error),
+    /// The named template does not exist or failed to render.
+    Template(String),
+    /// A custom transport failed to deliver the email.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Address(e) => write!(f, "invalid address: {e}"),
+            Error::Message(e) => write!(f, "invalid email: {e}"),
+            Error::Smtp(e) => write!(f, "smtp error: {e}"),
+            Error::Template(name) => write!(f, "failed to render template {name:?}"),
+            Error::Transport(e) => write!(f, "transport error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Address(e) => Some(e),
+            Error::Message(e) => Some(e),
+            Error::Smtp(e) => Some(e),
+            Error::Template(_) => None,
+            Error::Transport(e) => Some(&**e),
+        }
+    }
+}
+
+impl From<AddressError> for Error {
+    fn from(error: AddressError) -> Self {
+        Error::Address(error)
+    }
+}
+
+impl From<lettre::error::Error> for Error {
+    fn from(error: lettre::error::Error) -> Self {
+        Error::Message(error)
+    }
+}
+
+impl From<lettre::transport::smtp::Error> for Error {
+    fn from(error: lettre::transport::smtp::Error) -> Self {
+        Error::Smtp(error)
+    }
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for Error {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            Error::Address(_) => Err(Status::BadRequest),
+            e => {
+                error!("failed to send email: {e}");
+                Err(Status::InternalServerError)
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Email sending for Rocket.
+//!
+//! This crate provides a [`Mailer`] handle for sending [`Email`]s
+//! asynchronously, configured via the application's configuration and
+//! delivered via SMTP, a custom [`Transport`] like a provider's HTTP API, or,
+//! for testing, a [`Mock`] transport that records emails instead of sending
+//! them. With the `templates` feature enabled, email bodies can be rendered
+//! from `rocket_dyn_templates` templates via `Email::template()`.
+//!
+//! # Usage
+//!
+//! Depend on the crate, enabling `templates` to render bodies from templates:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_mail = { version = "0.1.0", features = ["templates"] }
+//! ```
+//!
+//! Then configure the transport in the `mail` table of `Rocket.toml`, as
+//! described in [`Config`]. Below, emails are delivered via SMTP in release
+//! builds and recorded in debug builds:
+//!
+//! ```toml
+//! [release.mail]
+//! host = "smtp.example.com"
+//! username = "app"
+//! password = "hunter2"
+//! from = "Example <noreply@example.com>"
+//!
+//! [debug.mail]
+//! transport = "mock"
+//! from = "Example <noreply@example.com>"
+//! ```
+//!
+//! Finally, attach the fairing returned by [`Mailer::fairing()`] and use the
+//! [`Mailer`] request guard to send emails:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_mail::{Mailer, Email, Error};
+//!
+//! #[post("/welcome/<address>")]
+//! async fn welcome(address: &str, mailer: Mailer) -> Result<(), Error> {
+//!     let email = Email::new()
+//!         .to(address.parse()?)
+//!         .subject("Welcome!")
+//!         .text("Thanks for signing up.");
+//!
+//!     mailer.send(email).await
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![welcome])
+//!         .attach(Mailer::fairing())
+//! }
+//! ```
+//!
+//! # Testing
+//!
+//! When the `mock` transport is configured, the [`Mock`] transport is in
+//! managed state, and its [outbox](Mock::outbox()) lists the emails sent. See
+//! [`Mock`] for an example.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_mail")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod email;
+mod error;
+mod mailer;
+mod transport;
+
+pub use lettre;
+
+pub use email::Email;
+pub use error::Error;
+pub use mailer::{Mailer, Config, TransportKind, Tls};
+pub use transport::{Transport, Smtp, Mock};
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use lettre::{AsyncSmtpTransport, Tokio1Executor};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+
+use rocket::{Rocket, Build};
+use rocket::fairing::{self, Fairing, AdHoc};
+use rocket::http::Status;
+use rocket::outcome::IntoOutcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::serde::Deserialize;
+
+use crate::{Email, Error, Transport, Smtp, Mock};
+
+/// Configuration for the [`Mailer`] created by [`Mailer::fairing()`], read from
+/// the `mail` table of the application's configuration.
+///
+/// ```toml
+/// [default.mail]
+/// transport = "smtp"
+/// host = "smtp.example.com"
+/// port = 587
+/// tls = "starttls"
+/// username = "app"
+/// password = "hunter2"
+/// from = "Example <noreply@example.com>"
+/// timeout = 10
+///
+/// [debug.mail]
+/// transport = "mock"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Config {
+    /// The transport to use. Defaults to [`TransportKind::Smtp`].
+    #[serde(default)]
+    pub transport: TransportKind,
+    /// The SMTP server's host name. Required for the SMTP transport.
+    #[serde(default)]
+    pub host: Option<String>,
+    /// The SMTP server's port. Defaults to the standard port for `tls`.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// How to secure the connection to the SMTP server. Defaults to
+    /// [`Tls::StartTls`].
+    #[serde(default)]
+    pub tls: Tls,
+    /// The SMTP username, if authentication is required.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// The SMTP password, if authentication is required.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// The default sender of emails without one.
+    #[serde(default)]
+    pub from: Option<String>,
+    /// The SMTP connection and command timeout in seconds. Defaults to `10`.
+    #[serde(default = "Config::default_timeout")]
+    pub timeout: u64,
+}
+
+/// The kind of transport configured via [`Config::transport`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum TransportKind {
+    /// Deliver emails to an SMTP server via [`Smtp`].
+    #[default]
+    Smtp,
+    /// Record emails in a [`Mock`] instead of delivering them.
+    Mock,
+}
+
+/// How to secure an SMTP connection, configured via [`Config::tls`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum Tls {
+    /// Upgrade the connection via `STARTTLS`, failing if the server does not
+    /// support it. The default port is 587.
+    #[default]
+    StartTls,
+    /// Connect via TLS. The default port is 465.
+    Tls,
+    /// Do not secure the connection. The default port is 25. Only use this
+    /// for local development servers.
+    None,
+}
+
+impl Config {
+    fn default_timeout() -> u64 {
+        10
+    }
+
+    fn smtp(&self) -> Result<Smtp, Error> {
+        let Some(host) = self.host.as_deref() else {
+            return Err(Error::Transport("missing `host` for smtp transport".into()));
+        };
+
+        let mut builder = match self.tls {
+            Tls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+            Tls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            Tls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+        };
+
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let timeout = Duration::from_secs(self.timeout);
+        Ok(Smtp::new(builder.timeout(Some(timeout)).build()))
+    }
+}
+
+/// A handle for sending emails, available as managed state.
+///
+/// A `Mailer` is typically created from the application's configuration and
+/// managed by the fairing returned by [`Mailer::fairing()`], then retrieved in
+/// handlers as a request guard. It can also be created from any [`Transport`]
+/// via [`Mailer::new()`] and [managed](rocket::Rocket::manage()) directly.
+/// Cloning a `Mailer` is cheap.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_mail::{Mailer, Email, Error};
+///
+/// #[post("/reset/<address>")]
+/// async fn reset(address: &str, mailer: Mailer) -> Result<(), Error> {
+///     let email = Email::new()
+///         .to(address.parse()?)
+///         .subject("Reset your password")
+///         .text("Follow this link to reset your password: ...");
+///
+///     mailer.send(email).await
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![reset])
+///         .attach(Mailer::fairing())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Mailer {
+    transport: Arc<dyn Transport>,
+    from: Option<Mailbox>,
+}
+
+impl Mailer {
+    /// Creates a new `Mailer` sending emails via `transport`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_mail::{Mailer, Mock};
+    ///
+    /// let mailer = Mailer::new(Mock::new());
+    /// ```
+    pub fn new<T: Transport>(transport: T) -> Self {
+        Mailer { transport: Arc::new(transport), from: None }
+    }
+
+    /// Sets the default sender of emails without a sender to `mailbox`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_mail::{Mailer, Mock};
+    ///
+    /// # fn f() -> Result<Mailer, rocket_mail::Error> {
+    /// let mailer = Mailer::new(Mock::new())
+    ///     .default_from("Example <noreply@example.com>".parse()?);
+    /// # Ok(mailer)
+    /// # }
+    /// ```
+    pub fn default_from(mut self, mailbox: Mailbox) -> Self {
+        self.from = Some(mailbox);
+        self
+    }
+
+    /// Returns a fairing that, on ignition, creates a `Mailer` from the
+    /// [`Config`] in the `mail` table of the application's configuration and
+    /// places it in managed state. If the configured transport is `mock`, the
+    /// [`Mock`] transport is placed in managed state as well.
+    ///
+    /// Ignition fails if the configuration is missing or invalid.
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("Mailer", |rocket| async move {
+            Mailer::ignite(rocket)
+        })
+    }
+
+    fn ignite(rocket: Rocket<Build>) -> fairing::Result {
+        let config = match rocket.figment().extract_inner::<Config>("mail") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("invalid mail configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let from = match config.from.as_deref().map(str::parse::<Mailbox>).transpose() {
+            Ok(from) => from,
+            Err(e) => {
+                error!("invalid default sender {:?}: {e}", config.from);
+                return Err(rocket);
+            }
+        };
+
+        match config.transport {
+            TransportKind::Smtp => match config.smtp() {
+                Ok(smtp) => Ok(rocket.manage(Mailer { transport: Arc::new(smtp), from })),
+                Err(e) => {
+                    error!("failed to configure smtp transport: {e}");
+                    Err(rocket)
+                }
+            },
+            TransportKind::Mock => {
+                let mock = Mock::new();
+                let mailer = Mailer { transport: Arc::new(mock.clone()), from };
+                Ok(rocket.manage(mailer).manage(mock))
+            }
+        }
+    }
+
+    /// Sends `email`, using the default sender if `email` has none.
+    pub async fn send(&self, mut email: Email) -> Result<(), Error> {
+        email.default_from(self.from.as_ref());
+        self.transport.send(&email).await
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Mailer {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.rocket().state::<Mailer>()
+            .cloned()
+            .or_error((Status::InternalServerError, ()))
+    }
+}
+
+impl std::fmt::Debug for Mailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mailer")
+            .field("from", &self.from)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::{Arc, Mutex};
+
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use crate::{Email, Error};
+
+/// A means of delivering emails.
+///
+/// Implement `Transport` to send emails through a provider's HTTP API instead
+/// of SMTP, and use it via [`Mailer::new()`](crate::Mailer::new()). Provider
+/// APIs accepting raw MIME messages can send the message returned by
+/// [`Email::to_message()`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_mail::{Transport, Email, Error};
+///
+/// struct Provider {
+///     api_key: String,
+/// }
+///
+/// #[rocket::async_trait]
+/// impl Transport for Provider {
+///     async fn send(&self, email: &Email) -> Result<(), Error> {
+///         let message = email.to_message()?.formatted();
+///         /* post `message` to the provider's API using `self.api_key`... */
+///         Ok(())
+///     }
+/// }
+/// ```
+#[rocket::async_trait]
+pub trait Transport: Send + Sync + 'static {
+    /// Delivers `email`, which has a sender.
+    async fn send(&self, email: &Email) -> Result<(), Error>;
+}
+
+/// A transport delivering emails to an SMTP server.
+///
+/// An `Smtp` transport is created by
+/// [`Mailer::fairing()`](crate::Mailer::fairing()) when the `transport`
+/// configuration parameter is `smtp`. Use [`Smtp::new()`] to configure an SMTP
+/// transport programmatically.
+#[derive(Clone)]
+pub struct Smtp {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl Smtp {
+    /// Creates a new SMTP transport from a configured `lettre` transport.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_mail::{Mailer, Smtp};
+    /// use rocket_mail::lettre::{AsyncSmtpTransport, Tokio1Executor};
+    ///
+    /// # fn f() -> Result<Mailer, rocket_mail::Error> {
+    /// let smtp = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay("smtp.example.com")?
+    ///     .build();
+    ///
+    /// let mailer = Mailer::new(Smtp::new(smtp));
+    /// # Ok(mailer)
+    /// # }
+    /// ```
+    pub fn new(transport: AsyncSmtpTransport<Tokio1Executor>) -> Self {
+        Smtp { transport }
+    }
+}
+
+#[rocket::async_trait]
+impl Transport for Smtp {
+    async fn send(&self, email: &Email) -> Result<(), Error> {
+        self.transport.send(email.to_message()?).await?;
+        Ok(())
+    }
+}
+
+/// A transport recording emails instead of delivering them, for testing.
+///
+/// When the `transport` configuration parameter is `mock`, the fairing
+/// returned by [`Mailer::fairing()`](crate::Mailer::fairing()) manages a
+/// `Mock` alongside the [`Mailer`](crate::Mailer). Retrieve it from a local
+/// client's Rocket instance to inspect the emails an application sent. Emails
+/// are composed via [`Email::to_message()`] before being recorded, so invalid
+/// emails fail to send as they would with a real transport.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::local::blocking::Client;
+/// use rocket_mail::{Mailer, Mock, Email, Error};
+///
+/// #[post("/invite/<address>")]
+/// async fn invite(address: &str, mailer: Mailer) -> Result<(), Error> {
+///     mailer.send(Email::new().to(address.parse()?).subject("Join us!")).await
+/// }
+///
+/// let figment = rocket::Config::figment()
+///     .merge(("mail.transport", "mock"))
+///     .merge(("mail.from", "app@example.com"));
+///
+/// let rocket = rocket::custom(figment)
+///     .mount("/", routes![invite])
+///     .attach(Mailer::fairing());
+///
+/// let client = Client::tracked(rocket).unwrap();
+/// client.post("/invite/jane@example.com").dispatch();
+///
+/// let outbox = client.rocket().state::<Mock>().unwrap().outbox();
+/// assert_eq!(outbox[0].subject, "Join us!");
+/// assert_eq!(outbox[0].to[0].email.to_string(), "jane@example.com");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Mock {
+    outbox: Arc<Mutex<Vec<Email>>>,
+}
+
+impl Mock {
+    /// Creates a new mock transport with an empty outbox.
+    pub fn new() -> Self {
+        Mock::default()
+    }
+
+    /// Returns the emails sent so far, oldest first.
+    pub fn outbox(&self) -> Vec<Email> {
+        self.outbox.lock().expect("outbox lock").clone()
+    }
+
+    /// Empties the outbox.
+    pub fn clear(&self) {
+        self.outbox.lock().expect("outbox lock").clear();
+    }
+}
+
+#[rocket::async_trait]
+impl Transport for Mock {
+    async fn send(&self, email: &Email) -> Result<(), Error> {
+        email.to_message()?;
+        self.outbox.lock().expect("outbox lock").push(email.clone());
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Smtp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Smtp").finish_non_exhaustive()
+    }
+}
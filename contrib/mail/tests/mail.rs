@@ -0,0 +1,93 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::figment::Figment;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket_mail::{Mailer, Mock, Email, Error};
+
+#[post("/welcome/<address>")]
+async fn welcome(address: &str, mailer: Mailer) -> Result<(), Error> {
+    let email = Email::new()
+        .to(address.parse()?)
+        .subject("Welcome!")
+        .text("Thanks for signing up.")
+        .html("<p>Thanks for signing up.</p>");
+
+    mailer.send(email).await
+}
+
+fn rocket(figment: Figment) -> Rocket<Build> {
+    rocket::custom(rocket::Config::figment().merge(figment))
+        .mount("/", routes![welcome])
+        .attach(Mailer::fairing())
+}
+
+fn mock() -> Figment {
+    Figment::new()
+        .merge(("mail.transport", "mock"))
+        .merge(("mail.from", "Example <noreply@example.com>"))
+}
+
+#[rocket::async_test]
+async fn mock_records_emails() {
+    let client = Client::tracked(rocket(mock())).await.unwrap();
+    let response = client.post("/welcome/jane@example.com").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let mock = client.rocket().state::<Mock>().unwrap();
+    let outbox = mock.outbox();
+    assert_eq!(outbox.len(), 1);
+    assert_eq!(outbox[0].subject, "Welcome!");
+    assert_eq!(outbox[0].to[0].email.to_string(), "jane@example.com");
+    assert_eq!(outbox[0].from.as_ref().unwrap().to_string(), "Example <noreply@example.com>");
+    assert_eq!(outbox[0].text.as_deref(), Some("Thanks for signing up."));
+
+    mock.clear();
+    assert!(mock.outbox().is_empty());
+}
+
+#[rocket::async_test]
+async fn invalid_emails_are_not_sent() {
+    let client = Client::tracked(rocket(mock())).await.unwrap();
+    let response = client.post("/welcome/not-an-address").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(client.rocket().state::<Mock>().unwrap().outbox().is_empty());
+
+    let mailer = Mailer::new(Mock::new());
+    let email = Email::new().to("jane@example.com".parse().unwrap());
+    assert!(matches!(mailer.send(email).await, Err(Error::Message(_))));
+}
+
+#[rocket::async_test]
+async fn messages_are_multipart() {
+    let email = Email::new()
+        .from("noreply@example.com".parse().unwrap())
+        .to("jane@example.com".parse().unwrap())
+        .subject("Hi")
+        .text("plain body")
+        .html("<b>html body</b>");
+
+    let message = String::from_utf8(email.to_message().unwrap().formatted()).unwrap();
+    assert!(message.contains("multipart/alternative"));
+    assert!(message.contains("plain body"));
+    assert!(message.contains("<b>html body</b>"));
+}
+
+#[rocket::async_test]
+async fn fairing_validates_config() {
+    let smtp = Figment::new()
+        .merge(("mail.host", "localhost"))
+        .merge(("mail.tls", "none"))
+        .merge(("mail.port", 2525));
+
+    let rocket = rocket(smtp).ignite().await.unwrap();
+    assert!(rocket.state::<Mailer>().is_some());
+    assert!(rocket.state::<Mock>().is_none());
+
+    let missing_host = Figment::new().merge(("mail.transport", "smtp"));
+    assert!(rocket(missing_host).ignite().await.is_err());
+
+    let bad_from = mock().merge(("mail.from", "not an address"));
+    assert!(rocket(bad_from).ignite().await.is_err());
+}
@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use object_store::{ObjectStore, WriteMultipart};
+use object_store::aws::AmazonS3Builder;
+use object_store::path::Path;
+
+use rocket::{Rocket, Build};
+use rocket::data::{Data, ByteUnit};
+use rocket::fairing::{self, Fairing, AdHoc};
+use rocket::fs::TempFile;
+use rocket::http::Status;
+use rocket::outcome::IntoOutcome;
+use rocket::request::{self, FromRequest, Request};
+use rocket::serde::Deserialize;
+use rocket::tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{Error, StoredFile};
+
+/// The size of each part of a multipart upload and the threshold above which
+/// uploads are multipart. S3 requires parts of at least 5MiB.
+const PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// The maximum number of parts uploaded concurrently.
+const MAX_CONCURRENCY: usize = 4;
+
+/// Configuration for the [`Bucket`] created by [`Bucket::fairing()`], read
+/// from the `object_store` table of the application's configuration.
+///
+/// Only `bucket` is required. Options not set are read from the standard
+/// `AWS_*` environment variables, like `AWS_ACCESS_KEY_ID`, if set.
+///
+/// ```toml
+/// [default.object_store]
+/// bucket = "uploads"
+/// endpoint = "http://localhost:9000"
+/// region = "us-east-1"
+/// access_key_id = "minio"
+/// secret_access_key = "minio123"
+/// allow_http = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Config {
+    /// The name of the bucket.
+    pub bucket: String,
+    /// The endpoint of an S3-compatible service. Defaults to AWS S3.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// The region of the bucket. Defaults to `us-east-1`.
+    #[serde(default = "Config::default_region")]
+    pub region: String,
+    /// The access key ID.
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// The secret access key.
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// Whether to allow plain HTTP endpoints. Defaults to `false`.
+    #[serde(default)]
+    pub allow_http: bool,
+}
+
+impl Config {
+    fn default_region() -> String {
+        "us-east-1".into()
+    }
+}
+
+/// A handle to a bucket in an object store, available as managed state.
+///
+/// A `Bucket` wraps any [`ObjectStore`]. Typically, it is created from the
+/// application's configuration and managed by the fairing returned by
+/// [`Bucket::fairing()`] and retrieved in handlers as a request guard, but it
+/// can also be created from an existing store via [`Bucket::new()`] and
+/// [managed](rocket::Rocket::manage()) directly. Cloning a `Bucket` is cheap.
+///
+/// Uploads are streamed to the store as they are read: bodies smaller than
+/// 5MiB are stored with a single request, larger bodies with a multipart
+/// upload of 5MiB parts. At most a few parts are held in memory at once, and
+/// nothing is written to disk.
+#[derive(Debug, Clone)]
+pub struct Bucket {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl Bucket {
+    /// Creates a new `Bucket` backed by `store`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_object_store::Bucket;
+    /// use rocket_object_store::object_store::memory::InMemory;
+    ///
+    /// let bucket = Bucket::new(InMemory::new());
+    /// ```
+    pub fn new<S: ObjectStore>(store: S) -> Self {
+        Bucket { store: Arc::new(store) }
+    }
+
+    /// Creates a new `Bucket` for the S3-compatible bucket described by
+    /// `config`.
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(&config.bucket)
+            .with_region(&config.region)
+            .with_allow_http(config.allow_http);
+
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+
+        if let Some(key_id) = &config.access_key_id {
+            builder = builder.with_access_key_id(key_id);
+        }
+
+        if let Some(secret) = &config.secret_access_key {
+            builder = builder.with_secret_access_key(secret);
+        }
+
+        Ok(Bucket::new(builder.build()?))
+    }
+
+    /// Returns a fairing that, on ignition, creates a `Bucket` from the
+    /// [`Config`] in the `object_store` table of the application's
+    /// configuration and places it in managed state. Ignition fails if the
+    /// configuration is missing or invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_object_store::{Bucket, StoredFile, Error};
+    ///
+    /// #[get("/files/<key>")]
+    /// async fn file(key: &str, bucket: Bucket) -> Result<StoredFile, Error> {
+    ///     bucket.get(key).await
+    /// }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount("/", routes![file])
+    ///         .attach(Bucket::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("Object Store", |rocket| async move {
+            Bucket::ignite(rocket)
+        })
+    }
+
+    fn ignite(rocket: Rocket<Build>) -> fairing::Result {
+        let config = match rocket.figment().extract_inner::<Config>("object_store") {
+            Ok(config) => config,
+            Err(e) => {
+                error!("invalid object store configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        match Bucket::from_config(&config) {
+            Ok(bucket) => Ok(rocket.manage(bucket)),
+            Err(e) => {
+                error!("failed to configure bucket {}: {e}", config.bucket);
+                Err(rocket)
+            }
+        }
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &Arc<dyn ObjectStore> {
+        &self.store
+    }
+
+    /// Fetches the metadata of the object at `key` and returns a responder
+    /// that streams its contents.
+    ///
+    /// Fails with an error for which [`Error::is_not_found()`] returns `true`
+    /// if the object does not exist.
+    pub async fn get(&self, key: &str) -> Result<StoredFile, Error> {
+        let meta = self.store.head(&Path::from(key)).await?;
+        Ok(StoredFile::new(self.store.clone(), meta))
+    }
+
+    /// Deletes the object at `key`.
+    pub async fn delete(&self, key: &str) -> Result<(), Error> {
+        Ok(self.store.delete(&Path::from(key)).await?)
+    }
+
+    /// Streams the contents of `reader` to the object at `key`, replacing any
+    /// existing object, and returns the number of bytes stored.
+    pub async fn put_reader<R>(&self, key: &str, reader: R) -> Result<u64, Error>
+        where R: AsyncRead + Send
+    {
+        self.upload(key, reader, None).await
+    }
+
+    /// Streams `data` to the object at `key`, replacing any existing object,
+    /// and returns the number of bytes stored.
+    ///
+    /// If `data` exceeds `limit`, fails with [`Error::TooLarge`] without
+    /// storing anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::data::{Data, ToByteUnit};
+    /// use rocket_object_store::{Bucket, Error};
+    ///
+    /// #[put("/files/<key>", data = "<data>")]
+    /// async fn upload(key: &str, data: Data<'_>, bucket: Bucket) -> Result<String, Error> {
+    ///     let len = bucket.put_data(key, data, 1.gibibytes()).await?;
+    ///     Ok(format!("stored {len} bytes"))
+    /// }
+    /// ```
+    pub async fn put_data(&self, key: &str, data: Data<'_>, limit: ByteUnit) -> Result<u64, Error> {
+        // Read one byte past the limit so that an exceeded limit is detected.
+        let stream = data.open(limit + 1);
+        self.upload(key, stream, Some(limit.as_u64())).await
+    }
+
+    /// Streams the contents of `file` to the object at `key`, replacing any
+    /// existing object, and returns the number of bytes stored.
+    pub async fn put_temp_file(&self, key: &str, file: &TempFile<'_>) -> Result<u64, Error> {
+        self.upload(key, file.open().await?, None).await
+    }
+
+    async fn upload<R>(&self, key: &str, reader: R, limit: Option<u64>) -> Result<u64, Error>
+        where R: AsyncRead + Send
+    {
+        let path = Path::from(key);
+        let mut reader = std::pin::pin!(reader);
+        let mut buf = Vec::with_capacity(PART_SIZE);
+        let first = read_part(&mut reader, &mut buf).await?;
+        if limit.map_or(false, |limit| first > limit) {
+            return Err(Error::TooLarge);
+        }
+
+        if buf.len() < PART_SIZE {
+            self.store.put(&path, buf.into()).await?;
+            return Ok(first);
+        }
+
+        let upload = self.store.put_multipart(&path).await?;
+        let mut writer = WriteMultipart::new_with_chunk_size(upload, PART_SIZE);
+        writer.write(&buf);
+
+        let mut written = first;
+        let result = loop {
+            let n = match read_part(&mut reader, &mut buf).await {
+                Ok(0) => break Ok(()),
+                Ok(n) => n,
+                Err(e) => break Err(e.into()),
+            };
+
+            written += n;
+            if limit.map_or(false, |limit| written > limit) {
+                break Err(Error::TooLarge);
+            }
+
+            if let Err(e) = writer.wait_for_capacity(MAX_CONCURRENCY).await {
+                break Err(e.into());
+            }
+
+            writer.write(&buf);
+        };
+
+        match result {
+            Ok(()) => {
+                writer.finish().await?;
+                Ok(written)
+            }
+            Err(e) => {
+                if let Err(abort) = writer.abort().await {
+                    warn!("failed to abort multipart upload of {key}: {abort}");
+                }
+
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Clears `buf` and reads up to `PART_SIZE` bytes into it from `reader`,
+/// returning the number of bytes read. Fewer bytes are read only at EOF.
+async fn read_part<R>(reader: &mut R, buf: &mut Vec<u8>) -> std::io::Result<u64>
+    where R: AsyncRead + Unpin
+{
+    buf.clear();
+    reader.take(PART_SIZE as u64).read_to_end(buf).await.map(|n| n as u64)
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Bucket {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.rocket().state::<Bucket>()
+            .cloned()
+            .or_error((Status::InternalServerError, ()))
+    }
+}
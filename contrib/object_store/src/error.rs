@@ -0,0 +1,72 @@
+use std::{fmt, io};
+
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+/// An error from the object store or while streaming an upload to it.
+///
+/// As a [`Responder`], an error responds with `404 Not Found` if the object
+/// does not exist, `413 Payload Too Large` if an upload exceeded its limit,
+/// and `500 Internal Server Error` otherwise.
+#[derive(Debug)]
+pub enum Error {
+    /// The object store returned an error.
+    Store(object_store::Error),
+    /// Reading the upload failed.
+    Io(io::Error),
+    /// The upload exceeded its size limit. Nothing was stored.
+    TooLarge,
+}
+
+impl Error {
+    /// Returns `true` if the error is due to the object not existing.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::Store(object_store::Error::NotFound { .. }))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Store(e) => write!(f, "object store error: {e}"),
+            Error::Io(e) => write!(f, "i/o error: {e}"),
+            Error::TooLarge => write!(f, "upload exceeded size limit"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Store(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::TooLarge => None,
+        }
+    }
+}
+
+impl From<object_store::Error> for Error {
+    fn from(error: object_store::Error) -> Self {
+        Error::Store(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for Error {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            e if e.is_not_found() => Err(Status::NotFound),
+            Error::TooLarge => Err(Status::PayloadTooLarge),
+            e => {
+                error!("object store request failed: {e}");
+                Err(Status::InternalServerError)
+            }
+        }
+    }
+}
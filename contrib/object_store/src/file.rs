@@ -0,0 +1,282 @@
+use std::io;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{BoxStream, StreamExt};
+use object_store::{GetOptions, GetRange, ObjectMeta, ObjectStore};
+use object_store::path::Path;
+
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use rocket::http::{ContentType, Status};
+use rocket::tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+/// A responder that streams an object from an object store.
+///
+/// Retrieve a `StoredFile` via [`Bucket::get()`](crate::Bucket::get()). Only
+/// the object's metadata is fetched up front; its contents are fetched as the
+/// response body is written, without being buffered in memory or on disk.
+///
+/// The response's `Content-Type` is derived from the extension of the object's
+/// key and can be overridden via [`StoredFile::set_content_type()`]. The
+/// response includes the object's `ETag`, if the store reports one, and
+/// `Accept-Ranges: bytes`.
+///
+/// # Range Requests
+///
+/// If the request has a `Range` header with a single byte range, only that
+/// range is fetched from the store and the response is `206 Partial Content`
+/// with a corresponding `Content-Range` header. If the range lies outside of
+/// the object, the response is `416 Range Not Satisfiable`. Requests for
+/// multiple ranges or in units other than bytes receive the entire object.
+#[derive(Debug)]
+pub struct StoredFile {
+    pub(crate) store: Arc<dyn ObjectStore>,
+    pub(crate) meta: ObjectMeta,
+    pub(crate) content_type: Option<ContentType>,
+}
+
+impl StoredFile {
+    pub(crate) fn new(store: Arc<dyn ObjectStore>, meta: ObjectMeta) -> Self {
+        let content_type = meta.location.extension().and_then(ContentType::from_extension);
+        StoredFile { store, meta, content_type }
+    }
+
+    /// Returns the key of the object.
+    pub fn key(&self) -> &str {
+        self.meta.location.as_ref()
+    }
+
+    /// Returns the size of the object in bytes.
+    pub fn len(&self) -> u64 {
+        self.meta.size as u64
+    }
+
+    /// Returns `true` if the object is empty.
+    pub fn is_empty(&self) -> bool {
+        self.meta.size == 0
+    }
+
+    /// Returns the `ETag` of the object, if the store reports one.
+    pub fn etag(&self) -> Option<&str> {
+        self.meta.e_tag.as_deref()
+    }
+
+    /// Returns the `Content-Type` the object will be served with, if any.
+    pub fn content_type(&self) -> Option<&ContentType> {
+        self.content_type.as_ref()
+    }
+
+    /// Sets the `Content-Type` the object is served with to `content_type`.
+    pub fn set_content_type(&mut self, content_type: ContentType) {
+        self.content_type = Some(content_type);
+    }
+
+    /// Returns the object's metadata as reported by the store.
+    pub fn meta(&self) -> &ObjectMeta {
+        &self.meta
+    }
+}
+
+/// Parses the value of a `Range` header for an object of `size` bytes.
+///
+/// Returns `Ok(None)` if the whole object should be served, `Ok(Some(range))`
+/// if only `range` should be, and `Err(())` if the range is unsatisfiable.
+fn parse_range(header: Option<&str>, size: u64) -> Result<Option<Range<u64>>, ()> {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return Ok(None);
+    };
+
+    let Some((start, end)) = spec.trim().split_once('-').filter(|_| !spec.contains(',')) else {
+        return Ok(None);
+    };
+
+    let (start, end) = (start.trim(), end.trim());
+    let range = match (start.parse::<u64>(), end.parse::<u64>()) {
+        _ if start.is_empty() => match end.parse::<u64>() {
+            Ok(0) => return Err(()),
+            Ok(n) => size.saturating_sub(n)..size,
+            Err(_) => return Ok(None),
+        },
+        (Ok(start), _) if end.is_empty() => start..size,
+        (Ok(start), Ok(end)) if start <= end => start..end.saturating_add(1).min(size),
+        _ => return Ok(None),
+    };
+
+    match range.start < size {
+        true => Ok(Some(range)),
+        false => Err(()),
+    }
+}
+
+impl<'r> Responder<'r, 'static> for StoredFile {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let size = self.len();
+        let mut response = Response::build();
+        response.raw_header("Accept-Ranges", "bytes");
+        if let Some(etag) = self.meta.e_tag.clone() {
+            response.raw_header("ETag", etag);
+        }
+
+        let range = match parse_range(req.headers().get_one("Range"), size) {
+            Ok(Some(range)) => {
+                let content_range = format!("bytes {}-{}/{size}", range.start, range.end - 1);
+                response.status(Status::PartialContent);
+                response.raw_header("Content-Range", content_range);
+                range
+            }
+            Ok(None) => 0..size,
+            Err(()) => {
+                return response.status(Status::RangeNotSatisfiable)
+                    .raw_header("Content-Range", format!("bytes */{size}"))
+                    .ok();
+            }
+        };
+
+        if let Some(content_type) = self.content_type {
+            response.header(content_type);
+        }
+
+        let len = (range.end - range.start) as usize;
+        let reader = ObjectReader::new(self.store, self.meta.location, range);
+        response.sized_body(len, reader).ok()
+    }
+}
+
+type Chunks = BoxStream<'static, object_store::Result<Bytes>>;
+
+enum State {
+    Idle,
+    Fetching(BoxFuture<'static, object_store::Result<Chunks>>),
+    Streaming(Chunks),
+}
+
+/// Reads the bytes in `range` of an object, fetching them on the first read.
+/// Seeking within the range restarts the fetch at the new position.
+struct ObjectReader {
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    range: Range<u64>,
+    pos: u64,
+    chunk: Bytes,
+    state: State,
+}
+
+impl ObjectReader {
+    fn new(store: Arc<dyn ObjectStore>, path: Path, range: Range<u64>) -> Self {
+        let pos = range.start;
+        ObjectReader { store, path, range, pos, chunk: Bytes::new(), state: State::Idle }
+    }
+
+    fn fetch(&self) -> BoxFuture<'static, object_store::Result<Chunks>> {
+        let (store, path) = (self.store.clone(), self.path.clone());
+        let range = GetRange::Bounded(self.pos as usize..self.range.end as usize);
+        async move {
+            let options = GetOptions { range: Some(range), ..Default::default() };
+            Ok(store.get_opts(&path, options).await?.into_stream())
+        }.boxed()
+    }
+}
+
+impl AsyncRead for ObjectReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if !this.chunk.is_empty() {
+                let n = this.chunk.len().min(buf.remaining());
+                buf.put_slice(&this.chunk[..n]);
+                this.chunk.advance(n);
+                this.pos += n as u64;
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.pos >= this.range.end {
+                return Poll::Ready(Ok(()));
+            }
+
+            let result = match this.state {
+                State::Idle => {
+                    this.state = State::Fetching(this.fetch());
+                    continue;
+                }
+                State::Fetching(ref mut fetch) => match ready!(fetch.poll_unpin(cx)) {
+                    Ok(chunks) => {
+                        this.state = State::Streaming(chunks);
+                        continue;
+                    }
+                    Err(e) => Err(io::Error::other(e)),
+                },
+                State::Streaming(ref mut chunks) => match ready!(chunks.poll_next_unpin(cx)) {
+                    Some(Ok(chunk)) => {
+                        this.chunk = chunk;
+                        continue;
+                    }
+                    Some(Err(e)) => Err(io::Error::other(e)),
+                    None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "object truncated")),
+                },
+            };
+
+            this.state = State::Idle;
+            return Poll::Ready(result);
+        }
+    }
+}
+
+impl AsyncSeek for ObjectReader {
+    fn start_seek(mut self: Pin<&mut Self>, position: io::SeekFrom) -> io::Result<()> {
+        let (base, offset) = match position {
+            io::SeekFrom::Start(n) => (self.range.start, n as i64),
+            io::SeekFrom::End(n) => (self.range.end, n),
+            io::SeekFrom::Current(n) => (self.pos, n),
+        };
+
+        let pos = base.checked_add_signed(offset)
+            .filter(|pos| *pos >= self.range.start)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek"))?;
+
+        if pos != self.pos {
+            self.pos = pos;
+            self.chunk = Bytes::new();
+            self.state = State::Idle;
+        }
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.pos - self.range.start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_range;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range(None, 100), Ok(None));
+        assert_eq!(parse_range(Some("bytes=0-9"), 100), Ok(Some(0..10)));
+        assert_eq!(parse_range(Some("bytes=90-"), 100), Ok(Some(90..100)));
+        assert_eq!(parse_range(Some("bytes=-10"), 100), Ok(Some(90..100)));
+        assert_eq!(parse_range(Some("bytes=-500"), 100), Ok(Some(0..100)));
+        assert_eq!(parse_range(Some("bytes=50-500"), 100), Ok(Some(50..100)));
+
+        assert_eq!(parse_range(Some("bytes=100-"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=200-300"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=-0"), 100), Err(()));
+        assert_eq!(parse_range(Some("bytes=0-"), 0), Err(()));
+
+        assert_eq!(parse_range(Some("bytes=9-0"), 100), Ok(None));
+        assert_eq!(parse_range(Some("bytes=0-1,5-6"), 100), Ok(None));
+        assert_eq!(parse_range(Some("items=0-9"), 100), Ok(None));
+        assert_eq!(parse_range(Some("bytes=a-b"), 100), Ok(None));
+    }
+}
@@ -0,0 +1,80 @@
+//! S3-compatible object storage for Rocket.
+//!
+//! This crate provides a [`Bucket`] handle to a bucket in an object store,
+//! configured via the application's configuration, a [`StoredFile`] responder
+//! that streams objects to clients with support for range requests, and
+//! helpers that stream request bodies and [`TempFile`]s directly to the store
+//! without buffering them on disk. Any store supported by the [`object_store`]
+//! crate can be used; [`Bucket::fairing()`] configures an S3-compatible one,
+//! including AWS S3, MinIO, and Cloudflare R2.
+//!
+//! [`TempFile`]: rocket::fs::TempFile
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_object_store = "0.1.0"
+//! ```
+//!
+//! Then configure the bucket in the `object_store` table of `Rocket.toml`, as
+//! described in [`Config`]:
+//!
+//! ```toml
+//! [default.object_store]
+//! bucket = "uploads"
+//! region = "eu-west-1"
+//! ```
+//!
+//! Finally, attach the fairing returned by [`Bucket::fairing()`] and use the
+//! [`Bucket`] request guard to store and retrieve objects:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::data::{Data, ToByteUnit};
+//! use rocket::form::Form;
+//! use rocket::fs::TempFile;
+//! use rocket_object_store::{Bucket, StoredFile, Error};
+//!
+//! #[put("/files/<key>", data = "<data>")]
+//! async fn put(key: &str, data: Data<'_>, bucket: Bucket) -> Result<(), Error> {
+//!     bucket.put_data(key, data, 256.mebibytes()).await?;
+//!     Ok(())
+//! }
+//!
+//! #[post("/avatars/<user>", data = "<file>")]
+//! async fn avatar(user: &str, file: Form<TempFile<'_>>, bucket: Bucket) -> Result<(), Error> {
+//!     bucket.put_temp_file(&format!("avatars/{user}"), &file).await?;
+//!     Ok(())
+//! }
+//!
+//! #[get("/files/<key>")]
+//! async fn get(key: &str, bucket: Bucket) -> Result<StoredFile, Error> {
+//!     bucket.get(key).await
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![put, avatar, get])
+//!         .attach(Bucket::fairing())
+//! }
+//! ```
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_object_store")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod bucket;
+mod error;
+mod file;
+
+pub use object_store;
+
+pub use bucket::{Bucket, Config};
+pub use error::Error;
+pub use file::StoredFile;
@@ -0,0 +1,124 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Config, Rocket, Build};
+use rocket::data::{Data, ToByteUnit};
+use rocket::figment::Figment;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket_object_store::{Bucket, StoredFile, Error};
+use rocket_object_store::object_store::memory::InMemory;
+
+#[put("/<key>", data = "<data>")]
+async fn put(key: &str, data: Data<'_>, bucket: Bucket) -> Result<String, Error> {
+    let len = bucket.put_data(key, data, 8.mebibytes()).await?;
+    Ok(len.to_string())
+}
+
+#[get("/<key>")]
+async fn get(key: &str, bucket: Bucket) -> Result<StoredFile, Error> {
+    bucket.get(key).await
+}
+
+#[delete("/<key>")]
+async fn delete(key: &str, bucket: Bucket) -> Result<(), Error> {
+    bucket.delete(key).await
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(Bucket::new(InMemory::new()))
+        .mount("/", routes![put, get, delete]);
+
+    Client::tracked(rocket).await.unwrap()
+}
+
+fn range(value: &str) -> Header<'static> {
+    Header::new("Range", value.to_string())
+}
+
+#[rocket::async_test]
+async fn objects_round_trip() {
+    let client = client().await;
+    let response = client.put("/hello.txt").body("Hello, world!").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "13");
+
+    let response = client.get("/hello.txt").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::Text));
+    assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+    assert!(response.headers().get_one("ETag").is_some());
+    assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+
+    let response = client.delete("/hello.txt").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/hello.txt").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn ranges_are_served() {
+    let client = client().await;
+    client.put("/hello.txt").body("Hello, world!").dispatch().await;
+
+    let response = client.get("/hello.txt").header(range("bytes=7-11")).dispatch().await;
+    assert_eq!(response.status(), Status::PartialContent);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 7-11/13"));
+    assert_eq!(response.into_string().await.unwrap(), "world");
+
+    let response = client.get("/hello.txt").header(range("bytes=-6")).dispatch().await;
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 7-12/13"));
+    assert_eq!(response.into_string().await.unwrap(), "world!");
+
+    let response = client.get("/hello.txt").header(range("bytes=13-")).dispatch().await;
+    assert_eq!(response.status(), Status::RangeNotSatisfiable);
+    assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */13"));
+
+    let response = client.get("/hello.txt").header(range("bytes=0-1,3-4")).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+}
+
+#[rocket::async_test]
+async fn large_uploads_are_multipart() {
+    let client = client().await;
+    let body: Vec<u8> = (0..6 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let response = client.put("/large.bin").body(&body).dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), body.len().to_string());
+
+    let response = client.get("/large.bin").dispatch().await;
+    assert_eq!(response.into_bytes().await.unwrap(), body);
+
+    let response = client.get("/large.bin").header(range("bytes=5242880-5242889")).dispatch().await;
+    assert_eq!(response.into_bytes().await.unwrap(), &body[5242880..5242890]);
+}
+
+#[rocket::async_test]
+async fn oversized_uploads_are_rejected() {
+    let client = client().await;
+    let body = vec![0u8; 9 * 1024 * 1024];
+    let response = client.put("/huge.bin").body(&body).dispatch().await;
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    let response = client.get("/huge.bin").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+fn configured(config: Figment) -> Rocket<Build> {
+    rocket::custom(Figment::from(Config::debug_default()).merge(config))
+        .attach(Bucket::fairing())
+}
+
+#[rocket::async_test]
+async fn fairing_reads_config() {
+    let figment = Figment::new()
+        .merge(("object_store.bucket", "uploads"))
+        .merge(("object_store.endpoint", "http://localhost:9000"))
+        .merge(("object_store.allow_http", true));
+
+    let rocket = configured(figment).ignite().await.unwrap();
+    assert!(rocket.state::<Bucket>().is_some());
+
+    let rocket = configured(Figment::new()).ignite().await;
+    assert!(rocket.is_err());
+}
@@ -0,0 +1,374 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::tokio::time::Instant;
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The configuration of a [`CircuitBreaker`].
+///
+/// Breakers configured via [`Breakers::fairing()`](crate::Breakers::fairing())
+/// read their configuration from the `breakers` table of the application's
+/// configuration, keyed by breaker name. Missing values take their defaults:
+///
+/// ```toml
+/// [default.breakers.payments]
+/// failure_rate = 0.25
+/// minimum_calls = 20
+/// window = 50
+/// open_for = 10
+/// half_open_calls = 2
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct BreakerConfig {
+    /// The fraction, from `0` to `1`, of failed calls in the window at or
+    /// above which the breaker opens. Defaults to `0.5`.
+    pub failure_rate: f64,
+    /// The minimum number of calls in the window before the breaker can open.
+    /// Defaults to `10`.
+    pub minimum_calls: u32,
+    /// The number of most recent calls the failure rate is computed over.
+    /// Defaults to `20`.
+    pub window: u32,
+    /// The number of seconds an open breaker rejects calls before allowing
+    /// probe calls. Defaults to `30`.
+    pub open_for: f64,
+    /// The number of probe calls allowed, and that must succeed, while the
+    /// breaker is half-open before it closes. Defaults to `1`.
+    pub half_open_calls: u32,
+}
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum State {
+    /// Calls are allowed and their outcomes tracked.
+    Closed,
+    /// Calls are rejected without being made.
+    Open,
+    /// A limited number of probe calls are allowed to test for recovery.
+    HalfOpen,
+}
+
+/// A snapshot of the metrics of a [`CircuitBreaker`].
+///
+/// Counters are totals since the breaker was created. `Stats` serializes to a
+/// map of its fields, making it suitable for exporting to a metrics system or
+/// reporting from an admin endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde")]
+#[non_exhaustive]
+pub struct Stats {
+    /// The breaker's current state.
+    pub state: State,
+    /// The number of calls that completed.
+    pub calls: u64,
+    /// The number of calls that completed with an error.
+    pub failures: u64,
+    /// The number of calls rejected because the breaker was open.
+    pub rejected: u64,
+    /// The failure rate over the current window.
+    pub failure_rate: f64,
+}
+
+/// A circuit breaker for calls to a fallible dependency.
+///
+/// A breaker wraps calls to a dependency, like a remote API, via
+/// [`CircuitBreaker::call()`]. While _closed_, calls are made and their
+/// outcomes recorded. Once at least
+/// [`minimum_calls`](BreakerConfig::minimum_calls) of the last
+/// [`window`](BreakerConfig::window) calls were made and the fraction that
+/// failed reaches [`failure_rate`](BreakerConfig::failure_rate), the breaker
+/// _opens_: calls are rejected with [`Error::Open`] without being made, giving
+/// the dependency time to recover and failing fast instead of waiting on it.
+///
+/// After [`open_for`](BreakerConfig::open_for) seconds, the breaker becomes
+/// _half-open_ and allows up to
+/// [`half_open_calls`](BreakerConfig::half_open_calls) probe calls. If they
+/// all succeed, the breaker closes. If any fails, it opens again.
+///
+/// State transitions are logged: opening at the `warn` level and recovery at
+/// the `info` level. [`CircuitBreaker::stats()`] returns metrics.
+///
+/// Cloning a breaker is cheap and yields a handle to the same breaker. To
+/// share breakers across requests, use [`Breakers`](crate::Breakers) or place
+/// a breaker in managed state.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_resilience::{CircuitBreaker, Error};
+///
+/// # async fn fetch_rates() -> Result<String, std::io::Error> { Ok("1.0".into()) }
+/// # rocket::async_test(async {
+/// let breaker = CircuitBreaker::new("rates");
+/// match breaker.call(fetch_rates()).await {
+///     Ok(rates) => println!("rates: {rates}"),
+///     Err(Error::Open) => println!("rates are unavailable"),
+///     Err(Error::Inner(e)) => println!("failed to fetch rates: {e}"),
+/// }
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    name: String,
+    config: BreakerConfig,
+    machine: Mutex<Machine>,
+}
+
+struct Machine {
+    phase: Phase,
+    window: VecDeque<bool>,
+    calls: u64,
+    failures: u64,
+    rejected: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { in_flight: u32, successes: u32 },
+}
+
+/// Admission of a call. Releases a probe slot if the call is cancelled.
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    done: bool,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        BreakerConfig {
+            failure_rate: 0.5,
+            minimum_calls: 10,
+            window: 20,
+            open_for: 30.0,
+            half_open_calls: 1,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a closed breaker named `name` with the default configuration.
+    /// The name identifies the breaker in logs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::{CircuitBreaker, State};
+    ///
+    /// let breaker = CircuitBreaker::new("payments");
+    /// assert_eq!(breaker.state(), State::Closed);
+    /// ```
+    pub fn new(name: impl Into<String>) -> Self {
+        CircuitBreaker::with_config(name, BreakerConfig::default())
+    }
+
+    /// Creates a closed breaker named `name` configured by `config`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::{CircuitBreaker, BreakerConfig};
+    ///
+    /// let config = BreakerConfig { failure_rate: 0.25, ..Default::default() };
+    /// let breaker = CircuitBreaker::with_config("payments", config);
+    /// ```
+    pub fn with_config(name: impl Into<String>, config: BreakerConfig) -> Self {
+        let machine = Machine {
+            phase: Phase::Closed,
+            window: VecDeque::with_capacity(config.window as usize),
+            calls: 0,
+            failures: 0,
+            rejected: 0,
+        };
+
+        CircuitBreaker {
+            inner: Arc::new(Inner { name: name.into(), config, machine: Mutex::new(machine) })
+        }
+    }
+
+    /// Returns the breaker's name.
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// Returns the breaker's configuration.
+    pub fn config(&self) -> &BreakerConfig {
+        &self.inner.config
+    }
+
+    /// Returns the breaker's current state.
+    ///
+    /// An open breaker whose [`open_for`](BreakerConfig::open_for) period has
+    /// elapsed is reported as half-open.
+    pub fn state(&self) -> State {
+        match self.inner.machine.lock().expect("breaker lock").phase {
+            Phase::Closed => State::Closed,
+            Phase::Open { until } if Instant::now() < until => State::Open,
+            Phase::Open { .. } | Phase::HalfOpen { .. } => State::HalfOpen,
+        }
+    }
+
+    /// Returns a snapshot of the breaker's metrics.
+    pub fn stats(&self) -> Stats {
+        let state = self.state();
+        let machine = self.inner.machine.lock().expect("breaker lock");
+        Stats {
+            state,
+            calls: machine.calls,
+            failures: machine.failures,
+            rejected: machine.rejected,
+            failure_rate: machine.failure_rate(),
+        }
+    }
+
+    /// Awaits `call` if the breaker allows it and records its outcome: `Ok`
+    /// is a success, `Err` a failure. Returns [`Error::Open`] without awaiting
+    /// `call` if the breaker rejects it.
+    ///
+    /// If the returned future is dropped before `call` completes, no outcome
+    /// is recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::{CircuitBreaker, Error};
+    ///
+    /// # rocket::async_test(async {
+    /// let breaker = CircuitBreaker::new("payments");
+    /// let result = breaker.call(async { Err::<(), _>("declined") }).await;
+    /// assert!(matches!(result, Err(Error::Inner("declined"))));
+    /// assert_eq!(breaker.stats().failures, 1);
+    /// # });
+    /// ```
+    pub async fn call<F, T, E>(&self, call: F) -> Result<T, Error<E>>
+        where F: Future<Output = Result<T, E>>
+    {
+        let mut permit = self.admit().ok_or(Error::Open)?;
+        let result = call.await;
+        permit.record(result.is_ok());
+        result.map_err(Error::Inner)
+    }
+
+    fn admit(&self) -> Option<Permit<'_>> {
+        let config = &self.inner.config;
+        let mut guard = self.inner.machine.lock().expect("breaker lock");
+        let machine = &mut *guard;
+        let probe = match machine.phase {
+            Phase::Closed => false,
+            Phase::Open { until } if Instant::now() < until => {
+                machine.rejected += 1;
+                return None;
+            }
+            Phase::Open { .. } => {
+                info!(breaker = self.name(), "circuit breaker half-open: probing");
+                machine.phase = Phase::HalfOpen { in_flight: 1, successes: 0 };
+                true
+            }
+            Phase::HalfOpen { ref mut in_flight, .. } if *in_flight < config.half_open_calls => {
+                *in_flight += 1;
+                true
+            }
+            Phase::HalfOpen { .. } => {
+                machine.rejected += 1;
+                return None;
+            }
+        };
+
+        Some(Permit { breaker: self, probe, done: false })
+    }
+
+    fn open(&self, machine: &mut Machine) {
+        let open_for = Duration::from_secs_f64(self.inner.config.open_for.max(0.0));
+        warn!(
+            breaker = self.name(),
+            failure_rate = machine.failure_rate(),
+            open_for_secs = open_for.as_secs_f64(),
+            "circuit breaker opened"
+        );
+
+        machine.phase = Phase::Open { until: Instant::now() + open_for };
+    }
+}
+
+impl Machine {
+    fn failure_rate(&self) -> f64 {
+        match self.window.len() {
+            0 => 0.0,
+            n => self.window.iter().filter(|success| !**success).count() as f64 / n as f64,
+        }
+    }
+}
+
+impl Permit<'_> {
+    fn record(&mut self, success: bool) {
+        self.done = true;
+        let breaker = self.breaker;
+        let config = &breaker.inner.config;
+        let mut guard = breaker.inner.machine.lock().expect("breaker lock");
+        let machine = &mut *guard;
+        machine.calls += 1;
+        machine.failures += u64::from(!success);
+
+        match machine.phase {
+            Phase::Closed => {
+                if machine.window.len() >= config.window.max(1) as usize {
+                    machine.window.pop_front();
+                }
+
+                machine.window.push_back(success);
+                let calls = machine.window.len() as u32;
+                if calls >= config.minimum_calls && machine.failure_rate() >= config.failure_rate {
+                    breaker.open(machine);
+                }
+            }
+            Phase::HalfOpen { ref mut in_flight, ref mut successes } if self.probe => {
+                *in_flight = in_flight.saturating_sub(1);
+                *successes += u32::from(success);
+                if !success {
+                    breaker.open(machine);
+                } else if *successes >= config.half_open_calls {
+                    info!(breaker = breaker.name(), "circuit breaker closed: recovered");
+                    machine.phase = Phase::Closed;
+                    machine.window.clear();
+                }
+            }
+            // The call was admitted before the breaker last changed state.
+            Phase::HalfOpen { .. } | Phase::Open { .. } => {}
+        }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.done || !self.probe {
+            return;
+        }
+
+        let mut machine = self.breaker.inner.machine.lock().expect("breaker lock");
+        if let Phase::HalfOpen { ref mut in_flight, .. } = machine.phase {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+    }
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("name", &self.inner.name)
+            .field("config", &self.inner.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, Responder};
+
+/// The error returned by [`CircuitBreaker::call()`](crate::CircuitBreaker::call()).
+///
+/// As a [`Responder`], [`Error::Open`] responds with `503 Service
+/// Unavailable` while [`Error::Inner`] responds with the inner error.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The breaker is open: the call was rejected without being made.
+    Open,
+    /// The call was made and failed with this error.
+    Inner(E),
+}
+
+impl<E> Error<E> {
+    /// Returns `true` if the call was rejected because the breaker is open.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::Error;
+    ///
+    /// assert!(Error::<()>::Open.is_open());
+    /// assert!(!Error::Inner(()).is_open());
+    /// ```
+    pub fn is_open(&self) -> bool {
+        matches!(self, Error::Open)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Open => f.write_str("circuit breaker is open"),
+            Error::Inner(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E> std::error::Error for Error<E>
+    where E: std::error::Error + 'static
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Open => None,
+            Error::Inner(e) => Some(e),
+        }
+    }
+}
+
+impl<'r, 'o: 'r, E: Responder<'r, 'o>> Responder<'r, 'o> for Error<E> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            Error::Open => Err(Status::ServiceUnavailable),
+            Error::Inner(e) => e.respond_to(req),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Circuit breakers and retry policies for outbound calls.
+//!
+//! Handlers often call out to other services: payment providers, internal
+//! APIs, search clusters. When such a dependency fails or slows down, naive
+//! calls pile up, tie up workers, and turn one failure into many. This crate
+//! provides two primitives for making these calls resilient:
+//!
+//!   * A [`CircuitBreaker`] tracks the failure rate of calls to a dependency
+//!     and, once it is too high, rejects calls outright for a while, failing
+//!     fast and giving the dependency time to recover. It then probes the
+//!     dependency with a limited number of calls before resuming normal
+//!     operation.
+//!   * A [`Retry`] policy retries failed calls with exponential backoff and
+//!     jitter.
+//!
+//! Both wrap arbitrary futures resolving to a `Result` and log their decisions
+//! via Rocket's tracing. Breakers report [`Stats`] suitable for metrics.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_resilience = "0.1.0"
+//! ```
+//!
+//! Then attach the fairing returned by [`Breakers::fairing()`], which places a
+//! registry of named breakers in managed state, and wrap outbound calls:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::State;
+//! use rocket_resilience::{Breakers, Error, Retry};
+//!
+//! # mod payments {
+//! #     pub async fn charge(_: u64) -> Result<String, String> { Ok("ok".into()) }
+//! # }
+//! #[post("/charge/<cents>")]
+//! async fn charge(cents: u64, breakers: &State<Breakers>) -> Result<String, Error<String>> {
+//!     breakers.get("payments")
+//!         .call(Retry::new(3).run(|| payments::charge(cents)))
+//!         .await
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![charge])
+//!         .attach(Breakers::fairing())
+//! }
+//! ```
+//!
+//! While the `payments` breaker is open, the route responds with `503 Service
+//! Unavailable` without calling the payment provider. Breakers are configured
+//! by name in the `breakers` table of `Rocket.toml`; see [`BreakerConfig`]:
+//!
+//! ```toml
+//! [default.breakers.payments]
+//! failure_rate = 0.25
+//! open_for = 10
+//! ```
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_resilience")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod breaker;
+mod error;
+mod registry;
+mod retry;
+
+pub use breaker::{CircuitBreaker, BreakerConfig, State, Stats};
+pub use error::Error;
+pub use registry::Breakers;
+pub use retry::Retry;
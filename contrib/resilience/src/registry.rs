@@ -0,0 +1,141 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use rocket::fairing::{AdHoc, Fairing};
+
+use crate::{BreakerConfig, CircuitBreaker, Stats};
+
+/// A registry of named [`CircuitBreaker`]s, available as managed state.
+///
+/// The registry is placed in managed state by the fairing returned from
+/// [`Breakers::fairing()`]. [`Breakers::get()`] returns the breaker with a
+/// given name, creating it on first use from its configuration in the
+/// `breakers` table of the application's configuration or, if it has none,
+/// from the default [`BreakerConfig`]. Cloning a `Breakers` is cheap.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket_resilience::{Breakers, Error};
+///
+/// # async fn quote() -> Result<String, std::io::Error> { Ok("42".into()) }
+/// #[get("/quote")]
+/// async fn get_quote(breakers: &State<Breakers>) -> Result<String, Error<String>> {
+///     breakers.get("quotes")
+///         .call(async { quote().await.map_err(|e| e.to_string()) })
+///         .await
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![get_quote])
+///         .attach(Breakers::fairing())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Breakers {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    configs: HashMap<String, BreakerConfig>,
+    breakers: RwLock<HashMap<String, CircuitBreaker>>,
+}
+
+impl Breakers {
+    /// Returns a fairing that reads breaker configurations from the
+    /// `breakers` table of the application's configuration and places a
+    /// `Breakers` in managed state. A missing `breakers` table is treated as
+    /// an empty one. Ignition fails if the table is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_resilience::Breakers;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Breakers::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("Circuit Breakers", |rocket| async move {
+            let configs = match rocket.figment().extract_inner("breakers") {
+                Ok(configs) => configs,
+                Err(e) if e.missing() => HashMap::new(),
+                Err(e) => {
+                    error!("invalid circuit breaker configuration: {e}");
+                    return Err(rocket);
+                }
+            };
+
+            Ok(rocket.manage(Breakers::new(configs)))
+        })
+    }
+
+    /// Creates a registry that configures breakers from `configs`, keyed by
+    /// breaker name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use rocket_resilience::{Breakers, BreakerConfig};
+    ///
+    /// let config = BreakerConfig { minimum_calls: 5, ..Default::default() };
+    /// let breakers = Breakers::new(HashMap::from([("payments".into(), config)]));
+    /// assert_eq!(breakers.get("payments").config().minimum_calls, 5);
+    /// ```
+    pub fn new(configs: HashMap<String, BreakerConfig>) -> Self {
+        Breakers { inner: Arc::new(Inner { configs, breakers: RwLock::default() }) }
+    }
+
+    /// Returns the breaker named `name`, creating it if it doesn't exist.
+    pub fn get(&self, name: &str) -> CircuitBreaker {
+        if let Some(breaker) = self.inner.breakers.read().expect("breakers lock").get(name) {
+            return breaker.clone();
+        }
+
+        let mut breakers = self.inner.breakers.write().expect("breakers lock");
+        breakers.entry(name.to_string())
+            .or_insert_with(|| {
+                let config = self.inner.configs.get(name).cloned().unwrap_or_default();
+                CircuitBreaker::with_config(name, config)
+            })
+            .clone()
+    }
+
+    /// Returns the [`Stats`] of every breaker created so far, keyed by name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::{Breakers, State};
+    ///
+    /// let breakers = Breakers::default();
+    /// breakers.get("payments");
+    ///
+    /// let stats = breakers.stats();
+    /// assert_eq!(stats["payments"].state, State::Closed);
+    /// ```
+    pub fn stats(&self) -> BTreeMap<String, Stats> {
+        self.inner.breakers.read().expect("breakers lock")
+            .iter()
+            .map(|(name, breaker)| (name.clone(), breaker.stats()))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for Breakers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Breakers")
+            .field("configs", &self.inner.configs)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
@@ -0,0 +1,193 @@
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+use rocket::tokio::time::sleep;
+
+/// A policy for retrying failed calls with exponential backoff.
+///
+/// A call is made up to [`attempts`](Retry::new()) times, until it succeeds.
+/// Before retry `n`, the policy waits [`backoff`](Retry::backoff())
+/// multiplied by `2^(n - 1)`, capped at [`max_backoff`](Retry::max_backoff()).
+/// With [`jitter`](Retry::jitter()), enabled by default, each wait is instead
+/// chosen at random between half of and the full delay, preventing clients
+/// that failed together from retrying in lockstep.
+///
+/// Retries are logged at the `debug` level.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket_resilience::Retry;
+///
+/// # async fn fetch() -> Result<String, std::io::Error> { Ok("".into()) }
+/// # rocket::async_test(async {
+/// let retry = Retry::new(3)
+///     .backoff(Duration::from_millis(50))
+///     .max_backoff(Duration::from_secs(1));
+///
+/// let body = retry.run(|| fetch()).await;
+/// # });
+/// ```
+///
+/// # Circuit Breakers
+///
+/// Retries compose with [`CircuitBreaker`](crate::CircuitBreaker)s in either
+/// order. Retrying _within_ a breaker call counts all attempts as one call:
+///
+/// ```rust
+/// use rocket_resilience::{CircuitBreaker, Retry};
+///
+/// # async fn fetch() -> Result<String, std::io::Error> { Ok("".into()) }
+/// # rocket::async_test(async {
+/// let breaker = CircuitBreaker::new("upstream");
+/// let body = breaker.call(Retry::new(3).run(|| fetch())).await;
+/// # });
+/// ```
+///
+/// Calling the breaker within each attempt counts every attempt. Use
+/// [`Retry::run_if()`] to stop retrying once the breaker opens:
+///
+/// ```rust
+/// use rocket_resilience::{CircuitBreaker, Retry};
+///
+/// # async fn fetch() -> Result<String, std::io::Error> { Ok("".into()) }
+/// # rocket::async_test(async {
+/// let breaker = CircuitBreaker::new("upstream");
+/// let body = Retry::new(3)
+///     .run_if(|| breaker.call(fetch()), |e| !e.is_open())
+///     .await;
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Retry {
+    attempts: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+}
+
+impl Retry {
+    /// Creates a policy that makes up to `attempts` attempts, including the
+    /// first, with a backoff of 100ms, a maximum backoff of 10s, and jitter.
+    /// An `attempts` of `0` is treated as `1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::Retry;
+    ///
+    /// let retry = Retry::new(5);
+    /// ```
+    pub fn new(attempts: u32) -> Self {
+        Retry {
+            attempts: attempts.max(1),
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+
+    /// Sets the delay before the first retry to `backoff`.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Caps the delay before any retry to `max_backoff`.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Enables or disables jitter.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the maximum delay before retry `n`, where the first retry, the
+    /// second attempt, is retry `1`. With jitter, the actual delay is between
+    /// half of and the returned delay.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_resilience::Retry;
+    ///
+    /// let retry = Retry::new(10)
+    ///     .backoff(Duration::from_millis(100))
+    ///     .max_backoff(Duration::from_millis(500));
+    ///
+    /// assert_eq!(retry.delay(1), Duration::from_millis(100));
+    /// assert_eq!(retry.delay(2), Duration::from_millis(200));
+    /// assert_eq!(retry.delay(3), Duration::from_millis(400));
+    /// assert_eq!(retry.delay(4), Duration::from_millis(500));
+    /// ```
+    pub fn delay(&self, n: u32) -> Duration {
+        let factor = 2u32.saturating_pow(n.saturating_sub(1));
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Calls `f` until the future it returns resolves to `Ok` or the attempts
+    /// are exhausted, returning the last result.
+    pub async fn run<F, Fut, T, E>(&self, f: F) -> Result<T, E>
+        where F: FnMut() -> Fut, Fut: Future<Output = Result<T, E>>
+    {
+        self.run_if(f, |_| true).await
+    }
+
+    /// Like [`Retry::run()`], but only retries errors for which `retryable`
+    /// returns `true`. Other errors are returned immediately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_resilience::Retry;
+    ///
+    /// # async fn send() -> Result<(), std::io::Error> { Ok(()) }
+    /// # rocket::async_test(async {
+    /// use std::io::ErrorKind;
+    ///
+    /// let result = Retry::new(3)
+    ///     .run_if(|| send(), |e| e.kind() != ErrorKind::PermissionDenied)
+    ///     .await;
+    /// # });
+    /// ```
+    pub async fn run_if<F, Fut, T, E, P>(&self, mut f: F, retryable: P) -> Result<T, E>
+        where F: FnMut() -> Fut,
+              Fut: Future<Output = Result<T, E>>,
+              P: Fn(&E) -> bool
+    {
+        let mut attempt = 1;
+        loop {
+            match f().await {
+                Err(e) if attempt < self.attempts && retryable(&e) => {
+                    let delay = self.jittered(self.delay(attempt));
+                    debug!(attempt, delay_ms = delay.as_millis() as u64, "retrying failed call");
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+
+        let half = delay / 2;
+        half + rand::thread_rng().gen_range(Duration::ZERO..=delay - half)
+    }
+}
+
+impl Default for Retry {
+    /// Returns [`Retry::new(3)`](Retry::new()).
+    fn default() -> Self {
+        Retry::new(3)
+    }
+}
@@ -0,0 +1,133 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rocket::State as Managed;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::tokio::time::sleep;
+use rocket_resilience::{Breakers, BreakerConfig, CircuitBreaker, Error, Retry, State};
+
+fn config() -> BreakerConfig {
+    BreakerConfig {
+        failure_rate: 0.5,
+        minimum_calls: 4,
+        window: 4,
+        open_for: 0.05,
+        half_open_calls: 1,
+    }
+}
+
+async fn call(breaker: &CircuitBreaker, calls: &AtomicUsize, ok: bool) -> Result<(), Error<()>> {
+    breaker.call(async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        if ok { Ok(()) } else { Err(()) }
+    }).await
+}
+
+#[rocket::async_test]
+async fn breaker_opens_probes_and_closes() {
+    let breaker = CircuitBreaker::with_config("test", config());
+    let calls = AtomicUsize::new(0);
+
+    for ok in [true, true, false] {
+        let _ = call(&breaker, &calls, ok).await;
+        assert_eq!(breaker.state(), State::Closed);
+    }
+
+    assert!(matches!(call(&breaker, &calls, false).await, Err(Error::Inner(()))));
+    assert_eq!(breaker.state(), State::Open);
+
+    assert!(matches!(call(&breaker, &calls, true).await, Err(Error::Open)));
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+    let stats = breaker.stats();
+    assert_eq!((stats.calls, stats.failures, stats.rejected), (4, 2, 1));
+
+    // A failed probe reopens the breaker.
+    sleep(Duration::from_millis(60)).await;
+    assert_eq!(breaker.state(), State::HalfOpen);
+    assert!(call(&breaker, &calls, false).await.is_err());
+    assert_eq!(breaker.state(), State::Open);
+
+    // A successful probe closes it.
+    sleep(Duration::from_millis(60)).await;
+    assert!(call(&breaker, &calls, true).await.is_ok());
+    assert_eq!(breaker.state(), State::Closed);
+    assert_eq!(breaker.stats().failure_rate, 0.0);
+}
+
+#[rocket::async_test]
+async fn breaker_needs_minimum_calls() {
+    let breaker = CircuitBreaker::with_config("test", config());
+    let calls = AtomicUsize::new(0);
+    for _ in 0..3 {
+        let _ = call(&breaker, &calls, false).await;
+    }
+
+    assert_eq!(breaker.state(), State::Closed);
+    let _ = call(&breaker, &calls, false).await;
+    assert_eq!(breaker.state(), State::Open);
+}
+
+#[rocket::async_test]
+async fn retry_retries_until_success_or_exhaustion() {
+    let retry = Retry::new(3).backoff(Duration::from_millis(1));
+
+    let attempts = AtomicUsize::new(0);
+    let result = retry.run(|| async {
+        match attempts.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err("failed"),
+            _ => Ok("done"),
+        }
+    }).await;
+
+    assert_eq!(result, Ok("done"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    let attempts = AtomicUsize::new(0);
+    let result = retry.run(|| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>("failed")
+    }).await;
+
+    assert_eq!(result, Err("failed"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    let attempts = AtomicUsize::new(0);
+    let result = retry.run_if(|| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>("fatal")
+    }, |e| *e != "fatal").await;
+
+    assert_eq!(result, Err("fatal"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[post("/flaky/<ok>")]
+async fn flaky(ok: bool, breakers: &Managed<Breakers>) -> Result<&'static str, Error<Status>> {
+    breakers.get("flaky")
+        .call(async move { if ok { Ok("ok") } else { Err(Status::BadGateway) } })
+        .await
+}
+
+#[rocket::async_test]
+async fn open_breakers_respond_with_503() {
+    let figment = rocket::Config::figment()
+        .merge(("breakers.flaky.minimum_calls", 2))
+        .merge(("breakers.flaky.window", 2));
+
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![flaky])
+        .attach(Breakers::fairing());
+
+    let client = Client::tracked(rocket).await.unwrap();
+    assert_eq!(client.post("/flaky/true").dispatch().await.status(), Status::Ok);
+    assert_eq!(client.post("/flaky/false").dispatch().await.status(), Status::BadGateway);
+    assert_eq!(client.post("/flaky/true").dispatch().await.status(), Status::ServiceUnavailable);
+
+    let breakers = client.rocket().state::<Breakers>().unwrap();
+    assert_eq!(breakers.stats()["flaky"].state, State::Open);
+    assert_eq!(breakers.get("flaky").config().open_for, 30.0);
+}
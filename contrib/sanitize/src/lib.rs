@@ -0,0 +1,79 @@
+//! Allowlist-based sanitization of user-provided HTML.
+//!
+//! Templates escape the values they render, which is the right default but
+//! the wrong behavior for content that is _meant_ to be HTML, like rich text
+//! from a WYSIWYG editor or rendered Markdown. Rendering such content
+//! unescaped is unsafe: it may contain scripts, event handlers, or
+//! `javascript:` links. This crate makes it safe to render by sanitizing it,
+//! removing everything not explicitly allowed by a [`Policy`]. Sanitization is
+//! performed by [`ammonia`].
+//!
+//! The crate provides:
+//!
+//!   * [`Sanitizer`], which sanitizes HTML according to a [`Policy`], and a
+//!     fairing, [`Sanitizer::fairing()`], that configures one from
+//!     `Rocket.toml` and places it in managed state;
+//!   * [`SanitizedHtml`], a responder that sanitizes before responding;
+//!   * a `sanitize` template filter for `tera`, `handlebars`, and `minijinja`,
+//!     each enabled by the feature of the same name.
+//!
+//! # Usage
+//!
+//! Depend on the crate, enabling the feature for your template engine, if any:
+//!
+//! ```toml
+//! [dependencies.rocket_sanitize]
+//! version = "0.1.0"
+//! features = ["tera"]
+//! ```
+//!
+//! Then respond with [`SanitizedHtml`] or, in templates, use the filter:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_sanitize::{Sanitizer, SanitizedHtml};
+//!
+//! #[post("/preview", data = "<body>")]
+//! fn preview(body: String) -> SanitizedHtml<String> {
+//!     SanitizedHtml(body)
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![preview])
+//!         .attach(Sanitizer::fairing())
+//! }
+//! ```
+//!
+//! With `rocket_dyn_templates`, register the filter when customizing engines:
+//!
+//! ```rust,ignore
+//! use rocket_dyn_templates::Template;
+//! use rocket_sanitize::Sanitizer;
+//!
+//! let templates = Template::custom(|engines| {
+//!     Sanitizer::default().register_tera(&mut engines.tera);
+//! });
+//! ```
+//!
+//! ```html
+//! <article>{{ post.body | sanitize }}</article>
+//! ```
+//!
+//! The filter's output is marked safe and isn't escaped again. See [`Policy`]
+//! for the default policy and how to configure it.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_sanitize")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod policy;
+mod response;
+mod sanitizer;
+
+pub use policy::Policy;
+pub use response::SanitizedHtml;
+pub use sanitizer::Sanitizer;
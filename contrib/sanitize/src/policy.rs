@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use rocket::serde::{Deserialize, Serialize};
+
+/// What a [`Sanitizer`](crate::Sanitizer) allows.
+///
+/// A policy is an allowlist: tags, attributes, and URL schemes that aren't
+/// listed are removed. Disallowed tags are removed but their contents kept,
+/// except for `script` and `style` elements, which are removed entirely unless
+/// allowed. Text is always escaped as needed.
+///
+/// Policies configured via [`Sanitizer::fairing()`](crate::Sanitizer::fairing())
+/// are read from the `sanitize` table of the application's configuration.
+/// Missing values take their defaults:
+///
+/// ```toml
+/// [default.sanitize]
+/// tags = ["p", "br", "a", "strong", "em", "ul", "ol", "li"]
+/// url_schemes = ["https", "mailto"]
+/// link_rel = "noopener noreferrer nofollow"
+/// strip_comments = true
+///
+/// [default.sanitize.attributes]
+/// a = ["href", "title"]
+/// "*" = ["lang"]
+/// ```
+///
+/// The default policy allows common rich text formatting, links, images,
+/// and tables, with no styling, classes, or event handlers.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct Policy {
+    /// The allowed tags.
+    pub tags: BTreeSet<String>,
+    /// The allowed attributes, keyed by the tag they're allowed on. Attributes
+    /// keyed by `*` are allowed on every allowed tag.
+    pub attributes: BTreeMap<String, BTreeSet<String>>,
+    /// The allowed schemes of URLs in attributes like `href` and `src`.
+    /// Relative URLs are always allowed.
+    pub url_schemes: BTreeSet<String>,
+    /// The `rel` attribute set on every link, overriding any present, or
+    /// `None` to leave links as they are. Defaults to
+    /// `noopener noreferrer nofollow`, preventing user content from
+    /// influencing search ranking or accessing the opening page.
+    pub link_rel: Option<String>,
+    /// Whether HTML comments are removed. Defaults to `true`.
+    pub strip_comments: bool,
+}
+
+const DEFAULT_TAGS: &[&str] = &[
+    "a", "abbr", "b", "blockquote", "br", "code", "del", "em", "h1", "h2", "h3",
+    "h4", "h5", "h6", "hr", "i", "img", "ins", "kbd", "li", "ol", "p", "pre", "s",
+    "strong", "sub", "sup", "table", "tbody", "td", "th", "thead", "tr", "u", "ul",
+];
+
+const DEFAULT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href", "title"]),
+    ("abbr", &["title"]),
+    ("img", &["src", "alt", "title", "width", "height"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan"]),
+];
+
+const DEFAULT_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+impl Policy {
+    /// A policy that allows no tags at all: markup is removed, leaving only
+    /// text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sanitize::{Policy, Sanitizer};
+    ///
+    /// let sanitizer = Sanitizer::new(Policy::text());
+    /// assert_eq!(sanitizer.clean("<b>bold</b> & <i>brash</i>"), "bold &amp; brash");
+    /// ```
+    pub fn text() -> Self {
+        Policy {
+            tags: BTreeSet::new(),
+            attributes: BTreeMap::new(),
+            url_schemes: BTreeSet::new(),
+            link_rel: None,
+            strip_comments: true,
+        }
+    }
+
+    /// Allows `tag`.
+    pub fn allow_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Allows the attribute `attr` on `tag`, or on every tag if `tag` is
+    /// `*`. Does not allow `tag` itself.
+    pub fn allow_attribute(mut self, tag: impl Into<String>, attr: impl Into<String>) -> Self {
+        self.attributes.entry(tag.into()).or_default().insert(attr.into());
+        self
+    }
+
+    /// Allows URLs with the scheme `scheme`.
+    pub fn allow_url_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.url_schemes.insert(scheme.into());
+        self
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        fn strings(list: &[&str]) -> BTreeSet<String> {
+            list.iter().map(|s| s.to_string()).collect()
+        }
+
+        Policy {
+            tags: strings(DEFAULT_TAGS),
+            attributes: DEFAULT_ATTRIBUTES.iter()
+                .map(|(tag, attrs)| (tag.to_string(), strings(attrs)))
+                .collect(),
+            url_schemes: strings(DEFAULT_URL_SCHEMES),
+            link_rel: Some("noopener noreferrer nofollow".into()),
+            strip_comments: true,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use rocket::request::Request;
+use rocket::response::{self, Responder, content::RawHtml};
+
+use crate::Sanitizer;
+
+/// A responder that sanitizes HTML before responding with it.
+///
+/// The wrapped value is sanitized with the [`Sanitizer`] in managed state, if
+/// there is one, and a sanitizer with the default [`Policy`](crate::Policy)
+/// otherwise. The response has a content type of `text/html`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_sanitize::SanitizedHtml;
+///
+/// # struct Post { body: String }
+/// # async fn load_post(_: u64) -> Option<Post> { None }
+/// #[get("/posts/<id>/body")]
+/// async fn post_body(id: u64) -> Option<SanitizedHtml<String>> {
+///     let post = load_post(id).await?;
+///     Some(SanitizedHtml(post.body))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizedHtml<T>(pub T);
+
+impl<'r, T: AsRef<str>> Responder<'r, 'static> for SanitizedHtml<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let html = match req.rocket().state::<Sanitizer>() {
+            Some(sanitizer) => sanitizer.clean(self.0.as_ref()),
+            None => Sanitizer::default().clean(self.0.as_ref()),
+        };
+
+        RawHtml(html).respond_to(req)
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use rocket::fairing::{AdHoc, Fairing};
+
+use crate::Policy;
+
+/// Sanitizes HTML according to a [`Policy`].
+///
+/// A `Sanitizer` is created from a policy via [`Sanitizer::new()`] or, with
+/// the default policy, via [`Sanitizer::default()`]. The fairing returned by
+/// [`Sanitizer::fairing()`] places a `Sanitizer` with the configured policy in
+/// managed state, where it is used by [`SanitizedHtml`](crate::SanitizedHtml).
+/// Cloning a `Sanitizer` is cheap.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_sanitize::Sanitizer;
+///
+/// let sanitizer = Sanitizer::default();
+/// let html = r#"<p onclick="steal()">Hi <a href="javascript:steal()">there</a>!</p>
+///     <script>steal()</script>"#;
+///
+/// let clean = sanitizer.clean(html);
+/// assert_eq!(clean.trim(), r#"<p>Hi <a rel="noopener noreferrer nofollow">there</a>!</p>"#);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sanitizer {
+    policy: Arc<Policy>,
+}
+
+impl Sanitizer {
+    /// Returns a fairing that reads a [`Policy`] from the `sanitize` table of
+    /// the application's configuration and places a `Sanitizer` with that
+    /// policy in managed state. A missing `sanitize` table is treated as an
+    /// empty one. Ignition fails if the table is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_sanitize::Sanitizer;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(Sanitizer::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        AdHoc::try_on_ignite("HTML Sanitizer", |rocket| async move {
+            let policy = match rocket.figment().extract_inner("sanitize") {
+                Ok(policy) => policy,
+                Err(e) if e.missing() => Policy::default(),
+                Err(e) => {
+                    error!("invalid HTML sanitization policy: {e}");
+                    return Err(rocket);
+                }
+            };
+
+            Ok(rocket.manage(Sanitizer::new(policy)))
+        })
+    }
+
+    /// Creates a sanitizer that enforces `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sanitize::{Policy, Sanitizer};
+    ///
+    /// let policy = Policy::text().allow_tag("em");
+    /// let sanitizer = Sanitizer::new(policy);
+    /// assert_eq!(sanitizer.clean("<p><em>Hi!</em></p>"), "<em>Hi!</em>");
+    /// ```
+    pub fn new(policy: Policy) -> Self {
+        Sanitizer { policy: Arc::new(policy) }
+    }
+
+    /// Returns the policy being enforced.
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Returns `html` sanitized according to the policy.
+    pub fn clean(&self, html: &str) -> String {
+        // `ammonia` sets `rel` itself, and requires it not be allowed, when
+        // `link_rel` is set.
+        fn allowed(attrs: &BTreeSet<String>, allow_rel: bool) -> HashSet<&str> {
+            attrs.iter()
+                .map(|attr| attr.as_str())
+                .filter(|attr| allow_rel || *attr != "rel")
+                .collect()
+        }
+
+        let policy = &*self.policy;
+        let link_rel = policy.link_rel.as_deref();
+        let mut generic = HashSet::new();
+        let mut tag_attributes = HashMap::new();
+        for (tag, attrs) in &policy.attributes {
+            match tag.as_str() {
+                "*" => generic = allowed(attrs, link_rel.is_none()),
+                tag => { tag_attributes.insert(tag, allowed(attrs, link_rel.is_none())); }
+            }
+        }
+
+        let tags: HashSet<&str> = policy.tags.iter().map(|tag| tag.as_str()).collect();
+        let clean_content = ["script", "style"].into_iter()
+            .filter(|tag| !tags.contains(tag))
+            .collect();
+
+        let mut builder = ammonia::Builder::empty();
+        builder.tags(tags)
+            .clean_content_tags(clean_content)
+            .generic_attributes(generic)
+            .tag_attributes(tag_attributes)
+            .url_schemes(policy.url_schemes.iter().map(|s| s.as_str()).collect())
+            .link_rel(link_rel)
+            .strip_comments(policy.strip_comments);
+
+        builder.clean(html).to_string()
+    }
+}
+
+#[cfg(feature = "tera")]
+impl Sanitizer {
+    /// Registers a `sanitize` filter with `tera`. The filter sanitizes its
+    /// input with this sanitizer and marks the output safe, so it isn't
+    /// escaped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sanitize::Sanitizer;
+    ///
+    /// let mut tera = tera::Tera::default();
+    /// Sanitizer::default().register_tera(&mut tera);
+    /// tera.add_raw_template("post", "{{ body | sanitize }}").unwrap();
+    ///
+    /// let mut context = tera::Context::new();
+    /// context.insert("body", "<b>hi</b><script>alert(1)</script>");
+    /// assert_eq!(tera.render("post", &context).unwrap(), "<b>hi</b>");
+    /// ```
+    pub fn register_tera(&self, tera: &mut tera::Tera) {
+        struct Filter(Sanitizer);
+
+        impl tera::Filter for Filter {
+            fn filter(
+                &self,
+                value: &tera::Value,
+                _: &HashMap<String, tera::Value>
+            ) -> tera::Result<tera::Value> {
+                let html = value.as_str()
+                    .ok_or_else(|| tera::Error::msg("`sanitize` expects a string"))?;
+
+                Ok(self.0.clean(html).into())
+            }
+
+            fn is_safe(&self) -> bool {
+                true
+            }
+        }
+
+        tera.register_filter("sanitize", Filter(self.clone()));
+    }
+}
+
+#[cfg(feature = "handlebars")]
+impl Sanitizer {
+    /// Registers a `sanitize` helper with `handlebars`. The helper writes its
+    /// first parameter, sanitized with this sanitizer, without escaping it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sanitize::Sanitizer;
+    ///
+    /// let mut hbs = handlebars::Handlebars::new();
+    /// Sanitizer::default().register_handlebars(&mut hbs);
+    /// hbs.register_template_string("post", "{{sanitize body}}").unwrap();
+    ///
+    /// let data = [("body", "<b>hi</b><script>alert(1)</script>")].into_iter()
+    ///     .collect::<std::collections::HashMap<_, _>>();
+    ///
+    /// assert_eq!(hbs.render("post", &data).unwrap(), "<b>hi</b>");
+    /// ```
+    pub fn register_handlebars(&self, handlebars: &mut handlebars::Handlebars<'_>) {
+        use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+        use handlebars::RenderErrorReason;
+
+        let sanitizer = self.clone();
+        let helper = move |
+            h: &Helper<'_>,
+            _: &Handlebars<'_>,
+            _: &Context,
+            _: &mut RenderContext<'_, '_>,
+            out: &mut dyn Output
+        | -> HelperResult {
+            let html = h.param(0)
+                .ok_or(RenderErrorReason::ParamNotFoundForIndex("sanitize", 0))?
+                .value()
+                .render();
+
+            out.write(&sanitizer.clean(&html))?;
+            Ok(())
+        };
+
+        handlebars.register_helper("sanitize", Box::new(helper));
+    }
+}
+
+#[cfg(feature = "minijinja")]
+impl Sanitizer {
+    /// Registers a `sanitize` filter with `env`. The filter sanitizes its
+    /// input with this sanitizer and marks the output safe, so it isn't
+    /// escaped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_sanitize::Sanitizer;
+    ///
+    /// let mut env = minijinja::Environment::new();
+    /// Sanitizer::default().register_minijinja(&mut env);
+    /// env.add_template("post.html", "{{ body | sanitize }}").unwrap();
+    ///
+    /// let template = env.get_template("post.html").unwrap();
+    /// let html = template.render(minijinja::context! {
+    ///     body => "<b>hi</b><script>alert(1)</script>"
+    /// });
+    ///
+    /// assert_eq!(html.unwrap(), "<b>hi</b>");
+    /// ```
+    pub fn register_minijinja(&self, env: &mut minijinja::Environment<'_>) {
+        let sanitizer = self.clone();
+        env.add_filter("sanitize", move |html: &str| {
+            minijinja::Value::from_safe_string(sanitizer.clean(html))
+        });
+    }
+}
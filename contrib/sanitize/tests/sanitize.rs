@@ -0,0 +1,86 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::ContentType;
+use rocket::local::blocking::Client;
+use rocket_sanitize::{Policy, Sanitizer, SanitizedHtml};
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> SanitizedHtml<String> {
+    SanitizedHtml(body)
+}
+
+fn clean(client: &Client, html: &str) -> String {
+    let response = client.post("/").body(html).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    response.into_string().unwrap()
+}
+
+#[test]
+fn default_policy_removes_dangerous_content() {
+    let sanitizer = Sanitizer::default();
+    let cases = [
+        ("<p>Hello, <strong>world</strong>!</p>", "<p>Hello, <strong>world</strong>!</p>"),
+        ("<img src=x onerror=alert(1)>", r#"<img src="x">"#),
+        ("<script>alert(1)</script>ok", "ok"),
+        ("<style>body { display: none }</style>ok", "ok"),
+        ("<iframe src=evil></iframe>ok", "ok"),
+        ("<!-- hidden -->ok", "ok"),
+        (
+            r#"<a href="javascript:alert(1)">x</a>"#,
+            r#"<a rel="noopener noreferrer nofollow">x</a>"#,
+        ),
+        (r#"<p style="color: red" class="x">x</p>"#, "<p>x</p>"),
+        ("<div><em>kept</em></div>", "<em>kept</em>"),
+        ("1 < 2 & 3 > 2", "1 &lt; 2 &amp; 3 &gt; 2"),
+    ];
+
+    for (html, expected) in cases {
+        assert_eq!(sanitizer.clean(html), expected, "sanitizing {html:?}");
+    }
+
+    let link = sanitizer.clean(r#"<a href="https://rocket.rs" rel="me" target="_blank">x</a>"#);
+    assert_eq!(link, r#"<a href="https://rocket.rs" rel="noopener noreferrer nofollow">x</a>"#);
+}
+
+#[test]
+fn custom_policies() {
+    let policy = Policy::text()
+        .allow_tag("a")
+        .allow_attribute("a", "href")
+        .allow_attribute("a", "rel")
+        .allow_attribute("*", "lang")
+        .allow_url_scheme("https");
+
+    let sanitizer = Sanitizer::new(Policy { link_rel: None, strip_comments: false, ..policy });
+    assert_eq!(
+        sanitizer.clean(r#"<a href="https://x.y" rel="me" lang="en" title="t">x</a><!--c-->"#),
+        r#"<a href="https://x.y" rel="me" lang="en">x</a><!--c-->"#
+    );
+
+    assert_eq!(sanitizer.clean(r#"<a href="http://x.y">x</a>"#), "<a>x</a>");
+
+    // Explicitly allowing `script` keeps it.
+    let sanitizer = Sanitizer::new(Policy::text().allow_tag("script"));
+    assert_eq!(sanitizer.clean("<script>1</script>"), "<script>1</script>");
+}
+
+#[test]
+fn responder_uses_managed_policy() {
+    let client = Client::debug_with(routes![echo]).unwrap();
+    assert_eq!(clean(&client, "<b>x</b><script>y</script>"), "<b>x</b>");
+
+    let figment = rocket::Config::figment().merge(("sanitize.tags", ["i"]));
+    let rocket = rocket::custom(figment).mount("/", routes![echo]).attach(Sanitizer::fairing());
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(clean(&client, "<b>x</b><i>y</i>"), "x<i>y</i>");
+
+    let policy = client.rocket().state::<Sanitizer>().unwrap().policy();
+    assert_eq!(policy.link_rel, Policy::default().link_rel);
+}
+
+#[test]
+fn invalid_policy_fails_ignition() {
+    let figment = rocket::Config::figment().merge(("sanitize.tags", 5));
+    let rocket = rocket::custom(figment).attach(Sanitizer::fairing());
+    assert!(Client::debug(rocket).is_err());
+}
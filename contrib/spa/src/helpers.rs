@@ -0,0 +1,58 @@
+use rocket_dyn_templates::Engines;
+
+use crate::Manifest;
+
+/// Registers the `asset` helper with every enabled engine in `engines`.
+pub fn register(manifest: &Manifest, engines: &mut Engines) {
+    #[cfg(feature = "tera")] {
+        use std::collections::HashMap;
+        use rocket_dyn_templates::tera::{self, Value};
+
+        let manifest = manifest.clone();
+        engines.tera.register_function("asset", move |args: &HashMap<String, Value>| {
+            let name = args.get("name")
+                .and_then(|name| name.as_str())
+                .ok_or_else(|| tera::Error::msg("`asset` expects a string `name` argument"))?;
+
+            manifest.asset(name)
+                .map(Value::from)
+                .ok_or_else(|| tera::Error::msg(format!("unknown asset `{name}`")))
+        });
+    }
+
+    #[cfg(feature = "handlebars")] {
+        use rocket_dyn_templates::handlebars::{self, RenderErrorReason};
+
+        let manifest = manifest.clone();
+        let helper = move |
+            h: &handlebars::Helper<'_>,
+            _: &handlebars::Handlebars<'_>,
+            _: &handlebars::Context,
+            _: &mut handlebars::RenderContext<'_, '_>,
+            out: &mut dyn handlebars::Output
+        | -> handlebars::HelperResult {
+            let name = h.param(0)
+                .and_then(|param| param.value().as_str())
+                .ok_or(RenderErrorReason::ParamNotFoundForIndex("asset", 0))?;
+
+            let url = manifest.asset(name)
+                .ok_or_else(|| RenderErrorReason::Other(format!("unknown asset `{name}`")))?;
+
+            out.write(url)?;
+            Ok(())
+        };
+
+        engines.handlebars.register_helper("asset", Box::new(helper));
+    }
+
+    #[cfg(feature = "minijinja")] {
+        use rocket_dyn_templates::minijinja::{Error, ErrorKind};
+
+        let manifest = manifest.clone();
+        engines.minijinja.add_function("asset", move |name: &str| {
+            manifest.asset(name)
+                .map(String::from)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidOperation, "unknown asset"))
+        });
+    }
+}
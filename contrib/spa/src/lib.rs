@@ -0,0 +1,62 @@
+//! Single-page application hosting for Rocket.
+//!
+//! Frontends built with tools like Vite or webpack are a directory of static
+//! files: an `index.html`, fingerprinted assets such as `main-4f2a9c.js`, and
+//! a manifest mapping logical asset names to the fingerprinted files. Hosting
+//! one involves a standard contract: serve the files, cache fingerprinted
+//! assets forever, and answer client-side routes, like `/users/42`, with the
+//! `index.html` so the application can route them itself. This crate
+//! implements that contract.
+//!
+//! The [`Spa`] fairing serves a build directory with the HTML5 history
+//! fallback and loads its asset [`Manifest`], which can be retrieved in
+//! handlers via the `&Manifest` request guard and, with one of the `tera`,
+//! `handlebars`, or `minijinja` features, used in templates via an `asset`
+//! helper.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_spa = "0.1.0"
+//! ```
+//!
+//! Then attach a [`Spa`] fairing for the build directory:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_spa::Spa;
+//!
+//! #[get("/api/hello")]
+//! fn hello() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![hello])
+//!         .attach(Spa::new("frontend/dist"))
+//! }
+//! ```
+//!
+//! Requests to `/api/hello` are handled by `hello`; requests for files in
+//! `frontend/dist` are answered with the file; and any other navigation is
+//! answered with `frontend/dist/index.html`.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_spa")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod spa;
+mod manifest;
+
+#[cfg(any(feature = "tera", feature = "handlebars", feature = "minijinja"))]
+mod helpers;
+
+pub use spa::Spa;
+pub use manifest::Manifest;
@@ -0,0 +1,300 @@
+use std::io;
+use std::sync::Arc;
+use std::path::Path;
+use std::collections::{HashMap, HashSet};
+
+use rocket::http::Status;
+use rocket::request::{self, Request, FromRequest};
+use rocket::serde::{Deserialize, json::serde_json};
+
+/// A map from logical asset names to fingerprinted asset URLs.
+///
+/// Bundlers like Vite and webpack emit assets with content hashes in their
+/// file names, `main-4f2a9c.js` for `src/main.ts`, for instance, and record
+/// the mapping in a JSON manifest. A `Manifest` reads such a file and resolves
+/// logical names, exactly as they appear in the manifest, to URLs.
+///
+/// Both manifest formats are supported:
+///
+///   * Vite's `.vite/manifest.json`, whose entries are objects with a `file`
+///     and, optionally, a list of `css` files:
+///
+///     ```json
+///     { "src/main.ts": {
+///       "file": "assets/main-4f2a9c.js",
+///       "css": ["assets/main-81bd.css"]
+///     } }
+///     ```
+///
+///   * The `manifest.json` emitted by `webpack-manifest-plugin`, whose entries
+///     are file names:
+///
+///     ```json
+///     { "main.js": "static/main.4f2a9c.js" }
+///     ```
+///
+/// Relative file names are resolved against the base URL the manifest was
+/// loaded with. Absolute paths and full URLs are used as they are.
+///
+/// # Request Guard
+///
+/// `&Manifest` is a request guard that retrieves the manifest loaded by the
+/// [`Spa`](crate::Spa) fairing. It fails with `500 Internal Server Error` if
+/// the fairing isn't attached.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::content::RawHtml;
+/// use rocket_spa::Manifest;
+///
+/// #[get("/embed")]
+/// fn embed(manifest: &Manifest) -> Option<RawHtml<String>> {
+///     let script = manifest.asset("src/embed.ts")?;
+///     Some(RawHtml(format!(r#"<script type="module" src="{script}"></script>"#)))
+/// }
+/// ```
+///
+/// # Template Helper
+///
+/// With the `tera`, `handlebars`, or `minijinja` features enabled,
+/// [`Manifest::register()`] registers an `asset` helper with the respective
+/// engine of `rocket_dyn_templates` which resolves an asset name to its URL:
+///
+/// ```rust
+/// # #[cfg(feature = "tera")] mod example {
+/// use rocket::{Rocket, Build};
+/// use rocket_dyn_templates::Template;
+/// use rocket_spa::{Manifest, Spa};
+///
+/// fn rocket() -> Rocket<Build> {
+///     let manifest = Manifest::load("dist/.vite/manifest.json", "/")
+///         .unwrap_or_default();
+///
+///     let helpers = manifest.clone();
+///     rocket::build()
+///         .attach(Spa::new("dist").manifest(manifest))
+///         .attach(Template::custom(move |engines| helpers.register(engines)))
+/// }
+/// # }
+/// ```
+///
+/// In a Tera template, the helper is called as `{{ asset(name="src/main.ts")
+/// }}`, in a Handlebars template as `{{asset "src/main.ts"}}`, and in a
+/// minijinja template as `{{ asset("src/main.ts") }}`. Rendering fails if the
+/// asset is not in the manifest.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    files: HashSet<String>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    url: String,
+    css: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde", untagged)]
+enum RawEntry {
+    Webpack(String),
+    Vite {
+        file: String,
+        #[serde(default)]
+        css: Vec<String>,
+    },
+}
+
+impl Manifest {
+    /// Reads and parses the manifest at `path`. Relative asset file names are
+    /// resolved against `base`, usually the path the built frontend is served
+    /// at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid Vite or
+    /// webpack manifest.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_spa::Manifest;
+    ///
+    /// let manifest = Manifest::load("dist/.vite/manifest.json", "/app")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P, base: &str) -> io::Result<Manifest> {
+        let json = std::fs::read_to_string(path)?;
+        Manifest::parse(&json, base)
+    }
+
+    /// Parses the manifest in `json`, resolving relative asset file names
+    /// against `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid Vite or webpack manifest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_spa::Manifest;
+    ///
+    /// let json = r#"{ "src/main.ts": { "file": "assets/main-4f2a9c.js" } }"#;
+    /// let manifest = Manifest::parse(json, "/app").unwrap();
+    /// assert_eq!(manifest.asset("src/main.ts"), Some("/app/assets/main-4f2a9c.js"));
+    /// ```
+    pub fn parse(json: &str, base: &str) -> io::Result<Manifest> {
+        let raw: HashMap<String, RawEntry> = serde_json::from_str(json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let base = base.trim_end_matches('/');
+        let mut inner = Inner::default();
+        for (name, entry) in raw {
+            let (file, css) = match entry {
+                RawEntry::Webpack(file) => (file, vec![]),
+                RawEntry::Vite { file, css } => (file, css),
+            };
+
+            let entry = Entry {
+                url: inner.resolve(base, &file),
+                css: css.iter().map(|file| inner.resolve(base, file)).collect(),
+            };
+
+            inner.entries.insert(name, entry);
+        }
+
+        Ok(Manifest { inner: Arc::new(inner) })
+    }
+
+    /// Returns the URL of the fingerprinted file for the asset `name`, if it
+    /// is in the manifest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_spa::Manifest;
+    ///
+    /// let manifest = Manifest::parse(r#"{ "main.js": "static/main.4f2a9c.js" }"#, "/").unwrap();
+    /// assert_eq!(manifest.asset("main.js"), Some("/static/main.4f2a9c.js"));
+    /// assert_eq!(manifest.asset("other.js"), None);
+    /// ```
+    pub fn asset(&self, name: &str) -> Option<&str> {
+        self.inner.entries.get(name).map(|entry| entry.url.as_str())
+    }
+
+    /// Returns the URLs of the stylesheets Vite extracted for the asset
+    /// `name`. The iterator is empty if there are none or `name` is not in the
+    /// manifest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_spa::Manifest;
+    ///
+    /// let json = r#"{ "src/main.ts": { "file": "assets/a.js", "css": ["assets/a.css"] } }"#;
+    /// let manifest = Manifest::parse(json, "/").unwrap();
+    /// let css: Vec<_> = manifest.css("src/main.ts").collect();
+    /// assert_eq!(css, ["/assets/a.css"]);
+    /// ```
+    pub fn css(&self, name: &str) -> impl Iterator<Item = &str> + '_ {
+        self.inner.entries.get(name)
+            .into_iter()
+            .flat_map(|entry| entry.css.iter().map(|url| url.as_str()))
+    }
+
+    /// Returns `true` if the file at `path`, relative to the served directory,
+    /// is a fingerprinted file named by the manifest.
+    pub(crate) fn is_fingerprinted(&self, path: &str) -> bool {
+        self.inner.files.contains(path)
+    }
+
+    /// Registers an `asset` helper which resolves asset names to URLs with
+    /// each enabled template engine.
+    #[cfg(any(feature = "tera", feature = "handlebars", feature = "minijinja"))]
+    pub fn register(&self, engines: &mut rocket_dyn_templates::Engines) {
+        crate::helpers::register(self, engines)
+    }
+}
+
+impl Inner {
+    /// Returns the URL of `file` and records relative files as fingerprinted.
+    fn resolve(&mut self, base: &str, file: &str) -> String {
+        if file.contains("://") {
+            return file.to_string();
+        }
+
+        if let Some(path) = file.strip_prefix('/') {
+            let relative = match base.trim_start_matches('/') {
+                "" => Some(path),
+                dir => path.strip_prefix(dir).and_then(|path| path.strip_prefix('/')),
+            };
+
+            if let Some(path) = relative {
+                self.files.insert(path.to_string());
+            }
+
+            return file.to_string();
+        }
+
+        self.files.insert(file.to_string());
+        format!("{base}/{file}")
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r Manifest {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.rocket().state::<Manifest>() {
+            Some(manifest) => request::Outcome::Success(manifest),
+            None => {
+                error!("`Manifest` guard used without attaching the `Spa` fairing");
+                request::Outcome::Error((Status::InternalServerError, ()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Manifest;
+
+    #[test]
+    fn test_resolve() {
+        let json = r#"{
+            "a": "static/a.1.js",
+            "b": "/app/static/b.2.js",
+            "c": "/elsewhere/c.3.js",
+            "d": "https://cdn.example.com/d.4.js",
+            "e": { "file": "assets/e.5.js", "css": ["assets/e.6.css"], "isEntry": true }
+        }"#;
+
+        let manifest = Manifest::parse(json, "/app/").unwrap();
+        assert_eq!(manifest.asset("a"), Some("/app/static/a.1.js"));
+        assert_eq!(manifest.asset("b"), Some("/app/static/b.2.js"));
+        assert_eq!(manifest.asset("c"), Some("/elsewhere/c.3.js"));
+        assert_eq!(manifest.asset("d"), Some("https://cdn.example.com/d.4.js"));
+        assert_eq!(manifest.asset("e"), Some("/app/assets/e.5.js"));
+        assert_eq!(manifest.css("e").collect::<Vec<_>>(), ["/app/assets/e.6.css"]);
+
+        assert!(manifest.is_fingerprinted("static/a.1.js"));
+        assert!(manifest.is_fingerprinted("static/b.2.js"));
+        assert!(manifest.is_fingerprinted("assets/e.6.css"));
+        assert!(!manifest.is_fingerprinted("elsewhere/c.3.js"));
+        assert!(!manifest.is_fingerprinted("index.html"));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Manifest::parse("[]", "/").is_err());
+        assert!(Manifest::parse(r#"{ "a": 1 }"#, "/").is_err());
+        assert!(Manifest::parse(r#"{ "a": { "css": [] } }"#, "/").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+
+use rocket::{Rocket, Build, Request, Data};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::fs::NamedFile;
+use rocket::http::{Method, Status, uri::{Segments, fmt::Path as UriPath}};
+use rocket::response::Cached;
+use rocket::route::{Route, Handler, Outcome};
+
+use crate::Manifest;
+
+/// The manifests looked for, in order, when none is set explicitly.
+const MANIFESTS: &[&str] = &[".vite/manifest.json", "manifest.json", "asset-manifest.json"];
+
+/// How long fingerprinted files are cached for: one year, in seconds.
+const IMMUTABLE_MAX_AGE: u32 = 365 * 24 * 60 * 60;
+
+/// A fairing that serves a built single-page application.
+///
+/// On ignition, the fairing loads the asset [`Manifest`], manages it, and
+/// mounts a handler at the [base path](Spa::base()), by default `/`, for `GET`
+/// requests which:
+///
+///   * serves files from the build directory. Dotfiles are never served.
+///     Fingerprinted files, those named by the manifest, are served with a
+///     `Cache-Control: public, max-age=31536000, immutable` header;
+///   * serves the index file, by default `index.html`, with a `Cache-Control:
+///     no-cache` header, in place of any missing file whose name has no
+///     extension, as long as the client accepts HTML. This is the "HTML5
+///     history" fallback that lets client-side routes like `/users/42` load
+///     the application;
+///   * forwards with a `404` otherwise, so requests for missing assets like
+///     `/assets/gone.js` are not answered with the index.
+///
+/// The handler is mounted with a rank of `20`, below the default ranks of
+/// routes and [`FileServer`](rocket::fs::FileServer), so application routes,
+/// such as API endpoints, take precedence.
+///
+/// Unless a manifest is set via [`Spa::manifest()`], the first of
+/// `.vite/manifest.json`, `manifest.json`, and `asset-manifest.json` that
+/// exists in the build directory is loaded. Ignition fails if it is invalid.
+/// Without any manifest, no file is treated as fingerprinted.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_spa::Spa;
+///
+/// #[get("/api/status")]
+/// fn status() -> &'static str {
+///     "ok"
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![status])
+///         .attach(Spa::new("frontend/dist"))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spa {
+    root: PathBuf,
+    base: String,
+    index: PathBuf,
+    manifest: Option<Manifest>,
+    rank: isize,
+}
+
+impl Spa {
+    /// The default rank of the mounted route.
+    const DEFAULT_RANK: isize = 20;
+
+    /// Returns a fairing that serves the application built into `root`.
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Spa {
+            root: root.as_ref().into(),
+            base: "/".into(),
+            index: "index.html".into(),
+            manifest: None,
+            rank: Self::DEFAULT_RANK,
+        }
+    }
+
+    /// Serves the application at `base` instead of `/`. Relative asset file
+    /// names in a loaded manifest are resolved against `base`.
+    ///
+    /// # Panics
+    ///
+    /// Ignition panics if `base` is not a valid mount point.
+    pub fn base<B: Into<String>>(mut self, base: B) -> Self {
+        self.base = base.into();
+        self
+    }
+
+    /// Serves `file`, relative to the build directory, as the fallback
+    /// instead of `index.html`.
+    pub fn index<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.index = file.as_ref().into();
+        self
+    }
+
+    /// Uses `manifest` instead of looking for one in the build directory.
+    pub fn manifest(mut self, manifest: Manifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    /// Sets the rank of the mounted route to `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+
+    /// Returns the explicitly set manifest or the first one found in the build
+    /// directory, if any.
+    fn load_manifest(&self) -> std::io::Result<Manifest> {
+        if let Some(manifest) = &self.manifest {
+            return Ok(manifest.clone());
+        }
+
+        match MANIFESTS.iter().map(|file| self.root.join(file)).find(|path| path.is_file()) {
+            Some(path) => Manifest::load(path, &self.base),
+            None => Ok(Manifest::default()),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Spa {
+    fn info(&self) -> Info {
+        Info { name: "Single-Page Application", kind: Kind::Ignite }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if rocket.state::<Manifest>().is_some() {
+            error!("an asset `Manifest` is already being managed");
+            return Err(rocket);
+        }
+
+        let manifest = match self.load_manifest() {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                error!(root = %self.root.display(), "failed to load asset manifest: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let index = self.root.join(&self.index);
+        if !index.is_file() {
+            warn!(index = %index.display(), "single-page application index does not exist");
+        }
+
+        let server = Server { root: self.root.clone(), index, manifest: manifest.clone() };
+        let mut route = Route::ranked(self.rank, Method::Get, "/<path..>", server);
+        route.name = Some("Spa".into());
+
+        Ok(rocket.manage(manifest).mount(self.base.clone(), vec![route]))
+    }
+}
+
+/// The handler mounted by [`Spa`].
+#[derive(Clone)]
+struct Server {
+    root: PathBuf,
+    index: PathBuf,
+    manifest: Manifest,
+}
+
+impl Server {
+    /// Whether `req` accepts an HTML response, as navigations do.
+    fn accepts_html(req: &Request<'_>) -> bool {
+        req.accept().map_or(true, |accept| accept.media_types()
+            .any(|media| media.is_html() || (media.top() == "*" && media.sub() == "*")))
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Server {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let path = req.segments::<Segments<'_, UriPath>>(0..).ok()
+            .and_then(|segments| segments.to_path_buf(false).ok());
+
+        let Some(path) = path else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+
+        let file = self.root.join(&path);
+        if rocket::tokio::fs::metadata(&file).await.map_or(false, |m| m.is_file()) {
+            let relative = path.iter()
+                .filter_map(|component| component.to_str())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let mut response = Cached::new(NamedFile::open(&file).await.ok());
+            if self.manifest.is_fingerprinted(&relative) {
+                response = response.public().max_age(IMMUTABLE_MAX_AGE).immutable();
+            }
+
+            return Outcome::from(req, response);
+        }
+
+        if path.extension().is_some() || !Self::accepts_html(req) {
+            return Outcome::forward(data, Status::NotFound);
+        }
+
+        match NamedFile::open(&self.index).await {
+            Ok(index) => Outcome::from(req, Cached::new(index).no_cache()),
+            Err(_) => Outcome::forward(data, Status::NotFound),
+        }
+    }
+}
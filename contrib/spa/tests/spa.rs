@@ -0,0 +1,99 @@
+#[macro_use] extern crate rocket;
+
+use std::fs;
+use std::path::Path;
+
+use rocket::http::{Accept, Status};
+use rocket::local::blocking::Client;
+use rocket_spa::{Manifest, Spa};
+
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+#[get("/api/asset")]
+fn asset(manifest: &Manifest) -> Option<String> {
+    manifest.asset("src/main.ts").map(String::from)
+}
+
+fn build(root: &Path) {
+    fs::create_dir_all(root.join(".vite")).unwrap();
+    fs::create_dir_all(root.join("assets")).unwrap();
+    fs::write(root.join("index.html"), "<html>index</html>").unwrap();
+    fs::write(root.join("favicon.ico"), "icon").unwrap();
+    fs::write(root.join("assets/main-4f2a9c.js"), "main()").unwrap();
+    fs::write(root.join(".vite/manifest.json"), r#"{
+        "src/main.ts": { "file": "assets/main-4f2a9c.js", "isEntry": true }
+    }"#).unwrap();
+}
+
+fn client(spa: Spa) -> Client {
+    let rocket = rocket::build().mount("/", routes![asset]).attach(spa);
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn serves_files_and_falls_back_to_index() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+    let client = client(Spa::new(dir.path()));
+
+    let response = client.get("/assets/main-4f2a9c.js").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(IMMUTABLE));
+    assert_eq!(response.into_string().unwrap(), "main()");
+
+    let response = client.get("/favicon.ico").dispatch();
+    assert!(response.headers().get_one("Cache-Control").is_none());
+    assert_eq!(response.into_string().unwrap(), "icon");
+
+    for path in ["/", "/users/42", "/settings/profile?tab=2"] {
+        let response = client.get(path).header(Accept::HTML).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+        assert_eq!(response.into_string().unwrap(), "<html>index</html>");
+    }
+
+    let response = client.get("/api/asset").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/assets/main-4f2a9c.js");
+}
+
+#[test]
+fn does_not_mask_missing_assets() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+    let client = client(Spa::new(dir.path()));
+
+    assert_eq!(client.get("/assets/gone-123.js").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/.vite/manifest.json").dispatch().status(), Status::NotFound);
+
+    let response = client.get("/users/42").header(Accept::JSON).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn serves_at_base_with_explicit_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+
+    let json = r#"{ "src/main.ts": "assets/main-4f2a9c.js" }"#;
+    let manifest = Manifest::parse(json, "/app").unwrap();
+    let client = client(Spa::new(dir.path()).base("/app").manifest(manifest));
+
+    let response = client.get("/app/assets/main-4f2a9c.js").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(IMMUTABLE));
+
+    let response = client.get("/app/users/42").dispatch();
+    assert_eq!(response.into_string().unwrap(), "<html>index</html>");
+
+    assert_eq!(client.get("/users/42").dispatch().status(), Status::NotFound);
+    let response = client.get("/api/asset").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/app/assets/main-4f2a9c.js");
+}
+
+#[test]
+fn invalid_manifest_fails_ignition() {
+    let dir = tempfile::tempdir().unwrap();
+    build(dir.path());
+    fs::write(dir.path().join(".vite/manifest.json"), "[1, 2, 3]").unwrap();
+
+    let rocket = rocket::build().attach(Spa::new(dir.path()));
+    assert!(Client::debug(rocket).is_err());
+}
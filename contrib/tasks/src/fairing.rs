@@ -0,0 +1,175 @@
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rand::Rng;
+use rocket::{Rocket, Build, Orbit, Shutdown};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::futures::FutureExt;
+use rocket::time::OffsetDateTime;
+use rocket::tokio::{self, task::JoinHandle};
+
+use crate::task::{Task, Job, Context};
+
+/// A fairing that runs scheduled background [`Task`]s.
+///
+/// At liftoff, the fairing begins scheduling every registered task. When Rocket
+/// begins shutting down, no further runs are started, and runs in progress are
+/// given the configured [grace period] to complete before they are cancelled.
+///
+/// Task names must be unique; launch is aborted otherwise.
+///
+/// [grace period]: rocket::config::ShutdownConfig::grace
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+/// use rocket_tasks::{Tasks, Task, Schedule};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let every_minute = Schedule::every(Duration::from_secs(60));
+///     let nightly = Schedule::cron("0 2 * * *").unwrap();
+///
+///     rocket::build()
+///         .attach(Tasks::new()
+///             .task(Task::new("tick", every_minute, |_| async { println!("tick") }))
+///             .task(Task::new("report", nightly, |_| async { println!("report") })))
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Tasks {
+    tasks: Vec<Task>,
+    schedulers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Tasks {
+    /// Creates a new fairing with no tasks.
+    pub fn new() -> Self {
+        Tasks::default()
+    }
+
+    /// Registers `task` to be run by this fairing.
+    pub fn task(mut self, task: Task) -> Self {
+        self.tasks.push(task);
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Tasks {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Liftoff | Kind::Shutdown, name: "Tasks" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        for (i, task) in self.tasks.iter().enumerate() {
+            if self.tasks[..i].iter().any(|t| t.name == task.name) {
+                error!(name = %task.name, "duplicate task name");
+                return Err(rocket);
+            }
+        }
+
+        Ok(rocket)
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let grace = Duration::from_secs(rocket.config().shutdown.grace.into());
+        let mut schedulers = self.schedulers.lock().expect("schedulers lock");
+        span_info!("tasks" => self.tasks.iter().for_each(|task| {
+            match (task.init)(rocket) {
+                Ok(job) => {
+                    info!(name = %task.name, schedule = ?task.schedule);
+                    let scheduler = Scheduler::new(task, job, rocket.shutdown(), grace);
+                    schedulers.push(tokio::spawn(scheduler.run()));
+                },
+                Err(state) => error!(
+                    name = %task.name, state,
+                    "task requires unmanaged state and will not run"
+                ),
+            }
+        }));
+    }
+
+    async fn on_shutdown(&self, _: &Rocket<Orbit>) {
+        let schedulers = std::mem::take(&mut *self.schedulers.lock().expect("schedulers lock"));
+        for scheduler in schedulers {
+            let _ = scheduler.await;
+        }
+    }
+}
+
+/// Schedules the runs of a single task until shutdown.
+struct Scheduler {
+    context: Context,
+    schedule: crate::Schedule,
+    jitter: Duration,
+    overlap: bool,
+    grace: Duration,
+    job: Job,
+    runs: Vec<JoinHandle<()>>,
+}
+
+impl Scheduler {
+    fn new(task: &Task, job: Job, shutdown: Shutdown, grace: Duration) -> Self {
+        Scheduler {
+            context: Context { name: task.name.as_ref().into(), shutdown },
+            schedule: task.schedule.clone(),
+            jitter: task.jitter,
+            overlap: task.overlap,
+            grace,
+            job,
+            runs: vec![],
+        }
+    }
+
+    async fn run(mut self) {
+        let name = self.context.name.clone();
+        let mut next = self.schedule.next_after(OffsetDateTime::now_utc());
+        while let Some(time) = next {
+            let jitter = match self.jitter.is_zero() {
+                true => Duration::ZERO,
+                false => rand::thread_rng().gen_range(Duration::ZERO..=self.jitter),
+            };
+
+            let delay = Duration::try_from(time - OffsetDateTime::now_utc()).unwrap_or_default();
+            let shutdown = self.context.shutdown();
+            if tokio::time::timeout(delay + jitter, shutdown).await.is_ok() {
+                break;
+            }
+
+            self.runs.retain(|run| !run.is_finished());
+            if !self.overlap && !self.runs.is_empty() {
+                warn!(%name, "previous run still in progress: skipping scheduled run");
+            } else {
+                let job = AssertUnwindSafe((self.job)(self.context.clone()));
+                let name = name.clone();
+                self.runs.push(tokio::spawn(async move {
+                    if job.catch_unwind().await.is_err() {
+                        error!(%name, "task run panicked");
+                    }
+                }));
+            }
+
+            // If runs fell behind schedule, skip to the next future time.
+            let now = OffsetDateTime::now_utc();
+            next = match self.schedule.next_after(time) {
+                Some(next) if next < now => self.schedule.next_after(now),
+                next => next,
+            };
+        }
+
+        self.runs.retain(|run| !run.is_finished());
+        if self.runs.is_empty() {
+            return;
+        }
+
+        let all = async { for run in &mut self.runs { let _ = run.await; } };
+        if tokio::time::timeout(self.grace, all).await.is_err() {
+            warn!(%name, "run did not complete within grace period: cancelling");
+            self.runs.iter().for_each(|run| run.abort());
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Scheduled background tasks for Rocket.
+//!
+//! This crate provides the [`Tasks`] fairing, which runs named, recurring
+//! [`Task`]s on a fixed interval or a cron-like [`Schedule`]. Tasks can access
+//! managed state, are spread out with optional jitter, never overlap with
+//! themselves unless allowed to, and stop gracefully when Rocket shuts down.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_tasks = "0.1.0"
+//! ```
+//!
+//! Then create tasks and attach them via a [`Tasks`] fairing. Below, a
+//! pastebin-like application purges expired pastes every ten minutes and
+//! compacts its storage every night at 3AM UTC:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::sync::{Arc, Mutex};
+//! use std::time::{Duration, Instant};
+//!
+//! use rocket_tasks::{Tasks, Task, Schedule};
+//!
+//! #[derive(Clone, Default)]
+//! struct Pastes(Arc<Mutex<Vec<(String, Instant)>>>);
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let every_ten_minutes = Schedule::every(Duration::from_secs(10 * 60));
+//!     let purge = Task::with_state("purge", every_ten_minutes, |pastes: Pastes, _| async move {
+//!         let now = Instant::now();
+//!         pastes.0.lock().unwrap().retain(|(_, expires)| *expires > now);
+//!     });
+//!
+//!     let nightly = Schedule::cron("0 3 * * *").expect("valid cron expression");
+//!     let compact = Task::new("compact", nightly, |ctxt| async move {
+//!         println!("running `{}`", ctxt.name());
+//!     });
+//!
+//!     rocket::build()
+//!         .manage(Pastes::default())
+//!         .attach(Tasks::new().task(purge).task(compact.jitter(Duration::from_secs(60))))
+//! }
+//! ```
+//!
+//! # Shutdown
+//!
+//! When Rocket begins [shutting down](rocket::Shutdown), tasks stop being
+//! scheduled. Runs in progress are given the configured [grace period] to
+//! complete before they are cancelled. Jobs that run for a long time can
+//! observe [`Context::shutdown()`] to finish early.
+//!
+//! [grace period]: rocket::config::ShutdownConfig::grace
+//!
+//! # Job Queues
+//!
+//! For work that is triggered by requests rather than by the clock, the
+//! [`queue`] module provides an in-process job queue with retries.
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_tasks")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+pub mod queue;
+
+mod fairing;
+mod task;
+mod schedule;
+
+pub use fairing::Tasks;
+pub use task::{Task, Context};
+pub use schedule::{Schedule, Error};
@@ -0,0 +1,211 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rocket::tokio::time::Instant;
+
+use crate::queue::Error;
+
+/// A job reserved from a [`Backend`] for processing.
+#[derive(Debug)]
+pub struct Reserved<J, R> {
+    /// The job.
+    pub job: J,
+    /// The number of previous attempts to process the job that failed.
+    pub attempts: u32,
+    /// The backend's receipt for the reservation.
+    pub receipt: R,
+}
+
+/// Storage for a [`Queue`](crate::queue::Queue).
+///
+/// A backend stores jobs of type `J` until they are processed. To provide
+/// at-least-once semantics, jobs are not removed when they are
+/// [reserved](Backend::reserve()) but only when they are
+/// [acknowledged](Backend::ack()) after processing completes. A persistent
+/// backend should make a reserved job that is neither acknowledged nor
+/// [retried](Backend::retry()) within some timeout available for reservation
+/// again, so that jobs survive worker or process crashes.
+///
+/// The [`Memory`] backend is used by default. Implementing this trait allows
+/// jobs to be stored elsewhere, say in Redis or Postgres.
+#[rocket::async_trait]
+pub trait Backend<J: Send + 'static>: Send + Sync + 'static {
+    /// A backend-specific receipt identifying a reservation.
+    type Receipt: Send + 'static;
+
+    /// Adds `job` to the queue.
+    async fn push(&self, job: J) -> Result<(), Error>;
+
+    /// Reserves the next job ready for processing, if any, without waiting.
+    async fn reserve(&self) -> Result<Option<Reserved<J, Self::Receipt>>, Error>;
+
+    /// Removes a successfully processed job from the queue.
+    async fn ack(&self, receipt: Self::Receipt) -> Result<(), Error>;
+
+    /// Makes a job whose processing failed available for reservation again
+    /// after `delay` with its attempt count incremented.
+    async fn retry(&self, job: Reserved<J, Self::Receipt>, delay: Duration) -> Result<(), Error>;
+
+    /// Removes a job that exhausted its retries from the queue.
+    ///
+    /// The default implementation simply [acknowledges](Backend::ack()) the
+    /// job. Backends may instead, say, move the job to a dead-letter queue.
+    async fn reject(&self, job: Reserved<J, Self::Receipt>) -> Result<(), Error> {
+        self.ack(job.receipt).await
+    }
+}
+
+/// An in-memory [`Backend`], the default.
+///
+/// Jobs are stored in a FIFO queue in memory. As such, jobs that have not been
+/// processed when the process exits are lost. Use a persistent backend for
+/// jobs that must survive restarts.
+///
+/// A reserved job is kept in memory until it is acknowledged, retried, or
+/// rejected. If none of these happens within the [visibility timeout], as
+/// when a worker is cancelled mid-job, the job is made available for
+/// reservation again with its attempt count incremented. Retrying or
+/// acknowledging a job whose reservation has timed out has no effect.
+///
+/// [visibility timeout]: Memory::visibility_timeout()
+#[derive(Debug)]
+pub struct Memory<J> {
+    state: Mutex<State<J>>,
+    visibility: Duration,
+}
+
+#[derive(Debug)]
+struct State<J> {
+    next_receipt: u64,
+    ready: VecDeque<Job<J>>,
+    in_flight: HashMap<u64, (Job<J>, Instant)>,
+}
+
+#[derive(Debug)]
+struct Job<J> {
+    job: J,
+    attempts: u32,
+    ready: Instant,
+}
+
+impl<J> Memory<J> {
+    /// The default [visibility timeout](Memory::visibility_timeout()): five
+    /// minutes.
+    pub const DEFAULT_VISIBILITY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+    /// Creates a new, empty in-memory backend.
+    pub fn new() -> Self {
+        let state = State { next_receipt: 0, ready: VecDeque::new(), in_flight: HashMap::new() };
+        Memory { state: Mutex::new(state), visibility: Self::DEFAULT_VISIBILITY_TIMEOUT }
+    }
+
+    /// Sets how long a reserved job may remain unacknowledged before it is
+    /// made available for reservation again. Defaults to
+    /// [`Memory::DEFAULT_VISIBILITY_TIMEOUT`].
+    ///
+    /// Jobs that take longer than `timeout` to process may be processed
+    /// concurrently by several workers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_tasks::queue::{Memory, Workers};
+    ///
+    /// let backend = Memory::new().visibility_timeout(Duration::from_secs(30));
+    /// let workers = Workers::new("resize", |path: String| async move {
+    ///     println!("resizing {path}");
+    ///     Ok::<_, std::io::Error>(())
+    /// }).backend(backend);
+    /// ```
+    pub fn visibility_timeout(mut self, timeout: Duration) -> Self {
+        self.visibility = timeout;
+        self
+    }
+
+    /// Returns the number of jobs waiting in the queue, not counting jobs
+    /// currently being processed.
+    pub fn len(&self) -> usize {
+        self.state.lock().expect("jobs lock").ready.len()
+    }
+
+    /// Returns `true` if no jobs are waiting in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of jobs currently reserved for processing.
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().expect("jobs lock").in_flight.len()
+    }
+}
+
+impl<J> State<J> {
+    /// Makes jobs whose reservation has timed out available again.
+    fn requeue_expired(&mut self, now: Instant) {
+        let expired: Vec<u64> = self.in_flight.iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(receipt, _)| *receipt)
+            .collect();
+
+        for receipt in expired {
+            if let Some((mut job, _)) = self.in_flight.remove(&receipt) {
+                job.attempts += 1;
+                job.ready = now;
+                self.ready.push_back(job);
+            }
+        }
+    }
+}
+
+impl<J> Default for Memory<J> {
+    fn default() -> Self {
+        Memory::new()
+    }
+}
+
+#[rocket::async_trait]
+impl<J: Clone + Send + 'static> Backend<J> for Memory<J> {
+    type Receipt = u64;
+
+    async fn push(&self, job: J) -> Result<(), Error> {
+        let job = Job { job, attempts: 0, ready: Instant::now() };
+        self.state.lock().expect("jobs lock").ready.push_back(job);
+        Ok(())
+    }
+
+    async fn reserve(&self) -> Result<Option<Reserved<J, u64>>, Error> {
+        let mut state = self.state.lock().expect("jobs lock");
+        let now = Instant::now();
+        state.requeue_expired(now);
+
+        let Some(i) = state.ready.iter().position(|job| job.ready <= now) else {
+            return Ok(None);
+        };
+
+        let job = state.ready.remove(i).expect("index in bounds");
+        let receipt = state.next_receipt;
+        state.next_receipt += 1;
+
+        let reserved = Reserved { job: job.job.clone(), attempts: job.attempts, receipt };
+        state.in_flight.insert(receipt, (job, now + self.visibility));
+        Ok(Some(reserved))
+    }
+
+    async fn ack(&self, receipt: u64) -> Result<(), Error> {
+        self.state.lock().expect("jobs lock").in_flight.remove(&receipt);
+        Ok(())
+    }
+
+    async fn retry(&self, job: Reserved<J, u64>, delay: Duration) -> Result<(), Error> {
+        let mut state = self.state.lock().expect("jobs lock");
+        if let Some((mut job, _)) = state.in_flight.remove(&job.receipt) {
+            job.attempts += 1;
+            job.ready = Instant::now() + delay;
+            state.ready.push_back(job);
+        }
+
+        Ok(())
+    }
+}
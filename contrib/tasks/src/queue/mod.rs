@@ -0,0 +1,109 @@
+//! An in-process job queue with at-least-once semantics.
+//!
+//! Request handlers push typed jobs onto a [`Queue`]. A [`Workers`] fairing
+//! manages the queue and processes its jobs concurrently, retrying failed jobs
+//! with exponential backoff. Jobs are stored in a [`Backend`], by default the
+//! in-memory [`Memory`] backend.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::State;
+//! use rocket_tasks::queue::{Queue, Workers};
+//!
+//! #[derive(Clone, Debug)]
+//! struct Welcome {
+//!     email: String,
+//! }
+//!
+//! async fn send_welcome(job: Welcome) -> Result<(), std::io::Error> {
+//!     println!("sending a welcome email to {}", job.email);
+//!     Ok(())
+//! }
+//!
+//! #[post("/signup/<email>")]
+//! async fn signup(email: &str, queue: &State<Queue<Welcome>>) -> &'static str {
+//!     match queue.push(Welcome { email: email.into() }).await {
+//!         Ok(()) => "Check your inbox!",
+//!         Err(_) => "Try again later.",
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![signup])
+//!         .attach(Workers::new("welcome", send_welcome).concurrency(4).retries(3))
+//! }
+//! ```
+//!
+//! # Semantics
+//!
+//! A job is only removed from its backend once it has been processed
+//! successfully or has failed more than the configured number of
+//! [retries](Workers::retries()). A job may thus be processed more than once,
+//! for instance, if a persistent backend redelivers it after a crash, and jobs
+//! should be idempotent.
+//!
+//! # Shutdown
+//!
+//! When Rocket begins shutting down, workers continue to process jobs until no
+//! more jobs are ready, draining the queue, and then exit. Workers that are
+//! still running at the end of the configured [grace period] are cancelled.
+//!
+//! [grace period]: rocket::config::ShutdownConfig::grace
+
+mod backend;
+mod workers;
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use rocket::tokio::sync::Notify;
+
+pub use backend::{Backend, Memory, Reserved};
+pub use workers::Workers;
+
+/// An error produced by a [`Backend`].
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// A handle to a job queue, available as managed state.
+///
+/// A `Queue<J, B>` is managed by the [`Workers<J, B>`] fairing that processes
+/// its jobs and is typically retrieved in handlers via `&State<Queue<J>>`.
+/// Cloning a `Queue` is cheap and yields a handle to the same queue.
+pub struct Queue<J, B = Memory<J>> {
+    shared: Arc<Shared<B>>,
+    _job: PhantomData<fn(J)>,
+}
+
+struct Shared<B> {
+    backend: B,
+    notify: Notify,
+}
+
+impl<J: Send + 'static, B: Backend<J>> Queue<J, B> {
+    pub(crate) fn new(backend: B) -> Self {
+        let shared = Shared { backend, notify: Notify::new() };
+        Queue { shared: Arc::new(shared), _job: PhantomData }
+    }
+
+    /// Adds `job` to the queue and wakes an idle worker, if any.
+    pub async fn push(&self, job: J) -> Result<(), Error> {
+        self.shared.backend.push(job).await?;
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+
+    /// Returns a reference to the queue's backend.
+    pub fn backend(&self) -> &B {
+        &self.shared.backend
+    }
+}
+
+impl<J, B> Clone for Queue<J, B> {
+    fn clone(&self) -> Self {
+        Queue { shared: self.shared.clone(), _job: PhantomData }
+    }
+}
@@ -0,0 +1,307 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Orbit, Shutdown};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::futures::future::{self, BoxFuture, FutureExt};
+use rocket::tokio::{self, task::JoinHandle};
+
+use crate::queue::{Queue, Backend, Memory};
+
+type Handler<J> = Arc<dyn Fn(J) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+type Init<J> = Box<dyn Fn(&Rocket<Orbit>) -> Result<Handler<J>, &'static str> + Send + Sync>;
+
+/// The longest delay between retries of a failed job.
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// A fairing that manages a [`Queue`] and processes its jobs.
+///
+/// At ignition, the fairing manages a [`Queue<J, B>`] backed by the configured
+/// [`Backend`]. At liftoff, it starts [`concurrency`](Workers::concurrency())
+/// workers, each of which repeatedly reserves a job from the queue and calls
+/// the handler with it. A job whose handler returns an error or panics is
+/// retried up to [`retries`](Workers::retries()) times, waiting
+/// [`backoff`](Workers::backoff()), doubled after every failed attempt, in
+/// between attempts.
+///
+/// See the [module docs](crate::queue) for an example.
+pub struct Workers<J, B = Memory<J>> {
+    name: Cow<'static, str>,
+    backend: Mutex<Option<B>>,
+    init: Init<J>,
+    concurrency: usize,
+    retries: u32,
+    backoff: Duration,
+    poll: Duration,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl<J: Clone + Send + Sync + 'static> Workers<J> {
+    /// Creates a fairing named `name` that processes jobs of type `J` from an
+    /// in-memory queue by calling `handler`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_tasks::queue::Workers;
+    ///
+    /// let workers = Workers::new("resize", |path: String| async move {
+    ///     println!("resizing {path}");
+    ///     Ok::<_, std::io::Error>(())
+    /// });
+    /// ```
+    pub fn new<N, F, Fut, E>(name: N, handler: F) -> Self
+        where N: Into<Cow<'static, str>>,
+              F: Fn(J) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = Result<(), E>> + Send + 'static,
+              E: Display,
+    {
+        let handler: Handler<J> = Arc::new(move |job: J| {
+            handler(job).map(|r| r.map_err(|e| e.to_string())).boxed()
+        });
+
+        Workers::with_init(name, move |_| Ok(handler.clone()))
+    }
+
+    /// Creates a fairing named `name` that processes jobs of type `J` from an
+    /// in-memory queue by calling `handler` with the job and a clone of the
+    /// managed state of type `T`.
+    ///
+    /// The state is retrieved once, at liftoff. If no state of type `T` is
+    /// managed at liftoff, an error is logged and no jobs are processed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    /// use rocket_tasks::queue::Workers;
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct Processed(Arc<AtomicUsize>);
+    ///
+    /// let workers = Workers::with_state("count", |_: u64, n: Processed| async move {
+    ///     n.0.fetch_add(1, Ordering::Relaxed);
+    ///     Ok::<_, std::io::Error>(())
+    /// });
+    ///
+    /// let rocket = rocket::build()
+    ///     .manage(Processed::default())
+    ///     .attach(workers);
+    /// ```
+    pub fn with_state<T, N, F, Fut, E>(name: N, handler: F) -> Self
+        where T: Clone + Send + Sync + 'static,
+              N: Into<Cow<'static, str>>,
+              F: Fn(J, T) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = Result<(), E>> + Send + 'static,
+              E: Display,
+    {
+        let handler = Arc::new(handler);
+        Workers::with_init(name, move |rocket| {
+            let state = rocket.state::<T>().ok_or(std::any::type_name::<T>())?.clone();
+            let handler = handler.clone();
+            let handler: Handler<J> = Arc::new(move |job: J| {
+                handler(job, state.clone()).map(|r| r.map_err(|e| e.to_string())).boxed()
+            });
+
+            Ok(handler)
+        })
+    }
+
+    fn with_init<N, I>(name: N, init: I) -> Self
+        where N: Into<Cow<'static, str>>,
+              I: Fn(&Rocket<Orbit>) -> Result<Handler<J>, &'static str> + Send + Sync + 'static
+    {
+        Workers {
+            name: name.into(),
+            backend: Mutex::new(Some(Memory::new())),
+            init: Box::new(init),
+            concurrency: 1,
+            retries: 5,
+            backoff: Duration::from_secs(1),
+            poll: Duration::from_secs(1),
+            workers: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<J: Clone + Send + Sync + 'static, B: Backend<J>> Workers<J, B> {
+    /// Stores jobs in `backend` instead of the current backend.
+    ///
+    /// The managed [`Queue`] is then of type `Queue<J, B2>`.
+    pub fn backend<B2: Backend<J>>(self, backend: B2) -> Workers<J, B2> {
+        Workers {
+            name: self.name,
+            backend: Mutex::new(Some(backend)),
+            init: self.init,
+            concurrency: self.concurrency,
+            retries: self.retries,
+            backoff: self.backoff,
+            poll: self.poll,
+            workers: self.workers,
+        }
+    }
+
+    /// Sets the number of jobs processed concurrently. Defaults to `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn concurrency(mut self, workers: usize) -> Self {
+        assert!(workers > 0, "concurrency must be non-zero");
+        self.concurrency = workers;
+        self
+    }
+
+    /// Sets the number of times a failed job is retried before it is
+    /// [rejected](Backend::reject()). Defaults to `5`.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed job. The delay is
+    /// doubled for every subsequent retry, up to an hour. Defaults to one
+    /// second.
+    pub fn backoff(mut self, delay: Duration) -> Self {
+        self.backoff = delay;
+        self
+    }
+
+    /// Sets how often idle workers check the backend for ready jobs that were
+    /// not pushed through this process's [`Queue`], such as retried jobs or
+    /// jobs pushed by other processes to a shared backend. Defaults to one
+    /// second.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll = interval;
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl<J: Clone + Send + Sync + 'static, B: Backend<J>> Fairing for Workers<J, B> {
+    fn info(&self) -> Info {
+        Info { kind: Kind::Ignite | Kind::Liftoff | Kind::Shutdown, name: "Job Queue" }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if rocket.state::<Queue<J, B>>().is_some() {
+            error!(name = %self.name, "a queue for these jobs is already being managed");
+            return Err(rocket);
+        }
+
+        match self.backend.lock().expect("backend lock").take() {
+            Some(backend) => Ok(rocket.manage(Queue::<J, B>::new(backend))),
+            None => Err(rocket),
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let queue = rocket.state::<Queue<J, B>>().expect("queue managed in on_ignite");
+        let handler = match (self.init)(rocket) {
+            Ok(handler) => handler,
+            Err(state) => {
+                error!(name = %self.name, state, "job handler requires unmanaged state");
+                return;
+            }
+        };
+
+        info!(name = %self.name, workers = self.concurrency, "starting job queue workers");
+        let mut workers = self.workers.lock().expect("workers lock");
+        for _ in 0..self.concurrency {
+            let worker = Worker {
+                name: self.name.clone(),
+                queue: queue.clone(),
+                handler: handler.clone(),
+                shutdown: rocket.shutdown(),
+                retries: self.retries,
+                backoff: self.backoff,
+                poll: self.poll,
+            };
+
+            workers.push(tokio::spawn(worker.run()));
+        }
+    }
+
+    async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
+        let grace = Duration::from_secs(rocket.config().shutdown.grace.into());
+        let mut workers = std::mem::take(&mut *self.workers.lock().expect("workers lock"));
+        let all = async { for worker in &mut workers { let _ = worker.await; } };
+        if tokio::time::timeout(grace, all).await.is_err() {
+            warn!(name = %self.name, "job queue did not drain within grace period: cancelling");
+            workers.iter().for_each(|worker| worker.abort());
+        }
+    }
+}
+
+/// A single worker processing jobs from a queue until shutdown.
+struct Worker<J, B> {
+    name: Cow<'static, str>,
+    queue: Queue<J, B>,
+    handler: Handler<J>,
+    shutdown: Shutdown,
+    retries: u32,
+    backoff: Duration,
+    poll: Duration,
+}
+
+impl<J: Clone + Send + Sync + 'static, B: Backend<J>> Worker<J, B> {
+    async fn run(self) {
+        let name = &self.name;
+        let backend = self.queue.backend();
+        loop {
+            let reserved = match backend.reserve().await {
+                Ok(Some(reserved)) => reserved,
+                Ok(None) if self.is_shutdown() => break,
+                Ok(None) => {
+                    self.idle().await;
+                    continue;
+                }
+                Err(e) => {
+                    error!(%name, "failed to reserve job: {e}");
+                    if self.is_shutdown() { break }
+                    self.idle().await;
+                    continue;
+                }
+            };
+
+            let job = AssertUnwindSafe((self.handler)(reserved.job.clone()));
+            let result = match job.catch_unwind().await {
+                Ok(result) => result,
+                Err(_) => Err("job handler panicked".into()),
+            };
+
+            let result = match result {
+                Ok(()) => backend.ack(reserved.receipt).await,
+                Err(e) if reserved.attempts < self.retries => {
+                    let delay = self.backoff.saturating_mul(1 << reserved.attempts.min(16));
+                    warn!(%name, attempt = reserved.attempts + 1, "job failed: {e}");
+                    backend.retry(reserved, delay.min(MAX_BACKOFF)).await
+                }
+                Err(e) => {
+                    error!(%name, attempts = reserved.attempts + 1, "job failed permanently: {e}");
+                    backend.reject(reserved).await
+                }
+            };
+
+            if let Err(e) = result {
+                error!(%name, "failed to update job state: {e}");
+            }
+        }
+    }
+
+    fn is_shutdown(&self) -> bool {
+        self.shutdown.clone().now_or_never().is_some()
+    }
+
+    /// Waits until a job is pushed, the poll interval elapses, or shutdown.
+    async fn idle(&self) {
+        let notified = std::pin::pin!(self.queue.shared.notify.notified());
+        let wake = future::select(notified, self.shutdown.clone());
+        let _ = tokio::time::timeout(self.poll, wake).await;
+    }
+}
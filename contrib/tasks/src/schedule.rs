@@ -0,0 +1,308 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use rocket::time::{self, Date, Month, OffsetDateTime, UtcOffset};
+
+/// When a [`Task`](crate::Task) runs.
+///
+/// A schedule is either a fixed interval, constructed via
+/// [`Schedule::every()`], or a cron expression, parsed via [`Schedule::cron()`]
+/// or [`FromStr`]:
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket_tasks::Schedule;
+///
+/// let every_minute = Schedule::every(Duration::from_secs(60));
+/// let nightly = Schedule::cron("30 4 * * *").unwrap();
+/// let weekdays: Schedule = "0 9-17 * * 1-5".parse().unwrap();
+/// let hourly: Schedule = "@hourly".parse().unwrap();
+/// ```
+///
+/// # Cron Expressions
+///
+/// A cron expression consists of five whitespace-separated fields: _minute_
+/// (`0-59`), _hour_ (`0-23`), _day of month_ (`1-31`), _month_ (`1-12`), and
+/// _day of week_ (`0-7`, where both `0` and `7` are Sunday). Each field is a
+/// comma-separated list of:
+///
+///   * `*`, matching every value;
+///   * a value `n` or an inclusive range `a-b`;
+///   * any of the above followed by `/step`, matching every `step`-th value.
+///     `n/step` is shorthand for `n-max/step`.
+///
+/// As in traditional cron, when both _day of month_ and _day of week_ are
+/// restricted, that is, neither is `*`, a day matches if _either_ field
+/// matches. The shorthands `@yearly`, `@monthly`, `@weekly`, `@daily`, and
+/// `@hourly` are also accepted. Times are always interpreted in UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule(Kind);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Every(Duration),
+    Cron(Cron),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// An error parsing a cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The expression had the contained number of fields instead of five.
+    FieldCount(usize),
+    /// The field named `field` contained the invalid or out-of-range `value`.
+    Field {
+        /// The name of the invalid field.
+        field: &'static str,
+        /// The invalid field value.
+        value: String,
+    },
+}
+
+impl Schedule {
+    /// A schedule that runs once every `period`, starting `period` after
+    /// liftoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_tasks::Schedule;
+    ///
+    /// let schedule = Schedule::every(Duration::from_secs(30));
+    /// ```
+    pub fn every(period: Duration) -> Schedule {
+        assert!(!period.is_zero(), "scheduling period must be non-zero");
+        Schedule(Kind::Every(period))
+    }
+
+    /// Parses a cron expression. See [cron
+    /// expressions](#cron-expressions) for the syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_tasks::Schedule;
+    ///
+    /// assert!(Schedule::cron("*/15 * * * *").is_ok());
+    /// assert!(Schedule::cron("*/15 * * *").is_err());
+    /// assert!(Schedule::cron("60 * * * *").is_err());
+    /// ```
+    pub fn cron(expr: &str) -> Result<Schedule, Error> {
+        Cron::parse(expr).map(|cron| Schedule(Kind::Cron(cron)))
+    }
+
+    /// Returns the first time strictly after `time` at which this schedule
+    /// runs, or `None` if there is no such time in the next few years, as is
+    /// the case for, say, `0 0 31 2 *`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::time::macros::datetime;
+    /// use rocket_tasks::Schedule;
+    ///
+    /// let schedule = Schedule::cron("0 12 * * *").unwrap();
+    /// let next = schedule.next_after(datetime!(2024-01-01 12:00 UTC));
+    /// assert_eq!(next, Some(datetime!(2024-01-02 12:00 UTC)));
+    /// ```
+    pub fn next_after(&self, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        match &self.0 {
+            Kind::Every(period) => time.checked_add((*period).try_into().ok()?),
+            Kind::Cron(cron) => cron.next_after(time),
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schedule::cron(s)
+    }
+}
+
+impl Cron {
+    const FIELDS: [(&'static str, u8, u8); 5] = [
+        ("minute", 0, 59),
+        ("hour", 0, 23),
+        ("day of month", 1, 31),
+        ("month", 1, 12),
+        ("day of week", 0, 7),
+    ];
+
+    fn parse(expr: &str) -> Result<Cron, Error> {
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expr => expr,
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != Self::FIELDS.len() {
+            return Err(Error::FieldCount(fields.len()));
+        }
+
+        let mut sets = [0u64; 5];
+        for (i, (&value, &(field, min, max))) in fields.iter().zip(&Self::FIELDS).enumerate() {
+            sets[i] = parse_field(value, min, max)
+                .ok_or_else(|| Error::Field { field, value: value.into() })?;
+        }
+
+        // Sunday is both `0` and `7`; normalize to `0`.
+        let weekdays = (sets[4] | (sets[4] >> 7)) & 0x7f;
+        Ok(Cron {
+            minutes: sets[0],
+            hours: sets[1],
+            days: sets[2],
+            months: sets[3],
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    fn day_matches(&self, date: Date) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().number_days_from_sunday()) != 0;
+        match self.any_day || self.any_weekday {
+            true => day && weekday,
+            false => day || weekday,
+        }
+    }
+
+    fn next_after(&self, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        let time = time.to_offset(UtcOffset::UTC)
+            .replace_second(0).ok()?
+            .replace_nanosecond(0).ok()?;
+
+        // Every valid schedule matches within a leap cycle plus change.
+        let limit = time.checked_add(time::Duration::days(366 * 5))?;
+        let mut time = time.checked_add(time::Duration::minutes(1))?;
+        while time < limit {
+            if self.months & (1 << u8::from(time.month())) == 0 {
+                let (year, month) = match time.month() {
+                    Month::December => (time.year() + 1, Month::January),
+                    month => (time.year(), month.next()),
+                };
+
+                time = Date::from_calendar_date(year, month, 1).ok()?.midnight().assume_utc();
+            } else if !self.day_matches(time.date()) {
+                time = time.date().next_day()?.midnight().assume_utc();
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = time.replace_minute(0).ok()?.checked_add(time::Duration::HOUR)?;
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time = time.checked_add(time::Duration::MINUTE)?;
+            } else {
+                return Some(time);
+            }
+        }
+
+        None
+    }
+}
+
+/// Parses a single cron field into a bitset of values in `min..=max`.
+fn parse_field(field: &str, min: u8, max: u8) -> Option<u64> {
+    let mut set = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<u8>().ok()?)),
+            None => (item, None),
+        };
+
+        let (start, end) = match (range, range.split_once('-')) {
+            ("*", _) => (min, max),
+            (_, Some((a, b))) => (a.parse().ok()?, b.parse().ok()?),
+            (n, None) => {
+                let n = n.parse().ok()?;
+                (n, if step.is_some() { max } else { n })
+            }
+        };
+
+        let step = step.unwrap_or(1);
+        if start < min || end > max || start > end || step == 0 {
+            return None;
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+
+    Some(set)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::FieldCount(n) => write!(f, "expected 5 cron fields, found {n}"),
+            Error::Field { field, value } => write!(f, "invalid {field} field `{value}`"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::time::macros::datetime;
+
+    fn next(expr: &str, time: OffsetDateTime) -> Option<OffsetDateTime> {
+        Schedule::cron(expr).unwrap().next_after(time)
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Schedule::cron("* * * *"), Err(Error::FieldCount(4)));
+        assert!(matches!(Schedule::cron("* 24 * * *"), Err(Error::Field { field: "hour", .. })));
+        assert!(matches!(Schedule::cron("* * 0 * *"), Err(Error::Field { .. })));
+        assert!(matches!(Schedule::cron("*/0 * * * *"), Err(Error::Field { .. })));
+        assert!(matches!(Schedule::cron("5-1 * * * *"), Err(Error::Field { .. })));
+        assert!(matches!(Schedule::cron("a * * * *"), Err(Error::Field { .. })));
+    }
+
+    #[test]
+    fn next_times() {
+        let t = datetime!(2024-02-28 23:59:30 UTC);
+        assert_eq!(next("* * * * *", t), Some(datetime!(2024-02-29 00:00 UTC)));
+        assert_eq!(next("*/15 * * * *", t), Some(datetime!(2024-02-29 00:00 UTC)));
+        assert_eq!(next("5,50 * * * *", t), Some(datetime!(2024-02-29 00:05 UTC)));
+        assert_eq!(next("0 4 * * *", t), Some(datetime!(2024-02-29 04:00 UTC)));
+        assert_eq!(next("0 0 1 * *", t), Some(datetime!(2024-03-01 00:00 UTC)));
+        assert_eq!(next("0 0 29 2 *", t), Some(datetime!(2024-02-29 00:00 UTC)));
+        assert_eq!(next("@yearly", t), Some(datetime!(2025-01-01 00:00 UTC)));
+        assert_eq!(next("0 0 31 2 *", t), None);
+
+        // 2024-02-29 is a Thursday.
+        assert_eq!(next("0 12 * * 7", t), Some(datetime!(2024-03-03 12:00 UTC)));
+        assert_eq!(next("0 12 * * 1-5/2", t), Some(datetime!(2024-03-01 12:00 UTC)));
+        assert_eq!(next("0 12 15 * 4", t), Some(datetime!(2024-02-29 12:00 UTC)));
+    }
+
+    #[test]
+    fn next_interval() {
+        let every = Schedule::every(Duration::from_secs(90));
+        let t = datetime!(2024-01-01 00:00 UTC);
+        assert_eq!(every.next_after(t), Some(datetime!(2024-01-01 00:01:30 UTC)));
+    }
+}
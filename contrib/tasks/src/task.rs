@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocket::{Rocket, Orbit, Shutdown};
+use rocket::futures::future::{BoxFuture, FutureExt};
+
+use crate::Schedule;
+
+/// A named, recurring background task.
+///
+/// A task pairs a [`Schedule`] with an async job. Tasks are run by the
+/// [`Tasks`](crate::Tasks) fairing, which starts scheduling them at liftoff.
+/// Tasks are created with [`Task::new()`] or, when they need access to managed
+/// state, [`Task::with_state()`], and optionally configured with
+/// [`Task::jitter()`] and [`Task::overlap()`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket_tasks::{Task, Schedule};
+///
+/// let schedule = Schedule::every(Duration::from_secs(60));
+/// let task = Task::new("heartbeat", schedule, |ctxt| async move {
+///     println!("task `{}` is alive", ctxt.name());
+/// });
+///
+/// // Delay every run by up to 5 additional seconds.
+/// let task = task.jitter(Duration::from_secs(5));
+/// ```
+pub struct Task {
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) schedule: Schedule,
+    pub(crate) jitter: Duration,
+    pub(crate) overlap: bool,
+    pub(crate) init: Box<dyn Fn(&Rocket<Orbit>) -> Result<Job, &'static str> + Send + Sync>,
+}
+
+/// A task's job, as produced by its initializer at liftoff.
+pub(crate) type Job = Arc<dyn Fn(Context) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Information about a running task, passed to each invocation of its job.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub(crate) name: Arc<str>,
+    pub(crate) shutdown: Shutdown,
+}
+
+impl Task {
+    /// Creates a task named `name` that runs `job` according to `schedule`.
+    ///
+    /// The job is called with a [`Context`] every time the task is scheduled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_tasks::{Task, Schedule};
+    ///
+    /// let nightly = Schedule::cron("0 3 * * *").unwrap();
+    /// let task = Task::new("vacuum", nightly, |_| async {
+    ///     println!("vacuuming...");
+    /// });
+    /// ```
+    pub fn new<N, F, Fut>(name: N, schedule: Schedule, job: F) -> Task
+        where N: Into<Cow<'static, str>>,
+              F: Fn(Context) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = ()> + Send + 'static,
+    {
+        let job: Job = Arc::new(move |ctxt: Context| job(ctxt).boxed());
+        Task::with_init(name, schedule, move |_| Ok(job.clone()))
+    }
+
+    /// Creates a task named `name` that runs `job` according to `schedule`
+    /// with a clone of the managed state of type `T`.
+    ///
+    /// The state is retrieved once, at liftoff, and cloned for every run. As a
+    /// result, `T` is typically a cheaply cloneable handle, such as an `Arc` or
+    /// a database pool. If no state of type `T` is managed at liftoff, an error
+    /// is logged and the task never runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// use rocket_tasks::{Tasks, Task, Schedule};
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct Pastes(Arc<Mutex<Vec<String>>>);
+    ///
+    /// let every_hour = Schedule::every(Duration::from_secs(60 * 60));
+    /// let purge = Task::with_state("purge", every_hour, |pastes: Pastes, _| async move {
+    ///     pastes.0.lock().unwrap().clear();
+    /// });
+    ///
+    /// let rocket = rocket::build()
+    ///     .manage(Pastes::default())
+    ///     .attach(Tasks::new().task(purge));
+    /// ```
+    pub fn with_state<T, N, F, Fut>(name: N, schedule: Schedule, job: F) -> Task
+        where T: Clone + Send + Sync + 'static,
+              N: Into<Cow<'static, str>>,
+              F: Fn(T, Context) -> Fut + Send + Sync + 'static,
+              Fut: Future<Output = ()> + Send + 'static,
+    {
+        let job = Arc::new(job);
+        Task::with_init(name, schedule, move |rocket| {
+            let state = rocket.state::<T>().ok_or(std::any::type_name::<T>())?.clone();
+            let job = job.clone();
+            let job: Job = Arc::new(move |ctxt: Context| job(state.clone(), ctxt).boxed());
+            Ok(job)
+        })
+    }
+
+    fn with_init<N, I>(name: N, schedule: Schedule, init: I) -> Task
+        where N: Into<Cow<'static, str>>,
+              I: Fn(&Rocket<Orbit>) -> Result<Job, &'static str> + Send + Sync + 'static
+    {
+        Task {
+            name: name.into(),
+            schedule,
+            jitter: Duration::ZERO,
+            overlap: false,
+            init: Box::new(init),
+        }
+    }
+
+    /// Delays every run by a random duration between zero and `max`.
+    ///
+    /// Jitter spreads out tasks that would otherwise run in lockstep, for
+    /// instance, across several instances of the same application. By
+    /// default, there is no jitter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_tasks::{Task, Schedule};
+    ///
+    /// let task = Task::new("sync", Schedule::cron("@hourly").unwrap(), |_| async { })
+    ///     .jitter(Duration::from_secs(90));
+    /// ```
+    pub fn jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
+    /// Sets whether a run may start while a previous run is still in
+    /// progress.
+    ///
+    /// By default, overlapping runs are prevented: if a run is still in
+    /// progress when the task is next scheduled, the new run is skipped and a
+    /// warning is logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket_tasks::{Task, Schedule};
+    ///
+    /// let every_second = Schedule::every(Duration::from_secs(1));
+    /// let task = Task::new("ping", every_second, |_| async { }).overlap(true);
+    /// ```
+    pub fn overlap(mut self, enabled: bool) -> Self {
+        self.overlap = enabled;
+        self
+    }
+
+    /// Returns the name of the task.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the task's schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+}
+
+impl Context {
+    /// The name of the running task.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A future that resolves when Rocket begins shutting down.
+    ///
+    /// Runs in progress at shutdown are given the configured
+    /// [grace period](rocket::config::ShutdownConfig::grace) to complete
+    /// before they are cancelled. Long-running jobs can select on this future
+    /// to finish early and cleanly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::tokio::{self, time::{sleep, Duration}};
+    /// use rocket_tasks::{Task, Schedule};
+    ///
+    /// let task = Task::new("crawl", Schedule::cron("@daily").unwrap(), |ctxt| async move {
+    ///     for _ in 0..1000 {
+    ///         tokio::select! {
+    ///             _ = sleep(Duration::from_secs(1)) => { /* crawl a page */ },
+    ///             _ = ctxt.shutdown() => break,
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+}
+
+impl std::fmt::Debug for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Task")
+            .field("name", &self.name)
+            .field("schedule", &self.schedule)
+            .field("jitter", &self.jitter)
+            .field("overlap", &self.overlap)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::local::asynchronous::Client;
+use rocket::tokio::time::sleep;
+use rocket_tasks::queue::{Backend, Memory, Queue, Workers};
+
+#[derive(Clone, Default)]
+struct Log(Arc<Mutex<Vec<(u32, bool)>>>);
+
+impl Log {
+    fn entries(&self) -> Vec<(u32, bool)> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Fails odd jobs on their first attempt, logging every attempt.
+fn workers() -> Workers<u32> {
+    let first_attempts = Arc::new(Mutex::new(Vec::new()));
+    Workers::with_state("log", move |job: u32, log: Log| {
+        let first_attempts = first_attempts.clone();
+        async move {
+            let first = {
+                let mut seen = first_attempts.lock().unwrap();
+                let first = !seen.contains(&job);
+                seen.push(job);
+                first
+            };
+
+            let ok = !(first && job % 2 == 1);
+            log.0.lock().unwrap().push((job, ok));
+            match ok {
+                true => Ok(()),
+                false => Err(format!("job {job} failed")),
+            }
+        }
+    })
+}
+
+#[rocket::async_test]
+async fn jobs_are_retried_until_success() {
+    let log = Log::default();
+    let rocket = rocket::build()
+        .manage(log.clone())
+        .attach(workers().concurrency(2).backoff(Duration::from_millis(10)));
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let queue = client.rocket().state::<Queue<u32>>().unwrap();
+    for job in 0..4 {
+        queue.push(job).await.unwrap();
+    }
+
+    sleep(Duration::from_millis(100)).await;
+    client.terminate().await;
+
+    let mut entries = log.entries();
+    entries.sort();
+    assert_eq!(entries, [(0, true), (1, false), (1, true), (2, true), (3, false), (3, true)]);
+}
+
+#[rocket::async_test]
+async fn jobs_are_rejected_after_retries() {
+    let log = Log::default();
+    let rocket = rocket::build()
+        .manage(log.clone())
+        .attach(workers().retries(0));
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let queue = client.rocket().state::<Queue<u32>>().unwrap();
+    queue.push(1).await.unwrap();
+    sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(log.entries(), [(1, false)]);
+    assert!(queue.backend().is_empty());
+    assert_eq!(queue.backend().in_flight(), 0);
+}
+
+#[rocket::async_test]
+async fn queue_drains_on_shutdown() {
+    let log = Log::default();
+    let rocket = rocket::build()
+        .manage(log.clone())
+        .attach(workers());
+
+    let client = Client::untracked(rocket).await.unwrap();
+    let queue = client.rocket().state::<Queue<u32>>().unwrap().clone();
+    for job in (0..10).step_by(2) {
+        queue.push(job).await.unwrap();
+    }
+
+    client.terminate().await;
+    assert_eq!(log.entries().len(), 5);
+    assert!(queue.backend().is_empty());
+}
+
+#[rocket::async_test]
+async fn reserved_jobs_are_kept_until_acknowledged() {
+    let backend = Memory::new();
+    backend.push("a").await.unwrap();
+    backend.push("b").await.unwrap();
+
+    let a = backend.reserve().await.unwrap().unwrap();
+    assert_eq!((a.job, a.attempts), ("a", 0));
+    assert_eq!((backend.len(), backend.in_flight()), (1, 1));
+
+    backend.ack(a.receipt).await.unwrap();
+    assert_eq!((backend.len(), backend.in_flight()), (1, 0));
+
+    let b = backend.reserve().await.unwrap().unwrap();
+    backend.retry(b, Duration::ZERO).await.unwrap();
+    let b = backend.reserve().await.unwrap().unwrap();
+    assert_eq!((b.job, b.attempts), ("b", 1));
+}
+
+#[rocket::async_test]
+async fn unacknowledged_jobs_are_redelivered() {
+    let backend = Memory::new().visibility_timeout(Duration::from_millis(10));
+    backend.push(7).await.unwrap();
+
+    let lost = backend.reserve().await.unwrap().unwrap();
+    assert!(backend.reserve().await.unwrap().is_none());
+
+    sleep(Duration::from_millis(20)).await;
+    let redelivered = backend.reserve().await.unwrap().unwrap();
+    assert_eq!((redelivered.job, redelivered.attempts), (7, 1));
+
+    // The timed out reservation no longer affects the job.
+    backend.retry(lost, Duration::ZERO).await.unwrap();
+    assert!(backend.is_empty());
+
+    backend.ack(redelivered.receipt).await.unwrap();
+    assert_eq!((backend.len(), backend.in_flight()), (0, 0));
+}
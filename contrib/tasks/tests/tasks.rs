@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rocket::local::asynchronous::Client;
+use rocket::tokio::time::sleep;
+use rocket_tasks::{Tasks, Task, Schedule};
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Counter {
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+fn every(millis: u64) -> Schedule {
+    Schedule::every(Duration::from_millis(millis))
+}
+
+fn counting(name: &'static str, busy: Duration) -> Task {
+    Task::with_state(name, every(10), move |counter: Counter, _| async move {
+        counter.0.fetch_add(1, Ordering::SeqCst);
+        sleep(busy).await;
+    })
+}
+
+#[rocket::async_test]
+async fn tasks_run_with_state_until_shutdown() {
+    let counter = Counter::default();
+    let rocket = rocket::build()
+        .manage(counter.clone())
+        .attach(Tasks::new().task(counting("count", Duration::ZERO)));
+
+    let client = Client::untracked(rocket).await.unwrap();
+    sleep(Duration::from_millis(200)).await;
+    assert!(counter.get() >= 2, "ran {} times", counter.get());
+
+    client.terminate().await;
+    let runs = counter.get();
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(counter.get(), runs);
+}
+
+#[rocket::async_test]
+async fn overlapping_runs_are_skipped() {
+    let (serial, overlapping) = (Counter::default(), Counter::default());
+    let busy = Duration::from_millis(100);
+
+    let serial_rocket = rocket::build()
+        .manage(serial.clone())
+        .attach(Tasks::new().task(counting("serial", busy)));
+
+    let overlapping_rocket = rocket::build()
+        .manage(overlapping.clone())
+        .attach(Tasks::new().task(counting("overlapping", busy).overlap(true)));
+
+    let serial_client = Client::untracked(serial_rocket).await.unwrap();
+    let overlapping_client = Client::untracked(overlapping_rocket).await.unwrap();
+    sleep(Duration::from_millis(150)).await;
+    serial_client.terminate().await;
+    overlapping_client.terminate().await;
+
+    assert!(serial.get() <= 2, "serial task ran {} times", serial.get());
+    assert!(overlapping.get() > 2, "overlapping task ran {} times", overlapping.get());
+}
+
+#[rocket::async_test]
+async fn duplicate_names_abort_launch() {
+    let tasks = Tasks::new()
+        .task(Task::new("dup", every(10), |_| async { }))
+        .task(Task::new("dup", every(20), |_| async { }));
+
+    assert!(rocket::build().attach(tasks).ignite().await.is_err());
+}
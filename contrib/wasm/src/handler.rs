@@ -0,0 +1,265 @@
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+
+use rocket::{Data, Request, Response, Route};
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::http::{Header, Method, Status};
+use rocket::response::{self, Responder};
+use rocket::route::{Handler, Outcome};
+use wasmtime::{Config, Engine, Error, InstancePre, Linker, Module, Store, StoreLimitsBuilder};
+
+use crate::host::{self, GuestRequest, GuestResponse, Host};
+
+/// A handler that runs a sandboxed WebAssembly module.
+///
+/// See the [crate level docs](crate) for the host API and an example.
+#[derive(Clone)]
+pub struct Wasm {
+    engine: Engine,
+    pre: InstancePre<Host>,
+    entry: String,
+    fuel: u64,
+    memory: ByteUnit,
+    body: ByteUnit,
+    rank: Option<isize>,
+}
+
+#[derive(Clone)]
+struct WasmHandler(Arc<Wasm>);
+
+impl Wasm {
+    /// The default amount of fuel available to each request: `10,000,000`.
+    pub const FUEL: u64 = 10_000_000;
+
+    /// The default memory limit of each instance: 16 MiB.
+    pub const MEMORY: ByteUnit = ByteUnit::Mebibyte(16);
+
+    /// The default response body limit: 1 MiB.
+    pub const BODY: ByteUnit = ByteUnit::Mebibyte(1);
+
+    /// Compiles the module in `bytes`, which may be in the binary or text
+    /// format, and links it against the host API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module is invalid or imports anything other
+    /// than functions of the host API.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_wasm::Wasm;
+    ///
+    /// let wasm = Wasm::new(r#"
+    ///     (module
+    ///         (memory (export "memory") 1)
+    ///         (func (export "handle")))
+    /// "#);
+    ///
+    /// assert!(wasm.is_ok());
+    /// ```
+    pub fn new<B: AsRef<[u8]>>(bytes: B) -> Result<Wasm, Error> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes)?;
+        let mut linker = Linker::new(&engine);
+        host::link(&mut linker)?;
+
+        Ok(Wasm {
+            pre: linker.instantiate_pre(&module)?,
+            engine,
+            entry: "handle".into(),
+            fuel: Wasm::FUEL,
+            memory: Wasm::MEMORY,
+            body: Wasm::BODY,
+            rank: None,
+        })
+    }
+
+    /// Reads and compiles the module at `path`. See [`Wasm::new()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket_wasm::Wasm;
+    ///
+    /// let wasm = Wasm::from_file("functions/hello.wasm");
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Wasm, Error> {
+        Wasm::new(std::fs::read(path)?)
+    }
+
+    /// Sets the name of the exported function called to handle each request.
+    /// Defaults to `handle`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let wasm = rocket_wasm::Wasm::new("(module (func (export \"main\")))").unwrap();
+    /// let wasm = wasm.entry("main");
+    /// ```
+    pub fn entry<S: Into<String>>(mut self, name: S) -> Self {
+        self.entry = name.into();
+        self
+    }
+
+    /// Sets the amount of fuel available to each request. Every executed
+    /// instruction consumes fuel; a request that runs out of fuel fails.
+    /// Defaults to [`Wasm::FUEL`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let wasm = rocket_wasm::Wasm::new("(module)").unwrap();
+    /// let wasm = wasm.fuel(1_000_000);
+    /// ```
+    pub fn fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    /// Sets the maximum size of the linear memory of each instance. Attempts
+    /// to grow memory beyond the limit fail. Defaults to [`Wasm::MEMORY`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let wasm = rocket_wasm::Wasm::new("(module)").unwrap();
+    /// let wasm = wasm.memory(4.mebibytes());
+    /// ```
+    pub fn memory(mut self, limit: ByteUnit) -> Self {
+        self.memory = limit;
+        self
+    }
+
+    /// Sets the maximum size of a response body. A request whose response
+    /// exceeds the limit fails. Defaults to [`Wasm::BODY`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let wasm = rocket_wasm::Wasm::new("(module)").unwrap();
+    /// let wasm = wasm.body(64.kibibytes());
+    /// ```
+    pub fn body(mut self, limit: ByteUnit) -> Self {
+        self.body = limit;
+        self
+    }
+
+    /// Sets the rank of the handler's route to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let wasm = rocket_wasm::Wasm::new("(module)").unwrap();
+    /// let wasm = wasm.rank(20);
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    /// Instantiates the module in a fresh store and calls its entry point.
+    fn run(&self, request: GuestRequest) -> Result<GuestResponse, Error> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.memory.as_u64().try_into().unwrap_or(usize::MAX))
+            .instances(1)
+            .build();
+
+        let host = Host {
+            request,
+            response: GuestResponse { status: 200, headers: vec![], body: vec![] },
+            limits,
+            max_body: self.body.as_u64().try_into().unwrap_or(usize::MAX),
+        };
+
+        let mut store = Store::new(&self.engine, host);
+        store.limiter(|host| &mut host.limits);
+        store.set_fuel(self.fuel)?;
+
+        let instance = self.pre.instantiate(&mut store)?;
+        let entry = instance.get_typed_func::<(), ()>(&mut store, &self.entry)?;
+        entry.call(&mut store, ())?;
+        Ok(store.into_data().response)
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for WasmHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let limit = req.limits().get("wasm").unwrap_or(1.mebibytes());
+        let body = match data.open(limit).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => return Outcome::Error(Status::PayloadTooLarge),
+            Err(e) => {
+                debug!("failed to read wasm request body: {e}");
+                return Outcome::Error(Status::BadRequest);
+            }
+        };
+
+        let request = GuestRequest {
+            method: req.method().as_str().into(),
+            uri: req.uri().to_string(),
+            headers: req.headers().iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect(),
+            body,
+        };
+
+        let wasm = self.0.clone();
+        match rocket::tokio::task::spawn_blocking(move || wasm.run(request)).await {
+            Ok(Ok(response)) => Outcome::from(req, response),
+            Ok(Err(e)) => {
+                error!(entry = %self.0.entry, "wasm handler failed: {e:#}");
+                Outcome::Error(Status::InternalServerError)
+            }
+            Err(e) => {
+                error!(entry = %self.0.entry, "wasm handler panicked: {e}");
+                Outcome::Error(Status::InternalServerError)
+            }
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for GuestResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.status(Status::new(self.status));
+        for (name, value) in self.headers {
+            response.header_adjoin(Header::new(name, value));
+        }
+
+        response.sized_body(self.body.len(), Cursor::new(self.body)).ok()
+    }
+}
+
+/// Returns a route, matching requests with any method to any path, that
+/// handles requests by running the module.
+impl From<Wasm> for Vec<Route> {
+    fn from(wasm: Wasm) -> Self {
+        let rank = wasm.rank;
+        let handler = WasmHandler(Arc::new(wasm));
+        let mut route = Route::ranked(rank, None::<Method>, "/<_..>", handler);
+        route.name = Some("WebAssembly handler".into());
+        vec![route]
+    }
+}
+
+impl std::fmt::Debug for Wasm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wasm")
+            .field("entry", &self.entry)
+            .field("fuel", &self.fuel)
+            .field("memory", &self.memory)
+            .field("body", &self.body)
+            .field("rank", &self.rank)
+            .finish()
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops::Range;
+
+use wasmtime::{Caller, Error, Extern, Linker, Memory, Result, StoreLimits};
+
+/// The state of a store: the request being handled and the response the guest
+/// is building.
+pub(crate) struct Host {
+    pub request: GuestRequest,
+    pub response: GuestResponse,
+    pub limits: StoreLimits,
+    pub max_body: usize,
+}
+
+pub(crate) struct GuestRequest {
+    pub method: String,
+    pub uri: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+pub(crate) struct GuestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl GuestRequest {
+    fn field(&self, field: i32) -> Result<&[u8]> {
+        match field {
+            0 => Ok(self.method.as_bytes()),
+            1 => Ok(self.uri.as_bytes()),
+            2 => Ok(&self.body),
+            _ => Err(Error::msg(format!("unknown request field {field}"))),
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&[u8]> {
+        self.headers.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_bytes())
+    }
+}
+
+/// Defines the host API in the `rocket` namespace of `linker`.
+pub(crate) fn link(linker: &mut Linker<Host>) -> Result<()> {
+    linker.func_wrap("rocket", "request_len",
+        |caller: Caller<'_, Host>, field: i32| -> Result<i32> {
+            Ok(caller.data().request.field(field)?.len() as i32)
+        })?;
+
+    linker.func_wrap("rocket", "request_read",
+        |mut caller: Caller<'_, Host>, field: i32, ptr: i32, len: i32| -> Result<i32> {
+            let (mem, host) = memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let value = host.request.field(field)?;
+            let n = value.len().min(len as u32 as usize);
+            mem[range(mem.len(), ptr, n as i32)?].copy_from_slice(&value[..n]);
+            Ok(n as i32)
+        })?;
+
+    linker.func_wrap("rocket", "header_len",
+        |mut caller: Caller<'_, Host>, name_ptr: i32, name_len: i32| -> Result<i32> {
+            let (mem, host) = memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let name = string(mem, name_ptr, name_len)?;
+            Ok(host.request.header(&name).map_or(-1, |v| v.len() as i32))
+        })?;
+
+    linker.func_wrap("rocket", "header_read",
+        |mut caller: Caller<'_, Host>, name_ptr: i32, name_len: i32, ptr: i32, len: i32|
+            -> Result<i32>
+        {
+            let (mem, host) = memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let name = string(mem, name_ptr, name_len)?;
+            let Some(value) = host.request.header(&name) else {
+                return Ok(-1);
+            };
+
+            let n = value.len().min(len as u32 as usize);
+            mem[range(mem.len(), ptr, n as i32)?].copy_from_slice(&value[..n]);
+            Ok(n as i32)
+        })?;
+
+    linker.func_wrap("rocket", "response_status",
+        |mut caller: Caller<'_, Host>, code: i32| -> Result<()> {
+            if !(100..=599).contains(&code) {
+                return Err(Error::msg(format!("invalid response status {code}")));
+            }
+
+            caller.data_mut().response.status = code as u16;
+            Ok(())
+        })?;
+
+    linker.func_wrap("rocket", "response_header",
+        |mut caller: Caller<'_, Host>, name_ptr: i32, name_len: i32, ptr: i32, len: i32|
+            -> Result<()>
+        {
+            let (mem, host) = memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let name = string(mem, name_ptr, name_len)?;
+            let value = string(mem, ptr, len)?;
+            if !is_valid_header(&name, &value) {
+                return Err(Error::msg(format!("invalid response header `{name}`")));
+            }
+
+            host.response.headers.push((name, value));
+            Ok(())
+        })?;
+
+    linker.func_wrap("rocket", "response_body",
+        |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> Result<()> {
+            let (mem, host) = memory(&mut caller)?.data_and_store_mut(&mut caller);
+            let bytes = &mem[range(mem.len(), ptr, len)?];
+            if host.response.body.len() + bytes.len() > host.max_body {
+                return Err(Error::msg("response body exceeds limit"));
+            }
+
+            host.response.body.extend_from_slice(bytes);
+            Ok(())
+        })?;
+
+    Ok(())
+}
+
+/// Returns `true` if `name` is a valid header name, `value` a valid header
+/// value, and the header doesn't affect message framing, which Rocket manages.
+fn is_valid_header(name: &str, value: &str) -> bool {
+    const FRAMING: [&str; 3] = ["content-length", "transfer-encoding", "connection"];
+
+    let is_token = |b: u8| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b);
+    !name.is_empty()
+        && name.bytes().all(is_token)
+        && value.bytes().all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+        && !FRAMING.iter().any(|framing| name.eq_ignore_ascii_case(framing))
+}
+
+fn memory(caller: &mut Caller<'_, Host>) -> Result<Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(Error::msg("module does not export `memory`")),
+    }
+}
+
+/// Returns the range of guest memory of size `mem_len` at `ptr` of length
+/// `len`, or an error if it is out of bounds.
+fn range(mem_len: usize, ptr: i32, len: i32) -> Result<Range<usize>> {
+    let (start, len) = (ptr as u32 as usize, len as u32 as usize);
+    start.checked_add(len)
+        .filter(|&end| end <= mem_len)
+        .map(|end| start..end)
+        .ok_or_else(|| Error::msg("guest memory access out of bounds"))
+}
+
+/// Returns a copy of the UTF-8 string in guest memory at `ptr` of length `len`.
+fn string(mem: &[u8], ptr: i32, len: i32) -> Result<String> {
+    let bytes = &mem[range(mem.len(), ptr, len)?];
+    std::str::from_utf8(bytes)
+        .map(|s| s.to_owned())
+        .map_err(|_| Error::msg("guest string is not UTF-8"))
+}
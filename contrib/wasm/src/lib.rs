@@ -0,0 +1,102 @@
+//! Sandboxed WebAssembly handlers for Rocket.
+//!
+//! This crate runs request handlers implemented as WebAssembly modules, such
+//! as user-provided edge functions, inside a [wasmtime] sandbox. A module can
+//! only observe the request it is handling and only affect the response it
+//! produces, through the host API below. Every request runs in a fresh
+//! instance, limited to a fixed amount of [fuel](Wasm::fuel()), bounding the
+//! instructions it may execute, and a fixed amount of
+//! [memory](Wasm::memory()). A module that traps, exceeds a limit, or misuses
+//! the host API fails the request with `500 Internal Server Error`.
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_wasm = "0.1.0"
+//! ```
+//!
+//! Then compile a module and mount it. A [`Wasm`] handler matches requests
+//! with any method to any path under its mount point:
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! use rocket::data::ToByteUnit;
+//! use rocket_wasm::Wasm;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let function = Wasm::from_file("functions/hello.wasm")
+//!         .expect("valid module")
+//!         .fuel(1_000_000)
+//!         .memory(4.mebibytes());
+//!
+//!     rocket::build().mount("/edge", function)
+//! }
+//! ```
+//!
+//! The request body is limited by the `wasm` data limit, 1 MiB by default.
+//!
+//! # Host API
+//!
+//! A module must export a linear memory named `memory` and a function, named
+//! `handle` by default, that takes no arguments and returns nothing. The
+//! module may import the following functions from the `rocket` namespace. All
+//! pointers and lengths are `i32`s referring to the module's memory, and all
+//! strings are UTF-8.
+//!
+//! | function                                         | description                |
+//! |--------------------------------------------------|----------------------------|
+//! | `request_len(field) -> len`                      | a request field's length   |
+//! | `request_read(field, ptr, len) -> n`             | copies a request field     |
+//! | `header_len(name_ptr, name_len) -> len`          | a header's length, or `-1` |
+//! | `header_read(name_ptr, name_len, ptr, len) -> n` | copies a header, or `-1`   |
+//! | `response_status(code)`                          | sets the status            |
+//! | `response_header(name_ptr, name_len, ptr, len)`  | adds a header              |
+//! | `response_body(ptr, len)`                        | appends to the body        |
+//!
+//! Request fields are `0` for the method, `1` for the origin URI, and `2` for
+//! the body. The `read` functions return the number of bytes copied. Header
+//! names are case-insensitive; when a header appears more than once, the first
+//! value is returned. Responses default to `200 OK` with an empty body.
+//! Response headers that affect framing, like `Content-Length`, are rejected.
+//!
+//! # Example
+//!
+//! A module that echoes the request body:
+//!
+//! ```rust
+//! use rocket::local::blocking::Client;
+//! use rocket_wasm::Wasm;
+//!
+//! let echo = Wasm::new(r#"
+//!     (module
+//!         (import "rocket" "request_read"
+//!             (func $read (param i32 i32 i32) (result i32)))
+//!         (import "rocket" "response_body"
+//!             (func $body (param i32 i32)))
+//!         (memory (export "memory") 1)
+//!         (func (export "handle")
+//!             (call $body
+//!                 (i32.const 0)
+//!                 (call $read (i32.const 2) (i32.const 0) (i32.const 4096)))))
+//! "#).unwrap();
+//!
+//! let client = Client::debug(rocket::build().mount("/echo", echo)).unwrap();
+//! let response = client.post("/echo").body("hi!").dispatch();
+//! assert_eq!(response.into_string().unwrap(), "hi!");
+//! ```
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_wasm")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod host;
+mod handler;
+
+pub use handler::Wasm;
+pub use wasmtime;
@@ -0,0 +1,104 @@
+use rocket::data::ToByteUnit;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket_wasm::Wasm;
+
+const MODULE: &str = r#"
+(module
+    (import "rocket" "request_len" (func $len (param i32) (result i32)))
+    (import "rocket" "request_read" (func $read (param i32 i32 i32) (result i32)))
+    (import "rocket" "header_read" (func $header_read (param i32 i32 i32 i32) (result i32)))
+    (import "rocket" "response_status" (func $status (param i32)))
+    (import "rocket" "response_header" (func $header (param i32 i32 i32 i32)))
+    (import "rocket" "response_body" (func $body (param i32 i32)))
+    (memory (export "memory") 1)
+    (data (i32.const 0) "content-type")
+    (data (i32.const 16) "text/plain")
+    (data (i32.const 32) "x-name")
+    (data (i32.const 48) "content-length")
+    (data (i32.const 64) " ")
+
+    ;; Responds `201` with the method, URI, and body, separated by spaces.
+    (func (export "handle")
+        (call $status (i32.const 201))
+        (call $header (i32.const 0) (i32.const 12) (i32.const 16) (i32.const 10))
+        (call $body (i32.const 1024) (call $read (i32.const 0) (i32.const 1024) (i32.const 16)))
+        (call $body (i32.const 64) (i32.const 1))
+        (call $body (i32.const 1024) (call $read (i32.const 1) (i32.const 1024) (i32.const 64)))
+        (call $body (i32.const 64) (i32.const 1))
+        (call $body (i32.const 1024) (call $read (i32.const 2) (i32.const 1024) (i32.const 512))))
+
+    ;; Responds with the value of the `X-Name` header, if any.
+    (func (export "greet")
+        (local $n i32)
+        (local.set $n
+            (call $header_read (i32.const 32) (i32.const 6) (i32.const 1024) (i32.const 64)))
+        (if (i32.lt_s (local.get $n) (i32.const 0))
+            (then (call $status (i32.const 400)) (return)))
+        (call $body (i32.const 1024) (local.get $n)))
+
+    (func (export "spin") (loop $forever (br $forever)))
+
+    (func (export "grow")
+        (if (i32.eq (memory.grow (i32.const 512)) (i32.const -1))
+            (then unreachable)))
+
+    (func (export "oob") (call $body (i32.const 65000) (i32.const 1000)))
+
+    (func (export "framing")
+        (call $header (i32.const 48) (i32.const 14) (i32.const 16) (i32.const 1)))
+
+    (func (export "bad_status") (call $status (i32.const 42))))
+"#;
+
+fn client(configure: impl FnOnce(Wasm) -> Wasm) -> Client {
+    let wasm = configure(Wasm::new(MODULE).unwrap());
+    Client::debug(rocket::build().mount("/fn", wasm)).unwrap()
+}
+
+#[test]
+fn wasm_handler_sees_request_and_responds() {
+    let client = client(|wasm| wasm);
+    let response = client.put("/fn/a/b?c").body("hello, wasm").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    assert!(response.content_type().unwrap().is_plain());
+    assert_eq!(response.into_string().unwrap(), "PUT /fn/a/b?c hello, wasm");
+
+    let client = self::client(|wasm| wasm.entry("greet"));
+    let response = client.get("/fn").header(Header::new("X-Name", "Bob")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Bob");
+
+    let response = client.get("/fn").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn wasm_handler_limits_are_enforced() {
+    let client = client(|wasm| wasm.entry("spin").fuel(10_000));
+    assert_eq!(client.get("/fn").dispatch().status(), Status::InternalServerError);
+
+    let client = self::client(|wasm| wasm.entry("grow").memory(1.mebibytes()));
+    assert_eq!(client.get("/fn").dispatch().status(), Status::InternalServerError);
+
+    let client = self::client(|wasm| wasm.entry("grow").memory(64.mebibytes()));
+    assert_eq!(client.get("/fn").dispatch().status(), Status::Ok);
+
+    let client = self::client(|wasm| wasm.body(4.bytes()));
+    let response = client.post("/fn").body("too long").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
+
+#[test]
+fn wasm_handler_host_api_misuse_fails() {
+    for entry in ["oob", "framing", "bad_status", "missing"] {
+        let client = client(|wasm| wasm.entry(entry));
+        let response = client.get("/fn").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError, "{entry}");
+    }
+}
+
+#[test]
+fn invalid_modules_are_rejected() {
+    assert!(Wasm::new("(module").is_err());
+    assert!(Wasm::new(r#"(module (import "env" "f" (func)))"#).is_err());
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+use std::collections::BTreeMap;
+
+use rocket::{Rocket, Build, Request, Data};
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::{ContentType, Method};
+use rocket::response::Redirect;
+use rocket::route::{Route, Handler, Outcome};
+use rocket::serde::{Serialize, json::serde_json};
+
+use crate::{AssetLinks, AppleAppSiteAssociation, Jrd};
+use crate::webfinger::WebFinger;
+
+/// A fairing that serves documents under `/.well-known`.
+///
+/// Several protocols expect documents at fixed, [well-known] paths:
+/// OpenID Connect discovery, passkey and app link associations, WebFinger,
+/// and more. `WellKnown` registers them in one place, with typed builders for
+/// common formats, and mounts a route for each at ignition.
+///
+/// | method                                      | path                                      |
+/// |---------------------------------------------|-------------------------------------------|
+/// | [`WellKnown::webfinger()`]                  | `/.well-known/webfinger`                  |
+/// | [`WellKnown::change_password()`]            | `/.well-known/change-password`            |
+/// | [`WellKnown::asset_links()`]                | `/.well-known/assetlinks.json`            |
+/// | [`WellKnown::apple_app_site_association()`] | `/.well-known/apple-app-site-association` |
+/// | [`WellKnown::json()`]                       | `/.well-known/{name}`                     |
+/// | [`WellKnown::document()`]                   | `/.well-known/{name}`                     |
+///
+/// Registering a document with the name of an existing one replaces it.
+/// Ignition fails if a document fails to serialize or a name is not a valid
+/// path segment.
+///
+/// [well-known]: https://www.rfc-editor.org/rfc/rfc8615
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::json::json;
+/// use rocket_well_known::{WellKnown, AssetLinks, AppleAppSiteAssociation, Jrd, JrdLink};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let well_known = WellKnown::fairing()
+///         .change_password("/settings/password")
+///         .asset_links(AssetLinks::new().android_app("com.example.app", ["AB:CD:EF"]))
+///         .apple_app_site_association(AppleAppSiteAssociation::new()
+///             .web_credentials("ABCDE12345.com.example.app"))
+///         .webfinger(|resource| {
+///             let user = resource.strip_prefix("acct:")?.strip_suffix("@example.com")?;
+///             Some(Jrd::new(resource).link(JrdLink::new("self")
+///                 .media_type("application/activity+json")
+///                 .href(format!("https://example.com/users/{user}"))))
+///         })
+///         .json("openid-configuration", json!({ "issuer": "https://example.com" }));
+///
+///     rocket::build().attach(well_known)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WellKnown {
+    documents: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Document(Document),
+    Redirect(String),
+    WebFinger(WebFinger),
+    Invalid(String),
+}
+
+/// Handler for a static document.
+#[derive(Debug, Clone)]
+struct Document {
+    content_type: ContentType,
+    body: Arc<[u8]>,
+}
+
+/// Handler for a redirect.
+#[derive(Debug, Clone)]
+struct RedirectTo(String);
+
+impl WellKnown {
+    /// Returns a fairing that serves no documents.
+    pub fn fairing() -> Self {
+        Self::default()
+    }
+
+    fn register(mut self, name: &str, entry: Entry) -> Self {
+        self.documents.insert(name.to_string(), entry);
+        self
+    }
+
+    /// Serves `body` with the given `content_type` at `/.well-known/{name}`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::ContentType;
+    /// use rocket_well_known::WellKnown;
+    ///
+    /// let well_known = WellKnown::fairing()
+    ///     .document("security.txt", ContentType::Plain, "Contact: mailto:a@example.com\n");
+    /// ```
+    pub fn document<B: AsRef<[u8]>>(self, name: &str, content_type: ContentType, body: B) -> Self {
+        let body = body.as_ref().into();
+        self.register(name, Entry::Document(Document { content_type, body }))
+    }
+
+    /// Serves `value`, serialized as JSON, at `/.well-known/{name}`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::json::json;
+    /// use rocket_well_known::WellKnown;
+    ///
+    /// let well_known = WellKnown::fairing()
+    ///     .json("openid-configuration", json!({ "issuer": "https://example.com" }));
+    /// ```
+    pub fn json<T: Serialize>(self, name: &str, value: T) -> Self {
+        match serde_json::to_vec(&value) {
+            Ok(json) => self.document(name, ContentType::JSON, json),
+            Err(e) => self.register(name, Entry::Invalid(e.to_string())),
+        }
+    }
+
+    /// Serves `links` at `/.well-known/assetlinks.json`.
+    pub fn asset_links(self, links: AssetLinks) -> Self {
+        self.json("assetlinks.json", links)
+    }
+
+    /// Serves `association` at `/.well-known/apple-app-site-association`.
+    pub fn apple_app_site_association(self, association: AppleAppSiteAssociation) -> Self {
+        self.json("apple-app-site-association", association)
+    }
+
+    /// Redirects `/.well-known/change-password` to `uri`, the page where users
+    /// change their password, so that password managers can take users
+    /// straight to it.
+    ///
+    /// See the [W3C specification](https://w3c.github.io/webappsec-change-password-url/).
+    pub fn change_password<U: Into<String>>(self, uri: U) -> Self {
+        self.register("change-password", Entry::Redirect(uri.into()))
+    }
+
+    /// Answers WebFinger queries at `/.well-known/webfinger` with `resolver`.
+    ///
+    /// `resolver` is called with the value of the `resource` query parameter,
+    /// usually an `acct:` URI, and returns the descriptor of the resource, if
+    /// it exists. The handler responds with:
+    ///
+    ///   * `400 Bad Request` if `resource` is missing;
+    ///   * `404 Not Found` if `resolver` returns `None`;
+    ///   * the descriptor as `application/jrd+json` otherwise, with its links
+    ///     filtered to the relation types in `rel` query parameters, if any.
+    ///
+    /// All responses allow cross-origin requests, as [RFC 7033] requires.
+    ///
+    /// [RFC 7033]: https://www.rfc-editor.org/rfc/rfc7033
+    pub fn webfinger<F>(self, resolver: F) -> Self
+        where F: Fn(&str) -> Option<Jrd> + Send + Sync + 'static
+    {
+        self.register("webfinger", Entry::WebFinger(WebFinger(Arc::new(resolver))))
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for WellKnown {
+    fn info(&self) -> Info {
+        Info { name: "Well-Known URIs", kind: Kind::Ignite }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut routes = vec![];
+        for (name, entry) in &self.documents {
+            if name.is_empty() || name.contains(['/', '<', '>', '?', '#', '%', ' ']) {
+                error!(%name, "invalid well-known document name");
+                return Err(rocket);
+            }
+
+            let uri = format!("/{name}");
+            let mut route = match entry.clone() {
+                Entry::Document(doc) => Route::new(Method::Get, &uri, doc),
+                Entry::Redirect(to) => Route::new(Method::Get, &uri, RedirectTo(to)),
+                Entry::WebFinger(finger) => Route::new(Method::Get, &uri, finger),
+                Entry::Invalid(e) => {
+                    error!(%name, "failed to serialize well-known document: {e}");
+                    return Err(rocket);
+                }
+            };
+
+            route.name = Some(format!("WellKnown: {name}").into());
+            routes.push(route);
+        }
+
+        Ok(rocket.mount("/.well-known", routes))
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for Document {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, (self.content_type.clone(), self.body.clone()))
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for RedirectTo {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, Redirect::found(self.0.clone()))
+    }
+}
@@ -0,0 +1,176 @@
+use rocket::serde::{Serialize, json::{Value, json}};
+
+/// An Android Digital Asset Links document: `/.well-known/assetlinks.json`.
+///
+/// The document lists statements that associate the site with Android apps
+/// and other sites. Android uses it to verify [App Links] and to share
+/// credentials, including passkeys, between the site and an app.
+///
+/// [App Links]: https://developer.android.com/training/app-links
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_well_known::AssetLinks;
+///
+/// let links = AssetLinks::new()
+///     .android_app("com.example.app", ["14:6D:E9:83:C5:73:06:50:D8:EE:B9:95:2F:34:FC:64"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde", transparent)]
+pub struct AssetLinks(Vec<Value>);
+
+impl AssetLinks {
+    /// The relation that lets the app open the site's URLs.
+    pub const HANDLE_ALL_URLS: &'static str = "delegate_permission/common.handle_all_urls";
+
+    /// The relation that lets the app use the site's credentials.
+    pub const GET_LOGIN_CREDS: &'static str = "delegate_permission/common.get_login_creds";
+
+    /// Returns an empty list of statements.
+    pub fn new() -> Self {
+        AssetLinks(vec![])
+    }
+
+    /// Associates the Android app `package`, signed with the certificates whose
+    /// SHA-256 `fingerprints` are given, with the site via both
+    /// [`HANDLE_ALL_URLS`](Self::HANDLE_ALL_URLS) and
+    /// [`GET_LOGIN_CREDS`](Self::GET_LOGIN_CREDS).
+    pub fn android_app<I, S>(self, package: &str, fingerprints: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let fingerprints: Vec<String> = fingerprints.into_iter().map(Into::into).collect();
+        let target = json!({
+            "namespace": "android_app",
+            "package_name": package,
+            "sha256_cert_fingerprints": fingerprints,
+        });
+
+        self.statement([Self::HANDLE_ALL_URLS, Self::GET_LOGIN_CREDS], target)
+    }
+
+    /// Associates the site at `origin`, like `https://example.com`, with this
+    /// one via [`GET_LOGIN_CREDS`](Self::GET_LOGIN_CREDS).
+    pub fn web(self, origin: &str) -> Self {
+        let target = json!({ "namespace": "web", "site": origin });
+        self.statement([Self::GET_LOGIN_CREDS], target)
+    }
+
+    /// Adds a statement with the `relations` and the raw `target`.
+    pub fn statement<I, S>(mut self, relations: I, target: Value) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let relations: Vec<String> = relations.into_iter().map(Into::into).collect();
+        self.0.push(json!({ "relation": relations, "target": target }));
+        self
+    }
+}
+
+/// An Apple app site association document:
+/// `/.well-known/apple-app-site-association`.
+///
+/// The document associates the site with iOS and macOS apps. Apple platforms
+/// use it to verify [universal links] and to share credentials, including
+/// passkeys, between the site and an app. Apps are identified by their app
+/// ID: a team ID and bundle ID, like `ABCDE12345.com.example.app`.
+///
+/// [universal links]: https://developer.apple.com/documentation/xcode/supporting-associated-domains
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_well_known::AppleAppSiteAssociation;
+///
+/// let aasa = AppleAppSiteAssociation::new()
+///     .app_links("ABCDE12345.com.example.app", ["/users/*", "/posts/*"])
+///     .web_credentials("ABCDE12345.com.example.app");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppleAppSiteAssociation {
+    app_links: Vec<(String, Vec<String>)>,
+    web_credentials: Vec<String>,
+}
+
+impl AppleAppSiteAssociation {
+    /// Returns a document that associates no apps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lets the app `app_id` open URLs whose paths match one of `paths`, which
+    /// may contain `*` and `?` wildcards.
+    pub fn app_links<I, S>(mut self, app_id: &str, paths: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.app_links.push((app_id.into(), paths.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Lets the app `app_id` use the site's credentials.
+    pub fn web_credentials(mut self, app_id: &str) -> Self {
+        self.web_credentials.push(app_id.into());
+        self
+    }
+}
+
+impl Serialize for AppleAppSiteAssociation {
+    fn serialize<S: rocket::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut document = json!({});
+        if !self.app_links.is_empty() {
+            let details: Vec<_> = self.app_links.iter()
+                .map(|(app_id, paths)| json!({
+                    "appIDs": [app_id],
+                    "components": paths.iter().map(|p| json!({ "/": p })).collect::<Vec<_>>(),
+                }))
+                .collect();
+
+            document["applinks"] = json!({ "details": details });
+        }
+
+        if !self.web_credentials.is_empty() {
+            document["webcredentials"] = json!({ "apps": self.web_credentials });
+        }
+
+        document.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_links() {
+        let links = AssetLinks::new().android_app("com.example", ["AA:BB"]).web("https://a.com");
+        assert_eq!(json!(links), json!([
+            {
+                "relation": [AssetLinks::HANDLE_ALL_URLS, AssetLinks::GET_LOGIN_CREDS],
+                "target": {
+                    "namespace": "android_app",
+                    "package_name": "com.example",
+                    "sha256_cert_fingerprints": ["AA:BB"]
+                }
+            },
+            {
+                "relation": [AssetLinks::GET_LOGIN_CREDS],
+                "target": { "namespace": "web", "site": "https://a.com" }
+            }
+        ]));
+    }
+
+    #[test]
+    fn test_apple_app_site_association() {
+        assert_eq!(json!(AppleAppSiteAssociation::new()), json!({}));
+
+        let aasa = AppleAppSiteAssociation::new()
+            .app_links("T.app", ["/a/*", "/b"])
+            .web_credentials("T.app");
+
+        assert_eq!(json!(aasa), json!({
+            "applinks": {
+                "details": [{ "appIDs": ["T.app"], "components": [{ "/": "/a/*" }, { "/": "/b" }] }]
+            },
+            "webcredentials": { "apps": ["T.app"] }
+        }));
+    }
+}
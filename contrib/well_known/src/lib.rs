@@ -0,0 +1,54 @@
+//! Well-known URI documents for Rocket.
+//!
+//! Many protocols locate metadata about a site at fixed paths under
+//! `/.well-known` ([RFC 8615]): WebFinger for federated identities, Digital
+//! Asset Links and Apple app site associations for app links and passkeys,
+//! `change-password` for password managers, OpenID Connect discovery, and so
+//! on. The [`WellKnown`] fairing serves such documents, with typed builders
+//! for the common formats:
+//!
+//!   * [`Jrd`] and [`JrdLink`] for WebFinger responses;
+//!   * [`AssetLinks`] for `assetlinks.json`;
+//!   * [`AppleAppSiteAssociation`] for `apple-app-site-association`.
+//!
+//! Any other document can be served from a serializable value or raw bytes.
+//!
+//! [RFC 8615]: https://www.rfc-editor.org/rfc/rfc8615
+//!
+//! # Usage
+//!
+//! Depend on the crate:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket_well_known = "0.1.0"
+//! ```
+//!
+//! Then attach a [`WellKnown`] fairing with the documents to serve:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_well_known::{WellKnown, AssetLinks};
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .attach(WellKnown::fairing()
+//!             .change_password("/account/password")
+//!             .asset_links(AssetLinks::new().android_app("com.example.app", ["AB:CD"])))
+//! }
+//! ```
+
+#![doc(html_root_url = "https://api.rocket.rs/master/rocket_well_known")]
+#![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
+#![doc(html_logo_url = "https://rocket.rs/images/logo-boxed.png")]
+
+#[macro_use] extern crate rocket;
+
+mod fairing;
+mod formats;
+mod webfinger;
+
+pub use fairing::WellKnown;
+pub use formats::{AssetLinks, AppleAppSiteAssociation};
+pub use webfinger::{Jrd, JrdLink};
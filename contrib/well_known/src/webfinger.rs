@@ -0,0 +1,177 @@
+use std::fmt;
+use std::sync::Arc;
+use std::collections::BTreeMap;
+
+use rocket::{Request, Data};
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::WithHeader;
+use rocket::route::{Handler, Outcome};
+use rocket::serde::{Serialize, json::serde_json};
+
+/// A JSON Resource Descriptor: the response to a WebFinger query.
+///
+/// See [RFC 7033 §4.4](https://www.rfc-editor.org/rfc/rfc7033#section-4.4).
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_well_known::{Jrd, JrdLink};
+///
+/// let jrd = Jrd::new("acct:bob@example.com")
+///     .alias("https://example.com/@bob")
+///     .link(JrdLink::new("self")
+///         .media_type("application/activity+json")
+///         .href("https://example.com/users/bob"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Jrd {
+    /// The URI of the resource described.
+    pub subject: String,
+    /// Other URIs that identify the resource.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Properties of the resource, by URI.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Option<String>>,
+    /// Links related to the resource.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<JrdLink>,
+}
+
+/// A link in a [`Jrd`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct JrdLink {
+    /// The relation type of the link: a registered name or a URI.
+    pub rel: String,
+    /// The media type of the link's target.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// The URI of the link's target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+    /// Human-readable titles for the link, by language tag.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub titles: BTreeMap<String, String>,
+    /// Properties of the link, by URI.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Option<String>>,
+}
+
+impl Jrd {
+    /// Returns a descriptor for `subject` with no aliases, properties, or
+    /// links.
+    pub fn new<S: Into<String>>(subject: S) -> Self {
+        Jrd {
+            subject: subject.into(),
+            aliases: vec![],
+            properties: BTreeMap::new(),
+            links: vec![],
+        }
+    }
+
+    /// Adds the alias `uri`.
+    pub fn alias<S: Into<String>>(mut self, uri: S) -> Self {
+        self.aliases.push(uri.into());
+        self
+    }
+
+    /// Sets the property `uri` to `value`.
+    pub fn property<S: Into<String>>(mut self, uri: S, value: Option<String>) -> Self {
+        self.properties.insert(uri.into(), value);
+        self
+    }
+
+    /// Adds the link `link`.
+    pub fn link(mut self, link: JrdLink) -> Self {
+        self.links.push(link);
+        self
+    }
+}
+
+impl JrdLink {
+    /// Returns a link with relation type `rel` and no other members.
+    pub fn new<S: Into<String>>(rel: S) -> Self {
+        JrdLink {
+            rel: rel.into(),
+            media_type: None,
+            href: None,
+            titles: BTreeMap::new(),
+            properties: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the media type of the link's target.
+    pub fn media_type<S: Into<String>>(mut self, media_type: S) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Sets the URI of the link's target.
+    pub fn href<S: Into<String>>(mut self, href: S) -> Self {
+        self.href = Some(href.into());
+        self
+    }
+
+    /// Sets the title of the link in the language `lang`, or `und` if unknown.
+    pub fn title<L: Into<String>, S: Into<String>>(mut self, lang: L, title: S) -> Self {
+        self.titles.insert(lang.into(), title.into());
+        self
+    }
+
+    /// Sets the property `uri` to `value`.
+    pub fn property<S: Into<String>>(mut self, uri: S, value: Option<String>) -> Self {
+        self.properties.insert(uri.into(), value);
+        self
+    }
+}
+
+/// The resolver of a WebFinger endpoint.
+type Resolver = dyn Fn(&str) -> Option<Jrd> + Send + Sync;
+
+/// Handler for `/.well-known/webfinger` queries.
+#[derive(Clone)]
+pub(crate) struct WebFinger(pub Arc<Resolver>);
+
+impl fmt::Debug for WebFinger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WebFinger").finish_non_exhaustive()
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for WebFinger {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let resource = match req.query_value::<&str>("resource") {
+            Some(Ok(resource)) if !resource.is_empty() => resource,
+            _ => return Outcome::error(Status::BadRequest),
+        };
+
+        let Some(mut jrd) = (self.0)(resource) else {
+            return Outcome::error(Status::NotFound);
+        };
+
+        let rels: Vec<_> = req.query_fields()
+            .filter(|field| field.name.source() == "rel")
+            .map(|field| field.value)
+            .collect();
+
+        if !rels.is_empty() {
+            jrd.links.retain(|link| rels.contains(&link.rel.as_str()));
+        }
+
+        let json = match serde_json::to_string(&jrd) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("failed to serialize WebFinger response: {e}");
+                return Outcome::error(Status::InternalServerError);
+            }
+        };
+
+        // RFC 7033 §5: servers must allow cross-origin requests.
+        let cors = Header::new("Access-Control-Allow-Origin", "*");
+        let response = (ContentType::new("application", "jrd+json"), json);
+        Outcome::from(req, WithHeader(cors, response))
+    }
+}
@@ -0,0 +1,127 @@
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::json::{json, Value};
+use rocket_well_known::{AppleAppSiteAssociation, AssetLinks, Jrd, JrdLink, WellKnown};
+
+fn client(well_known: WellKnown) -> Client {
+    Client::debug(rocket::build().attach(well_known)).unwrap()
+}
+
+fn webfinger() -> WellKnown {
+    WellKnown::fairing().webfinger(|resource| {
+        let user = resource.strip_prefix("acct:")?.strip_suffix("@example.com")?;
+        Some(Jrd::new(resource)
+            .link(JrdLink::new("self")
+                .media_type("application/activity+json")
+                .href(format!("https://example.com/users/{user}")))
+            .link(JrdLink::new("http://webfinger.net/rel/profile-page")
+                .href(format!("https://example.com/@{user}"))))
+    })
+}
+
+#[test]
+fn asset_links() {
+    let links = AssetLinks::new().android_app("com.example.app", ["AB:CD"]);
+    let client = client(WellKnown::fairing().asset_links(links.clone()));
+
+    let response = client.get("/.well-known/assetlinks.json").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_json::<Value>().unwrap(), json!(links));
+}
+
+#[test]
+fn apple_app_site_association() {
+    let aasa = AppleAppSiteAssociation::new().web_credentials("ABCDE12345.com.example.app");
+    let client = client(WellKnown::fairing().apple_app_site_association(aasa));
+
+    let response = client.get("/.well-known/apple-app-site-association").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({
+        "webcredentials": { "apps": ["ABCDE12345.com.example.app"] }
+    }));
+}
+
+#[test]
+fn change_password() {
+    let client = client(WellKnown::fairing().change_password("/account/password"));
+
+    let response = client.get("/.well-known/change-password").dispatch();
+    assert_eq!(response.status(), Status::Found);
+    assert_eq!(response.headers().get_one("Location"), Some("/account/password"));
+}
+
+#[test]
+fn documents() {
+    let client = client(WellKnown::fairing()
+        .document("security.txt", ContentType::Plain, "Contact: mailto:a@example.com\n")
+        .json("openid-configuration", json!({ "issuer": "https://example.com" })));
+
+    let response = client.get("/.well-known/security.txt").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().unwrap(), "Contact: mailto:a@example.com\n");
+
+    let response = client.get("/.well-known/openid-configuration").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_json::<Value>().unwrap()["issuer"], "https://example.com");
+
+    let response = client.get("/.well-known/unknown").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn webfinger_resolves_resources() {
+    let client = client(webfinger());
+
+    let response = client.get("/.well-known/webfinger?resource=acct:bob@example.com").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "jrd+json")));
+    assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({
+        "subject": "acct:bob@example.com",
+        "links": [
+            {
+                "rel": "self",
+                "type": "application/activity+json",
+                "href": "https://example.com/users/bob"
+            },
+            {
+                "rel": "http://webfinger.net/rel/profile-page",
+                "href": "https://example.com/@bob"
+            }
+        ]
+    }));
+}
+
+#[test]
+fn webfinger_filters_links_by_rel() {
+    let client = client(webfinger());
+
+    let uri = "/.well-known/webfinger?resource=acct:bob@example.com&rel=self";
+    let response = client.get(uri).dispatch();
+
+    let jrd = response.into_json::<Value>().unwrap();
+    assert_eq!(jrd["links"].as_array().unwrap().len(), 1);
+    assert_eq!(jrd["links"][0]["rel"], "self");
+}
+
+#[test]
+fn webfinger_errors() {
+    let client = client(webfinger());
+
+    let response = client.get("/.well-known/webfinger").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client.get("/.well-known/webfinger?resource=acct:bob@other.com").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn invalid_names_fail_ignition() {
+    let rocket = rocket::build().attach(WellKnown::fairing().json("a/b", json!({})));
+    assert!(Client::debug(rocket).is_err());
+
+    let rocket = rocket::build().attach(WellKnown::fairing().json("", json!({})));
+    assert!(Client::debug(rocket).is_err());
+}
@@ -7,6 +7,7 @@ use rocket::futures::stream::{Stream, FusedStream};
 
 use crate::frame::{Message, CloseFrame};
 use crate::result::{Result, Error};
+use crate::registry::{Command, Registration};
 
 /// A readable and writeable WebSocket [`Message`] `async` stream.
 ///
@@ -31,22 +32,75 @@ use crate::result::{Result, Error};
 /// }
 /// ```
 ///
+/// If a [`Registry`](crate::registry::Registry) is managed, the stream also
+/// delivers messages sent to the connection via the registry whenever it is
+/// read from or flushed. See [delivery](crate::registry#delivery) for details.
+///
 /// [`StreamExt`]: rocket::futures::StreamExt
 /// [`SinkExt`]: rocket::futures::SinkExt
-pub struct DuplexStream(tokio_tungstenite::WebSocketStream<IoStream>);
+pub struct DuplexStream {
+    inner: tokio_tungstenite::WebSocketStream<IoStream>,
+    registration: Option<Registration>,
+    /// A command from the registry waiting for the sink to become ready.
+    pending: Option<Command>,
+    /// Whether the connection was closed via the registry.
+    killed: bool,
+}
 
 impl DuplexStream {
-    pub(crate) async fn new(stream: IoStream, config: crate::Config) -> Self {
+    pub(crate) async fn new(
+        stream: IoStream,
+        config: crate::Config,
+        registration: Option<Registration>,
+    ) -> Self {
         use tokio_tungstenite::WebSocketStream;
         use crate::tungstenite::protocol::Role;
 
         let inner = WebSocketStream::from_raw_socket(stream, Role::Server, Some(config));
-        DuplexStream(inner.await)
+        DuplexStream { inner: inner.await, registration, pending: None, killed: false }
     }
 
     /// Close the stream now. This does not typically need to be called.
     pub async fn close(&mut self, msg: Option<CloseFrame<'_>>) -> Result<()> {
-        self.0.close(msg).await
+        self.inner.close(msg).await
+    }
+
+    /// Writes commands queued via the registry, if any, to the sink.
+    fn poll_registry(&mut self, cx: &mut Context<'_>) -> Result<()> {
+        let Some(registration) = &self.registration else {
+            return Ok(());
+        };
+
+        registration.register_waker(cx.waker());
+        while !self.killed {
+            let Some(command) = self.pending.take().or_else(|| registration.next_command()) else {
+                break;
+            };
+
+            match self.inner.poll_ready_unpin(cx) {
+                Poll::Ready(result) => result?,
+                Poll::Pending => {
+                    self.pending = Some(command);
+                    break;
+                }
+            }
+
+            let message = match command {
+                Command::Send(message) => message,
+                Command::Close(frame) => {
+                    self.killed = true;
+                    Message::Close(frame)
+                }
+            };
+
+            registration.sent(&message);
+            self.inner.start_send_unpin(message)?;
+        }
+
+        match self.inner.poll_flush_unpin(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Ok(()),
+        }
     }
 }
 
@@ -54,17 +108,35 @@ impl Stream for DuplexStream {
     type Item = Result<Message>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.get_mut().0.poll_next_unpin(cx)
+        let this = self.get_mut();
+        if let Err(e) = this.poll_registry(cx) {
+            return Poll::Ready(Some(Err(e)));
+        }
+
+        if this.killed {
+            // Give the close frame a chance to be written, then end the stream.
+            return this.inner.poll_flush_unpin(cx).map(|_| None);
+        }
+
+        let item = this.inner.poll_next_unpin(cx);
+        if let (Some(registration), Poll::Ready(Some(Ok(message)))) = (&this.registration, &item) {
+            registration.received(message);
+        }
+
+        item
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
+        match self.killed {
+            true => (0, Some(0)),
+            false => self.inner.size_hint(),
+        }
     }
 }
 
 impl FusedStream for DuplexStream {
     fn is_terminated(&self) -> bool {
-        self.0.is_terminated()
+        self.killed || self.inner.is_terminated()
     }
 }
 
@@ -72,18 +144,32 @@ impl Sink<Message> for DuplexStream {
     type Error = Error;
 
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.get_mut().0.poll_ready_unpin(cx)
+        match self.killed {
+            true => Poll::Ready(Err(Error::ConnectionClosed)),
+            false => self.get_mut().inner.poll_ready_unpin(cx),
+        }
     }
 
     fn start_send(self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
-        self.get_mut().0.start_send_unpin(item)
+        let this = self.get_mut();
+        if this.killed {
+            return Err(Error::ConnectionClosed);
+        }
+
+        if let Some(registration) = &this.registration {
+            registration.sent(&item);
+        }
+
+        this.inner.start_send_unpin(item)
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.get_mut().0.poll_flush_unpin(cx)
+        let this = self.get_mut();
+        this.poll_registry(cx)?;
+        this.inner.poll_flush_unpin(cx)
     }
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.get_mut().0.poll_close_unpin(cx)
+        self.get_mut().inner.poll_close_unpin(cx)
     }
 }
@@ -69,6 +69,10 @@
 //!     }
 //! }
 //! ```
+//!
+//! Active connections can be listed, messaged, and closed via a
+//! [`Registry`](registry::Registry) when one is managed by the application.
+//! See the [`registry`] module for details.
 
 #![doc(html_root_url = "https://api.rocket.rs/master/rocket_ws")]
 #![doc(html_favicon_url = "https://rocket.rs/images/favicon.ico")]
@@ -81,6 +85,9 @@ mod tungstenite {
 mod duplex;
 mod websocket;
 
+pub mod local;
+pub mod registry;
+
 pub use self::websocket::{WebSocket, Channel};
 
 /// A WebSocket message.
@@ -0,0 +1,64 @@
+//! In-process WebSocket connections for testing.
+//!
+//! The [`connect()`] function performs a WebSocket handshake against a local,
+//! non-networked instance of Rocket via [`rocket::local`], returning the
+//! client's end of the connection as a [`LocalWebSocket`]. This allows
+//! WebSocket handlers to be tested without binding to a port:
+//!
+//! ```rust
+//! # use rocket::{get, routes};
+//! # use rocket_ws as ws;
+//! use rocket::local::asynchronous::Client;
+//! use rocket::futures::{SinkExt, StreamExt};
+//!
+//! #[get("/echo")]
+//! fn echo(ws: ws::WebSocket) -> ws::Stream!['static] {
+//!     ws::Stream! { ws =>
+//!         for await message in ws {
+//!             yield message?;
+//!         }
+//!     }
+//! }
+//!
+//! # rocket::async_test(async {
+//! let client = Client::debug_with(routes![echo]).await.unwrap();
+//! let mut socket = ws::local::connect(client.get("/echo")).await.unwrap();
+//! socket.send("Hello!".into()).await.unwrap();
+//!
+//! let reply = socket.next().await.unwrap().unwrap();
+//! assert_eq!(reply, ws::Message::from("Hello!"));
+//! # });
+//! ```
+
+use tokio_tungstenite::WebSocketStream;
+use rocket::http::Header;
+use rocket::local::asynchronous::{LocalRequest, LocalResponse, LocalUpgraded};
+
+use crate::tungstenite::protocol::Role;
+
+/// The client's end of a WebSocket connection to a local Rocket instance.
+///
+/// This type implements [`Stream`](rocket::futures::Stream) and
+/// [`Sink`](rocket::futures::Sink) of [`Message`](crate::Message)s.
+pub type LocalWebSocket<'c> = WebSocketStream<LocalUpgraded<'c>>;
+
+/// Performs a WebSocket handshake by dispatching `request`, returning the
+/// client's end of the connection on success and the response on failure.
+///
+/// The WebSocket handshake headers are added to `request` as needed. The
+/// connection is in the client role: messages sent are masked as they would be
+/// by any other WebSocket client. See the [module docs](self) for an example.
+pub async fn connect(
+    request: LocalRequest<'_>
+) -> Result<LocalWebSocket<'_>, LocalResponse<'_>> {
+    use crate::tungstenite::handshake::client::generate_key;
+
+    let upgraded = request
+        .header(Header::new("Sec-WebSocket-Version", "13"))
+        .header(Header::new("Sec-WebSocket-Key", generate_key()))
+        .dispatch_upgrade("websocket")
+        .await?;
+
+    let stream = WebSocketStream::from_raw_socket(upgraded, Role::Client, None);
+    Ok(stream.await)
+}
@@ -0,0 +1,330 @@
+//! A registry of active WebSocket connections.
+//!
+//! Connections are invisible once upgraded: the handler owns the stream and
+//! nothing else can observe or reach it. When a [`Registry`] is managed by the
+//! application, every connection accepted via the [`WebSocket`] guard is
+//! instead listed in the registry for as long as it's open, along with its
+//! route, peer, connection time, [subprotocol], and the number of bytes sent
+//! and received. The registry can also [broadcast](Registry::broadcast()) a
+//! message to every connection, [send](Registry::send()) a message to one, or
+//! forcibly [close](Registry::close()) one, as admin tooling or abuse
+//! mitigation might need to.
+//!
+//! The registry is retrieved as managed state, that is, via [`State`]:
+//!
+//! ```rust
+//! # use rocket::{get, post, routes};
+//! # use rocket_ws as ws;
+//! use rocket::State;
+//! use ws::registry::Registry;
+//!
+//! #[get("/chat")]
+//! fn chat(ws: ws::WebSocket) -> ws::Stream!['static] {
+//!     ws::Stream! { ws =>
+//!         for await message in ws {
+//!             yield message?;
+//!         }
+//!     }
+//! }
+//!
+//! #[get("/admin/connections")]
+//! fn connections(registry: &State<Registry>) -> String {
+//!     registry.connections().iter()
+//!         .map(|c| format!("{} {:?} {}/{}\n", c.id, c.route, c.bytes_in, c.bytes_out))
+//!         .collect()
+//! }
+//!
+//! #[post("/admin/announce", data = "<message>")]
+//! fn announce(registry: &State<Registry>, message: String) -> String {
+//!     let sent = registry.broadcast(message.into());
+//!     format!("sent to {} connection(s)", sent)
+//! }
+//!
+//! #[post("/admin/kick/<id>")]
+//! fn kick(registry: &State<Registry>, id: u64) -> Option<()> {
+//!     registry.close(id, None).then_some(())
+//! }
+//!
+//! #[rocket::launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .manage(Registry::new())
+//!         .mount("/", routes![chat, connections, announce, kick])
+//! }
+//! ```
+//!
+//! # Delivery
+//!
+//! A connection's handler owns its [`DuplexStream`], so messages sent via the
+//! registry are queued and written by the stream itself whenever it is read
+//! from or flushed. Handlers that continuously read from the stream, as nearly
+//! all do, deliver queued messages promptly. At most [`Registry::QUEUE_LIMIT`]
+//! messages are queued per connection; messages beyond the limit are dropped
+//! for that connection, so that a slow or unresponsive client cannot exhaust
+//! memory.
+//!
+//! Closing a connection via [`Registry::close()`] sends a close frame, after
+//! which the stream yields no further messages and rejects further writes. The
+//! handler thus terminates without waiting for the client to acknowledge the
+//! close, and the underlying connection is shut down.
+//!
+//! Bytes sent and received are counted as the payload length of each message,
+//! including control messages, and exclude framing overhead.
+//!
+//! [`WebSocket`]: crate::WebSocket
+//! [`State`]: rocket::State
+//! [`DuplexStream`]: crate::stream::DuplexStream
+//! [subprotocol]: crate::WebSocket::subprotocol()
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use rocket::futures::task::AtomicWaker;
+use rocket::listener::Endpoint;
+use rocket::request::Request;
+
+use crate::Message;
+use crate::frame::CloseFrame;
+
+/// A registry of active WebSocket connections.
+///
+/// See the [module level docs](self) for details and an example.
+#[derive(Clone, Default)]
+pub struct Registry {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: AtomicU64,
+    connections: Mutex<BTreeMap<u64, Arc<Shared>>>,
+}
+
+/// Information about an active WebSocket connection.
+///
+/// Returned by [`Registry::connections()`] and [`Registry::get()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// A unique, increasing identifier for the connection.
+    pub id: u64,
+    /// The URI of the route that accepted the connection, if any.
+    pub route: Option<String>,
+    /// The remote endpoint of the connection, if known.
+    pub peer: Option<Endpoint>,
+    /// When the connection was established.
+    pub connected: SystemTime,
+    /// The subprotocol selected via [`WebSocket::subprotocol()`], if any.
+    ///
+    /// [`WebSocket::subprotocol()`]: crate::WebSocket::subprotocol()
+    pub subprotocol: Option<String>,
+    /// The number of payload bytes received from the client so far.
+    pub bytes_in: u64,
+    /// The number of payload bytes sent to the client so far.
+    pub bytes_out: u64,
+}
+
+/// A message queued for delivery on a connection.
+pub(crate) enum Command {
+    Send(Message),
+    Close(Option<CloseFrame<'static>>),
+}
+
+/// State shared between the registry and a connection's stream.
+pub(crate) struct Shared {
+    id: u64,
+    route: Option<String>,
+    peer: Option<Endpoint>,
+    connected: SystemTime,
+    subprotocol: Option<String>,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    queue: Mutex<VecDeque<Command>>,
+    waker: AtomicWaker,
+}
+
+/// A connection that will be registered once it is established. Captured by
+/// the `WebSocket` request guard.
+pub(crate) struct Pending {
+    registry: Registry,
+    route: Option<String>,
+    peer: Option<Endpoint>,
+}
+
+/// Removes a connection from the registry when dropped.
+pub(crate) struct Registration {
+    registry: Registry,
+    shared: Arc<Shared>,
+}
+
+impl Registry {
+    /// The maximum number of messages queued for delivery on a single
+    /// connection. Messages sent to a connection with a full queue are
+    /// dropped.
+    pub const QUEUE_LIMIT: usize = 64;
+
+    /// Creates an empty registry. The registry only tracks connections once it
+    /// is managed via [`Rocket::manage()`](rocket::Rocket::manage()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_ws::registry::Registry;
+    ///
+    /// let rocket = rocket::build().manage(Registry::new());
+    /// ```
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Returns information about every active connection, in connection
+    /// order.
+    pub fn connections(&self) -> Vec<ConnectionInfo> {
+        self.inner.connections.lock().unwrap()
+            .values()
+            .map(|shared| shared.info())
+            .collect()
+    }
+
+    /// Returns information about the active connection with id `id`, if there
+    /// is one.
+    pub fn get(&self, id: u64) -> Option<ConnectionInfo> {
+        self.inner.connections.lock().unwrap()
+            .get(&id)
+            .map(|shared| shared.info())
+    }
+
+    /// Returns the number of active connections.
+    pub fn len(&self) -> usize {
+        self.inner.connections.lock().unwrap().len()
+    }
+
+    /// Returns `true` if there are no active connections.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues `message` for delivery on every active connection. Returns the
+    /// number of connections the message was queued on.
+    ///
+    /// Connections whose queue is full, or that are being closed, are
+    /// skipped. See [delivery](self#delivery) for details.
+    pub fn broadcast(&self, message: Message) -> usize {
+        self.inner.connections.lock().unwrap()
+            .values()
+            .filter(|shared| shared.push(Command::Send(message.clone())))
+            .count()
+    }
+
+    /// Queues `message` for delivery on the connection with id `id`. Returns
+    /// `true` if the message was queued and `false` if there is no such
+    /// connection, its queue is full, or it is being closed.
+    pub fn send(&self, id: u64, message: Message) -> bool {
+        self.shared(id).map_or(false, |shared| shared.push(Command::Send(message)))
+    }
+
+    /// Forcibly closes the connection with id `id`, sending `frame`, if any,
+    /// in the close message. Returns `true` if there was such a connection.
+    ///
+    /// Messages queued before the call are sent before the close message.
+    /// Closing takes precedence over the queue limit.
+    pub fn close(&self, id: u64, frame: Option<CloseFrame<'static>>) -> bool {
+        self.shared(id).map_or(false, |shared| shared.push(Command::Close(frame)))
+    }
+
+    fn shared(&self, id: u64) -> Option<Arc<Shared>> {
+        self.inner.connections.lock().unwrap().get(&id).cloned()
+    }
+}
+
+impl Shared {
+    fn info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            id: self.id,
+            route: self.route.clone(),
+            peer: self.peer.clone(),
+            connected: self.connected,
+            subprotocol: self.subprotocol.clone(),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Queues `command`, waking the connection's stream. Returns `false` if
+    /// the command was dropped.
+    fn push(&self, command: Command) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        if matches!(queue.back(), Some(Command::Close(_))) {
+            return false;
+        }
+
+        if matches!(command, Command::Send(_)) && queue.len() >= Registry::QUEUE_LIMIT {
+            return false;
+        }
+
+        queue.push_back(command);
+        drop(queue);
+        self.waker.wake();
+        true
+    }
+}
+
+impl Pending {
+    /// Captures the details of the connection being requested by `req` if a
+    /// `Registry` is managed.
+    pub(crate) fn from_request(req: &Request<'_>) -> Option<Self> {
+        Some(Pending {
+            registry: req.rocket().state::<Registry>()?.clone(),
+            route: req.route().map(|route| route.uri.to_string()),
+            peer: req.remote().cloned(),
+        })
+    }
+
+    /// Adds the now established connection to the registry.
+    pub(crate) fn register(self, subprotocol: Option<String>) -> Registration {
+        let inner = &self.registry.inner;
+        let shared = Arc::new(Shared {
+            id: inner.next_id.fetch_add(1, Ordering::Relaxed),
+            route: self.route,
+            peer: self.peer,
+            connected: SystemTime::now(),
+            subprotocol,
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            queue: Mutex::new(VecDeque::new()),
+            waker: AtomicWaker::new(),
+        });
+
+        inner.connections.lock().unwrap().insert(shared.id, shared.clone());
+        Registration { registry: self.registry, shared }
+    }
+}
+
+impl Registration {
+    /// Registers `waker` to be woken when a command is queued.
+    pub(crate) fn register_waker(&self, waker: &std::task::Waker) {
+        self.shared.waker.register(waker);
+    }
+
+    /// Removes and returns the next queued command, if any.
+    pub(crate) fn next_command(&self) -> Option<Command> {
+        self.shared.queue.lock().unwrap().pop_front()
+    }
+
+    /// Records the receipt of `message`.
+    pub(crate) fn received(&self, message: &Message) {
+        self.shared.bytes_in.fetch_add(message.len() as u64, Ordering::Relaxed);
+    }
+
+    /// Records the sending of `message`.
+    pub(crate) fn sent(&self, message: &Message) {
+        self.shared.bytes_out.fetch_add(message.len() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.registry.inner.connections.lock().unwrap().remove(&self.shared.id);
+    }
+}
@@ -9,6 +9,7 @@ use rocket::http::Status;
 use crate::{Config, Message};
 use crate::stream::DuplexStream;
 use crate::result::{Result, Error};
+use crate::registry::Pending;
 
 /// A request guard identifying WebSocket requests. Converts into a [`Channel`]
 /// or [`MessageStream`].
@@ -24,7 +25,11 @@ use crate::result::{Result, Error};
 /// [`Stream!`](crate::Stream!), [`WebSocket::channel()`], or
 /// [`WebSocket::stream()`]. The connection can be configured via
 /// [`WebSocket::config()`]; see [`Config`] for details on configuring a
-/// connection.
+/// connection. A subprotocol requested by the client can be selected via
+/// [`WebSocket::subprotocol()`].
+///
+/// If a [`Registry`](crate::registry::Registry) is managed, the connection is
+/// listed in the registry once it is established.
 ///
 /// ### Forwarding
 ///
@@ -33,6 +38,9 @@ use crate::result::{Result, Error};
 pub struct WebSocket {
     config: Config,
     key: String,
+    requested: Vec<String>,
+    subprotocol: Option<String>,
+    pending: Option<Pending>,
 }
 
 impl WebSocket {
@@ -187,6 +195,71 @@ impl WebSocket {
         &self.key
     }
 
+    /// Returns the subprotocols requested by the client via the
+    /// `Sec-WebSocket-Protocol` header, in order of preference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// # use rocket_ws as ws;
+    /// #
+    /// #[get("/chat")]
+    /// fn chat(ws: ws::WebSocket) -> ws::Stream!['static] {
+    ///     let wants_json = ws.subprotocols().any(|p| p == "chat.json");
+    ///     ws.stream(|io| io)
+    /// }
+    /// ```
+    pub fn subprotocols(&self) -> impl Iterator<Item = &str> {
+        self.requested.iter().map(|p| p.as_str())
+    }
+
+    /// Selects `protocol` as the connection's subprotocol if the client
+    /// requested it. Otherwise, the selection is left unchanged.
+    ///
+    /// The selected subprotocol is sent to the client in the
+    /// `Sec-WebSocket-Protocol` header of the handshake response. If this
+    /// method is called several times, the last requested `protocol` wins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// # use rocket_ws as ws;
+    /// #
+    /// #[get("/chat")]
+    /// fn chat(ws: ws::WebSocket) -> ws::Stream!['static] {
+    ///     let ws = ws.subprotocol("chat.v1").subprotocol("chat.v2");
+    ///     ws.stream(|io| io)
+    /// }
+    /// ```
+    pub fn subprotocol(mut self, protocol: &str) -> Self {
+        if self.subprotocols().any(|p| p == protocol) {
+            self.subprotocol = Some(protocol.to_string());
+        }
+
+        self
+    }
+
+    /// Returns a builder for the handshake response.
+    fn handshake<'o>(&self) -> response::Builder<'o> {
+        let mut response = Response::build();
+        response.raw_header("Sec-Websocket-Version", "13")
+            .raw_header("Sec-WebSocket-Accept", self.key.clone());
+
+        if let Some(protocol) = &self.subprotocol {
+            response.raw_header("Sec-WebSocket-Protocol", protocol.clone());
+        }
+
+        response
+    }
+
+    /// Performs the handshake over `io`, registering the connection.
+    async fn connect(self, io: IoStream) -> DuplexStream {
+        let registration = self.pending.map(|pending| pending.register(self.subprotocol));
+        DuplexStream::new(io, self.config, registration).await
+    }
+
 }
 
 /// A streaming channel, returned by [`WebSocket::channel()`].
@@ -230,7 +303,20 @@ impl<'r> FromRequest<'r> for WebSocket {
         let key = headers.get_one("Sec-WebSocket-Key").map(|k| derive_accept_key(k.as_bytes()));
         match key {
             Some(key) if is_upgrade && is_ws && is_13 => {
-                Outcome::Success(WebSocket { key, config: Config::default() })
+                let requested = headers.get("Sec-WebSocket-Protocol")
+                    .flat_map(|h| h.split(','))
+                    .map(|p| p.trim())
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.to_string())
+                    .collect();
+
+                Outcome::Success(WebSocket {
+                    key,
+                    config: Config::default(),
+                    requested,
+                    subprotocol: None,
+                    pending: Pending::from_request(req),
+                })
             },
             Some(_) | None => Outcome::Forward(Status::BadRequest)
         }
@@ -239,11 +325,7 @@ impl<'r> FromRequest<'r> for WebSocket {
 
 impl<'r, 'o: 'r> Responder<'r, 'o> for Channel<'o> {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
-        Response::build()
-            .raw_header("Sec-Websocket-Version", "13")
-            .raw_header("Sec-WebSocket-Accept", self.ws.key.clone())
-            .upgrade("websocket", self)
-            .ok()
+        self.ws.handshake().upgrade("websocket", self).ok()
     }
 }
 
@@ -251,19 +333,15 @@ impl<'r, 'o: 'r, S> Responder<'r, 'o> for MessageStream<'o, S>
     where S: futures::Stream<Item = Result<Message>> + Send + 'o
 {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
-        Response::build()
-            .raw_header("Sec-Websocket-Version", "13")
-            .raw_header("Sec-WebSocket-Accept", self.ws.key.clone())
-            .upgrade("websocket", self)
-            .ok()
+        self.ws.handshake().upgrade("websocket", self).ok()
     }
 }
 
 #[rocket::async_trait]
 impl IoHandler for Channel<'_> {
     async fn io(self: Box<Self>, io: IoStream) -> io::Result<()> {
-        let stream = DuplexStream::new(io, self.ws.config).await;
-        let result = (self.handler)(stream).await;
+        let Channel { ws, handler } = *self;
+        let result = handler(ws.connect(io).await).await;
         handle_result(result).map(|_| ())
     }
 }
@@ -273,8 +351,9 @@ impl<'r, S> IoHandler for MessageStream<'r, S>
     where S: futures::Stream<Item = Result<Message>> + Send + 'r
 {
     async fn io(self: Box<Self>, io: IoStream) -> io::Result<()> {
-        let (mut sink, source) = DuplexStream::new(io, self.ws.config).await.split();
-        let stream = (self.handler)(source);
+        let MessageStream { ws, handler } = *self;
+        let (mut sink, source) = ws.connect(io).await.split();
+        let stream = handler(source);
         rocket::tokio::pin!(stream);
         while let Some(msg) = stream.next().await {
             let result = match msg {
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use rocket::{get, routes};
+use rocket::futures::{SinkExt, StreamExt};
+use rocket::local::asynchronous::Client;
+use rocket::http::Header;
+use rocket::tokio::time::{sleep, timeout};
+
+use rocket_ws as ws;
+use ws::frame::{CloseCode, CloseFrame};
+use ws::registry::Registry;
+
+#[get("/echo")]
+fn echo(ws: ws::WebSocket) -> ws::Stream!['static] {
+    let ws = ws.subprotocol("echo.v1");
+    ws::Stream! { ws =>
+        for await message in ws {
+            yield message?;
+        }
+    }
+}
+
+async fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(Registry::new())
+        .mount("/", routes![echo]);
+
+    Client::debug(rocket).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn connections_are_tracked_messaged_and_closed() {
+    let client = client().await;
+    let registry = client.rocket().state::<Registry>().unwrap();
+    assert!(registry.is_empty());
+
+    let request = client.get("/echo")
+        .header(Header::new("Sec-WebSocket-Protocol", "echo.v0, echo.v1"));
+
+    let mut socket = ws::local::connect(request).await.unwrap();
+    socket.send("Hello!".into()).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), ws::Message::from("Hello!"));
+
+    let connections = registry.connections();
+    assert_eq!(connections.len(), 1);
+    assert_eq!(connections[0].route.as_deref(), Some("/echo"));
+    assert_eq!(connections[0].subprotocol.as_deref(), Some("echo.v1"));
+    assert_eq!((connections[0].bytes_in, connections[0].bytes_out), (6, 6));
+
+    let id = connections[0].id;
+    assert_eq!(registry.broadcast("Everyone!".into()), 1);
+    assert!(registry.send(id, "You!".into()));
+    assert!(!registry.send(id + 1, "Nobody!".into()));
+    assert_eq!(socket.next().await.unwrap().unwrap(), ws::Message::from("Everyone!"));
+    assert_eq!(socket.next().await.unwrap().unwrap(), ws::Message::from("You!"));
+    assert_eq!(registry.get(id).unwrap().bytes_out, 6 + 9 + 4);
+
+    let frame = CloseFrame { code: CloseCode::Policy, reason: "Goodbye!".into() };
+    assert!(registry.close(id, Some(frame.clone())));
+    let message = socket.next().await.unwrap().unwrap();
+    assert_eq!(message, ws::Message::Close(Some(frame)));
+
+    timeout(Duration::from_secs(5), async {
+        while !registry.is_empty() {
+            sleep(Duration::from_millis(10)).await;
+        }
+    }).await.expect("connection is unregistered once closed");
+
+    assert!(!registry.close(id, None));
+}
+
+#[rocket::async_test]
+async fn unmanaged_registry_tracks_nothing() {
+    let client = Client::debug_with(routes![echo]).await.unwrap();
+    let mut socket = ws::local::connect(client.get("/echo")).await.unwrap();
+    socket.send("Hello!".into()).await.unwrap();
+    assert_eq!(socket.next().await.unwrap().unwrap(), ws::Message::from("Hello!"));
+    assert!(client.rocket().state::<Registry>().is_none());
+}
@@ -0,0 +1,143 @@
+use devise::{Spanned, SpanWrapped, Result, FromMeta, Diagnostic};
+use devise::ext::SpanDiagnosticExt;
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+
+/// The parsed arguments to `#[cache]`.
+#[derive(Debug, FromMeta)]
+struct Meta {
+    max_age: Option<SpanWrapped<usize>>,
+    s_maxage: Option<SpanWrapped<usize>>,
+    public: Option<bool>,
+    private: Option<bool>,
+    no_cache: Option<bool>,
+    no_store: Option<bool>,
+    must_revalidate: Option<bool>,
+    immutable: Option<bool>,
+    vary: Option<SpanWrapped<String>>,
+}
+
+/// Returns the number of seconds in `value` as a `u32`.
+fn seconds(value: &SpanWrapped<usize>) -> Result<u32> {
+    u32::try_from(value.value)
+        .map_err(|_| value.span().error("number of seconds is out of range for `u32`"))
+}
+
+impl Meta {
+    /// Returns the `Cached` builder calls corresponding to `self`.
+    fn directives(&self, span: proc_macro2::Span) -> Result<Vec<TokenStream>> {
+        let flag = |value: Option<bool>| value.unwrap_or(false);
+        if flag(self.public) && flag(self.private) {
+            return Err(span.error("`public` and `private` are mutually exclusive"));
+        }
+
+        if let Some(max_age) = self.max_age.as_ref().filter(|_| flag(self.no_store)) {
+            return Err(max_age.span().error("`max_age` has no effect with `no_store`"));
+        }
+
+        let mut calls = vec![];
+        if let Some(max_age) = &self.max_age {
+            let seconds = seconds(max_age)?;
+            calls.push(quote!(max_age(#seconds)));
+        }
+
+        if let Some(s_maxage) = &self.s_maxage {
+            let seconds = seconds(s_maxage)?;
+            calls.push(quote!(s_maxage(#seconds)));
+        }
+
+        let flags = [
+            (self.public, quote!(public())),
+            (self.private, quote!(private())),
+            (self.no_cache, quote!(no_cache())),
+            (self.no_store, quote!(no_store())),
+            (self.must_revalidate, quote!(must_revalidate())),
+            (self.immutable, quote!(immutable())),
+        ];
+
+        calls.extend(flags.into_iter().filter(|(set, _)| flag(*set)).map(|(_, call)| call));
+        if let Some(vary) = &self.vary {
+            for name in vary.value.split(',').map(|name| name.trim()) {
+                if name.is_empty() || !name.bytes().all(|b| b.is_ascii_graphic() && b != b':') {
+                    return Err(vary.span().error(format!("invalid header name `{name}`"))
+                        .help("`vary` expects a comma-separated list of header names"));
+                }
+
+                calls.push(quote!(vary(#name)));
+            }
+        }
+
+        if calls.is_empty() {
+            return Err(span.error("expected at least one caching directive")
+                .help("for example: `#[cache(max_age = 300, public)]`"));
+        }
+
+        Ok(calls)
+    }
+}
+
+fn _cache(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> Result<TokenStream> {
+    let args: TokenStream = args.into();
+    let span = args.span();
+    let function: syn::ItemFn = syn::parse(input)
+        .map_err(Diagnostic::from)
+        .map_err(|diag| diag.help("`#[cache]` can only be used on functions"))?;
+
+    let attr: devise::MetaItem = syn::parse2(quote!(cache(#args)))?;
+    let directives = Meta::from_meta(&attr)?.directives(span)?;
+
+    // The user's function is nested, renamed, inside of a function with the
+    // same signature whose return value is wrapped in a `Cached`.
+    let mut inner = function.clone();
+    inner.attrs.clear();
+    inner.vis = syn::Visibility::Inherited;
+    inner.sig.ident = syn::Ident::new("__rocket_cached", function.sig.ident.span());
+
+    let mut outer_sig = function.sig.clone();
+    let mut args = vec![];
+    for input in outer_sig.inputs.iter_mut() {
+        let syn::FnArg::Typed(arg) = input else {
+            return Err(input.span().error("`#[cache]` cannot be used on methods"));
+        };
+
+        let syn::Pat::Ident(pat) = &*arg.pat else {
+            return Err(arg.pat.span().error("function arguments must be named")
+                .help("`#[cache]` requires arguments of the form `ident: Type`"));
+        };
+
+        let ident = pat.ident.clone();
+
+        *arg.pat = syn::parse_quote!(#ident);
+        args.push(ident);
+    }
+
+    let ret_ty = match &function.sig.output {
+        syn::ReturnType::Type(_, ty) => quote!(#ty),
+        syn::ReturnType::Default => quote!(()),
+    };
+
+    outer_sig.output = syn::parse_quote_spanned!(ret_ty.span() =>
+        -> #_response::Cached<#ret_ty>
+    );
+
+    let _await = function.sig.asyncness.map(|a| quote_spanned!(a.span() => .await));
+    let (attrs, vis) = (&function.attrs, &function.vis);
+    Ok(quote! {
+        #(#attrs)*
+        #vis #outer_sig {
+            #[allow(clippy::all)]
+            #inner
+
+            #_response::Cached::new(__rocket_cached(#(#args),*) #_await)
+                #(.#directives)*
+        }
+    })
+}
+
+pub fn cache_attribute(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream
+) -> TokenStream {
+    _cache(args, input).unwrap_or_else(|d| d.emit_as_item_tokens())
+}
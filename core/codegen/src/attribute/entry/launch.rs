@@ -1,4 +1,4 @@
-use devise::{Spanned, Result};
+use devise::{Diagnostic, Spanned, Result};
 use devise::ext::SpanDiagnosticExt;
 use proc_macro2::{TokenStream, Span};
 
@@ -53,10 +53,121 @@ fn likely_spawns(f: &syn::ItemFn) -> Option<&syn::ExprCall> {
     v.0
 }
 
+/// Returns the type of the value `expr` constructs if it can be determined
+/// syntactically: `expr` is a struct expression or a call to a tuple struct.
+fn constructed_type(expr: &syn::Expr) -> Option<syn::Type> {
+    let path = match expr {
+        syn::Expr::Struct(e) => &e.path,
+        syn::Expr::Call(e) => match &*e.func {
+            syn::Expr::Path(p) if p.path.segments.len() == 1 => &p.path,
+            _ => return None,
+        },
+        syn::Expr::Paren(e) => return constructed_type(&e.expr),
+        _ => return None,
+    };
+
+    // Avoid enum variants and functions, which are not types.
+    let name = path.segments.last()?.ident.to_string();
+    let camel_case = name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.contains(|c: char| c.is_ascii_lowercase());
+
+    match camel_case && !matches!(&*name, "Some" | "Ok" | "Err") {
+        true => Some(syn::Type::Path(syn::TypePath { qself: None, path: path.clone() })),
+        false => None,
+    }
+}
+
+/// Returns the names of the types and modules declared inside of `f`, which
+/// cannot be named outside of it.
+fn local_items(f: &syn::ItemFn) -> Vec<syn::Ident> {
+    use syn::visit::{self, Visit};
+
+    struct ItemFinder(Vec<syn::Ident>);
+
+    impl<'ast> Visit<'ast> for ItemFinder {
+        fn visit_item(&mut self, i: &'ast syn::Item) {
+            match i {
+                syn::Item::Struct(i) => self.0.push(i.ident.clone()),
+                syn::Item::Enum(i) => self.0.push(i.ident.clone()),
+                syn::Item::Union(i) => self.0.push(i.ident.clone()),
+                syn::Item::Type(i) => self.0.push(i.ident.clone()),
+                syn::Item::Mod(i) => self.0.push(i.ident.clone()),
+                _ => {}
+            }
+
+            visit::visit_item(self, i);
+        }
+    }
+
+    let mut v = ItemFinder(vec![]);
+    v.visit_block(&f.block);
+    v.0
+}
+
+/// Returns the types of the values in `.manage()` calls in `f` that can be
+/// determined syntactically, without duplicates. Types declared inside of `f`
+/// are skipped.
+fn managed_types(f: &syn::ItemFn) -> Vec<syn::Type> {
+    use syn::visit::{self, Visit};
+
+    struct ManageFinder(Vec<syn::Type>, Vec<syn::Ident>);
+
+    impl<'ast> Visit<'ast> for ManageFinder {
+        fn visit_expr_method_call(&mut self, i: &'ast syn::ExprMethodCall) {
+            if i.method == "manage" && i.args.len() == 1 {
+                let turbofish = i.turbofish.as_ref()
+                    .and_then(|t| t.args.first())
+                    .and_then(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                        _ => None,
+                    });
+
+                if let Some(ty) = turbofish.or_else(|| constructed_type(&i.args[0])) {
+                    let local = match &ty {
+                        syn::Type::Path(p) if p.qself.is_none() => p.path.leading_colon.is_none()
+                            && p.path.segments.first().map_or(false, |s| self.1.contains(&s.ident)),
+                        _ => false,
+                    };
+
+                    if !local && !self.0.contains(&ty) {
+                        self.0.push(ty);
+                    }
+                }
+            }
+
+            visit::visit_expr_method_call(self, i);
+        }
+    }
+
+    let mut v = ManageFinder(vec![], local_items(f));
+    v.visit_item_fn(f);
+    v.0
+}
+
+/// Parses the arguments to `#[launch]`, returning `true` if `registry` is set.
+fn registry_arg(args: TokenStream) -> Result<bool> {
+    if args.is_empty() {
+        return Ok(false);
+    }
+
+    match syn::parse2::<syn::Ident>(args) {
+        Ok(ident) if ident == "registry" => Ok(true),
+        Ok(ident) => Err(ident.span()
+            .error("unknown `#[launch]` argument")
+            .help("the only supported argument is `registry`")),
+        Err(e) => Err(Diagnostic::from(e).help("the only supported argument is `registry`")),
+    }
+}
+
 impl EntryAttr for Launch {
     const REQUIRES_ASYNC: bool = false;
 
     fn function(f: &mut syn::ItemFn) -> Result<TokenStream> {
+        Self::function_with_args(TokenStream::new(), f)
+    }
+
+    fn function_with_args(args: TokenStream, f: &mut syn::ItemFn) -> Result<TokenStream> {
+        let registry = registry_arg(args)?;
         if f.sig.ident == "main" {
             return Err(Span::call_site()
                 .error("attribute cannot be applied to `main` function")
@@ -103,6 +214,38 @@ impl EntryAttr for Launch {
             }
         }
 
+        // With `registry`, register the managed types for `state!` against a
+        // marker type, which `state!` expects to find at the crate root.
+        let registry = registry.then(|| {
+            let managed = managed_types(f).into_iter().map(|ty| quote_spanned!(ty.span() =>
+                impl ::rocket::Managed<__rocket_managed> for #ty {
+                    type Type = Self;
+                }
+            ));
+
+            quote_spanned!(Span::call_site() =>
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                /// Rocket code generated registry of types managed by `#[launch]`.
+                pub struct __rocket_managed;
+
+                const _: () = {
+                    let path = ::core::module_path!().as_bytes();
+                    let mut i = 0;
+                    while i < path.len() {
+                        if path[i] == b':' {
+                            ::core::panic!("`#[launch(registry)]` must be applied to a \
+                                function at the crate root: `state!` can't find it elsewhere");
+                        }
+
+                        i += 1;
+                    }
+                };
+
+                #(#managed)*
+            )
+        });
+
         let (vis, mut sig) = (&f.vis, f.sig.clone());
         sig.ident = syn::Ident::new("main", f.sig.ident.span());
         let ret_ty = _ExitCode.respanned(ty.span());
@@ -112,6 +255,8 @@ impl EntryAttr for Launch {
         Ok(quote_spanned!(block.span() =>
             #[allow(dead_code)] #f
 
+            #registry
+
             #vis #sig {
                 #_error::Error::report(::rocket::async_main(#launch))
             }
@@ -15,10 +15,16 @@ trait EntryAttr {
 
     /// Return a new or rewritten function, using block as the main execution.
     fn function(f: &mut syn::ItemFn) -> Result<TokenStream>;
+
+    /// Like [`EntryAttr::function()`] but with the attribute's arguments,
+    /// which are ignored by default.
+    fn function_with_args(_args: TokenStream, f: &mut syn::ItemFn) -> Result<TokenStream> {
+        Self::function(f)
+    }
 }
 
 fn _async_entry<A: EntryAttr>(
-    _args: proc_macro::TokenStream,
+    args: proc_macro::TokenStream,
     input: proc_macro::TokenStream
 ) -> Result<TokenStream> {
     let mut function: syn::ItemFn = syn::parse(input)
@@ -39,7 +45,7 @@ fn _async_entry<A: EntryAttr>(
             .span_note(function.sig.span(), "this function must take no arguments"));
     }
 
-    A::function(&mut function)
+    A::function_with_args(args.into(), &mut function)
 }
 
 macro_rules! async_entry {
@@ -4,3 +4,4 @@ pub mod route;
 pub mod param;
 pub mod async_bound;
 pub mod suppress;
+pub mod cache;
@@ -18,6 +18,8 @@ pub struct Dynamic {
     pub name: Name,
     pub index: usize,
     pub trailing: bool,
+    /// Whether the parameter's value is constrained by a glob, as in `<a: *>`.
+    pub constrained: bool,
 }
 
 #[derive(Debug, Clone)]
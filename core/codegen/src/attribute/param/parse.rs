@@ -5,7 +5,7 @@ use proc_macro2::Span;
 use crate::name::Name;
 use crate::proc_macro_ext::StringLit;
 use crate::attribute::param::{Parameter, Dynamic};
-use crate::http::uri::fmt::{Part, Kind, Path};
+use crate::http::uri::{Glob, error::GlobError, fmt::{Part, Kind, Path}};
 use crate::attribute::suppress::Lint;
 
 #[derive(Debug)]
@@ -25,6 +25,8 @@ pub enum ErrorKind {
     EarlyTrailing,
     NoTrailing,
     Static,
+    Constrained,
+    BadConstraint(GlobError),
 }
 
 impl Dynamic {
@@ -51,6 +53,12 @@ impl Parameter {
         let lint = Lint::SegmentChars;
         if segment.starts_with('<') && segment.ends_with('>') {
             let mut name = &segment[1..(segment.len() - 1)];
+            let mut constraint = None;
+            if let Some((param, pattern)) = name.split_once(':') {
+                name = param.trim_end();
+                constraint = Some(pattern.trim());
+            }
+
             if name.ends_with("..") {
                 trailing = true;
                 name = &name[..(name.len() - 2)];
@@ -63,7 +71,19 @@ impl Parameter {
                 return Err(Error::new(name, span, ErrorKind::BadIdent));
             }
 
-            let dynamic = Dynamic { name: Name::new(name, span), trailing, index: 0 };
+            if let Some(pattern) = constraint {
+                let pattern_span = subspan(pattern, segment, source_span);
+                if P::KIND != Kind::Path {
+                    return Err(Error::new(pattern, pattern_span, ErrorKind::Constrained));
+                } else if let Err(e) = Glob::parse(pattern) {
+                    let kind = ErrorKind::BadConstraint(e);
+                    return Err(Error::new(pattern, pattern_span, kind));
+                }
+            }
+
+            let constrained = constraint.is_some();
+            let name = Name::new(name, span);
+            let dynamic = Dynamic { name, trailing, constrained, index: 0 };
             if dynamic.is_wild() && P::KIND != Kind::Path {
                 return Err(Error::new(name, span, ErrorKind::Ignored));
             } else if dynamic.is_wild() {
@@ -131,6 +151,8 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::NoTrailing => "parameter cannot be trailing".fmt(f),
             ErrorKind::EarlyTrailing => "unexpected text after trailing parameter".fmt(f),
             ErrorKind::Static => "unexpected static parameter".fmt(f),
+            ErrorKind::Constrained => "parameter cannot be constrained".fmt(f),
+            ErrorKind::BadConstraint(e) => write!(f, "invalid parameter constraint: {}", e),
         }
     }
 }
@@ -177,6 +199,15 @@ impl From<Error<'_>> for Diagnostic {
                 error.span.error(error.kind.to_string())
                     .help(format!("parameter must be dynamic: `<{}>`", candidate))
             }
+            ErrorKind::Constrained => {
+                error.span.error(error.kind.to_string())
+                    .help("only path parameters can be constrained")
+            }
+            ErrorKind::BadConstraint(_) => {
+                error.span.error(error.kind.to_string())
+                    .help("constraints are globs such as `*.md` or `[a-z0-9]+`")
+                    .note("see `rocket::http::uri::Glob` for the full syntax")
+            }
         }
     }
 }
@@ -191,6 +222,8 @@ impl devise::FromMeta for Dynamic {
             return Err(Error::new(&string, span, ErrorKind::Ignored).into());
         } else if param.trailing {
             return Err(Error::new(&string, span, ErrorKind::NoTrailing).into());
+        } else if param.constrained {
+            return Err(Error::new(&string, span, ErrorKind::Constrained).into());
         } else {
             Ok(param)
         }
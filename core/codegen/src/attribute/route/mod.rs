@@ -31,6 +31,11 @@ impl Route {
     pub fn query_guards(&self) -> impl Iterator<Item = &Guard> {
         self.query_params.iter().filter_map(|p| p.guard())
     }
+
+    /// The request guards declared via `guards(..)` in the route attribute.
+    pub fn declared_guards(&self) -> impl Iterator<Item = &syn::Type> {
+        self.attr.guards.iter().flat_map(|guards| guards.value.0.iter())
+    }
 }
 
 fn query_decls(route: &Route) -> Option<TokenStream> {
@@ -41,7 +46,7 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
     }
 
     define_spanned_export!(Span::call_site() =>
-        __req, __data, _form, Outcome, _Ok, _Err, _Some, _None, Status
+        __req, __data, _form, _request, Outcome, _Ok, _Err, _Some, _None, Status
     );
 
     // Record all of the static parameters for later filtering.
@@ -67,7 +72,10 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
 
             let ty = quote_spanned!(ty.span() => <#ty as #FromForm>);
             let init = quote_spanned!(ty.span() => #ty::init(#_form::Options::Lenient));
-            let finalize = quote_spanned!(ty.span() => #ty::finalize(#ident));
+            let finalize = quote_spanned!(ty.span() => match #ty::finalize(#ident) {
+                #_Ok(_v) => #ty::validate(_v, #__req).await,
+                #_Err(_err) => #_Err(_err),
+            });
             let push = match guard.trailing {
                 true => quote_spanned!(ty.span() => #ty::push_value(&mut #ident, _f)),
                 _ => quote_spanned!(ty.span() => #ty::push_value(&mut #ident, _f.shift())),
@@ -77,6 +85,8 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
         })
         .split6();
 
+    let ty = route.query_guards().map(|guard| &guard.ty);
+
     #[allow(non_snake_case)]
     Some(quote! {
         let (#(#ident),*) = {
@@ -98,6 +108,15 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
                 let #ident = match #finalize_expr {
                     #_Ok(_v) => #_Some(_v),
                     #_Err(_err) => {
+                        #__req.record_guard_failure(
+                            #_request::GuardKind::Query,
+                            #_Some(#name),
+                            stringify!(#ty),
+                            #Status::UnprocessableEntity,
+                            true,
+                            #_Some(format_args!("{}", _err)),
+                        );
+
                         __e.extend(_err.with_name(#_form::NameView::new(#name)));
                         #_None
                     },
@@ -123,15 +142,32 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
 }
 
 fn request_guard_decl(guard: &Guard) -> TokenStream {
-    let (ident, ty) = (guard.fn_ident.rocketized(), &guard.ty);
+    let (name, ident, ty) = (&guard.fn_ident, guard.fn_ident.rocketized(), &guard.ty);
     define_spanned_export!(ty.span() =>
-        __req, __data, _request, display_hack, FromRequest, Outcome
+        __req, __data, _request, _profile, _Some, _None, display_hack, FromRequest, Outcome
     );
 
     quote_spanned! { ty.span() =>
-        let #ident: #ty = match <#ty as #FromRequest>::from_request(#__req).await {
-            #Outcome::Success(__v) => __v,
+        let #ident: #ty = match {
+            let __mark = #_profile::Mark::now();
+            let __outcome = <#ty as #FromRequest>::from_request(#__req).await;
+            __mark.record(#__req, #_profile::Stage::Guard(stringify!(#name)));
+            __outcome
+        } {
+            #Outcome::Success(__v) => {
+                #__req.discard_failure_explanation();
+                __v
+            },
             #Outcome::Forward(__e) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Request,
+                    #_Some(stringify!(#name)),
+                    stringify!(#ty),
+                    __e,
+                    true,
+                    #_None,
+                );
+
                 ::rocket::trace::info!(
                     name: "forward",
                     target: concat!("rocket::codegen::route::", module_path!()),
@@ -145,6 +181,15 @@ fn request_guard_decl(guard: &Guard) -> TokenStream {
             },
             #[allow(unreachable_code)]
             #Outcome::Error((__c, __e)) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Request,
+                    #_Some(stringify!(#name)),
+                    stringify!(#ty),
+                    __c,
+                    false,
+                    #_Some(format_args!("{}", #display_hack!(&__e))),
+                );
+
                 ::rocket::trace::info!(
                     name: "failure",
                     target: concat!("rocket::codegen::route::", module_path!()),
@@ -160,15 +205,96 @@ fn request_guard_decl(guard: &Guard) -> TokenStream {
     }
 }
 
+fn declared_guard_decl(
+    ty: &syn::Type,
+    on_fail: Option<&crate::http_codegen::Status>
+) -> TokenStream {
+    define_spanned_export!(ty.span() =>
+        __req, __data, _request, _profile, _Some, _None, display_hack, FromRequest, Outcome
+    );
+
+    // With `on_fail`, both forwards and errors become errors with that status.
+    let forward = match on_fail {
+        Some(status) => quote_spanned!(ty.span() => return #Outcome::Error(#status)),
+        None => quote_spanned!(ty.span() => return #Outcome::Forward((#__data, __e))),
+    };
+
+    let error = match on_fail {
+        Some(status) => quote_spanned!(ty.span() => #status),
+        None => quote_spanned!(ty.span() => __c),
+    };
+
+    quote_spanned! { ty.span() =>
+        match {
+            let __mark = #_profile::Mark::now();
+            let __outcome = <#ty as #FromRequest>::from_request(#__req).await;
+            __mark.record(#__req, #_profile::Stage::Guard(stringify!(#ty)));
+            __outcome
+        } {
+            #Outcome::Success(_) => #__req.discard_failure_explanation(),
+            #Outcome::Forward(__e) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Request,
+                    #_None,
+                    stringify!(#ty),
+                    __e,
+                    true,
+                    #_None,
+                );
+
+                ::rocket::trace::info!(
+                    name: "forward",
+                    target: concat!("rocket::codegen::route::", module_path!()),
+                    type_name = stringify!(#ty),
+                    status = __e.code,
+                    "declared request guard forwarding"
+                );
+
+                #forward;
+            },
+            #[allow(unreachable_code)]
+            #Outcome::Error((__c, __e)) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Request,
+                    #_None,
+                    stringify!(#ty),
+                    __c,
+                    false,
+                    #_Some(format_args!("{}", #display_hack!(&__e))),
+                );
+
+                ::rocket::trace::info!(
+                    name: "failure",
+                    target: concat!("rocket::codegen::route::", module_path!()),
+                    type_name = stringify!(#ty),
+                    reason = %#display_hack!(__e),
+                    "declared request guard failed"
+                );
+
+                return #Outcome::Error(#error);
+            }
+        };
+    }
+}
+
 fn param_guard_decl(guard: &Guard) -> TokenStream {
     let (i, name, ty) = (guard.index, &guard.name, &guard.ty);
     define_spanned_export!(ty.span() =>
-        __req, __data, _None, _Some, _Ok, _Err,
+        __req, __data, _request, _None, _Some, _Ok, _Err,
         Outcome, FromSegments, FromParam, Status, display_hack
     );
 
     // Returned when a dynamic parameter fails to parse.
     let parse_error = quote!({
+        #__req.record_guard_failure(
+            #_request::GuardKind::Path,
+            #_Some(#name),
+            stringify!(#ty),
+            #Status::UnprocessableEntity,
+            true,
+            #_Some(format_args!("{}", #display_hack!(&__error))),
+        );
+
         ::rocket::trace::info!(
             name: "forward",
             target: concat!("rocket::codegen::route::", module_path!()),
@@ -218,13 +344,32 @@ fn param_guard_decl(guard: &Guard) -> TokenStream {
 }
 
 fn data_guard_decl(guard: &Guard) -> TokenStream {
-    let (ident, ty) = (guard.fn_ident.rocketized(), &guard.ty);
-    define_spanned_export!(ty.span() => __req, __data, display_hack, FromData, Outcome);
+    let (name, ident, ty) = (&guard.fn_ident, guard.fn_ident.rocketized(), &guard.ty);
+    define_spanned_export!(ty.span() =>
+        __req, __data, _request, _profile, _Some, _None, display_hack, FromData, Outcome
+    );
 
     quote_spanned! { ty.span() =>
-        let #ident: #ty = match <#ty as #FromData>::from_data(#__req, #__data).await {
-            #Outcome::Success(__d) => __d,
+        let #ident: #ty = match {
+            let __mark = #_profile::Mark::now();
+            let __outcome = <#ty as #FromData>::from_data(#__req, #__data).await;
+            __mark.record(#__req, #_profile::Stage::Guard(stringify!(#name)));
+            __outcome
+        } {
+            #Outcome::Success(__d) => {
+                #__req.discard_failure_explanation();
+                __d
+            },
             #Outcome::Forward((__d, __e)) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Data,
+                    #_Some(stringify!(#name)),
+                    stringify!(#ty),
+                    __e,
+                    true,
+                    #_None,
+                );
+
                 ::rocket::trace::info!(
                     name: "forward",
                     target: concat!("rocket::codegen::route::", module_path!()),
@@ -238,6 +383,15 @@ fn data_guard_decl(guard: &Guard) -> TokenStream {
             }
             #[allow(unreachable_code)]
             #Outcome::Error((__c, __e)) => {
+                #__req.record_guard_failure(
+                    #_request::GuardKind::Data,
+                    #_Some(stringify!(#name)),
+                    stringify!(#ty),
+                    __c,
+                    false,
+                    #_Some(format_args!("{}", #display_hack!(&__e))),
+                );
+
                 ::rocket::trace::info!(
                     name: "failure",
                     target: concat!("rocket::codegen::route::", module_path!()),
@@ -307,10 +461,16 @@ fn responder_outcome_expr(route: &Route) -> TokenStream {
     let _await = route.handler.sig.asyncness
         .map(|a| quote_spanned!(a.span() => .await));
 
-    define_spanned_export!(ret_span => __req, _route);
+    define_spanned_export!(ret_span => __req, _route, _profile);
     quote_spanned! { mixed(ret_span) =>
+        let ___mark = #_profile::Mark::now();
         let ___responder = #user_handler_fn_name(#(#parameter_names),*) #_await;
-        #_route::Outcome::from(#__req, ___responder)
+        ___mark.record(#__req, #_profile::Stage::Handler);
+
+        let ___mark = #_profile::Mark::now();
+        let ___outcome = #_route::Outcome::from(#__req, ___responder);
+        ___mark.record(#__req, #_profile::Stage::Responder);
+        ___outcome
     }
 }
 
@@ -357,6 +517,7 @@ fn sentinels_expr(route: &Route) -> TokenStream {
 
     let eligible_types = route.guards()
         .map(|guard| &guard.ty)
+        .chain(route.declared_guards())
         .chain(ret_ty.as_ref())
         .flat_map(|ty| ty.unfold_with_ty_macros(TY_MACS, ty_mac_mapper))
         .filter(|ty| ty.is_concrete(&generic_idents))
@@ -376,10 +537,43 @@ fn sentinels_expr(route: &Route) -> TokenStream {
     quote!(::std::vec![#(#sentinel),*])
 }
 
+fn typed_route_impl(route: &Route) -> Option<TokenStream> {
+    let generic_idents: Vec<_> = route.handler.sig.generics
+        .type_params()
+        .map(|p| &p.ident)
+        .collect();
+
+    // Generic data guards can't be named outside of the handler, so we don't
+    // emit an implementation for routes with them.
+    let data = match route.data_guard {
+        Some(ref guard) if !guard.ty.is_concrete(&generic_idents) => return None,
+        Some(ref guard) => {
+            let ty = guard.ty.with_static_lifetimes();
+            quote_spanned!(ty.span() => fn(#ty))
+        }
+        None => quote!(fn()),
+    };
+
+    let handler_fn_name = &route.handler.sig.ident;
+    let method = Optional(route.attr.method.clone());
+    define_spanned_export!(handler_fn_name.span() => _local, _http, _Option);
+    Some(quote! {
+        /// Rocket code generated typed route implementation.
+        #[allow(nonstandard_style, deprecated, clippy::style)]
+        impl #_local::TypedRoute for #handler_fn_name {
+            type Data = #data;
+
+            const METHOD: #_Option<#_http::Method> = #method;
+        }
+    })
+}
+
 fn codegen_route(route: Route) -> Result<TokenStream> {
     use crate::exports::*;
 
     // Generate the declarations for all of the guards.
+    let on_fail = route.attr.on_fail.as_ref().map(|status| &status.value);
+    let declared_guards = route.declared_guards().map(|ty| declared_guard_decl(ty, on_fail));
     let request_guards = route.request_guards.iter().map(request_guard_decl);
     let param_guards = route.param_guards().map(param_guard_decl);
     let query_guards = query_decls(&route);
@@ -388,17 +582,30 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     // Extract the sentinels from the route.
     let sentinels = sentinels_expr(&route);
 
+    // Record the names and types of dynamic parameters for route metadata.
+    let params = route.param_guards().chain(route.query_guards()).map(|guard| {
+        let (name, ty) = (&guard.name, guard.ty.with_stripped_lifetimes());
+        quote!((#name, stringify!(#ty)))
+    });
+
     // Gather info about the function.
     let (vis, handler_fn) = (&route.handler.vis, &route.handler);
     let deprecated = handler_fn.attrs.iter().find(|a| a.path().is_ident("deprecated"));
     let handler_fn_name = &handler_fn.sig.ident;
     let internal_uri_macro = internal_uri_macro_decl(&route);
     let responder_outcome = responder_outcome_expr(&route);
+    let typed_route = typed_route_impl(&route);
 
     let method = Optional(route.attr.method.clone());
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
     let format = Optional(route.attr.format.as_ref());
+    let deprecated = Optional(route.attr.deprecated.as_ref().map(|deprecated| {
+        let since = deprecated.since.value.0;
+        let sunset = Optional(deprecated.sunset.as_ref().map(|date| date.value.0));
+        let successor = Optional(deprecated.successor.as_ref().map(|uri| &uri.value.0));
+        quote!((#since, #sunset, #successor))
+    }));
 
     Ok(quote! {
         #handler_fn
@@ -417,6 +624,7 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     #__data: #Data<'__r>
                 ) -> #_route::BoxFuture<'__r> {
                     #_Box::pin(async move {
+                        #(#declared_guards)*
                         #(#request_guards)*
                         #(#param_guards)*
                         #query_guards
@@ -435,6 +643,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     rank: #rank,
                     sentinels: #sentinels,
                     location: (::core::file!(), ::core::line!(), ::core::column!()),
+                    params: &[#(#params),*],
+                    deprecated: #deprecated,
                 }
             }
 
@@ -444,6 +654,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
             }
         }
 
+        #typed_route
+
         /// Rocket code generated wrapping URI macro.
         #internal_uri_macro
     })
@@ -490,6 +702,9 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        guards: method_attribute.guards,
+        on_fail: method_attribute.on_fail,
+        deprecated: method_attribute.deprecated,
     };
 
     codegen_route(Route::from(attribute, function)?)
@@ -5,7 +5,7 @@ use proc_macro2::Span;
 
 use crate::attribute::suppress::Lint;
 use crate::proc_macro_ext::Diagnostics;
-use crate::http_codegen::{Method, MediaType};
+use crate::http_codegen::{Method, MediaType, Status};
 use crate::attribute::param::{Parameter, Dynamic, Guard};
 use crate::syn_ext::FnArgExt;
 use crate::name::Name;
@@ -48,6 +48,9 @@ pub struct Attribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub guards: Option<SpanWrapped<Guards>>,
+    pub on_fail: Option<SpanWrapped<Status>>,
+    pub deprecated: Option<SpanWrapped<Deprecation>>,
 }
 
 /// The parsed `#[method(..)]` (e.g, `get`, `put`, etc.) attribute.
@@ -58,6 +61,101 @@ pub struct MethodAttribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub guards: Option<SpanWrapped<Guards>>,
+    pub on_fail: Option<SpanWrapped<Status>>,
+    pub deprecated: Option<SpanWrapped<Deprecation>>,
+}
+
+/// The request guard types in `guards(A, B, ..)`: required but not bound.
+#[derive(Debug)]
+pub struct Guards(pub Vec<syn::Type>);
+
+impl FromMeta for Guards {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        use quote::ToTokens;
+
+        let types = meta.list()?
+            .map(|item| syn::parse2::<syn::Type>(item.to_token_stream())
+                .map(|ty| ty.with_stripped_lifetimes())
+                .map_err(|e| item.span().error(format!("invalid guard type: {}", e))))
+            .collect::<Result<Vec<_>>>()?;
+
+        if types.is_empty() {
+            return Err(meta.span().error("expected at least one request guard type")
+                .help("list request guard types as in `guards(AdminUser, ApiKey)`"));
+        }
+
+        Ok(Guards(types))
+    }
+}
+
+/// The parsed `deprecated(since = "..", sunset = "..", successor = "..")`.
+#[derive(Debug, FromMeta)]
+pub struct Deprecation {
+    pub since: SpanWrapped<Date>,
+    pub sunset: Option<SpanWrapped<Date>>,
+    pub successor: Option<SpanWrapped<Successor>>,
+}
+
+/// A `YYYY-MM-DD` date as the number of seconds since the Unix epoch at
+/// midnight, UTC, of that day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date(pub i64);
+
+impl Date {
+    fn parse(string: &str) -> Option<Date> {
+        let mut parts = string.split('-');
+        let mut next = |len: usize| parts.next()
+            .filter(|p| p.len() == len && p.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|p| p.parse::<i64>().ok());
+
+        let (year, month, day) = (next(4)?, next(2)?, next(2)?);
+        if parts.next().is_some() || year < 1 || !(1..=12).contains(&month) {
+            return None;
+        }
+
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+
+        if !(1..=days_in_month).contains(&day) {
+            return None;
+        }
+
+        // Days since the epoch of the proleptic Gregorian `year-month-day`.
+        let year = if month <= 2 { year - 1 } else { year };
+        let (era, year_of_era) = (year / 400, year % 400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Some(Date((era * 146097 + day_of_era - 719468) * 86400))
+    }
+}
+
+impl FromMeta for Date {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        let string = crate::proc_macro_ext::StringLit::from_meta(meta)?;
+        Date::parse(&string).ok_or_else(|| string.span().error("invalid date")
+            .help("dates are of the form \"YYYY-MM-DD\", as in \"2025-06-30\""))
+    }
+}
+
+/// The URI of a deprecated route's successor: any valid URI reference.
+#[derive(Debug)]
+pub struct Successor(pub String);
+
+impl FromMeta for Successor {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        let string = crate::proc_macro_ext::StringLit::from_meta(meta)?;
+        match crate::http::uri::Reference::parse(&string) {
+            Ok(_) => Ok(Successor(string.0)),
+            Err(e) => Err(string.span().error(format!("invalid successor URI: {}", e))
+                .help("expected a URI reference, as in \"/v2/users\"")),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -151,6 +249,28 @@ impl Route {
             }
         }
 
+        // Check that `on_fail` is an error status applied to declared guards.
+        if let Some(ref on_fail) = attr.on_fail {
+            let class = on_fail.value.0.class();
+            if attr.guards.is_none() {
+                diags.push(on_fail.full_span.error("`on_fail` requires `guards`")
+                    .help("`on_fail` sets the status used when a guard in `guards(..)` fails"));
+            } else if !class.is_client_error() && !class.is_server_error() {
+                diags.push(on_fail.span.error("`on_fail` must be an error status")
+                    .help("error statuses are in the range [400, 599]"));
+            }
+        }
+
+        // Check that a deprecated route's sunset follows its deprecation.
+        if let Some(ref deprecated) = attr.deprecated {
+            if let Some(sunset) = deprecated.sunset.as_ref() {
+                if sunset.value < deprecated.since.value {
+                    diags.push(sunset.span().error("`sunset` precedes deprecation")
+                        .span_note(deprecated.since.span(), "route is deprecated `since` here"));
+                }
+            }
+        }
+
         // Check the validity of function arguments.
         let span = handler.sig.paren_token.span.join();
         let mut arguments = Arguments { map: ArgumentMap::new(), span };
@@ -217,7 +337,7 @@ impl Route {
             })
             .enumerate()
             .map(|(index, (name, (ident, ty)))| Guard {
-                source: Dynamic { index, name: name.clone(), trailing: false },
+                source: Dynamic { index, name: name.clone(), trailing: false, constrained: false },
                 fn_ident: ident.clone(),
                 ty: ty.clone(),
             })
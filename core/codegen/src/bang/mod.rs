@@ -44,6 +44,14 @@ pub fn catchers_macro(input: proc_macro::TokenStream) -> TokenStream {
         .unwrap_or_else(|diag| diag.emit_as_expr_tokens())
 }
 
+pub fn state_macro(input: proc_macro::TokenStream) -> TokenStream {
+    syn::parse::<syn::Type>(input)
+        .map(|ty| quote_spanned! { ty.span() =>
+            &::rocket::State<<#ty as ::rocket::Managed<crate::__rocket_managed>>::Type>
+        })
+        .unwrap_or_else(|e| e.to_compile_error())
+}
+
 pub fn uri_macro(input: proc_macro::TokenStream) -> TokenStream {
     uri::_uri_macro(input.into())
         .unwrap_or_else(|diag| diag.emit_as_expr_tokens_or(quote! {
@@ -29,6 +29,7 @@ pub struct FieldAttr {
     pub validate: Option<SpanWrapped<syn::Expr>>,
     pub default: Option<syn::Expr>,
     pub default_with: Option<syn::Expr>,
+    pub step: Option<SpanWrapped<usize>>,
 }
 
 impl FieldAttr {
@@ -248,6 +249,7 @@ impl<'a> Visit<'a> for RecordMemberAccesses {
 struct ValidationMutator<'a> {
     field: Field<'a>,
     visited: bool,
+    request: bool,
 }
 
 impl ValidationMutator<'_> {
@@ -294,6 +296,10 @@ impl VisitMut for ValidationMutator<'_> {
         self.visited = true;
         let accessor = self.field.context_ident().with_span(self.field.ty.span());
         call.args.insert(0, syn::parse_quote!(#accessor));
+        if self.request {
+            call.args.push(syn::parse_quote!(__req));
+        }
+
         syn::visit_mut::visit_expr_call_mut(self, call);
     }
 
@@ -343,17 +349,42 @@ impl VisitMut for ValidationMutator<'_> {
     }
 }
 
+/// Validators run during `finalize()`.
 pub fn validators(field: Field<'_>) -> Result<impl Iterator<Item = syn::Expr> + '_> {
+    validators_of(field, false)
+}
+
+/// Validators of the form `f(..).await`, run during `validate()` with the
+/// request passed as the last argument to `f`.
+pub fn async_validators(field: Field<'_>) -> Result<impl Iterator<Item = syn::Expr> + '_> {
+    validators_of(field, true)
+}
+
+fn validators_of(
+    field: Field<'_>,
+    is_async: bool,
+) -> Result<impl Iterator<Item = syn::Expr> + '_> {
+    // Asynchronous validators run outside of the parsing context, so their
+    // errors are named relative to the form; `validate()` adds any parents.
+    let span = field.span();
+    define_spanned_export!(span => _form);
+    let name_opt = match (is_async, field.first_field_name()?) {
+        (false, _) => field.name_buf_opt()?,
+        (true, Some(name)) => quote_spanned!(span => Some(#_form::NameBuf::from((None, #name)))),
+        (true, None) => quote_spanned!(span => None::<#_form::NameBuf>),
+    };
+
     Ok(FieldAttr::from_attrs(FieldAttr::NAME, &field.attrs)?
         .into_iter()
         .chain(FieldAttr::from_attrs(FieldAttr::NAME, field.parent.attrs())?)
         .filter_map(|a| a.validate)
+        .filter(move |expr| matches!(**expr, syn::Expr::Await(_)) == is_async)
         .map(move |mut expr| {
             let mut record = RecordMemberAccesses::default();
             record.accesses.insert((field.context_ident(), true));
             record.visit_expr(&expr);
 
-            let mut v = ValidationMutator { field, visited: false };
+            let mut v = ValidationMutator { field, visited: false, request: is_async };
             v.visit_expr_mut(&mut expr);
 
             let span = expr.key_span.unwrap_or(field.ty.span());
@@ -366,7 +397,6 @@ pub fn validators(field: Field<'_>) -> Result<impl Iterator<Item = syn::Expr> +
 
             let matchers = quote_spanned!(span => (#(Some(#matchers)),*));
             let values = quote_spanned!(span => (#(#values),*));
-            let name_opt = field.name_buf_opt().unwrap();
 
             define_spanned_export!(span => _form);
             let expr: syn::Expr = syn::parse_quote_spanned!(span => {
@@ -454,6 +484,56 @@ pub fn default(field: Field<'_>) -> Result<Option<TokenStream>> {
     }
 }
 
+pub fn step(field: Field<'_>) -> Result<Option<usize>> {
+    let mut steps = FieldAttr::from_attrs(FieldAttr::NAME, &field.attrs)?
+        .into_iter()
+        .filter_map(|a| a.step);
+
+    let step = steps.next();
+    if let Some(dup) = steps.next() {
+        return Err(dup.span().error("duplicate form step")
+            .help("at most one `step` is allowed per field"));
+    }
+
+    match step {
+        Some(step) if step.value == 0 => Err(step.span().error("form steps start at `1`")),
+        step => Ok(step.map(|s| s.value)),
+    }
+}
+
+/// The validator calls, i.e, `len(..)` in `len(..).or_else(..)`, of the
+/// synchronous validators that don't refer to `self`, paired with the name of
+/// the called function.
+pub fn validator_calls(field: Field<'_>) -> Result<Vec<(String, syn::ExprCall)>> {
+    fn refers_to_self(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(i) => i == "self",
+            TokenTree::Group(g) => refers_to_self(g.stream()),
+            _ => false,
+        })
+    }
+
+    fn validator_call(mut expr: &syn::Expr) -> Option<(String, syn::ExprCall)> {
+        while let syn::Expr::MethodCall(call) = expr {
+            expr = &call.receiver;
+        }
+
+        let syn::Expr::Call(call) = expr else { return None };
+        let syn::Expr::Path(path) = &*call.func else { return None };
+        let name = path.path.segments.last()?.ident.to_string();
+        Some((name, call.clone()))
+    }
+
+    Ok(FieldAttr::from_attrs(FieldAttr::NAME, &field.attrs)?
+        .into_iter()
+        .chain(FieldAttr::from_attrs(FieldAttr::NAME, field.parent.attrs())?)
+        .filter_map(|a| a.validate)
+        .filter(|expr| !matches!(**expr, syn::Expr::Await(_)))
+        .filter(|expr| !refers_to_self((**expr).to_token_stream()))
+        .filter_map(|expr| validator_call(&expr))
+        .collect())
+}
+
 type Dup = (usize, Span, Span);
 
 pub fn first_duplicate<K: Spanned, V: PartialEq + Spanned>(
@@ -5,7 +5,8 @@ use devise::*;
 
 use crate::exports::*;
 use crate::derive::form_field::FieldName::*;
-use crate::derive::form_field::{FieldExt, default, first_duplicate, validators};
+use crate::derive::form_field::{FieldExt, default, first_duplicate, step};
+use crate::derive::form_field::{validators, async_validators, validator_calls};
 use crate::syn_ext::{GenericsExt as _, TypeExt as _};
 
 type WherePredicates = syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>;
@@ -57,6 +58,89 @@ fn fields_map<F>(fields: Fields<'_>, map_f: F) -> Result<TokenStream>
     })
 }
 
+// Emits an implementation of `Steps` if any field is assigned to a step.
+fn steps_impl(fields: Fields<'_>) -> Result<TokenStream> {
+    let (mut matchers, mut last) = (vec![], 0);
+    for field in fields.iter() {
+        let Some(step) = step(field)? else { continue };
+        last = last.max(step);
+        matchers.extend(field.field_names()?.into_iter().map(|f| match f {
+            Cased(f) => quote!(#f => #_Some(#step)),
+            Uncased(f) => quote!(__n if __n.as_uncased() == #f => #_Some(#step)),
+        }));
+    }
+
+    if matchers.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    let input = fields.parent.input();
+    let (ident, name) = (input.ident(), input.ident().to_string());
+    let (impl_gen, ty_gen, where_clause) = input.generics().split_for_impl();
+    Ok(quote! {
+        impl #impl_gen #_form::Steps for #ident #ty_gen #where_clause {
+            const NAME: &'static str = #name;
+            const LAST: usize = #last;
+
+            fn step(__name: &str) -> #_Option<usize> {
+                match __name {
+                    #(#matchers,)*
+                    _ => #_None,
+                }
+            }
+        }
+    })
+}
+
+// Emits an implementation of `FormConstraints` exporting the constraints of
+// each named field: whether it's required and its `len`, `range`, and `ext`
+// validators. Generic fields are only exported as optional.
+fn constraints_impl(fields: Fields<'_>) -> Result<TokenStream> {
+    let generic_idents = fields.parent.input().generics().type_idents();
+    let mut exports = vec![];
+    for field in fields.iter() {
+        let Some(name) = field.first_field_name()? else { continue };
+        let (ty, span) = (field.stripped_ty(), field.span());
+        let concrete = field.ty.is_concrete(&generic_idents);
+        let required = match concrete && default(field)?.is_none() {
+            true => quote_spanned!(span => #_form::FieldConstraints::is_required::<#ty>()),
+            false => quote!(false),
+        };
+
+        let setters = validator_calls(field)?.into_iter().filter_map(|(f, call)| {
+            let args = &call.args;
+            match f.as_str() {
+                "len" if concrete => Some(quote_spanned!(call.span() =>
+                    __fc.set_length::<#ty, _, _>(#args)
+                )),
+                "range" => Some(quote_spanned!(call.span() => __fc.set_range(#args))),
+                "ext" => Some(quote_spanned!(call.span() => __fc.set_accept(#args))),
+                _ => None,
+            }
+        });
+
+        exports.push(quote! {
+            let __fc = __c.field(#name);
+            __fc.required = #required;
+            #(#setters;)*
+        });
+    }
+
+    let input = fields.parent.input();
+    let ident = input.ident();
+    let (impl_gen, ty_gen, where_clause) = input.generics().split_for_impl();
+    Ok(quote! {
+        #[allow(unused_mut, clippy::all)]
+        impl #impl_gen #_form::FormConstraints for #ident #ty_gen #where_clause {
+            fn constraints() -> #_form::Constraints {
+                let mut __c = #_form::Constraints::new();
+                #({ #exports })*
+                __c
+            }
+        }
+    })
+}
+
 fn generic_bounds_tokens(input: Input<'_>) -> Result<TokenStream> {
     MapperBuild::new()
         .try_enum_map(|m, e| mapper::enum_null(m, e))
@@ -157,6 +241,14 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
             #[allow(unused_imports)]
             use #_http::uncased::AsUncased;
         })
+        .outer_mapper(MapperBuild::new()
+            .try_enum_map(|m, e| mapper::enum_null(m, e))
+            .try_fields_map(|_, fields| steps_impl(fields))
+        )
+        .outer_mapper(MapperBuild::new()
+            .try_enum_map(|m, e| mapper::enum_null(m, e))
+            .try_fields_map(|_, fields| constraints_impl(fields))
+        )
         .outer_mapper(quote!(#[allow(clippy::all, clippy::pedantic, clippy::nursery)]))
         .outer_mapper(quote!(#[allow(renamed_and_removed_lints)]))
         .outer_mapper(quote!(#[allow(private_in_public)]))
@@ -223,6 +315,60 @@ pub fn derive_from_form(input: proc_macro::TokenStream) -> TokenStream {
                     __c.__errors.push(__e);
                 }
             }))
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                async fn validate(
+                    __v: Self,
+                    __req: &'r #_request::Request<'_>
+                ) -> #_Result<Self, #_form::Errors<'r>> {
+                    #[allow(unused_imports)]
+                    use #_form::validate::*;
+
+                    #output
+                }
+            })
+            .try_fields_map(|_, fields| {
+                let (_ok, _some, _err, _none) = (_Ok, _Some, _Err, _None);
+                let ident = fields.iter().map(|f| f.context_ident()).collect::<Vec<_>>();
+                let member = fields.iter().map(|f| f.member());
+                let validate = fields.iter().map(|f| {
+                    let (ident, ty) = (f.context_ident(), f.stripped_ty());
+                    let parent = f.first_field_name()?
+                        .map(|name| quote_spanned!(ty.span() => .with_parent(#name)));
+
+                    Ok(quote_spanned! { ty.span() =>
+                        match <#ty as #_form::FromForm<'r>>::validate(#ident, __req).await {
+                            #_ok(#ident) => #_some(#ident),
+                            #_err(__e) => { __errors.extend(__e #parent); #_none }
+                        }
+                    })
+                }).collect::<Result<Vec<_>>>()?;
+
+                let validator = fields.iter().flat_map(|f| async_validators(f).unwrap());
+                let builder = fields.builder(|f| {
+                    let ident = f.context_ident();
+                    quote_spanned!(ident.span() => #ident.unwrap())
+                });
+
+                Ok(quote_spanned!(fields.span() =>
+                    let mut __errors = #_form::Errors::new();
+                    let Self { #(#member: #ident),* } = __v;
+                    #(let #ident = #validate;)*
+
+                    #(
+                        if let #_err(__e) = #validator {
+                            __errors.extend(__e);
+                        }
+                    )*
+
+                    if !__errors.is_empty() {
+                        return #_Err(__errors);
+                    }
+
+                    Ok(#builder)
+                ))
+            })
+        )
         .inner_mapper(MapperBuild::new()
             .with_output(|_, output| quote! {
                 fn finalize(mut __c: Self::Context) -> #_Result<Self, #_form::Errors<'r>> {
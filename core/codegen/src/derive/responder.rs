@@ -1,15 +1,32 @@
 use quote::ToTokens;
 use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
 use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 
 use crate::exports::*;
 use crate::syn_ext::{TypeExt as _, GenericsExt as _};
 use crate::http_codegen::{ContentType, Status};
 
-#[derive(Debug, Default, FromMeta)]
+/// A `status` or `content_type` override: a literal, checked at compile-time,
+/// or an arbitrary expression.
+enum Override<T> {
+    Literal(SpanWrapped<T>),
+    Expr(syn::Expr),
+}
+
+/// A parameter in a `#[response(...)]` attribute on a structure or variant.
+enum ItemParam {
+    Status(syn::Ident, syn::Expr),
+    ContentType(syn::Ident, syn::Expr),
+    Header(syn::LitStr, syn::Expr),
+}
+
+#[derive(Default)]
 struct ItemAttr {
-    content_type: Option<SpanWrapped<ContentType>>,
-    status: Option<SpanWrapped<Status>>,
+    content_type: Option<Override<ContentType>>,
+    status: Option<Override<Status>>,
+    headers: Vec<(syn::LitStr, syn::Expr)>,
 }
 
 #[derive(Default, FromMeta)]
@@ -17,6 +34,79 @@ struct FieldAttr {
     ignore: bool,
 }
 
+impl Parse for ItemParam {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = input.parse::<syn::Ident>()?;
+        if key == "header" {
+            let content;
+            syn::parenthesized!(content in input);
+            let name = content.parse::<syn::LitStr>()?;
+            content.parse::<syn::Token![,]>()?;
+            let value = content.parse::<syn::Expr>()?;
+            content.parse::<Option<syn::Token![,]>>()?;
+            return Ok(ItemParam::Header(name, value));
+        }
+
+        input.parse::<syn::Token![=]>()?;
+        let value = input.parse::<syn::Expr>()?;
+        match key.to_string().as_str() {
+            "status" => Ok(ItemParam::Status(key, value)),
+            "content_type" => Ok(ItemParam::ContentType(key, value)),
+            _ => Err(syn::Error::new(key.span(),
+                "unknown parameter: expected `status`, `content_type`, or `header`")),
+        }
+    }
+}
+
+impl<T: FromMeta> Override<T> {
+    fn new(expr: syn::Expr) -> Result<Self> {
+        match expr {
+            syn::Expr::Lit(lit) => {
+                let meta = MetaItem::Tokens(lit.into_token_stream());
+                Ok(Override::Literal(SpanWrapped::from_meta(&meta)?))
+            }
+            expr => Ok(Override::Expr(expr)),
+        }
+    }
+}
+
+impl ItemAttr {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut item = ItemAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+            let parser = Punctuated::<ItemParam, syn::Token![,]>::parse_terminated;
+            let params = attr.parse_args_with(parser)?;
+            for param in params {
+                match param {
+                    ItemParam::Status(key, value) => {
+                        if item.status.is_some() {
+                            return Err(key.span().error("duplicate `status` parameter"));
+                        }
+
+                        item.status = Some(Override::new(value)?);
+                    }
+                    ItemParam::ContentType(key, value) => {
+                        if item.content_type.is_some() {
+                            return Err(key.span().error("duplicate `content_type` parameter"));
+                        }
+
+                        item.content_type = Some(Override::new(value)?);
+                    }
+                    ItemParam::Header(name, value) => {
+                        if !crate::http::Header::is_valid_name(&name.value()) {
+                            return Err(name.span().error("invalid header name"));
+                        }
+
+                        item.headers.push((name, value));
+                    }
+                }
+            }
+        }
+
+        Ok(item)
+    }
+}
+
 pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
     let impl_tokens = quote!(impl<'r, 'o: 'r> #_response::Responder<'r, 'o>);
     DeriveGenerator::build_for(input, impl_tokens)
@@ -25,13 +115,20 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
         .type_bound_mapper(MapperBuild::new()
             .try_enum_map(|m, e| mapper::enum_null(m, e))
             .try_fields_map(|_, fields| {
-                let generic_idents = fields.parent.input().generics().type_idents();
+                let generics = fields.parent.input().generics();
+                let generic_idents = generics.type_idents();
                 let lifetime = |ty: &syn::Type| syn::Lifetime::new("'o", ty.span());
                 let mut types = fields.iter()
                     .map(|f| (f, &f.field.inner.ty))
                     .map(|(f, ty)| (f, ty.with_replaced_lifetimes(lifetime(ty))));
 
-                let mut bounds = vec![];
+                // The first lifetime is replaced with `'o`; the rest outlive it.
+                let mut bounds: Vec<_> = generics.lifetimes()
+                    .skip(1)
+                    .map(|l| &l.lifetime)
+                    .map(|l| quote_spanned!(l.span() => #l: 'o))
+                    .collect();
+
                 if let Some((_, ty)) = types.next() {
                     if !ty.is_concrete(&generic_idents) {
                         let span = ty.span();
@@ -54,10 +151,6 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
             })
         )
         .validator(ValidatorBuild::new()
-            .input_validate(|_, i| match i.generics().lifetimes().count() > 1 {
-                true => Err(i.generics().span().error("only one lifetime is supported")),
-                false => Ok(())
-            })
             .fields_validate(|_, fields| match fields.is_empty() {
                 true => Err(fields.span().error("need at least one field")),
                 false => Ok(())
@@ -74,8 +167,60 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
                     quote_spanned!(item.span() => __res.set_header(#item);)
                 }
 
-                let attr = ItemAttr::one_from_attrs("response", fields.parent.attrs())?
-                    .unwrap_or_default();
+                let attr = ItemAttr::from_attrs(fields.parent.attrs())?;
+
+                // Expressions are evaluated before any field is moved, with
+                // named fields in scope by reference.
+                let bindings = fields.iter()
+                    .filter_map(|f| f.ident.as_ref().map(|ident| (ident, f.accessor())))
+                    .map(|(ident, accessor)| quote! {
+                        #[allow(unused_variables)]
+                        let #ident = &#accessor;
+                    });
+
+                let bindings = quote!(#(#bindings)*);
+                let mut computed = vec![];
+                let mut evaluate = |ident: syn::Ident, ty: TokenStream, expr: TokenStream| {
+                    computed.push(quote_spanned! { expr.span() =>
+                        let #ident: #ty = { #bindings #expr };
+                    });
+
+                    ident
+                };
+
+                let content_type = attr.content_type.map(|content_type| match content_type {
+                    Override::Literal(content_type) => set_header_tokens(content_type),
+                    Override::Expr(expr) => {
+                        let ty = quote!(#_http::ContentType);
+                        let ident = syn::Ident::new("__content_type", expr.span());
+                        set_header_tokens(evaluate(ident, ty, expr.into_token_stream()))
+                    }
+                });
+
+                let status = attr.status.map(|status| match status {
+                    Override::Literal(status) => {
+                        quote_spanned!(status.span() => __res.set_status(#status);)
+                    }
+                    Override::Expr(expr) => {
+                        let ty = quote!(#_http::Status);
+                        let ident = syn::Ident::new("__status", expr.span());
+                        let ident = evaluate(ident, ty, expr.into_token_stream());
+                        quote!(__res.set_status(#ident);)
+                    }
+                });
+
+                let computed_headers = attr.headers.into_iter()
+                    .enumerate()
+                    .map(|(i, (name, value))| {
+                        let ty = quote!(#_http::Header<'o>);
+                        let ident = syn::Ident::new(&format!("__header_{i}"), value.span());
+                        let expr = quote_spanned! { value.span() =>
+                            #_http::Header::new(#name, #value)
+                        };
+
+                        set_header_tokens(evaluate(ident, ty, expr))
+                    })
+                    .collect::<Vec<_>>();
 
                 let responder = fields.iter().next().map(|f| {
                     let (accessor, ty) = (f.accessor(), f.ty.with_stripped_lifetimes());
@@ -96,14 +241,11 @@ pub fn derive_responder(input: proc_macro::TokenStream) -> TokenStream {
                     }
                 }
 
-                let content_type = attr.content_type.map(set_header_tokens);
-                let status = attr.status.map(|status| {
-                    quote_spanned!(status.span() => __res.set_status(#status);)
-                });
-
                 Ok(quote! {
+                    #(#computed)*
                     #responder
                     #(#headers)*
+                    #(#computed_headers)*
                     #content_type
                     #status
                     #_Ok(__res)
@@ -69,6 +69,8 @@ define_exported_paths! {
     _request => ::rocket::request,
     _response => ::rocket::response,
     _route => ::rocket::route,
+    _profile => ::rocket::profile,
+    _local => ::rocket::local,
     _error => ::rocket::error,
     _catcher => ::rocket::catcher,
     _sentinel => ::rocket::sentinel,
@@ -162,6 +162,13 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'guards' '(' TYPE (',' TYPE)* ')'
+        ///            | 'on_fail' '=' STATUS
+        ///            | 'deprecated' '(' deprecation (',' deprecation)* ')'
+        ///
+        /// deprecation := 'since' '=' '"' DATE '"'
+        ///              | 'sunset' '=' '"' DATE '"'
+        ///              | 'successor' '=' '"' URI '"'
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// TRAILING_PARAM := '<' IDENT '..>'
@@ -171,6 +178,10 @@ macro_rules! route_attribute {
         ///
         /// INTEGER := unsigned integer, as defined by Rust
         /// IDENT := valid identifier, as defined by Rust
+        /// TYPE := valid type, as defined by Rust
+        /// STATUS := unsigned integer in [400, 599]
+        /// DATE := date of the form YYYY-MM-DD
+        /// URI := valid URI reference
         /// ```
         ///
         /// The generic route attribute is defined as:
@@ -220,6 +231,42 @@ macro_rules! route_attribute {
         /// A route argument declared a `_` must _not_ appear in the function
         /// argument list and has no typing requirements.
         ///
+        /// Every type in `guards(..)` is required to implement [`FromRequest`].
+        /// The guards are run but their values are not passed to the function,
+        /// making requirements such as authorization visible in the attribute:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate rocket;
+        /// # use rocket::request::{self, FromRequest, Request};
+        /// # struct AdminUser;
+        /// # #[rocket::async_trait]
+        /// # impl<'r> FromRequest<'r> for AdminUser {
+        /// #     type Error = ();
+        /// #     async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        /// #         request::Outcome::Success(AdminUser)
+        /// #     }
+        /// # }
+        /// #[delete("/user/<id>", guards(AdminUser), on_fail = 403)]
+        /// fn delete_user(id: usize) { /* .. */ }
+        /// ```
+        ///
+        /// When `on_fail` is set, a declared guard that fails _or_ forwards
+        /// fails the request with the given status. `on_fail` only applies to
+        /// guards declared in `guards(..)` and requires them to be present.
+        ///
+        /// A route with a `deprecated(..)` argument is reported as deprecated
+        /// at launch, and its successful responses carry `Deprecation`,
+        /// `Sunset`, and `Link: <URI>; rel=successor-version` headers as set
+        /// by the [`Deprecated`] responder. `since` is required; `sunset`, if
+        /// present, must not precede it. Dates are midnight, UTC:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate rocket;
+        /// #[get("/v1/users", deprecated(since = "2024-06-01", sunset = "2025-06-01",
+        ///     successor = "/v2/users"))]
+        /// fn users() -> &'static str { "[]" }
+        /// ```
+        ///
         /// The return type of the decorated function must implement the
         /// [`Responder`] trait.
         ///
@@ -231,6 +278,7 @@ macro_rules! route_attribute {
         /// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
         /// [`Route`]: ../rocket/struct.Route.html
         /// [`Responder`]: ../rocket/response/trait.Responder.html
+        /// [`Deprecated`]: ../rocket/response/struct.Deprecated.html
         ///
         /// # Semantics
         ///
@@ -242,16 +290,22 @@ macro_rules! route_attribute {
         ///      the generated function according to the trait that their type
         ///      must implement. The order in which arguments are processed is:
         ///
-        ///         1. Request guards from left to right.
+        ///         1. Guards declared in `guards(..)`, from left to right.
+        ///
+        ///            If a declared guard fails, the request is handled as with
+        ///            any other request guard, unless `on_fail` is set, in
+        ///            which case the request fails with that status.
+        ///
+        ///         2. Request guards from left to right.
         ///
         ///            If a request guard fails, the request is forwarded if the
         ///            [`Outcome`] is `Forward` or failed if the [`Outcome`] is
         ///            `Error`. See [`FromRequest` Outcomes] for further detail.
         ///
-        ///         2. Path and query guards in an unspecified order. If a path
+        ///         3. Path and query guards in an unspecified order. If a path
         ///            or query guard fails, the request is forwarded.
         ///
-        ///         3. Data guard, if any.
+        ///         4. Data guard, if any.
         ///
         ///            If a data guard fails, the request is forwarded if the
         ///            [`Outcome`] is `Forward` or failed if the [`Outcome`] is
@@ -390,6 +444,61 @@ pub fn suppress(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::suppress::suppress_attribute(args, input))
 }
 
+/// Attribute to declare the cacheability of a route's responses.
+///
+/// The attribute wraps the value returned by the decorated function in a
+/// [`Cached`] responder which sets `Cache-Control` and `Vary` headers on
+/// successful responses. It can be applied before or after the route
+/// attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/greeting")]
+/// #[cache(max_age = 300, public, vary = "Accept-Language")]
+/// fn greeting() -> &'static str {
+///     "Hello!"
+/// }
+///
+/// #[cache(no_store)]
+/// #[get("/balance")]
+/// async fn balance() -> String {
+///     "$42".into()
+/// }
+/// ```
+///
+/// A request for `/greeting` receives a response with the headers
+/// `Cache-Control: max-age=300, public` and `Vary: Accept-Language`.
+///
+/// # Grammar
+///
+/// The grammar for the `#[cache]` attribute is:
+///
+/// ```text
+/// cache := directive (',' directive)*
+///
+/// directive := 'max_age' '=' INTEGER
+///            | 's_maxage' '=' INTEGER
+///            | 'public' | 'private' | 'no_cache' | 'no_store'
+///            | 'must_revalidate' | 'immutable'
+///            | 'vary' '=' '"' HEADER_NAME (',' HEADER_NAME)* '"'
+///
+/// INTEGER := unsigned integer, a number of seconds
+/// HEADER_NAME := valid HTTP header name
+/// ```
+///
+/// Each directive corresponds to the `Cache-Control` directive of the same name
+/// with underscores replaced by dashes. `public` and `private` are mutually
+/// exclusive, as are `max_age` and `no_store`. A `Cache-Control` header set by
+/// the returned responder itself takes precedence.
+///
+/// To also cache responses on the server, see the `rocket_cache` crate.
+///
+/// [`Cached`]: ../rocket/response/struct.Cached.html
+#[proc_macro_attribute]
+pub fn cache(args: TokenStream, input: TokenStream) -> TokenStream {
+    emit!(attribute::cache::cache_attribute(args, input))
+}
+
 /// Retrofits supports for `async fn` in unit tests.
 ///
 /// Simply decorate a test `async fn` with `#[async_test]` instead of `#[test]`:
@@ -508,6 +617,12 @@ pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
 ///     rocket::build()
 /// }
 /// ```
+///
+/// When applied as `#[launch(registry)]` to a function at the crate root, the
+/// types of the values managed via `.manage()` in the attributed function are
+/// registered for the [`state!`](macro@state) macro, which checks at
+/// compile-time that state used by a route is managed. Without `registry`,
+/// `.manage()` calls are left as they are.
 #[proc_macro_attribute]
 pub fn launch(args: TokenStream, input: TokenStream) -> TokenStream {
     emit!(attribute::entry::launch_attribute(args, input))
@@ -608,7 +723,10 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///
 /// Each field type is required to implement [`FromForm`].
 ///
-/// The derive generates an implementation of the [`FromForm`] trait.
+/// The derive generates an implementation of the [`FromForm`] trait as well as
+/// of [`form::FormConstraints`], which exports the constraints declared by each
+/// named field's type and its `len`, `range`, and `ext` validators so that they
+/// can be mirrored by client-side validation.
 ///
 /// **Named Fields**
 ///
@@ -631,7 +749,7 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 /// attribute, `form`, with the following syntax:
 ///
 /// ```text
-/// field := name? default? validate*
+/// field := name? default? validate* step?
 ///
 /// name := 'name' '=' name_val ','?
 /// name_val :=  '"' FIELD_NAME '"'
@@ -642,6 +760,8 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///
 /// validate := 'validate' '=' EXPR ','?
 ///
+/// step := 'step' '=' INTEGER ','?
+///
 /// FIELD_NAME := valid field name, according to the HTML5 spec
 /// EXPR := valid expression, as defined by Rust
 /// ```
@@ -692,6 +812,39 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///     `Err`, the errors are added to the thus-far collected errors. If more
 ///     than one `validate` attribute is applied, _all_ validations are run.
 ///
+///     If `expr` is of the form `f(..).await`, the validator is asynchronous:
+///     the request is passed as the last argument to `f`, giving it access to
+///     managed state and other request guards, and the validation is run by
+///     request-aware guards like `Form` after all synchronous validations
+///     succeed. Errors are attributed to the field as with synchronous
+///     validators.
+///
+///     ```rust
+///     # #[macro_use] extern crate rocket;
+///     use std::collections::HashSet;
+///
+///     use rocket::form;
+///     use rocket::request::Request;
+///
+///     struct Users(HashSet<String>);
+///
+///     async fn unique<'v>(name: &str, req: &Request<'_>) -> form::Result<'v, ()> {
+///         let users = req.rocket().state::<Users>().expect("managed users");
+///         if users.0.contains(name) {
+///             Err(form::Error::validation("username is taken"))?;
+///         }
+///
+///         Ok(())
+///     }
+///
+///     #[derive(FromForm)]
+///     struct Signup<'r> {
+///         #[field(validate = len(3..))]
+///         #[field(validate = unique().await)]
+///         username: &'r str,
+///     }
+///     ```
+///
 ///   * **`default = expr`**
 ///
 ///     If `expr` is not literally `None`, the parameter sets the default value
@@ -722,8 +875,30 @@ pub fn derive_from_form_field(input: TokenStream) -> TokenStream {
 ///     }
 ///     ```
 ///
+///   * **`step = INTEGER`**
+///
+///     Assigns the field to step `INTEGER`, starting at `1`, of a multi-step
+///     form. If any field is assigned a step, the derive also implements
+///     [`form::Steps`], allowing the structure to be collected over several
+///     submissions with [`FormSession`]. Fields without a step are part of the
+///     last step. At most one `step` may be applied to a field.
+///
+///     ```rust
+///     # #[macro_use] extern crate rocket;
+///     #[derive(FromForm)]
+///     struct Checkout {
+///         #[field(step = 1, validate = contains('@'))]
+///         email: String,
+///         #[field(step = 2)]
+///         address: String,
+///     }
+///     ```
+///
 /// [`FromForm`]: ../rocket/form/trait.FromForm.html
 /// [`form::Errors`]: ../rocket/form/struct.Errors.html
+/// [`form::Steps`]: ../rocket/form/trait.Steps.html
+/// [`form::FormConstraints`]: ../rocket/form/trait.FormConstraints.html
+/// [`FormSession`]: ../rocket/form/struct.FormSession.html
 ///
 /// # Generics
 ///
@@ -893,14 +1068,18 @@ pub fn derive_from_param(input: TokenStream) -> TokenStream {
 /// these positions has the following grammar:
 ///
 /// ```text
-/// response := parameter (',' parameter)?
+/// response := parameter (',' parameter)*
 ///
-/// parameter := 'status' '=' STATUS
-///            | 'content_type' '=' CONTENT_TYPE
+/// parameter := 'status' '=' (STATUS | EXPR)
+///            | 'content_type' '=' (CONTENT_TYPE | EXPR)
+///            | 'header' '(' HEADER_NAME ',' EXPR ')'
 ///
 /// STATUS := unsigned integer >= 100 and < 600
 /// CONTENT_TYPE := string literal, as defined by Rust, identifying a valid
 ///                 Content-Type, as defined by Rocket
+/// HEADER_NAME := string literal, as defined by Rust, identifying a valid
+///                header name
+/// EXPR := any non-literal expression, as defined by Rust
 /// ```
 ///
 /// It can be used as follows:
@@ -949,16 +1128,59 @@ pub fn derive_from_param(input: TokenStream) -> TokenStream {
 /// shorthands. The [`Response`] produced from the generated implementation will
 /// have its content-type overridden to this value.
 ///
+/// Literal values are checked at compile-time. Either parameter may instead be
+/// an arbitrary expression evaluating to a [`Status`] or [`ContentType`],
+/// respectively. The expression is evaluated before the response is built, with
+/// every named field of the structure or variant in scope as a reference:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{Status, ContentType};
+///
+/// #[derive(Responder)]
+/// enum ApiError {
+///     #[response(status = Status::new(*code), content_type = ContentType::JSON)]
+///     Upstream { body: String, code: u16 },
+///     #[response(status = if *retry { Status::ServiceUnavailable } else { Status::BadGateway })]
+///     Unavailable { body: &'static str, retry: bool },
+/// }
+/// ```
+///
+/// Finally, the `header` parameter, which may be repeated, adds a header with
+/// the given name and a computed value: any expression convertible into a
+/// `Cow<str>`. Like `status` and `content_type` expressions, it may refer to
+/// named fields. Because fields are moved into the response after the value is
+/// computed, values borrowing from a field must be made owned, for instance with
+/// `to_string()`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(Responder)]
+/// #[response(status = 429, header("Retry-After", retry_after.to_string()))]
+/// #[response(header("X-RateLimit-Policy", "100;w=60"))]
+/// struct RateLimited {
+///     body: &'static str,
+///     #[response(ignore)]
+///     retry_after: u64,
+/// }
+/// ```
+///
+/// Headers are applied in order: those from header fields, then those from
+/// `header` parameters, and finally `content_type` and `status`. Later values
+/// replace earlier ones with the same name.
+///
 /// [`Responder`]: ../rocket/response/trait.Responder.html
 /// [`Response`]: ../rocket/struct.Response.html
 /// [`Response::set_header()`]: ../rocket/response/struct.Response.html#method.set_header
 /// [`ContentType::parse_flexible()`]: ../rocket/http/struct.ContentType.html#method.parse_flexible
+/// [`Status`]: ../rocket/http/struct.Status.html
+/// [`ContentType`]: ../rocket/http/struct.ContentType.html
 ///
 /// # Generics
 ///
-/// The derive accepts any number of type generics and at most one lifetime
-/// generic. If a type generic is present and the generic is used in the first
-/// field of a structure, the generated implementation will require a bound of
+/// The derive accepts any number of type and lifetime generics. If a type
+/// generic is present and the generic is used in the first field of a
+/// structure, the generated implementation will require a bound of
 /// `Responder<'r, 'o>` for the field type containing the generic. In all other
 /// fields, unless ignores, a bound of `Into<Header<'o>` is added.
 ///
@@ -992,8 +1214,8 @@ pub fn derive_from_param(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
-/// If a lifetime generic is present, it will be replaced with `'o` in the
-/// generated implementation `impl Responder<'r, 'o>`:
+/// If a lifetime generic is present, the first will be replaced with `'o` in
+/// the generated implementation `impl Responder<'r, 'o>`:
 ///
 /// ```rust
 /// # #[macro_use] extern crate rocket;
@@ -1011,6 +1233,21 @@ pub fn derive_from_param(input: TokenStream) -> TokenStream {
 /// #[derive(Responder)]
 /// struct SomeResult<'o, T>(Result<T, &'o str>);
 /// ```
+///
+/// Any other lifetime generics are required to outlive `'o`, allowing variants
+/// to wrap responders with different lifetimes:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// // Generates `impl<'r, 'o, 'b, T> Responder<'r, 'o> for Either<'o, 'b, T>`
+/// // where `'b: 'o` and `T: Responder<'r, 'o>`.
+/// #[derive(Responder)]
+/// enum Either<'a, 'b, T> {
+///     Text(&'a str),
+///     Bytes(&'b [u8]),
+///     File(T),
+/// }
+/// ```
 #[proc_macro_derive(Responder, attributes(response))]
 pub fn derive_responder(input: TokenStream) -> TokenStream {
     emit!(derive::responder::derive_responder(input))
@@ -1250,6 +1487,65 @@ pub fn catchers(input: TokenStream) -> TokenStream {
     emit!(bang::catchers_macro(input))
 }
 
+/// Compile-time checked managed state request guard.
+///
+/// `state!(T)` expands to the type `&State<T>` with the additional requirement
+/// that `T` is managed in the crate's [`#[launch(registry)]`](macro@launch)
+/// function, so that a route using state that is never managed fails to
+/// compile instead of failing at launch or at runtime:
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate rocket;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// struct HitCount(AtomicUsize);
+///
+/// struct Unmanaged;
+///
+/// #[get("/")]
+/// fn index(hits: state!(HitCount)) -> String {
+///     hits.0.fetch_add(1, Ordering::Relaxed).to_string()
+/// }
+///
+/// // error[E0277]: the trait bound `Unmanaged: Managed<__rocket_managed>` is
+/// // not satisfied
+/// #[get("/bad")]
+/// fn bad(unmanaged: state!(Unmanaged)) { }
+///
+/// #[launch(registry)]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .manage(HitCount(AtomicUsize::new(0)))
+/// }
+/// ```
+///
+/// `#[launch(registry)]` registers the type of every `.manage()` call in the
+/// launch function whose type it can determine syntactically: calls with an
+/// explicit type, as in `.manage::<Config>(config)`, and calls passing a
+/// struct expression, as in `.manage(Config { .. })` or
+/// `.manage(HitCount(..))`. For any other value, such as
+/// `.manage(Mutex::new(..))`, and for values of generic types, as in
+/// `.manage::<Wrapper<u8>>(Wrapper(1))`, use an explicit type. Types declared
+/// inside of the launch function are not registered.
+///
+/// `state!` can only be used in a crate whose `#[launch(registry)]` function
+/// is at the crate root, as is typical in a single-binary application; using
+/// `registry` anywhere else is a compile-time error. Elsewhere, or for state
+/// managed outside of the launch function, use `&State<T>` directly.
+///
+/// The grammar for `state!` is defined as:
+///
+/// ```text
+/// state := TYPE
+///
+/// TYPE := a type, as defined by Rust
+/// ```
+#[proc_macro]
+pub fn state(input: TokenStream) -> TokenStream {
+    emit!(bang::state_macro(input))
+}
+
 /// Type-safe, encoding-safe route and non-route URI generation.
 ///
 /// The `uri!` macro creates type-safe, URL-safe URIs given a route and concrete
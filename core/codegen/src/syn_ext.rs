@@ -29,6 +29,7 @@ pub trait FnArgExt {
 pub trait TypeExt {
     fn unfold_with_ty_macros(&self, names: &[&str], mapper: MacTyMapFn) -> Vec<Child<'_>>;
     fn is_concrete(&self, generic_ident: &[&Ident]) -> bool;
+    fn with_static_lifetimes(&self) -> syn::Type;
 }
 
 pub trait GenericsExt {
@@ -213,6 +214,31 @@ impl TypeExt for syn::Type {
         visitor.visit_type(self);
         visitor.0
     }
+
+    /// Replaces every lifetime, including elided reference lifetimes, with
+    /// `'static`. Lifetimes hidden in paths, as in `Data` for `Data<'_>`, are
+    /// left elided.
+    fn with_static_lifetimes(&self) -> syn::Type {
+        use syn::visit_mut::{self, VisitMut};
+
+        struct StaticVisitor;
+
+        impl VisitMut for StaticVisitor {
+            fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+                *lifetime = syn::Lifetime::new("'static", lifetime.span());
+            }
+
+            fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+                let span = reference.and_token.spans[0];
+                reference.lifetime.get_or_insert_with(|| syn::Lifetime::new("'static", span));
+                visit_mut::visit_type_reference_mut(self, reference);
+            }
+        }
+
+        let mut ty = self.clone();
+        StaticVisitor.visit_type_mut(&mut ty);
+        ty
+    }
 }
 
 impl GenericsExt for syn::Generics {
@@ -146,3 +146,69 @@ async fn generic_responder() {
     assert_eq!(r.content_type().unwrap(), ContentType::Text);
     assert_eq!(r.body_mut().to_string().await.unwrap(), "beep beep");
 }
+
+#[derive(Responder)]
+enum Nested<'a, 'b, T> {
+    Text(&'a str),
+    Bytes(&'b [u8], ContentType),
+    Json(Json<T>),
+    #[response(status = Status::new(*code), content_type = ContentType::HTML)]
+    Computed { body: MyResult<'b, T, &'a str, ContentType, Accept>, code: u16 },
+}
+
+#[rocket::async_test]
+async fn multi_lifetime_responder() {
+    let client = Client::debug_with(vec![]).await.expect("valid rocket");
+    let local_req = client.get("/");
+    let req = local_req.inner();
+
+    let (text, bytes) = (String::from("text"), vec![1, 2, 3]);
+    let v: Nested<'_, '_, ()> = Nested::Text(&text);
+    let mut r = v.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::Ok);
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "text");
+
+    let v: Nested<'_, '_, ()> = Nested::Bytes(&bytes, ContentType::PNG);
+    let mut r = v.respond_to(req).unwrap();
+    assert_eq!(r.content_type().unwrap(), ContentType::PNG);
+    assert_eq!(r.body_mut().to_bytes().await.unwrap(), bytes);
+
+    let body = MyResult::Err(&*text, ContentType::JPEG, Accept::Text);
+    let v = Nested::<'_, '_, ()>::Computed { body, code: 418 };
+    let mut r = v.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::ImATeapot);
+    assert_eq!(r.content_type().unwrap(), ContentType::HTML);
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "text");
+}
+
+#[derive(Responder)]
+#[response(status = if *retry { Status::ServiceUnavailable } else { Status::BadGateway })]
+#[response(header("Retry-After", retry_after.to_string()), header("X-Upstream", "down"))]
+struct Unavailable {
+    body: &'static str,
+    kind: ContentType,
+    #[response(ignore)]
+    retry: bool,
+    #[response(ignore)]
+    retry_after: u64,
+}
+
+#[rocket::async_test]
+async fn computed_status_and_headers() {
+    let client = Client::debug_with(vec![]).await.expect("valid rocket");
+    let local_req = client.get("/");
+    let req = local_req.inner();
+
+    let v = Unavailable { body: "later", kind: ContentType::Text, retry: true, retry_after: 30 };
+    let mut r = v.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::ServiceUnavailable);
+    assert_eq!(r.content_type().unwrap(), ContentType::Text);
+    assert_eq!(r.headers().get_one("Retry-After"), Some("30"));
+    assert_eq!(r.headers().get_one("X-Upstream"), Some("down"));
+    assert_eq!(r.body_mut().to_string().await.unwrap(), "later");
+
+    let v = Unavailable { body: "never", kind: ContentType::Text, retry: false, retry_after: 0 };
+    let r = v.respond_to(req).unwrap();
+    assert_eq!(r.status(), Status::BadGateway);
+    assert_eq!(r.headers().get_one("Retry-After"), Some("0"));
+}
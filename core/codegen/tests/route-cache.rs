@@ -0,0 +1,74 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::{Cached, WithHeader};
+
+#[get("/before")]
+#[cache(max_age = 300, public, vary = "Accept-Language, Accept")]
+fn before() -> &'static str {
+    "before"
+}
+
+#[cache(s_maxage = 60, must_revalidate)]
+#[get("/after/<name>")]
+async fn after(name: &str) -> String {
+    format!("after {name}")
+}
+
+#[get("/early/<fail>")]
+#[cache(no_store)]
+fn early(fail: bool) -> Result<&'static str, Status> {
+    if fail {
+        return Err(Status::ImATeapot);
+    }
+
+    Ok("early")
+}
+
+#[get("/unit/<_x>")]
+#[cache(private, no_cache)]
+fn unit(_x: usize, _config: &rocket::Config) { }
+
+#[get("/explicit")]
+#[cache(max_age = 10, vary = "Accept")]
+fn explicit() -> WithHeader<Header<'static>, WithHeader<Header<'static>, &'static str>> {
+    let vary = WithHeader(Header::new("Vary", "Cookie"), "explicit");
+    WithHeader(Header::new("Cache-Control", "no-store"), vary)
+}
+
+#[test]
+fn test_cache_attribute() {
+    let routes = routes![before, after, early, unit, explicit];
+    let client = Client::debug_with(routes).unwrap();
+
+    let response = client.get("/before").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=300, public"));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Language, Accept"));
+    assert_eq!(response.into_string().unwrap(), "before");
+
+    let response = client.get("/after/bob").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("s-maxage=60, must-revalidate"));
+    assert!(response.headers().get_one("Vary").is_none());
+    assert_eq!(response.into_string().unwrap(), "after bob");
+
+    let response = client.get("/early/false").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("no-store"));
+
+    let response = client.get("/early/true").dispatch();
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert!(response.headers().get_one("Cache-Control").is_none());
+
+    let response = client.get("/unit/7").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("private, no-cache"));
+
+    let response = client.get("/explicit").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("no-store"));
+    assert_eq!(response.headers().get_one("Vary"), Some("Cookie, Accept"));
+}
+
+#[test]
+fn test_cached_uri() {
+    assert_eq!(uri!(after("bob")), "/after/bob");
+    let _: Cached<()> = unit(1, &rocket::Config::default());
+}
@@ -0,0 +1,89 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Link, Status};
+use rocket::local::blocking::Client;
+use rocket::response::{Deprecated, WithHeader};
+use rocket::time::macros::datetime;
+
+#[get("/v1/users", deprecated(since = "2023-07-01", sunset = "2024-02-29",
+    successor = "/v2/users"))]
+fn users() -> &'static str {
+    "users"
+}
+
+#[route("/v1/item/<id>", method = POST, deprecated(since = "1970-01-01"))]
+fn item(id: usize) -> String {
+    id.to_string()
+}
+
+#[get("/v1/paged", deprecated(since = "2023-07-01", successor = "/v2/paged"))]
+fn paged() -> WithHeader<Link, &'static str> {
+    WithHeader(Link::new().next("/v1/paged?page=2"), "paged")
+}
+
+#[get("/v1/dynamic", deprecated(since = "2023-07-01"))]
+fn dynamic() -> Deprecated<&'static str> {
+    Deprecated::new(datetime!(2022-01-01 0:00 UTC), "dynamic")
+}
+
+#[get("/v1/fail", deprecated(since = "2023-07-01"))]
+fn fail() -> Status {
+    Status::ImATeapot
+}
+
+#[get("/v2/users")]
+fn current() -> &'static str {
+    "current"
+}
+
+#[test]
+fn test_deprecated_routes() {
+    let routes = routes![users, item, paged, dynamic, fail, current];
+    let client = Client::debug_with(routes).unwrap();
+
+    let response = client.get("/v1/users").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("@1688169600"));
+    assert_eq!(response.headers().get_one("Sunset"), Some("Thu, 29 Feb 2024 00:00:00 GMT"));
+    assert_eq!(response.headers().get_one("Link"), Some("</v2/users>; rel=successor-version"));
+    assert_eq!(response.into_string().unwrap(), "users");
+
+    let response = client.post("/v1/item/7").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("@0"));
+    assert!(response.headers().get_one("Sunset").is_none());
+    assert!(response.headers().get_one("Link").is_none());
+
+    // Successor links are adjoined to existing links.
+    let response = client.get("/v1/paged").dispatch();
+    let links: Vec<_> = response.headers().get("Link").collect();
+    assert_eq!(links, ["</v1/paged?page=2>; rel=next", "</v2/paged>; rel=successor-version"]);
+
+    // A `Deprecated` responder takes precedence over the route's deprecation.
+    let response = client.get("/v1/dynamic").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("@1640995200"));
+
+    let response = client.get("/v1/fail").dispatch();
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert!(response.headers().get_one("Deprecation").is_none());
+
+    let response = client.get("/v2/users").dispatch();
+    assert!(response.headers().get_one("Deprecation").is_none());
+}
+
+#[test]
+fn test_deprecated_metadata() {
+    let rocket = rocket::build().mount("/", routes![users, item, current]);
+    let route = |name| rocket.routes().find(|r| r.name.as_deref() == Some(name)).unwrap();
+
+    let deprecated = route("users").deprecated.as_ref().unwrap();
+    assert_eq!(deprecated.since, datetime!(2023-07-01 0:00 UTC));
+    assert_eq!(deprecated.sunset, Some(datetime!(2024-02-29 0:00 UTC)));
+    assert_eq!(deprecated.successor.as_deref(), Some("/v2/users"));
+    assert!(deprecated.is_sunset());
+
+    let deprecated = route("item").deprecated.as_ref().unwrap();
+    assert_eq!(deprecated.since, datetime!(1970-01-01 0:00 UTC));
+    assert!(deprecated.sunset.is_none() && deprecated.successor.is_none());
+    assert!(!deprecated.is_sunset());
+
+    assert!(route("current").deprecated.is_none());
+}
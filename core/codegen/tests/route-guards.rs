@@ -0,0 +1,103 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request};
+
+struct AdminUser;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminUser {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("X-Role") {
+            Some("admin") => request::Outcome::Success(AdminUser),
+            Some(_) => request::Outcome::Error((Status::Unauthorized, ())),
+            None => request::Outcome::Forward(Status::Unauthorized),
+        }
+    }
+}
+
+struct ApiKey<'r>(&'r str);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ApiKey<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("X-Api-Key") {
+            Some(key) if key == "secret" => request::Outcome::Success(ApiKey(key)),
+            _ => request::Outcome::Error((Status::BadRequest, ())),
+        }
+    }
+}
+
+#[get("/plain", guards(AdminUser))]
+fn plain() -> &'static str {
+    "plain"
+}
+
+#[get("/strict/<id>", guards(AdminUser, ApiKey<'_>), on_fail = 403)]
+fn strict(id: usize) -> String {
+    format!("strict {id}")
+}
+
+#[route("/generic", method = POST, guards(ApiKey<'_>), rank = 2)]
+fn generic(key: ApiKey<'_>) -> String {
+    format!("key {}", key.0)
+}
+
+#[get("/plain", rank = 2)]
+fn plain_fallback() -> &'static str {
+    "fallback"
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![plain, strict, generic, plain_fallback]).unwrap()
+}
+
+#[test]
+fn test_declared_guards() {
+    let client = client();
+    let admin = Header::new("X-Role", "admin");
+    let user = Header::new("X-Role", "user");
+    let key = Header::new("X-Api-Key", "secret");
+
+    let response = client.get("/plain").header(admin.clone()).dispatch();
+    assert_eq!(response.into_string().unwrap(), "plain");
+
+    // A forwarding declared guard forwards without `on_fail`...
+    let response = client.get("/plain").dispatch();
+    assert_eq!(response.into_string().unwrap(), "fallback");
+
+    // ...and errors keep the guard's status.
+    let response = client.get("/plain").header(user.clone()).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.post("/generic").header(key.clone()).dispatch();
+    assert_eq!(response.into_string().unwrap(), "key secret");
+
+    let response = client.post("/generic").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn test_declared_guards_on_fail() {
+    let client = client();
+    let admin = Header::new("X-Role", "admin");
+    let key = Header::new("X-Api-Key", "secret");
+
+    let response = client.get("/strict/5").header(admin.clone()).header(key).dispatch();
+    assert_eq!(response.into_string().unwrap(), "strict 5");
+
+    let response = client.get("/strict/5").header(admin).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client.get("/strict/5").header(Header::new("X-Role", "user")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // Declared guards run ahead of parameter guards.
+    let response = client.get("/strict/bad").dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
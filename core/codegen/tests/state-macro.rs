@@ -0,0 +1,44 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::local::blocking::Client;
+
+struct HitCount(AtomicUsize);
+
+struct AppConfig {
+    name: &'static str,
+}
+
+type Log = Mutex<Vec<String>>;
+
+#[get("/")]
+fn index(hits: state!(HitCount), config: state!(AppConfig), log: state!(Log)) -> String {
+    let hits = hits.0.fetch_add(1, Ordering::Relaxed) + 1;
+    log.lock().unwrap().push(format!("hit {hits}"));
+    format!("{}: {hits}", config.name)
+}
+
+#[get("/log")]
+fn log(log: state!(Log), answer: state!(usize)) -> String {
+    format!("{} {answer}", log.lock().unwrap().join(", "))
+}
+
+#[launch(registry)]
+fn rocket() -> _ {
+    rocket::build()
+        .mount("/", routes![index, log])
+        .manage(HitCount(AtomicUsize::new(0)))
+        .manage(AppConfig { name: "app" })
+        .manage::<Log>(Mutex::new(vec![]))
+        .manage::<usize>(42)
+}
+
+#[test]
+fn checked_state_is_retrieved() {
+    let client = Client::debug(rocket()).unwrap();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "app: 1");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "app: 2");
+    assert_eq!(client.get("/log").dispatch().into_string().unwrap(), "hit 1, hit 2 42");
+}
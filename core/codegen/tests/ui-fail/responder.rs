@@ -13,7 +13,7 @@ enum Bar { } // NO ERROR
 enum Foo { Bark, }
 
 #[derive(Responder)]
-struct Thing4<'a, 'b>(&'a str, &'b str);
+struct Thing4<'a, 'b>(&'a str, &'b str); // NO ERROR
 
 #[derive(Responder)]
 struct Thing5<T>(T); // NO ERROR
@@ -0,0 +1,35 @@
+// With `registry`, `#[launch]` registers the types it can name and skips
+// types declared inside of the launch function. Generic types are named
+// explicitly.
+
+#[macro_use] extern crate rocket;
+
+use rocket::fairing::AdHoc;
+
+struct Wrapper<T>(T);
+
+struct Count(usize);
+
+#[get("/")]
+fn index(count: state!(Count), wrapper: state!(Wrapper<u8>)) -> String {
+    format!("{} {}", count.0, wrapper.0)
+}
+
+#[launch(registry)]
+fn rocket() -> _ {
+    struct Local(usize);
+
+    mod inner {
+        pub struct Nested;
+    }
+
+    rocket::custom(rocket::Config::figment().merge(("port", 0)))
+        .mount("/", routes![index])
+        .manage(Count(0))
+        .manage::<Wrapper<u8>>(Wrapper(1))
+        .manage(Local(3))
+        .manage(inner::Nested {})
+        .attach(AdHoc::on_liftoff("Shutdown", |rocket| Box::pin(async move {
+            rocket.shutdown().notify();
+        })))
+}
@@ -0,0 +1,24 @@
+// Without `registry`, `#[launch]` leaves `.manage()` calls alone, whatever
+// the type of the managed value.
+
+#[macro_use] extern crate rocket;
+
+use rocket::fairing::AdHoc;
+
+struct Wrapper<T>(T);
+
+struct Pair<A, B> { a: A, b: B }
+
+#[launch]
+fn rocket() -> _ {
+    struct Local(usize);
+
+    rocket::custom(rocket::Config::figment().merge(("port", 0)))
+        .manage(Wrapper(1u8))
+        .manage(Pair { a: 1u16, b: "two" })
+        .manage(Local(3))
+        .attach(AdHoc::on_liftoff("Shutdown", |rocket| Box::pin(async move {
+            let _ = (rocket.state::<Wrapper<u8>>(), rocket.state::<Pair<u16, &str>>());
+            rocket.shutdown().notify();
+        })))
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+
+use uncased::UncasedStr;
+
+use crate::header::typed::{self, TypedHeader, HeaderError};
+
+/// The HTTP `Accept-Language` header.
+///
+/// An `Accept-Language` header is composed of zero or more language ranges,
+/// each with an optional quality value (a [`QLanguage`]). Language ranges are
+/// ordered by descending quality; ranges with equal quality retain the order
+/// in which they appear in the header.
+///
+/// # Usage
+///
+/// `AcceptLanguage` is a [request guard]. [`AcceptLanguage::negotiate()`]
+/// selects the best match among the languages an application supports:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::AcceptLanguage;
+///
+/// #[get("/")]
+/// fn index(languages: Option<AcceptLanguage<'_>>) -> &'static str {
+///     let language = languages.and_then(|l| l.negotiate(&["en", "fr"]));
+///     match language {
+///         Some("fr") => "Bonjour!",
+///         _ => "Hello!",
+///     }
+/// }
+/// ```
+///
+/// [request guard]: ../../rocket/request/trait.FromRequest.html#provided-implementations
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptLanguage<'a>(Vec<QLanguage<'a>>);
+
+/// A language range with an associated quality value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QLanguage<'a> {
+    range: &'a str,
+    quality: f32,
+}
+
+impl<'a> AcceptLanguage<'a> {
+    /// Returns an iterator over the language ranges in `self` in order of
+    /// preference, including those with a quality of `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{AcceptLanguage, TypedHeader};
+    ///
+    /// let header = AcceptLanguage::parse("fr;q=0.5, de, en;q=0.8").unwrap();
+    /// let ranges: Vec<_> = header.iter().map(|l| l.range()).collect();
+    /// assert_eq!(ranges, ["de", "en", "fr"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &QLanguage<'a>> + '_ {
+        self.0.iter()
+    }
+
+    /// Returns the client's preferred language range, if any. A range with a
+    /// quality of `0` is never preferred.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{AcceptLanguage, TypedHeader};
+    ///
+    /// let header = AcceptLanguage::parse("en;q=0.8, fr-CA").unwrap();
+    /// assert_eq!(header.preferred(), Some("fr-CA"));
+    ///
+    /// let header = AcceptLanguage::parse("en;q=0").unwrap();
+    /// assert_eq!(header.preferred(), None);
+    /// ```
+    pub fn preferred(&self) -> Option<&'a str> {
+        self.iter().find(|l| l.quality > 0.0).map(|l| l.range)
+    }
+
+    /// Returns the language tag in `available` which best matches the
+    /// client's preferences, if any.
+    ///
+    /// Language ranges are tried in order of preference. A range matches a
+    /// tag if the two are equal or if the range is a prefix of the tag
+    /// followed by `-`, both case-insensitively. If no tag matches a range,
+    /// the range's subtags are removed from the end until one does, so that
+    /// `en-US` matches an available `en`. The range `*` matches the first
+    /// tag in `available`. Tags matched by ranges with a quality of `0` are
+    /// never returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{AcceptLanguage, TypedHeader};
+    ///
+    /// let header = AcceptLanguage::parse("de-CH, en;q=0.5").unwrap();
+    /// assert_eq!(header.negotiate(&["en-GB", "de"]), Some("de"));
+    /// assert_eq!(header.negotiate(&["en-GB", "fr"]), Some("en-GB"));
+    /// assert_eq!(header.negotiate(&["fr"]), None);
+    ///
+    /// let header = AcceptLanguage::parse("*, fr;q=0").unwrap();
+    /// assert_eq!(header.negotiate(&["fr", "es"]), Some("es"));
+    /// ```
+    pub fn negotiate<'t>(&self, available: &[&'t str]) -> Option<&'t str> {
+        let excluded = |tag: &str| self.iter()
+            .filter(|l| l.quality == 0.0)
+            .any(|l| l.range != "*" && l.matches(tag));
+
+        let available = || available.iter().copied().filter(|tag| !excluded(*tag));
+        for language in self.iter().filter(|l| l.quality > 0.0) {
+            if language.range == "*" {
+                return available().next();
+            }
+
+            let mut range = language.range;
+            loop {
+                let candidate = QLanguage { range, quality: language.quality };
+                if let Some(tag) = available().find(|tag| candidate.matches(tag)) {
+                    return Some(tag);
+                }
+
+                match range.rsplit_once('-') {
+                    Some((prefix, _)) => range = prefix,
+                    None => break,
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> QLanguage<'a> {
+    /// The language range: a language tag like `en-US` or `*`.
+    pub fn range(&self) -> &'a str {
+        self.range
+    }
+
+    /// The quality value between `0` and `1`, which defaults to `1`.
+    pub fn quality(&self) -> f32 {
+        self.quality
+    }
+
+    /// Returns `true` if `self` matches the language `tag`.
+    fn matches(&self, tag: &str) -> bool {
+        let len = self.range.len();
+        match tag.get(..len) {
+            Some(prefix) if UncasedStr::new(prefix) == UncasedStr::new(self.range) => {
+                tag.len() == len || tag[len..].starts_with('-')
+            }
+            _ => false,
+        }
+    }
+}
+
+fn is_language_range(range: &str) -> bool {
+    fn is_subtag(subtag: &str, alpha: bool) -> bool {
+        (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| match alpha {
+            true => b.is_ascii_alphabetic(),
+            false => b.is_ascii_alphanumeric(),
+        })
+    }
+
+    let mut subtags = range.split('-');
+    range == "*" || (subtags.next().map_or(false, |s| is_subtag(s, true))
+        && subtags.all(|s| is_subtag(s, false)))
+}
+
+fn parse(value: &str) -> Result<Vec<QLanguage<'_>>, HeaderError> {
+    let malformed = |reason| HeaderError::Malformed { name: AcceptLanguage::NAME, reason };
+
+    let mut languages = vec![];
+    for element in typed::split(value, ',') {
+        let mut parts = element.split(';').map(|p| p.trim());
+        let range = parts.next().unwrap_or_default();
+        if !is_language_range(range) {
+            return Err(malformed("invalid language range"));
+        }
+
+        let quality = match parts.next() {
+            Some(q) => match q.strip_prefix("q=").or_else(|| q.strip_prefix("Q=")) {
+                Some(q) if q.len() <= 5 => q.parse::<f32>().ok()
+                    .filter(|q| (0.0..=1.0).contains(q))
+                    .ok_or_else(|| malformed("invalid quality value"))?,
+                _ => return Err(malformed("invalid quality value")),
+            },
+            None => 1.0,
+        };
+
+        if parts.next().is_some() {
+            return Err(malformed("unexpected parameter"));
+        }
+
+        languages.push(QLanguage { range, quality });
+    }
+
+    Ok(languages)
+}
+
+impl<'a> TypedHeader<'a> for AcceptLanguage<'a> {
+    const NAME: &'static str = "Accept-Language";
+
+    fn parse(value: &'a str) -> Result<Self, HeaderError> {
+        Self::from_values(std::iter::once(value))
+    }
+
+    fn from_values<I: Iterator<Item = &'a str>>(values: I) -> Result<Self, HeaderError> {
+        let mut languages = typed::parse_list(Self::NAME, values, parse)?;
+        languages.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        Ok(AcceptLanguage(languages))
+    }
+}
+
+impl fmt::Display for AcceptLanguage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, language) in self.iter().enumerate() {
+            if i != 0 { f.write_str(", ")?; }
+            match language.quality {
+                q if q == 1.0 => write!(f, "{}", language.range)?,
+                q => write!(f, "{};q={}", language.range, q)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let header = AcceptLanguage::parse("en-US, fr;q=0.5,de-DE-1996;q=0.9, *;q=0.1").unwrap();
+        let ranges: Vec<_> = header.iter().map(|l| (l.range(), l.quality())).collect();
+        assert_eq!(ranges, [("en-US", 1.0), ("de-DE-1996", 0.9), ("fr", 0.5), ("*", 0.1)]);
+        assert_eq!(header.to_string(), "en-US, de-DE-1996;q=0.9, fr;q=0.5, *;q=0.1");
+
+        assert!(AcceptLanguage::parse("").is_ok());
+        assert!(AcceptLanguage::parse("en_US").is_err());
+        assert!(AcceptLanguage::parse("toolonglanguage").is_err());
+        assert!(AcceptLanguage::parse("en;q=2").is_err());
+        assert!(AcceptLanguage::parse("en;q=0.12345").is_err());
+        assert!(AcceptLanguage::parse("en;level=1").is_err());
+    }
+
+    #[test]
+    fn test_from_values() {
+        let values = ["fr;q=0.3", "en"].into_iter();
+        let header = AcceptLanguage::from_values(values).unwrap();
+        assert_eq!(header.preferred(), Some("en"));
+
+        let missing = AcceptLanguage::from_values(std::iter::empty());
+        assert_eq!(missing, Err(HeaderError::Missing("Accept-Language")));
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let header = AcceptLanguage::parse("EN-us;q=0.9, fr").unwrap();
+        assert_eq!(header.negotiate(&["en-US", "fr-CA"]), Some("fr-CA"));
+        assert_eq!(header.negotiate(&["en-us", "es"]), Some("en-us"));
+        assert_eq!(header.negotiate(&["en", "es"]), Some("en"));
+        assert_eq!(header.negotiate(&["es"]), None);
+        assert_eq!(header.negotiate(&["fra"]), None);
+    }
+}
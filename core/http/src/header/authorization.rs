@@ -0,0 +1,213 @@
+use std::fmt;
+
+use uncased::UncasedStr;
+
+use crate::header::typed::{self, TypedHeader, HeaderError};
+
+/// The HTTP `Authorization` header.
+///
+/// The header's credentials are parsed according to their scheme: `Basic`
+/// credentials are decoded into a username and password, `Bearer` credentials
+/// are a token, and credentials for any other scheme are left as-is. The
+/// `Debug` implementation never displays passwords, tokens, or credentials.
+///
+/// # Usage
+///
+/// `Authorization` is a [request guard] which fails with `401 Unauthorized`
+/// if the header is missing and with `400 Bad Request` if it is malformed:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{Authorization, Status};
+///
+/// #[get("/")]
+/// fn secret(auth: Authorization<'_>) -> Result<&'static str, Status> {
+///     match auth.bearer() {
+///         Some("letmein") => Ok("Welcome!"),
+///         _ => Err(Status::Forbidden),
+///     }
+/// }
+/// ```
+///
+/// [request guard]: ../../rocket/request/trait.FromRequest.html#provided-implementations
+#[derive(Clone, PartialEq, Eq)]
+pub enum Authorization<'a> {
+    /// The `Basic` scheme: a base64-encoded `username:password` pair.
+    Basic {
+        /// The decoded username.
+        username: String,
+        /// The decoded password.
+        password: String,
+    },
+    /// The `Bearer` scheme: a token.
+    Bearer(&'a str),
+    /// Any other scheme with its raw credentials, if any.
+    Other {
+        /// The authentication scheme.
+        scheme: &'a UncasedStr,
+        /// The raw credentials following the scheme.
+        credentials: &'a str,
+    },
+}
+
+impl<'a> Authorization<'a> {
+    /// Returns the authentication scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Authorization, TypedHeader};
+    ///
+    /// let auth = Authorization::parse("bearer abc").unwrap();
+    /// assert_eq!(auth.scheme(), "Bearer");
+    ///
+    /// let auth = Authorization::parse("Digest username=\"bob\"").unwrap();
+    /// assert_eq!(auth.scheme(), "Digest");
+    /// ```
+    pub fn scheme(&self) -> &UncasedStr {
+        match self {
+            Authorization::Basic { .. } => "Basic".into(),
+            Authorization::Bearer(_) => "Bearer".into(),
+            Authorization::Other { scheme, .. } => scheme,
+        }
+    }
+
+    /// Returns the username and password if `self` uses the `Basic` scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Authorization, TypedHeader};
+    ///
+    /// let auth = Authorization::parse("Basic Ym9iOmh1bnRlcjI=").unwrap();
+    /// assert_eq!(auth.basic(), Some(("bob", "hunter2")));
+    /// assert_eq!(auth.bearer(), None);
+    /// ```
+    pub fn basic(&self) -> Option<(&str, &str)> {
+        match self {
+            Authorization::Basic { username, password } => {
+                Some((username.as_str(), password.as_str()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the token if `self` uses the `Bearer` scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Authorization, TypedHeader};
+    ///
+    /// let auth = Authorization::parse("Bearer mF_9.B5f-4.1JqM").unwrap();
+    /// assert_eq!(auth.bearer(), Some("mF_9.B5f-4.1JqM"));
+    /// assert_eq!(auth.basic(), None);
+    /// ```
+    pub fn bearer(&self) -> Option<&'a str> {
+        match self {
+            Authorization::Bearer(token) => Some(*token),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if `string` is an RFC 9110 `token68`.
+fn is_token68(string: &str) -> bool {
+    let data = string.trim_end_matches('=');
+    !data.is_empty() && data.bytes().all(|b| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b))
+}
+
+impl<'a> TypedHeader<'a> for Authorization<'a> {
+    const NAME: &'static str = "Authorization";
+
+    fn parse(value: &'a str) -> Result<Self, HeaderError> {
+        let malformed = |reason| HeaderError::Malformed { name: Self::NAME, reason };
+
+        let value = value.trim();
+        let (scheme, credentials) = value.split_once(' ').unwrap_or((value, ""));
+        if !typed::is_token(scheme) {
+            return Err(malformed("invalid authentication scheme"));
+        }
+
+        let (scheme, credentials) = (UncasedStr::new(scheme), credentials.trim());
+        if scheme == "Basic" {
+            if !is_token68(credentials) {
+                return Err(malformed("invalid basic credentials"));
+            }
+
+            let mut buffer = vec![0; credentials.len()];
+            let decoded = binascii::b64decode(credentials.as_bytes(), &mut buffer)
+                .map_err(|_| malformed("basic credentials are not base64"))?;
+
+            let decoded = std::str::from_utf8(decoded)
+                .map_err(|_| malformed("basic credentials are not UTF-8"))?;
+
+            let (username, password) = decoded.split_once(':')
+                .ok_or_else(|| malformed("basic credentials are missing a `:`"))?;
+
+            let (username, password) = (username.to_string(), password.to_string());
+            Ok(Authorization::Basic { username, password })
+        } else if scheme == "Bearer" {
+            match is_token68(credentials) {
+                true => Ok(Authorization::Bearer(credentials)),
+                false => Err(malformed("invalid bearer token")),
+            }
+        } else {
+            Ok(Authorization::Other { scheme, credentials })
+        }
+    }
+}
+
+impl fmt::Debug for Authorization<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Authorization::Basic { username, .. } => f.debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Authorization::Bearer(_) => f.write_str("Bearer(..)"),
+            Authorization::Other { scheme, .. } => f.debug_struct("Other")
+                .field("scheme", scheme)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let auth = Authorization::parse("basic YTpiOmM=").unwrap();
+        assert_eq!(auth.basic(), Some(("a", "b:c")));
+
+        let auth = Authorization::parse("Basic Ym9iOg==").unwrap();
+        assert_eq!(auth.basic(), Some(("bob", "")));
+
+        let auth = Authorization::parse("Bearer abc==").unwrap();
+        assert_eq!(auth.bearer(), Some("abc=="));
+
+        let auth = Authorization::parse("Custom").unwrap();
+        assert_eq!(auth.scheme(), "custom");
+
+        assert!(Authorization::parse("").is_err());
+        assert!(Authorization::parse("Basic").is_err());
+        assert!(Authorization::parse("Basic !!!!").is_err());
+        assert!(Authorization::parse("Basic Ym9i").is_err());
+        assert!(Authorization::parse("Bearer a b").is_err());
+        assert!(Authorization::parse("Bea/rer abc").is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts() {
+        let auth = Authorization::parse("Basic Ym9iOmh1bnRlcjI=").unwrap();
+        let debug = format!("{:?}", auth);
+        assert!(debug.contains("bob") && !debug.contains("hunter2"));
+
+        let auth = Authorization::parse("Bearer secret").unwrap();
+        assert!(!format!("{:?}", auth).contains("secret"));
+    }
+}
@@ -0,0 +1,330 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use uncased::UncasedStr;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::Header;
+use crate::header::link::write_token_or_quoted;
+use crate::header::typed::{self, TypedHeader, HeaderError};
+
+/// The RFC 8187 `attr-char` set: everything else in an `ext-value` is encoded.
+const ATTR_CHAR_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'!').remove(b'#').remove(b'$').remove(b'&').remove(b'+').remove(b'-')
+    .remove(b'.').remove(b'^').remove(b'_').remove(b'`').remove(b'|').remove(b'~');
+
+/// The HTTP `Content-Disposition` header.
+///
+/// A `Content-Disposition` header is composed of a disposition type, like
+/// `attachment` or `form-data`, and zero or more parameters. Parameter values
+/// are unquoted. Extended parameters like `filename*` ([RFC 8187]) are
+/// percent-decoded if their charset is UTF-8 and preferred over their plain
+/// counterparts by [`ContentDisposition::filename()`].
+///
+/// [RFC 8187]: https://datatracker.ietf.org/doc/html/rfc8187
+///
+/// # Usage
+///
+/// `ContentDisposition` is a [request guard]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::ContentDisposition;
+///
+/// #[put("/", data = "<file>")]
+/// fn upload(file: Vec<u8>, disposition: ContentDisposition<'_>) -> String {
+///     let name = disposition.filename().unwrap_or("upload".into());
+///     format!("received {} bytes for {}", file.len(), name)
+/// }
+/// ```
+///
+/// [request guard]: ../../rocket/request/trait.FromRequest.html#provided-implementations
+///
+/// # Header
+///
+/// A `ContentDisposition` can also be built with
+/// [`ContentDisposition::attachment()`] or [`ContentDisposition::inline()`]
+/// and used as a response header via its `Into<Header>` implementation. Any
+/// filename is encoded as required by [RFC 6266]:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{ContentDisposition, Header};
+///
+/// let header: Header<'_> = ContentDisposition::attachment("report.pdf").into();
+/// assert_eq!(header.value(), "attachment; filename=report.pdf");
+/// ```
+///
+/// [RFC 6266]: https://datatracker.ietf.org/doc/html/rfc6266
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition<'a> {
+    disposition: &'a UncasedStr,
+    params: Vec<(&'a UncasedStr, Cow<'a, str>)>,
+}
+
+impl ContentDisposition<'static> {
+    /// A `Content-Disposition: inline` header with no parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ContentDisposition;
+    ///
+    /// let header = ContentDisposition::inline();
+    /// assert!(header.is_inline());
+    /// assert_eq!(header.to_string(), "inline");
+    /// ```
+    pub fn inline() -> ContentDisposition<'static> {
+        ContentDisposition { disposition: "inline".into(), params: vec![] }
+    }
+
+    /// A `Content-Disposition: attachment` header with the filename
+    /// `filename`. See [`ContentDisposition::with_filename()`] for details on
+    /// how `filename` is encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ContentDisposition;
+    ///
+    /// let header = ContentDisposition::attachment("my report.pdf");
+    /// assert!(header.is_attachment());
+    /// assert_eq!(header.filename().unwrap(), "my report.pdf");
+    /// assert_eq!(header.to_string(), r#"attachment; filename="my report.pdf""#);
+    /// ```
+    pub fn attachment(filename: &str) -> ContentDisposition<'static> {
+        ContentDisposition { disposition: "attachment".into(), params: vec![] }
+            .with_filename(filename)
+    }
+
+    /// A `Content-Disposition: form-data` header for the form field named
+    /// `name`, as used in the parts of a `multipart/form-data` body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ContentDisposition;
+    ///
+    /// let header = ContentDisposition::form_data("user name");
+    /// assert!(header.is_form_data());
+    /// assert_eq!(header.name().unwrap(), "user name");
+    /// assert_eq!(header.to_string(), r#"form-data; name="user name""#);
+    /// ```
+    pub fn form_data(name: &str) -> ContentDisposition<'static> {
+        let params = vec![("name".into(), name.to_string().into())];
+        ContentDisposition { disposition: "form-data".into(), params }
+    }
+
+    /// Sets the `filename` parameter to `filename`, replacing any existing
+    /// `filename` or `filename*` parameters.
+    ///
+    /// If `filename` contains only ASCII characters, it is emitted as is,
+    /// quoted if necessary. Otherwise, a `filename*` parameter with the
+    /// percent-encoded UTF-8 filename is emitted as well as a `filename`
+    /// fallback for legacy clients in which every non-ASCII character is
+    /// replaced with `_`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ContentDisposition;
+    ///
+    /// let header = ContentDisposition::inline().with_filename("€ rates.csv");
+    /// assert_eq!(header.filename().unwrap(), "€ rates.csv");
+    /// assert_eq!(header.to_string(),
+    ///     r#"inline; filename="_ rates.csv"; filename*=UTF-8''%E2%82%AC%20rates.csv"#);
+    /// ```
+    pub fn with_filename(mut self, filename: &str) -> ContentDisposition<'static> {
+        self.params.retain(|(name, _)| {
+            UncasedStr::new(name.as_str().trim_end_matches('*')) != "filename"
+        });
+        if filename.is_ascii() {
+            self.params.push(("filename".into(), filename.to_string().into()));
+            return self;
+        }
+
+        let fallback = filename.chars()
+            .map(|c| if c.is_ascii() { c } else { '_' })
+            .collect::<String>();
+
+        let encoded = utf8_percent_encode(filename, ATTR_CHAR_ENCODE_SET);
+        self.params.push(("filename".into(), fallback.into()));
+        self.params.push(("filename*".into(), format!("UTF-8''{}", encoded).into()));
+        self
+    }
+}
+
+impl<'a> ContentDisposition<'a> {
+    /// The disposition type, such as `inline`, `attachment`, or `form-data`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{ContentDisposition, TypedHeader};
+    ///
+    /// let header = ContentDisposition::parse("Attachment").unwrap();
+    /// assert_eq!(header.disposition(), "attachment");
+    /// assert!(header.is_attachment());
+    /// ```
+    pub fn disposition(&self) -> &'a UncasedStr {
+        self.disposition
+    }
+
+    /// Returns `true` if the disposition type is `inline`.
+    pub fn is_inline(&self) -> bool {
+        self.disposition == "inline"
+    }
+
+    /// Returns `true` if the disposition type is `attachment`.
+    pub fn is_attachment(&self) -> bool {
+        self.disposition == "attachment"
+    }
+
+    /// Returns `true` if the disposition type is `form-data`.
+    pub fn is_form_data(&self) -> bool {
+        self.disposition == "form-data"
+    }
+
+    /// Returns the value of the first parameter named `name`, matched
+    /// case-insensitively. If `name` is `foo` and there is a valid extended
+    /// `foo*` parameter, its decoded value is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{ContentDisposition, TypedHeader};
+    ///
+    /// let header = r#"form-data; name="field"; filename*=UTF-8''%E2%82%AC.txt"#;
+    /// let header = ContentDisposition::parse(header).unwrap();
+    /// assert_eq!(header.param("name").unwrap(), "field");
+    /// assert_eq!(header.param("filename").unwrap(), "€.txt");
+    /// assert_eq!(header.param("Filename*").unwrap(), "€.txt");
+    /// assert!(header.param("size").is_none());
+    /// ```
+    pub fn param(&self, name: &str) -> Option<Cow<'_, str>> {
+        let name = UncasedStr::new(name.trim_end_matches('*'));
+        let mut params = self.params.iter();
+        let extended = params.clone()
+            .filter(|(n, _)| n.as_str().strip_suffix('*').map(UncasedStr::new) == Some(name))
+            .find_map(|(_, v)| decode_extended(v));
+
+        extended.or_else(|| params.find(|(n, _)| *n == name).map(|(_, v)| v.as_ref().into()))
+    }
+
+    /// The `name` parameter, used by `form-data`.
+    pub fn name(&self) -> Option<Cow<'_, str>> {
+        self.param("name")
+    }
+
+    /// The `filename` parameter, preferring `filename*` if it is present.
+    ///
+    /// The returned name is as sent by the client: it must be sanitized, for
+    /// instance with [`FileName`], before it is used as a path.
+    ///
+    /// [`FileName`]: ../../rocket/fs/struct.FileName.html
+    pub fn filename(&self) -> Option<Cow<'_, str>> {
+        self.param("filename")
+    }
+}
+
+/// Decodes an RFC 8187 `ext-value`: `charset'[language]'value`. Only the
+/// UTF-8 charset is supported.
+fn decode_extended(value: &str) -> Option<Cow<'_, str>> {
+    let mut parts = value.splitn(3, '\'');
+    let (charset, _, value) = (parts.next()?, parts.next()?, parts.next()?);
+    if !charset.eq_ignore_ascii_case("utf-8") {
+        return None;
+    }
+
+    percent_decode_str(value).decode_utf8().ok()
+}
+
+impl<'a> TypedHeader<'a> for ContentDisposition<'a> {
+    const NAME: &'static str = "Content-Disposition";
+
+    fn parse(value: &'a str) -> Result<Self, HeaderError> {
+        let malformed = |reason| HeaderError::Malformed { name: Self::NAME, reason };
+
+        let mut parts = typed::split(value, ';');
+        let disposition = parts.next().unwrap_or_default();
+        if !typed::is_token(disposition) {
+            return Err(malformed("invalid disposition type"));
+        }
+
+        let params = parts
+            .map(|param| match typed::param(param) {
+                Some((name, Some(value))) => Ok((UncasedStr::new(name), value)),
+                _ => Err(malformed("invalid disposition parameter")),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ContentDisposition { disposition: disposition.into(), params })
+    }
+}
+
+impl fmt::Display for ContentDisposition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.disposition)?;
+        for (name, value) in &self.params {
+            write!(f, "; {}=", name)?;
+            match name.as_str().ends_with('*') {
+                true => f.write_str(value)?,
+                false => write_token_or_quoted(f, value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ContentDisposition<'_>> for Header<'static> {
+    fn from(header: ContentDisposition<'_>) -> Self {
+        Header::new(ContentDisposition::NAME, header.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let header = r#"attachment; FILENAME="a \"b\".txt"; filename*=iso-8859-1'en'%A3"#;
+        let header = ContentDisposition::parse(header).unwrap();
+        assert!(header.is_attachment());
+        assert_eq!(header.filename().unwrap(), "a \"b\".txt");
+
+        let header = ContentDisposition::parse("inline;filename*=utf-8'en'caf%C3%A9").unwrap();
+        assert!(header.is_inline());
+        assert_eq!(header.filename().unwrap(), "café");
+        assert!(header.name().is_none());
+
+        assert!(ContentDisposition::parse("").is_err());
+        assert!(ContentDisposition::parse("attachment; filename").is_err());
+        assert!(ContentDisposition::parse("attachment; filename=a b").is_err());
+        assert!(ContentDisposition::parse("attach ment").is_err());
+    }
+
+    #[test]
+    fn test_build() {
+        let header = ContentDisposition::attachment("a\"b\\c.txt");
+        assert_eq!(header.to_string(), r#"attachment; filename="a\"b\\c.txt""#);
+
+        let header = ContentDisposition::attachment("ü.txt").with_filename("x.txt");
+        assert_eq!(header.to_string(), "attachment; filename=x.txt");
+
+        let header = ContentDisposition::attachment("naïve (1).txt");
+        let string = header.to_string();
+        assert_eq!(string,
+            r#"attachment; filename="na_ve (1).txt"; filename*=UTF-8''na%C3%AFve%20%281%29.txt"#);
+
+        let parsed = ContentDisposition::parse(&string).unwrap();
+        assert_eq!(parsed.filename().unwrap(), "naïve (1).txt");
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+
+use time::OffsetDateTime;
+
+use crate::Header;
+use crate::header::retry_after::http_date;
+
+/// The HTTP `Deprecation` header as defined in [RFC 9745].
+///
+/// A `Deprecation` header indicates that a resource is or will be deprecated
+/// as of the contained date. The date is formatted as a structured field date:
+/// an `@` followed by the number of seconds since the Unix epoch. A
+/// deprecated resource continues to work, but clients should migrate away from
+/// it, typically before the date in an accompanying [`Sunset`] header.
+///
+/// [RFC 9745]: https://datatracker.ietf.org/doc/html/rfc9745
+///
+/// # Header
+///
+/// `Deprecation` implements `Into<Header>`, so it can be used in any context
+/// where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::Deprecation;
+/// use rocket::response::Response;
+/// use rocket::time::macros::datetime;
+///
+/// let deprecation = Deprecation(datetime!(2023-06-30 23:59:59 UTC));
+/// let response = Response::build().header(deprecation).finalize();
+/// assert_eq!(response.headers().get_one("Deprecation"), Some("@1688169599"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation(pub OffsetDateTime);
+
+/// The HTTP `Sunset` header as defined in [RFC 8594].
+///
+/// A `Sunset` header indicates the date after which a resource is expected
+/// to become unresponsive. The date is formatted as an HTTP-date after being
+/// converted to UTC.
+///
+/// [RFC 8594]: https://datatracker.ietf.org/doc/html/rfc8594
+///
+/// # Header
+///
+/// `Sunset` implements `Into<Header>`, so it can be used in any context where
+/// an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::Sunset;
+/// use rocket::response::Response;
+/// use rocket::time::macros::datetime;
+///
+/// let sunset = Sunset(datetime!(2018-12-31 23:59:59 UTC));
+/// let response = Response::build().header(sunset).finalize();
+/// let value = response.headers().get_one("Sunset");
+/// assert_eq!(value, Some("Mon, 31 Dec 2018 23:59:59 GMT"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sunset(pub OffsetDateTime);
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.0.unix_timestamp())
+    }
+}
+
+impl fmt::Display for Sunset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&http_date(self.0))
+    }
+}
+
+impl From<Deprecation> for Header<'static> {
+    fn from(deprecation: Deprecation) -> Self {
+        Header::new("Deprecation", deprecation.to_string())
+    }
+}
+
+impl From<Sunset> for Header<'static> {
+    fn from(sunset: Sunset) -> Self {
+        Header::new("Sunset", sunset.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_format() {
+        let header: Header<'_> = Deprecation(datetime!(1970-01-01 00:00:00 UTC)).into();
+        assert_eq!(header.name(), "Deprecation");
+        assert_eq!(header.value(), "@0");
+
+        let date = Deprecation(datetime!(2023-07-01 01:59:59 +2));
+        assert_eq!(date.to_string(), "@1688169599");
+
+        let header: Header<'_> = Sunset(datetime!(2000-01-01 00:30:00 +1)).into();
+        assert_eq!(header.name(), "Sunset");
+        assert_eq!(header.value(), "Fri, 31 Dec 1999 23:30:00 GMT");
+    }
+}
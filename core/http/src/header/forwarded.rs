@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+use std::net::IpAddr;
+
+use crate::ProxyProto;
+use crate::header::typed::{self, TypedHeader, HeaderError};
+
+/// The HTTP `Forwarded` header as defined in [RFC 7239].
+///
+/// A `Forwarded` header is a list of [`ForwardedElement`]s, one for each
+/// proxy a request passed through, in the order the proxies were traversed.
+/// The first element thus describes the original client's request, but as
+/// the header may be set or extended by anyone, it should only be trusted
+/// as far as the proxies that set it are.
+///
+/// [RFC 7239]: https://datatracker.ietf.org/doc/html/rfc7239
+///
+/// # Usage
+///
+/// `Forwarded` is a [request guard]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Forwarded;
+///
+/// #[get("/")]
+/// fn index(forwarded: Forwarded<'_>) -> String {
+///     let client = forwarded.first().and_then(|e| e.for_ip());
+///     format!("Hello, {:?}!", client)
+/// }
+/// ```
+///
+/// [request guard]: ../../rocket/request/trait.FromRequest.html#provided-implementations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forwarded<'a>(Vec<ForwardedElement<'a>>);
+
+/// A single element of a [`Forwarded`] header.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ForwardedElement<'a> {
+    for_node: Option<Cow<'a, str>>,
+    by_node: Option<Cow<'a, str>>,
+    host: Option<Cow<'a, str>>,
+    proto: Option<Cow<'a, str>>,
+}
+
+impl<'a> Forwarded<'a> {
+    /// Returns an iterator over the elements in `self` in header order.
+    pub fn iter(&self) -> impl Iterator<Item = &ForwardedElement<'a>> + '_ {
+        self.0.iter()
+    }
+
+    /// Returns the first element in `self`, which describes the request as
+    /// received by the first proxy, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Forwarded, TypedHeader};
+    ///
+    /// let header = "for=192.0.2.43;proto=https, for=198.51.100.17";
+    /// let forwarded = Forwarded::parse(header).unwrap();
+    /// let first = forwarded.first().unwrap();
+    /// assert_eq!(first.for_node(), Some("192.0.2.43"));
+    /// assert!(first.proto().unwrap().is_https());
+    /// assert_eq!(forwarded.iter().count(), 2);
+    /// ```
+    pub fn first(&self) -> Option<&ForwardedElement<'a>> {
+        self.0.first()
+    }
+}
+
+impl ForwardedElement<'_> {
+    /// The `for` parameter: the node making the request to the proxy.
+    pub fn for_node(&self) -> Option<&str> {
+        self.for_node.as_deref()
+    }
+
+    /// The `by` parameter: the interface where the proxy received the request.
+    pub fn by_node(&self) -> Option<&str> {
+        self.by_node.as_deref()
+    }
+
+    /// The `host` parameter: the `Host` header as received by the proxy.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// The `proto` parameter: the protocol used to make the request.
+    pub fn proto(&self) -> Option<ProxyProto<'_>> {
+        self.proto.as_deref().map(ProxyProto::from)
+    }
+
+    /// The IP address in the `for` parameter, if it is an IP address and not
+    /// an obfuscated identifier or `unknown`. Any port is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use std::net::{IpAddr, Ipv6Addr};
+    /// use rocket::http::{Forwarded, TypedHeader};
+    ///
+    /// let forwarded = Forwarded::parse(r#"For="[2001:db8:cafe::17]:4711""#).unwrap();
+    /// let ip = "2001:db8:cafe::17".parse::<Ipv6Addr>().unwrap();
+    /// assert_eq!(forwarded.first().unwrap().for_ip(), Some(IpAddr::V6(ip)));
+    ///
+    /// let forwarded = Forwarded::parse("for=_hidden").unwrap();
+    /// assert_eq!(forwarded.first().unwrap().for_ip(), None);
+    /// ```
+    pub fn for_ip(&self) -> Option<IpAddr> {
+        let node = self.for_node()?;
+        if let Some(rest) = node.strip_prefix('[') {
+            return rest.split_once(']')?.0.parse().ok();
+        }
+
+        let ip = node.split_once(':').map_or(node, |(ip, _)| ip);
+        ip.parse().ok()
+    }
+}
+
+fn parse(value: &str) -> Result<Vec<ForwardedElement<'_>>, HeaderError> {
+    let malformed = |reason| HeaderError::Malformed { name: Forwarded::NAME, reason };
+
+    let mut elements = vec![];
+    for element in typed::split(value, ',') {
+        let mut forwarded = ForwardedElement::default();
+        for pair in typed::split(element, ';') {
+            let (name, value) = match typed::param(pair) {
+                Some((name, Some(value))) => (name, value),
+                _ => return Err(malformed("invalid forwarded pair")),
+            };
+
+            let param = match name {
+                _ if name.eq_ignore_ascii_case("for") => &mut forwarded.for_node,
+                _ if name.eq_ignore_ascii_case("by") => &mut forwarded.by_node,
+                _ if name.eq_ignore_ascii_case("host") => &mut forwarded.host,
+                _ if name.eq_ignore_ascii_case("proto") => &mut forwarded.proto,
+                _ => continue,
+            };
+
+            if param.replace(value).is_some() {
+                return Err(malformed("duplicate forwarded parameter"));
+            }
+        }
+
+        elements.push(forwarded);
+    }
+
+    Ok(elements)
+}
+
+impl<'a> TypedHeader<'a> for Forwarded<'a> {
+    const NAME: &'static str = "Forwarded";
+
+    fn parse(value: &'a str) -> Result<Self, HeaderError> {
+        Self::from_values(std::iter::once(value))
+    }
+
+    fn from_values<I: Iterator<Item = &'a str>>(values: I) -> Result<Self, HeaderError> {
+        typed::parse_list(Self::NAME, values, parse).map(Forwarded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let header = r#"for="_gazonk", For="[2001:db8:cafe::17]:4711";by=10.0.0.1"#;
+        let forwarded = Forwarded::parse(header).unwrap();
+        let elements: Vec<_> = forwarded.iter().collect();
+        assert_eq!(elements[0].for_node(), Some("_gazonk"));
+        assert_eq!(elements[1].for_node(), Some("[2001:db8:cafe::17]:4711"));
+        assert_eq!(elements[1].by_node(), Some("10.0.0.1"));
+
+        let forwarded = Forwarded::parse(r#"for="1.2.3.4:80";host=example.com;ext=1"#).unwrap();
+        let first = forwarded.first().unwrap();
+        assert_eq!(first.for_ip(), Some([1, 2, 3, 4].into()));
+        assert_eq!(first.host(), Some("example.com"));
+        assert_eq!(first.proto(), None);
+
+        assert!(Forwarded::parse("for").is_err());
+        assert!(Forwarded::parse("for=a;for=b").is_err());
+        assert!(Forwarded::parse("for=[::1]").is_err());
+        assert!(Forwarded::parse("for=1.2.3.4:80").is_err());
+    }
+}
@@ -2,8 +2,10 @@ use std::borrow::{Borrow, Cow};
 use std::fmt;
 
 use indexmap::IndexMap;
+use tinyvec::TinyVec;
 
 use crate::uncased::{Uncased, UncasedStr};
+use crate::{TypedHeader, HeaderError};
 
 /// Simple representation of an HTTP header.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -243,9 +245,13 @@ impl fmt::Display for Header<'_> {
 /// returns values for headers of names "AbC", "ABC", "abc", and so on.
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct HeaderMap<'h> {
-    headers: IndexMap<Uncased<'h>, Vec<Cow<'h, str>>>
+    headers: IndexMap<Uncased<'h>, Values<'h>>
 }
 
+/// The values for a single header name. Most headers have exactly one value,
+/// which is stored inline, avoiding a heap allocation per header.
+type Values<'h> = TinyVec<[Cow<'h, str>; 1]>;
+
 impl<'h> HeaderMap<'h> {
     /// Returns an empty header collection.
     ///
@@ -393,6 +399,30 @@ impl<'h> HeaderMap<'h> {
             })
     }
 
+    /// Parses the values stored for the header [`T::NAME`] into a
+    /// [`TypedHeader`] `T`. Returns [`HeaderError::Missing`] if there are no
+    /// such values.
+    ///
+    /// [`T::NAME`]: TypedHeader::NAME
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{HeaderMap, Authorization, HeaderError};
+    ///
+    /// let mut map = HeaderMap::new();
+    /// assert_eq!(map.get_typed::<Authorization>(), Err(HeaderError::Missing("Authorization")));
+    ///
+    /// map.add_raw("Authorization", "Bearer abc123");
+    /// let auth = map.get_typed::<Authorization>().unwrap();
+    /// assert_eq!(auth.bearer(), Some("abc123"));
+    /// ```
+    #[inline]
+    pub fn get_typed<'a, T: TypedHeader<'a>>(&'a self) -> Result<T, HeaderError> {
+        T::from_values(self.get(T::NAME))
+    }
+
     /// Replace any header that matches the name of `header.name` with `header`.
     /// If there is no such header in `self`, add `header`. If the matching
     /// header had multiple values, all of the values are removed, and only the
@@ -446,7 +476,9 @@ impl<'h> HeaderMap<'h> {
     #[inline(always)]
     pub fn replace<'p: 'h, H: Into<Header<'p>>>(&mut self, header: H) -> bool {
         let header = header.into();
-        self.headers.insert(header.name, vec![header.value]).is_some()
+        let mut values = Values::new();
+        values.push(header.value);
+        self.headers.insert(header.name, values).is_some()
     }
 
     /// A convenience method to replace a header using a raw name and value.
@@ -499,7 +531,7 @@ impl<'h> HeaderMap<'h> {
     pub fn replace_all<'n, 'v: 'h, H>(&mut self, name: H, values: Vec<Cow<'v, str>>)
         where 'n: 'h, H: Into<Cow<'n, str>>
     {
-        self.headers.insert(Uncased::new(name), values);
+        self.headers.insert(Uncased::new(name), TinyVec::Heap(values));
     }
 
     /// Adds `header` into the map. If a header with `header.name` was
@@ -579,7 +611,7 @@ impl<'h> HeaderMap<'h> {
     {
         self.headers.entry(Uncased::new(name))
             .or_default()
-            .append(values)
+            .extend(values.drain(..))
     }
 
     /// Remove all of the values for header with name `name`.
@@ -702,7 +734,10 @@ impl<'h> HeaderMap<'h> {
     #[doc(hidden)]
     #[inline]
     pub fn into_iter_raw(self) -> impl Iterator<Item=(Uncased<'h>, Vec<Cow<'h, str>>)> {
-        self.headers.into_iter()
+        self.headers.into_iter().map(|(name, values)| match values {
+            TinyVec::Heap(values) => (name, values),
+            TinyVec::Inline(values) => (name, values.into_iter().collect()),
+        })
     }
 }
 
@@ -765,8 +800,8 @@ impl<'h> IntoIterator for HeaderMap<'h> {
 ///
 /// See [`HeaderMap::into_iter()`] for details.
 pub struct IntoIter<'h> {
-    headers: indexmap::map::IntoIter<Uncased<'h>, Vec<Cow<'h, str>>>,
-    current: Option<(Uncased<'h>, std::vec::IntoIter<Cow<'h, str>>)>,
+    headers: indexmap::map::IntoIter<Uncased<'h>, Values<'h>>,
+    current: Option<(Uncased<'h>, tinyvec::TinyVecIterator<[Cow<'h, str>; 1]>)>,
 }
 
 impl<'h> Iterator for IntoIter<'h> {
@@ -824,4 +859,27 @@ mod tests {
         let vals: Vec<_> = map.get("x-CuStOm").collect();
         assert_eq!(vals, vec!["a", "b", "c"]);
     }
+
+    #[test]
+    fn inline_and_spilled_values() {
+        let mut map = HeaderMap::new();
+        map.add_raw("X-One", "1");
+        map.add_raw("X-Many", "a");
+        map.add_raw("X-Many", "b");
+        map.add_all("X-Many", &mut vec!["c".into(), "d".into()]);
+        map.replace_all("X-Empty", vec![]);
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.get_one("X-Empty"), None);
+        assert_eq!(map.get("X-Many").collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+
+        let raw: Vec<_> = map.clone().into_iter_raw()
+            .map(|(name, values)| format!("{name}={}", values.len()))
+            .collect();
+
+        assert_eq!(raw, ["X-One=1", "X-Many=4", "X-Empty=0"]);
+
+        let headers: Vec<_> = map.into_iter().map(|h| h.to_string()).collect();
+        assert_eq!(headers, ["X-One: 1", "X-Many: a", "X-Many: b", "X-Many: c", "X-Many: d"]);
+    }
 }
@@ -53,6 +53,7 @@ macro_rules! known_media_types {
         EventStream (is_event_stream): "SSE stream", "text", "event-stream",
         Markdown (is_markdown): "markdown text", "text", "markdown" ; "charset" => "utf-8",
         EXE (is_exe): "executable", "application", "vnd.microsoft.portable-executable",
+        NDJSON (is_ndjson): "newline-delimited JSON", "application", "x-ndjson",
     })
 }
 
@@ -113,6 +114,7 @@ macro_rules! known_extensions {
         "md" => Markdown,
         "markdown" => Markdown,
         "exe" => EXE,
+        "ndjson" => NDJSON,
     })
 }
 
@@ -134,5 +136,6 @@ macro_rules! known_shorthands {
         "pdf" => PDF,
         "markdown" => Markdown,
         "md" => Markdown,
+        "ndjson" => NDJSON,
     })
 }
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::Header;
+use crate::header::{TypedHeader, HeaderError};
+use crate::header::retry_after::{http_date, HTTP_DATE_FMT};
+
+/// The HTTP `Last-Modified` header.
+///
+/// A `Last-Modified` header indicates the date the resource was last
+/// modified. The date is formatted as an HTTP-date after being converted to
+/// UTC and is truncated to whole seconds. Clients use it to make conditional
+/// requests with an `If-Modified-Since` header, which Rocket answers
+/// automatically for responses wrapped in a `rocket::response::Validated`.
+///
+/// # Header
+///
+/// `LastModified` implements `Into<Header>`, so it can be used in any context
+/// where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::LastModified;
+/// use rocket::response::Response;
+/// use rocket::time::macros::datetime;
+///
+/// let last_modified = LastModified(datetime!(1994-11-06 08:49:37 UTC));
+/// let response = Response::build().header(last_modified).finalize();
+/// let value = response.headers().get_one("Last-Modified");
+/// assert_eq!(value, Some("Sun, 06 Nov 1994 08:49:37 GMT"));
+/// ```
+///
+/// # Parsing
+///
+/// `LastModified` is a [`TypedHeader`] that parses an HTTP-date in the
+/// preferred IMF-fixdate format:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{HeaderMap, LastModified};
+/// use rocket::time::macros::datetime;
+///
+/// let mut map = HeaderMap::new();
+/// map.add_raw("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT");
+///
+/// let last_modified = map.get_typed::<LastModified>().unwrap();
+/// assert_eq!(last_modified.0, datetime!(1994-11-06 08:49:37 UTC));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastModified(pub OffsetDateTime);
+
+impl LastModified {
+    /// Parses an HTTP-date in the IMF-fixdate format, such as the value of a
+    /// `Last-Modified` or `If-Modified-Since` header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::LastModified;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let date = LastModified::parse_date("Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(date, Some(datetime!(1994-11-06 08:49:37 UTC)));
+    ///
+    /// assert!(LastModified::parse_date("Sunday, 06-Nov-94 08:49:37 GMT").is_none());
+    /// ```
+    pub fn parse_date(value: &str) -> Option<OffsetDateTime> {
+        PrimitiveDateTime::parse(value.trim(), HTTP_DATE_FMT)
+            .map(|date| date.assume_utc())
+            .ok()
+    }
+}
+
+impl fmt::Display for LastModified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&http_date(self.0))
+    }
+}
+
+impl From<LastModified> for Header<'static> {
+    fn from(last_modified: LastModified) -> Self {
+        Header::new("Last-Modified", last_modified.to_string())
+    }
+}
+
+impl TypedHeader<'_> for LastModified {
+    const NAME: &'static str = "Last-Modified";
+
+    fn parse(value: &str) -> Result<Self, HeaderError> {
+        match LastModified::parse_date(value) {
+            Some(date) => Ok(LastModified(date)),
+            None => Err(HeaderError::Malformed { name: Self::NAME, reason: "invalid HTTP-date" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_format() {
+        let header: Header<'_> = LastModified(datetime!(2000-01-01 00:30:00.75 +1)).into();
+        assert_eq!(header.name(), "Last-Modified");
+        assert_eq!(header.value(), "Fri, 31 Dec 1999 23:30:00 GMT");
+    }
+
+    #[test]
+    fn test_parse() {
+        let date = LastModified::parse(" Fri, 31 Dec 1999 23:30:00 GMT").unwrap();
+        assert_eq!(date, LastModified(datetime!(1999-12-31 23:30:00 UTC)));
+        assert_eq!(date.to_string(), "Fri, 31 Dec 1999 23:30:00 GMT");
+
+        assert!(LastModified::parse("Fri, 31 Dec 1999 23:30:00").is_err());
+        assert!(LastModified::parse("Fri, 31 Dec 1999 25:30:00 GMT").is_err());
+        assert!(LastModified::parse("Fri Dec 31 23:30:00 1999").is_err());
+        assert!(LastModified::parse("").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::Header;
+use crate::header::typed;
+
+/// The HTTP `Link` header as defined in [RFC 8288].
+///
+/// A `Link` header is composed of one or more links, each with a target URI,
+/// a relation type (`rel`), and optionally other parameters. `Link` is built
+/// by chaining calls that add links; [`Link::param()`] adds a parameter to
+/// the most recently added link. Parameter values are quoted as necessary.
+///
+/// [RFC 8288]: https://datatracker.ietf.org/doc/html/rfc8288
+///
+/// # Header
+///
+/// `Link` implements `Into<Header>`, so it can be used in any context where an
+/// `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::Link;
+/// use rocket::response::Response;
+///
+/// let link = Link::new()
+///     .next("/items?page=3")
+///     .prev("/items?page=1")
+///     .link("/docs/items", "help").param("title", "Item API");
+///
+/// let response = Response::build().header(link).finalize();
+/// assert_eq!(response.headers().get_one("Link").unwrap(), concat!(
+///     "</items?page=3>; rel=next, </items?page=1>; rel=prev, ",
+///     r#"</docs/items>; rel=help; title="Item API""#
+/// ));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Link {
+    links: Vec<LinkValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LinkValue {
+    uri: String,
+    params: Vec<(Cow<'static, str>, String)>,
+}
+
+impl Link {
+    /// Returns a `Link` header with no links.
+    pub fn new() -> Link {
+        Link::default()
+    }
+
+    /// Adds a link to `uri` with the relation type `rel`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Link;
+    ///
+    /// let link = Link::new().link("https://rocket.rs", "related");
+    /// assert_eq!(link.to_string(), "<https://rocket.rs>; rel=related");
+    /// ```
+    pub fn link<U: fmt::Display, R: fmt::Display>(mut self, uri: U, rel: R) -> Self {
+        let params = vec![("rel".into(), rel.to_string())];
+        self.links.push(LinkValue { uri: uri.to_string(), params });
+        self
+    }
+
+    /// Adds the parameter `name` with `value` to the most recently added link.
+    /// Does nothing if no link has been added.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Link;
+    ///
+    /// let link = Link::new().link("/a.css", "alternate stylesheet").param("title", "Dark");
+    /// assert_eq!(link.to_string(), r#"</a.css>; rel="alternate stylesheet"; title=Dark"#);
+    /// ```
+    pub fn param<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<Cow<'static, str>>, V: fmt::Display
+    {
+        if let Some(link) = self.links.last_mut() {
+            link.params.push((name.into(), value.to_string()));
+        }
+
+        self
+    }
+
+    /// Adds a link to the next page: `rel=next`.
+    pub fn next<U: fmt::Display>(self, uri: U) -> Self {
+        self.link(uri, "next")
+    }
+
+    /// Adds a link to the previous page: `rel=prev`.
+    pub fn prev<U: fmt::Display>(self, uri: U) -> Self {
+        self.link(uri, "prev")
+    }
+
+    /// Adds a link to the first page: `rel=first`.
+    pub fn first<U: fmt::Display>(self, uri: U) -> Self {
+        self.link(uri, "first")
+    }
+
+    /// Adds a link to the last page: `rel=last`.
+    pub fn last<U: fmt::Display>(self, uri: U) -> Self {
+        self.link(uri, "last")
+    }
+
+    /// Adds the `first`, `prev`, `next`, and `last` links for `page` of a
+    /// collection whose pages are numbered `1` through `last`, as applicable.
+    /// The URI of each page is produced by `uri`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Link;
+    ///
+    /// let link = Link::new().paginate(1, 3, |p| format!("/items?page={}", p));
+    /// assert_eq!(link.to_string(), "</items?page=2>; rel=next, </items?page=3>; rel=last");
+    ///
+    /// let link = Link::new().paginate(3, 3, |p| format!("/items?page={}", p));
+    /// assert_eq!(link.to_string(), "</items?page=1>; rel=first, </items?page=2>; rel=prev");
+    /// ```
+    pub fn paginate<U, F>(mut self, page: u64, last: u64, uri: F) -> Self
+        where U: fmt::Display, F: Fn(u64) -> U
+    {
+        if page > 1 {
+            self = self.first(uri(1)).prev(uri(page - 1));
+        }
+
+        if page < last {
+            self = self.next(uri(page + 1)).last(uri(last));
+        }
+
+        self
+    }
+
+    /// Adds a `preload` link to `uri` for a resource of the type `kind`, such
+    /// as `style`, `script`, or `font`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::Link;
+    ///
+    /// let link = Link::new().preload("/app.css", "style");
+    /// assert_eq!(link.to_string(), "</app.css>; rel=preload; as=style");
+    /// ```
+    pub fn preload<U: fmt::Display>(self, uri: U, kind: &str) -> Self {
+        self.link(uri, "preload").param("as", kind)
+    }
+
+    /// Returns `true` if there are no links in `self`.
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+/// Writes `value` as a `token` if it is one or as a `quoted-string`.
+pub(crate) fn write_token_or_quoted(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    if typed::is_token(value) {
+        return f.write_str(value);
+    }
+
+    f.write_str("\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+
+        write!(f, "{}", c)?;
+    }
+
+    f.write_str("\"")
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, link) in self.links.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "<{}>", link.uri)?;
+            for (name, value) in &link.params {
+                write!(f, "; {}=", name)?;
+                write_token_or_quoted(f, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Link> for Header<'static> {
+    fn from(link: Link) -> Self {
+        Header::new("Link", link.to_string())
+    }
+}
@@ -5,11 +5,31 @@ mod content_type;
 mod accept;
 mod header;
 mod proxy_proto;
+mod typed;
+mod accept_language;
+mod authorization;
+mod content_disposition;
+mod forwarded;
+mod prefer;
+mod link;
+mod retry_after;
+mod deprecation;
+mod last_modified;
 
 pub use self::content_type::ContentType;
 pub use self::accept::{Accept, QMediaType};
 pub use self::media_type::MediaType;
 pub use self::header::{Header, HeaderMap};
 pub use self::proxy_proto::ProxyProto;
+pub use self::typed::{TypedHeader, HeaderError};
+pub use self::accept_language::{AcceptLanguage, QLanguage};
+pub use self::authorization::Authorization;
+pub use self::content_disposition::ContentDisposition;
+pub use self::forwarded::{Forwarded, ForwardedElement};
+pub use self::prefer::{Prefer, Preference};
+pub use self::link::Link;
+pub use self::retry_after::RetryAfter;
+pub use self::deprecation::{Deprecation, Sunset};
+pub use self::last_modified::LastModified;
 
 pub(crate) use self::media_type::Source;
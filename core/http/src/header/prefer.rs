@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use uncased::UncasedStr;
+
+use crate::header::typed::{self, TypedHeader, HeaderError};
+
+/// The HTTP `Prefer` header as defined in [RFC 7240].
+///
+/// A `Prefer` header is a list of [`Preference`]s, each with an optional
+/// value and parameters. Accessors are provided for the preferences defined
+/// by RFC 7240. As required by the RFC, only the first occurrence of any
+/// preference is considered.
+///
+/// [RFC 7240]: https://datatracker.ietf.org/doc/html/rfc7240
+///
+/// # Usage
+///
+/// `Prefer` is a [request guard]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{Prefer, Status};
+///
+/// #[post("/")]
+/// fn create(prefer: Option<Prefer<'_>>) -> Result<&'static str, Status> {
+///     match prefer.as_ref().and_then(|p| p.returns()) {
+///         Some("minimal") => Err(Status::NoContent),
+///         _ => Ok("created"),
+///     }
+/// }
+/// ```
+///
+/// [request guard]: ../../rocket/request/trait.FromRequest.html#provided-implementations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefer<'a>(Vec<Preference<'a>>);
+
+/// A single preference in a [`Prefer`] header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preference<'a> {
+    name: &'a UncasedStr,
+    value: Option<Cow<'a, str>>,
+    params: Vec<(&'a UncasedStr, Option<Cow<'a, str>>)>,
+}
+
+impl<'a> Prefer<'a> {
+    /// Returns an iterator over the preferences in `self` in header order.
+    pub fn iter(&self) -> impl Iterator<Item = &Preference<'a>> + '_ {
+        self.0.iter()
+    }
+
+    /// Returns the first preference named `name`, matched case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Prefer, TypedHeader};
+    ///
+    /// let prefer = Prefer::parse("foo; bar=\"baz\", Foo=2").unwrap();
+    /// let foo = prefer.get("FOO").unwrap();
+    /// assert_eq!(foo.value(), None);
+    /// assert_eq!(foo.param("bar"), Some(Some("baz")));
+    /// assert!(prefer.get("bar").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&Preference<'a>> {
+        self.iter().find(|p| p.name == name)
+    }
+
+    /// The value of the `return` preference: `minimal` or `representation`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Prefer, TypedHeader};
+    ///
+    /// let prefer = Prefer::parse("return=minimal").unwrap();
+    /// assert_eq!(prefer.returns(), Some("minimal"));
+    /// ```
+    pub fn returns(&self) -> Option<&str> {
+        self.get("return")?.value()
+    }
+
+    /// Returns `true` if the `respond-async` preference is present.
+    pub fn respond_async(&self) -> bool {
+        self.get("respond-async").is_some()
+    }
+
+    /// The value of the `wait` preference in seconds, if it is valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Prefer, TypedHeader};
+    ///
+    /// let prefer = Prefer::parse("respond-async, wait=10").unwrap();
+    /// assert!(prefer.respond_async());
+    /// assert_eq!(prefer.wait(), Some(10));
+    /// ```
+    pub fn wait(&self) -> Option<u64> {
+        self.get("wait")?.value()?.parse().ok()
+    }
+
+    /// The value of the `handling` preference: `strict` or `lenient`.
+    pub fn handling(&self) -> Option<&str> {
+        self.get("handling")?.value()
+    }
+}
+
+impl Preference<'_> {
+    /// The name of the preference.
+    pub fn name(&self) -> &UncasedStr {
+        self.name
+    }
+
+    /// The value of the preference, if any.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns `Some(value)` if the preference has a parameter named `name`,
+    /// matched case-insensitively, and `None` otherwise.
+    pub fn param(&self, name: &str) -> Option<Option<&str>> {
+        self.params.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_deref())
+    }
+}
+
+fn parse<'v>(value: &'v str) -> Result<Vec<Preference<'v>>, HeaderError> {
+    let malformed = |reason| HeaderError::Malformed { name: Prefer::NAME, reason };
+    let param = |string: &'v str| match typed::param(string) {
+        Some((name, value)) => Ok((UncasedStr::new(name), value)),
+        None => Err(malformed("invalid preference")),
+    };
+
+    let mut preferences = vec![];
+    for element in typed::split(value, ',') {
+        let mut parts = typed::split(element, ';');
+        let (name, value) = param(parts.next().unwrap_or_default())?;
+        let params = parts.map(&param).collect::<Result<_, _>>()?;
+        preferences.push(Preference { name, value, params });
+    }
+
+    Ok(preferences)
+}
+
+impl<'a> TypedHeader<'a> for Prefer<'a> {
+    const NAME: &'static str = "Prefer";
+
+    fn parse(value: &'a str) -> Result<Self, HeaderError> {
+        Self::from_values(std::iter::once(value))
+    }
+
+    fn from_values<I: Iterator<Item = &'a str>>(values: I) -> Result<Self, HeaderError> {
+        typed::parse_list(Self::NAME, values, parse).map(Prefer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let prefer = Prefer::from_values(["return=representation", "wait=x"].into_iter()).unwrap();
+        assert_eq!(prefer.returns(), Some("representation"));
+        assert_eq!(prefer.wait(), None);
+        assert!(!prefer.respond_async());
+
+        let prefer = Prefer::parse("handling=lenient; a; b=\"c, d\", handling=strict").unwrap();
+        assert_eq!(prefer.handling(), Some("lenient"));
+        assert_eq!(prefer.get("handling").unwrap().param("b"), Some(Some("c, d")));
+        assert_eq!(prefer.get("handling").unwrap().param("A"), Some(None));
+        assert_eq!(prefer.iter().count(), 2);
+
+        assert!(Prefer::parse("a b").is_err());
+        assert!(Prefer::parse("a; =b").is_err());
+    }
+}
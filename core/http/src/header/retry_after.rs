@@ -0,0 +1,118 @@
+use std::fmt;
+
+use time::{macros::format_description, format_description::FormatItem};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::Header;
+
+/// The IMF-fixdate format of an HTTP-date as defined in [RFC 9110].
+///
+/// [RFC 9110]: https://datatracker.ietf.org/doc/html/rfc9110#section-5.6.7
+pub(crate) static HTTP_DATE_FMT: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT"
+);
+
+/// Formats `date` as an HTTP-date after converting it to UTC.
+pub(crate) fn http_date(date: OffsetDateTime) -> String {
+    date.to_offset(UtcOffset::UTC)
+        .format(HTTP_DATE_FMT)
+        .expect("HTTP-date formatting is infallible")
+}
+
+/// The HTTP `Retry-After` header.
+///
+/// A `Retry-After` header indicates how long a client should wait before
+/// making a follow-up request, either as a number of seconds, created with
+/// [`RetryAfter::seconds()`], or as a date, created with
+/// [`RetryAfter::date()`]. It is typically sent with a `503 Service
+/// Unavailable`, `429 Too Many Requests`, or `3xx` response.
+///
+/// # Header
+///
+/// `RetryAfter` implements `Into<Header>`, so it can be used in any context
+/// where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{RetryAfter, Status};
+/// use rocket::response::{status, WithHeader};
+///
+/// #[get("/")]
+/// fn busy() -> WithHeader<RetryAfter, status::Custom<&'static str>> {
+///     WithHeader(RetryAfter::seconds(120), status::Custom(Status::ServiceUnavailable, "busy"))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// A delay in seconds.
+    Seconds(u64),
+    /// A point in time.
+    Date(OffsetDateTime),
+}
+
+impl RetryAfter {
+    /// A `Retry-After` header with a delay of `seconds` seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::RetryAfter;
+    ///
+    /// assert_eq!(RetryAfter::seconds(30).to_string(), "30");
+    /// ```
+    pub fn seconds(seconds: u64) -> RetryAfter {
+        RetryAfter::Seconds(seconds)
+    }
+
+    /// A `Retry-After` header with the date `date`. The date is converted to
+    /// UTC and formatted as an HTTP-date.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::RetryAfter;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let date = RetryAfter::date(datetime!(2015-10-21 09:28:00 +2));
+    /// assert_eq!(date.to_string(), "Wed, 21 Oct 2015 07:28:00 GMT");
+    /// ```
+    pub fn date<T: Into<OffsetDateTime>>(date: T) -> RetryAfter {
+        RetryAfter::Date(date.into())
+    }
+}
+
+impl fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryAfter::Seconds(seconds) => write!(f, "{}", seconds),
+            RetryAfter::Date(date) => f.write_str(&http_date(*date)),
+        }
+    }
+}
+
+impl From<RetryAfter> for Header<'static> {
+    fn from(retry_after: RetryAfter) -> Self {
+        Header::new("Retry-After", retry_after.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_format() {
+        let header: Header<'_> = RetryAfter::seconds(0).into();
+        assert_eq!(header.name(), "Retry-After");
+        assert_eq!(header.value(), "0");
+
+        let date = RetryAfter::date(datetime!(1994-11-06 08:49:37 UTC));
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        let date = RetryAfter::date(datetime!(2000-01-01 00:30:00 +1));
+        assert_eq!(date.to_string(), "Fri, 31 Dec 1999 23:30:00 GMT");
+    }
+}
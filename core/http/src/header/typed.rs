@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// A header parsed from its raw value(s).
+///
+/// Typed headers are parsed from the values of the header named
+/// [`TypedHeader::NAME`], usually via [`HeaderMap::get_typed()`]. In Rocket,
+/// every typed header is also a request guard which fails with a
+/// [`HeaderError`] if the header is missing or malformed.
+///
+/// [`HeaderMap::get_typed()`]: crate::HeaderMap::get_typed()
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{HeaderMap, AcceptLanguage, HeaderError};
+///
+/// let mut map = HeaderMap::new();
+/// map.add_raw("Accept-Language", "fr;q=0.8, en-US");
+///
+/// let languages = map.get_typed::<AcceptLanguage>().unwrap();
+/// assert_eq!(languages.preferred(), Some("en-US"));
+///
+/// let missing = map.get_typed::<rocket::http::Prefer>().unwrap_err();
+/// assert_eq!(missing, HeaderError::Missing("Prefer"));
+/// ```
+pub trait TypedHeader<'a>: Sized {
+    /// The name of the header.
+    const NAME: &'static str;
+
+    /// Parses a single header `value`.
+    fn parse(value: &'a str) -> Result<Self, HeaderError>;
+
+    /// Parses `Self` from all of the header's `values`, in order.
+    ///
+    /// The default implementation parses the first value and ignores the rest.
+    /// Headers whose values are comma-separated lists parse and combine every
+    /// value as if they were a single, comma-separated value.
+    fn from_values<I: Iterator<Item = &'a str>>(mut values: I) -> Result<Self, HeaderError> {
+        match values.next() {
+            Some(value) => Self::parse(value),
+            None => Err(HeaderError::Missing(Self::NAME)),
+        }
+    }
+}
+
+/// An error parsing a [`TypedHeader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderError {
+    /// The header with the given name is missing.
+    Missing(&'static str),
+    /// The header is present, but its value is malformed.
+    Malformed {
+        /// The name of the header.
+        name: &'static str,
+        /// Why the value is malformed.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::Missing(name) => write!(f, "missing `{}` header", name),
+            HeaderError::Malformed { name, reason } => {
+                write!(f, "malformed `{}` header: {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeaderError { }
+
+/// Parses every value in `values` of the comma-separated list header named
+/// `name` via `parse` and combines the resulting elements.
+pub(crate) fn parse_list<'a, T, I>(
+    name: &'static str,
+    values: I,
+    parse: fn(&'a str) -> Result<Vec<T>, HeaderError>,
+) -> Result<Vec<T>, HeaderError>
+    where I: Iterator<Item = &'a str>
+{
+    let mut values = values.peekable();
+    if values.peek().is_none() {
+        return Err(HeaderError::Missing(name));
+    }
+
+    let mut items = vec![];
+    for value in values {
+        items.extend(parse(value)?);
+    }
+
+    Ok(items)
+}
+
+/// Returns `true` if `string` is a non-empty RFC 9110 `token`.
+pub(crate) fn is_token(string: &str) -> bool {
+    crate::Header::is_valid_name(string)
+}
+
+/// Splits `string` on `sep` where `sep` doesn't appear in a quoted string.
+/// Each piece is trimmed and empty pieces are skipped.
+pub(crate) fn split(string: &str, sep: char) -> impl Iterator<Item = &str> {
+    let (mut quoted, mut escaped) = (false, false);
+    string.split(move |c| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == sep && !quoted => return true,
+            _ => { }
+        }
+
+        false
+    })
+    .map(|piece| piece.trim())
+    .filter(|piece| !piece.is_empty())
+}
+
+/// Parses `string` as a `token` or a `quoted-string`, returning the unescaped
+/// contents of the latter.
+pub(crate) fn unquote(string: &str) -> Option<Cow<'_, str>> {
+    let Some(quoted) = string.strip_prefix('"') else {
+        return is_token(string).then_some(Cow::Borrowed(string));
+    };
+
+    let inner = quoted.strip_suffix('"')?;
+    if !inner.contains('\\') {
+        return (!inner.contains('"')).then_some(Cow::Borrowed(inner));
+    }
+
+    let (mut unescaped, mut chars) = (String::with_capacity(inner.len()), inner.chars());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.push(chars.next()?),
+            '"' => return None,
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(Cow::Owned(unescaped))
+}
+
+/// Parses a `name[=value]` parameter. The name must be a `token` while the
+/// value, if any, may be a `token` or a `quoted-string`.
+pub(crate) fn param(string: &str) -> Option<(&str, Option<Cow<'_, str>>)> {
+    let (name, value) = match string.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(unquote(value.trim())?)),
+        None => (string.trim(), None),
+    };
+
+    is_token(name).then_some((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let pieces: Vec<_> = split(r#"a, "b,c" ,, d="e\",f""#, ',').collect();
+        assert_eq!(pieces, ["a", r#""b,c""#, r#"d="e\",f""#]);
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote("token").unwrap(), "token");
+        assert_eq!(unquote(r#""a b""#).unwrap(), "a b");
+        assert_eq!(unquote(r#""a \"b\"""#).unwrap(), "a \"b\"");
+        assert!(unquote("a b").is_none());
+        assert!(unquote(r#""a"#).is_none());
+        assert!(unquote(r#""a"b""#).is_none());
+        assert!(unquote("").is_none());
+    }
+
+    #[test]
+    fn test_param() {
+        assert_eq!(param("a=b").unwrap(), ("a", Some("b".into())));
+        assert_eq!(param(" a = \"b c\" ").unwrap(), ("a", Some("b c".into())));
+        assert_eq!(param("a").unwrap(), ("a", None));
+        assert!(param("a b=c").is_none());
+        assert!(param("a=").is_none());
+    }
+}
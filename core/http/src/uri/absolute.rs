@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::ext::IntoOwned;
 use crate::parse::{Extent, IndexedStr};
-use crate::uri::{Authority, Path, Query, Data, Error, as_utf8_unchecked, fmt};
+use crate::uri::{Authority, Path, Query, QueryMap, Data, Error, as_utf8_unchecked, fmt};
 
 /// A URI with a scheme, authority, path, and query.
 ///
@@ -230,6 +230,44 @@ impl<'a> Absolute<'a> {
         self.query.as_ref().map(|data| Query { source: &self.source, data })
     }
 
+    /// Returns the decoded parameters of the query part of this URI as a
+    /// [`QueryMap`], which is empty if there is no query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let uri = uri!("https://rocket.rs/a?a=1&b=x%20y&a=2");
+    /// let query = uri.query_map();
+    /// assert_eq!(query.get_all("a").collect::<Vec<_>>(), ["1", "2"]);
+    /// assert_eq!(query.get("b"), Some("x y"));
+    ///
+    /// assert!(uri!("https://rocket.rs/a").query_map().is_empty());
+    /// ```
+    pub fn query_map(&self) -> QueryMap {
+        self.query().map(QueryMap::from).unwrap_or_default()
+    }
+
+    /// Sets the query part of this URI to the percent-encoded parameters in
+    /// `query`, removing the query entirely if `query` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut uri = uri!("https://rocket.rs/a?page=1&q=rust");
+    /// let mut query = uri.query_map();
+    /// query.set("page", 2).append("q", "web dev");
+    /// uri.set_query_map(&query);
+    /// assert_eq!(uri.query().unwrap(), "page=2&q=rust&q=web%20dev");
+    ///
+    /// uri.set_query_map(&Default::default());
+    /// assert!(uri.query().is_none());
+    /// ```
+    pub fn set_query_map(&mut self, query: &QueryMap) {
+        self.set_query(query.to_query_string().map(Cow::Owned));
+    }
+
     /// Removes the query part of this URI, if there is any.
     ///
     /// # Example
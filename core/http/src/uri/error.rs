@@ -40,3 +40,33 @@ impl fmt::Display for PathError {
 }
 
 impl std::error::Error for PathError { }
+
+/// An error parsing a [`Glob`](crate::uri::Glob) pattern.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GlobError {
+    /// The pattern was empty.
+    Empty,
+    /// A character class starting at the wrapped index was never closed.
+    Unclosed(usize),
+    /// The character class starting at the wrapped index was empty.
+    EmptyClass(usize),
+    /// The character range starting at the wrapped index was reversed.
+    BadRange(usize),
+    /// The pattern contained the wrapped character, which may not appear in
+    /// a URI segment.
+    BadChar(char),
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::Empty => write!(f, "empty pattern"),
+            GlobError::Unclosed(i) => write!(f, "unclosed character class at {i}"),
+            GlobError::EmptyClass(i) => write!(f, "empty character class at {i}"),
+            GlobError::BadRange(i) => write!(f, "reversed character range at {i}"),
+            GlobError::BadChar(c) => write!(f, "invalid character: {c:?}"),
+        }
+    }
+}
+
+impl std::error::Error for GlobError { }
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use crate::uri::error::GlobError;
+
+/// A pattern constraining the value of a dynamic route parameter.
+///
+/// A `Glob` is declared after a parameter's name, separated by a `:`, as in
+/// `<file: *.md>` or `<id: [0-9]+>`. A request matches a route only if the
+/// value of each constrained parameter matches its pattern. For a segment
+/// parameter (`<a: ..>`), the value is the percent-decoded segment. For a
+/// trailing parameter (`<a..: ..>`), it is the decoded remaining segments
+/// joined by `/`.
+///
+/// # Syntax
+///
+/// A pattern must match the value in its entirety. Patterns are composed of:
+///
+///   * `*`: matches any sequence of characters, including an empty one.
+///   * `[set]`: matches exactly one character in `set`, where `set` is a
+///     sequence of characters and ranges of characters, like `a-z`. If `set`
+///     starts with `!`, matches exactly one character _not_ in `set`.
+///   * `[set]+`: matches one or more characters in `set`.
+///   * Any other character matches itself.
+///
+/// Patterns cannot contain `/`, `?`, `<`, or `>`, and sets cannot contain `]`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::http::uri::Glob;
+///
+/// let glob = Glob::parse("*.md").unwrap();
+/// assert!(glob.matches("README.md"));
+/// assert!(glob.matches("docs/guide.md"));
+/// assert!(!glob.matches("README.txt"));
+///
+/// let glob = Glob::parse("v[0-9]+").unwrap();
+/// assert!(glob.matches("v1"));
+/// assert!(glob.matches("v42"));
+/// assert!(!glob.matches("v"));
+/// assert!(!glob.matches("v1a"));
+///
+/// let glob = Glob::parse("[!.]*").unwrap();
+/// assert!(glob.matches("visible"));
+/// assert!(!glob.matches(".hidden"));
+///
+/// assert!(Glob::parse("[a-").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Glob {
+    source: String,
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    matcher: Matcher,
+    /// Whether the token matches zero or more characters instead of one.
+    repeat: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Any,
+    Char(char),
+    Set { ranges: Vec<(char, char)>, negated: bool },
+}
+
+impl Matcher {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Char(expected) => c == *expected,
+            Matcher::Set { ranges, negated } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+impl Glob {
+    /// Parses `pattern` into a `Glob`. See [Syntax](#syntax).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::{Glob, error::GlobError};
+    ///
+    /// assert!(Glob::parse("[a-z]+.txt").is_ok());
+    /// assert_eq!(Glob::parse("").unwrap_err(), GlobError::Empty);
+    /// assert_eq!(Glob::parse("[z-a]").unwrap_err(), GlobError::BadRange(1));
+    /// assert_eq!(Glob::parse("a/b").unwrap_err(), GlobError::BadChar('/'));
+    /// ```
+    pub fn parse(pattern: &str) -> Result<Glob, GlobError> {
+        if pattern.is_empty() {
+            return Err(GlobError::Empty);
+        }
+
+        let mut tokens = vec![];
+        let mut chars = pattern.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let matcher = match c {
+                '/' | '?' | '<' | '>' => return Err(GlobError::BadChar(c)),
+                '*' => {
+                    tokens.push(Token { matcher: Matcher::Any, repeat: true });
+                    continue;
+                }
+                '[' => {
+                    let negated = chars.next_if(|&(_, c)| c == '!').is_some();
+                    let mut ranges = vec![];
+                    loop {
+                        let (j, lo) = chars.next().ok_or(GlobError::Unclosed(i))?;
+                        match lo {
+                            ']' if ranges.is_empty() => return Err(GlobError::EmptyClass(i)),
+                            ']' => break,
+                            '/' | '?' | '<' | '>' => return Err(GlobError::BadChar(lo)),
+                            _ => {}
+                        }
+
+                        let is_range = chars.peek().is_some_and(|&(_, c)| c == '-')
+                            && chars.clone().nth(1).is_some_and(|(_, c)| c != ']');
+
+                        if !is_range {
+                            ranges.push((lo, lo));
+                            continue;
+                        }
+
+                        chars.next();
+                        let (_, hi) = chars.next().ok_or(GlobError::Unclosed(i))?;
+                        if matches!(hi, '/' | '?' | '<' | '>') {
+                            return Err(GlobError::BadChar(hi));
+                        } else if hi < lo {
+                            return Err(GlobError::BadRange(j));
+                        }
+
+                        ranges.push((lo, hi));
+                    }
+
+                    Matcher::Set { ranges, negated }
+                }
+                c => Matcher::Char(c),
+            };
+
+            if let Matcher::Set { .. } = matcher {
+                if chars.next_if(|&(_, c)| c == '+').is_some() {
+                    tokens.push(Token { matcher: matcher.clone(), repeat: false });
+                    tokens.push(Token { matcher, repeat: true });
+                    continue;
+                }
+            }
+
+            tokens.push(Token { matcher, repeat: false });
+        }
+
+        Ok(Glob { source: pattern.to_string(), tokens })
+    }
+
+    /// Returns `true` if `value`, in its entirety, matches `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Glob;
+    ///
+    /// let glob = Glob::parse("*-[0-9]+").unwrap();
+    /// assert!(glob.matches("release-2024"));
+    /// assert!(glob.matches("-1"));
+    /// assert!(!glob.matches("release-"));
+    /// ```
+    pub fn matches(&self, value: &str) -> bool {
+        // Simulates all parses at once, so matching takes linear time in
+        // `value` for any pattern. `states[i]` means "matched up to token i".
+        let n = self.tokens.len();
+        let mut states = vec![false; n + 1];
+        let mut next = vec![false; n + 1];
+        states[0] = true;
+        self.close(&mut states);
+
+        for c in value.chars() {
+            next.iter_mut().for_each(|s| *s = false);
+            for (i, token) in self.tokens.iter().enumerate() {
+                if states[i] && token.matcher.matches(c) {
+                    next[if token.repeat { i } else { i + 1 }] = true;
+                }
+            }
+
+            std::mem::swap(&mut states, &mut next);
+            self.close(&mut states);
+            if !states.contains(&true) {
+                return false;
+            }
+        }
+
+        states[n]
+    }
+
+    /// Marks states reachable without consuming input, i.e, by skipping
+    /// repeated tokens, as reached.
+    fn close(&self, states: &mut [bool]) {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if states[i] && token.repeat {
+                states[i + 1] = true;
+            }
+        }
+    }
+
+    /// Returns the pattern `self` was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Glob;
+    ///
+    /// assert_eq!(Glob::parse("*.md").unwrap().as_str(), "*.md");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for Glob {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Glob { }
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glob;
+
+    #[track_caller]
+    fn matches(pattern: &str, value: &str) -> bool {
+        Glob::parse(pattern).unwrap().matches(value)
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(matches("abc", "abc"));
+        assert!(!matches("abc", "abcd"));
+        assert!(!matches("abc", "ab"));
+        assert!(matches("*", ""));
+        assert!(matches("**", "anything"));
+        assert!(matches("a*c", "ac"));
+        assert!(matches("a*c", "abbbc"));
+        assert!(!matches("a*c", "abbbd"));
+        assert!(matches("*a*a*a*", "aaa"));
+        assert!(!matches("*a*a*a*", "aa"));
+        assert!(matches("[abc]", "b"));
+        assert!(!matches("[abc]", "d"));
+        assert!(!matches("[abc]", "ab"));
+        assert!(matches("[a-c]+", "abcabc"));
+        assert!(!matches("[a-c]+", ""));
+        assert!(matches("[!a-c]", "d"));
+        assert!(!matches("[!a-c]", "a"));
+        assert!(matches("[-a]+", "-a-"));
+        assert!(matches("[a-]+", "-a-"));
+        assert!(matches("[0-9a-f]+.[a-z]*", "deadbeef.js"));
+        assert!(matches("[0-9a-f]+.[a-z]*", "deadbeef."));
+        assert!(!matches("[0-9a-f]+.[a-z]*", "deadbeef.JS"));
+        assert!(matches("é*", "étoile"));
+        assert!(matches("a+", "a+"));
+    }
+
+    #[test]
+    fn glob_errors() {
+        use crate::uri::error::GlobError::*;
+
+        assert_eq!(Glob::parse("").unwrap_err(), Empty);
+        assert_eq!(Glob::parse("[").unwrap_err(), Unclosed(0));
+        assert_eq!(Glob::parse("a[bc").unwrap_err(), Unclosed(1));
+        assert_eq!(Glob::parse("[a-").unwrap_err(), Unclosed(0));
+        assert_eq!(Glob::parse("[]").unwrap_err(), EmptyClass(0));
+        assert_eq!(Glob::parse("[!]").unwrap_err(), EmptyClass(0));
+        assert_eq!(Glob::parse("x[b-a]").unwrap_err(), BadRange(2));
+        assert_eq!(Glob::parse("a?").unwrap_err(), BadChar('?'));
+        assert_eq!(Glob::parse("[/]").unwrap_err(), BadChar('/'));
+    }
+}
@@ -10,6 +10,8 @@ mod segments;
 mod path_query;
 mod asterisk;
 mod host;
+mod query_map;
+mod glob;
 
 pub mod error;
 pub mod fmt;
@@ -26,3 +28,5 @@ pub use self::reference::*;
 pub use self::path_query::*;
 pub use self::asterisk::*;
 pub use self::host::*;
+pub use self::query_map::*;
+pub use self::glob::*;
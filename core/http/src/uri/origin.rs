@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::ext::IntoOwned;
 use crate::parse::{Extent, IndexedStr, uri::tables::is_pchar};
-use crate::uri::{Error, Path, Query, Data, as_utf8_unchecked, fmt};
+use crate::uri::{Error, Path, Query, QueryMap, Data, as_utf8_unchecked, fmt};
 use crate::{RawStr, RawStrBuf};
 
 /// A URI with an absolute path and optional query: `/path?query`.
@@ -297,6 +297,44 @@ impl<'a> Origin<'a> {
         self.query.as_ref().map(|data| Query { source: &self.source, data })
     }
 
+    /// Returns the decoded parameters of the query part of this URI as a
+    /// [`QueryMap`], which is empty if there is no query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let uri = uri!("/a/b?a=1&b=x%20y&a=2");
+    /// let query = uri.query_map();
+    /// assert_eq!(query.get_all("a").collect::<Vec<_>>(), ["1", "2"]);
+    /// assert_eq!(query.get("b"), Some("x y"));
+    ///
+    /// assert!(uri!("/a/b").query_map().is_empty());
+    /// ```
+    pub fn query_map(&self) -> QueryMap {
+        self.query().map(QueryMap::from).unwrap_or_default()
+    }
+
+    /// Sets the query part of this URI to the percent-encoded parameters in
+    /// `query`, removing the query entirely if `query` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut uri = uri!("/a/b?page=1&q=rust");
+    /// let mut query = uri.query_map();
+    /// query.set("page", 2).append("q", "web dev");
+    /// uri.set_query_map(&query);
+    /// assert_eq!(uri.query().unwrap(), "page=2&q=rust&q=web%20dev");
+    ///
+    /// uri.set_query_map(&Default::default());
+    /// assert!(uri.query().is_none());
+    /// ```
+    pub fn set_query_map(&mut self, query: &QueryMap) {
+        self.set_query(query.to_query_string().map(Cow::Owned));
+    }
+
     /// Applies the function `f` to the internal `path` and returns a new
     /// `Origin` with the new path. If the path returned from `f` is invalid,
     /// returns `None`. Otherwise, returns `Some`, even if the new path is
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::RawStr;
+use crate::uri::Query;
+
+/// An ordered multimap of decoded query parameters.
+///
+/// A `QueryMap` is a modifiable view of a URI's query. It is retrieved via
+/// methods such as [`Origin::query_map()`](crate::uri::Origin::query_map()),
+/// which decode each `name=value` pair, and written back via methods such as
+/// [`Origin::set_query_map()`](crate::uri::Origin::set_query_map()), which
+/// percent-encode each pair. Parameters retain their relative order unless
+/// explicitly [sorted](QueryMap::sort()). This makes it possible to build
+/// URIs, for instance for redirects, without manipulating query strings.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// let mut uri = uri!("/search?q=rocket+launch&page=2&utm_source=mail");
+///
+/// let mut query = uri.query_map();
+/// assert_eq!(query.get("q"), Some("rocket launch"));
+/// assert_eq!(query.get_as::<usize>("page"), Some(Ok(2)));
+///
+/// query.set("page", 3).remove("utm_source");
+/// query.append("tag", "a&b");
+/// uri.set_query_map(&query);
+/// assert_eq!(uri, "/search?q=rocket%20launch&page=3&tag=a%26b");
+/// ```
+///
+/// # Encoding
+///
+/// Names and values are stored decoded and percent-encoded when displayed.
+/// Parameters with an empty value are displayed as their name alone, so `a=`
+/// is written as `a`. Empty segments, such as those in `a&&b`, are dropped.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct QueryMap {
+    params: Vec<(String, String)>,
+}
+
+impl QueryMap {
+    /// Returns an empty `QueryMap`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::QueryMap;
+    ///
+    /// let query = QueryMap::new();
+    /// assert!(query.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        QueryMap::default()
+    }
+
+    /// Returns the number of parameters, counting repeated names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?a=1&a=2&b").query_map();
+    /// assert_eq!(query.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns `true` if there are no parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// assert!(uri!("/").query_map().is_empty());
+    /// assert!(!uri!("/?a").query_map().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns `true` if there is a parameter named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?debug&page=1").query_map();
+    /// assert!(query.contains("debug"));
+    /// assert!(!query.contains("Debug"));
+    /// ```
+    pub fn contains(&self, name: &str) -> bool {
+        self.params.iter().any(|(n, _)| n == name)
+    }
+
+    /// Returns the value of the first parameter named `name`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?a=1&a=2&b").query_map();
+    /// assert_eq!(query.get("a"), Some("1"));
+    /// assert_eq!(query.get("b"), Some(""));
+    /// assert_eq!(query.get("c"), None);
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// Returns the values of all parameters named `name`, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?a=1&b=0&a=2").query_map();
+    /// let values: Vec<_> = query.get_all("a").collect();
+    /// assert_eq!(values, ["1", "2"]);
+    /// ```
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.params.iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Parses the value of the first parameter named `name` as a `T`. Returns
+    /// `None` if there is no such parameter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?page=2&size=big").query_map();
+    /// assert_eq!(query.get_as::<u8>("page"), Some(Ok(2)));
+    /// assert!(query.get_as::<u8>("size").unwrap().is_err());
+    /// assert!(query.get_as::<u8>("limit").is_none());
+    /// ```
+    pub fn get_as<T: FromStr>(&self, name: &str) -> Option<Result<T, T::Err>> {
+        self.get(name).map(|value| value.parse())
+    }
+
+    /// Appends a parameter named `name` with value `value`, keeping existing
+    /// parameters with the same name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut query = uri!("/?a=1").query_map();
+    /// query.append("a", 2).append("b", true);
+    /// assert_eq!(query.to_string(), "a=1&a=2&b=true");
+    /// ```
+    pub fn append<N, V>(&mut self, name: N, value: V) -> &mut Self
+        where N: Into<String>, V: fmt::Display
+    {
+        self.params.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Sets the value of the parameter named `name` to `value`. If there are
+    /// existing parameters with the name, the first is updated in place and
+    /// the rest are removed. Otherwise, the parameter is appended.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut query = uri!("/?a=1&b=2&a=3").query_map();
+    /// query.set("a", "x").set("c", 4);
+    /// assert_eq!(query.to_string(), "a=x&b=2&c=4");
+    /// ```
+    pub fn set<N, V>(&mut self, name: N, value: V) -> &mut Self
+        where N: Into<String>, V: fmt::Display
+    {
+        let (name, value) = (name.into(), value.to_string());
+        match self.params.iter().position(|(n, _)| *n == name) {
+            Some(i) => {
+                self.params[i].1 = value;
+                let rest = self.params.split_off(i + 1);
+                self.params.extend(rest.into_iter().filter(|(n, _)| *n != name));
+            }
+            None => self.params.push((name, value)),
+        }
+
+        self
+    }
+
+    /// Removes all parameters named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut query = uri!("/?a=1&b=2&a=3").query_map();
+    /// query.remove("a");
+    /// assert_eq!(query.to_string(), "b=2");
+    /// ```
+    pub fn remove(&mut self, name: &str) -> &mut Self {
+        self.params.retain(|(n, _)| n != name);
+        self
+    }
+
+    /// Retains only the parameters for which `f` returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut query = uri!("/?utm_source=a&q=b&utm_medium=c").query_map();
+    /// query.retain(|name, _| !name.starts_with("utm_"));
+    /// assert_eq!(query.to_string(), "q=b");
+    /// ```
+    pub fn retain<F: FnMut(&str, &str) -> bool>(&mut self, mut f: F) -> &mut Self {
+        self.params.retain(|(n, v)| f(n, v));
+        self
+    }
+
+    /// Sorts parameters by name, retaining the relative order of parameters
+    /// with the same name. Useful for producing a canonical query, such as for
+    /// caching or signing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let mut query = uri!("/?b=1&a=2&c&a=1").query_map();
+    /// query.sort();
+    /// assert_eq!(query.to_string(), "a=2&a=1&b=1&c");
+    /// ```
+    pub fn sort(&mut self) -> &mut Self {
+        self.params.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self
+    }
+
+    /// Returns an iterator over the decoded `(name, value)` pairs, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let query = uri!("/?a=1&b=x%20y").query_map();
+    /// let pairs: Vec<_> = query.iter().collect();
+    /// assert_eq!(pairs, [("a", "1"), ("b", "x y")]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    /// Returns the percent-encoded query string for `self`, or `None` if
+    /// `self` is empty.
+    pub(crate) fn to_query_string(&self) -> Option<String> {
+        (!self.is_empty()).then(|| self.to_string())
+    }
+}
+
+impl fmt::Display for QueryMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            if i != 0 {
+                f.write_str("&")?;
+            }
+
+            f.write_str(RawStr::new(name).percent_encode().as_str())?;
+            if !value.is_empty() {
+                f.write_str("=")?;
+                f.write_str(RawStr::new(value).percent_encode().as_str())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Query<'_>> for QueryMap {
+    fn from(query: Query<'_>) -> Self {
+        query.segments().collect()
+    }
+}
+
+impl<N: Into<String>, V: Into<String>> FromIterator<(N, V)> for QueryMap {
+    fn from_iter<I: IntoIterator<Item = (N, V)>>(iter: I) -> Self {
+        let mut map = QueryMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<N: Into<String>, V: Into<String>> Extend<(N, V)> for QueryMap {
+    fn extend<I: IntoIterator<Item = (N, V)>>(&mut self, iter: I) {
+        self.params.extend(iter.into_iter().map(|(n, v)| (n.into(), v.into())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::uri::Origin;
+
+    #[test]
+    fn round_trip() {
+        let uri = Origin::parse("/?a+b%2F=some+one%40gmail.com&&%26%3D2&c=%3D&d=").unwrap();
+        let query = uri.query_map();
+        let pairs: Vec<_> = query.iter().collect();
+        assert_eq!(pairs, [("a b/", "some one@gmail.com"), ("&=2", ""), ("c", "="), ("d", "")]);
+
+        let mut uri = uri.clone();
+        uri.set_query_map(&query);
+        assert_eq!(uri.query().unwrap(), "a%20b%2F=some%20one@gmail.com&%26%3D2&c=%3D&d");
+        assert_eq!(uri.query_map(), query);
+
+        uri.set_query_map(&Default::default());
+        assert!(uri.query().is_none());
+    }
+}
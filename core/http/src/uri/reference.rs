@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use crate::RawStr;
 use crate::ext::IntoOwned;
 use crate::uri::{Authority, Data, Origin, Absolute, Asterisk};
-use crate::uri::{Path, Query, Error, as_utf8_unchecked, fmt};
+use crate::uri::{Path, Query, QueryMap, Error, as_utf8_unchecked, fmt};
 use crate::parse::{Extent, IndexedStr};
 
 /// A URI-reference with optional scheme, authority, relative path, query, and
@@ -281,6 +281,46 @@ impl<'a> Reference<'a> {
         self.query.as_ref().map(|data| Query { source: &self.source, data })
     }
 
+    /// Returns the decoded parameters of the query part of this URI as a
+    /// [`QueryMap`], which is empty if there is no query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Reference;
+    ///
+    /// let uri = Reference::parse("/a?a=1&b=x%20y&a=2#top").unwrap();
+    /// let query = uri.query_map();
+    /// assert_eq!(query.get_all("a").collect::<Vec<_>>(), ["1", "2"]);
+    /// assert_eq!(query.get("b"), Some("x y"));
+    ///
+    /// assert!(Reference::parse("/a#top").unwrap().query_map().is_empty());
+    /// ```
+    pub fn query_map(&self) -> QueryMap {
+        self.query().map(QueryMap::from).unwrap_or_default()
+    }
+
+    /// Sets the query part of this URI to the percent-encoded parameters in
+    /// `query`, removing the query entirely if `query` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::Reference;
+    ///
+    /// let mut uri = Reference::parse("/a?page=1&q=rust#top").unwrap();
+    /// let mut query = uri.query_map();
+    /// query.set("page", 2).append("q", "web dev");
+    /// uri.set_query_map(&query);
+    /// assert_eq!(uri, "/a?page=2&q=rust&q=web%20dev#top");
+    ///
+    /// uri.set_query_map(&Default::default());
+    /// assert_eq!(uri, "/a#top");
+    /// ```
+    pub fn set_query_map(&mut self, query: &QueryMap) {
+        self.query = query.to_query_string().map(|q| Data::new(Cow::Owned(q)));
+    }
+
     /// Returns the fragment part, if any.
     ///
     /// # Example
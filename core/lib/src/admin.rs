@@ -0,0 +1,402 @@
+//! Read-only introspection endpoints for operators.
+//!
+//! The [`Admin`] handler serves a set of read-only JSON endpoints that report
+//! on a running application, forming a small runtime dashboard. Mounted at
+//! `/admin`, the endpoints are:
+//!
+//! | Path              | Reports                                                  |
+//! |-------------------|----------------------------------------------------------|
+//! | `/admin`          | All of the below in one document, keyed by section name. |
+//! | `/admin/routes`   | Mounted routes: name, method, URI, rank, and format.     |
+//! | `/admin/catchers` | Registered catchers: name, code, and base.               |
+//! | `/admin/fairings` | Attached fairings: name and kinds.                       |
+//! | `/admin/config`   | The active configuration, with secrets redacted.         |
+//! | `/admin/state`    | The type names of managed state.                         |
+//! | `/admin/metrics`  | Load and runtime statistics and [custom metrics].        |
+//! | `/admin/errors`   | [Recent server errors], oldest first.                    |
+//! | `/admin/tasks`    | Running [supervised tasks]: id, name, and spawn time.    |
+//!
+//! The data is gathered via [`Rocket::routes()`], [`Rocket::catchers()`],
+//! [`Rocket::fairing_info()`], [`Rocket::figment()`],
+//! [`Rocket::managed_types()`], [`Rocket::load()`],
+//! [`Rocket::runtime_stats()`], [`Rocket::recent_errors()`], and
+//! [`Rocket::tasks()`], which applications can also call directly.
+//!
+//! [custom metrics]: Admin::metrics()
+//! [Recent server errors]: crate::catcher::ErrorSummary
+//! [supervised tasks]: crate::task
+//!
+//! # Authorization
+//!
+//! The endpoints expose details about an application that should not be
+//! public. Every request must be authorized, either by a bearer token, via
+//! [`Admin::token()`], or by a custom check, via [`Admin::authorize()`].
+//! Unauthorized requests fail with `401 Unauthorized`.
+//!
+//! # Redaction
+//!
+//! Configuration values whose key, at any depth, contains `secret`, `password`,
+//! `passwd`, `token`, `key`, `credential`, `url`, or `dsn`, ignoring case, are
+//! replaced with `"[redacted]"`. The last two cover database URLs, which often
+//! embed credentials. Additional terms can be added via [`Admin::redact()`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::admin::Admin;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let token = std::env::var("ADMIN_TOKEN").unwrap_or_default();
+//!     rocket::build().mount("/admin", Admin::token(token))
+//! }
+//! ```
+//!
+//! An operator then queries the endpoints with the token:
+//!
+//! ```sh
+//! curl -H "Authorization: Bearer $ADMIN_TOKEN" http://localhost:8000/admin/routes
+//! ```
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use crate::{Data, Request, Rocket, Orbit, Route};
+use crate::http::{Method, Status};
+use crate::route::{Handler, Outcome};
+use crate::serde::json::{Json, Value, json, serde_json};
+
+type AuthorizeFn = Box<dyn Fn(&Request<'_>) -> bool + Send + Sync + 'static>;
+
+type MetricFn = Box<dyn Fn(&Rocket<Orbit>) -> Value + Send + Sync + 'static>;
+
+/// The terms that, when contained in a configuration key, cause its value to
+/// be redacted.
+const REDACTED_TERMS: &[&str] = &[
+    "secret", "password", "passwd", "token", "key", "credential", "url", "dsn",
+];
+
+/// A handler serving read-only introspection endpoints.
+///
+/// See the [module level docs](self) for details and an example.
+pub struct Admin {
+    authorize: AuthorizeFn,
+    metrics: Vec<(Cow<'static, str>, MetricFn)>,
+    redact: Vec<String>,
+    rank: Option<isize>,
+}
+
+struct Shared {
+    authorize: AuthorizeFn,
+    metrics: Vec<(Cow<'static, str>, MetricFn)>,
+    redact: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Index,
+    Routes,
+    Catchers,
+    Fairings,
+    Config,
+    State,
+    Metrics,
+    Errors,
+    Tasks,
+}
+
+#[derive(Clone)]
+struct Endpoint {
+    shared: Arc<Shared>,
+    section: Section,
+}
+
+impl Admin {
+    fn new(authorize: AuthorizeFn) -> Self {
+        Admin { authorize, metrics: vec![], redact: vec![], rank: None }
+    }
+
+    /// Creates a new `Admin` handler that authorizes requests bearing
+    /// `token`: those with an `Authorization: Bearer {token}` header. Tokens
+    /// are compared in constant time. An empty token authorizes no requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::admin::Admin;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount("/admin", Admin::token("s3cr3t-t0k3n"))
+    /// }
+    /// ```
+    pub fn token<T: Into<String>>(token: T) -> Self {
+        let token = token.into();
+        Admin::new(Box::new(move |req| {
+            let bearer = req.headers()
+                .get_one("Authorization")
+                .and_then(|value| value.strip_prefix("Bearer "));
+
+            let bearer = bearer.unwrap_or_default();
+            !token.is_empty() && eq_ct(bearer.as_bytes(), token.as_bytes())
+        }))
+    }
+
+    /// Creates a new `Admin` handler that authorizes requests for which `f`
+    /// returns `true`. This allows reusing an application's existing
+    /// authentication, for instance, by checking for a user inserted into
+    /// the request's [extensions](crate::Request::extensions()) by a fairing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::admin::Admin;
+    ///
+    /// struct Operator;
+    ///
+    /// let admin = Admin::authorize(|req| req.extensions().contains::<Operator>());
+    /// ```
+    pub fn authorize<F>(f: F) -> Self
+        where F: Fn(&Request<'_>) -> bool + Send + Sync + 'static
+    {
+        Admin::new(Box::new(f))
+    }
+
+    /// Registers a custom metric named `name`, reported by `/metrics` as the
+    /// value returned by `f`. The function `f` is called with the running
+    /// Rocket instance, which provides access to managed state, on every
+    /// request for metrics.
+    ///
+    /// Custom metrics allow reporting the status of application resources,
+    /// such as database connection pools, that Rocket knows nothing about.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::admin::Admin;
+    /// use rocket::serde::json::json;
+    ///
+    /// struct Pool { size: u32, idle: u32 }
+    ///
+    /// let admin = Admin::token("s3cr3t-t0k3n")
+    ///     .metrics("pool", |rocket| match rocket.state::<Pool>() {
+    ///         Some(pool) => json!({ "size": pool.size, "idle": pool.idle }),
+    ///         None => json!(null),
+    ///     });
+    /// ```
+    pub fn metrics<N, F>(mut self, name: N, f: F) -> Self
+        where N: Into<Cow<'static, str>>,
+              F: Fn(&Rocket<Orbit>) -> Value + Send + Sync + 'static
+    {
+        self.metrics.push((name.into(), Box::new(f)));
+        self
+    }
+
+    /// Additionally redacts configuration values whose key contains `term`,
+    /// ignoring case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::admin::Admin;
+    ///
+    /// let admin = Admin::token("s3cr3t-t0k3n").redact("smtp");
+    /// ```
+    pub fn redact<T: Into<String>>(mut self, term: T) -> Self {
+        self.redact.push(term.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Sets the rank of the admin routes to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::admin::Admin;
+    ///
+    /// let admin = Admin::token("s3cr3t-t0k3n").rank(-10);
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+}
+
+impl Shared {
+    fn report(&self, section: Section, rocket: &Rocket<Orbit>) -> Value {
+        match section {
+            Section::Index => json!({
+                "routes": self.report(Section::Routes, rocket),
+                "catchers": self.report(Section::Catchers, rocket),
+                "fairings": self.report(Section::Fairings, rocket),
+                "config": self.report(Section::Config, rocket),
+                "state": self.report(Section::State, rocket),
+                "metrics": self.report(Section::Metrics, rocket),
+                "errors": self.report(Section::Errors, rocket),
+                "tasks": self.report(Section::Tasks, rocket),
+            }),
+            Section::Routes => rocket.routes()
+                .map(|route| json!({
+                    "name": route.name,
+                    "method": route.method.map(|m| m.to_string()),
+                    "uri": route.uri.to_string(),
+                    "rank": route.rank,
+                    "format": route.format.as_ref().map(|f| f.to_string()),
+                }))
+                .collect(),
+            Section::Catchers => rocket.catchers()
+                .map(|catcher| json!({
+                    "name": catcher.name,
+                    "code": catcher.code,
+                    "base": catcher.base().to_string(),
+                }))
+                .collect(),
+            Section::Fairings => rocket.fairing_info()
+                .map(|info| json!({
+                    "name": info.name,
+                    "kinds": info.kind.to_string().split(", ").collect::<Vec<_>>(),
+                }))
+                .collect(),
+            Section::Config => {
+                let mut config = match rocket.figment().extract::<Value>() {
+                    Ok(config) => config,
+                    Err(e) => return json!({ "error": e.to_string() }),
+                };
+
+                redact(&mut config, &self.redact);
+                json!({ "profile": rocket.figment().profile().to_string(), "values": config })
+            }
+            Section::State => rocket.managed_types().collect(),
+            Section::Metrics => {
+                let load = rocket.load();
+                let runtime = rocket.runtime_stats().map(|stats| json!({
+                    "workers": stats.workers,
+                    "alive_tasks": stats.alive_tasks,
+                    "global_queue_depth": stats.global_queue_depth,
+                    "idle_workers": stats.idle_workers,
+                    "blocking_threads": stats.blocking_threads,
+                    "max_blocking_threads": stats.max_blocking_threads,
+                }));
+
+                let custom = self.metrics.iter()
+                    .map(|(name, f)| (name.to_string(), f(rocket)))
+                    .collect::<serde_json::Map<_, _>>();
+
+                json!({
+                    "load": {
+                        "connections": load.connections,
+                        "requests": load.requests,
+                        "queued": load.queued,
+                        "shed": load.shed,
+                        "bytes_read": load.bytes_read,
+                        "bytes_written": load.bytes_written,
+                    },
+                    "runtime": runtime,
+                    "custom": custom,
+                })
+            }
+            Section::Errors => rocket.recent_errors()
+                .into_iter()
+                .map(|error| json!({
+                    "reference": error.reference.to_string(),
+                    "status": error.status.code,
+                    "method": error.method.as_str(),
+                    "path": error.path,
+                    "route": error.route,
+                    "timestamp": error.time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs()),
+                }))
+                .collect(),
+            Section::Tasks => rocket.tasks().running()
+                .into_iter()
+                .map(|task| json!({
+                    "id": task.id,
+                    "name": task.name,
+                    "spawned": task.spawned.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs()),
+                }))
+                .collect(),
+        }
+    }
+}
+
+/// Replaces the values of keys containing a redacted term with a placeholder.
+fn redact(value: &mut Value, extra: &[String]) {
+    match value {
+        Value::Object(map) => for (key, value) in map.iter_mut() {
+            let key = key.to_ascii_lowercase();
+            let sensitive = REDACTED_TERMS.iter().copied()
+                .chain(extra.iter().map(|term| term.as_str()))
+                .any(|term| key.contains(term));
+
+            match sensitive {
+                true => *value = Value::from("[redacted]"),
+                false => redact(value, extra),
+            }
+        },
+        Value::Array(values) => values.iter_mut().for_each(|value| redact(value, extra)),
+        _ => {}
+    }
+}
+
+/// Compares `a` and `b` in time independent of their contents.
+fn eq_ct(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[crate::async_trait]
+impl Handler for Endpoint {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        if !(self.shared.authorize)(req) {
+            return Outcome::Error(Status::Unauthorized);
+        }
+
+        let value = self.shared.report(self.section, req.rocket());
+        Outcome::from(req, Json(value))
+    }
+}
+
+impl From<Admin> for Vec<Route> {
+    fn from(admin: Admin) -> Self {
+        let shared = Arc::new(Shared {
+            authorize: admin.authorize,
+            metrics: admin.metrics,
+            redact: admin.redact,
+        });
+
+        [
+            ("/", "Admin: index", Section::Index),
+            ("/routes", "Admin: routes", Section::Routes),
+            ("/catchers", "Admin: catchers", Section::Catchers),
+            ("/fairings", "Admin: fairings", Section::Fairings),
+            ("/config", "Admin: config", Section::Config),
+            ("/state", "Admin: state", Section::State),
+            ("/metrics", "Admin: metrics", Section::Metrics),
+            ("/errors", "Admin: errors", Section::Errors),
+            ("/tasks", "Admin: tasks", Section::Tasks),
+        ]
+            .into_iter()
+            .map(|(path, name, section)| {
+                let endpoint = Endpoint { shared: shared.clone(), section };
+                let mut route = Route::new(Method::Get, path, endpoint);
+                if let Some(rank) = admin.rank {
+                    route.rank = rank;
+                }
+
+                route.name = Some(name.into());
+                route
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for Admin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Admin")
+            .field("metrics", &self.metrics.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .field("redact", &self.redact)
+            .field("rank", &self.rank)
+            .finish_non_exhaustive()
+    }
+}
@@ -4,9 +4,10 @@ use std::io::Cursor;
 use crate::http::uri::Path;
 use crate::http::ext::IntoOwned;
 use crate::response::Response;
-use crate::request::Request;
-use crate::http::{Status, ContentType, uri};
-use crate::catcher::{Handler, BoxFuture};
+use crate::request::{Request, GuardFailure, GuardKind};
+use crate::http::{Status, StatusClass, ContentType, RawStr, uri};
+use crate::catcher::{Handler, BoxFuture, ErrorReference};
+use crate::i18n::Locale;
 
 /// An error catching route.
 ///
@@ -359,10 +360,10 @@ impl fmt::Debug for Catcher {
 }
 
 macro_rules! html_error_template {
-    ($code:expr, $reason:expr, $description:expr) => (
+    ($lang:expr, $code:expr, $reason:expr, $description:expr) => (
         concat!(
 r#"<!DOCTYPE html>
-<html lang="en">
+<html lang=""#, $lang, r#"">
 <head>
     <meta charset="utf-8">
     <meta name="color-scheme" content="light dark">
@@ -421,7 +422,12 @@ macro_rules! default_handler_fn {
             req: &'r Request<'_>
         ) -> Response<'r> {
             let preferred = req.accept().map(|a| a.preferred());
-            let (mime, text) = if preferred.map_or(false, |a| a.is_json()) {
+            let is_json = preferred.map_or(false, |a| a.is_json());
+            let locale = Locale::of(req);
+            let (mime, text) = if let Some(text) = localized(status, is_json, &locale) {
+                let mime = if is_json { ContentType::JSON } else { ContentType::HTML };
+                (mime, Cow::Owned(text))
+            } else if is_json {
                 let json: Cow<'_, str> = match status.code {
                     $($code => json_error_template!($code, $reason, $description).into(),)*
                     code => format!(json_error_fmt_template!("{}", "Unknown Error",
@@ -431,14 +437,25 @@ macro_rules! default_handler_fn {
                 (ContentType::JSON, json)
             } else {
                 let html: Cow<'_, str> = match status.code {
-                    $($code => html_error_template!($code, $reason, $description).into(),)*
-                    code => format!(html_error_template!("{}", "Unknown Error",
+                    $($code => html_error_template!("en", $code, $reason, $description).into(),)*
+                    code => format!(html_error_template!("en", "{}", "Unknown Error",
                             "An unknown error has occurred."), code, code).into(),
                 };
 
                 (ContentType::HTML, html)
             };
 
+            let text = match req.error_reference() {
+                Some(reference) => with_reference(&mime, &text, reference, &locale).into(),
+                None => text,
+            };
+
+            let failures = req.guard_failures();
+            let text = match status.class() == StatusClass::ClientError && !failures.is_empty() {
+                true => with_failures(&mime, &text, &failures, &locale).into(),
+                false => text,
+            };
+
             let mut r = Response::build().status(status).header(mime).finalize();
             match text {
                 Cow::Owned(v) => r.set_sized_body(v.len(), Cursor::new(v)),
@@ -447,9 +464,132 @@ macro_rules! default_handler_fn {
 
             r
         }
+
+        /// Returns the English reason and description of `code`, and whether
+        /// `code` is known.
+        fn english(code: u16) -> (&'static str, &'static str, bool) {
+            match code {
+                $($code => ($reason, $description, true),)*
+                _ => ("Unknown Error", "An unknown error has occurred.", false),
+            }
+        }
+
+        /// Renders the default error page for `status` in the language of
+        /// `locale` if the language has a catalog. Untranslated messages are
+        /// in English.
+        fn localized(status: Status, is_json: bool, locale: &Locale<'_>) -> Option<String> {
+            let catalog = locale.catalog()?;
+            let (reason, description, known) = english(status.code);
+            let key = |part: &str| match known {
+                true => format!("status.{}.{}", status.code, part),
+                false => format!("status.unknown.{}", part),
+            };
+
+            let reason = catalog.get(&key("reason")).unwrap_or(reason);
+            let description = catalog.get(&key("description")).unwrap_or(description);
+            let page = match is_json {
+                true => format!(json_error_fmt_template!("{}", "{}", "{}"),
+                    status.code, escape_json(reason), escape_json(description)),
+                false => format!(html_error_template!("{}", "{}", "{}", "{}"),
+                    RawStr::new(locale.tag()).html_escape(),
+                    status.code, RawStr::new(reason).html_escape(),
+                    status.code, RawStr::new(reason).html_escape(),
+                    RawStr::new(description).html_escape()),
+            };
+
+            Some(page)
+        }
     )
 }
 
+/// Adds `reference` to the default error page `text` of type `mime`.
+fn with_reference(
+    mime: &ContentType,
+    text: &str,
+    reference: ErrorReference,
+    locale: &Locale<'_>,
+) -> String {
+    if *mime == ContentType::JSON {
+        let (head, tail) = text.rsplit_once("\n  }").expect("JSON template");
+        format!("{head},\n    \"reference\": \"{reference}\"\n  }}{tail}")
+    } else {
+        let label = RawStr::new(locale.get("error.reference").unwrap_or("Reference")).html_escape();
+        let (head, tail) = text.split_once("<hr />").expect("HTML template");
+        format!("{head}<p>{label}: <code>{reference}</code></p>\n        <hr />{tail}")
+    }
+}
+
+/// Adds a list of the guard `failures` to the default error page `text` of
+/// type `mime`.
+fn with_failures(
+    mime: &ContentType,
+    text: &str,
+    failures: &[GuardFailure],
+    locale: &Locale<'_>,
+) -> String {
+    if *mime == ContentType::JSON {
+        let string = |s: Option<&str>| match s {
+            Some(s) => format!("\"{}\"", escape_json(s)),
+            None => "null".into(),
+        };
+
+        let failures: Vec<String> = failures.iter()
+            .map(|failure| {
+                let kind = match failure.kind {
+                    GuardKind::Path => "path",
+                    GuardKind::Query => "query",
+                    GuardKind::Request => "request",
+                    GuardKind::Data => "data",
+                };
+
+                format!(
+                    "{{ \"route\": {}, \"kind\": \"{kind}\", \"parameter\": {}, \
+                    \"type\": \"{}\", \"status\": {}, \"forwarded\": {}, \"reason\": {} }}",
+                    string(failure.route.as_deref()),
+                    string(failure.parameter.as_deref()),
+                    escape_json(&failure.type_name),
+                    failure.status.code,
+                    failure.forwarded,
+                    string(failure.reason.as_deref()),
+                )
+            })
+            .collect();
+
+        let (head, tail) = text.rsplit_once("\n  }").expect("JSON template");
+        let list = failures.join(",\n      ");
+        format!("{head},\n    \"failures\": [\n      {list}\n    ]\n  }}{tail}")
+    } else {
+        let label = locale.get("error.guard_failures").unwrap_or("Guard failures");
+        let items: String = failures.iter()
+            .map(|failure| failure.to_string())
+            .map(|item| format!("\n            <li>{}</li>", RawStr::new(&item).html_escape()))
+            .collect();
+
+        let (head, tail) = text.split_once("<hr />").expect("HTML template");
+        format!("{head}<p>{}:</p>\n        <ul align=\"left\">{items}\n        </ul>\n        \
+            <hr />{tail}", RawStr::new(label).html_escape())
+    }
+}
+
+fn escape_json(string: &str) -> Cow<'_, str> {
+    if !string.contains(|c: char| c == '"' || c == '\\' || c.is_control()) {
+        return string.into();
+    }
+
+    let mut escaped = String::with_capacity(string.len() + 8);
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.into()
+}
+
 default_handler_fn! {
     400, "Bad Request", "The request could not be understood by the server due \
         to malformed syntax.",
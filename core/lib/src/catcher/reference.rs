@@ -0,0 +1,144 @@
+use std::fmt;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+
+use crate::http::{Method, Status};
+
+/// A short code identifying a server error.
+///
+/// When a request fails with a `5xx` status, Rocket assigns the request an
+/// `ErrorReference` before invoking the error catcher. The reference is:
+///
+///   * logged, at the `error` level, within the request's span, alongside the
+///     request's ID and URI,
+///   * sent to the client in the `X-Error-Reference` response header, and
+///   * included in the body of Rocket's default error pages.
+///
+/// Custom catchers can retrieve the reference via
+/// [`Request::error_reference()`](crate::Request::error_reference()) to display
+/// it. A user who reports the reference allows the corresponding log entries to
+/// be found. References are random and reveal nothing about the error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::http::Status;
+///
+/// #[catch(500)]
+/// fn internal_error(req: &Request<'_>) -> String {
+///     match req.error_reference() {
+///         Some(reference) => format!("Something went wrong. Reference: {reference}"),
+///         None => "Something went wrong.".into(),
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn fails() -> Status {
+///     Status::InternalServerError
+/// }
+///
+/// # use rocket::local::blocking::Client;
+/// let rocket = rocket::build()
+///     .mount("/", routes![fails])
+///     .register("/", catchers![internal_error]);
+///
+/// # let client = Client::debug(rocket).unwrap();
+/// # let response = client.get("/").dispatch();
+/// # let header = response.headers().get_one("X-Error-Reference").unwrap().to_string();
+/// # let body = response.into_string().unwrap();
+/// # assert!(body.ends_with(&header));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorReference(u32);
+
+impl ErrorReference {
+    /// The name of the response header containing the reference.
+    pub const HEADER: &'static str = "X-Error-Reference";
+
+    /// Returns a new, random reference.
+    pub(crate) fn generate() -> ErrorReference {
+        ErrorReference(rand::random())
+    }
+}
+
+impl fmt::Display for ErrorReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08X}", self.0)
+    }
+}
+
+/// A summary of a recent server error.
+///
+/// Rocket keeps summaries of the most recent server errors, those assigned an
+/// [`ErrorReference`], in memory. They are returned, oldest first, by
+/// [`Rocket::recent_errors()`](crate::Rocket::recent_errors()). Summaries
+/// intentionally omit the request's query, headers, and body, which may
+/// contain sensitive data.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::local::blocking::Client;
+///
+/// #[get("/fails")]
+/// fn fails() -> Status {
+///     Status::ServiceUnavailable
+/// }
+///
+/// let client = Client::debug_with(routes![fails]).unwrap();
+/// let response = client.get("/fails?secret=1").dispatch();
+/// let header = response.headers().get_one("X-Error-Reference").unwrap();
+///
+/// let errors = client.rocket().recent_errors();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].reference.to_string(), header);
+/// assert_eq!(errors[0].status, Status::ServiceUnavailable);
+/// assert_eq!(errors[0].path, "/fails");
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ErrorSummary {
+    /// The error's reference.
+    pub reference: ErrorReference,
+    /// The error's status.
+    pub status: Status,
+    /// The method of the failed request.
+    pub method: Method,
+    /// The path of the failed request, without the query.
+    pub path: String,
+    /// The name of the route that last handled the request, if any.
+    pub route: Option<Cow<'static, str>>,
+    /// When the error occurred.
+    pub time: SystemTime,
+}
+
+/// A bounded log of recent server errors.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorLog {
+    entries: Mutex<VecDeque<ErrorSummary>>,
+}
+
+impl ErrorLog {
+    /// The maximum number of errors retained. Older errors are dropped.
+    pub const CAPACITY: usize = 64;
+
+    pub fn record(&self, summary: ErrorSummary) {
+        let mut entries = self.entries.lock();
+        if entries.len() == Self::CAPACITY {
+            entries.pop_front();
+        }
+
+        entries.push_back(summary);
+    }
+
+    pub fn recent(&self) -> Vec<ErrorSummary> {
+        self.entries.lock().iter().cloned().collect()
+    }
+}
@@ -5,10 +5,12 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "secrets")]
 use crate::config::SecretKey;
-use crate::config::{ShutdownConfig, Level, TraceFormat, Ident, CliColors};
+use crate::config::{ShutdownConfig, LoadConfig, StrictConfig, NormalizeConfig, RedactConfig};
+use crate::config::{Level, TraceFormat, Ident, CliColors};
 use crate::request::{self, Request, FromRequest};
 use crate::http::uncased::Uncased;
-use crate::data::Limits;
+use crate::http::uri::Absolute;
+use crate::data::{Limits, BufferPolicy};
 
 /// Rocket server configuration.
 ///
@@ -66,6 +68,17 @@ pub struct Config {
     pub workers: usize,
     /// Limit on threads to start for blocking tasks. **(default: `512`)**
     pub max_blocking: usize,
+    /// Name of the async runtime's threads. **(default: `"rocket-worker-thread"`)**
+    ///
+    /// _**Note:** Rocket only reads this value from sources in the [default
+    /// provider](Config::figment())._
+    pub thread_name: String,
+    /// Stack size, in bytes, of the async runtime's threads; the runtime's
+    /// default, currently 2MiB, when `0`. **(default: `0`)**
+    ///
+    /// _**Note:** Rocket only reads this value from sources in the [default
+    /// provider](Config::figment())._
+    pub thread_stack_size: usize,
     /// How, if at all, to identify the server via the `Server` header.
     /// **(default: `"Rocket"`)**
     pub ident: Ident,
@@ -102,14 +115,57 @@ pub struct Config {
     /// [`"X-Forwarded-Proto"`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-Forwarded-Proto
     #[serde(deserialize_with = "crate::config::http_header::deserialize")]
     pub proxy_proto_header: Option<Uncased<'static>>,
+    /// The absolute URL at which clients reach the application, such as
+    /// `"https://example.com/app"`, typically that of a proxy in front of the
+    /// application. Used by [`Request::absolute_uri()`] to build absolute URLs
+    /// for links, emails, and redirects.
+    ///
+    /// **(default: `None`)**
+    pub public_url: Option<Absolute<'static>>,
     /// Streaming read size limits. **(default: [`Limits::default()`])**
     pub limits: Limits,
     /// Directory to store temporary files in. **(default:
     /// [`std::env::temp_dir()`])**
     #[serde(serialize_with = "RelativePathBuf::serialize_relative")]
     pub temp_dir: RelativePathBuf,
+    /// Policy for storing request bodies read into a [`TempFile`]. **(default:
+    /// [`BufferPolicy::default()`])**
+    ///
+    /// [`TempFile`]: crate::fs::TempFile
+    pub buffer: BufferPolicy,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Time budget for handling a request in seconds, exposed via the
+    /// [`Deadline`] request guard; disabled when `0`. **(default: `0`)**
+    ///
+    /// [`Deadline`]: crate::request::Deadline
+    pub request_timeout: u32,
+    /// The name of a header, typically set by a client or an upstream service,
+    /// which contains a time budget for the request that further limits the
+    /// [`Deadline`]. Values are seconds, such as `1.5`, or integers suffixed
+    /// with a `grpc-timeout` unit, such as `1500m`.
+    ///
+    /// To disable using any header for this purpose, set this value to `false`
+    /// or `None`. Deserialization semantics are identical to those of
+    /// [`Config::ip_header`] (the value must be a valid HTTP header name).
+    ///
+    /// **(default: `None`)**
+    ///
+    /// [`Deadline`]: crate::request::Deadline
+    #[serde(deserialize_with = "crate::config::http_header::deserialize")]
+    pub timeout_header: Option<Uncased<'static>>,
+    /// Whether to respond with `405 Method Not Allowed` and an `Allow` header
+    /// when routes match a request's URI but not its method. When `false`,
+    /// such requests are answered with `404 Not Found`. **(default: `true`)**
+    pub method_not_allowed: bool,
+    /// Whether to respond to an `OPTIONS` request that no route handles, but
+    /// whose URI some route matches, with `204 No Content` and an `Allow`
+    /// header. When `false`, such requests are answered with `404 Not Found`.
+    /// **(default: `true`)**
+    ///
+    /// _**Note:** Prior to this option, such requests were always answered
+    /// with `404 Not Found`. Set it to `false` to restore that behavior._
+    pub auto_options: bool,
     /// The secret key for signing and encrypting. **(default: `0`)**
     ///
     /// _**Note:** This field _always_ serializes as a 256-bit array of `0`s to
@@ -120,10 +176,23 @@ pub struct Config {
     pub secret_key: SecretKey,
     /// Graceful shutdown configuration. **(default: [`ShutdownConfig::default()`])**
     pub shutdown: ShutdownConfig,
+    /// Connection limit and load shedding configuration. **(default:
+    /// [`LoadConfig::default()`])**
+    pub load: LoadConfig,
+    /// Strict request validation configuration. **(default:
+    /// [`StrictConfig::default()`])**
+    pub strict: StrictConfig,
+    /// Request path normalization configuration. **(default:
+    /// [`NormalizeConfig::default()`])**
+    pub normalize: NormalizeConfig,
+    /// Trace redaction configuration. **(default:
+    /// [`RedactConfig::default()`])**
+    pub redact: RedactConfig,
     /// Max level to log. **(default: _debug_ `info` / _release_ `error`)**
     #[serde(with = "crate::trace::level")]
     pub log_level: Option<Level>,
-    /// Format to use when logging. **(default: _debug_ `pretty` / _release_ `compact`)**
+    /// Format to use when logging: `pretty`, `compact`, or `json`. **(default:
+    /// _debug_ `pretty` / _release_ `compact`)**
     pub log_format: TraceFormat,
     /// Whether to use colors and emoji when logging. **(default:
     /// [`CliColors::Auto`])**
@@ -184,15 +253,27 @@ impl Config {
             profile: Self::DEBUG_PROFILE,
             workers: num_cpus::get(),
             max_blocking: 512,
+            thread_name: "rocket-worker-thread".into(),
+            thread_stack_size: 0,
             ident: Ident::default(),
             ip_header: Some(Uncased::from_borrowed("X-Real-IP")),
             proxy_proto_header: None,
+            public_url: None,
             limits: Limits::default(),
             temp_dir: std::env::temp_dir().into(),
+            buffer: BufferPolicy::default(),
             keep_alive: 5,
+            request_timeout: 0,
+            timeout_header: None,
+            method_not_allowed: true,
+            auto_options: true,
             #[cfg(feature = "secrets")]
             secret_key: SecretKey::zero(),
             shutdown: ShutdownConfig::default(),
+            load: LoadConfig::default(),
+            strict: StrictConfig::default(),
+            normalize: NormalizeConfig::default(),
+            redact: RedactConfig::default(),
             log_level: Some(Level::INFO),
             log_format: TraceFormat::Pretty,
             cli_colors: CliColors::Auto,
@@ -255,7 +336,9 @@ impl Config {
     pub fn figment() -> Figment {
         Figment::from(Config::default())
             .merge(Toml::file(Env::var_or("ROCKET_CONFIG", "Rocket.toml")).nested())
-            .merge(Env::prefixed("ROCKET_").ignore(&["PROFILE"]).global())
+            .merge(Env::prefixed("ROCKET_")
+                .ignore(&["PROFILE", "LISTEN_FDS", "READY_FD"])
+                .global())
             .select(Profile::from_env_or("ROCKET_PROFILE", Self::DEFAULT_PROFILE))
     }
 
@@ -279,6 +362,7 @@ impl Config {
         let figment = Figment::from(provider);
         let mut config = figment.extract::<Self>()?;
         config.profile = figment.profile().clone();
+        config.shutdown.validate()?;
         Ok(config)
     }
 
@@ -338,9 +422,24 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::max_blocking`].
     pub const MAX_BLOCKING: &'static str = "max_blocking";
 
+    /// The stringy parameter name for setting/extracting [`Config::thread_name`].
+    pub const THREAD_NAME: &'static str = "thread_name";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::thread_stack_size`].
+    pub const THREAD_STACK_SIZE: &'static str = "thread_stack_size";
+
     /// The stringy parameter name for setting/extracting [`Config::keep_alive`].
     pub const KEEP_ALIVE: &'static str = "keep_alive";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::request_timeout`].
+    pub const REQUEST_TIMEOUT: &'static str = "request_timeout";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::timeout_header`].
+    pub const TIMEOUT_HEADER: &'static str = "timeout_header";
+
     /// The stringy parameter name for setting/extracting [`Config::ident`].
     pub const IDENT: &'static str = "ident";
 
@@ -350,6 +449,9 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::proxy_proto_header`].
     pub const PROXY_PROTO_HEADER: &'static str = "proxy_proto_header";
 
+    /// The stringy parameter name for setting/extracting [`Config::public_url`].
+    pub const PUBLIC_URL: &'static str = "public_url";
+
     /// The stringy parameter name for setting/extracting [`Config::limits`].
     pub const LIMITS: &'static str = "limits";
 
@@ -359,6 +461,9 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::temp_dir`].
     pub const TEMP_DIR: &'static str = "temp_dir";
 
+    /// The stringy parameter name for setting/extracting [`Config::buffer`].
+    pub const BUFFER: &'static str = "buffer";
+
     /// The stringy parameter name for setting/extracting [`Config::log_level`].
     pub const LOG_LEVEL: &'static str = "log_level";
 
@@ -368,15 +473,28 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::shutdown`].
     pub const SHUTDOWN: &'static str = "shutdown";
 
+    /// The stringy parameter name for setting/extracting [`Config::load`].
+    pub const LOAD: &'static str = "load";
+
+    /// The stringy parameter name for setting/extracting [`Config::strict`].
+    pub const STRICT: &'static str = "strict";
+
+    /// The stringy parameter name for setting/extracting [`Config::normalize`].
+    pub const NORMALIZE: &'static str = "normalize";
+
+    /// The stringy parameter name for setting/extracting [`Config::redact`].
+    pub const REDACT: &'static str = "redact";
+
     /// The stringy parameter name for setting/extracting [`Config::cli_colors`].
     pub const CLI_COLORS: &'static str = "cli_colors";
 
     /// An array of all of the stringy parameter names.
     pub const PARAMETERS: &'static [&'static str] = &[
-        Self::WORKERS, Self::MAX_BLOCKING, Self::KEEP_ALIVE, Self::IDENT,
-        Self::IP_HEADER, Self::PROXY_PROTO_HEADER, Self::LIMITS,
-        Self::SECRET_KEY, Self::TEMP_DIR, Self::LOG_LEVEL, Self::LOG_FORMAT,
-        Self::SHUTDOWN, Self::CLI_COLORS,
+        Self::WORKERS, Self::MAX_BLOCKING, Self::THREAD_NAME, Self::THREAD_STACK_SIZE,
+        Self::KEEP_ALIVE, Self::REQUEST_TIMEOUT, Self::TIMEOUT_HEADER, Self::IDENT,
+        Self::IP_HEADER, Self::PROXY_PROTO_HEADER, Self::PUBLIC_URL, Self::LIMITS,
+        Self::SECRET_KEY, Self::TEMP_DIR, Self::BUFFER, Self::LOG_LEVEL, Self::LOG_FORMAT,
+        Self::SHUTDOWN, Self::LOAD, Self::STRICT, Self::NORMALIZE, Self::REDACT, Self::CLI_COLORS,
     ];
 
     /// The stringy parameter name for setting/extracting [`Config::profile`].
@@ -115,15 +115,24 @@ mod ident;
 mod config;
 mod cli_colors;
 mod http_header;
+mod normalize;
+mod redact;
+mod strict;
 #[cfg(test)]
 mod tests;
 
 pub use ident::Ident;
 pub use config::Config;
 pub use cli_colors::CliColors;
+pub use normalize::NormalizeConfig;
+pub use redact::RedactConfig;
+pub use strict::StrictConfig;
+
+pub(crate) use strict::Violation;
 
 pub use crate::trace::{TraceFormat, Level};
 pub use crate::shutdown::ShutdownConfig;
+pub use crate::load::LoadConfig;
 
 #[cfg(feature = "tls")]
 pub use crate::tls::TlsConfig;
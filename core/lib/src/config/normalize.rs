@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "nfc")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use crate::config::Violation;
+use crate::http::RawStr;
+use crate::http::uri::Origin;
+
+/// Path normalization configuration.
+///
+/// The same path can be spelled many ways: `/a/b`, `/%61/b`, `/a/./b`, and
+/// `/a/c/../b` all name the same resource, as do paths with equivalent but
+/// differently composed Unicode characters. Code that inspects the raw path,
+/// like a fairing guarding `/admin`, and the router, which matches decoded
+/// segments, can disagree on what a request refers to. Normalization gives
+/// every request a single, canonical path before any fairing or route sees
+/// it.
+///
+/// When `enabled`, Rocket rewrites the path of every incoming request, before
+/// running request fairings, by:
+///
+///   1. percent-decoding each segment;
+///   2. rejecting the request with a `400 Bad Request` if a decoded segment
+///      is not valid UTF-8, contains a NUL byte, or still contains a
+///      percent-encoded byte, a sign of double encoding;
+///   3. converting each segment to Unicode Normalization Form C if `nfc` is
+///      `true` and the `nfc` feature is enabled;
+///   4. removing `.` segments and resolving `..` segments against the
+///      preceding segment if `dot_segments` is `true`;
+///   5. percent-encoding each segment again, consistently.
+///
+/// The query is left as is. The normalized URI is returned by
+/// [`Request::uri()`] while the URI as received is available via
+/// [`Request::raw_uri()`]. Each rejection is logged at the `warn` level with
+/// the reason, method, URI, and remote address of the request. As with
+/// [strict validation](crate::config::StrictConfig), an HTTP/1 connection is
+/// closed after the response to a rejected request.
+///
+/// [`Request::uri()`]: crate::Request::uri()
+/// [`Request::raw_uri()`]: crate::Request::raw_uri()
+///
+/// ```toml
+/// [default.normalize]
+/// enabled = true
+/// nfc = true
+/// dot_segments = true
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, NormalizeConfig};
+///
+/// let config = Config {
+///     normalize: NormalizeConfig {
+///         enabled: true,
+///         ..Default::default()
+///     },
+///     ..Config::default()
+/// };
+///
+/// assert!(config.normalize.dot_segments);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizeConfig {
+    /// Whether to normalize request paths.
+    ///
+    /// **default: `false`**
+    pub enabled: bool,
+    /// Whether to convert segments to Unicode Normalization Form C.
+    ///
+    /// Only available when the `nfc` feature is enabled. Otherwise, segments
+    /// are never converted.
+    ///
+    /// **default: `true`**
+    #[cfg(feature = "nfc")]
+    #[cfg_attr(nightly, doc(cfg(feature = "nfc")))]
+    pub nfc: bool,
+    /// Whether to remove `.` and resolve `..` segments.
+    ///
+    /// **default: `true`**
+    pub dot_segments: bool,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::NormalizeConfig;
+    ///
+    /// let config = NormalizeConfig {
+    ///     enabled: true,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for NormalizeConfig {
+    fn default() -> Self {
+        NormalizeConfig {
+            enabled: false,
+            #[cfg(feature = "nfc")]
+            nfc: true,
+            dot_segments: true,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl NormalizeConfig {
+    /// Normalizes the path of `uri` according to `self`. Returns `Ok(None)` if
+    /// `self` isn't enabled or the path is already normalized.
+    pub(crate) fn normalize(&self, uri: &Origin<'_>) -> Result<Option<Origin<'static>>, Violation> {
+        if !self.enabled {
+            return Ok(None);
+        }
+
+        let raw_segments: Vec<&RawStr> = uri.path().raw_segments().collect();
+        let mut segments: Vec<Cow<'_, str>> = Vec::with_capacity(raw_segments.len());
+        for (i, raw) in raw_segments.iter().enumerate() {
+            let decoded = raw.percent_decode().map_err(|_| Violation::InvalidPathEncoding)?;
+            if decoded.contains('\0') {
+                return Err(Violation::NullInPath);
+            }
+
+            if is_over_encoded(&decoded) {
+                return Err(Violation::OverEncodedPath);
+            }
+
+            #[cfg(feature = "nfc")]
+            let decoded = match self.nfc && !is_nfc(&decoded) {
+                true => Cow::Owned(decoded.nfc().collect()),
+                false => decoded,
+            };
+
+            let segment = decoded;
+
+            let is_last = i + 1 == raw_segments.len();
+            match &*segment {
+                "." if self.dot_segments => {},
+                ".." if self.dot_segments => { segments.pop(); },
+                _ => {
+                    segments.push(segment);
+                    continue;
+                }
+            }
+
+            // A trailing dot-segment leaves a trailing slash: `/a/b/..` is `/a/`.
+            if is_last {
+                segments.push(Cow::Borrowed(""));
+            }
+        }
+
+        let mut path = String::with_capacity(uri.path().as_str().len());
+        for segment in &segments {
+            path.push('/');
+            path.push_str(RawStr::new(segment).percent_encode().as_str());
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        if path == uri.path().as_str() {
+            return Ok(None);
+        }
+
+        let query = uri.query().map(|q| q.as_str().to_owned());
+        Ok(Some(Origin::new(path, query)))
+    }
+}
+
+/// Returns `true` if `decoded` contains a percent-encoded byte.
+fn is_over_encoded(decoded: &str) -> bool {
+    decoded.as_bytes()
+        .windows(3)
+        .any(|w| w[0] == b'%' && w[1].is_ascii_hexdigit() && w[2].is_ascii_hexdigit())
+}
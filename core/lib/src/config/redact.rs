@@ -0,0 +1,206 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::Header;
+use crate::util::Formatter;
+
+/// The placeholder that replaces redacted values.
+const REDACTED: &str = "[redacted]";
+
+/// Trace redaction configuration.
+///
+/// At the `debug` level, Rocket traces the headers of every request and
+/// response, and at every level it traces request URIs. Headers like
+/// `Authorization` and `Cookie`, and query parameters like `?token=`, carry
+/// credentials that must not end up in logs. Redaction scrubs these values
+/// from everything Rocket traces, so debug logging can be enabled in
+/// production without leaking secrets:
+///
+///   * The value of each header in `headers` is replaced with `[redacted]`.
+///     For `Cookie` headers, each cookie's value is replaced while its name is
+///     kept. For `Set-Cookie` headers, the cookie's value is replaced while its
+///     name and attributes are kept.
+///   * The value of each query parameter whose name contains a term in
+///     `query`, ignoring case, is replaced with `[redacted]`.
+///
+/// Header names are compared case-insensitively. Redaction only affects
+/// traces; requests and responses are left as is. Applications that trace
+/// requests themselves, for instance in an access log fairing, can apply the
+/// same rules via [`RedactConfig::header()`] and [`RedactConfig::uri()`].
+///
+/// ```toml
+/// [default.redact]
+/// headers = ["Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie", "X-Api-Key"]
+/// query = ["token", "key", "secret", "password", "passwd", "signature", "credential"]
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, RedactConfig};
+///
+/// let config = Config {
+///     redact: RedactConfig {
+///         query: vec!["session".into()],
+///         ..Default::default()
+///     },
+///     ..Config::default()
+/// };
+///
+/// let uri = config.redact.uri("/search?q=rocket&session_id=abc123");
+/// assert_eq!(uri.to_string(), "/search?q=rocket&session_id=[redacted]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    /// Names of headers whose values are redacted.
+    ///
+    /// **default: `["Authorization", "Proxy-Authorization", "Cookie",
+    /// "Set-Cookie", "X-Api-Key"]`**
+    pub headers: Vec<String>,
+    /// Terms which, when contained in the name of a query parameter, cause
+    /// its value to be redacted.
+    ///
+    /// **default: `["token", "key", "secret", "password", "passwd",
+    /// "signature", "credential"]`**
+    pub query: Vec<String>,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::RedactConfig;
+    ///
+    /// let config = RedactConfig {
+    ///     headers: vec![],
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        let headers = ["Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie", "X-Api-Key"];
+        let query = ["token", "key", "secret", "password", "passwd", "signature", "credential"];
+        RedactConfig {
+            headers: headers.into_iter().map(String::from).collect(),
+            query: query.into_iter().map(String::from).collect(),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl RedactConfig {
+    /// Returns `header` with its value redacted if its name is in `headers`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::RedactConfig;
+    /// use rocket::http::Header;
+    ///
+    /// let redact = RedactConfig::default();
+    ///
+    /// let header = redact.header(Header::new("Authorization", "Bearer s3cr3t"));
+    /// assert_eq!(header.value(), "[redacted]");
+    ///
+    /// let header = redact.header(Header::new("Cookie", "theme=dark; session=abc"));
+    /// assert_eq!(header.value(), "theme=[redacted]; session=[redacted]");
+    ///
+    /// let header = redact.header(Header::new("Set-Cookie", "session=abc; Path=/; HttpOnly"));
+    /// assert_eq!(header.value(), "session=[redacted]; Path=/; HttpOnly");
+    ///
+    /// let header = redact.header(Header::new("Accept", "text/html"));
+    /// assert_eq!(header.value(), "text/html");
+    /// ```
+    pub fn header<'h>(&self, header: Header<'h>) -> Header<'h> {
+        let name = header.name().as_str();
+        if !self.headers.iter().any(|h| h.eq_ignore_ascii_case(name)) {
+            return header;
+        }
+
+        let value = if name.eq_ignore_ascii_case("Cookie") {
+            header.value()
+                .split(';')
+                .map(|cookie| redact_pair(cookie.trim()))
+                .collect::<Vec<_>>()
+                .join("; ")
+        } else if name.eq_ignore_ascii_case("Set-Cookie") {
+            let (cookie, attributes) = match header.value().split_once(';') {
+                Some((cookie, attributes)) => (cookie, Some(attributes)),
+                None => (header.value(), None),
+            };
+
+            let cookie = redact_pair(cookie.trim());
+            match attributes {
+                Some(attributes) => format!("{cookie};{attributes}"),
+                None => cookie,
+            }
+        } else {
+            REDACTED.into()
+        };
+
+        Header { name: header.name, value: value.into() }
+    }
+
+    /// Returns a value that displays as `uri` with the value of every query
+    /// parameter whose name contains a term in `query` redacted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::RedactConfig;
+    ///
+    /// let redact = RedactConfig::default();
+    ///
+    /// let uri = redact.uri("/login?next=/home&access_token=abc&API_KEY=def");
+    /// assert_eq!(uri.to_string(), "/login?next=/home&access_token=[redacted]&API_KEY=[redacted]");
+    ///
+    /// let uri = redact.uri("/login?next=/home");
+    /// assert_eq!(uri.to_string(), "/login?next=/home");
+    /// ```
+    pub fn uri<'a, T: fmt::Display + 'a>(&'a self, uri: T) -> impl fmt::Display + 'a {
+        Formatter(move |f| {
+            let uri = uri.to_string();
+            let (path, query) = match uri.split_once('?') {
+                Some((path, query)) if !self.query.is_empty() => (path, query),
+                _ => return f.write_str(&uri),
+            };
+
+            write!(f, "{path}?")?;
+            for (i, param) in query.split('&').enumerate() {
+                if i != 0 {
+                    f.write_str("&")?;
+                }
+
+                match param.split_once('=') {
+                    Some((name, _)) if self.is_sensitive_param(name) => {
+                        write!(f, "{name}={REDACTED}")?
+                    }
+                    _ => f.write_str(param)?,
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if `name` contains one of the terms in `query`,
+    /// ignoring case.
+    fn is_sensitive_param(&self, name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        self.query.iter().any(|term| name.contains(&term.to_ascii_lowercase()))
+    }
+}
+
+/// Redacts the value of a `name=value` pair, keeping the name.
+fn redact_pair(pair: &str) -> String {
+    match pair.split_once('=') {
+        Some((name, _)) => format!("{name}={REDACTED}"),
+        None => REDACTED.into(),
+    }
+}
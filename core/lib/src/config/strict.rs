@@ -0,0 +1,212 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::{ByteUnit, ToByteUnit};
+use crate::http::Status;
+
+/// Strict request validation configuration.
+///
+/// HTTP/1 parsers disagree on edge cases, like a request with both a
+/// `Content-Length` and a `Transfer-Encoding` header. When a proxy and the
+/// server behind it frame such a request differently, an attacker can
+/// _smuggle_ a second request past the proxy. Rocket's HTTP layer already
+/// rejects malformed requests, but applications exposed directly to the
+/// internet, or behind proxies of unknown quality, may want to reject
+/// ambiguous requests outright.
+///
+/// When `enabled`, Rocket rejects requests that:
+///
+///   * have both a `Content-Length` and a `Transfer-Encoding` header, more
+///     than one `Content-Length` or `Transfer-Encoding` header, a
+///     `Content-Length` that isn't a decimal integer, or a `Transfer-Encoding`
+///     other than `chunked`, with a `400 Bad Request`;
+///   * have a header name or value containing a CR, LF, or NUL byte, with a
+///     `400 Bad Request`, should a protocol implementation let one through;
+///   * have more than `max_headers` headers or a header section, counted as
+///     `name: value\r\n` for each header, larger than `max_header_size`, with
+///     a `431 Request Header Fields Too Large`.
+///
+/// Rejected requests are answered by the catcher for the status and never
+/// reach a route. The response to a rejected HTTP/1 request carries a
+/// `Connection: close` header, and the connection is closed once it is
+/// written, so that no bytes following the rejected request are ever
+/// interpreted as another request. Each rejection is logged at the `warn`
+/// level with the reason, method, URI, and remote address of the request.
+///
+/// Validation applies to the request head as received from the network.
+/// Requests dispatched via a [local client](crate::local) have no such head
+/// and are thus not validated.
+///
+/// ```toml
+/// [default.strict]
+/// enabled = true
+/// max_headers = 64
+/// max_header_size = "16KiB"
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, StrictConfig};
+/// use rocket::data::ToByteUnit;
+///
+/// let config = Config {
+///     strict: StrictConfig {
+///         enabled: true,
+///         max_header_size: 16.kibibytes(),
+///         ..Default::default()
+///     },
+///     ..Config::default()
+/// };
+///
+/// assert_eq!(config.strict.max_headers, 100);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrictConfig {
+    /// Whether to reject ambiguous or oversized requests.
+    ///
+    /// **default: `false`**
+    pub enabled: bool,
+    /// Maximum number of headers in a request.
+    ///
+    /// **default: `100`**
+    pub max_headers: usize,
+    /// Maximum size of a request's header section.
+    ///
+    /// **default: `32KiB`**
+    pub max_header_size: ByteUnit,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::StrictConfig;
+    ///
+    /// let config = StrictConfig {
+    ///     enabled: true,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for StrictConfig {
+    fn default() -> Self {
+        StrictConfig {
+            enabled: false,
+            max_headers: 100,
+            max_header_size: 32.kibibytes(),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// The reason a request was rejected by strict validation or normalization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Violation {
+    AmbiguousLength,
+    InvalidContentLength,
+    InvalidTransferEncoding,
+    InvalidHeaderBytes(String),
+    TooManyHeaders { count: usize, limit: usize },
+    HeaderSectionTooLarge { size: ByteUnit, limit: ByteUnit },
+    InvalidPathEncoding,
+    NullInPath,
+    OverEncodedPath,
+}
+
+impl StrictConfig {
+    /// Checks the head of a request against `self`, returning the first
+    /// violation found, if any. Always succeeds if `self` isn't enabled.
+    pub(crate) fn check(&self, head: &http::request::Parts) -> Result<(), Violation> {
+        use http::header::{CONTENT_LENGTH, TRANSFER_ENCODING};
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let headers = &head.headers;
+        if headers.len() > self.max_headers {
+            return Err(Violation::TooManyHeaders { count: headers.len(), limit: self.max_headers });
+        }
+
+        let size: usize = headers.iter()
+            .map(|(name, value)| name.as_str().len() + value.len() + 4)
+            .sum();
+
+        if size.bytes() > self.max_header_size {
+            let limit = self.max_header_size;
+            return Err(Violation::HeaderSectionTooLarge { size: size.bytes(), limit });
+        }
+
+        let invalid = |bytes: &[u8]| bytes.iter().any(|b| matches!(b, b'\r' | b'\n' | b'\0'));
+        for (name, value) in headers.iter() {
+            if invalid(name.as_str().as_bytes()) || invalid(value.as_bytes()) {
+                return Err(Violation::InvalidHeaderBytes(name.as_str().into()));
+            }
+        }
+
+        let lengths = headers.get_all(CONTENT_LENGTH).iter().count();
+        let encodings = headers.get_all(TRANSFER_ENCODING).iter().count();
+        if (lengths > 0 && encodings > 0) || lengths > 1 || encodings > 1 {
+            return Err(Violation::AmbiguousLength);
+        }
+
+        if let Some(length) = headers.get(CONTENT_LENGTH) {
+            let bytes = length.as_bytes();
+            if bytes.is_empty() || !bytes.iter().all(|b| b.is_ascii_digit()) {
+                return Err(Violation::InvalidContentLength);
+            }
+        }
+
+        if let Some(encoding) = headers.get(TRANSFER_ENCODING) {
+            if !encoding.as_bytes().eq_ignore_ascii_case(b"chunked") {
+                return Err(Violation::InvalidTransferEncoding);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Violation {
+    /// The status to respond to the rejected request with.
+    pub(crate) fn status(&self) -> Status {
+        match self {
+            Violation::TooManyHeaders { .. } | Violation::HeaderSectionTooLarge { .. } => {
+                Status::RequestHeaderFieldsTooLarge
+            }
+            _ => Status::BadRequest,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::AmbiguousLength => {
+                write!(f, "conflicting `Content-Length` and `Transfer-Encoding` headers")
+            }
+            Violation::InvalidContentLength => write!(f, "invalid `Content-Length` header"),
+            Violation::InvalidTransferEncoding => {
+                write!(f, "unsupported `Transfer-Encoding` header")
+            }
+            Violation::InvalidHeaderBytes(name) => {
+                write!(f, "CR, LF, or NUL in header `{name}`")
+            }
+            Violation::TooManyHeaders { count, limit } => {
+                write!(f, "{count} headers exceeds limit of {limit}")
+            }
+            Violation::HeaderSectionTooLarge { size, limit } => {
+                write!(f, "header section of {size} exceeds limit of {limit}")
+            }
+            Violation::InvalidPathEncoding => write!(f, "path segment is not valid UTF-8"),
+            Violation::NullInPath => write!(f, "NUL in path segment"),
+            Violation::OverEncodedPath => write!(f, "percent-encoded path segment is encoded"),
+        }
+    }
+}
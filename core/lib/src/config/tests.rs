@@ -4,7 +4,7 @@ use tracing::Level;
 
 // use crate::log::LogLevel;
 use crate::data::{Limits, ToByteUnit};
-use crate::config::{Config, CliColors};
+use crate::config::{Config, CliColors, TraceFormat};
 
 #[test]
 fn test_figment_is_default() {
@@ -112,6 +112,76 @@ fn test_toml_file() {
     });
 }
 
+#[test]
+fn test_public_url() {
+    figment::Jail::expect_with(|jail| {
+        let config = Config::from(Config::figment());
+        assert_eq!(config.public_url, None);
+
+        jail.set_env("ROCKET_PUBLIC_URL", "https://rocket.rs/app");
+        let config = Config::from(Config::figment());
+        assert_eq!(config.public_url.unwrap(), "https://rocket.rs/app");
+
+        jail.set_env("ROCKET_PUBLIC_URL", "/app");
+        assert!(Config::try_from(Config::figment()).is_err());
+
+        Ok(())
+    });
+}
+
+#[test]
+#[cfg(unix)]
+fn test_upgrade_signal() {
+    use crate::config::Sig;
+
+    figment::Jail::expect_with(|jail| {
+        let config = Config::from(Config::figment());
+        assert_eq!(config.shutdown.upgrade, None);
+        assert_eq!(config.shutdown.upgrade_timeout, 30);
+
+        jail.set_env("ROCKET_SHUTDOWN", r#"{upgrade="usr2",upgrade_timeout=5}"#);
+        let config = Config::from(Config::figment());
+        assert_eq!(config.shutdown.upgrade, Some(Sig::Usr2));
+        assert_eq!(config.shutdown.upgrade_timeout, 5);
+
+        jail.set_env("ROCKET_SHUTDOWN", r#"{upgrade="term"}"#);
+        assert!(Config::try_from(Config::figment()).is_err());
+
+        jail.set_env("ROCKET_SHUTDOWN", r#"{upgrade="hup",signals=["term","hup"]}"#);
+        assert!(Config::try_from(Config::figment()).is_err());
+
+        jail.set_env("ROCKET_SHUTDOWN", r#"{upgrade="int"}"#);
+        assert!(Config::try_from(Config::figment()).is_err());
+
+        jail.set_env("ROCKET_SHUTDOWN", r#"{upgrade="int",ctrlc=false}"#);
+        let config = Config::from(Config::figment());
+        assert_eq!(config.shutdown.upgrade, Some(Sig::Int));
+
+        Ok(())
+    });
+}
+
+#[test]
+fn test_log_format() {
+    figment::Jail::expect_with(|jail| {
+        let config = Config::from(Config::figment());
+        assert_eq!(config.log_format, TraceFormat::Pretty);
+
+        jail.set_env("ROCKET_LOG_FORMAT", "json");
+        let config = Config::from(Config::figment());
+        assert_eq!(config.log_format, TraceFormat::Json);
+
+        jail.set_env("ROCKET_LOG_FORMAT", "COMPACT");
+        let config = Config::from(Config::figment());
+        assert_eq!(config.log_format, TraceFormat::Compact);
+
+        jail.set_env("ROCKET_LOG_FORMAT", "xml");
+        assert!(Config::try_from(Config::figment()).is_err());
+
+        Ok(())
+    });
+}
+
 #[test]
 fn test_cli_colors() {
     figment::Jail::expect_with(|jail| {
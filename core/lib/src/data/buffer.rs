@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use figment::value::magic::RelativePathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::Config;
+use crate::data::{ByteUnit, ToByteUnit};
+
+/// Policy for storing request bodies read into a [`TempFile`].
+///
+/// By default, every [`TempFile`], including every file field in a multipart
+/// form, is streamed into a new named file in the [`temp_dir`]. A
+/// `BufferPolicy` changes where bodies go:
+///
+///   * `memory`: bodies of at most this size are held in memory instead; larger
+///     bodies are written to a temporary file. Disabled when `0`.
+///   * `temp_dir`: the directory for temporary files, overriding [`temp_dir`].
+///     Set this to a directory on the same device as the files' destination
+///     so that [`TempFile::persist_to()`] is a cheap rename, and away from
+///     memory-backed file systems for large uploads.
+///   * `unnamed`: whether to create temporary files without a name, via
+///     `O_TMPFILE` on Linux, which the system removes even if the process
+///     crashes. Such files are copied, not renamed, when persisted.
+///
+/// The configured policy, set via the `buffer` [configuration
+/// parameter](crate::config), applies to all requests:
+///
+/// ```toml
+/// [default.buffer]
+/// memory = "64KiB"
+/// temp_dir = "/var/tmp/uploads"
+/// unnamed = false
+/// ```
+///
+/// A request fairing or request guard can override it for a single request
+/// with [`Request::set_buffer_policy()`]. Data guards that stream the body
+/// elsewhere, like [`Data`] itself or a custom [`FromData`] implementation
+/// using [`Data::open()`], never buffer the body regardless of the policy.
+///
+/// [`TempFile`]: crate::fs::TempFile
+/// [`TempFile::persist_to()`]: crate::fs::TempFile::persist_to()
+/// [`temp_dir`]: crate::Config::temp_dir
+/// [`Request::set_buffer_policy()`]: crate::Request::set_buffer_policy()
+/// [`Data`]: crate::data::Data
+/// [`Data::open()`]: crate::data::Data::open()
+/// [`FromData`]: crate::data::FromData
+///
+/// # Example
+///
+/// Hold small uploads to `/avatar` in memory:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::{BufferPolicy, ToByteUnit};
+/// use rocket::fairing::AdHoc;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(AdHoc::on_request("Avatar Buffering", |req, _| Box::pin(async {
+///         if req.uri().path() == "/avatar" {
+///             req.set_buffer_policy(BufferPolicy {
+///                 memory: 256.kibibytes(),
+///                 ..Default::default()
+///             });
+///         }
+///     })))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BufferPolicy {
+    /// Maximum size of a body held in memory; disabled when `0`.
+    ///
+    /// **default: `0`**
+    pub memory: ByteUnit,
+    /// Directory for temporary files; [`Config::temp_dir`] when `None`.
+    ///
+    /// **default: `None`**
+    pub temp_dir: Option<RelativePathBuf>,
+    /// Whether to create unnamed temporary files.
+    ///
+    /// **default: `false`**
+    pub unnamed: bool,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::data::BufferPolicy;
+    ///
+    /// let policy = BufferPolicy {
+    ///     unnamed: true,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for BufferPolicy {
+    fn default() -> Self {
+        BufferPolicy {
+            memory: 0.bytes(),
+            temp_dir: None,
+            unnamed: false,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl BufferPolicy {
+    /// The directory temporary files are created in under `config`.
+    pub(crate) fn temp_dir(&self, config: &Config) -> PathBuf {
+        self.temp_dir.as_ref().unwrap_or(&config.temp_dir).relative()
+    }
+}
@@ -1,10 +1,13 @@
 use std::io;
 use std::pin::Pin;
 
+use tokio::io::AsyncRead;
+
 use crate::data::ByteUnit;
 use crate::data::data_stream::{DataStream, RawReader, RawStream};
 use crate::data::peekable::Peekable;
 use crate::data::transform::{Transform, TransformBuf, Inspect, InPlaceMap};
+use crate::data::progress::{Progress, ProgressTransform};
 
 /// Type representing the body data of a request.
 ///
@@ -62,6 +65,32 @@ impl<'r> Data<'r> {
         Data::new(Peekable::with_buffer(data, true, RawReader::new(RawStream::Empty)))
     }
 
+    /// Creates a `Data` whose body is read from `reader`.
+    ///
+    /// This is typically used by [data fairings](crate::fairing::Fairing#data)
+    /// to wrap or replace the body of a request. Limits applied when the
+    /// returned `Data` is [opened](Data::open()) apply to the bytes read from
+    /// `reader`, not to any data `reader` may itself read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::data::{Data, ToByteUnit};
+    /// use rocket::tokio::io::AsyncReadExt;
+    ///
+    /// fn prefixed<'r>(data: Data<'r>) -> Data<'r> {
+    ///     let prefix = Cursor::new(b"prefix: ".to_vec());
+    ///     Data::from_reader(prefix.chain(data.open(1.mebibytes())))
+    /// }
+    /// ```
+    pub fn from_reader<R>(reader: R) -> Data<'r>
+        where R: AsyncRead + Send + 'r
+    {
+        let reader: Pin<Box<dyn AsyncRead + Send + 'r>> = Box::pin(reader);
+        Data::from(tokio_util::io::ReaderStream::new(reader))
+    }
+
     /// Returns the raw data stream, limited to `limit` bytes.
     ///
     /// The stream contains all of the data in the body of the request,
@@ -200,6 +229,36 @@ impl<'r> Data<'r> {
         self.chain_transform(Inspect(Box::new(f)))
     }
 
+    /// Chain a [`Transform`] that reports the [`Progress`] of reading the data
+    /// to `f` as it streams. `total` is the expected size of the data, if
+    /// known, typically from the request's `Content-Length`.
+    ///
+    /// `f` is called every time data is read and once more when the data has
+    /// been read completely. Note that bytes are counted at the transform's
+    /// position in the chain. To share progress with other requests, use a
+    /// [`ProgressTracker`](crate::data::ProgressTracker).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// #[post("/upload", data = "<data>")]
+    /// async fn upload(mut data: Data<'_>) -> std::io::Result<String> {
+    ///     data.chain_progress(None, |progress| {
+    ///         println!("read {} bytes at {:.0} B/s", progress.read(), progress.rate());
+    ///     });
+    ///
+    ///     data.open(1.mebibytes()).into_string().await.map(|s| s.into_inner())
+    /// }
+    /// ```
+    pub fn chain_progress<F>(&mut self, total: Option<u64>, f: F) -> &mut Self
+        where F: FnMut(&Progress) + Send + Sync + 'static
+    {
+        self.chain_transform(ProgressTransform::new(total, Box::new(f)))
+    }
+
     /// Chain a [`Transform`] that can in-place map the data as it streams.
     /// Unlike [`Data::chain_try_inplace_map()`], this version assumes the
     /// mapper is infallible.
@@ -65,12 +65,21 @@ pub type BaseReader<'r> = Take<Chain<Cursor<Vec<u8>>, RawReader<'r>>>;
 /// Direct reader to the underlying data stream. Not limited in any manner.
 pub type RawReader<'r> = StreamReader<RawStream<'r>, Bytes>;
 
+/// A type-erased request body from an external server or runtime.
+pub type BoxedBody = Pin<Box<dyn Body<Data = Bytes, Error = io::Error> + Send + 'static>>;
+
+/// A type-erased reader producing request body data.
+pub type BoxedReader<'r> = tokio_util::io::ReaderStream<Pin<Box<dyn AsyncRead + Send + 'r>>>;
+
 /// Raw underlying data stream.
 pub enum RawStream<'r> {
     Empty,
     Body(HyperBody),
+    Boxed(BoxedBody),
+    Reader(BoxedReader<'r>),
     #[cfg(feature = "http3-preview")]
     H3Body(crate::listener::Cancellable<crate::listener::quic::QuicRx>),
+    #[cfg(feature = "multipart")]
     Multipart(multer::Field<'r>),
 }
 
@@ -127,7 +136,7 @@ impl<'r> DataStream<'r> {
     }
 
     /// Whether a previous read exhausted the set limit _and then some_.
-    async fn limit_exceeded(&mut self) -> io::Result<bool> {
+    pub(crate) async fn limit_exceeded(&mut self) -> io::Result<bool> {
         let base = self.base_mut();
 
         #[cold]
@@ -346,8 +355,15 @@ impl Stream for RawStream<'_> {
                     .map_ok(|frame| frame.into_data().unwrap_or_else(|_| Bytes::new()))
                     .map_err(io::Error::other)
             },
+            RawStream::Boxed(body) => {
+                body.as_mut()
+                    .poll_frame(cx)
+                    .map_ok(|frame| frame.into_data().unwrap_or_else(|_| Bytes::new()))
+            },
+            RawStream::Reader(reader) => Pin::new(reader).poll_next(cx),
             #[cfg(feature = "http3-preview")]
             RawStream::H3Body(stream) => Pin::new(stream).poll_next(cx),
+            #[cfg(feature = "multipart")]
             RawStream::Multipart(s) => Pin::new(s).poll_next(cx).map_err(io::Error::other),
             RawStream::Empty => Poll::Ready(None),
         }
@@ -360,8 +376,15 @@ impl Stream for RawStream<'_> {
                 let (lower, upper) = (hint.lower(), hint.upper());
                 (lower as usize, upper.map(|x| x as usize))
             },
+            RawStream::Boxed(body) => {
+                let hint = body.size_hint();
+                let (lower, upper) = (hint.lower(), hint.upper());
+                (lower as usize, upper.map(|x| x as usize))
+            },
+            RawStream::Reader(reader) => reader.size_hint(),
             #[cfg(feature = "http3-preview")]
             RawStream::H3Body(_) => (0, Some(0)),
+            #[cfg(feature = "multipart")]
             RawStream::Multipart(mp) => mp.size_hint(),
             RawStream::Empty => (0, Some(0)),
         }
@@ -373,8 +396,11 @@ impl std::fmt::Display for RawStream<'_> {
         match self {
             RawStream::Empty => f.write_str("empty stream"),
             RawStream::Body(_) => f.write_str("request body"),
+            RawStream::Boxed(_) => f.write_str("external request body"),
+            RawStream::Reader(_) => f.write_str("transformed request body"),
             #[cfg(feature = "http3-preview")]
             RawStream::H3Body(_) => f.write_str("http3 quic stream"),
+            #[cfg(feature = "multipart")]
             RawStream::Multipart(_) => f.write_str("multipart form field"),
         }
     }
@@ -386,6 +412,18 @@ impl<'r> From<HyperBody> for RawStream<'r> {
     }
 }
 
+impl<'r> From<BoxedBody> for RawStream<'r> {
+    fn from(value: BoxedBody) -> Self {
+        Self::Boxed(value)
+    }
+}
+
+impl<'r> From<BoxedReader<'r>> for RawStream<'r> {
+    fn from(value: BoxedReader<'r>) -> Self {
+        Self::Reader(value)
+    }
+}
+
 #[cfg(feature = "http3-preview")]
 impl<'r> From<crate::listener::Cancellable<crate::listener::quic::QuicRx>> for RawStream<'r> {
     fn from(value: crate::listener::Cancellable<crate::listener::quic::QuicRx>) -> Self {
@@ -393,6 +431,7 @@ impl<'r> From<crate::listener::Cancellable<crate::listener::quic::QuicRx>> for R
     }
 }
 
+#[cfg(feature = "multipart")]
 impl<'r> From<multer::Field<'r>> for RawStream<'r> {
     fn from(value: multer::Field<'r>) -> Self {
         Self::Multipart(value)
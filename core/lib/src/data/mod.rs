@@ -8,7 +8,9 @@ mod from_data;
 mod limits;
 mod io_stream;
 mod transform;
+mod progress;
 mod peekable;
+mod buffer;
 
 pub use self::data::Data;
 pub use self::data_stream::DataStream;
@@ -18,5 +20,7 @@ pub use self::capped::{N, Capped};
 pub use self::io_stream::{IoHandler, IoStream};
 pub use ubyte::{ByteUnit, ToByteUnit};
 pub use self::transform::{Transform, TransformBuf};
+pub use self::progress::{Progress, ProgressTracker};
+pub use self::buffer::BufferPolicy;
 
-pub(crate) use self::data_stream::RawStream;
+pub(crate) use self::data_stream::{RawStream, BoxedBody};
@@ -0,0 +1,211 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::HashMap;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tokio::io::ReadBuf;
+use tokio::sync::watch;
+
+use crate::data::{Data, Transform, TransformBuf};
+
+/// A snapshot of the progress of reading a request body.
+///
+/// Reported by transforms chained via [`Data::chain_progress()`] and by
+/// [`ProgressTracker`] subscriptions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    read: u64,
+    total: Option<u64>,
+    elapsed: Duration,
+    complete: bool,
+}
+
+impl Progress {
+    fn new(total: Option<u64>) -> Self {
+        Progress { read: 0, total, elapsed: Duration::ZERO, complete: false }
+    }
+
+    /// The number of bytes read so far.
+    pub fn read(&self) -> u64 {
+        self.read
+    }
+
+    /// The total number of bytes expected, if known.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// The time elapsed between the progress transform being chained and this
+    /// snapshot.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The average read rate in bytes per second.
+    pub fn rate(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.read as f64 / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// The fraction, between `0.0` and `1.0`, of the total read so far, if the
+    /// total is known.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total? {
+            0 => Some(1.0),
+            total => Some((self.read as f64 / total as f64).min(1.0)),
+        }
+    }
+
+    /// Whether the body has been read completely.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+}
+
+type Callback = Box<dyn FnMut(&Progress) + Send + Sync + 'static>;
+
+pub(crate) struct ProgressTransform {
+    progress: Progress,
+    started: Instant,
+    callback: Callback,
+}
+
+impl ProgressTransform {
+    pub(crate) fn new(total: Option<u64>, callback: Callback) -> Self {
+        ProgressTransform { progress: Progress::new(total), started: Instant::now(), callback }
+    }
+}
+
+impl Transform for ProgressTransform {
+    fn transform(mut self: Pin<&mut Self>, buf: &mut TransformBuf<'_, '_>) -> io::Result<()> {
+        self.progress.read += buf.fresh().len() as u64;
+        self.progress.elapsed = self.started.elapsed();
+        let this = &mut *self;
+        (this.callback)(&this.progress);
+        Ok(())
+    }
+
+    fn poll_finish(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if !self.progress.complete {
+            self.progress.complete = true;
+            self.progress.elapsed = self.started.elapsed();
+            let this = &mut *self;
+            (this.callback)(&this.progress);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Publishes the progress of request bodies being read, keyed by an upload ID.
+///
+/// A `ProgressTracker` lets one request observe the progress of another: an
+/// upload handler [tracks](ProgressTracker::track()) its body under an ID
+/// known to the client, and a second handler, typically serving an
+/// [`EventStream`](crate::response::stream::EventStream) or a WebSocket,
+/// [subscribes](ProgressTracker::subscribe()) to updates for the same ID to
+/// drive a progress bar.
+///
+/// An ID is tracked from the call to `track()` until the tracked `Data` is
+/// dropped, usually right after it is read. Subscriptions end when tracking
+/// ends.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket::data::{Data, ToByteUnit, ProgressTracker};
+/// use rocket::response::stream::{Event, EventStream};
+///
+/// #[post("/upload/<id>", data = "<data>")]
+/// async fn upload(id: &str, mut data: Data<'_>, tracker: &State<ProgressTracker>) {
+///     tracker.track(id, &mut data, None);
+///     let _file = data.open(128.mebibytes()).into_bytes().await;
+///     /* store the file... */
+/// }
+///
+/// #[get("/upload/<id>/progress")]
+/// fn progress(id: &str, tracker: &State<ProgressTracker>) -> Option<EventStream![]> {
+///     let mut updates = tracker.subscribe(id)?;
+///     Some(EventStream! {
+///         while updates.changed().await.is_ok() {
+///             let progress = *updates.borrow_and_update();
+///             yield Event::data(progress.read().to_string());
+///         }
+///     })
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .manage(ProgressTracker::new())
+///         .mount("/", routes![upload, progress])
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker {
+    uploads: Arc<Mutex<HashMap<String, (u64, watch::Receiver<Progress>)>>>,
+    next: Arc<AtomicU64>,
+}
+
+/// Publishes updates for one tracked upload; stops tracking when dropped.
+struct Tracked {
+    id: String,
+    generation: u64,
+    sender: watch::Sender<Progress>,
+    uploads: Arc<Mutex<HashMap<String, (u64, watch::Receiver<Progress>)>>>,
+}
+
+impl ProgressTracker {
+    /// Creates a new tracker with no tracked uploads.
+    pub fn new() -> Self {
+        ProgressTracker::default()
+    }
+
+    /// Tracks the progress of reading `data` under `id`, replacing any
+    /// existing upload tracked under `id`. `total` is the expected size of the
+    /// body, if known, typically from the request's `Content-Length`.
+    pub fn track<I: Into<String>>(&self, id: I, data: &mut Data<'_>, total: Option<u64>) {
+        let id = id.into();
+        let generation = self.next.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = watch::channel(Progress::new(total));
+        self.uploads.lock().insert(id.clone(), (generation, receiver));
+
+        let tracked = Tracked { id, generation, sender, uploads: self.uploads.clone() };
+        data.chain_progress(total, move |progress| {
+            let _ = tracked.sender.send(*progress);
+        });
+    }
+
+    /// Subscribes to progress updates for the upload tracked under `id`, if
+    /// any. The receiver's `changed()` method returns an error once tracking
+    /// ends.
+    pub fn subscribe(&self, id: &str) -> Option<watch::Receiver<Progress>> {
+        self.uploads.lock().get(id).map(|(_, receiver)| receiver.clone())
+    }
+
+    /// Returns the latest progress of the upload tracked under `id`, if any.
+    pub fn get(&self, id: &str) -> Option<Progress> {
+        self.uploads.lock().get(id).map(|(_, receiver)| *receiver.borrow())
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        let mut uploads = self.uploads.lock();
+        if uploads.get(&self.id).map_or(false, |(gen, _)| *gen == self.generation) {
+            uploads.remove(&self.id);
+        }
+    }
+}
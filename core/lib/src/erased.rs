@@ -2,6 +2,7 @@ use std::io;
 use std::mem::transmute;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::task::{Poll, Context};
 
 use futures::future::BoxFuture;
@@ -10,6 +11,7 @@ use tokio::io::{AsyncRead, ReadBuf};
 
 use crate::data::{Data, IoHandler, RawStream};
 use crate::{Request, Response, Rocket, Orbit};
+use crate::response::Completion;
 
 // TODO: Magic with trait async fn to get rid of the box pin.
 // TODO: Write safety proofs.
@@ -39,10 +41,21 @@ pub struct ErasedResponse {
     // XXX: SAFETY: This (dependent) field must come first due to drop order!
     response: Response<'static>,
     _request: Arc<ErasedRequest>,
+    completion: Option<Completion>,
 }
 
 impl Drop for ErasedResponse {
-    fn drop(&mut self) { }
+    fn drop(&mut self) {
+        let shared = &self._request.request.state.shared;
+        let read = shared.bytes_read.load(Ordering::Relaxed);
+        let written = shared.bytes_written.load(Ordering::Relaxed);
+        self._request._rocket.load.record_bytes(read, written);
+        let completion = self.completion
+            .or_else(|| Completion::of_bodiless(&self._request.request, &self.response))
+            .unwrap_or(Completion::Aborted);
+
+        shared.completion.run(completion, written);
+    }
 }
 
 pub struct ErasedIoHandler {
@@ -122,6 +135,7 @@ impl ErasedRequest {
         ErasedResponse {
             _request: parent,
             response,
+            completion: None,
         }
     }
 }
@@ -186,6 +200,15 @@ impl AsyncRead for ErasedResponse {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        self.get_mut().with_inner_mut(|r| Pin::new(r.body_mut()).poll_read(cx, buf))
+        let this = self.get_mut();
+        let init_fill = buf.filled().len();
+        let result = this.with_inner_mut(|r| Pin::new(r.body_mut()).poll_read(cx, buf));
+        let written = (buf.filled().len() - init_fill) as u64;
+        this._request.request.state.shared.bytes_written.fetch_add(written, Ordering::Relaxed);
+        if this.completion.is_none() {
+            this.completion = Completion::of_read(&result, init_fill, buf);
+        }
+
+        result
     }
 }
@@ -1,5 +1,6 @@
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
 use crate::fairing::{Fairing, Info, Kind};
+use crate::request::ConnectionInfo;
 
 #[derive(Default)]
 pub struct Fairings {
@@ -7,12 +8,16 @@ pub struct Fairings {
     all_fairings: Vec<Box<dyn Fairing>>,
     // Ignite fairings that have failed.
     failures: Vec<Info>,
+    // Names of fairings that are removed and ignored when attached.
+    disabled: Vec<String>,
     // The number of ignite fairings from `self.ignite` we've run.
     num_ignited: usize,
     // The vectors below hold indices into `all_fairings`.
     ignite: Vec<usize>,
     liftoff: Vec<usize>,
+    connection: Vec<usize>,
     request: Vec<usize>,
+    data: Vec<usize>,
     response: Vec<usize>,
     shutdown: Vec<usize>,
 }
@@ -41,7 +46,9 @@ impl Fairings {
     pub fn active(&self) -> impl Iterator<Item = &usize> {
         self.ignite.iter()
             .chain(self.liftoff.iter())
+            .chain(self.connection.iter())
             .chain(self.request.iter())
+            .chain(self.data.iter())
             .chain(self.response.iter())
             .chain(self.shutdown.iter())
     }
@@ -61,9 +68,47 @@ impl Fairings {
         iter!(self, self.unique_active()).map(|v| v.1).collect()
     }
 
+    /// Removes the fairing at index `i` from all active sets.
+    fn deactivate(&mut self, i: usize) {
+        // Finds `k` in `from` and removes it if it's there.
+        let remove = |k: usize, from: &mut Vec<usize>| {
+            if let Ok(j) = from.binary_search(&k) {
+                from.remove(j);
+            }
+        };
+
+        remove(i, &mut self.ignite);
+        remove(i, &mut self.liftoff);
+        remove(i, &mut self.connection);
+        remove(i, &mut self.request);
+        remove(i, &mut self.data);
+        remove(i, &mut self.response);
+        remove(i, &mut self.shutdown);
+    }
+
+    /// Removes every active fairing named `name`. Fairings named `name` that
+    /// are attached later are ignored.
+    pub fn disable(&mut self, name: String) {
+        let matching: Vec<usize> = iter!(self, self.unique_active())
+            .filter(|(_, f)| f.info().name == name)
+            .map(|(i, _)| i)
+            .collect();
+
+        for i in matching {
+            self.deactivate(i);
+        }
+
+        self.disabled.push(name);
+    }
+
     pub fn add(&mut self, fairing: Box<dyn Fairing>) {
         let this = &fairing;
         let this_info = this.info();
+        if self.disabled.iter().any(|name| name == this_info.name) {
+            debug!(name = this_info.name, "ignoring disabled fairing");
+            return;
+        }
+
         if this_info.kind.is(Kind::Singleton) {
             // If we already ran a duplicate on ignite, then fail immediately.
             // There is no way to uphold the "only run last singleton" promise.
@@ -83,13 +128,6 @@ impl Fairings {
                 }
             }
 
-            // Finds `k` in `from` and removes it if it's there.
-            let remove = |k: usize, from: &mut Vec<usize>| {
-                if let Ok(j) = from.binary_search(&k) {
-                    from.remove(j);
-                }
-            };
-
             // Collect all of the active duplicates.
             let mut dups: Vec<usize> = iter!(self, self.unique_active())
                 .filter(|(_, f)| f.type_id() == this.type_id())
@@ -99,11 +137,7 @@ impl Fairings {
             // Reverse the dup indices so `remove` is stable given shifts.
             dups.sort(); dups.dedup(); dups.reverse();
             for i in dups {
-                remove(i, &mut self.ignite);
-                remove(i, &mut self.liftoff);
-                remove(i, &mut self.request);
-                remove(i, &mut self.response);
-                remove(i, &mut self.shutdown);
+                self.deactivate(i);
             }
         }
 
@@ -111,7 +145,9 @@ impl Fairings {
         self.all_fairings.push(fairing);
         if this_info.kind.is(Kind::Ignite) { self.ignite.push(index); }
         if this_info.kind.is(Kind::Liftoff) { self.liftoff.push(index); }
+        if this_info.kind.is(Kind::Connection) { self.connection.push(index); }
         if this_info.kind.is(Kind::Request) { self.request.push(index); }
+        if this_info.kind.is(Kind::Data) { self.data.push(index); }
         if this_info.kind.is(Kind::Response) { self.response.push(index); }
         if this_info.kind.is(Kind::Shutdown) { self.shutdown.push(index); }
     }
@@ -154,6 +190,20 @@ impl Fairings {
         futures::future::join_all(liftoff_futures).await;
     }
 
+    #[inline(always)]
+    pub async fn handle_connect(&self, conn: ConnectionInfo<'_>) {
+        for fairing in iter!(self.connection) {
+            fairing.on_connect(conn).await
+        }
+    }
+
+    #[inline(always)]
+    pub async fn handle_disconnect(&self, conn: ConnectionInfo<'_>) {
+        for fairing in iter!(self.connection) {
+            fairing.on_disconnect(conn).await
+        }
+    }
+
     #[inline(always)]
     pub async fn handle_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
         for fairing in iter!(self.request) {
@@ -161,6 +211,14 @@ impl Fairings {
         }
     }
 
+    #[inline(always)]
+    pub async fn handle_data(&self, req: &Request<'_>, data: &mut Data<'_>) {
+        for fairing in iter!(self.data) {
+            let original = std::mem::replace(data, Data::local(vec![]));
+            *data = fairing.on_data(req, original).await;
+        }
+    }
+
     #[inline(always)]
     pub async fn handle_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         for fairing in iter!(self.response) {
@@ -212,7 +270,9 @@ impl std::fmt::Debug for Fairings {
         f.debug_struct("Fairings")
             .field("launch", &debug_info(iter!(self.ignite)))
             .field("liftoff", &debug_info(iter!(self.liftoff)))
+            .field("connection", &debug_info(iter!(self.connection)))
             .field("request", &debug_info(iter!(self.request)))
+            .field("data", &debug_info(iter!(self.data)))
             .field("response", &debug_info(iter!(self.response)))
             .field("shutdown", &debug_info(iter!(self.shutdown)))
             .finish()
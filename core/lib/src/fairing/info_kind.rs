@@ -38,7 +38,9 @@ pub struct Info {
 ///
 ///   * Ignite
 ///   * Liftoff
+///   * Connection
 ///   * Request
+///   * Data
 ///   * Response
 ///   * Shutdown
 ///
@@ -74,6 +76,12 @@ impl Kind {
     /// [singleton](crate::fairing::Fairing#singletons) fairing.
     pub const Singleton: Kind = Kind(1 << 5);
 
+    /// `Kind` flag representing a request for a 'data' callback.
+    pub const Data: Kind = Kind(1 << 6);
+
+    /// `Kind` flag representing a request for 'connection' callbacks.
+    pub const Connection: Kind = Kind(1 << 7);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the kinds in `other` are also in `self`.
     ///
@@ -143,7 +151,9 @@ impl std::fmt::Display for Kind {
 
         write("ignite", Kind::Ignite)?;
         write("liftoff", Kind::Liftoff)?;
+        write("connection", Kind::Connection)?;
         write("request", Kind::Request)?;
+        write("data", Kind::Data)?;
         write("response", Kind::Response)?;
         write("shutdown", Kind::Shutdown)?;
         write("singleton", Kind::Singleton)
@@ -1,4 +1,5 @@
-//! Fairings: callbacks at launch, liftoff, request, and response time.
+//! Fairings: callbacks at launch, liftoff, connection, request, data, and
+//! response time.
 //!
 //! Fairings allow for structured interposition at various points in the
 //! application lifetime. Fairings can be seen as a restricted form of
@@ -52,6 +53,7 @@
 use std::any::Any;
 
 use crate::{Rocket, Request, Response, Data, Build, Orbit};
+use crate::request::ConnectionInfo;
 
 mod fairings;
 mod ad_hoc;
@@ -101,8 +103,8 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// ## Fairing Callbacks
 ///
-/// There are five kinds of fairing callbacks: launch, liftoff, request,
-/// response, and shutdown. A fairing can request any combination of these
+/// There are seven kinds of fairing callbacks: launch, liftoff, connection,
+/// request, data, response, and shutdown. A fairing can request any combination of these
 /// callbacks through the `kind` field of the [`Info`] structure returned from
 /// the `info` method. Rocket will only invoke the callbacks identified in the
 /// fairing's [`Kind`].
@@ -140,6 +142,30 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     Liftoff fairings are run concurrently; resolution of all fairings is
 ///     awaited before resuming request serving.
 ///
+///   * **<a name="connection">Connection</a> (`on_connect`, `on_disconnect`)**
+///
+///     A connection callback, represented by the [`Fairing::on_connect()`]
+///     and [`Fairing::on_disconnect()`] methods, is called when a client
+///     connection is opened and closed, respectively. `on_connect` is called
+///     after the connection is accepted, and any TLS handshake has completed,
+///     but before any request on the connection is read. `on_disconnect` is
+///     called after the final response on the connection has been written or
+///     the connection has otherwise terminated. Both receive the connection's
+///     [`ConnectionInfo`], including its [`ConnectionState`], which is shared
+///     with every request on the connection. A connection callback can thus
+///     initialize per-connection state that request guards later read, or
+///     record per-connection metrics when the connection closes.
+///
+///     Connection callbacks are executed in `attach()` order. A connection
+///     that fails before it is accepted, as during a failed TLS handshake, does
+///     not invoke connection callbacks. Connection callbacks are not invoked
+///     for requests dispatched via a [local client](crate::local). Over HTTP/3,
+///     callbacks are invoked once per QUIC connection, not once per request
+///     stream, and `on_disconnect` is called after the connection closes and
+///     every in-flight response on it has finished.
+///
+///     [`ConnectionState`]: crate::request::ConnectionState
+///
 ///   * **<a name="request">Request</a> (`on_request`)**
 ///
 ///     A request callback, represented by the [`Fairing::on_request()`] method,
@@ -153,6 +179,26 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     via response callbacks. Any modifications to a request are persisted and
 ///     can potentially alter how a request is routed.
 ///
+///   * **<a name="data">Data</a> (`on_data`)**
+///
+///     A data callback, represented by the [`Fairing::on_data()`] method, is
+///     called after all request callbacks have run and before the request is
+///     routed. It receives the request's [`Data`] by value and returns the
+///     `Data` that will be seen by request processing from then on, including
+///     by [data guards]. A data callback can thus wrap the incoming body, for
+///     instance to decompress or decrypt it, or replace it entirely. Use
+///     [`Data::from_reader()`] to construct the returned `Data` from a reader.
+///
+///     Data callbacks are executed in `attach()` order, each receiving the
+///     `Data` returned by the previous callback. A fairing that opens the
+///     incoming `Data` chooses the limit for the bytes _it_ reads, typically
+///     from [`Request::limits()`]; data guards apply their own limits to the
+///     bytes read from the `Data` the last callback returned. A fairing that
+///     expands data, by decompressing it, for example, should thus limit the
+///     incoming, compressed data, while data guards continue to limit the
+///     expanded data. [Transforms](Data::chain_transform()) chained before a
+///     data callback runs apply to the data the callback reads.
+///
 ///   * **<a name="response">Response</a> (`on_response`)**
 ///
 ///     A response callback, represented by the [`Fairing::on_response()`]
@@ -168,6 +214,10 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     request. Additionally, Rocket will automatically strip the body for
 ///     `HEAD` requests _after_ response fairings have run.
 ///
+///     A response callback can wrap or replace the response body with
+///     [`Response::map_body()`], which defines how transformed bodies interact
+///     with `Content-Length`, content encodings, and other response fairings.
+///
 ///   * **<a name="shutdown">Shutdown</a> (`on_shutdown`)**
 ///
 ///     A shutdown callback, represented by the [`Fairing::on_shutdown()`]
@@ -209,9 +259,11 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///
 /// A `Fairing` implementation has one required method: [`info`]. A `Fairing`
 /// can also implement any of the available callbacks: `on_ignite`, `on_liftoff`,
-/// `on_request`, and `on_response`. A `Fairing` _must_ set the appropriate
-/// callback kind in the `kind` field of the returned `Info` structure from
-/// [`info`] for a callback to actually be called by Rocket.
+/// `on_connect`, `on_disconnect`, `on_request`, `on_data`, `on_response`, and
+/// `on_shutdown`. A `Fairing`
+/// _must_ set the appropriate callback kind in the `kind` field of the returned
+/// `Info` structure from [`info`] for a callback to actually be called by
+/// Rocket.
 ///
 /// ## Fairing `Info`
 ///
@@ -251,6 +303,7 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 /// ```rust
 /// use rocket::{Rocket, Request, Data, Response, Build, Orbit};
 /// use rocket::fairing::{self, Fairing, Info, Kind};
+/// use rocket::request::ConnectionInfo;
 ///
 /// # struct MyType;
 /// #[rocket::async_trait]
@@ -270,11 +323,26 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///         # unimplemented!()
 ///     }
 ///
+///     async fn on_connect(&self, conn: ConnectionInfo<'_>) {
+///         /* ... */
+///         # unimplemented!()
+///     }
+///
+///     async fn on_disconnect(&self, conn: ConnectionInfo<'_>) {
+///         /* ... */
+///         # unimplemented!()
+///     }
+///
 ///     async fn on_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
 ///         /* ... */
 ///         # unimplemented!()
 ///     }
 ///
+///     async fn on_data<'r>(&self, req: &Request<'_>, data: Data<'r>) -> Data<'r> {
+///         /* ... */
+///         # unimplemented!()
+///     }
+///
 ///     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
 ///         /* ... */
 ///         # unimplemented!()
@@ -356,6 +424,11 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 /// Fairings can use [request-local state] to persist or carry data between
 /// requests and responses, or to pass data to a request guard.
 ///
+/// Values computed by a request fairing can also be stored in the request's
+/// typed [`Extensions`](crate::request::Extensions) via
+/// [`Request::extensions()`]. Unlike the local cache, extensions never compute
+/// a missing value, so a guard can tell whether the fairing ran at all.
+///
 /// As an example, the following fairing uses request-local state to time
 /// requests, setting an `X-Response-Time` header on all responses with the
 /// elapsed time. It also exposes the start time of a request via a `StartTime`
@@ -489,6 +562,74 @@ pub trait Fairing: Send + Sync + AsAny + 'static {
     /// The default implementation of this method does nothing.
     async fn on_liftoff(&self, _rocket: &Rocket<Orbit>) { }
 
+    /// The connection open callback.
+    ///
+    /// See [Fairing Callbacks](#connection) for complete semantics.
+    ///
+    /// This method is called when a client connection is opened if
+    /// `Kind::Connection` is in the `kind` field of the `Info` structure for
+    /// this fairing. The `ConnectionInfo` parameter describes the connection.
+    ///
+    /// # Example
+    ///
+    /// A fairing that counts the requests served on each connection and logs
+    /// the count when the connection closes:
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use rocket::{Request, Data};
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    /// use rocket::request::ConnectionInfo;
+    ///
+    /// #[derive(Default)]
+    /// struct Served(AtomicUsize);
+    ///
+    /// struct ConnectionMetrics;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for ConnectionMetrics {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Connection Metrics", kind: Kind::Connection | Kind::Request }
+    ///     }
+    ///
+    ///     async fn on_connect(&self, conn: ConnectionInfo<'_>) {
+    ///         conn.state().insert(Served::default());
+    ///     }
+    ///
+    ///     async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+    ///         let state = req.connection_info().state();
+    ///         let served = state.get_or_insert_with(Served::default);
+    ///         served.0.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    ///
+    ///     async fn on_disconnect(&self, conn: ConnectionInfo<'_>) {
+    ///         if let Some(served) = conn.state().get::<Served>() {
+    ///             let count = served.0.load(Ordering::Relaxed);
+    ///             println!("{:?} served {count} requests", conn.remote());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    async fn on_connect(&self, _conn: ConnectionInfo<'_>) { }
+
+    /// The connection close callback.
+    ///
+    /// See [Fairing Callbacks](#connection) for complete semantics.
+    ///
+    /// This method is called when a client connection is closed if
+    /// `Kind::Connection` is in the `kind` field of the `Info` structure for
+    /// this fairing. The `ConnectionInfo` parameter describes the connection.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    async fn on_disconnect(&self, _conn: ConnectionInfo<'_>) { }
+
     /// The request callback.
     ///
     /// See [Fairing Callbacks](#request) for complete semantics.
@@ -503,6 +644,59 @@ pub trait Fairing: Send + Sync + AsAny + 'static {
     /// The default implementation of this method does nothing.
     async fn on_request(&self, _req: &mut Request<'_>, _data: &mut Data<'_>) {}
 
+    /// The data callback. Returns the `Data` to use for the remainder of the
+    /// request's processing.
+    ///
+    /// See [Fairing Callbacks](#data) for complete semantics.
+    ///
+    /// This method is called after request callbacks have run if `Kind::Data`
+    /// is in the `kind` field of the `Info` structure for this fairing. The
+    /// `&Request` parameter is the incoming request, and the `Data` parameter
+    /// is the incoming data as returned by the previous data callback, if any.
+    ///
+    /// # Example
+    ///
+    /// A fairing that decrypts request bodies marked as encrypted. The
+    /// encrypted data is buffered and limited to 1MiB; data guards apply their
+    /// own limits to the decrypted data:
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use rocket::{Request, Data};
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    ///
+    /// # fn decrypt(bytes: &[u8]) -> Vec<u8> { bytes.to_vec() }
+    /// struct Decrypt;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for Decrypt {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Decrypt", kind: Kind::Data }
+    ///     }
+    ///
+    ///     async fn on_data<'r>(&self, req: &Request<'_>, data: Data<'r>) -> Data<'r> {
+    ///         if req.headers().get_one("X-Encrypted").is_none() {
+    ///             return data;
+    ///         }
+    ///
+    ///         // Data guards see an empty body if the data is too large.
+    ///         let plaintext = match data.open(1.mebibytes()).into_bytes().await {
+    ///             Ok(bytes) if bytes.is_complete() => decrypt(&bytes),
+    ///             _ => vec![],
+    ///         };
+    ///
+    ///         Data::from_reader(Cursor::new(plaintext))
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method returns `data` unchanged.
+    async fn on_data<'r>(&self, _req: &Request<'_>, data: Data<'r>) -> Data<'r> { data }
+
     /// The response callback.
     ///
     /// See [Fairing Callbacks](#response) for complete semantics.
@@ -555,11 +749,26 @@ impl<T: Fairing + ?Sized> Fairing for std::sync::Arc<T> {
         (self as &T).on_liftoff(rocket).await
     }
 
+    #[inline]
+    async fn on_connect(&self, conn: ConnectionInfo<'_>) {
+        (self as &T).on_connect(conn).await
+    }
+
+    #[inline]
+    async fn on_disconnect(&self, conn: ConnectionInfo<'_>) {
+        (self as &T).on_disconnect(conn).await
+    }
+
     #[inline]
     async fn on_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
         (self as &T).on_request(req, data).await
     }
 
+    #[inline]
+    async fn on_data<'r>(&self, req: &Request<'_>, data: Data<'r>) -> Data<'r> {
+        (self as &T).on_data(req, data).await
+    }
+
     #[inline]
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
         (self as &T).on_response(req, res).await
@@ -0,0 +1,173 @@
+use std::ops::{Bound, RangeBounds};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::http::ContentType;
+use crate::form::{FromForm, Options, validate::Len};
+
+/// Trait implemented by [`#[derive(FromForm)]`](derive@crate::FromForm) to
+/// export the constraints declared on a form's fields.
+///
+/// The exported [`Constraints`] mirror the field validators understood by
+/// browsers so that templates can render matching HTML5 validation attributes
+/// or client-side validators from the same declarations that validate the
+/// form on the server. See [`FieldConstraints`] for the recognized validators.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::FormConstraints;
+///
+/// #[derive(FromForm)]
+/// struct Signup<'r> {
+///     #[field(validate = len(3..=32))]
+///     username: &'r str,
+///     #[field(validate = range(13..))]
+///     age: u8,
+///     newsletter: bool,
+/// }
+///
+/// let constraints = Signup::constraints();
+/// let username = constraints.get("username").unwrap();
+/// assert!(username.required);
+/// assert_eq!(username.min_length, Some(3));
+/// assert_eq!(username.max_length, Some(32));
+/// assert_eq!(constraints.get("age").unwrap().min, Some(13));
+/// assert!(!constraints.get("newsletter").unwrap().required);
+/// ```
+pub trait FormConstraints {
+    /// Returns the constraints declared on the fields of `Self`.
+    fn constraints() -> Constraints;
+}
+
+/// The constraints of a form's fields, keyed by field name.
+///
+/// Returned by [`FormConstraints::constraints()`]. Serializes as a map from
+/// field names to [`FieldConstraints`], in declaration order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Constraints {
+    fields: IndexMap<String, FieldConstraints>,
+}
+
+/// The constraints of a single form field.
+///
+/// Constraints are derived from the field's type and its validators:
+///
+/// | constraint                  | derived from                                  |
+/// |-----------------------------|-----------------------------------------------|
+/// | `required`                  | field type has no default and no `default`    |
+/// | `min_length`, `max_length`  | `validate = len(range)`                       |
+/// | `min`, `max`                | `validate = range(range)`                     |
+/// | `accept`                    | `validate = ext(content_type)`                |
+///
+/// Validators may be followed by method calls such as `.or_else(msg!(..))`.
+/// Other validators, including `async` validators, are not exported. Bounds
+/// are inclusive, matching the corresponding HTML5 attributes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FieldConstraints {
+    /// Whether the field must be present.
+    pub required: bool,
+    /// The minimum length of the field's value.
+    pub min_length: Option<u64>,
+    /// The maximum length of the field's value.
+    pub max_length: Option<u64>,
+    /// The minimum value of the field.
+    pub min: Option<isize>,
+    /// The maximum value of the field.
+    pub max: Option<isize>,
+    /// The accepted content type.
+    pub accept: Option<String>,
+}
+
+impl Constraints {
+    /// Creates an empty set of constraints.
+    pub fn new() -> Self {
+        Constraints::default()
+    }
+
+    /// Returns the constraints of the field named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&FieldConstraints> {
+        self.fields.get(name)
+    }
+
+    /// Returns the constraints of the field named `name`, inserting default,
+    /// empty constraints if there are none.
+    pub fn field(&mut self, name: &str) -> &mut FieldConstraints {
+        self.fields.entry(name.into()).or_default()
+    }
+
+    /// Returns an iterator over field names and their constraints.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldConstraints)> {
+        self.fields.iter().map(|(name, c)| (name.as_str(), c))
+    }
+}
+
+/// Returns the inclusive `[start, end]` of `range`, with each bound converted
+/// via `f` and excluded bounds adjusted by one.
+fn inclusive<T, U, R>(range: &R, f: impl Fn(T) -> U) -> [Option<U>; 2]
+    where T: Copy, U: Step, R: RangeBounds<T>
+{
+    let start = match range.start_bound() {
+        Bound::Included(v) => Some(f(*v)),
+        Bound::Excluded(v) => Some(f(*v).next()),
+        Bound::Unbounded => None,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(v) => Some(f(*v)),
+        Bound::Excluded(v) => Some(f(*v).prev()),
+        Bound::Unbounded => None,
+    };
+
+    [start, end]
+}
+
+trait Step {
+    fn next(self) -> Self;
+    fn prev(self) -> Self;
+}
+
+macro_rules! impl_step {
+    ($($T:ty),*) => ($(
+        impl Step for $T {
+            fn next(self) -> Self { self.saturating_add(1) }
+            fn prev(self) -> Self { self.saturating_sub(1) }
+        }
+    )*)
+}
+
+impl_step!(u64, isize);
+
+impl FieldConstraints {
+    /// Returns `true` if a value of type `T` is required, that is, if `T` has
+    /// no [default](FromForm::default()) value.
+    pub fn is_required<'r, T: FromForm<'r>>() -> bool {
+        T::default(Options::Lenient).is_none()
+    }
+
+    /// Sets the minimum and maximum length from a `len()` validator's `range`
+    /// for a field of type `V`.
+    pub fn set_length<V, L, R>(&mut self, range: R)
+        where V: Len<L> + ?Sized, L: Copy, R: RangeBounds<L>
+    {
+        let [min, max] = inclusive(&range, V::len_into_u64);
+        self.min_length = min;
+        self.max_length = max;
+    }
+
+    /// Sets the minimum and maximum value from a `range()` validator's `range`.
+    pub fn set_range<R: RangeBounds<isize>>(&mut self, range: R) {
+        let [min, max] = inclusive(&range, |v: isize| v);
+        self.min = min;
+        self.max = max;
+    }
+
+    /// Sets the accepted content type from an `ext()` validator's
+    /// `content_type`.
+    pub fn set_accept(&mut self, content_type: ContentType) {
+        self.accept = Some(content_type.media_type().to_string());
+    }
+}
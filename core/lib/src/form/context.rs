@@ -1,6 +1,10 @@
+use std::borrow::{Borrow, Cow};
+use std::ops::Deref;
+
 use serde::Serialize;
 use indexmap::{IndexMap, IndexSet};
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::Status;
 
@@ -63,6 +67,10 @@ pub struct Contextual<'v, T> {
 /// their values recorded. All submitted field names, including data field
 /// names, can be retrieved via [`Context::fields()`].
 ///
+/// A context can also be created from an existing value via
+/// [`Context::from_value()`], so that a single template can render both a
+/// form pre-filled from a model and a submitted form with its errors.
+///
 /// # Field Errors
 ///
 /// # Serialization
@@ -81,7 +89,7 @@ pub struct Contextual<'v, T> {
 #[derive(Debug, Default, Serialize)]
 pub struct Context<'v> {
     errors: IndexMap<NameBuf<'v>, Errors<'v>>,
-    values: IndexMap<&'v Name, Vec<&'v str>>,
+    values: IndexMap<FieldName<'v>, Vec<Cow<'v, str>>>,
     data_fields: IndexSet<&'v Name>,
     form_errors: Errors<'v>,
     #[serde(skip)]
@@ -104,9 +112,9 @@ impl<'v> Context<'v> {
     ///     let field_names = form.context.fields();
     /// }
     /// ```
-    pub fn fields(&self) -> impl Iterator<Item = &'v Name> + '_ {
-        self.values.iter()
-            .map(|(name, _)| *name)
+    pub fn fields(&self) -> impl Iterator<Item = &Name> + '_ {
+        self.values.keys()
+            .map(|name| &**name)
             .chain(self.data_fields.iter().copied())
     }
 
@@ -129,8 +137,8 @@ impl<'v> Context<'v> {
     ///     let first_value_for_foo_bar = form.context.field_value("foo.bar");
     /// }
     /// ```
-    pub fn field_value<N: AsRef<Name>>(&self, name: N) -> Option<&'v str> {
-        self.values.get(name.as_ref())?.first().cloned()
+    pub fn field_value<N: AsRef<Name>>(&self, name: N) -> Option<&str> {
+        self.values.get(name.as_ref())?.first().map(|v| &**v)
     }
 
     /// Returns the values, if any, submitted for the _value_ field named
@@ -152,12 +160,12 @@ impl<'v> Context<'v> {
     ///     let values_for_foo_bar = form.context.field_values("foo.bar");
     /// }
     /// ```
-    pub fn field_values<N>(&self, name: N) -> impl Iterator<Item = &'v str> + '_
+    pub fn field_values<N>(&self, name: N) -> impl Iterator<Item = &str> + '_
         where N: AsRef<Name>
     {
         self.values
             .get(name.as_ref())
-            .map(|e| e.iter().cloned())
+            .map(|e| e.iter().map(|v| &**v))
             .into_iter()
             .flatten()
     }
@@ -348,6 +356,105 @@ impl<'v> Context<'v> {
     pub fn push_errors<E: Into<Errors<'v>>>(&mut self, errors: E) {
         errors.into().into_iter().for_each(|e| self.push_error(e))
     }
+
+    /// Creates a context with the fields and values of `value`, as if `value`
+    /// had been submitted as a form, and no errors.
+    ///
+    /// `value` must serialize as a `struct` or map. Field names are derived
+    /// as Rocket would parse them: nested structures and maps are joined with
+    /// `.`, sequences of scalars repeat the field name, and other sequences
+    /// are indexed as `name[i]`. `None` and unit values are omitted, and unit
+    /// enum variants are represented by their name. Returns an error if
+    /// `value` contains an unsupported value such as bytes or a non-unit enum
+    /// variant.
+    ///
+    /// This makes it possible to render edit forms pre-filled from a model
+    /// with the same template used to re-render a submitted form with errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::{Form, Context, Contextual};
+    /// use rocket::serde::Serialize;
+    ///
+    /// #[derive(Serialize, FromForm)]
+    /// #[serde(crate = "rocket::serde")]
+    /// struct User {
+    ///     name: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// # fn render(_: &Context<'_>) { }
+    /// #[get("/users/edit")]
+    /// fn edit() {
+    ///     let user = User { name: "Bob".into(), tags: vec!["a".into(), "b".into()] };
+    ///     let context = Context::from_value(&user).expect("serializable user");
+    ///     assert_eq!(context.field_value("name"), Some("Bob"));
+    ///     assert_eq!(context.field_values("tags").collect::<Vec<_>>(), ["a", "b"]);
+    ///     render(&context);
+    /// }
+    ///
+    /// #[post("/users/edit", data = "<form>")]
+    /// fn update(form: Form<Contextual<'_, User>>) {
+    ///     // On error, render the same template with `&form.context`.
+    ///     render(&form.context);
+    /// }
+    /// ```
+    pub fn from_value<T>(value: &T) -> std::result::Result<Self, Error<'v>>
+        where T: Serialize + ?Sized
+    {
+        let fields = super::ser::fields(value).map_err(|e| {
+            Error::from(ErrorKind::Custom(Status::InternalServerError, Box::new(e)))
+        })?;
+
+        let mut context = Context::default();
+        for (name, value) in fields {
+            let name = FieldName(Cow::Owned(name));
+            context.values.entry(name).or_default().push(Cow::Owned(value));
+        }
+
+        Ok(context)
+    }
+}
+
+/// The name of a field in a [`Context`], borrowed from a submitted form or
+/// owned when created via [`Context::from_value()`].
+#[derive(Debug, Clone)]
+struct FieldName<'v>(Cow<'v, str>);
+
+impl Deref for FieldName<'_> {
+    type Target = Name;
+
+    fn deref(&self) -> &Name {
+        Name::new(&*self.0)
+    }
+}
+
+impl Borrow<Name> for FieldName<'_> {
+    fn borrow(&self) -> &Name {
+        self
+    }
+}
+
+impl PartialEq for FieldName<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for FieldName<'_> { }
+
+impl std::hash::Hash for FieldName<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl Serialize for FieldName<'_> {
+    fn serialize<S: serde::Serializer>(&self, ser: S) -> std::result::Result<S::Ok, S::Error> {
+        (**self).serialize(ser)
+    }
 }
 
 impl<'f> From<Errors<'f>> for Context<'f> {
@@ -367,7 +474,8 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Contextual<'v, T> {
     }
 
     fn push_value((ref mut val_ctxt, ctxt): &mut Self::Context, field: ValueField<'v>) {
-        ctxt.values.entry(field.name.source()).or_default().push(field.value);
+        let name = FieldName(Cow::Borrowed(field.name.source().as_str()));
+        ctxt.values.entry(name).or_default().push(Cow::Borrowed(field.value));
         T::push_value(val_ctxt, field);
     }
 
@@ -391,4 +499,15 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Contextual<'v, T> {
 
         Ok(Contextual { value, context })
     }
+
+    async fn validate(mut this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        if let Some(value) = this.value.take() {
+            match T::validate(value, req).await {
+                Ok(value) => this.value = Some(value),
+                Err(errors) => this.context.push_errors(errors),
+            }
+        }
+
+        Ok(this)
+    }
 }
@@ -12,6 +12,7 @@ use serde::{Serialize, ser::{Serializer, SerializeStruct}};
 use crate::http::Status;
 use crate::form::name::{NameBuf, Name};
 use crate::data::ByteUnit;
+use crate::i18n::{self, Catalog, Locale};
 
 /// A collection of [`Error`]s.
 ///
@@ -198,6 +199,8 @@ pub enum ErrorKind<'v> {
     /// [`Status::UnprocessableEntity`] if one is not directly specified.
     Custom(Status, Box<dyn std::error::Error + Send>),
     /// An error while parsing a multipart form occurred.
+    #[cfg(feature = "multipart")]
+    #[cfg_attr(nightly, doc(cfg(feature = "multipart")))]
     Multipart(multer::Error),
     /// A string was invalid UTF-8.
     Utf8(Utf8Error),
@@ -303,6 +306,23 @@ impl<'v> Errors<'v> {
         }
     }
 
+    /// Prefixes the field name of each error in `self` with the key `parent`,
+    /// or sets it to `parent` if it is not set. Used by generated code to
+    /// attribute errors of nested forms.
+    #[doc(hidden)]
+    pub fn with_parent(mut self, parent: &str) -> Self {
+        for error in self.iter_mut() {
+            let name = match error.name.take() {
+                Some(name) => format!("{}.{}", parent, name),
+                None => parent.to_string(),
+            };
+
+            error.name = Some(name.into());
+        }
+
+        self
+    }
+
     /// Consumes `self` and returns a new `Errors` with each field value set to
     /// `value` if it was not already set.
     ///
@@ -723,12 +743,12 @@ impl<'v> Error<'v> {
     ///  ```
     pub fn status(&self) -> Status {
         use ErrorKind::*;
-        use multer::Error::*;
 
         match self.kind {
-            | InvalidLength { min: None, .. }
-            | Multipart(FieldSizeExceeded { .. })
-            | Multipart(StreamSizeExceeded { .. }) => Status::PayloadTooLarge,
+            InvalidLength { min: None, .. } => Status::PayloadTooLarge,
+            #[cfg(feature = "multipart")]
+            | Multipart(multer::Error::FieldSizeExceeded { .. })
+            | Multipart(multer::Error::StreamSizeExceeded { .. }) => Status::PayloadTooLarge,
             Unknown => Status::InternalServerError,
             Io(_) if self.entity == Entity::Form => Status::BadRequest,
             Custom(status, _) => status,
@@ -784,6 +804,7 @@ impl<'v, T: Into<ErrorKind<'v>>> From<T> for Error<'v> {
     }
 }
 
+#[cfg(feature = "multipart")]
 impl<'a> From<multer::Error> for Error<'a> {
     fn from(error: multer::Error) -> Self {
         use multer::Error::*;
@@ -811,64 +832,130 @@ impl<'a> From<multer::Error> for Error<'a> {
     }
 }
 
-impl fmt::Display for ErrorKind<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl ErrorKind<'_> {
+    /// Returns the default message for this error in the language of
+    /// `locale`, falling back to English for messages without a translation.
+    /// See [`i18n`](crate::i18n) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::{Form, Contextual};
+    /// use rocket::i18n::Locale;
+    ///
+    /// #[post("/", data = "<form>")]
+    /// fn submit(form: Form<Contextual<'_, String>>, locale: Locale<'_>) -> String {
+    ///     form.context.errors()
+    ///         .map(|e| e.kind.localized(&locale))
+    ///         .collect::<Vec<_>>()
+    ///         .join(", ")
+    /// }
+    /// ```
+    pub fn localized(&self, locale: &Locale<'_>) -> String {
+        let mut message = String::new();
+        let _ = self.write(&mut message, locale.catalog());
+        message
+    }
+
+    fn write<W: fmt::Write>(&self, w: &mut W, catalog: Option<&Catalog>) -> fmt::Result {
+        let mut msg = |key: &str, default: &str, args: &[(&str, &dyn fmt::Display)]| {
+            let template = catalog.and_then(|c| c.get(key)).unwrap_or(default);
+            w.write_str(&i18n::render(template, args))
+        };
+
         match self {
             ErrorKind::InvalidLength { min, max } => {
                 match (min, max) {
-                    (None, None) => write!(f, "invalid length: incomplete")?,
-                    (None, Some(k)) if *k < 1024 => write!(f, "length cannot exceed {}", k)?,
-                    (None, Some(k)) => write!(f, "size must not exceed {}", ByteUnit::from(*k))?,
-                    (Some(1), None) => write!(f, "cannot be empty")?,
-                    (Some(k), None) if *k < 1024 => write!(f, "expected at least {}", k)?,
-                    (Some(k), None) => write!(f, "size must be at least {}", ByteUnit::from(*k))?,
+                    (None, None) => {
+                        msg("form.length.incomplete", "invalid length: incomplete", &[])
+                    }
+                    (None, Some(k)) if *k < 1024 => {
+                        msg("form.length.max", "length cannot exceed {max}", &[("max", k)])
+                    }
+                    (None, Some(k)) => {
+                        let max = ByteUnit::from(*k);
+                        msg("form.size.max", "size must not exceed {max}", &[("max", &max)])
+                    }
+                    (Some(1), None) => msg("form.empty", "cannot be empty", &[]),
+                    (Some(k), None) if *k < 1024 => {
+                        msg("form.length.min", "expected at least {min}", &[("min", k)])
+                    }
+                    (Some(k), None) => {
+                        let min = ByteUnit::from(*k);
+                        msg("form.size.min", "size must be at least {min}", &[("min", &min)])
+                    }
                     (Some(i), Some(j)) if *i < 1024 && *j < 1024 => {
-                        write!(f, "length must be between {} and {}", i, j)?;
+                        msg("form.length.between", "length must be between {min} and {max}",
+                            &[("min", i), ("max", j)])
                     }
                     (Some(i), Some(j)) => {
                         let (i, j) = (ByteUnit::from(*i), ByteUnit::from(*j));
-                        write!(f, "size must be between {} and {}", i, j)?;
+                        msg("form.size.between", "size must be between {min} and {max}",
+                            &[("min", &i), ("max", &j)])
                     }
                 }
             }
             ErrorKind::InvalidChoice { choices } => {
                 match *choices.as_ref() {
-                    [] => write!(f, "invalid choice")?,
-                    [ref choice] => write!(f, "expected {}", choice)?,
+                    [] => msg("form.choice.invalid", "invalid choice", &[]),
+                    [ref choice] => {
+                        msg("form.choice.one", "expected {choice}", &[("choice", choice)])
+                    }
                     _ => {
-                        write!(f, "expected one of ")?;
-                        for (i, choice) in choices.iter().enumerate() {
-                            if i != 0 { write!(f, ", ")?; }
-                            write!(f, "`{}`", choice)?;
-                        }
+                        let choices = choices.iter()
+                            .map(|choice| format!("`{}`", choice))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        msg("form.choice.any", "expected one of {choices}",
+                            &[("choices", &choices)])
                     }
                 }
             }
             ErrorKind::OutOfRange { start, end } => {
                 match (start, end) {
-                    (None, None) => write!(f, "value is out of range")?,
-                    (None, Some(k)) => write!(f, "value cannot exceed {}", k)?,
-                    (Some(k), None) => write!(f, "value must be at least {}", k)?,
-                    (Some(i), Some(j)) => write!(f, "value must be between {} and {}", i, j)?,
+                    (None, None) => msg("form.range.out", "value is out of range", &[]),
+                    (None, Some(k)) => {
+                        msg("form.range.max", "value cannot exceed {max}", &[("max", k)])
+                    }
+                    (Some(k), None) => {
+                        msg("form.range.min", "value must be at least {min}", &[("min", k)])
+                    }
+                    (Some(i), Some(j)) => {
+                        msg("form.range.between", "value must be between {min} and {max}",
+                            &[("min", i), ("max", j)])
+                    }
                 }
             }
-            ErrorKind::Validation(msg) => msg.fmt(f)?,
-            ErrorKind::Duplicate => "duplicate".fmt(f)?,
-            ErrorKind::Missing => "missing".fmt(f)?,
-            ErrorKind::Unexpected => "unexpected".fmt(f)?,
-            ErrorKind::Unknown => "unknown internal error".fmt(f)?,
-            ErrorKind::Custom(_, e) => e.fmt(f)?,
-            ErrorKind::Multipart(e) => write!(f, "invalid multipart: {}", e)?,
-            ErrorKind::Utf8(e) => write!(f, "invalid UTF-8: {}", e)?,
-            ErrorKind::Char(e) => write!(f, "invalid character: {}", e)?,
-            ErrorKind::Int(e) => write!(f, "invalid integer: {}", e)?,
-            ErrorKind::Bool(e) => write!(f, "invalid boolean: {}", e)?,
-            ErrorKind::Float(e) => write!(f, "invalid float: {}", e)?,
-            ErrorKind::Addr(e) => write!(f, "invalid address: {}", e)?,
-            ErrorKind::Io(e) => write!(f, "i/o error: {}", e)?,
+            ErrorKind::Validation(m) => w.write_str(m),
+            ErrorKind::Duplicate => msg("form.duplicate", "duplicate", &[]),
+            ErrorKind::Missing => msg("form.missing", "missing", &[]),
+            ErrorKind::Unexpected => msg("form.unexpected", "unexpected", &[]),
+            ErrorKind::Unknown => msg("form.unknown", "unknown internal error", &[]),
+            ErrorKind::Custom(_, e) => write!(w, "{}", e),
+            #[cfg(feature = "multipart")]
+            ErrorKind::Multipart(e) => {
+                msg("form.multipart", "invalid multipart: {error}", &[("error", e)])
+            }
+            ErrorKind::Utf8(e) => msg("form.utf8", "invalid UTF-8: {error}", &[("error", e)]),
+            ErrorKind::Char(e) => {
+                msg("form.char", "invalid character: {error}", &[("error", e)])
+            }
+            ErrorKind::Int(e) => msg("form.int", "invalid integer: {error}", &[("error", e)]),
+            ErrorKind::Bool(e) => msg("form.bool", "invalid boolean: {error}", &[("error", e)]),
+            ErrorKind::Float(e) => msg("form.float", "invalid float: {error}", &[("error", e)]),
+            ErrorKind::Addr(e) => {
+                msg("form.addr", "invalid address: {error}", &[("error", e)])
+            }
+            ErrorKind::Io(e) => msg("form.io", "i/o error: {error}", &[("error", e)]),
         }
+    }
+}
 
-        Ok(())
+impl fmt::Display for ErrorKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, None)
     }
 }
 
@@ -887,6 +974,7 @@ impl crate::http::ext::IntoOwned for ErrorKind<'_> {
             Unexpected => Unexpected,
             Unknown => Unknown,
             Custom(s, e) => Custom(s, e),
+            #[cfg(feature = "multipart")]
             Multipart(e) => Multipart(e),
             Utf8(e) => Utf8(e),
             Char(e) => Char(e),
@@ -917,6 +1005,7 @@ impl<'a, 'b> PartialEq<ErrorKind<'b>> for ErrorKind<'a> {
             (Missing, Missing) => true,
             (Unexpected, Unexpected) => true,
             (Custom(a, _), Custom(b, _)) => a == b,
+            #[cfg(feature = "multipart")]
             (Multipart(a), Multipart(b)) => a == b,
             (Utf8(a), Utf8(b)) => a == b,
             (Int(a), Int(b)) => a == b,
@@ -1042,8 +1131,9 @@ impl Entity {
             | ErrorKind::Unknown
             | ErrorKind::Unexpected => Entity::Field,
 
-            | ErrorKind::Multipart(_)
-            | ErrorKind::Io(_) => Entity::Form,
+            #[cfg(feature = "multipart")]
+            ErrorKind::Multipart(_) => Entity::Form,
+            ErrorKind::Io(_) => Entity::Form,
         }
     }
 }
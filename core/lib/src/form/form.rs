@@ -107,6 +107,10 @@ use crate::form::prelude::{*, parser::{Parser, RawStrParser}};
 /// is aborted, an error is created and pushed via [`FromForm::push_error()`],
 /// and the form is finalized.
 ///
+/// Multipart support requires the `multipart` feature, enabled by default.
+/// Without it, multipart requests forward with a `415: Unsupported Media
+/// Type` status.
+///
 /// ### Individual Fields
 ///
 /// Individual fields _may_ have data limits as well. The type of the field
@@ -331,7 +335,12 @@ impl<'r, T: FromForm<'r>> FromData<'r> for Form<T> {
             }
         }
 
-        match T::finalize(context) {
+        let value = match T::finalize(context) {
+            Ok(value) => T::validate(value, req).await,
+            Err(e) => Err(e),
+        };
+
+        match value {
             Ok(value) => Outcome::Success(Form(value)),
             Err(e) => Outcome::Error((e.status(), e)),
         }
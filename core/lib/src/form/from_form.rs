@@ -6,6 +6,7 @@ use std::sync::Arc;
 use either::Either;
 use indexmap::IndexMap;
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::uncased::AsUncased;
 
@@ -551,6 +552,19 @@ pub trait FromForm<'r>: Send + Sized {
     /// collection of [`Errors`] otherwise.
     fn finalize(ctxt: Self::Context) -> Result<'r, Self>;
 
+    /// Asynchronously validates the finalized `value` with access to the
+    /// request `req`. Returns the value when successful or a collection of
+    /// [`Errors`] otherwise.
+    ///
+    /// This method is called by request-aware data guards like [`Form`] after
+    /// a successful [`finalize()`](FromForm::finalize()); it is _not_ called by
+    /// [`Form::parse()`] and friends. The derive implements it to run `async`
+    /// field validators and to validate nested forms. The default
+    /// implementation returns `value` unchanged, which is always correct.
+    async fn validate(value: Self, _req: &'r Request<'_>) -> Result<'r, Self> {
+        Ok(value)
+    }
+
     /// Returns a default value, if any, to use when a value is desired and
     /// parsing fails.
     ///
@@ -833,6 +847,13 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Option<T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         Ok(T::finalize(this).ok())
     }
+
+    async fn validate(value: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match value {
+            Some(value) => Ok(T::validate(value, req).await.ok()),
+            None => Ok(None),
+        }
+    }
 }
 
 #[crate::async_trait]
@@ -854,6 +875,13 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Result<'v, T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         Ok(T::finalize(this))
     }
+
+    async fn validate(value: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        match value {
+            Ok(value) => Ok(T::validate(value, req).await),
+            Err(e) => Ok(Err(e)),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -369,6 +369,9 @@ mod form;
 mod context;
 mod strict;
 mod lenient;
+mod session;
+mod constraints;
+mod ser;
 mod parser;
 mod buffer;
 pub mod validate;
@@ -398,6 +401,8 @@ pub use form::*;
 pub use context::*;
 pub use strict::*;
 pub use lenient::*;
+pub use session::*;
+pub use constraints::*;
 
 #[doc(hidden)]
 pub mod prelude {
@@ -1,3 +1,4 @@
+#[cfg(feature = "multipart")]
 use multer::Multipart;
 use either::Either;
 
@@ -10,6 +11,7 @@ type Result<'r, T> = std::result::Result<T, Error<'r>>;
 
 type Field<'r, 'i> = Either<ValueField<'r>, DataField<'r, 'i>>;
 
+#[cfg(feature = "multipart")]
 pub struct MultipartParser<'r, 'i> {
     request: &'r Request<'i>,
     buffer: &'r SharedStack<String>,
@@ -23,8 +25,12 @@ pub struct RawStrParser<'r> {
 }
 
 pub enum Parser<'r, 'i> {
+    #[cfg(feature = "multipart")]
     Multipart(MultipartParser<'r, 'i>),
     RawStr(RawStrParser<'r>),
+    /// Uninhabited: keeps `'i` in use when multipart support is compiled out.
+    #[cfg(not(feature = "multipart"))]
+    Never(std::convert::Infallible, std::marker::PhantomData<&'r Request<'i>>),
 }
 
 impl<'r, 'i> Parser<'r, 'i> {
@@ -34,6 +40,7 @@ impl<'r, 'i> Parser<'r, 'i> {
     ) -> Outcome<'r, Parser<'r, 'i>, Errors<'r>> {
         let parser = match req.content_type() {
             Some(c) if c.is_form() => Self::from_form(req, data).await,
+            #[cfg(feature = "multipart")]
             Some(c) if c.is_form_data() => Self::from_multipart(req, data).await,
             _ => return Outcome::Forward((data, Status::UnsupportedMediaType)),
         };
@@ -57,6 +64,7 @@ impl<'r, 'i> Parser<'r, 'i> {
         }))
     }
 
+    #[cfg(feature = "multipart")]
     async fn from_multipart(req: &'r Request<'i>, data: Data<'r>) -> Result<'r, Parser<'r, 'i>> {
         let boundary = req.content_type()
             .ok_or(multer::Error::NoMultipart)?
@@ -84,8 +92,11 @@ impl<'r, 'i> Parser<'r, 'i> {
 
     pub async fn next(&mut self) -> Option<Result<'r, Field<'r, 'i>>> {
         match self {
+            #[cfg(feature = "multipart")]
             Parser::Multipart(ref mut p) => p.next().await,
-            Parser::RawStr(ref mut p) => p.next().map(|f| Ok(Either::Left(f)))
+            Parser::RawStr(ref mut p) => p.next().map(|f| Ok(Either::Left(f))),
+            #[cfg(not(feature = "multipart"))]
+            Parser::Never(never, _) => match *never {},
         }
     }
 }
@@ -150,6 +161,7 @@ mod raw_str_parse_tests {
     }
 }
 
+#[cfg(feature = "multipart")]
 impl<'r, 'i> MultipartParser<'r, 'i> {
     /// Returns `None` when there are no further fields. Otherwise tries to
     /// parse the next multipart form field and returns the result.
@@ -0,0 +1,257 @@
+//! Flattens a `Serialize` value into form fields for `Context::from_value()`.
+
+use std::fmt;
+
+use serde::ser::{self, Serialize, Impossible};
+
+/// An error flattening a value into form fields.
+#[derive(Debug)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value cannot be represented as form fields: {}", self.0)
+    }
+}
+
+impl std::error::Error for Error { }
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Returns the `(name, value)` fields of `value`, which must serialize as a
+/// `struct` or map, in order.
+pub fn fields<T: Serialize + ?Sized>(value: &T) -> Result<Vec<(String, String)>> {
+    let mut fields = vec![];
+    value.serialize(Fields { name: String::new(), scalar: None, fields: &mut fields })?;
+    Ok(fields)
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    match prefix.is_empty() {
+        true => key.to_string(),
+        false => format!("{}.{}", prefix, key),
+    }
+}
+
+/// Serializes a value named `name`. Scalars are emitted as `scalar`, if set,
+/// so that sequences of scalars repeat the sequence's name.
+struct Fields<'a> {
+    name: String,
+    scalar: Option<&'a str>,
+    fields: &'a mut Vec<(String, String)>,
+}
+
+impl<'a> Fields<'a> {
+    fn push<V: ToString>(self, value: V) -> Result<()> {
+        let name = self.scalar.map(|s| s.to_string()).unwrap_or(self.name);
+        if name.is_empty() {
+            return Err(Error("a scalar is not a form".into()));
+        }
+
+        self.fields.push((name, value.to_string()));
+        Ok(())
+    }
+
+    fn nested(&mut self, key: &str) -> Fields<'_> {
+        Fields { name: join(&self.name, key), scalar: None, fields: &mut *self.fields }
+    }
+
+    fn unsupported<T>(kind: &str) -> Result<T> {
+        Err(Error(format!("{} are not supported", kind)))
+    }
+}
+
+macro_rules! serialize_scalars {
+    ($($method:ident: $T:ty),*) => ($(
+        fn $method(self, v: $T) -> Result<()> {
+            self.push(v)
+        }
+    )*)
+}
+
+impl<'a> ser::Serializer for Fields<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Seq<'a>;
+    type SerializeTuple = Seq<'a>;
+    type SerializeTupleStruct = Seq<'a>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Map<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_scalars! {
+        serialize_bool: bool, serialize_char: char, serialize_str: &str,
+        serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64,
+        serialize_i128: i128, serialize_u8: u8, serialize_u16: u16, serialize_u32: u32,
+        serialize_u64: u64, serialize_u128: u128, serialize_f32: f32, serialize_f64: f64
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<()> {
+        Self::unsupported("bytes")
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &str, _: u32, variant: &'static str) -> Result<()> {
+        self.push(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<()>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(self, _: &str, _: u32, _: &str, _: &T) -> Result<()>
+        where T: Serialize + ?Sized
+    {
+        Self::unsupported("newtype variants")
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Seq<'a>> {
+        if self.name.is_empty() {
+            return Err(Error("a sequence is not a form".into()));
+        }
+
+        Ok(Seq { name: self.name, index: 0, fields: self.fields })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Seq<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Seq<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize
+    ) -> Result<Self::SerializeTupleVariant> {
+        Self::unsupported("tuple variants")
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Map<'a>> {
+        Ok(Map { fields: self, key: None })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize
+    ) -> Result<Self::SerializeStructVariant> {
+        Self::unsupported("struct variants")
+    }
+}
+
+impl ser::SerializeStruct for Fields<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+        where T: Serialize + ?Sized
+    {
+        value.serialize(self.nested(key))
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Seq<'a> {
+    name: String,
+    index: usize,
+    fields: &'a mut Vec<(String, String)>,
+}
+
+impl Seq<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let name = format!("{}[{}]", self.name, self.index);
+        self.index += 1;
+        value.serialize(Fields { name, scalar: Some(&self.name), fields: &mut *self.fields })
+    }
+}
+
+macro_rules! impl_serialize_seq {
+    ($($Trait:ident::$method:ident),*) => ($(
+        impl ser::$Trait for Seq<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+                self.element(value)
+            }
+
+            fn end(self) -> Result<()> {
+                Ok(())
+            }
+        }
+    )*)
+}
+
+impl_serialize_seq! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field
+}
+
+struct Map<'a> {
+    fields: Fields<'a>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for Map<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        let mut fields = vec![];
+        key.serialize(Fields { name: String::new(), scalar: Some("key"), fields: &mut fields })?;
+        match fields.pop() {
+            Some((_, key)) if fields.is_empty() => self.key = Some(key),
+            _ => return Err(Error("map keys must be scalars".into())),
+        }
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().ok_or_else(|| Error("map value without key".into()))?;
+        value.serialize(self.fields.nested(&key))
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
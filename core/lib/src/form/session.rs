@@ -0,0 +1,297 @@
+/// Trait implemented by forms split into multiple steps.
+///
+/// This trait is implemented by [`#[derive(FromForm)]`](derive@crate::FromForm)
+/// for structures in which at least one field is assigned to a step via
+/// `#[field(step = n)]`. It is used by [`FormSession`] to determine which
+/// fields to accept and validate when a step is submitted. It is not intended
+/// to be implemented manually.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::Steps;
+///
+/// #[derive(FromForm)]
+/// struct Checkout<'r> {
+///     #[field(step = 1)]
+///     email: &'r str,
+///     #[field(step = 2)]
+///     address: &'r str,
+///     coupon: Option<&'r str>,
+/// }
+///
+/// assert_eq!(Checkout::LAST, 2);
+/// assert_eq!(Checkout::step("email"), Some(1));
+/// assert_eq!(Checkout::step("coupon"), None);
+/// ```
+pub trait Steps {
+    /// The name of the form, used to name its session cookie.
+    const NAME: &'static str;
+
+    /// The last step of the form. Steps are numbered starting at `1`.
+    const LAST: usize;
+
+    /// Returns the step the field with the top-level key `name` is assigned to,
+    /// if any. Fields without a step are validated with the last step.
+    fn step(name: &str) -> Option<usize>;
+}
+
+#[cfg(feature = "secrets")]
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+pub use self::session::FormSession;
+
+#[cfg(feature = "secrets")]
+mod session {
+    use std::borrow::Cow;
+    use std::marker::PhantomData;
+
+    use crate::{Request, Data};
+    use crate::data::{self, FromData, Limits};
+    use crate::request::{self, FromRequest};
+    use crate::http::{RawStr, Status, CookieJar, Cookie};
+    use crate::outcome::Outcome;
+    use crate::form::{self, Form, FromForm, Errors, Error};
+
+    use crate::form::name::NameView;
+    use super::Steps;
+
+    /// The name of the hidden field identifying the submitted step.
+    const STEP_FIELD: &str = "_step";
+
+    /// Returns the percent-decoded top-level key of the raw field `field`.
+    fn key_of(field: &str) -> String {
+        let name = field.split_once('=').map_or(field, |(name, _)| name);
+        let name = RawStr::new(name).url_decode_lossy();
+        NameView::new(&*name).key_lossy().as_str().to_string()
+    }
+
+    /// Returns the step the raw field `field` belongs to.
+    fn step_of<T: Steps>(field: &str) -> usize {
+        T::step(&key_of(field)).unwrap_or(T::LAST)
+    }
+
+    /// A request and data guard for multi-step forms that accumulates the
+    /// validated fields of each step in a private cookie.
+    ///
+    /// Fields of a [`FromForm`] structure are assigned to steps with the
+    /// `#[field(step = n)]` attribute, starting at `1`. Each step is submitted
+    /// as a separate `application/x-www-form-urlencoded` form with a hidden
+    /// `_step` field containing the step's number. As a data guard, a
+    /// `FormSession<T>`:
+    ///
+    ///   1. Replaces the previously accumulated fields of the submitted step
+    ///      with the submitted fields, ignoring fields of other steps.
+    ///   2. Validates the fields of the submitted step _and_ all previous
+    ///      steps. Fields without a step are validated with the last step.
+    ///   3. On success, stores the accumulated fields in a private cookie. On
+    ///      failure, the guard fails with the validation [`Errors`], exactly
+    ///      like [`Form`], and the cookie is left unchanged.
+    ///
+    /// Once the last step has been submitted, [`FormSession::finish()`] parses
+    /// the complete form and removes the cookie. As a request guard, a
+    /// `FormSession<T>` retrieves the accumulated state without submitting a
+    /// step, for instance to prefill a step's fields or to redirect to the
+    /// [next step](FormSession::next_step()).
+    ///
+    /// This type is only available with the `secrets` feature enabled. As
+    /// fields are stored in a cookie, the accumulated form, once encrypted,
+    /// should not exceed the ~4KiB cookie size limit of most browsers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::form::FormSession;
+    /// use rocket::response::Redirect;
+    ///
+    /// #[derive(FromForm)]
+    /// struct Checkout {
+    ///     #[field(step = 1, validate = contains('@'))]
+    ///     email: String,
+    ///     #[field(step = 2, validate = len(1..))]
+    ///     address: String,
+    ///     #[field(step = 3, validate = eq(true))]
+    ///     accept: bool,
+    /// }
+    ///
+    /// #[post("/checkout", data = "<session>")]
+    /// fn step(session: FormSession<'_, Checkout>) -> Result<String, Redirect> {
+    ///     if let Some(next) = session.next_step() {
+    ///         return Err(Redirect::to(format!("/checkout/{}", next)));
+    ///     }
+    ///
+    ///     let checkout = session.finish().map_err(|_| Redirect::to("/checkout/1"))?;
+    ///     Ok(format!("Thanks! Shipping to {}.", checkout.address))
+    /// }
+    /// ```
+    pub struct FormSession<'r, T> {
+        cookies: &'r CookieJar<'r>,
+        completed: usize,
+        fields: String,
+        _form: PhantomData<fn() -> T>,
+    }
+
+    impl<'r, T: Steps> FormSession<'r, T> {
+        fn cookie_name() -> String {
+            format!("form.{}", T::NAME)
+        }
+
+        fn load(cookies: &'r CookieJar<'r>) -> Self {
+            let mut session = FormSession {
+                cookies,
+                completed: 0,
+                fields: String::new(),
+                _form: PhantomData,
+            };
+
+            if let Some(cookie) = cookies.get_private(&Self::cookie_name()) {
+                for field in cookie.value().split('&').filter(|f| !f.is_empty()) {
+                    match field.strip_prefix(STEP_FIELD).and_then(|v| v.strip_prefix('=')) {
+                        Some(step) => session.completed = step.parse().unwrap_or(0),
+                        None => session.push(field),
+                    }
+                }
+            }
+
+            session
+        }
+
+        fn push(&mut self, field: &str) {
+            if !self.fields.is_empty() {
+                self.fields.push('&');
+            }
+
+            self.fields.push_str(field);
+        }
+
+        fn store(&self) {
+            let value = format!("{}={}&{}", STEP_FIELD, self.completed, self.fields);
+            self.cookies.add_private(Cookie::new(Self::cookie_name(), value));
+        }
+
+        /// Returns the highest step completed so far, or `0` if no step has
+        /// been completed.
+        pub fn completed(&self) -> usize {
+            self.completed
+        }
+
+        /// Returns the step following the highest completed step, or `None`
+        /// if all steps have been completed.
+        pub fn next_step(&self) -> Option<usize> {
+            (self.completed < T::LAST).then_some(self.completed + 1)
+        }
+
+        /// Returns `true` if all steps have been completed.
+        pub fn is_complete(&self) -> bool {
+            self.completed >= T::LAST
+        }
+
+        /// Returns the percent-decoded value of the first accumulated field
+        /// named `name`, if any.
+        pub fn value(&self, name: &str) -> Option<Cow<'_, str>> {
+            self.fields.split('&')
+                .filter_map(|field| field.split_once('='))
+                .find(|(key, _)| RawStr::new(key).url_decode_lossy() == name)
+                .map(|(_, value)| RawStr::new(value).url_decode_lossy())
+        }
+
+        /// Discards all accumulated fields and removes the session cookie.
+        pub fn clear(self) {
+            self.cookies.remove_private(Self::cookie_name());
+        }
+    }
+
+    impl<'r, T: Steps + for<'a> FromForm<'a> + 'static> FormSession<'r, T> {
+        fn parse(&self) -> form::Result<'static, T> {
+            Form::<T>::parse_encoded(RawStr::new(&self.fields))
+        }
+
+        /// Parses the complete form from the accumulated fields. On success,
+        /// the session cookie is removed. On failure, it is left unchanged.
+        pub fn finish(self) -> form::Result<'static, T> {
+            let value = self.parse()?;
+            self.cookies.remove_private(Self::cookie_name());
+            Ok(value)
+        }
+    }
+
+    async fn read<'r>(req: &'r Request<'_>, data: Data<'r>) -> form::Result<'static, String> {
+        let limit = req.limits().get("form").unwrap_or(Limits::FORM);
+        let string = data.open(limit).into_string().await.map_err(Error::from)?;
+        if !string.is_complete() {
+            Err(Error::from((None, Some(limit.as_u64()))))?;
+        }
+
+        Ok(string.into_inner())
+    }
+
+    #[crate::async_trait]
+    impl<'r, T: Steps + for<'a> FromForm<'a> + 'static> FromData<'r> for FormSession<'r, T> {
+        type Error = Errors<'static>;
+
+        async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+            if !req.content_type().map_or(false, |ct| ct.is_form()) {
+                return Outcome::Forward((data, Status::UnsupportedMediaType));
+            }
+
+            let submitted = match read(req, data).await {
+                Ok(string) => string,
+                Err(e) => return Outcome::Error((e.status(), e)),
+            };
+
+            let fields = submitted.split('&').filter(|f| !f.is_empty());
+            let step = fields.clone()
+                .find(|field| key_of(field) == STEP_FIELD)
+                .and_then(|field| field.split_once('='))
+                .and_then(|(_, step)| step.parse::<usize>().ok())
+                .filter(|step| (1..=T::LAST).contains(step));
+
+            let Some(step) = step else {
+                let e = Errors::from(Error::validation("invalid form step").with_name(STEP_FIELD));
+                return Outcome::Error((e.status(), e));
+            };
+
+            let mut session = Self::load(req.cookies());
+            let previous = std::mem::take(&mut session.fields);
+            let current = fields.filter(|f| key_of(f) != STEP_FIELD && step_of::<T>(f) == step);
+            previous.split('&')
+                .filter(|field| !field.is_empty() && step_of::<T>(field) != step)
+                .chain(current)
+                .for_each(|field| session.push(field));
+
+            if let Err(mut errors) = session.parse() {
+                errors.retain(|e| {
+                    let key = e.name.as_ref().and_then(|name| name.keys().next());
+                    key.and_then(|key| T::step(key.as_str())).unwrap_or(T::LAST) <= step
+                });
+
+                if !errors.is_empty() {
+                    return Outcome::Error((errors.status(), errors));
+                }
+            }
+
+            session.completed = session.completed.max(step);
+            session.store();
+            Outcome::Success(session)
+        }
+    }
+
+    #[crate::async_trait]
+    impl<'r, T: Steps> FromRequest<'r> for FormSession<'r, T> {
+        type Error = std::convert::Infallible;
+
+        async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+            Outcome::Success(Self::load(req.cookies()))
+        }
+    }
+
+    impl<T> std::fmt::Debug for FormSession<'_, T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("FormSession")
+                .field("completed", &self.completed)
+                .field("fields", &self.fields)
+                .finish_non_exhaustive()
+        }
+    }
+}
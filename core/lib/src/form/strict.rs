@@ -1,5 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
+use crate::Request;
 use crate::form::prelude::*;
 use crate::http::uri::fmt::{Query, FromUriParam};
 
@@ -111,6 +112,10 @@ impl<'v, T: FromForm<'v>> FromForm<'v> for Strict<T> {
     fn finalize(this: Self::Context) -> Result<'v, Self> {
         T::finalize(this).map(Self)
     }
+
+    async fn validate(this: Self, req: &'v Request<'_>) -> Result<'v, Self> {
+        T::validate(this.0, req).await.map(Self)
+    }
 }
 
 impl<T> Deref for Strict<T> {
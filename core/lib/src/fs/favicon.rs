@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::{Request, Data};
+use crate::fs::NamedFile;
+use crate::http::{ContentType, Method, Status};
+use crate::response::Cached;
+use crate::route::{Route, Handler, Outcome};
+
+/// Handler that serves a site's icon at `/favicon.ico`.
+///
+/// Browsers request `/favicon.ico` for every site they visit. Without a route
+/// for it, each request results in a `404`, cluttering logs. A `Favicon` serves
+/// the icon from bytes or a file with a `Content-Type` matching the icon and a
+/// `Cache-Control` header that lets clients keep it for a day, configurable
+/// via [`Favicon::max_age()`].
+///
+/// # Example
+///
+/// Serve an icon embedded in the binary:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// # macro_rules! include_bytes { ($path:expr) => (&[0u8, 0, 1, 0]) }
+/// use rocket::fs::Favicon;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", Favicon::bytes(include_bytes!("../static/favicon.ico")))
+/// }
+/// ```
+///
+/// Serve an icon from the file system:
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fs::{Favicon, relative};
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", Favicon::file(relative!("static/icon.png")))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Favicon {
+    source: Source,
+    content_type: ContentType,
+    max_age: u32,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Bytes(Arc<[u8]>),
+    File(PathBuf),
+}
+
+impl Favicon {
+    /// How long clients may cache the icon for by default: one day.
+    const DEFAULT_MAX_AGE: u32 = 24 * 60 * 60;
+
+    /// Serves `bytes` as an `image/x-icon`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::Favicon;
+    ///
+    /// let favicon = Favicon::bytes(vec![0u8, 0, 1, 0]);
+    /// ```
+    pub fn bytes<B: AsRef<[u8]>>(bytes: B) -> Self {
+        Favicon {
+            source: Source::Bytes(bytes.as_ref().into()),
+            content_type: ContentType::Icon,
+            max_age: Self::DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Serves the file at `path`, read on every request, with a
+    /// `Content-Type` based on its extension, or `image/x-icon` if it has none
+    /// or the extension is unknown. If the file can't be opened, requests are
+    /// forwarded with a `404`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::Favicon;
+    ///
+    /// let favicon = Favicon::file("static/favicon.svg");
+    /// ```
+    pub fn file<P: AsRef<Path>>(path: P) -> Self {
+        let content_type = path.as_ref().extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(ContentType::from_extension)
+            .unwrap_or(ContentType::Icon);
+
+        Favicon {
+            source: Source::File(path.as_ref().into()),
+            content_type,
+            max_age: Self::DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Overrides the `Content-Type` the icon is served with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::Favicon;
+    /// use rocket::http::ContentType;
+    ///
+    /// let favicon = Favicon::bytes(b"<svg/>").content_type(ContentType::SVG);
+    /// ```
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Sets how long, in seconds, clients may cache the icon for. The default
+    /// is one day.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::Favicon;
+    ///
+    /// let favicon = Favicon::file("static/favicon.ico").max_age(7 * 24 * 60 * 60);
+    /// ```
+    pub fn max_age(mut self, seconds: u32) -> Self {
+        self.max_age = seconds;
+        self
+    }
+}
+
+impl From<Favicon> for Vec<Route> {
+    fn from(favicon: Favicon) -> Self {
+        let mut route = Route::new(Method::Get, "/favicon.ico", favicon);
+        route.name = Some("Favicon".into());
+        vec![route]
+    }
+}
+
+#[crate::async_trait]
+impl Handler for Favicon {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let content_type = self.content_type.clone();
+        match &self.source {
+            Source::Bytes(bytes) => {
+                let icon = Cached::new((content_type, bytes.clone()));
+                Outcome::from(req, icon.public().max_age(self.max_age))
+            }
+            Source::File(path) => match NamedFile::open(path).await {
+                Ok(file) => {
+                    let icon = Cached::new((content_type, file));
+                    Outcome::from(req, icon.public().max_age(self.max_age))
+                }
+                Err(e) => {
+                    warn!(path = %path.display(), "failed to open favicon: {e}");
+                    Outcome::forward(data, Status::NotFound)
+                }
+            }
+        }
+    }
+}
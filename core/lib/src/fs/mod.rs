@@ -4,6 +4,8 @@ mod server;
 mod named_file;
 mod temp_file;
 mod file_name;
+mod favicon;
+mod text_file;
 
 pub mod rewrite;
 
@@ -11,6 +13,8 @@ pub use server::*;
 pub use named_file::*;
 pub use temp_file::*;
 pub use file_name::*;
+pub use favicon::*;
+pub use text_file::*;
 
 crate::export! {
     /// Generates a crate-relative version of a path.
@@ -1,4 +1,5 @@
 use std::{io, mem};
+use std::io::SeekFrom;
 use std::path::{PathBuf, Path};
 
 use crate::Request;
@@ -10,7 +11,7 @@ use crate::fs::FileName;
 
 use tokio::task;
 use tokio::fs::{self, File};
-use tokio::io::{AsyncBufRead, BufReader};
+use tokio::io::{AsyncBufRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
 use tempfile::{NamedTempFile, TempPath};
 use either::Either;
 
@@ -47,10 +48,17 @@ use either::Either;
 /// | Name               | Default             | Description                             |
 /// |--------------------|---------------------|-----------------------------------------|
 /// | `temp_dir`         | [`env::temp_dir()`] | Directory for temporary file storage.   |
+/// | `buffer`           | [`BufferPolicy`]    | Memory, directory, and naming policy.   |
 /// | `limits.file`      | 1MiB                | Default limit for all file extensions.  |
 /// | `limits.file/$ext` | _N/A_               | Limit for files with extension `$ext`.  |
 ///
 /// [`env::temp_dir()`]: std::env::temp_dir()
+/// [`BufferPolicy`]: crate::data::BufferPolicy
+///
+/// The [`BufferPolicy`] can be overridden per request with
+/// [`Request::set_buffer_policy()`](crate::Request::set_buffer_policy()). If it
+/// holds a body in memory or in an unnamed file, [`TempFile::path()`] returns
+/// `None` until the file is persisted.
 ///
 /// When used as a form guard, the extension `$ext` is identified by the form
 /// field's `Content-Type` ([`ContentType::extension()`]). When used as a data
@@ -111,7 +119,20 @@ pub enum TempFile<'v> {
     #[doc(hidden)]
     Buffered {
         content: &'v [u8],
-    }
+    },
+    #[doc(hidden)]
+    Memory {
+        file_name: Option<&'v FileName>,
+        content_type: Option<ContentType>,
+        content: Vec<u8>,
+    },
+    #[doc(hidden)]
+    Unnamed {
+        file_name: Option<&'v FileName>,
+        content_type: Option<ContentType>,
+        file: File,
+        len: u64,
+    },
 }
 
 impl<'v> TempFile<'v> {
@@ -188,15 +209,7 @@ impl<'v> TempFile<'v> {
                     }
                 }
             }
-            TempFile::Buffered { content } => {
-                fs::write(&new_path, &content).await?;
-                *self = TempFile::File {
-                    file_name: None,
-                    content_type: None,
-                    path: Either::Right(new_path),
-                    len: content.len() as u64
-                };
-            }
+            _ => self.write_to(new_path).await?,
         }
 
         Ok(())
@@ -256,16 +269,7 @@ impl<'v> TempFile<'v> {
 
                 tokio::fs::copy(&either, path).await?;
             }
-            TempFile::Buffered { content } => {
-                let path = path.as_ref();
-                fs::write(&path, &content).await?;
-                *self = TempFile::File {
-                    file_name: None,
-                    content_type: None,
-                    path: Either::Right(path.to_path_buf()),
-                    len: content.len() as u64
-                };
-            }
+            _ => self.write_to(path.as_ref().to_path_buf()).await?,
         }
 
         Ok(())
@@ -302,7 +306,12 @@ impl<'v> TempFile<'v> {
     pub async fn move_copy_to<P>(&mut self, path: P) -> io::Result<()>
         where P: AsRef<Path>
     {
+        // Without a temporary file on disk, there's nothing to delete.
         let dest = path.as_ref();
+        if !matches!(self, TempFile::File { .. }) {
+            return self.persist_to(dest).await;
+        }
+
         self.copy_to(dest).await?;
 
         if let TempFile::File { path, .. } = self {
@@ -353,6 +362,14 @@ impl<'v> TempFile<'v> {
             TempFile::Buffered { content } => {
                 Ok(Either::Right(*content))
             },
+            TempFile::Memory { content, .. } => {
+                Ok(Either::Right(&content[..]))
+            },
+            TempFile::Unnamed { file, .. } => {
+                let mut file = file.try_clone().await?;
+                file.seek(SeekFrom::Start(0)).await?;
+                Ok(Either::Left(BufReader::new(file)))
+            },
         }
     }
 
@@ -394,6 +411,8 @@ impl<'v> TempFile<'v> {
         match self {
             TempFile::File { len, .. } => *len,
             TempFile::Buffered { content } => content.len() as u64,
+            TempFile::Memory { content, .. } => content.len() as u64,
+            TempFile::Unnamed { len, .. } => *len,
         }
     }
 
@@ -425,7 +444,7 @@ impl<'v> TempFile<'v> {
         match self {
             TempFile::File { path: Either::Left(p), .. } => Some(p.as_ref()),
             TempFile::File { path: Either::Right(p), .. } => Some(p.as_path()),
-            TempFile::Buffered { .. } => None,
+            _ => None,
         }
     }
 
@@ -477,6 +496,8 @@ impl<'v> TempFile<'v> {
     pub fn raw_name(&self) -> Option<&FileName> {
         match *self {
             TempFile::File { file_name, .. } => file_name,
+            TempFile::Memory { file_name, .. } => file_name,
+            TempFile::Unnamed { file_name, .. } => file_name,
             TempFile::Buffered { .. } => None
         }
     }
@@ -499,10 +520,38 @@ impl<'v> TempFile<'v> {
     pub fn content_type(&self) -> Option<&ContentType> {
         match self {
             TempFile::File { content_type, .. } => content_type.as_ref(),
+            TempFile::Memory { content_type, .. } => content_type.as_ref(),
+            TempFile::Unnamed { content_type, .. } => content_type.as_ref(),
             TempFile::Buffered { .. } => None
         }
     }
 
+    /// Writes the contents of `self`, which isn't a `TempFile::File`, to a new
+    /// file at `path`, replacing `self` with that file.
+    async fn write_to(&mut self, path: PathBuf) -> io::Result<()> {
+        let (file_name, content_type) = match self {
+            TempFile::File { .. } => unreachable!("`self` is already a file"),
+            TempFile::Buffered { content } => {
+                fs::write(&path, content).await?;
+                (None, None)
+            }
+            TempFile::Memory { file_name, content_type, content } => {
+                fs::write(&path, content).await?;
+                (*file_name, content_type.take())
+            }
+            TempFile::Unnamed { file_name, content_type, file, .. } => {
+                let mut source = file.try_clone().await?;
+                source.seek(SeekFrom::Start(0)).await?;
+                tokio::io::copy(&mut source, &mut File::create(&path).await?).await?;
+                (*file_name, content_type.take())
+            }
+        };
+
+        let len = self.len();
+        *self = TempFile::File { file_name, content_type, path: Either::Right(path), len };
+        Ok(())
+    }
+
     async fn from<'a>(
         req: &Request<'_>,
         data: Data<'_>,
@@ -515,20 +564,49 @@ impl<'v> TempFile<'v> {
             .or_else(|| req.limits().get("file"))
             .unwrap_or(Limits::FILE);
 
-        let temp_dir = req.rocket().config().temp_dir.relative();
-        let file = task::spawn_blocking(move || NamedTempFile::new_in(temp_dir));
+        // Hold the body in memory if it's no larger than the policy allows.
+        let policy = req.buffer_policy();
+        let mut stream = data.open(limit);
+        let mut buffer = vec![];
+        let memory = policy.memory.as_u64();
+        if memory > 0 {
+            (&mut stream).take(memory.saturating_add(1)).read_to_end(&mut buffer).await?;
+            if buffer.len() as u64 <= memory {
+                let complete = !stream.limit_exceeded().await?;
+                let n = N { written: buffer.len() as u64, complete };
+                let file = TempFile::Memory { file_name, content_type, content: buffer };
+                return Ok(Capped::new(file, n));
+            }
+        }
+
+        let (temp_dir, unnamed) = (policy.temp_dir(req.rocket().config()), policy.unnamed);
+        let file = task::spawn_blocking(move || match unnamed {
+            true => tempfile::tempfile_in(temp_dir).map(|file| (file, None)),
+            false => NamedTempFile::new_in(temp_dir).map(|file| {
+                let (file, temp_path) = file.into_parts();
+                (file, Some(temp_path))
+            }),
+        });
+
         let file = file.await;
         let file = file.map_err(|_| io::Error::new(io::ErrorKind::Other, "spawn_block panic"))??;
-        let (file, temp_path) = file.into_parts();
+        let (file, temp_path) = file;
 
         let mut file = File::from_std(file);
-        let fut = data.open(limit).stream_to(tokio::io::BufWriter::new(&mut file));
-        let n = fut.await;
-        let n = n?;
-        let temp_file = TempFile::File {
-            content_type, file_name,
-            path: Either::Left(temp_path),
-            len: n.written,
+        let mut writer = BufWriter::new(&mut file);
+        writer.write_all(&buffer).await?;
+        let n = stream.stream_to(&mut writer).await?;
+        drop(writer);
+
+        let len = buffer.len() as u64 + n.written;
+        let n = N { written: len, complete: n.complete };
+        let temp_file = match temp_path {
+            Some(temp_path) => TempFile::File {
+                content_type, file_name,
+                path: Either::Left(temp_path),
+                len,
+            },
+            None => TempFile::Unnamed { content_type, file_name, file, len },
         };
 
         Ok(Capped::new(temp_file, n))
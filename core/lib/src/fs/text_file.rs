@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::{Request, Data};
+use crate::http::{ContentType, Method, Status};
+use crate::response::Cached;
+use crate::route::{Route, Handler, Outcome};
+
+/// Handler that serves `robots.txt` or `security.txt` from a string.
+///
+/// Crawlers request `/robots.txt` and security researchers look for
+/// `/.well-known/security.txt` ([RFC 9116]). A `TextFile` serves either as
+/// `text/plain` with a `Cache-Control` header that lets clients keep it for a
+/// day, configurable via [`TextFile::max_age()`], from a string given in code
+/// or read from configuration.
+///
+/// | constructor                          | path                        | config key     |
+/// |--------------------------------------|-----------------------------|----------------|
+/// | [`TextFile::robots()`]               | `/robots.txt`               |                |
+/// | [`TextFile::robots_from_config()`]   | `/robots.txt`               | `robots_txt`   |
+/// | [`TextFile::security()`]             | `/.well-known/security.txt` |                |
+/// | [`TextFile::security_from_config()`] | `/.well-known/security.txt` | `security_txt` |
+///
+/// Contents read from configuration are looked up when the file is requested.
+/// If the key is not set, the request is forwarded with a `404`.
+///
+/// [RFC 9116]: https://www.rfc-editor.org/rfc/rfc9116
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fs::TextFile;
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", TextFile::robots("User-agent: *\nDisallow: /admin/\n"))
+///         .mount("/", TextFile::security_from_config())
+/// }
+/// ```
+///
+/// With the following in `Rocket.toml`:
+///
+/// ```toml
+/// [default]
+/// security_txt = """
+/// Contact: mailto:security@example.com
+/// Expires: 2030-01-01T00:00:00.000Z
+/// """
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextFile {
+    path: &'static str,
+    source: Source,
+    max_age: u32,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Contents(Arc<str>),
+    Config(&'static str),
+}
+
+impl TextFile {
+    /// How long clients may cache the file for by default: one day.
+    const DEFAULT_MAX_AGE: u32 = 24 * 60 * 60;
+
+    fn new(path: &'static str, source: Source) -> Self {
+        TextFile { path, source, max_age: Self::DEFAULT_MAX_AGE }
+    }
+
+    /// Serves `contents` at `/robots.txt`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::TextFile;
+    ///
+    /// let robots = TextFile::robots("User-agent: *\nDisallow:\n");
+    /// ```
+    pub fn robots<S: Into<Cow<'static, str>>>(contents: S) -> Self {
+        Self::new("/robots.txt", Source::Contents(contents.into().into()))
+    }
+
+    /// Serves the value of the `robots_txt` configuration parameter at
+    /// `/robots.txt`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::TextFile;
+    ///
+    /// let robots = TextFile::robots_from_config();
+    /// ```
+    pub fn robots_from_config() -> Self {
+        Self::new("/robots.txt", Source::Config("robots_txt"))
+    }
+
+    /// Serves `contents` at `/.well-known/security.txt`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::TextFile;
+    ///
+    /// let security = TextFile::security("Contact: mailto:security@example.com\n");
+    /// ```
+    pub fn security<S: Into<Cow<'static, str>>>(contents: S) -> Self {
+        Self::new("/.well-known/security.txt", Source::Contents(contents.into().into()))
+    }
+
+    /// Serves the value of the `security_txt` configuration parameter at
+    /// `/.well-known/security.txt`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::TextFile;
+    ///
+    /// let security = TextFile::security_from_config();
+    /// ```
+    pub fn security_from_config() -> Self {
+        Self::new("/.well-known/security.txt", Source::Config("security_txt"))
+    }
+
+    /// Sets how long, in seconds, clients may cache the file for. The default
+    /// is one day.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::TextFile;
+    ///
+    /// let robots = TextFile::robots("User-agent: *\nDisallow:\n").max_age(3600);
+    /// ```
+    pub fn max_age(mut self, seconds: u32) -> Self {
+        self.max_age = seconds;
+        self
+    }
+}
+
+impl From<TextFile> for Vec<Route> {
+    fn from(file: TextFile) -> Self {
+        let mut route = Route::new(Method::Get, file.path, file);
+        route.name = Some("TextFile".into());
+        vec![route]
+    }
+}
+
+#[crate::async_trait]
+impl Handler for TextFile {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let contents: Arc<str> = match &self.source {
+            Source::Contents(contents) => contents.clone(),
+            Source::Config(key) => match req.rocket().figment().extract_inner::<String>(key) {
+                Ok(contents) => contents.into(),
+                Err(e) if e.missing() => {
+                    warn!(path = self.path, "`{key}` is not configured");
+                    return Outcome::forward(data, Status::NotFound);
+                }
+                Err(e) => {
+                    error!(path = self.path, "invalid `{key}` configuration: {e}");
+                    return Outcome::error(Status::InternalServerError);
+                }
+            }
+        };
+
+        let file = Cached::new((ContentType::Plain, contents));
+        Outcome::from(req, file.public().max_age(self.max_age))
+    }
+}
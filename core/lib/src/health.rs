@@ -0,0 +1,367 @@
+//! Liveness and readiness probes.
+//!
+//! The [`Health`] handler serves two probes, `/healthz` and `/readyz`, that
+//! report the result of running a set of named, asynchronous health checks:
+//!
+//!   * `/healthz` (_liveness_) runs all checks registered via
+//!     [`Health::liveness()`].
+//!   * `/readyz` (_readiness_) runs all liveness checks as well as all checks
+//!     registered via [`Health::readiness()`].
+//!
+//! Checks run concurrently, each limited by its own timeout. A probe responds
+//! with `200 OK` if every check succeeds and `503 Service Unavailable`
+//! otherwise. The response body is a JSON document with the overall status
+//! and the status, latency, and error, if any, of every check:
+//!
+//! ```json
+//! {
+//!   "status": "down",
+//!   "checks": [
+//!     { "name": "db", "status": "up", "latency_ms": 1.27 },
+//!     { "name": "disk", "status": "down", "latency_ms": 0.08, "error": "disk full" }
+//!   ]
+//! }
+//! ```
+//!
+//! # Graceful Shutdown
+//!
+//! Once a shutdown is underway, `/readyz` reports a status of `"draining"`
+//! with `503 Service Unavailable` without running any checks. When the
+//! [`drain`](crate::config::ShutdownConfig::drain) period is configured,
+//! shutdowns triggered by signals wait `drain` seconds after readiness starts
+//! failing before Rocket stops accepting connections, allowing load balancers
+//! to stop routing traffic to the server first. Meanwhile, HTTP/1 responses
+//! are sent with `Connection: close` so that clients reconnect elsewhere.
+//!
+//! The draining response includes the shutdown's progress, as reported by
+//! [`Shutdown::status()`](crate::Shutdown::status()): its stage, the time
+//! since it was requested, and the number of requests in flight and
+//! connections open:
+//!
+//! ```json
+//! {
+//!   "status": "draining",
+//!   "checks": [],
+//!   "shutdown": { "stage": "draining", "elapsed_ms": 1520.4, "requests": 3, "connections": 12 }
+//! }
+//! ```
+//!
+//! The same progress is served at all times, with `200 OK`, by `/drainz` if
+//! enabled via [`Health::drain_endpoint()`], giving orchestrators visibility
+//! into long drains.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::time::Duration;
+//! use rocket::health::Health;
+//!
+//! struct Database;
+//!
+//! impl Database {
+//!     async fn ping(&self) -> Result<(), std::io::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let health = Health::new()
+//!         .liveness("alive", Duration::from_millis(100), |_| Box::pin(async {
+//!             Ok::<_, std::io::Error>(())
+//!         }))
+//!         .readiness("db", Duration::from_secs(1), |rocket| Box::pin(async move {
+//!             match rocket.state::<Database>() {
+//!                 Some(db) => db.ping().await.map_err(|e| e.to_string()),
+//!                 None => Err("database not managed".into()),
+//!             }
+//!         }));
+//!
+//!     rocket::build()
+//!         .manage(Database)
+//!         .mount("/", health)
+//! }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::future::{join_all, BoxFuture, FutureExt};
+
+use crate::{Data, Request, Rocket, Orbit, Route};
+use crate::http::{Method, Status};
+use crate::route::{Handler, Outcome};
+use crate::serde::json::{Json, Value, json};
+
+type CheckFn = Box<dyn for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<(), String>>
+    + Send + Sync + 'static>;
+
+/// A handler serving liveness and readiness probes.
+///
+/// See the [module level docs](self) for details and an example.
+#[derive(Default)]
+pub struct Health {
+    checks: Vec<Check>,
+    rank: Option<isize>,
+    drain_endpoint: bool,
+}
+
+struct Check {
+    name: Cow<'static, str>,
+    timeout: Duration,
+    readiness: bool,
+    check: CheckFn,
+}
+
+#[derive(Clone)]
+struct Probe {
+    checks: Arc<Vec<Check>>,
+    readiness: bool,
+}
+
+#[derive(Clone)]
+struct Drain;
+
+impl Health {
+    /// Creates a new `Health` handler with no checks. Without checks, both
+    /// probes always succeed unless a shutdown is underway.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::health::Health;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount("/", Health::new())
+    /// }
+    /// ```
+    pub fn new() -> Self {
+        Health::default()
+    }
+
+    /// Registers a liveness check named `name` with a timeout of `timeout`.
+    ///
+    /// Liveness checks are run by both `/healthz` and `/readyz`. They should
+    /// only fail when the application is unable to make progress and must be
+    /// restarted. The check function `f` is called with the running Rocket
+    /// instance, which provides access to managed state, and returns a future
+    /// resolving to `Ok(())` if the check succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::health::Health;
+    ///
+    /// let health = Health::new()
+    ///     .liveness("tick", Duration::from_millis(50), |_| Box::pin(async {
+    ///         Ok::<_, String>(())
+    ///     }));
+    /// ```
+    pub fn liveness<N, F, E>(self, name: N, timeout: Duration, f: F) -> Self
+        where N: Into<Cow<'static, str>>,
+              F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<(), E>>,
+              F: Send + Sync + 'static,
+              E: Display + 'static
+    {
+        self.check(name, timeout, false, f)
+    }
+
+    /// Registers a readiness check named `name` with a timeout of `timeout`.
+    ///
+    /// Readiness checks are only run by `/readyz`. They should fail when the
+    /// application is temporarily unable to serve traffic, for instance,
+    /// because an upstream service or database is unavailable. The check
+    /// function `f` is called with the running Rocket instance, which provides
+    /// access to managed state, and returns a future resolving to `Ok(())` if
+    /// the check succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::health::Health;
+    ///
+    /// let health = Health::new()
+    ///     .readiness("uploads", Duration::from_secs(1), |_| Box::pin(async {
+    ///         rocket::tokio::fs::metadata("/tmp/uploads").await.map(|_| ())
+    ///     }));
+    /// ```
+    pub fn readiness<N, F, E>(self, name: N, timeout: Duration, f: F) -> Self
+        where N: Into<Cow<'static, str>>,
+              F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<(), E>>,
+              F: Send + Sync + 'static,
+              E: Display + 'static
+    {
+        self.check(name, timeout, true, f)
+    }
+
+    /// Sets the rank of the probe routes to `rank`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::health::Health;
+    ///
+    /// let health = Health::new().rank(-10);
+    /// ```
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    /// Additionally serves the progress of a shutdown at `/drainz`.
+    ///
+    /// The endpoint always responds with `200 OK` and a JSON document with
+    /// the shutdown's `stage`, one of `running`, `draining`, `stopping`, or
+    /// `terminating`, the milliseconds `elapsed_ms` since it was requested, or
+    /// `null`, and the number of in-flight `requests` and open `connections`.
+    /// See [`Shutdown::status()`](crate::Shutdown::status()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::health::Health;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount("/", Health::new().drain_endpoint())
+    /// }
+    /// ```
+    pub fn drain_endpoint(mut self) -> Self {
+        self.drain_endpoint = true;
+        self
+    }
+
+    fn check<N, F, E>(mut self, name: N, timeout: Duration, readiness: bool, f: F) -> Self
+        where N: Into<Cow<'static, str>>,
+              F: for<'r> Fn(&'r Rocket<Orbit>) -> BoxFuture<'r, Result<(), E>>,
+              F: Send + Sync + 'static,
+              E: Display + 'static
+    {
+        let check: CheckFn = Box::new(move |rocket| {
+            f(rocket).map(|result| result.map_err(|e| e.to_string())).boxed()
+        });
+
+        self.checks.push(Check { name: name.into(), timeout, readiness, check });
+        self
+    }
+}
+
+impl Probe {
+    async fn run(&self, rocket: &Rocket<Orbit>) -> (Status, Value) {
+        let shutdown = &rocket.shutdown;
+        if self.readiness && (shutdown.drain.notified() || shutdown.start.notified()) {
+            let progress = progress(rocket);
+            let value = json!({ "status": "draining", "checks": [], "shutdown": progress });
+            return (Status::ServiceUnavailable, value);
+        }
+
+        let checks = self.checks.iter()
+            .filter(|check| self.readiness || !check.readiness)
+            .map(|check| async move {
+                let start = Instant::now();
+                let result = tokio::time::timeout(check.timeout, (check.check)(rocket)).await;
+                let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+                match result {
+                    Ok(Ok(())) => json!({
+                        "name": check.name,
+                        "status": "up",
+                        "latency_ms": latency_ms,
+                    }),
+                    Ok(Err(error)) => json!({
+                        "name": check.name,
+                        "status": "down",
+                        "latency_ms": latency_ms,
+                        "error": error,
+                    }),
+                    Err(_) => json!({
+                        "name": check.name,
+                        "status": "down",
+                        "latency_ms": latency_ms,
+                        "error": format!("timed out after {:?}", check.timeout),
+                    }),
+                }
+            });
+
+        let checks = join_all(checks).await;
+        let up = checks.iter().all(|check| check["status"] == "up");
+        let (status, value) = match up {
+            true => (Status::Ok, "up"),
+            false => (Status::ServiceUnavailable, "down"),
+        };
+
+        (status, json!({ "status": value, "checks": checks }))
+    }
+}
+
+#[crate::async_trait]
+impl Handler for Probe {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let (status, value) = self.run(req.rocket()).await;
+        Outcome::from(req, (status, Json(value)))
+    }
+}
+
+/// The progress of a shutdown, as served by `/drainz`.
+fn progress(rocket: &Rocket<Orbit>) -> Value {
+    let status = rocket.shutdown().status();
+    json!({
+        "stage": status.stage.as_str(),
+        "elapsed_ms": status.elapsed.map(|elapsed| elapsed.as_secs_f64() * 1000.0),
+        "requests": status.requests,
+        "connections": status.connections,
+    })
+}
+
+#[crate::async_trait]
+impl Handler for Drain {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, Json(progress(req.rocket())))
+    }
+}
+
+impl From<Health> for Vec<Route> {
+    fn from(health: Health) -> Self {
+        let checks = Arc::new(health.checks);
+        let mut routes: Vec<Route> = [
+            ("/healthz", "Health: liveness", false),
+            ("/readyz", "Health: readiness", true)
+        ].into_iter()
+            .map(|(path, name, readiness)| {
+                let probe = Probe { checks: checks.clone(), readiness };
+                let mut route = Route::new(Method::Get, path, probe);
+                route.name = Some(name.into());
+                route
+            })
+            .collect();
+
+        if health.drain_endpoint {
+            let mut route = Route::new(Method::Get, "/drainz", Drain);
+            route.name = Some("Health: drain".into());
+            routes.push(route);
+        }
+
+        if let Some(rank) = health.rank {
+            routes.iter_mut().for_each(|route| route.rank = rank);
+        }
+
+        routes
+    }
+}
+
+impl std::fmt::Debug for Health {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Health")
+            .field("checks", &self.checks.iter().map(|c| &c.name).collect::<Vec<_>>())
+            .field("rank", &self.rank)
+            .field("drain_endpoint", &self.drain_endpoint)
+            .finish()
+    }
+}
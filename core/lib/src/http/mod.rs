@@ -4,9 +4,15 @@
 //! HTTP library when needed.
 
 mod cookies;
+#[cfg(feature = "secrets")]
+mod signed;
 
 #[doc(inline)]
 pub use rocket_http::*;
 
 #[doc(inline)]
 pub use cookies::*;
+
+#[cfg(feature = "secrets")]
+#[cfg_attr(nightly, doc(cfg(feature = "secrets")))]
+pub use signed::*;
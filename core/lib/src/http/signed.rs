@@ -0,0 +1,281 @@
+use std::fmt;
+use std::time::Duration;
+
+use hmac::{Mac, Hmac};
+use sha2::Sha256;
+use time::OffsetDateTime;
+
+use crate::config::SecretKey;
+use crate::http::Status;
+use crate::http::uri::Origin;
+use crate::request::{FromRequest, Outcome, Request};
+
+/// Signs and verifies expiring URIs.
+///
+/// A signed URI carries an expiration time and an HMAC-SHA256 signature of
+/// its path, query, and expiration time, keyed by the application's
+/// [`SecretKey`]. Signed URIs allow granting time-limited access to a resource,
+/// such as a download link sent by email, without sessions or a database: the
+/// holder of a valid link may use it until it expires, but cannot alter it.
+///
+/// [`Signed::sign()`] appends `expires` and `signature` query parameters to a
+/// URI. The [`ValidSignature`] request guard, or [`Signed::verify()`], checks
+/// them. Signatures are compared in constant time.
+///
+/// Because signatures are keyed by the `secret_key`, rotating the key
+/// invalidates all outstanding signed URIs. In the debug profile, where a
+/// random key is generated at launch if none is configured, signed URIs only
+/// remain valid until the application restarts.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::time::Duration;
+///
+/// use rocket::config::SecretKey;
+/// use rocket::http::{Signed, ValidSignature};
+///
+/// #[get("/share/<file>")]
+/// fn share(file: &str, key: &SecretKey) -> String {
+///     let uri = Signed::sign(key, uri!(download(file)), Duration::from_secs(60 * 60));
+///     format!("download for the next hour at: {}", uri)
+/// }
+///
+/// #[get("/download/<file>")]
+/// fn download(file: &str, _signature: ValidSignature) -> String {
+///     format!("contents of {}", file)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Signed;
+
+/// A request guard that succeeds if the request URI was signed by
+/// [`Signed::sign()`] and has not expired.
+///
+/// # Outcome
+///
+/// The guard fails with `403 Forbidden` and a [`SignatureError`] if the
+/// request URI is missing a signature or its signature is malformed, invalid,
+/// or expired. Otherwise, it succeeds.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::ValidSignature;
+///
+/// #[get("/download/<file>")]
+/// fn download(file: &str, signature: ValidSignature) -> String {
+///     format!("{} (link expires at {})", file, signature.expires())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidSignature {
+    expires: OffsetDateTime,
+}
+
+/// An error verifying a signed URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureError {
+    /// The URI has no `expires` or `signature` query parameter.
+    Missing,
+    /// The `expires` or `signature` query parameter is malformed.
+    Malformed,
+    /// The URI's expiration time has passed.
+    Expired,
+    /// The signature does not match the URI.
+    Mismatch,
+}
+
+impl Signed {
+    /// Domain separation for signatures keyed by the secret key.
+    const CONTEXT: &'static [u8] = b"rocket signed uri\n";
+
+    /// Returns `uri` signed with `key` so that it expires `ttl` from now.
+    ///
+    /// The returned URI is `uri` with `expires` and `signature` query
+    /// parameters appended, in that order. The signature covers the URI
+    /// exactly as written, so it must be requested verbatim.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Duration;
+    ///
+    /// use rocket::config::SecretKey;
+    /// use rocket::http::Signed;
+    ///
+    /// let key = SecretKey::generate().unwrap();
+    /// let uri = Signed::sign(&key, uri!("/download/a.zip?v=2"), Duration::from_secs(60));
+    /// assert!(uri.query().unwrap().starts_with("v=2&expires="));
+    /// assert!(Signed::verify(&key, &uri).is_ok());
+    /// ```
+    pub fn sign(key: &SecretKey, uri: Origin<'_>, ttl: Duration) -> Origin<'static> {
+        let ttl = time::Duration::try_from(ttl).unwrap_or(time::Duration::MAX);
+        let expires = OffsetDateTime::now_utc().checked_add(ttl)
+            .unwrap_or(time::macros::datetime!(9999-12-31 23:59:59 UTC));
+
+        Signed::sign_until(key, uri, expires)
+    }
+
+    /// Returns `uri` signed with `key` so that it expires at `expires`.
+    ///
+    /// See [`Signed::sign()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::config::SecretKey;
+    /// use rocket::http::{Signed, SignatureError};
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let key = SecretKey::generate().unwrap();
+    /// let uri = Signed::sign_until(&key, uri!("/report"), datetime!(2000-01-01 0:00 UTC));
+    /// assert_eq!(Signed::verify(&key, &uri), Err(SignatureError::Expired));
+    /// ```
+    pub fn sign_until(
+        key: &SecretKey,
+        uri: Origin<'_>,
+        expires: OffsetDateTime,
+    ) -> Origin<'static> {
+        let expires = format!("expires={}", expires.unix_timestamp());
+        let signed = match uri.query() {
+            Some(query) => format!("{}?{}&{}", uri.path().as_str(), query.as_str(), expires),
+            None => format!("{}?{}", uri.path().as_str(), expires),
+        };
+
+        let signature = Signed::mac(key, &signed).finalize().into_bytes();
+        let mut hex = [0u8; 64];
+        let hex = binascii::bin2hex(&signature, &mut hex).expect("32 bytes fit in 64 hex digits");
+        let hex = std::str::from_utf8(hex).expect("hex is UTF-8");
+
+        Origin::parse_owned(format!("{}&signature={}", signed, hex))
+            .expect("signed URI is a valid origin URI")
+    }
+
+    /// Verifies that `uri` was signed with `key` and has not expired,
+    /// returning its expiration time if so.
+    ///
+    /// The `signature` must be the last query parameter in `uri`, immediately
+    /// preceded by `expires`, as produced by [`Signed::sign()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Duration;
+    ///
+    /// use rocket::config::SecretKey;
+    /// use rocket::http::{Signed, SignatureError};
+    /// use rocket::http::uri::Origin;
+    ///
+    /// let key = SecretKey::generate().unwrap();
+    /// let uri = Signed::sign(&key, uri!("/download/a.zip"), Duration::from_secs(60));
+    /// assert!(Signed::verify(&key, &uri).is_ok());
+    ///
+    /// let other = SecretKey::generate().unwrap();
+    /// assert_eq!(Signed::verify(&other, &uri), Err(SignatureError::Mismatch));
+    ///
+    /// let tampered = uri.to_string().replace("a.zip", "b.zip");
+    /// let tampered = Origin::parse(&tampered).unwrap();
+    /// assert_eq!(Signed::verify(&key, &tampered), Err(SignatureError::Mismatch));
+    ///
+    /// let unsigned = uri!("/download/a.zip");
+    /// assert_eq!(Signed::verify(&key, &unsigned), Err(SignatureError::Missing));
+    /// ```
+    pub fn verify(key: &SecretKey, uri: &Origin<'_>) -> Result<OffsetDateTime, SignatureError> {
+        let query = uri.query().ok_or(SignatureError::Missing)?.as_str();
+        let (signed_query, signature) = query.rsplit_once('&')
+            .and_then(|(rest, last)| Some((rest, last.strip_prefix("signature=")?)))
+            .ok_or(SignatureError::Missing)?;
+
+        let expires = signed_query.rsplit('&').next()
+            .and_then(|last| last.strip_prefix("expires="))
+            .ok_or(SignatureError::Missing)?;
+
+        let mut bytes = [0u8; 32];
+        let signature = match signature.len() {
+            64 => binascii::hex2bin(signature.as_bytes(), &mut bytes)
+                .map_err(|_| SignatureError::Malformed)?,
+            _ => return Err(SignatureError::Malformed),
+        };
+
+        let expires = expires.parse::<i64>().ok()
+            .and_then(|seconds| OffsetDateTime::from_unix_timestamp(seconds).ok())
+            .ok_or(SignatureError::Malformed)?;
+
+        let signed = format!("{}?{}", uri.path().as_str(), signed_query);
+        Signed::mac(key, &signed)
+            .verify_slice(signature)
+            .map_err(|_| SignatureError::Mismatch)?;
+
+        if expires < OffsetDateTime::now_utc() {
+            return Err(SignatureError::Expired);
+        }
+
+        Ok(expires)
+    }
+
+    fn mac(key: &SecretKey, signed: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key.key.signing())
+            .expect("HMAC accepts any key length");
+
+        mac.update(Self::CONTEXT);
+        mac.update(signed.as_bytes());
+        mac
+    }
+}
+
+impl ValidSignature {
+    /// Returns the time at which the request URI's signature expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::ValidSignature;
+    ///
+    /// #[get("/download")]
+    /// fn download(signature: ValidSignature) -> String {
+    ///     format!("expires at {}", signature.expires())
+    /// }
+    /// ```
+    pub fn expires(&self) -> OffsetDateTime {
+        self.expires
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for ValidSignature {
+    type Error = SignatureError;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match Signed::verify(&req.rocket().config().secret_key, req.uri()) {
+            Ok(expires) => Outcome::Success(ValidSignature { expires }),
+            Err(e) => {
+                let uri = req.rocket().config().redact.uri(req.uri());
+                debug!(%uri, "rejecting signed uri: {e}");
+                Outcome::Error((Status::Forbidden, e))
+            }
+        }
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::Missing => write!(f, "missing `expires` or `signature` parameter"),
+            SignatureError::Malformed => {
+                write!(f, "malformed `expires` or `signature` parameter")
+            }
+            SignatureError::Expired => write!(f, "signed uri has expired"),
+            SignatureError::Mismatch => write!(f, "signature does not match uri"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError { }
@@ -0,0 +1,377 @@
+//! Translation of Rocket's built-in messages.
+//!
+//! Rocket's built-in messages, namely the bodies of the default catcher's
+//! error pages and the default messages of [form errors](crate::form::Error),
+//! are in English. The [`Translations`] fairing registers [`Catalog`]s of
+//! translations for these messages in other languages. For every request, the
+//! language is chosen by negotiating the request's `Accept-Language` header
+//! against the registered languages and English, falling back to the
+//! configured default `locale`. The choice is exposed via the [`Locale`]
+//! request guard, which applications can use to translate form errors and
+//! their own messages.
+//!
+//! # Configuration
+//!
+//! | parameter | type     | default | description                                 |
+//! |-----------|----------|---------|---------------------------------------------|
+//! | `locale`  | `string` | `"en"`  | language used when no requested one matches |
+//!
+//! # Message Keys
+//!
+//! A catalog maps message keys to translated templates. Templates may refer
+//! to the message's arguments by name, as in `{max}`.
+//!
+//! | key                           | English                          | arguments    |
+//! |-------------------------------|----------------------------------|--------------|
+//! | `status.<code>.reason`        | `Not Found`, ...                 |              |
+//! | `status.<code>.description`   | `The requested resource...`, ... |              |
+//! | `status.unknown.reason`       | `Unknown Error`                  |              |
+//! | `status.unknown.description`  | `An unknown error has occurred.` |              |
+//! | `error.reference`             | `Reference`                      |              |
+//! | `form.length.incomplete`      | `invalid length: incomplete`     |              |
+//! | `form.length.max`             | `length cannot exceed {max}`     | `max`        |
+//! | `form.length.min`             | `expected at least {min}`        | `min`        |
+//! | `form.length.between`         | `length must be between {min}...`| `min`, `max` |
+//! | `form.size.max`               | `size must not exceed {max}`     | `max`        |
+//! | `form.size.min`               | `size must be at least {min}`    | `min`        |
+//! | `form.size.between`           | `size must be between {min}...`  | `min`, `max` |
+//! | `form.empty`                  | `cannot be empty`                |              |
+//! | `form.choice.invalid`         | `invalid choice`                 |              |
+//! | `form.choice.one`             | `expected {choice}`              | `choice`     |
+//! | `form.choice.any`             | `expected one of {choices}`      | `choices`    |
+//! | `form.range.out`              | `value is out of range`          |              |
+//! | `form.range.max`              | `value cannot exceed {max}`      | `max`        |
+//! | `form.range.min`              | `value must be at least {min}`   | `min`        |
+//! | `form.range.between`          | `value must be between {min}...` | `min`, `max` |
+//! | `form.duplicate`              | `duplicate`                      |              |
+//! | `form.missing`                | `missing`                        |              |
+//! | `form.unexpected`             | `unexpected`                     |              |
+//! | `form.unknown`                | `unknown internal error`         |              |
+//! | `form.multipart`              | `invalid multipart: {error}`     | `error`      |
+//! | `form.utf8`                   | `invalid UTF-8: {error}`         | `error`      |
+//! | `form.char`                   | `invalid character: {error}`     | `error`      |
+//! | `form.int`                    | `invalid integer: {error}`       | `error`      |
+//! | `form.bool`                   | `invalid boolean: {error}`       | `error`      |
+//! | `form.float`                  | `invalid float: {error}`         | `error`      |
+//! | `form.addr`                   | `invalid address: {error}`       | `error`      |
+//! | `form.io`                     | `i/o error: {error}`             | `error`      |
+//!
+//! Messages missing from a catalog are displayed in English.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::form::{Form, Contextual};
+//! use rocket::i18n::{Catalog, Locale, Translations};
+//!
+//! #[derive(FromForm)]
+//! struct Signup<'r> {
+//!     #[field(validate = len(3..))]
+//!     name: &'r str,
+//! }
+//!
+//! #[post("/", data = "<form>")]
+//! fn signup(form: Form<Contextual<'_, Signup<'_>>>, locale: Locale<'_>) -> String {
+//!     form.context.errors()
+//!         .map(|e| locale.form_error(e))
+//!         .collect::<Vec<_>>()
+//!         .join("\n")
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let german = Catalog::new("de")
+//!         .status(404, "Nicht gefunden", "Die angeforderte Ressource existiert nicht.")
+//!         .message("error.reference", "Referenz")
+//!         .message("form.length.min", "mindestens {min} Zeichen erwartet");
+//!
+//!     rocket::build()
+//!         .mount("/", routes![signup])
+//!         .attach(Translations::new().add(german))
+//! }
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Rocket, Build, Request};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{AcceptLanguage, TypedHeader};
+use crate::request::{FromRequest, Outcome};
+
+/// The language of Rocket's built-in messages.
+const BUILT_IN: &str = "en";
+
+/// Translations of Rocket's built-in messages into one language.
+///
+/// See the [module level docs](self) for the message keys.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    tag: Cow<'static, str>,
+    messages: HashMap<Cow<'static, str>, Cow<'static, str>>,
+}
+
+/// Fairing registering [`Catalog`]s of translations.
+///
+/// See the [module level docs](self) for details.
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    catalogs: Vec<Arc<Catalog>>,
+    default: Option<String>,
+}
+
+/// Request guard for the language selected for the request.
+///
+/// The guard never fails. Without the [`Translations`] fairing, the language
+/// is always English.
+#[derive(Debug, Clone, Copy)]
+pub struct Locale<'r> {
+    tag: &'r str,
+    catalog: Option<&'r Catalog>,
+}
+
+impl Catalog {
+    /// Creates an empty catalog for the language `tag`, such as `de` or
+    /// `pt-BR`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::i18n::Catalog;
+    ///
+    /// let catalog = Catalog::new("fr").message("form.missing", "manquant");
+    /// assert_eq!(catalog.tag(), "fr");
+    /// assert_eq!(catalog.get("form.missing"), Some("manquant"));
+    /// ```
+    pub fn new<T: Into<Cow<'static, str>>>(tag: T) -> Self {
+        Catalog { tag: tag.into(), messages: HashMap::new() }
+    }
+
+    /// Sets the translation of the message `key` to `template`.
+    pub fn message<K, V>(mut self, key: K, template: V) -> Self
+        where K: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>
+    {
+        self.messages.insert(key.into(), template.into());
+        self
+    }
+
+    /// Sets the translations of the reason and description of the status
+    /// `code`, as displayed by the default catcher.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::i18n::Catalog;
+    ///
+    /// let catalog = Catalog::new("es")
+    ///     .status(404, "No encontrado", "No se pudo encontrar el recurso solicitado.");
+    ///
+    /// assert_eq!(catalog.get("status.404.reason"), Some("No encontrado"));
+    /// ```
+    pub fn status<R, D>(self, code: u16, reason: R, description: D) -> Self
+        where R: Into<Cow<'static, str>>, D: Into<Cow<'static, str>>
+    {
+        self.message(format!("status.{code}.reason"), reason)
+            .message(format!("status.{code}.description"), description)
+    }
+
+    /// Returns this catalog's language tag.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the translation of the message `key`, if there is one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(|v| &**v)
+    }
+}
+
+impl Translations {
+    /// Creates a fairing with no catalogs.
+    pub fn new() -> Self {
+        Translations::default()
+    }
+
+    /// Registers `catalog`, replacing any catalog previously registered for
+    /// the same language.
+    pub fn add(mut self, catalog: Catalog) -> Self {
+        self.catalogs.retain(|c| !c.tag.eq_ignore_ascii_case(&catalog.tag));
+        self.catalogs.push(Arc::new(catalog));
+        self
+    }
+
+    /// Returns the catalog registered for `tag`, if any.
+    pub fn catalog(&self, tag: &str) -> Option<&Catalog> {
+        self.catalogs.iter().find(|c| c.tag.eq_ignore_ascii_case(tag)).map(|c| &**c)
+    }
+
+    /// Returns the language used when no requested language matches.
+    ///
+    /// Returns `"en"` until the fairing has ignited.
+    pub fn default_locale(&self) -> &str {
+        self.default.as_deref().unwrap_or(BUILT_IN)
+    }
+
+    fn select<'r>(&'r self, req: &Request<'_>) -> Locale<'r> {
+        let mut available: Vec<&str> = self.catalogs.iter().map(|c| c.tag()).collect();
+        if self.catalog(BUILT_IN).is_none() {
+            available.push(BUILT_IN);
+        }
+
+        let tag = AcceptLanguage::from_values(req.headers().get(AcceptLanguage::NAME)).ok()
+            .and_then(|header| header.negotiate(&available))
+            .unwrap_or_else(|| self.default_locale());
+
+        Locale { tag, catalog: self.catalog(tag) }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Translations {
+    fn info(&self) -> Info {
+        Info { name: "Translations", kind: Kind::Ignite | Kind::Singleton }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let default = match rocket.figment().extract_inner::<String>("locale") {
+            Ok(locale) => locale,
+            Err(e) if e.missing() => BUILT_IN.into(),
+            Err(e) => {
+                error!("invalid `locale` configuration: {e}");
+                return Err(rocket);
+            }
+        };
+
+        let mut translations = self.clone();
+        translations.default = Some(default);
+        Ok(rocket.manage(translations))
+    }
+}
+
+impl<'r> Locale<'r> {
+    /// Returns the locale selected for `req`.
+    pub(crate) fn of(req: &'r Request<'_>) -> Locale<'r> {
+        let Some(translations) = req.rocket().state::<Translations>() else {
+            return Locale { tag: BUILT_IN, catalog: None };
+        };
+
+        // Cache the selection: negotiation isn't free.
+        struct Selected(Result<usize, String>);
+
+        let selected = req.local_cache(|| {
+            let locale = translations.select(req);
+            Selected(translations.catalogs.iter()
+                .position(|c| c.tag == locale.tag)
+                .ok_or_else(|| locale.tag.to_string()))
+        });
+
+        match &selected.0 {
+            Ok(i) => {
+                let catalog = &*translations.catalogs[*i];
+                Locale { tag: catalog.tag(), catalog: Some(catalog) }
+            }
+            Err(tag) => Locale { tag, catalog: None },
+        }
+    }
+
+    /// Returns the selected language's tag.
+    pub fn tag(&self) -> &'r str {
+        self.tag
+    }
+
+    /// Returns the selected language's catalog, or `None` if it is the
+    /// built-in English or has no catalog.
+    pub fn catalog(&self) -> Option<&'r Catalog> {
+        self.catalog
+    }
+
+    /// Returns the translation of `key`, if the selected language has one.
+    pub fn get(&self, key: &str) -> Option<&'r str> {
+        self.catalog.and_then(|c| c.get(key))
+    }
+
+    /// Returns the translation of `key`, or `default` if there is none, with
+    /// each `{name}` in it replaced by the corresponding argument in `args`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::i18n::Locale;
+    ///
+    /// #[get("/cart/<n>")]
+    /// fn cart(n: usize, locale: Locale<'_>) -> String {
+    ///     locale.translate("app.cart", "{n} items in your cart", &[("n", &n)])
+    /// }
+    /// ```
+    pub fn translate(
+        &self,
+        key: &str,
+        default: &str,
+        args: &[(&str, &dyn fmt::Display)]
+    ) -> String {
+        render(self.get(key).unwrap_or(default), args)
+    }
+
+    /// Returns the message of the form error `error` in the selected language.
+    pub fn form_error(&self, error: &crate::form::Error<'_>) -> String {
+        error.kind.localized(self)
+    }
+}
+
+/// Replaces each `{name}` in `template` with the corresponding argument.
+/// Placeholders without a corresponding argument are left untouched.
+pub(crate) fn render(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let arg = placeholder.find('}').and_then(|end| {
+            let name = &placeholder[1..end];
+            args.iter().find(|(n, _)| *n == name).map(|arg| (end, arg))
+        });
+
+        match arg {
+            Some((end, (_, value))) => {
+                use std::fmt::Write;
+                let _ = write!(output, "{value}");
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Locale<'r> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Infallible> {
+        Outcome::Success(Locale::of(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    #[test]
+    fn render_replaces_named_placeholders() {
+        assert_eq!(render("between {min} and {max}", &[("min", &1), ("max", &5)]),
+            "between 1 and 5");
+        assert_eq!(render("{a}{a}", &[("a", &"x")]), "xx");
+        assert_eq!(render("{unknown} {a", &[("a", &"x")]), "{unknown} {a");
+        assert_eq!(render("no placeholders", &[]), "no placeholders");
+    }
+}
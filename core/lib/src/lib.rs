@@ -60,13 +60,19 @@
 //! |-----------------|----------|---------------------------------------------------------|
 //! | `trace`         | Yes      | Enables the default Rocket tracing [subscriber].        |
 //! | `http2`         | Yes      | Support for HTTP/2 (enabled by default).                |
-//! | `secrets`       | No       | Support for authenticated, encrypted [private cookies]. |
+//! | `multipart`     | Yes      | Support for [multipart forms] and file uploads.         |
+//! | `secrets`       | No       | Support for [private cookies] and [signed URIs].        |
 //! | `tls`           | No       | Support for [TLS] encrypted connections.                |
 //! | `mtls`          | No       | Support for verified clients via [mutual TLS].          |
 //! | `json`          | No       | Support for [JSON (de)serialization].                   |
 //! | `msgpack`       | No       | Support for [MessagePack (de)serialization].            |
 //! | `uuid`          | No       | Support for [UUID value parsing and (de)serialization]. |
 //! | `tokio-macros`  | No       | Enables the `macros` feature in the exported `tokio`    |
+//! | `webhook`       | No       | Support for verifying [webhook signatures].             |
+//! | `plugins`       | No       | Support for loading [plugins] from dynamic libraries.   |
+//! | `tower`         | No       | Support for [tower services and layers].                |
+//! | `html`          | No       | Support for [selecting HTML] in local responses.        |
+//! | `nfc`           | No       | Support for [Unicode normalization] of request paths.   |
 //! | `http3-preview` | No       | Experimental preview support for [HTTP/3].              |
 //!
 //! Disabled features can be selectively enabled in `Cargo.toml`:
@@ -76,20 +82,53 @@
 //! rocket = { version = "0.6.0-dev", features = ["secrets", "tls", "json"] }
 //! ```
 //!
-//! Conversely, HTTP/2 can be disabled:
+//! Conversely, default features can be disabled:
 //!
 //! ```toml
 //! [dependencies]
 //! rocket = { version = "0.6.0-dev", default-features = false }
 //! ```
 //!
+//! ### Minimal Builds
+//!
+//! Disabling all default features yields Rocket's _minimal_ feature set,
+//! intended for deployments where binary size and cold-start time matter,
+//! such as serverless functions. In a minimal build:
+//!
+//!   * Only HTTP/1 is supported; the `h2` stack isn't compiled.
+//!   * Multipart forms forward with `415 Unsupported Media Type`; `multer` and
+//!     its dependencies aren't compiled.
+//!   * The default tracing subscriber and its pretty-printer aren't compiled.
+//!     Events are still emitted and can be collected by any subscriber the
+//!     application installs.
+//!   * `tokio`'s macros, such as `tokio::select!`, aren't compiled. Rocket's own
+//!     `#[launch]` and `#[rocket::main]` remain available.
+//!
+//! Features are then enabled individually as needed. For instance, a minimal
+//! JSON API:
+//!
+//! ```toml
+//! [dependencies]
+//! rocket = { version = "0.6.0-dev", default-features = false, features = ["json"] }
+//! ```
+//!
+//! Templating and WebSocket support live in the separate `rocket_dyn_templates`
+//! and `rocket_ws` crates and are thus only compiled when depended on.
+//!
 //! [subscriber]: crate::trace::subscriber
+//! [multipart forms]: crate::form::Form#multipart-forms
 //! [JSON (de)serialization]: crate::serde::json
 //! [MessagePack (de)serialization]: crate::serde::msgpack
 //! [UUID value parsing and (de)serialization]: crate::serde::uuid
 //! [private cookies]: https://rocket.rs/master/guide/requests/#private-cookies
+//! [signed URIs]: crate::http::Signed
 //! [TLS]: https://rocket.rs/master/guide/configuration/#tls
 //! [mutual TLS]: crate::mtls
+//! [webhook signatures]: crate::webhook
+//! [plugins]: crate::plugin
+//! [tower services and layers]: crate::service#tower
+//! [selecting HTML]: crate::local::blocking::LocalResponse::html_select()
+//! [Unicode normalization]: crate::config::NormalizeConfig
 //! [HTTP/3]: crate::listener::quic
 //!
 //! ## Configuration
@@ -148,12 +187,37 @@ pub mod fs;
 pub mod http;
 pub mod listener;
 pub mod shutdown;
+pub mod load;
+pub mod runtime;
+pub mod task;
+pub mod timing;
+pub mod profile;
+pub mod i18n;
+pub mod service;
 #[cfg(feature = "tls")]
 #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
 pub mod tls;
 #[cfg(feature = "mtls")]
 #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
 pub mod mtls;
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+pub mod health;
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+pub mod admin;
+#[cfg(feature = "json")]
+#[cfg_attr(nightly, doc(cfg(feature = "json")))]
+pub mod pagination;
+#[cfg(all(feature = "secrets", feature = "json"))]
+#[cfg_attr(nightly, doc(cfg(all(feature = "secrets", feature = "json"))))]
+pub mod prefs;
+#[cfg(feature = "webhook")]
+#[cfg_attr(nightly, doc(cfg(feature = "webhook")))]
+pub mod webhook;
+#[cfg(feature = "plugins")]
+#[cfg_attr(nightly, doc(cfg(feature = "plugins")))]
+pub mod plugin;
 
 #[path = "rocket.rs"]
 mod rkt;
@@ -179,6 +243,7 @@ mod erased;
 #[doc(inline)] pub use crate::rkt::Rocket;
 #[doc(inline)] pub use crate::shutdown::Shutdown;
 #[doc(inline)] pub use crate::state::State;
+#[doc(hidden)] pub use crate::state::Managed;
 
 /// Retrofits support for `async fn` in trait impls and declarations.
 ///
@@ -225,16 +290,28 @@ pub fn custom<T: figment::Provider>(provider: T) -> Rocket<Build> {
 
 /// WARNING: This is unstable! Do not use this method outside of Rocket!
 #[doc(hidden)]
-pub fn async_run<F, R>(fut: F, workers: usize, sync: usize, force_end: bool, name: &str) -> R
+pub fn async_run<F, R>(
+    fut: F,
+    workers: usize,
+    sync: usize,
+    stack_size: usize,
+    force_end: bool,
+    name: &str
+) -> R
     where F: std::future::Future<Output = R>
 {
-    let runtime = tokio::runtime::Builder::new_multi_thread()
-        .thread_name(name)
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.thread_name(name)
         .worker_threads(workers)
         .max_blocking_threads(sync)
-        .enable_all()
-        .build()
-        .expect("create tokio runtime");
+        .enable_all();
+
+    if stack_size != 0 {
+        builder.thread_stack_size(stack_size);
+    }
+
+    runtime::instrument(&mut builder, workers, sync);
+    let runtime = builder.build().expect("create tokio runtime");
 
     let result = runtime.block_on(fut);
     if force_end {
@@ -247,7 +324,7 @@ pub fn async_run<F, R>(fut: F, workers: usize, sync: usize, force_end: bool, nam
 /// WARNING: This is unstable! Do not use this method outside of Rocket!
 #[doc(hidden)]
 pub fn async_test<R>(fut: impl std::future::Future<Output = R>) -> R {
-    async_run(fut, 1, 32, true, &format!("{WORKER_PREFIX}-test-thread"))
+    async_run(fut, 1, 32, 0, true, &format!("{WORKER_PREFIX}-test-thread"))
 }
 
 /// WARNING: This is unstable! Do not use this method outside of Rocket!
@@ -270,8 +347,10 @@ pub fn async_main<R>(fut: impl std::future::Future<Output = R> + Send) -> R {
     let fig = Config::figment();
     let workers = fig.extract_inner(Config::WORKERS).unwrap_or_else(bail);
     let max_blocking = fig.extract_inner(Config::MAX_BLOCKING).unwrap_or_else(bail);
+    let stack_size = fig.extract_inner(Config::THREAD_STACK_SIZE).unwrap_or_else(bail);
+    let name: String = fig.extract_inner(Config::THREAD_NAME).unwrap_or_else(bail);
     let force = fig.focus(Config::SHUTDOWN).extract_inner("force").unwrap_or_else(bail);
-    async_run(fut, workers, max_blocking, force, &format!("{WORKER_PREFIX}-thread"))
+    async_run(fut, workers, max_blocking, stack_size, force, &name)
 }
 
 /// Executes a `future` to completion on a new tokio-based Rocket async runtime.
@@ -360,8 +439,5 @@ pub fn execute<R, F>(future: F) -> R
 fn running_within_rocket_async_rt() -> impl std::future::Future<Output = bool> {
     use futures::FutureExt;
 
-    tokio::task::spawn_blocking(|| {
-        let this = std::thread::current();
-        this.name().map_or(false, |s| s.starts_with(WORKER_PREFIX))
-    }).map(|r| r.unwrap_or(false))
+    tokio::task::spawn_blocking(runtime::is_rocket_thread).map(|r| r.unwrap_or(false))
 }
@@ -1,11 +1,15 @@
+use std::time::SystemTime;
+use std::sync::atomic::Ordering;
+
 use futures::future::{FutureExt, Future};
 
 use crate::trace::Trace;
 use crate::util::Formatter;
 use crate::data::IoHandler;
-use crate::http::{Method, Status, Header};
+use crate::http::{Method, Status, StatusClass, Header, RetryAfter};
 use crate::outcome::Outcome;
 use crate::form::Form;
+use crate::catcher::{ErrorReference, ErrorSummary};
 use crate::{route, catcher, Rocket, Orbit, Request, Response, Data};
 
 // A token returned to force the execution of one method before another.
@@ -56,6 +60,15 @@ impl Rocket<Orbit> {
         req: &mut Request<'_>,
         data: &mut Data<'_>
     ) -> RequestToken {
+        // Count body bytes as they are read, before any transforms apply.
+        let shared = req.state.shared.clone();
+        data.chain_inspect(move |bytes| {
+            shared.bytes_read.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        });
+
+        // Normalize the path, if configured, before anything inspects it.
+        req.normalize_uri();
+
         // Check if this is a form and if the form contains the special _method
         // field which we use to reinterpret the request's method.
         if req.method() == Method::Post && req.content_type().map_or(false, |v| v.is_form()) {
@@ -73,11 +86,18 @@ impl Rocket<Orbit> {
         // Run request fairings.
         self.fairings.handle_request(req, data).await;
 
+        // Run data fairings, which may wrap or replace the body data. Data
+        // forwarded from a parent request has already been processed by them.
+        if !req.has_forwarded_data() {
+            self.fairings.handle_data(req, data).await;
+        }
+
         RequestToken
     }
 
     /// Dispatches the request to the router and processes the outcome to
-    /// produce a response. If the initial outcome is a *forward* and the
+    /// produce a response. Requests over the configured load limits are first
+    /// queued or shed with a `503`. If the initial outcome is a *forward* and the
     /// request was a HEAD request, the request is rewritten and rerouted as a
     /// GET. This is automatic HEAD handling.
     ///
@@ -98,22 +118,24 @@ impl Rocket<Orbit> {
         // Remember if the request is `HEAD` for later body stripping.
         let was_head_request = request.method() == Method::Head;
 
-        // Route the request and run the user's handlers.
-        let mut response = match self.route(request, data).await {
-            Outcome::Success(response) => response,
-            Outcome::Forward((data, _)) if request.method() == Method::Head => {
-                tracing::Span::current().record("autohandled", true);
+        // Wait for room to dispatch the request. If there is none, shed it.
+        // Internal requests are accounted for by their parent request.
+        let permit = match request.is_internal() {
+            true => Some(None),
+            false => self.load.request().await.map(Some),
+        };
 
-                // Dispatch the request again with Method `GET`.
-                request._set_method(Method::Get);
-                match self.route(request, data).await {
-                    Outcome::Success(response) => response,
-                    Outcome::Error(status) => self.dispatch_error(status, request).await,
-                    Outcome::Forward((_, status)) => self.dispatch_error(status, request).await,
+        let mut response = match permit {
+            Some(_) => self.route_and_handle(request, data).await,
+            None => {
+                let mut response = self.dispatch_error(Status::ServiceUnavailable, request).await;
+                let retry_after = self.config.load.retry_after;
+                if retry_after != 0 && !response.headers().contains("Retry-After") {
+                    response.set_header(RetryAfter::seconds(retry_after.into()));
                 }
+
+                response
             }
-            Outcome::Forward((_, status)) => self.dispatch_error(status, request).await,
-            Outcome::Error(status) => self.dispatch_error(status, request).await,
         };
 
         // Set the cookies. Note that error responses will only include cookies
@@ -131,19 +153,49 @@ impl Rocket<Orbit> {
             }
         }
 
+        // Apply the header policies of the mount the route was mounted with.
+        if let Some(route) = request.route() {
+            for policy in &route.header_policies {
+                policy.apply(request, &mut response);
+            }
+        }
+
         // Run the response fairings.
         self.fairings.handle_response(request, &mut response).await;
 
+        // Send preload links as `Link` headers. No `103 Early Hints` is sent.
+        let links: Vec<_> = response.preload_links().iter()
+            .map(|h| Header::new(h.name().to_string(), h.value().to_string()))
+            .collect();
+
+        for link in links {
+            response.adjoin_header(link);
+        }
+
         // Strip the body if this is a `HEAD` request or a 304 response.
         if was_head_request || response.status() == Status::NotModified {
             response.strip_body();
         }
 
+        // If the response has trailers, announce them and leave the body
+        // unsized so that it is chunked and the trailers can follow it.
+        let has_trailers = !response.trailers().is_empty();
+        if has_trailers {
+            let mut names: Vec<_> = response.trailers().iter()
+                .map(|h| h.name().to_string())
+                .collect();
+
+            names.dedup();
+            response.set_raw_header("Trailer", names.join(", "));
+        }
+
         // If the response status is 204, strip the body and its size (no
         // content-length header). Otherwise, check if the body is sized and use
         // that size to set the content-length headr appropriately.
         if response.status() == Status::NoContent {
             *response.body_mut() = crate::response::Body::unsized_none();
+        } else if has_trailers {
+            response.remove_header("Content-Length");
         } else if let Some(size) = response.body_mut().size().await {
             response.set_raw_header("Content-Length", size.to_string());
         }
@@ -152,7 +204,82 @@ impl Rocket<Orbit> {
             response.set_raw_header("Alt-Svc", alt_svc);
         }
 
-        // TODO: Should upgrades be handled here? We miss them on local clients.
+        // TODO: Should upgrades be handled here?
+        response
+    }
+
+    /// Routes the request and runs the user's handlers. If the outcome is a
+    /// forward of a `HEAD` request, the request is rerouted as a `GET`. If the
+    /// final outcome is a forward, it is handled by `dispatch_forward()`. If it
+    /// is an error, the appropriate catcher is invoked.
+    async fn route_and_handle<'s, 'r: 's>(
+        &'s self,
+        request: &'r Request<'s>,
+        data: Data<'r>
+    ) -> Response<'r> {
+        match self.route(request, data).await {
+            Outcome::Success(response) => response,
+            Outcome::Forward((data, _)) if request.method() == Method::Head => {
+                tracing::Span::current().record("autohandled", true);
+
+                // Dispatch the request again with Method `GET`.
+                request.set_auto_head();
+                match self.route(request, data).await {
+                    Outcome::Success(response) => response,
+                    Outcome::Error(status) => self.dispatch_error(status, request).await,
+                    Outcome::Forward((_, status)) => self.dispatch_forward(status, request).await,
+                }
+            }
+            Outcome::Forward((_, status)) => self.dispatch_forward(status, request).await,
+            Outcome::Error(status) => self.dispatch_error(status, request).await,
+        }
+    }
+
+    /// Responds to a request that was forwarded by every matching route, the
+    /// last with `status`. If `status` is `404` and some route matches the
+    /// request's URI with a different method, then:
+    ///
+    ///   * An `OPTIONS` request receives a `204` advertising allowed methods,
+    ///     unless [`Config::auto_options`] is `false`.
+    ///   * Any other request receives a `405` with an `Allow` header, unless
+    ///     [`Config::method_not_allowed`] is `false`.
+    ///
+    /// Otherwise, the error catcher for `status` is invoked. In the debug
+    /// profile, the recorded guard failures are logged first.
+    ///
+    /// [`Config::method_not_allowed`]: crate::Config::method_not_allowed
+    /// [`Config::auto_options`]: crate::Config::auto_options
+    async fn dispatch_forward<'r, 's: 'r>(
+        &'s self,
+        status: Status,
+        request: &'r Request<'s>
+    ) -> Response<'r> {
+        for failure in request.guard_failures() {
+            warn!(name: "guard_failure", status = failure.status.code, "{failure}");
+        }
+
+        if status != Status::NotFound {
+            return self.dispatch_error(status, request).await;
+        }
+
+        let method = request.method();
+        let allowed = request.router().allowed_methods(request);
+        let allow = allowed.iter().map(|m| m.as_str()).collect::<Vec<_>>().join(", ");
+        if !allowed.is_empty() && method == Method::Options && self.config.auto_options {
+            tracing::Span::current().record("autohandled", true);
+            return Response::build()
+                .status(Status::NoContent)
+                .raw_header("Allow", allow)
+                .finalize();
+        }
+
+        if allowed.is_empty() || allowed.contains(&method) || !self.config.method_not_allowed {
+            return self.dispatch_error(status, request).await;
+        }
+
+        request.set_allowed_methods(allowed);
+        let mut response = self.dispatch_error(Status::MethodNotAllowed, request).await;
+        response.set_raw_header("Allow", allow);
         response
     }
 
@@ -191,7 +318,7 @@ impl Rocket<Orbit> {
     #[inline]
     #[tracing::instrument("routing", skip_all, fields(
         method = %request.method(),
-        uri = %request.uri(),
+        uri = %self.config.redact.uri(request.uri()),
         format = request.format().map(display),
     ))]
     async fn route<'s, 'r: 's>(
@@ -202,20 +329,29 @@ impl Rocket<Orbit> {
         // Go through all matching routes until we fail or succeed or run out of
         // routes to try, in which case we forward with the last status.
         let mut status = Status::NotFound;
-        for route in self.router.route(request) {
+        for route in request.router().route(request) {
             // Retrieve and set the requests parameters.
             route.trace_info();
             request.set_route(route);
 
             let name = route.name.as_deref();
-            let outcome = catch_handle(name, || route.handler.handle(request, data)).await
-                .unwrap_or(Outcome::Error(Status::InternalServerError));
+            let outcome = catch_handle(name, || match request.is_auto_head() {
+                true => route.handler.handle_head(request, data),
+                false => route.handler.handle(request, data),
+            }).await.unwrap_or(Outcome::Error(Status::InternalServerError));
 
             // Check if the request processing completed (Some) or if the
             // request needs to be forwarded. If it does, continue the loop
             outcome.trace_info();
             match outcome {
-                o@Outcome::Success(_) | o@Outcome::Error(_) => return o,
+                Outcome::Success(mut response) => {
+                    if let Some(deprecated) = &route.deprecated {
+                        deprecated.set_headers(&mut response);
+                    }
+
+                    return Outcome::Success(response);
+                }
+                o@Outcome::Error(_) => return o,
                 Outcome::Forward(forwarded) => (data, status) = forwarded,
             }
         }
@@ -230,7 +366,10 @@ impl Rocket<Orbit> {
     //
     // On catcher error, the 500 error catcher is attempted. If _that_ errors,
     // the (infallible) default 500 error cather is used.
-    #[tracing::instrument("catching", skip_all, fields(status = status.code, uri = %req.uri()))]
+    #[tracing::instrument("catching", skip_all, fields(
+        status = status.code,
+        uri = %self.config.redact.uri(req.uri()),
+    ))]
     pub(crate) async fn dispatch_error<'r, 's: 'r>(
         &'s self,
         mut status: Status,
@@ -239,10 +378,24 @@ impl Rocket<Orbit> {
         // We may wish to relax this in the future.
         req.cookies().reset_delta();
 
-        loop {
+        let mut response = loop {
+            // Server errors are assigned a reference before catchers run.
+            if status.class() == StatusClass::ServerError && req.error_reference().is_none() {
+                let reference = req.assign_error_reference();
+                error!(%reference, status = status.code, "server error");
+                self.errors.record(ErrorSummary {
+                    reference,
+                    status,
+                    method: req.method(),
+                    path: req.uri().path().to_string(),
+                    route: req.route().and_then(|route| route.name.clone()),
+                    time: SystemTime::now(),
+                });
+            }
+
             // Dispatch to the `status` catcher.
             match self.invoke_catcher(status, req).await {
-                Ok(r) => return r,
+                Ok(r) => break r,
                 // If the catcher failed, try `500` catcher, unless this is it.
                 Err(e) if status.code != 500 => {
                     warn!(status = e.map(|r| r.code), "catcher failed: trying 500 catcher");
@@ -251,10 +404,18 @@ impl Rocket<Orbit> {
                 // The 500 catcher failed. There's no recourse. Use default.
                 Err(e) => {
                     error!(status = e.map(|r| r.code), "500 catcher failed");
-                    return catcher::default_handler(Status::InternalServerError, req);
+                    break catcher::default_handler(Status::InternalServerError, req);
                 }
             }
+        };
+
+        if let Some(reference) = req.error_reference() {
+            if !response.headers().contains(ErrorReference::HEADER) {
+                response.set_raw_header(ErrorReference::HEADER, reference.to_string());
+            }
         }
+
+        response
     }
 
     /// Invokes the handler with `req` for catcher with status `status`.
@@ -272,7 +433,7 @@ impl Rocket<Orbit> {
         status: Status,
         req: &'r Request<'s>
     ) -> Result<Response<'r>, Option<Status>> {
-        if let Some(catcher) = self.router.catch(status, req) {
+        if let Some(catcher) = req.router().catch(status, req) {
             catcher.trace_info();
             catch_handle(catcher.name.as_deref(), || catcher.handler.handle(status, req)).await
                 .map(|result| result.map_err(Some))
@@ -0,0 +1,118 @@
+use std::fmt;
+use std::future::{Future, IntoFuture};
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+
+use tokio::sync::Notify;
+
+use crate::listener::Endpoint;
+
+/// A handle to the endpoints Rocket is bound to, available once it launches.
+///
+/// Returned by [`Rocket::bound_addresses()`](crate::Rocket::bound_addresses())
+/// for an ignited or orbiting instance. `.await`ing the handle resolves to the
+/// endpoints once Rocket has bound its listeners, or immediately if it already
+/// has. When a port of `0` is configured, the endpoints contain the port
+/// actually assigned by the operating system, allowing integration tests and
+/// service registration code to discover it.
+///
+/// If the instance of Rocket is dropped without binding, say because binding
+/// failed, the handle resolves to an empty vector.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[rocket::main]
+/// # async fn main() -> Result<(), rocket::Error> {
+/// let rocket = rocket::custom(rocket::Config::figment().merge(("port", 0)))
+///     .ignite().await?;
+///
+/// let bound = rocket.bound_addresses();
+/// rocket::tokio::spawn(async move {
+///     let endpoints = bound.await;
+///     let port = endpoints[0].tcp().expect("tcp endpoint").port();
+///     println!("registering service on port {port}");
+/// });
+///
+/// rocket.launch().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BoundAddresses {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    endpoints: OnceLock<Vec<Endpoint>>,
+    notify: Notify,
+}
+
+/// Owned by an ignited or orbiting `Rocket`; sets the endpoints exactly once,
+/// falling back to none when dropped.
+pub(crate) struct Binding(BoundAddresses);
+
+impl BoundAddresses {
+    /// Returns the bound endpoints if Rocket has bound its listeners, or
+    /// `None` otherwise.
+    pub fn get(&self) -> Option<&[Endpoint]> {
+        self.inner.endpoints.get().map(|endpoints| endpoints.as_slice())
+    }
+}
+
+impl IntoFuture for BoundAddresses {
+    type Output = Vec<Endpoint>;
+
+    type IntoFuture = Pin<Box<dyn Future<Output = Vec<Endpoint>> + Send + 'static>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            loop {
+                // Create the future before checking to avoid a missed wakeup.
+                let notified = self.inner.notify.notified();
+                if let Some(endpoints) = self.inner.endpoints.get() {
+                    return endpoints.clone();
+                }
+
+                notified.await;
+            }
+        })
+    }
+}
+
+impl Binding {
+    pub fn new() -> Self {
+        Binding(BoundAddresses {
+            inner: Arc::new(Inner { endpoints: OnceLock::new(), notify: Notify::new() }),
+        })
+    }
+
+    pub fn handle(&self) -> BoundAddresses {
+        self.0.clone()
+    }
+
+    /// Sets the endpoints if they haven't been set and wakes all waiters.
+    pub fn set(&self, endpoints: &[Endpoint]) {
+        if self.0.inner.endpoints.set(endpoints.to_vec()).is_ok() {
+            self.0.inner.notify.notify_waiters();
+        }
+    }
+}
+
+impl Drop for Binding {
+    fn drop(&mut self) {
+        self.set(&[]);
+    }
+}
+
+impl fmt::Debug for BoundAddresses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoundAddresses").field(&self.get()).finish()
+    }
+}
+
+impl fmt::Debug for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
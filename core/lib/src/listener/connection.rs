@@ -10,6 +10,23 @@ use super::Endpoint;
 #[derive(Clone)]
 pub struct Certificates<'r>(Cow<'r, [der::CertificateDer<'r>]>);
 
+/// Details of the TLS session negotiated on a connection.
+///
+/// Values are as reported by the TLS implementation. With `rustls`, versions
+/// are of the form `TLSv1_3` and cipher suites of the form
+/// `TLS13_AES_128_GCM_SHA256`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// The negotiated ALPN protocol, such as `h2`, if any.
+    pub alpn: Option<String>,
+    /// The negotiated protocol version, if known.
+    pub version: Option<Cow<'static, str>>,
+    /// The negotiated cipher suite, if known.
+    pub cipher_suite: Option<Cow<'static, str>>,
+    /// The server name (SNI) requested by the client, if any.
+    pub server_name: Option<String>,
+}
+
 pub trait Connection: AsyncRead + AsyncWrite + Send + Unpin {
     fn endpoint(&self) -> io::Result<Endpoint>;
 
@@ -22,6 +39,20 @@ pub trait Connection: AsyncRead + AsyncWrite + Send + Unpin {
     /// Defaults to an empty vector to indicate that no certificates were
     /// presented.
     fn certificates(&self) -> Option<Certificates<'_>> { None }
+
+    /// The local endpoint the connection was accepted on.
+    ///
+    /// Defaults to an `Unsupported` error to indicate that the local endpoint
+    /// is unknown.
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Details of the TLS session negotiated on the connection, if any.
+    ///
+    /// Defaults to `None` to indicate that the connection is not secured by
+    /// TLS.
+    fn tls_info(&self) -> Option<TlsInfo> { None }
 }
 
 impl<A: Connection, B: Connection> Connection for Either<A, B> {
@@ -38,6 +69,20 @@ impl<A: Connection, B: Connection> Connection for Either<A, B> {
             Either::Right(c) => c.certificates(),
         }
     }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        match self {
+            Either::Left(c) => c.local_endpoint(),
+            Either::Right(c) => c.local_endpoint(),
+        }
+    }
+
+    fn tls_info(&self) -> Option<TlsInfo> {
+        match self {
+            Either::Left(c) => c.tls_info(),
+            Either::Right(c) => c.tls_info(),
+        }
+    }
 }
 
 impl Certificates<'_> {
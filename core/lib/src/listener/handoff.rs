@@ -0,0 +1,335 @@
+//! Zero-downtime upgrades by handing listening sockets off to a new process.
+//!
+//! On an upgrade, Rocket executes the current binary anew, letting the new
+//! process inherit the listening sockets of the current one. The new process
+//! binds to the inherited sockets instead of creating its own and, once it
+//! has lifted off, reports its readiness over an inherited socket pair. Only
+//! then does the current process begin a graceful shutdown. Because both
+//! processes share the same sockets, no connection is ever refused.
+//!
+//! Sockets passed by systemd via socket activation (`LISTEN_FDS`) are
+//! inherited the same way.
+
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::Duration;
+
+/// Comma-separated list of inherited listening socket file descriptors.
+const LISTEN_FDS: &str = "ROCKET_LISTEN_FDS";
+
+/// File descriptor of the socket on which to report readiness.
+const READY_FD: &str = "ROCKET_READY_FD";
+
+/// The first file descriptor passed by systemd socket activation.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Sockets inherited from a previous process, not yet claimed by a listener.
+static INHERITED: OnceLock<Mutex<Vec<OwnedFd>>> = OnceLock::new();
+
+/// Sockets bound by this process, to be handed off on upgrade.
+static BOUND: Mutex<Vec<RawFd>> = Mutex::new(Vec::new());
+
+/// Whether the sockets have been handed off to a new process.
+static HANDED_OFF: AtomicBool = AtomicBool::new(false);
+
+fn is_socket(fd: RawFd) -> bool {
+    let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+    let res = unsafe { libc::fstat(fd, stat.as_mut_ptr()) };
+    res == 0 && unsafe { stat.assume_init() }.st_mode & libc::S_IFMT == libc::S_IFSOCK
+}
+
+fn is_listening(fd: RawFd) -> bool {
+    let mut value: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let res = unsafe {
+        let ptr = &mut value as *mut libc::c_int as *mut libc::c_void;
+        libc::getsockopt(fd, libc::SOL_SOCKET, libc::SO_ACCEPTCONN, ptr, &mut len)
+    };
+
+    res == 0 && value != 0
+}
+
+fn set_cloexec(fd: RawFd, cloexec: bool) -> io::Result<()> {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let flags = match cloexec {
+        true => flags | libc::FD_CLOEXEC,
+        false => flags & !libc::FD_CLOEXEC,
+    };
+
+    match unsafe { libc::fcntl(fd, libc::F_SETFD, flags) } {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Returns the file descriptors passed to the process with ID `pid` according
+/// to the environment variables `env` returns: those in [`LISTEN_FDS`] or, if
+/// it's unset, those passed by systemd socket activation.
+fn passed_fds(env: impl Fn(&str) -> Option<String>, pid: u32) -> Vec<RawFd> {
+    match env(LISTEN_FDS) {
+        Some(fds) => fds.split(',').filter_map(|fd| fd.trim().parse().ok()).collect(),
+        None => {
+            let listen_pid = env("LISTEN_PID").and_then(|pid| pid.parse::<u32>().ok());
+            let count = env("LISTEN_FDS").and_then(|n| n.parse::<RawFd>().ok());
+            match (listen_pid, count) {
+                (Some(listen_pid), Some(n)) if listen_pid == pid => {
+                    (SD_LISTEN_FDS_START..SD_LISTEN_FDS_START + n).collect()
+                }
+                _ => vec![],
+            }
+        }
+    }
+}
+
+fn inherited() -> &'static Mutex<Vec<OwnedFd>> {
+    INHERITED.get_or_init(|| {
+        let env = |name: &str| std::env::var(name).ok();
+        let fds = passed_fds(env, std::process::id()).into_iter()
+            .filter(|&fd| is_socket(fd) && is_listening(fd))
+            .filter(|&fd| set_cloexec(fd, true).is_ok())
+            // SAFETY: `fd` is an open socket that was passed to this process
+            // for its exclusive use, and it is only ever taken once.
+            .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })
+            .collect();
+
+        Mutex::new(fds)
+    })
+}
+
+/// Removes and returns the first of `fds` that `claim` accepts.
+fn take<T>(
+    fds: &Mutex<Vec<OwnedFd>>,
+    claim: impl Fn(OwnedFd) -> Result<T, OwnedFd>
+) -> Option<T> {
+    let mut fds = fds.lock().expect("handoff lock");
+    let mut claimed = None;
+    for fd in std::mem::take(&mut *fds) {
+        match claimed {
+            Some(_) => fds.push(fd),
+            None => match claim(fd) {
+                Ok(value) => claimed = Some(value),
+                Err(fd) => fds.push(fd),
+            }
+        }
+    }
+
+    claimed
+}
+
+/// Takes the inherited TCP listener bound to `addr`, if any. A port of `0`
+/// matches any port.
+pub fn take_tcp(addr: SocketAddr) -> Option<std::net::TcpListener> {
+    take(inherited(), |fd| claim_tcp(fd, addr))
+}
+
+/// Takes the inherited Unix listener bound to `path`, if any.
+pub fn take_unix(path: &Path) -> Option<std::os::unix::net::UnixListener> {
+    take(inherited(), |fd| claim_unix(fd, path))
+}
+
+fn claim_tcp(fd: OwnedFd, addr: SocketAddr) -> Result<std::net::TcpListener, OwnedFd> {
+    let listener = std::net::TcpListener::from(fd);
+    match listener.local_addr() {
+        Ok(local) if local == addr => Ok(listener),
+        Ok(local) if addr.port() == 0 && local.ip() == addr.ip() => Ok(listener),
+        _ => Err(listener.into()),
+    }
+}
+
+fn claim_unix(fd: OwnedFd, path: &Path) -> Result<std::os::unix::net::UnixListener, OwnedFd> {
+    let listener = std::os::unix::net::UnixListener::from(fd);
+    match listener.local_addr() {
+        Ok(local) if local.as_pathname() == Some(path) => Ok(listener),
+        _ => Err(listener.into()),
+    }
+}
+
+/// Registers a listening socket to be handed off on upgrade.
+pub fn register<T: AsRawFd>(listener: &T) {
+    BOUND.lock().expect("handoff lock").push(listener.as_raw_fd());
+}
+
+/// Unregisters a listening socket previously registered with [`register()`].
+pub fn unregister<T: AsRawFd>(listener: &T) {
+    let fd = listener.as_raw_fd();
+    BOUND.lock().expect("handoff lock").retain(|&bound| bound != fd);
+}
+
+/// Whether this process's sockets have been handed off to a new process.
+pub fn handed_off() -> bool {
+    HANDED_OFF.load(Ordering::Acquire)
+}
+
+/// Closes any inherited sockets that weren't claimed and, if this process was
+/// started by an upgrade, reports its readiness to the previous process.
+pub fn ready() {
+    static READY: Once = Once::new();
+    READY.call_once(|| {
+        if let Some(fds) = INHERITED.get() {
+            fds.lock().expect("handoff lock").clear();
+        }
+
+        let Some(fd) = std::env::var(READY_FD).ok().and_then(|fd| fd.parse().ok()) else {
+            return;
+        };
+
+        if is_socket(fd) {
+            // SAFETY: `fd` is one end of a socket pair created by the previous
+            // process for this process's exclusive use.
+            let stream = unsafe { UnixStream::from_raw_fd(fd) };
+            if let Err(e) = report_ready(stream) {
+                warn!("failed to report upgrade readiness: {e}");
+            }
+        }
+    });
+}
+
+/// Reports readiness to the process waiting in [`await_ready()`] on the other
+/// end of `stream`.
+fn report_ready(mut stream: UnixStream) -> io::Result<()> {
+    stream.write_all(&[1])
+}
+
+/// Waits at most `timeout` for the process on the other end of `stream` to
+/// report its readiness via [`report_ready()`]. Returns `Ok(true)` if it did
+/// and `Ok(false)` if it closed `stream` without doing so.
+fn await_ready(mut stream: UnixStream, timeout: Duration) -> io::Result<bool> {
+    stream.set_read_timeout(Some(timeout))?;
+    let mut byte = [0];
+    match stream.read(&mut byte)? {
+        0 => Ok(false),
+        _ => Ok(byte[0] == 1),
+    }
+}
+
+/// Executes the current binary anew, handing off all registered listening
+/// sockets, and waits at most `timeout` for the new process to become ready.
+/// Returns the ID of the new process.
+pub async fn upgrade(timeout: Duration) -> io::Result<u32> {
+    use std::os::unix::process::CommandExt;
+
+    let mut fds: Vec<RawFd> = BOUND.lock().expect("handoff lock").clone();
+    fds.retain(|&fd| is_socket(fd) && is_listening(fd));
+    if fds.is_empty() {
+        return Err(io::Error::other("no listening sockets to hand off"));
+    }
+
+    let (local, remote) = UnixStream::pair()?;
+    let list = fds.iter().map(|fd| fd.to_string()).collect::<Vec<_>>().join(",");
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    command.args(std::env::args_os().skip(1))
+        .env(LISTEN_FDS, list)
+        .env(READY_FD, remote.as_raw_fd().to_string());
+
+    fds.push(remote.as_raw_fd());
+    // SAFETY: `fcntl` is async-signal-safe, and nothing is allocated.
+    unsafe {
+        command.pre_exec(move || fds.iter().try_for_each(|&fd| set_cloexec(fd, false)));
+    }
+
+    let mut child = command.spawn()?;
+    drop(remote);
+
+    let ready = tokio::task::spawn_blocking(move || await_ready(local, timeout));
+    match ready.await.map_err(io::Error::other)? {
+        Ok(true) => {
+            HANDED_OFF.store(true, Ordering::Release);
+            Ok(child.id())
+        }
+        result => {
+            let _ = child.kill();
+            let _ = child.wait();
+            match result {
+                Ok(_) => Err(io::Error::other("new process exited before becoming ready")),
+                Err(e) => Err(io::Error::new(e.kind(), format!("new process not ready: {e}"))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::os::unix::net::UnixListener;
+
+    use super::*;
+
+    fn passed(vars: &[(&str, &str)], pid: u32) -> Vec<RawFd> {
+        let vars: HashMap<_, _> = vars.iter().cloned().collect();
+        passed_fds(|name| vars.get(name).map(|v| v.to_string()), pid)
+    }
+
+    #[test]
+    fn passed_fds_are_parsed() {
+        assert!(passed(&[], 10).is_empty());
+        assert_eq!(passed(&[(LISTEN_FDS, "5")], 10), [5]);
+        assert_eq!(passed(&[(LISTEN_FDS, "5, 7,x,")], 10), [5, 7]);
+        assert_eq!(passed(&[("LISTEN_PID", "10"), ("LISTEN_FDS", "2")], 10), [3, 4]);
+        assert!(passed(&[("LISTEN_PID", "11"), ("LISTEN_FDS", "2")], 10).is_empty());
+        assert!(passed(&[("LISTEN_FDS", "2")], 10).is_empty());
+
+        let both = [(LISTEN_FDS, "9"), ("LISTEN_PID", "10"), ("LISTEN_FDS", "2")];
+        assert_eq!(passed(&both, 10), [9]);
+    }
+
+    #[test]
+    fn inherited_listeners_are_taken_by_address() {
+        let localhost = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let tcp = TcpListener::bind(localhost).unwrap();
+        let tcp_addr = tcp.local_addr().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rocket.sock");
+        let unix = UnixListener::bind(&path).unwrap();
+
+        let fds = Mutex::new(vec![OwnedFd::from(unix), OwnedFd::from(tcp)]);
+        let other = SocketAddr::from((Ipv4Addr::new(127, 0, 0, 2), tcp_addr.port()));
+        assert!(take(&fds, |fd| claim_tcp(fd, other)).is_none());
+        assert!(take(&fds, |fd| claim_unix(fd, &dir.path().join("other.sock"))).is_none());
+        assert_eq!(fds.lock().unwrap().len(), 2);
+
+        let taken = take(&fds, |fd| claim_tcp(fd, tcp_addr)).unwrap();
+        assert_eq!(taken.local_addr().unwrap(), tcp_addr);
+        assert!(take(&fds, |fd| claim_tcp(fd, localhost)).is_none());
+
+        let taken = take(&fds, |fd| claim_unix(fd, &path)).unwrap();
+        assert_eq!(taken.local_addr().unwrap().as_pathname(), Some(&*path));
+        assert!(fds.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn any_port_matches_inherited_tcp_listener() {
+        let localhost = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let tcp = TcpListener::bind(localhost).unwrap();
+        let tcp_addr = tcp.local_addr().unwrap();
+
+        let fds = Mutex::new(vec![OwnedFd::from(tcp)]);
+        let taken = take(&fds, |fd| claim_tcp(fd, localhost)).unwrap();
+        assert_eq!(taken.local_addr().unwrap(), tcp_addr);
+    }
+
+    #[test]
+    fn readiness_is_reported_over_socket_pair() {
+        let timeout = Duration::from_secs(5);
+        let (local, remote) = UnixStream::pair().unwrap();
+        report_ready(remote).unwrap();
+        assert!(await_ready(local, timeout).unwrap());
+
+        let (local, remote) = UnixStream::pair().unwrap();
+        drop(remote);
+        assert!(!await_ready(local, timeout).unwrap());
+
+        let (local, _remote) = UnixStream::pair().unwrap();
+        assert!(await_ready(local, Duration::from_millis(50)).is_err());
+    }
+}
@@ -5,6 +5,10 @@ mod endpoint;
 mod connection;
 mod bind;
 mod default;
+mod bound;
+
+#[cfg(unix)]
+pub(crate) mod handoff;
 
 #[cfg(unix)]
 #[cfg_attr(nightly, doc(cfg(unix)))]
@@ -18,6 +22,8 @@ pub use listener::*;
 pub use connection::*;
 pub use bind::*;
 pub use default::*;
+pub use bound::BoundAddresses;
 
 pub(crate) use cancellable::*;
 pub(crate) use bounced::*;
+pub(crate) use bound::Binding;
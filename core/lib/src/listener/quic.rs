@@ -38,7 +38,7 @@ use tokio::sync::Mutex;
 use tokio_stream::StreamExt;
 
 use crate::tls::{TlsConfig, Error};
-use crate::listener::Endpoint;
+use crate::listener::{Endpoint, TlsInfo};
 
 type H3Conn = h3::server::Connection<quic_h3::Connection, bytes::Bytes>;
 
@@ -48,10 +48,14 @@ pub struct QuicListener {
     tls: TlsConfig,
 }
 
-pub struct H3Stream(H3Conn, quic::connection::Result<SocketAddr>);
+pub struct H3Stream {
+    conn: H3Conn,
+    remote: quic::connection::Result<SocketAddr>,
+    local: quic::connection::Result<SocketAddr>,
+    server_name: Option<String>,
+}
 
 pub struct H3Connection {
-    pub(crate) remote: quic::connection::Result<SocketAddr>,
     pub(crate) parts: http::request::Parts,
     pub(crate) tx: QuicTx,
     pub(crate) rx: QuicRx,
@@ -105,9 +109,11 @@ impl QuicListener {
 
     pub async fn connect(&self, accept: quic::Connection) -> io::Result<H3Stream> {
         let remote = accept.remote_addr();
+        let local = accept.local_addr();
+        let server_name = accept.server_name().ok().flatten().map(|s| s.to_string());
         let quic_conn = quic_h3::Connection::new(accept);
         let conn = H3Conn::new(quic_conn).await.map_err(io::Error::other)?;
-        Ok(H3Stream(conn, remote))
+        Ok(H3Stream { conn, remote, local, server_name })
     }
 
     pub fn endpoint(&self) -> io::Result<Endpoint> {
@@ -117,8 +123,7 @@ impl QuicListener {
 
 impl H3Stream {
     pub async fn accept(&mut self) -> io::Result<Option<H3Connection>> {
-        let remote = self.1.clone();
-        let ((parts, _), (tx, rx)) = match self.0.accept().await {
+        let ((parts, _), (tx, rx)) = match self.conn.accept().await {
             Ok(Some((req, stream))) => (req.into_parts(), stream.split()),
             Ok(None) => return Ok(None),
             Err(e) => {
@@ -130,12 +135,17 @@ impl H3Stream {
             }
         };
 
-        Ok(Some(H3Connection { remote, parts, tx: QuicTx(tx), rx: QuicRx(rx) }))
+        let (tx, rx) = (QuicTx(tx), QuicRx(rx));
+        Ok(Some(H3Connection { parts, tx, rx }))
     }
 }
 
 impl QuicTx {
-    pub async fn send_response<S>(&mut self, response: http::Response<S>) -> io::Result<()>
+    pub async fn send_response<S>(
+        &mut self,
+        response: http::Response<S>,
+        trailers: Option<http::HeaderMap>,
+    ) -> io::Result<()>
         where S: Stream<Item = io::Result<Bytes>>
     {
         let (parts, body) = response.into_parts();
@@ -148,6 +158,10 @@ impl QuicTx {
             self.0.send_data(bytes).await.map_err(io::Error::other)?;
         }
 
+        if let Some(trailers) = trailers {
+            self.0.send_trailers(trailers).await.map_err(io::Error::other)?;
+        }
+
         self.0.finish().await.map_err(io::Error::other)
     }
 
@@ -157,10 +171,24 @@ impl QuicTx {
 }
 
 // FIXME: Expose certificates when possible.
-impl H3Connection {
+impl H3Stream {
     pub fn endpoint(&self) -> io::Result<Endpoint> {
         Ok(Endpoint::Quic(self.remote?).assume_tls())
     }
+
+    pub fn local_endpoint(&self) -> io::Result<Endpoint> {
+        Ok(Endpoint::Quic(self.local?).assume_tls())
+    }
+
+    /// QUIC always negotiates TLS 1.3 and HTTP/3.
+    pub fn tls_info(&self) -> TlsInfo {
+        TlsInfo {
+            alpn: Some("h3".into()),
+            version: Some("TLSv1_3".into()),
+            cipher_suite: None,
+            server_name: self.server_name.clone(),
+        }
+    }
 }
 
 mod async_traits {
@@ -29,6 +29,22 @@ impl Bind for TcpListener {
             .ok_or_else(|| io::Error::other("internal error: invalid endpoint"))
             .map_err(Right)?;
 
+        #[cfg(unix)] {
+            use crate::listener::handoff;
+
+            let listener = match handoff::take_tcp(addr) {
+                Some(listener) => {
+                    listener.set_nonblocking(true).map_err(Right)?;
+                    TcpListener::from_std(listener).map_err(Right)?
+                }
+                None => Self::bind(addr).await.map_err(Right)?,
+            };
+
+            handoff::register(&listener);
+            Ok(listener)
+        }
+
+        #[cfg(not(unix))]
         Self::bind(addr).await.map_err(Right)
     }
 
@@ -73,4 +89,8 @@ impl Connection for TcpStream {
     fn endpoint(&self) -> io::Result<Endpoint> {
         self.peer_addr().map(Endpoint::Tcp)
     }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        self.local_addr().map(Endpoint::Tcp)
+    }
 }
@@ -5,7 +5,7 @@ use either::{Either, Left, Right};
 use tokio::time::{sleep, Duration};
 
 use crate::fs::NamedFile;
-use crate::listener::{Listener, Bind, Connection, Endpoint};
+use crate::listener::{Listener, Bind, Connection, Endpoint, handoff};
 use crate::util::unix;
 use crate::{Ignite, Rocket};
 
@@ -69,6 +69,14 @@ impl UnixListener {
 
         Ok(UnixListener { lock, listener, path: path.into() })
     }
+
+    /// Listens on a socket inherited from a previous process. The previous
+    /// process may still hold the lock, so none is taken.
+    fn inherit(path: &Path, listener: std::os::unix::net::UnixListener) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::UnixListener::from_std(listener)?;
+        Ok(UnixListener { lock: None, listener, path: path.into() })
+    }
 }
 
 impl Bind for UnixListener {
@@ -80,7 +88,13 @@ impl Bind for UnixListener {
             .ok_or_else(|| Right(io::Error::other("internal error: invalid endpoint")))?;
 
         let reuse: Option<bool> = rocket.figment().extract_inner("reuse").map_err(Left)?;
-        Ok(Self::bind(path, reuse.unwrap_or(true)).await.map_err(Right)?)
+        let listener = match handoff::take_unix(path) {
+            Some(listener) => Self::inherit(path, listener).map_err(Right)?,
+            None => Self::bind(path, reuse.unwrap_or(true)).await.map_err(Right)?,
+        };
+
+        handoff::register(&listener.listener);
+        Ok(listener)
     }
 
     fn bind_endpoint(rocket: &Rocket<Ignite>) -> Result<Endpoint, Self::Error> {
@@ -113,16 +127,24 @@ impl Connection for UnixStream {
     fn endpoint(&self) -> io::Result<Endpoint> {
         self.local_addr()?.try_into()
     }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        self.local_addr()?.try_into()
+    }
 }
 
 impl Drop for UnixListener {
     fn drop(&mut self) {
-        if let Some(lock) = &self.lock {
+        handoff::unregister(&self.listener);
+
+        // Once handed off, the socket lives on in the new process.
+        if !handoff::handed_off() {
             let _ = std::fs::remove_file(&self.path);
+        }
+
+        if let Some(lock) = &self.lock {
             let _ = std::fs::remove_file(lock.path());
             let _ = unix::unlock_nonblocking(lock.file());
-        } else {
-            let _ = std::fs::remove_file(&self.path);
         }
     }
 }
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Connection limit and load shedding configuration.
+///
+/// See the [module level docs](crate::load) for a description of how each
+/// value affects the server. All limits are disabled by default.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, LoadConfig};
+///
+/// let config = Config {
+///     load: LoadConfig {
+///         max_requests: 256,
+///         queue: 512,
+///         ..Default::default()
+///     },
+///     ..Config::default()
+/// };
+///
+/// assert_eq!(config.load.max_connections, 0);
+/// assert_eq!(config.load.max_requests, 256);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadConfig {
+    /// Maximum number of concurrently open connections; unlimited when `0`.
+    ///
+    /// **default: `0`**
+    pub max_connections: usize,
+    /// Maximum number of concurrently dispatched requests; unlimited when
+    /// `0`.
+    ///
+    /// **default: `0`**
+    pub max_requests: usize,
+    /// Maximum number of requests waiting for dispatch when `max_requests`
+    /// are in flight. Requests beyond the queue are shed immediately.
+    ///
+    /// **default: `0`**
+    pub queue: usize,
+    /// Number of seconds a queued request waits to be dispatched before it is
+    /// shed; queued requests wait indefinitely when `0`.
+    ///
+    /// **default: `5`**
+    pub queue_timeout: u32,
+    /// The value, in seconds, of the `Retry-After` header sent with the
+    /// response to a shed request; omitted when `0`.
+    ///
+    /// **default: `1`**
+    pub retry_after: u32,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constructor or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::config::LoadConfig;
+    ///
+    /// let config = LoadConfig {
+    ///     max_connections: 1024,
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        LoadConfig {
+            max_connections: 0,
+            max_requests: 0,
+            queue: 0,
+            queue_timeout: 5,
+            retry_after: 1,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl LoadConfig {
+    pub(crate) fn queue_timeout(&self) -> Option<Duration> {
+        (self.queue_timeout != 0).then(|| Duration::from_secs(self.queue_timeout as u64))
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+
+use crate::load::LoadConfig;
+
+/// A snapshot of the server's load.
+///
+/// Returned by [`Rocket::load()`](crate::Rocket::load()). See the [module
+/// level docs](crate::load) for details.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadStats {
+    /// The number of open connections.
+    pub connections: usize,
+    /// The number of requests being dispatched.
+    pub requests: usize,
+    /// The number of requests waiting to be dispatched.
+    pub queued: usize,
+    /// The total number of requests shed since launch.
+    pub shed: u64,
+    /// The total number of request body bytes read by the application since
+    /// launch. See [`Request::body_bytes_read()`](crate::Request::body_bytes_read()).
+    pub bytes_read: u64,
+    /// The total number of response body bytes written to clients since
+    /// launch, as sent, that is, after any compression.
+    pub bytes_written: u64,
+}
+
+/// Enforces the limits in a [`LoadConfig`] and tracks [`LoadStats`].
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    config: LoadConfig,
+    connections: Arc<Semaphore>,
+    max_connections: usize,
+    requests: Semaphore,
+    max_requests: usize,
+    queued: AtomicUsize,
+    shed: AtomicU64,
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+/// Decrements the queue length when dropped, even if the waiting request is
+/// cancelled.
+struct Queued<'a>(&'a AtomicUsize);
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl Limiter {
+    pub fn new(config: &LoadConfig) -> Self {
+        let limit = |max: usize| match max {
+            0 => Semaphore::MAX_PERMITS,
+            n => n.min(Semaphore::MAX_PERMITS),
+        };
+
+        let max_connections = limit(config.max_connections);
+        let max_requests = limit(config.max_requests);
+        Limiter {
+            config: config.clone(),
+            connections: Arc::new(Semaphore::new(max_connections)),
+            max_connections,
+            requests: Semaphore::new(max_requests),
+            max_requests,
+            queued: AtomicUsize::new(0),
+            shed: AtomicU64::new(0),
+            bytes_read: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+        }
+    }
+
+    /// Waits until a new connection may be accepted. The returned permit must
+    /// be held for as long as the connection is open.
+    pub async fn connection(&self) -> OwnedSemaphorePermit {
+        if let Ok(permit) = self.connections.clone().try_acquire_owned() {
+            return permit;
+        }
+
+        debug!(max = self.config.max_connections, "connection limit reached: pausing accept");
+        self.connections.clone()
+            .acquire_owned().await
+            .expect("connection semaphore is never closed")
+    }
+
+    /// Admits a request for dispatch, queueing it if the request limit has
+    /// been reached and the queue has room. Returns `None` if the request is
+    /// shed. The returned permit must be held until the request is handled.
+    pub async fn request(&self) -> Option<SemaphorePermit<'_>> {
+        if let Ok(permit) = self.requests.try_acquire() {
+            return Some(permit);
+        }
+
+        let position = self.queued.fetch_add(1, Ordering::AcqRel);
+        let queued = Queued(&self.queued);
+        if position < self.config.queue {
+            let acquire = self.requests.acquire();
+            let permit = match self.config.queue_timeout() {
+                Some(timeout) => tokio::time::timeout(timeout, acquire).await.ok(),
+                None => Some(acquire.await),
+            };
+
+            if let Some(Ok(permit)) = permit {
+                return Some(permit);
+            }
+        }
+
+        drop(queued);
+        self.shed.fetch_add(1, Ordering::Relaxed);
+        let stats = self.stats();
+        warn!(
+            in_flight = stats.requests,
+            queued = stats.queued,
+            shed = stats.shed,
+            "request limit reached: shedding request"
+        );
+
+        None
+    }
+
+    /// Records the body bytes read and written by a completed request.
+    pub fn record_bytes(&self, read: u64, written: u64) {
+        self.bytes_read.fetch_add(read, Ordering::Relaxed);
+        self.bytes_written.fetch_add(written, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> LoadStats {
+        LoadStats {
+            connections: self.max_connections - self.connections.available_permits(),
+            requests: self.max_requests - self.requests.available_permits(),
+            queued: self.queued.load(Ordering::Acquire),
+            shed: self.shed.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+        }
+    }
+}
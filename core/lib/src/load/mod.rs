@@ -0,0 +1,47 @@
+//! Connection limits and load shedding.
+//!
+//! By default, Rocket accepts every connection and dispatches every request
+//! it receives, no matter how many are already in progress. Under sustained
+//! overload, this causes latency and memory use to grow without bound. The
+//! [`LoadConfig`] configuration parameters, `load.*`, bound both:
+//!
+//!   * `max_connections` limits the number of concurrently open connections.
+//!     Once the limit is reached, Rocket stops accepting new connections until
+//!     an open connection closes. Pending connections wait in the operating
+//!     system's listen backlog.
+//!
+//!   * `max_requests` limits the number of requests dispatched concurrently.
+//!     Once the limit is reached, new requests are _queued_ if fewer than
+//!     `queue` requests are already waiting and otherwise _shed_. A queued
+//!     request waits at most `queue_timeout` seconds to be dispatched before
+//!     it, too, is shed.
+//!
+//! A shed request is answered by the `503 Service Unavailable` catcher with a
+//! `Retry-After` header of `retry_after` seconds. Response fairings run as
+//! usual. With a `queue` of `0`, the default, requests over the limit are
+//! rejected immediately.
+//!
+//! ```toml
+//! [default.load]
+//! max_connections = 4096
+//! max_requests = 512
+//! queue = 1024
+//! queue_timeout = 5
+//! retry_after = 1
+//! ```
+//!
+//! # Metrics
+//!
+//! [`Rocket::load()`](crate::Rocket::load()) returns a [`LoadStats`] snapshot
+//! of open connections, in-flight and queued requests, the number of requests
+//! shed so far, and the total number of body bytes read and written, suitable
+//! for exporting to a metrics system. Each shed request is additionally logged
+//! at the `warn` level.
+
+mod config;
+mod limiter;
+
+pub(crate) use limiter::Limiter;
+
+pub use config::LoadConfig;
+pub use limiter::LoadStats;
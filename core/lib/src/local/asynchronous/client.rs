@@ -3,7 +3,8 @@ use std::fmt;
 use parking_lot::RwLock;
 
 use crate::{Rocket, Phase, Orbit, Ignite, Error};
-use crate::local::asynchronous::{LocalRequest, LocalResponse};
+use crate::local::TypedRoute;
+use crate::local::asynchronous::{LocalRequest, LocalResponse, TypedLocalRequest};
 use crate::http::{Method, uri::Origin};
 use crate::listener::Endpoint;
 
@@ -120,6 +121,9 @@ impl Client {
     pub_client_impl!("use rocket::local::asynchronous::Client;" @async await);
 }
 
+// Generates `ClientBuilder`, which uses the private methods above.
+pub_client_builder!("use rocket::local::asynchronous::Client;" @async await);
+
 impl std::fmt::Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self._rocket().fmt(f)
@@ -1,10 +1,13 @@
 use std::fmt;
+use std::marker::PhantomData;
 
 use crate::{Request, Data};
-use crate::http::{Status, Method};
+use crate::http::{Status, Method, Header};
 use crate::http::uri::Origin;
 
-use super::{Client, LocalResponse};
+use crate::local::{TypedRoute, TypedBody};
+
+use super::{Client, LocalResponse, LocalUpgraded};
 
 /// An `async` local request as returned by [`Client`](super::Client).
 ///
@@ -94,8 +97,12 @@ impl<'c> LocalRequest<'c> {
         // Actually dispatch the request.
         let mut data = Data::local(self.data);
         let token = rocket.preprocess(&mut self.request, &mut data).await;
-        let response = LocalResponse::new(self.request, move |req| {
-            rocket.dispatch(token, req, data)
+        let response = LocalResponse::new(self.request, move |req| async move {
+            if let Some(error) = req.errors.first() {
+                return rocket.dispatch_error(error.status(), req).await;
+            }
+
+            rocket.dispatch(token, req, data).await
         }).await;
 
         // If the client is tracking cookies, updates the internal cookie jar
@@ -119,6 +126,15 @@ impl<'c> LocalRequest<'c> {
         response
     }
 
+    async fn _dispatch_upgrade(
+        mut self,
+        protocol: &str
+    ) -> Result<LocalUpgraded<'c>, LocalResponse<'c>> {
+        self.add_header(Header::new("Connection", "Upgrade"));
+        self.add_header(Header::new("Upgrade", protocol.to_string()));
+        self._dispatch().await._upgrade()
+    }
+
     pub_request_impl!("# use rocket::local::asynchronous::Client;\n\
         use rocket::local::asynchronous::LocalRequest;" async await);
 }
@@ -153,3 +169,47 @@ impl<'c> std::ops::DerefMut for LocalRequest<'c> {
         self.inner_mut()
     }
 }
+
+/// An `async` local request to a statically known route, as returned by
+/// [`Client::call()`](super::Client::call()).
+///
+/// A `TypedLocalRequest` dereferences to a [`LocalRequest`] and can be
+/// converted into one by setting its body via [`body()`], whose type is checked
+/// against the route's data guard, or via [`into_inner()`].
+///
+/// [`body()`]: TypedLocalRequest::body()
+/// [`into_inner()`]: TypedLocalRequest::into_inner()
+pub struct TypedLocalRequest<'c, R> {
+    request: LocalRequest<'c>,
+    _route: PhantomData<fn() -> R>,
+}
+
+impl<'c, R: TypedRoute> TypedLocalRequest<'c, R> {
+    pub_typed_request_impl!("# use rocket::local::asynchronous::Client;" async await);
+}
+
+impl<R> Clone for TypedLocalRequest<'_, R> {
+    fn clone(&self) -> Self {
+        TypedLocalRequest { request: self.request.clone(), _route: PhantomData }
+    }
+}
+
+impl<R> std::fmt::Debug for TypedLocalRequest<'_, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.request.fmt(f)
+    }
+}
+
+impl<'c, R> std::ops::Deref for TypedLocalRequest<'c, R> {
+    type Target = LocalRequest<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.request
+    }
+}
+
+impl<'c, R> std::ops::DerefMut for TypedLocalRequest<'c, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.request
+    }
+}
@@ -1,11 +1,15 @@
 use std::io;
+use std::sync::atomic::Ordering;
 use std::future::Future;
 use std::{pin::Pin, task::{Context, Poll}};
 
-use tokio::io::{AsyncRead, ReadBuf};
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
 
 use crate::http::CookieJar;
-use crate::{Request, Response};
+use crate::data::{IoHandler, IoStream};
+use crate::response::Completion;
+use crate::response::stream::Event;
+use crate::{Rocket, Request, Response};
 
 /// An `async` response from a dispatched [`LocalRequest`](super::LocalRequest).
 ///
@@ -53,14 +57,23 @@ use crate::{Request, Response};
 ///
 /// For more, see [the top-level documentation](../index.html#localresponse).
 pub struct LocalResponse<'c> {
-    // XXX: SAFETY: This (dependent) field must come first due to drop order!
+    // XXX: SAFETY: These (dependent) fields must come first due to drop order!
+    io_handler: Option<(String, Box<dyn IoHandler + 'c>)>,
     response: Response<'c>,
     cookies: CookieJar<'c>,
+    completion: Option<Completion>,
     _request: Box<Request<'c>>,
 }
 
 impl Drop for LocalResponse<'_> {
-    fn drop(&mut self) { }
+    fn drop(&mut self) {
+        let completion = self.completion
+            .or_else(|| Completion::of_bodiless(&self._request, &self.response))
+            .unwrap_or(Completion::Aborted);
+
+        let shared = &self._request.state.shared;
+        shared.completion.run(completion, shared.bytes_written.load(Ordering::Relaxed));
+    }
 }
 
 impl<'c> LocalResponse<'c> {
@@ -87,8 +100,10 @@ impl<'c> LocalResponse<'c> {
         //      reference to contents of `Request`. All methods, instead, return
         //      references bounded by `self`. This is easily verified by noting
         //      that 1) `LocalResponse` fields are private, and 2) all `impl`s
-        //      of `LocalResponse` aside from this method abstract the lifetime
-        //      away as `'_`, ensuring it is not used for any output value.
+        //      of `LocalResponse` aside from this method and `_upgrade()`,
+        //      which moves `self` wholesale into a `LocalUpgraded` with the
+        //      same guarantees, abstract the lifetime away as `'_`, ensuring it
+        //      is not used for any output value.
         let boxed_req = Box::new(req);
         let request: &'c Request<'c> = unsafe { &*(&*boxed_req as *const _) };
 
@@ -97,15 +112,27 @@ impl<'c> LocalResponse<'c> {
             // known value in `request.cookies()`. This is okay: new cookies
             // should never be added to the resulting jar which is the only time
             // the value is used to set cookie defaults.
-            let response: Response<'c> = f(request).await;
+            let mut response: Response<'c> = f(request).await;
             let mut cookies = CookieJar::new(None, request.rocket());
             for cookie in response.cookies() {
                 cookies.add_original(cookie.into_owned());
             }
 
-            LocalResponse { _request: boxed_req, cookies, response, }
+            // As with the server, complete the upgrade, if one was requested.
+            let io_handler = Rocket::extract_io_handler(request, &mut response);
+            LocalResponse { _request: boxed_req, cookies, response, io_handler, completion: None }
         }
     }
+
+    pub(crate) fn _upgrade(mut self) -> Result<LocalUpgraded<'c>, Self> {
+        let Some((protocol, handler)) = self.io_handler.take() else {
+            return Err(self);
+        };
+
+        let (local, remote) = tokio::io::duplex(UPGRADE_BUFFER_SIZE);
+        let io = Some(handler.io(IoStream::from(remote)));
+        Ok(LocalUpgraded { io, stream: local, protocol, response: self })
+    }
 }
 
 impl LocalResponse<'_> {
@@ -118,11 +145,72 @@ impl LocalResponse<'_> {
     }
 
     pub(crate) async fn _into_string(mut self) -> io::Result<String> {
-        self.response.body_mut().to_string().await
+        let result = self.response.body_mut().to_string().await;
+        self.completion = Some(Completion::of_result(&result));
+        result
     }
 
     pub(crate) async fn _into_bytes(mut self) -> io::Result<Vec<u8>> {
-        self.response.body_mut().to_bytes().await
+        let result = self.response.body_mut().to_bytes().await;
+        self.completion = Some(Completion::of_result(&result));
+        result
+    }
+
+    pub(crate) async fn _next_event(&mut self) -> Option<Event> {
+        use tokio::io::AsyncReadExt;
+
+        let (mut lines, mut line) = (vec![], vec![]);
+        loop {
+            match self.read_u8().await.ok()? {
+                b'\n' => {
+                    let string = String::from_utf8(std::mem::take(&mut line)).ok()?;
+                    match string.strip_suffix('\r').unwrap_or(&string) {
+                        "" if lines.is_empty() => continue,
+                        "" => return Event::parse(lines.iter().map(String::as_str)),
+                        rest => lines.push(rest.to_string()),
+                    }
+                }
+                byte => line.push(byte),
+            }
+        }
+    }
+
+    /// Returns a stream of the Server-Sent [`Event`]s in the response body,
+    /// parsed as a client would. The stream ends when the body does.
+    ///
+    /// Events are read directly from the body; they are not buffered. As with
+    /// [`next_event()`](Self::next_event()), comments, including those sent as
+    /// heartbeats by [`EventStream`](crate::response::stream::EventStream), are
+    /// yielded as events.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::response::stream::{Event, EventStream};
+    /// use rocket::futures::StreamExt;
+    ///
+    /// #[get("/")]
+    /// fn events() -> EventStream![] {
+    ///     EventStream! {
+    ///         yield Event::data("a");
+    ///         yield Event::data("b").event("letter");
+    ///     }
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::debug_with(routes![events]).await.unwrap();
+    /// let mut response = client.get(uri!(events)).dispatch().await;
+    /// let events: Vec<_> = response.events().collect().await;
+    /// assert_eq!(events, [Event::data("a"), Event::data("b").event("letter")]);
+    /// # });
+    /// ```
+    pub fn events(&mut self) -> impl futures::Stream<Item = Event> + '_ {
+        futures::stream::unfold(self, |response| async move {
+            let event = response.next_event().await?;
+            Some((event, response))
+        })
     }
 
     #[cfg(feature = "json")]
@@ -206,7 +294,15 @@ impl AsyncRead for LocalResponse<'_> {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        Pin::new(self.response.body_mut()).poll_read(cx, buf)
+        let filled = buf.filled().len();
+        let result = Pin::new(self.response.body_mut()).poll_read(cx, buf);
+        let written = (buf.filled().len() - filled) as u64;
+        self._request.state.shared.bytes_written.fetch_add(written, Ordering::Relaxed);
+        if self.completion.is_none() {
+            self.completion = Completion::of_read(&result, filled, buf);
+        }
+
+        result
     }
 }
 
@@ -215,3 +311,145 @@ impl std::fmt::Debug for LocalResponse<'_> {
         self._response().fmt(f)
     }
 }
+
+/// The size of the in-memory buffer between a [`LocalUpgraded`] and its I/O
+/// handler, in each direction.
+const UPGRADE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// An `async` upgraded connection to an I/O handler, as returned by
+/// [`LocalRequest::dispatch_upgrade()`](super::LocalRequest::dispatch_upgrade()).
+///
+/// A `LocalUpgraded` is the client's end of a connection upgraded by an
+/// [`IoHandler`] registered in a response. It implements [`AsyncRead`] and
+/// [`AsyncWrite`]: bytes written are read by the handler, and bytes written by
+/// the handler are read from it. As such, a `LocalUpgraded` can be used with
+/// any protocol client that operates on an I/O stream. For instance, a
+/// WebSocket client can wrap it to test WebSocket handlers.
+///
+/// The handler itself runs _only_ while the `LocalUpgraded` is being read from,
+/// written to, flushed, or shut down: it is not spawned onto the runtime. A
+/// handler that terminates closes its end of the connection; subsequent reads
+/// return EOF.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{Request, Response};
+/// use rocket::data::{IoHandler, IoStream};
+/// use rocket::local::asynchronous::Client;
+/// use rocket::response::{self, Responder};
+/// use rocket::tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+///
+/// struct Echo;
+///
+/// #[rocket::async_trait]
+/// impl IoHandler for Echo {
+///     async fn io(self: Box<Self>, io: IoStream) -> io::Result<()> {
+///         let (mut reader, mut writer) = io::split(io);
+///         io::copy(&mut reader, &mut writer).await?;
+///         Ok(())
+///     }
+/// }
+///
+/// impl<'r> Responder<'r, 'r> for Echo {
+///     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
+///         Response::build().upgrade("echo", self).ok()
+///     }
+/// }
+///
+/// #[get("/echo")]
+/// fn echo() -> Echo { Echo }
+///
+/// # rocket::async_test(async {
+/// let client = Client::debug_with(routes![echo]).await.unwrap();
+/// let mut upgraded = client.get(uri!(echo)).dispatch_upgrade("echo").await.unwrap();
+/// assert_eq!(upgraded.protocol(), "echo");
+///
+/// upgraded.write_all(b"hello").await.unwrap();
+/// let mut buf = [0; 5];
+/// upgraded.read_exact(&mut buf).await.unwrap();
+/// assert_eq!(&buf, b"hello");
+/// # });
+/// ```
+pub struct LocalUpgraded<'c> {
+    // XXX: SAFETY: This (dependent) field must come first due to drop order!
+    io: Option<Pin<Box<dyn Future<Output = io::Result<()>> + Send + 'c>>>,
+    stream: DuplexStream,
+    protocol: String,
+    response: LocalResponse<'c>,
+}
+
+impl<'c> LocalUpgraded<'c> {
+    /// The protocol the connection was upgraded to.
+    pub fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    /// The `101 Switching Protocols` response that completed the upgrade.
+    pub fn response(&self) -> &LocalResponse<'c> {
+        &self.response
+    }
+
+    /// Returns `true` if the I/O handler has run to completion.
+    pub fn is_finished(&self) -> bool {
+        self.io.is_none()
+    }
+
+    /// Drives the I/O handler forward, if it's still running.
+    fn poll_io(&mut self, cx: &mut Context<'_>) {
+        if let Some(io) = self.io.as_mut() {
+            if let Poll::Ready(result) = io.as_mut().poll(cx) {
+                self.io = None;
+                if let Err(e) = result {
+                    warn!(error = %e, "local i/o handler terminated unsuccessfully");
+                }
+            }
+        }
+    }
+}
+
+impl AsyncRead for LocalUpgraded<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_io(cx);
+        Pin::new(&mut this.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for LocalUpgraded<'_> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.poll_io(cx);
+        Pin::new(&mut this.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_io(cx);
+        Pin::new(&mut this.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.poll_io(cx);
+        Pin::new(&mut this.stream).poll_shutdown(cx)
+    }
+}
+
+impl std::fmt::Debug for LocalUpgraded<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalUpgraded")
+            .field("protocol", &self.protocol)
+            .field("response", &self.response)
+            .finish_non_exhaustive()
+    }
+}
@@ -2,7 +2,8 @@ use std::fmt;
 use std::cell::RefCell;
 
 use crate::{Rocket, Phase, Orbit, Ignite, Error};
-use crate::local::{asynchronous, blocking::{LocalRequest, LocalResponse}};
+use crate::local::{asynchronous, TypedRoute};
+use crate::local::blocking::{LocalRequest, LocalResponse, TypedLocalRequest};
 use crate::http::{Method, uri::Origin};
 
 /// A `blocking` client to construct and dispatch local requests.
@@ -98,6 +99,9 @@ impl Client {
     pub_client_impl!("use rocket::local::blocking::Client;");
 }
 
+// Generates `ClientBuilder`, which uses the private methods above.
+pub_client_builder!("use rocket::local::blocking::Client;");
+
 impl std::fmt::Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self._rocket().fmt(f)
@@ -1,9 +1,11 @@
 use std::fmt;
+use std::marker::PhantomData;
 
 use crate::{Request, http::Method, local::asynchronous};
 use crate::http::uri::Origin;
+use crate::local::{TypedRoute, TypedBody};
 
-use super::{Client, LocalResponse};
+use super::{Client, LocalResponse, LocalUpgraded};
 
 /// A `blocking` local request as returned by [`Client`](super::Client).
 ///
@@ -61,6 +63,14 @@ impl<'c> LocalRequest<'c> {
         LocalResponse { inner, client: self.client }
     }
 
+    fn _dispatch_upgrade(self, protocol: &str) -> Result<LocalUpgraded<'c>, LocalResponse<'c>> {
+        let client = self.client;
+        match client.block_on(self.inner.dispatch_upgrade(protocol)) {
+            Ok(inner) => Ok(LocalUpgraded { inner, client }),
+            Err(inner) => Err(LocalResponse { inner, client }),
+        }
+    }
+
     pub_request_impl!("# use rocket::local::blocking::Client;\n\
         use rocket::local::blocking::LocalRequest;");
 }
@@ -84,3 +94,47 @@ impl<'c> std::ops::DerefMut for LocalRequest<'c> {
         self.inner_mut()
     }
 }
+
+/// A `blocking` local request to a statically known route, as returned by
+/// [`Client::call()`](super::Client::call()).
+///
+/// A `TypedLocalRequest` dereferences to a [`LocalRequest`] and can be
+/// converted into one by setting its body via [`body()`], whose type is checked
+/// against the route's data guard, or via [`into_inner()`].
+///
+/// [`body()`]: TypedLocalRequest::body()
+/// [`into_inner()`]: TypedLocalRequest::into_inner()
+pub struct TypedLocalRequest<'c, R> {
+    request: LocalRequest<'c>,
+    _route: PhantomData<fn() -> R>,
+}
+
+impl<'c, R: TypedRoute> TypedLocalRequest<'c, R> {
+    pub_typed_request_impl!("# use rocket::local::blocking::Client;");
+}
+
+impl<R> Clone for TypedLocalRequest<'_, R> {
+    fn clone(&self) -> Self {
+        TypedLocalRequest { request: self.request.clone(), _route: PhantomData }
+    }
+}
+
+impl<R> std::fmt::Debug for TypedLocalRequest<'_, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.request.fmt(f)
+    }
+}
+
+impl<'c, R> std::ops::Deref for TypedLocalRequest<'c, R> {
+    type Target = LocalRequest<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.request
+    }
+}
+
+impl<'c, R> std::ops::DerefMut for TypedLocalRequest<'c, R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.request
+    }
+}
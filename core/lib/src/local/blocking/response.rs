@@ -1,7 +1,8 @@
 use std::io;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::{Response, local::asynchronous, http::CookieJar};
+use crate::response::stream::Event;
 
 use super::Client;
 
@@ -71,6 +72,42 @@ impl LocalResponse<'_> {
         self.client.block_on(self.inner._into_bytes())
     }
 
+    fn _next_event(&mut self) -> Option<Event> {
+        self.client.block_on(self.inner._next_event())
+    }
+
+    /// Returns an iterator over the Server-Sent [`Event`]s in the response
+    /// body, parsed as a client would. The iterator ends when the body does.
+    ///
+    /// Events are read directly from the body; they are not buffered. As with
+    /// [`next_event()`](Self::next_event()), comments, including those sent as
+    /// heartbeats by [`EventStream`](crate::response::stream::EventStream), are
+    /// yielded as events.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::blocking::Client;
+    /// use rocket::response::stream::{Event, EventStream};
+    ///
+    /// #[get("/")]
+    /// fn events() -> EventStream![] {
+    ///     EventStream! {
+    ///         yield Event::data("a");
+    ///         yield Event::data("b").event("letter");
+    ///     }
+    /// }
+    ///
+    /// let client = Client::debug_with(routes![events]).unwrap();
+    /// let mut response = client.get(uri!(events)).dispatch();
+    /// let events: Vec<_> = response.events().collect();
+    /// assert_eq!(events, [Event::data("a"), Event::data("b").event("letter")]);
+    /// ```
+    pub fn events(&mut self) -> impl Iterator<Item = Event> + '_ {
+        std::iter::from_fn(move || self.next_event())
+    }
+
     #[cfg(feature = "json")]
     fn _into_json<T: Send + 'static>(self) -> Option<T>
         where T: serde::de::DeserializeOwned
@@ -101,3 +138,67 @@ impl std::fmt::Debug for LocalResponse<'_> {
         self._response().fmt(f)
     }
 }
+
+/// A `blocking` upgraded connection to an I/O handler, as returned by
+/// [`LocalRequest::dispatch_upgrade()`](super::LocalRequest::dispatch_upgrade()).
+///
+/// A `LocalUpgraded` is the client's end of a connection upgraded by an
+/// [`IoHandler`](crate::data::IoHandler) registered in a response. It
+/// implements [`io::Read`] and [`io::Write`]: bytes written are read by the
+/// handler, and bytes written by the handler are read from it. As such, a
+/// `LocalUpgraded` can be used with any blocking protocol client that operates
+/// on an I/O stream, such as a WebSocket client.
+///
+/// The handler itself runs _only_ while the `LocalUpgraded` is being read from,
+/// written to, or flushed. See
+/// [`asynchronous::LocalUpgraded`](crate::local::asynchronous::LocalUpgraded)
+/// for further details and an example.
+pub struct LocalUpgraded<'c> {
+    pub(in super) inner: asynchronous::LocalUpgraded<'c>,
+    pub(in super) client: &'c Client,
+}
+
+impl<'c> LocalUpgraded<'c> {
+    /// The protocol the connection was upgraded to.
+    pub fn protocol(&self) -> &str {
+        self.inner.protocol()
+    }
+
+    /// Returns the HTTP status of the `101 Switching Protocols` response that
+    /// completed the upgrade.
+    pub fn status(&self) -> crate::http::Status {
+        self.inner.response().status()
+    }
+
+    /// Returns the HTTP headers of the response that completed the upgrade.
+    pub fn headers(&self) -> &crate::http::HeaderMap<'_> {
+        self.inner.response().headers()
+    }
+
+    /// Returns `true` if the I/O handler has run to completion.
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+impl io::Read for LocalUpgraded<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.client.block_on(self.inner.read(buf))
+    }
+}
+
+impl io::Write for LocalUpgraded<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.client.block_on(self.inner.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.client.block_on(self.inner.flush())
+    }
+}
+
+impl std::fmt::Debug for LocalUpgraded<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -127,6 +127,27 @@ macro_rules! pub_client_impl {
         Self::_terminate(self) $(.$suffix)?
     }
 
+    /// Returns a builder for a `Client` for `rocket` that can override
+    /// managed state and disable fairings. See [`ClientBuilder`] for details.
+    ///
+    /// ```rust,no_run
+    #[doc = $import]
+    ///
+    /// let rocket = rocket::build();
+    /// let client = Client::builder(rocket)
+    ///     .without_fairing("Shield")
+    ///     .build();
+    /// ```
+    #[inline(always)]
+    pub fn builder(rocket: Rocket<crate::Build>) -> ClientBuilder {
+        ClientBuilder {
+            rocket,
+            overrides: crate::local::client::Overrides::default(),
+            tracked: true,
+            secure: false,
+        }
+    }
+
     #[doc(hidden)]
     pub $($prefix)? fn debug_with(routes: Vec<crate::Route>) -> Result<Self, Error> {
         let rocket = crate::custom(crate::Config::debug_default());
@@ -222,6 +243,41 @@ macro_rules! pub_client_impl {
         self._req(method, uri)
     }
 
+    /// Create a local request to the route `R` at the URI `uri`.
+    ///
+    /// The method of the request is the method of the route `R`, or `GET` if
+    /// the route matches any method. The returned request is typed: its
+    /// [`body()`](TypedLocalRequest::body()) can only be set to a value whose
+    /// type matches the route's data guard, and it can only be dispatched
+    /// directly if the route has no data guard. As a result, changes to a
+    /// route's signature result in compile-time errors in the tests that use
+    /// it. `R` is the type of the proxy structure generated by the route
+    /// attribute, named the same as the route's handler. The URI is typically
+    /// constructed with [`uri!`](crate::uri!).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    #[doc = $import]
+    ///
+    /// #[put("/<id>", data = "<note>")]
+    /// fn update(id: usize, note: String) { /* .. */ }
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// let req = client.call::<update>(uri!(update(10))).body("remember me");
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn call<'c, 'u: 'c, R: TypedRoute>(
+        &'c self,
+        uri: impl TryInto<Origin<'u>> + fmt::Display
+    ) -> TypedLocalRequest<'c, R> {
+        let method = R::METHOD.unwrap_or(Method::Get);
+        TypedLocalRequest::new(self.req(method, uri))
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn _ensure_impls_exist() {
@@ -232,3 +288,157 @@ macro_rules! pub_client_impl {
         is_debug::<Self>();
     }
 }}
+
+macro_rules! pub_client_builder {
+    ($import:literal $(@$prefix:tt $suffix:tt)?) =>
+{
+/// A builder for a [`Client`] that overrides managed state and disables
+/// fairings of the `Rocket` instance under test.
+///
+/// Returned by [`Client::builder()`]. Overrides let tests substitute, say, a
+/// fake payment client for the real one, or disable a fairing, without
+/// duplicating the application's build code for each test configuration.
+///
+///   * [`override_state()`](Self::override_state()) replaces managed state of
+///     a type, whether the application manages it before or during ignition.
+///   * [`without_fairing()`](Self::without_fairing()) removes fairings with a
+///     given name, including any attached by other fairings during ignition.
+///
+/// By default, the client tracks cookies and assumes a plaintext connection.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+#[doc = $import]
+/// use rocket::State;
+///
+/// trait Payments: Send + Sync {
+///     fn charge(&self, cents: u64) -> bool;
+/// }
+///
+/// struct Processor(Box<dyn Payments>);
+///
+/// struct Fake;
+///
+/// impl Payments for Fake {
+///     fn charge(&self, _: u64) -> bool { true }
+/// }
+///
+/// #[post("/charge")]
+/// fn charge(processor: &State<Processor>) -> &'static str {
+///     if processor.0.charge(100) { "charged" } else { "declined" }
+/// }
+///
+/// # fn rocket() -> rocket::Rocket<rocket::Build> { rocket::build() }
+/// let client = Client::builder(rocket())
+///     .override_state(Processor(Box::new(Fake)))
+///     .without_fairing("CORS")
+///     .build();
+/// ```
+pub struct ClientBuilder {
+    rocket: Rocket<crate::Build>,
+    overrides: crate::local::client::Overrides,
+    tracked: bool,
+    secure: bool,
+}
+
+impl ClientBuilder {
+    /// Overrides managed state of type `T` with `state`. Requests for
+    /// `&State<T>`, and calls to [`Rocket::state()`], return `state` even if
+    /// the application manages a `T` itself. Overriding the same type again
+    /// replaces the previous override.
+    pub fn override_state<T: Send + Sync + 'static>(mut self, state: T) -> Self {
+        self.overrides.state(state);
+        self
+    }
+
+    /// Disables every fairing whose [`Info::name`](crate::fairing::Info::name)
+    /// is `name`. Fairings with the name that are attached later, including
+    /// by other fairings during ignition, are ignored.
+    pub fn without_fairing<N: Into<String>>(mut self, name: N) -> Self {
+        self.overrides.fairing(name.into());
+        self
+    }
+
+    /// Sets whether the client tracks cookies. See [`Client::tracked()`].
+    ///
+    /// **default: `true`**
+    pub fn tracked(mut self, tracked: bool) -> Self {
+        self.tracked = tracked;
+        self
+    }
+
+    /// Sets whether requests are made as if over a TLS connection.
+    ///
+    /// **default: `false`**
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Applies the overrides and builds the client.
+    ///
+    /// # Errors
+    ///
+    /// If launching the `Rocket` instance would fail, excepting network
+    /// errors, the `Error` is returned.
+    pub $($prefix)? fn build(self) -> Result<Client, Error> {
+        let rocket = self.overrides.apply(self.rocket);
+        Client::_new(rocket, self.tracked, self.secure) $(.$suffix)?
+    }
+}
+
+impl std::fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("overrides", &self.overrides)
+            .field("tracked", &self.tracked)
+            .field("secure", &self.secure)
+            .finish_non_exhaustive()
+    }
+}
+}}
+
+type Apply = Box<dyn FnOnce(crate::Rocket<crate::Build>) -> crate::Rocket<crate::Build> + Send>;
+
+/// State overrides and disabled fairings applied by a `ClientBuilder`.
+#[derive(Default)]
+pub(crate) struct Overrides {
+    state: Vec<(std::any::TypeId, &'static str, Apply)>,
+    fairings: Vec<String>,
+}
+
+impl Overrides {
+    pub fn state<T: Send + Sync + 'static>(&mut self, state: T) {
+        let id = std::any::TypeId::of::<T>();
+        self.state.retain(|(other, ..)| *other != id);
+        let apply: Apply = Box::new(move |rocket| rocket.override_state(state));
+        self.state.push((id, std::any::type_name::<T>(), apply));
+    }
+
+    pub fn fairing(&mut self, name: String) {
+        self.fairings.push(name);
+    }
+
+    pub fn apply(self, mut rocket: crate::Rocket<crate::Build>) -> crate::Rocket<crate::Build> {
+        for name in self.fairings {
+            rocket = rocket.disable_fairing(name);
+        }
+
+        for (.., apply) in self.state {
+            rocket = apply(rocket);
+        }
+
+        rocket
+    }
+}
+
+impl std::fmt::Debug for Overrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Overrides")
+            .field("state", &self.state.iter().map(|(_, name, _)| name).collect::<Vec<_>>())
+            .field("fairings", &self.fairings)
+            .finish()
+    }
+}
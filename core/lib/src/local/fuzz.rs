@@ -0,0 +1,481 @@
+//! Smoke testing of every mounted route with generated inputs.
+//!
+//! A [`Fuzzer`] enumerates the routes of a local [`Client`]'s Rocket instance
+//! and dispatches requests to each, generating values for every dynamic path
+//! and query parameter, and reports the requests that resulted in a server
+//! error. Since Rocket answers requests whose handler panicked with a `500`,
+//! this includes panics. A fuzzer is a cheap way to find, say, an unchecked
+//! index or `unwrap()` in a large route table.
+//!
+//! [`Client`]: crate::local::blocking::Client
+//!
+//! # Generated Values
+//!
+//! Code generation records the name and type of every dynamic parameter of
+//! routes declared with attributes like `#[get]`. Values are generated based
+//! on the type of the parameter:
+//!
+//!   * integers, floats, `bool`, and `char`: valid values, biased towards
+//!     edge cases like `0` and `MAX`;
+//!   * `Option<T>` and `Result<T, E>`: values for `T`, or occasionally
+//!     arbitrary strings;
+//!   * `PathBuf` and other trailing path parameters: a few path segments;
+//!   * everything else, including strings: arbitrary short strings, including
+//!     Unicode and punctuation.
+//!
+//! Values for custom types can be generated via [`Fuzzer::generator()`].
+//! Trailing query parameters, `<params..>`, are left empty. Routes created
+//! manually, without code generation, receive arbitrary strings. Requests are
+//! sent without a body; those to routes with a `format` carry the format as
+//! their `Content-Type` or `Accept` header, as appropriate for the method.
+//!
+//! Generation is deterministic for a given [`Fuzzer::seed()`], so failures
+//! are reproducible.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::local::blocking::Client;
+//! use rocket::local::fuzz::Fuzzer;
+//!
+//! #[get("/page/<n>")]
+//! fn page(n: u8) -> &'static str {
+//!     // Panics if `n` is greater than `2`.
+//!     ["one", "two", "three"][n as usize]
+//! }
+//!
+//! #[get("/user/<id>")]
+//! fn user(id: UserId) -> String {
+//!     id.0
+//! }
+//! # struct UserId(String);
+//! # impl<'a> rocket::request::FromParam<'a> for UserId {
+//! #     type Error = ();
+//! #     fn from_param(s: &'a str) -> Result<Self, ()> { Ok(UserId(s.into())) }
+//! # }
+//!
+//! let client = Client::debug_with(routes![page, user]).unwrap();
+//! let report = Fuzzer::new()
+//!     .generator("UserId", |g| format!("u{}", g.below(1000)))
+//!     .run_blocking(&client);
+//!
+//! assert!(!report.is_ok());
+//! assert_eq!(report.failures[0].route.as_deref(), Some("page"));
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use crate::Route;
+use crate::http::{Method, Status, StatusClass, ContentType, Header, RawStr};
+use crate::local::{asynchronous, blocking};
+use crate::route::Segment;
+
+/// Generates requests for every route of a local client and reports failures.
+///
+/// See the [module level docs](self) for details.
+#[derive(Clone)]
+pub struct Fuzzer {
+    iterations: usize,
+    seed: u64,
+    generators: HashMap<String, Arc<Generator>>,
+    filter: Option<Arc<Filter>>,
+}
+
+/// A user-supplied value generator for a type.
+type Generator = dyn Fn(&mut Gen) -> String + Send + Sync;
+
+/// A user-supplied route filter.
+type Filter = dyn Fn(&Route) -> bool + Send + Sync;
+
+/// A source of randomness for value generators.
+///
+/// Passed to the generators registered via [`Fuzzer::generator()`].
+pub struct Gen {
+    rng: StdRng,
+}
+
+/// The result of a [`Fuzzer`] run.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Report {
+    /// The seed the run used.
+    pub seed: u64,
+    /// The number of requests dispatched.
+    pub requests: usize,
+    /// The requests that resulted in a server error, in dispatch order.
+    pub failures: Vec<Failure>,
+}
+
+/// A request that resulted in a server error.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Failure {
+    /// The name of the route the request was generated for, if it has one.
+    pub route: Option<Cow<'static, str>>,
+    /// The method of the request.
+    pub method: Method,
+    /// The URI of the request.
+    pub uri: String,
+    /// The status of the response.
+    pub status: Status,
+}
+
+impl Fuzzer {
+    /// The default number of requests dispatched per route.
+    pub const ITERATIONS: usize = 32;
+
+    /// The default seed.
+    pub const SEED: u64 = 0x5eed;
+
+    /// Returns a fuzzer that dispatches [`Fuzzer::ITERATIONS`] requests to
+    /// every route using the seed [`Fuzzer::SEED`].
+    pub fn new() -> Self {
+        Fuzzer {
+            iterations: Self::ITERATIONS,
+            seed: Self::SEED,
+            generators: HashMap::new(),
+            filter: None,
+        }
+    }
+
+    /// Sets the number of requests to dispatch per route to `iterations`.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the seed for value generation to `seed`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Generates values for parameters of type `type_name` with `generator`.
+    ///
+    /// The generator returns the unencoded value of the parameter as it would
+    /// appear in a URI; the fuzzer encodes it. `type_name` is the name of the
+    /// type as written in the route's signature without any module path,
+    /// references, or lifetimes: `UserId` for `&'r models::UserId`. A
+    /// generator for a type takes precedence over the built-in generators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::fuzz::Fuzzer;
+    ///
+    /// let fuzzer = Fuzzer::new()
+    ///     .generator("Slug", |g| g.choose(&["hello-world", "a", "-"]).to_string());
+    /// ```
+    pub fn generator<F>(mut self, type_name: &str, generator: F) -> Self
+        where F: Fn(&mut Gen) -> String + Send + Sync + 'static
+    {
+        self.generators.insert(normalize(type_name), Arc::new(generator));
+        self
+    }
+
+    /// Only dispatches requests to routes for which `filter` returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::fuzz::Fuzzer;
+    ///
+    /// // Don't fuzz routes mounted under `/admin`.
+    /// let fuzzer = Fuzzer::new().filter(|route| !route.uri.base().as_str().starts_with("/admin"));
+    /// ```
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&Route) -> bool + Send + Sync + 'static
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Dispatches requests to the routes of `client`'s instance, returning a
+    /// report of the requests that failed.
+    pub async fn run(&self, client: &asynchronous::Client) -> Report {
+        let mut gen = Gen { rng: StdRng::seed_from_u64(self.seed) };
+        let mut report = Report { seed: self.seed, requests: 0, failures: vec![] };
+        let routes = client.rocket().routes()
+            .filter(|route| self.filter.as_ref().map_or(true, |filter| filter(route)));
+
+        for route in routes {
+            let method = route.method.unwrap_or(Method::Get);
+            for _ in 0..self.iterations {
+                let uri = self.uri(route, &mut gen);
+                let mut request = client.req(method, uri.clone());
+                if let Some(format) = &route.format {
+                    match method.allows_request_body() {
+                        Some(true) => request.add_header(ContentType(format.clone())),
+                        _ => request.add_header(Header::new("Accept", format.to_string())),
+                    }
+                }
+
+                let status = request.dispatch().await.status();
+                report.requests += 1;
+                if status.class() == StatusClass::ServerError {
+                    warn!(route = route.name.as_deref(), %method, %uri, %status, "fuzzing failure");
+                    report.failures.push(Failure {
+                        route: route.name.clone(),
+                        method,
+                        uri,
+                        status,
+                    });
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Blocking variant of [`Fuzzer::run()`].
+    pub fn run_blocking(&self, client: &blocking::Client) -> Report {
+        client.block_on(self.run(client.inner()))
+    }
+
+    /// Generates a URI for `route`.
+    fn uri(&self, route: &Route, gen: &mut Gen) -> String {
+        let type_of = |name: &str| route.params.iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, ty)| normalize(ty));
+
+        let mut uri = String::new();
+        for segment in &route.uri.metadata.uri_segments {
+            if !segment.dynamic {
+                uri.push('/');
+                uri.push_str(&segment.value);
+                continue;
+            }
+
+            let ty = type_of(&segment.value).unwrap_or_default();
+            let count = if segment.dynamic_trail { gen.below(4) } else { 1 };
+            for _ in 0..count {
+                let value = match segment.dynamic_trail {
+                    true => gen.string(),
+                    false => self.value(&ty, gen),
+                };
+
+                uri.push('/');
+                uri.push_str(RawStr::new(&value).percent_encode().as_str());
+            }
+        }
+
+        if uri.is_empty() || (route.uri.path().as_str().ends_with('/') && !uri.ends_with('/')) {
+            uri.push('/');
+        }
+
+        let mut query = vec![];
+        for field in route.uri.query().map(|q| q.as_str().split('&')).into_iter().flatten() {
+            let segment = Segment::from(RawStr::new(field));
+            if !segment.dynamic {
+                query.push(field.to_string());
+            } else if !segment.dynamic_trail {
+                let ty = type_of(&segment.value).unwrap_or_default();
+                let value = self.value(&ty, gen);
+                let name = RawStr::new(&segment.value).percent_encode();
+                query.push(format!("{}={}", name, RawStr::new(&value).percent_encode()));
+            }
+        }
+
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(&query.join("&"));
+        }
+
+        uri
+    }
+
+    /// Generates a value for a parameter of the normalized type `ty`.
+    fn value(&self, ty: &str, gen: &mut Gen) -> String {
+        macro_rules! number {
+            ($($T:ident),*) => (match ty {
+                $(stringify!($T) => Some(match gen.below(4) {
+                    0 => gen.choose(&[$T::MIN, $T::MAX]).to_string(),
+                    1 => gen.choose(&[0 as $T, 1 as $T]).to_string(),
+                    _ => gen.rng.gen::<$T>().to_string(),
+                }),)*
+                _ => None,
+            })
+        }
+
+        if let Some(generator) = self.generators.get(ty) {
+            return generator(gen);
+        }
+
+        let number = number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+        if let Some(number) = number.or_else(|| number!(f32, f64)) {
+            return number;
+        }
+
+        let wrapped = ["Option<", "Result<", "Lenient<", "Strict<"].iter()
+            .find_map(|prefix| ty.strip_prefix(prefix)?.strip_suffix('>'));
+
+        if let Some(inner) = wrapped {
+            let inner = inner.split(',').next().unwrap_or(inner);
+            return match gen.below(4) {
+                0 => gen.string(),
+                _ => self.value(inner, gen),
+            };
+        }
+
+        match ty {
+            "bool" => gen.choose(&["true", "false"]).to_string(),
+            "char" => gen.string().chars().take(1).collect(),
+            "PathBuf" => (0..=gen.below(3)).map(|_| gen.string()).collect::<Vec<_>>().join("/"),
+            _ => gen.string(),
+        }
+    }
+}
+
+impl Default for Fuzzer {
+    fn default() -> Self {
+        Fuzzer::new()
+    }
+}
+
+impl fmt::Debug for Fuzzer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fuzzer")
+            .field("iterations", &self.iterations)
+            .field("seed", &self.seed)
+            .field("generators", &self.generators.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Gen {
+    /// Returns a random `bool`.
+    pub fn bool(&mut self) -> bool {
+        self.rng.gen()
+    }
+
+    /// Returns a random `u64`.
+    pub fn u64(&mut self) -> u64 {
+        self.rng.gen()
+    }
+
+    /// Returns a random number in `[0, n)`, or `0` if `n` is `0`.
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 { 0 } else { self.rng.gen_range(0..n) }
+    }
+
+    /// Returns a random item from `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Returns a short, non-empty, random string, usually alphanumeric but
+    /// occasionally containing punctuation, whitespace, or Unicode.
+    pub fn string(&mut self) -> String {
+        const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyz\
+            ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        const ODD: &[&str] = &[" ", "-", "_", ".", "..", "%", "&", "=", "+", "?", "#", "/",
+            "'", "\"", "<", ">", "\\", "é", "ß", "日本", "🚀", "\u{200b}"];
+
+        let len = 1 + self.below(12);
+        let mut string = String::with_capacity(len);
+        for _ in 0..len {
+            match self.below(8) {
+                0 => string.push_str(self.choose(ODD)),
+                _ => string.push(*self.choose(ALPHANUMERIC) as char),
+            }
+        }
+
+        string
+    }
+}
+
+impl Report {
+    /// Returns `true` if no request failed.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with the report if any request failed.
+    #[track_caller]
+    pub fn assert_ok(&self) {
+        if !self.is_ok() {
+            panic!("{self}");
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} requests failed (seed {:#x})", self.failures.len(),
+            self.requests, self.seed)?;
+
+        for failure in &self.failures {
+            write!(f, "\n  {}", failure)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} => {}", self.method, self.uri, self.status)?;
+        if let Some(route) = &self.route {
+            write!(f, " (route `{route}`)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Normalizes the type name `ty`: removes whitespace, references, lifetimes,
+/// and module paths, and the `Box`, `Rc`, and `Arc` wrappers.
+fn normalize(ty: &str) -> String {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut normalized = String::with_capacity(ty.len());
+    let mut chars = ty.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => {},
+            '\'' => while chars.next_if(|c| c.is_alphanumeric() || *c == '_').is_some() {},
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                let start = normalized.rfind(['<', ',']).map_or(0, |i| i + 1);
+                normalized.truncate(start);
+            }
+            _ => normalized.push(c),
+        }
+    }
+
+    let normalized = normalized.replace("<,", "<").replace(",>", ">").replace("<>", "");
+    for wrapper in ["Box<", "Rc<", "Arc<"] {
+        if let Some(inner) = normalized.strip_prefix(wrapper).and_then(|t| t.strip_suffix('>')) {
+            return inner.to_string();
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("usize"), "usize");
+        assert_eq!(normalize("& 'r str"), "str");
+        assert_eq!(normalize("&'_ models::UserId"), "UserId");
+        assert_eq!(normalize("std :: path :: PathBuf"), "PathBuf");
+        assert_eq!(normalize("Option < crate::Id<'r> >"), "Option<Id>");
+        assert_eq!(normalize("Foo<'a, 'b, T>"), "Foo<T>");
+        assert_eq!(normalize("Result<u8, &'r str>"), "Result<u8,str>");
+        assert_eq!(normalize("Box<str>"), "str");
+    }
+}
@@ -150,6 +150,29 @@
 //! needs to be dispatched multiple times, the request can first be cloned and
 //! then dispatched: `request.clone().dispatch()`.
 //!
+//! **Typed Requests**
+//!
+//! A request to a specific route can also be constructed via [`call()`]
+//! ([`async` `call()`]), which yields a [`TypedLocalRequest`] ([`async`
+//! `TypedLocalRequest`]) whose body is checked at compile-time against the
+//! route's data guard. Tests written this way fail to compile, rather than fail
+//! at runtime, when a route's signature changes:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # use rocket::local::blocking::Client;
+//! #[post("/<id>", data = "<note>")]
+//! fn note(id: usize, note: &str) { /* .. */ }
+//!
+//! # let client = Client::debug_with(routes![note]).unwrap();
+//! let response = client.call::<note>(uri!(note(10))).body("hi").dispatch();
+//! ```
+//!
+//! [`call()`]: blocking::Client::call()
+//! [`async` `call()`]: asynchronous::Client::call()
+//! [`TypedLocalRequest`]: blocking::TypedLocalRequest
+//! [`async` `TypedLocalRequest`]: asynchronous::TypedLocalRequest
+//!
 //! **Example**
 //!
 //! For a usage example, see [`LocalRequest`] or [`async` `LocalRequest`].
@@ -174,14 +197,47 @@
 //!
 //! For a usage example, see [`LocalResponse`] or [`async` `LocalResponse`].
 //!
+//! **Upgrades and Server-Sent Events**
+//!
+//! A request can be dispatched as a connection upgrade request via
+//! [`dispatch_upgrade()`] ([`async` `dispatch_upgrade()`]). If the response
+//! accepts the upgrade, the client's end of the upgraded connection is
+//! returned as a [`LocalUpgraded`] ([`async` `LocalUpgraded`]) which can be
+//! read from and written to directly or wrapped by a protocol client, such as a
+//! WebSocket client. No port is bound in either case.
+//!
+//! Server-Sent Events in a response body can be read one at a time via
+//! [`next_event()`] ([`async` `next_event()`]) or all at once via
+//! [`events()`] ([`async` `events()`]).
+//!
+//! [`dispatch_upgrade()`]: blocking::LocalRequest::dispatch_upgrade()
+//! [`async` `dispatch_upgrade()`]: asynchronous::LocalRequest::dispatch_upgrade()
+//! [`LocalUpgraded`]: blocking::LocalUpgraded
+//! [`async` `LocalUpgraded`]: asynchronous::LocalUpgraded
+//! [`next_event()`]: blocking::LocalResponse::next_event()
+//! [`async` `next_event()`]: asynchronous::LocalResponse::next_event()
+//! [`events()`]: blocking::LocalResponse::events()
+//! [`async` `events()`]: asynchronous::LocalResponse::events()
+//!
 //! [`into_string()`]: blocking::LocalResponse::into_string()
 //! [`async` `into_string()`]: asynchronous::LocalResponse::into_string()
 //! [`into_bytes()`]: blocking::LocalResponse::into_bytes()
 //! [`async` `into_bytes()`]: asynchronous::LocalResponse::into_bytes()
+//!
+//! # Fuzzing
+//!
+//! The [`fuzz`] module provides a [`Fuzzer`](fuzz::Fuzzer) which dispatches
+//! requests with generated parameter values to every mounted route of a
+//! `Client`'s instance, reporting requests that result in server errors,
+//! including panics.
 
 #[macro_use] mod client;
 #[macro_use] mod request;
 #[macro_use] mod response;
+#[macro_use] mod typed;
 
 pub mod asynchronous;
 pub mod blocking;
+pub mod fuzz;
+
+pub use typed::{TypedRoute, TypedBody};
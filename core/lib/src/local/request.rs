@@ -257,6 +257,28 @@ macro_rules! pub_request_impl {
         self
     }
 
+    /// Sets the TLS session details of the request's connection to `info`,
+    /// as reported by [`ConnectionInfo`](crate::request::ConnectionInfo).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::listener::TlsInfo;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let info = TlsInfo { alpn: Some("h2".into()), ..Default::default() };
+    /// let req = request.tls_info(info);
+    /// assert_eq!(req.inner().connection_info().alpn(), Some("h2"));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn tls_info(mut self, info: crate::listener::TlsInfo) -> Self {
+        self._request_mut().connection.tls = Some(std::sync::Arc::new(info));
+        self
+    }
+
     /// Sets the body data of the request.
     ///core/lib/src/local/request.rs
     /// # Examples
@@ -392,6 +414,36 @@ macro_rules! pub_request_impl {
         self._dispatch()$(.$suffix)?
     }
 
+    /// Dispatches the request as a request to upgrade the connection to
+    /// `protocol`, returning the upgraded connection if the response accepted
+    /// the upgrade and the response otherwise.
+    ///
+    /// The `Connection` and `Upgrade` headers are set on the request as
+    /// required. The upgrade is accepted, exactly as it would be by a server,
+    /// if the response registers an [`IoHandler`] for `protocol`. The
+    /// returned connection is the client's end of the stream passed to the
+    /// handler. See [`LocalUpgraded`] for details.
+    ///
+    /// [`IoHandler`]: crate::data::IoHandler
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let upgraded = request.dispatch_upgrade("websocket");
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub $($prefix)? fn dispatch_upgrade(
+        self,
+        protocol: &str
+    ) -> Result<LocalUpgraded<'c>, LocalResponse<'c>> {
+        self._dispatch_upgrade(protocol)$(.$suffix)?
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn _ensure_impls_exist() {
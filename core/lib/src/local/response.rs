@@ -37,6 +37,12 @@ macro_rules! pub_response_impl {
     getter_method!($doc_prelude, "HTTP headers",
         headers -> &crate::http::HeaderMap<'_>);
 
+    getter_method!($doc_prelude, "HTTP trailers, sent after the body,",
+        trailers -> &crate::http::HeaderMap<'_>);
+
+    getter_method!($doc_prelude, "preload links, sent as `Link` headers,",
+        preload_links -> &crate::http::HeaderMap<'_>);
+
     /// Return a cookie jar containing the HTTP cookies in the response.
     ///
     /// # Example
@@ -180,6 +186,177 @@ macro_rules! pub_response_impl {
         self._into_msgpack() $(.$suffix)?
     }
 
+    /// Asserts that the HTTP status of `self` is `status`, returning `self`
+    /// for further assertions.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a message containing the actual and expected status if they
+    /// differ.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use rocket::http::Status;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response.assert_status(Status::NotFound);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn assert_status(&self, status: crate::http::Status) -> &Self {
+        assert_eq!(self.status(), status, "unexpected response status");
+        self
+    }
+
+    /// Asserts that the response contains a header named `name` with the value
+    /// `value`, returning `self` for further assertions.
+    ///
+    /// Header names are compared case-insensitively. If the header has several
+    /// values, any of them may match.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a message containing the actual values of the header, if
+    /// any, if no value matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use rocket::http::Status;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response
+    ///     .assert_status(Status::NotFound)
+    ///     .assert_header("Content-Type", "text/html; charset=utf-8");
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+        let values: Vec<_> = self.headers().get(name).collect();
+        assert!(values.contains(&value),
+            "expected header `{name}: {value}`, found values {values:?}");
+
+        self
+    }
+
+    /// Asserts that the `Content-Type` of the response is `content_type`,
+    /// returning `self` for further assertions.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a message containing the actual `Content-Type`, if any, if
+    /// it differs from `content_type`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use rocket::http::ContentType;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// response.assert_content_type(ContentType::HTML);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn assert_content_type(&self, content_type: crate::http::ContentType) -> &Self {
+        assert_eq!(self.content_type(), Some(content_type), "unexpected content-type");
+        self
+    }
+
+    /// Consumes `self` and returns a canonical, human-readable serialization of
+    /// the response suitable for snapshot testing.
+    ///
+    /// The serialization consists of the status line, followed by each header
+    /// as `name: value` with names lowercased and sorted, an empty line, and
+    /// the body. If the body is JSON and the `json` feature is enabled, it is
+    /// pretty-printed. If it is not valid UTF-8, only its length is included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// let snapshot = response.into_snapshot();
+    /// assert!(snapshot.starts_with("404 Not Found\n"));
+    /// # });
+    /// ```
+    pub $($prefix)? fn into_snapshot(self) -> String {
+        let mut headers: Vec<_> = self.headers().iter()
+            .map(|h| (h.name().as_str().to_ascii_lowercase(), h.value().to_string()))
+            .collect();
+
+        headers.sort_by(|a, b| a.0.cmp(&b.0));
+        let status = self.status();
+        let is_json = self.content_type().map_or(false, |ct| ct.is_json());
+        let body = self.into_bytes() $(.$suffix)?;
+        crate::local::response::snapshot(status, headers, is_json, body)
+    }
+
+    /// Consumes `self`, parses its body as an HTML document, and returns the
+    /// text of every element matching the CSS selector `css_selector`, in
+    /// document order.
+    ///
+    /// The text of an element is the concatenation of all text nodes it
+    /// contains. If the body is unset or is not valid UTF-8, returns an empty
+    /// vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `css_selector` is not a valid CSS selector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// let titles = response.html_select("head > title");
+    /// assert_eq!(titles, ["404 Not Found"]);
+    /// # });
+    /// ```
+    #[cfg(feature = "html")]
+    #[cfg_attr(nightly, doc(cfg(feature = "html")))]
+    pub $($prefix)? fn html_select(self, css_selector: &str) -> Vec<String> {
+        let selector = crate::local::response::selector(css_selector);
+        let html = self.into_string() $(.$suffix)?.unwrap_or_default();
+        crate::local::response::html_select(&html, &selector)
+    }
+
+    /// Reads the next Server-Sent [`Event`] from the response body, parsing
+    /// it as a client would.
+    ///
+    /// Returns `None` if the body ends before another complete event is read,
+    /// if reading fails, or if the event is not valid UTF-8. Comments,
+    /// including those sent as heartbeats by [`EventStream`], are returned as
+    /// events.
+    ///
+    /// [`Event`]: crate::response::stream::Event
+    /// [`EventStream`]: crate::response::stream::EventStream
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let mut response: LocalResponse = response;
+    /// let event = response.next_event();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub $($prefix)? fn next_event(&mut self) -> Option<crate::response::stream::Event> {
+        self._next_event() $(.$suffix)?
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn _ensure_impls_exist() {
@@ -187,3 +364,62 @@ macro_rules! pub_response_impl {
         is_debug::<Self>();
     }
 }}
+
+/// Formats a response snapshot. See `into_snapshot()`.
+#[cfg_attr(not(feature = "json"), allow(unused_variables))]
+pub(crate) fn snapshot(
+    status: crate::http::Status,
+    headers: Vec<(String, String)>,
+    is_json: bool,
+    body: Option<Vec<u8>>,
+) -> String {
+    use std::fmt::Write;
+
+    let mut snapshot = format!("{status}\n");
+    for (name, value) in headers {
+        let _ = writeln!(snapshot, "{name}: {value}");
+    }
+
+    snapshot.push('\n');
+    let Some(body) = body else {
+        return snapshot;
+    };
+
+    #[cfg(feature = "json")]
+    if is_json {
+        if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&body) {
+            if let Ok(pretty) = serde_json::to_string_pretty(&value) {
+                snapshot.push_str(&pretty);
+                return snapshot;
+            }
+        }
+    }
+
+    match String::from_utf8(body) {
+        Ok(string) => snapshot.push_str(&string),
+        Err(e) => {
+            let _ = write!(snapshot, "<{} bytes of binary data>", e.as_bytes().len());
+        }
+    }
+
+    snapshot
+}
+
+/// Parses `css_selector`, panicking if it is invalid. See `html_select()`.
+#[cfg(feature = "html")]
+#[track_caller]
+pub(crate) fn selector(css_selector: &str) -> scraper::Selector {
+    match scraper::Selector::parse(css_selector) {
+        Ok(selector) => selector,
+        Err(e) => panic!("invalid CSS selector `{css_selector}`: {e}"),
+    }
+}
+
+/// Returns the text of every element in `html` matching `selector`.
+#[cfg(feature = "html")]
+pub(crate) fn html_select(html: &str, selector: &scraper::Selector) -> Vec<String> {
+    scraper::Html::parse_document(html)
+        .select(selector)
+        .map(|element| element.text().collect())
+        .collect()
+}
@@ -0,0 +1,231 @@
+use crate::http::ContentType;
+
+/// A route whose method and data guard type are known statically.
+///
+/// This trait is implemented automatically by Rocket's route attributes for
+/// the proxy structure generated for every route whose data guard is concrete,
+/// that is, not generic. It is what allows [`Client::call()`] to construct a
+/// request whose body is checked at compile-time against the route's data
+/// guard. There is no reason to implement this trait manually.
+///
+/// [`Client::call()`]: crate::local::blocking::Client::call()
+pub trait TypedRoute {
+    /// The route's data guard, represented as the function pointer type
+    /// `fn(Guard)`, or `fn()` if the route has no data guard.
+    ///
+    /// Every lifetime in `Guard`, including elided ones, is replaced with
+    /// `'static`, so that a guard like `&str` is represented as `fn(&'static
+    /// str)`. The body of a typed request can be set for a route if `Guard`
+    /// implements [`TypedBody`]. Lifetimes hidden in paths can't be replaced:
+    /// write `Data<'_>`, not `Data`, for a route's `Data` to be typed.
+    type Data;
+
+    /// The route's method, if any.
+    const METHOD: Option<crate::http::Method>;
+}
+
+/// A data guard that can be constructed from a typed value in local requests.
+///
+/// Implementations exist for the following data guards:
+///
+///   * `String` and `&str`, whose value is `str`
+///   * `Vec<u8>`, `&[u8]`, and [`Data`](crate::Data), whose value is `[u8]`
+///   * [`Form<T>`](crate::form::Form), whose value is `T`, which must
+///     implement [`UriDisplay<Query>`](crate::http::uri::fmt::UriDisplay)
+///   * [`Json<T>`](crate::serde::json::Json), whose value is `T`
+///   * [`MsgPack<T>`](crate::serde::msgpack::MsgPack), whose value is `T`
+///
+/// The representation of a route's data guard, `fn(Guard)`, implements the
+/// trait when `Guard` does, as does `fn()`, the representation of routes with
+/// no data guard, whose value is `()`. See [`TypedRoute::Data`] for details.
+///
+/// # Custom Data Guards
+///
+/// The trait can be implemented for custom data guards. As a guard is named
+/// with `'static` lifetimes, implementations for guards with lifetimes should
+/// be generic over them:
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket::local::TypedBody;
+///
+/// struct Csv<'r>(Vec<&'r str>);
+///
+/// impl<'r> TypedBody for Csv<'r> {
+///     type Value = [&'static str];
+///
+///     fn encode(value: &Self::Value) -> (Option<ContentType>, Vec<u8>) {
+///         (Some(ContentType::CSV), value.join(",").into_bytes())
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Implementations panic if `value` can't be encoded, as when serialization
+/// fails, instead of sending a body that the route would reject for unrelated
+/// reasons.
+pub trait TypedBody {
+    /// The type of the value the request body is encoded from.
+    type Value: ?Sized;
+
+    /// Encodes `value` into the `Content-Type` of the request, if any, and the
+    /// raw request body.
+    fn encode(value: &Self::Value) -> (Option<ContentType>, Vec<u8>);
+}
+
+impl TypedBody for fn() {
+    type Value = ();
+
+    fn encode(_: &()) -> (Option<ContentType>, Vec<u8>) {
+        (None, vec![])
+    }
+}
+
+impl<G: TypedBody> TypedBody for fn(G) {
+    type Value = G::Value;
+
+    fn encode(value: &G::Value) -> (Option<ContentType>, Vec<u8>) {
+        G::encode(value)
+    }
+}
+
+macro_rules! impl_raw_typed_body {
+    ($([$($l:lifetime)?] $T:ty => $V:ty, $CT:expr),* $(,)?) => ($(
+        impl<$($l)?> TypedBody for $T {
+            type Value = $V;
+
+            fn encode(value: &$V) -> (Option<ContentType>, Vec<u8>) {
+                (Some($CT), AsRef::<[u8]>::as_ref(value).to_vec())
+            }
+        }
+    )*)
+}
+
+impl_raw_typed_body! {
+    [] String => str, ContentType::Text,
+    ['a] &'a str => str, ContentType::Text,
+    [] Vec<u8> => [u8], ContentType::Binary,
+    ['a] &'a [u8] => [u8], ContentType::Binary,
+    ['a] crate::Data<'a> => [u8], ContentType::Binary,
+}
+
+impl<T> TypedBody for crate::form::Form<T>
+    where T: crate::http::uri::fmt::UriDisplay<crate::http::uri::fmt::Query>
+{
+    type Value = T;
+
+    fn encode(value: &T) -> (Option<ContentType>, Vec<u8>) {
+        use crate::http::uri::fmt::{UriDisplay, Query};
+
+        let body = (value as &dyn UriDisplay<Query>).to_string();
+        (Some(ContentType::Form), body.into_bytes())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: crate::serde::Serialize> TypedBody for crate::serde::json::Json<T> {
+    type Value = T;
+
+    fn encode(value: &T) -> (Option<ContentType>, Vec<u8>) {
+        match serde_json::to_vec(value) {
+            Ok(bytes) => (Some(ContentType::JSON), bytes),
+            Err(e) => panic!("failed to serialize typed JSON request body: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T, const C: bool> TypedBody for crate::serde::msgpack::MsgPack<T, C>
+    where T: crate::serde::Serialize
+{
+    type Value = T;
+
+    fn encode(value: &T) -> (Option<ContentType>, Vec<u8>) {
+        let bytes = if C {
+            rmp_serde::to_vec(value)
+        } else {
+            rmp_serde::to_vec_named(value)
+        };
+
+        match bytes {
+            Ok(bytes) => (Some(ContentType::MsgPack), bytes),
+            Err(e) => panic!("failed to serialize typed MessagePack request body: {e}"),
+        }
+    }
+}
+
+macro_rules! pub_typed_request_impl {
+    ($import:literal $($prefix:tt $suffix:tt)?) =>
+{
+    pub(crate) fn new(request: LocalRequest<'c>) -> Self {
+        TypedLocalRequest { request, _route: std::marker::PhantomData }
+    }
+
+    /// Sets the body of the request to `value` as encoded for the route's
+    /// data guard, returning the untyped request.
+    ///
+    /// The type of `value` is checked at compile-time against the route's data
+    /// guard: a route with a data guard of `Json<T>` accepts a `&T`, a route
+    /// with a data guard of `String` accepts a `&str`, and so on. See
+    /// [`TypedBody`](crate::local::TypedBody) for all supported guards. The
+    /// `Content-Type` of the request is set as appropriate for the guard.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    #[doc = $import]
+    ///
+    /// #[post("/<name>", data = "<greeting>")]
+    /// fn greet(name: &str, greeting: String) -> String {
+    ///     format!("{greeting}, {name}!")
+    /// }
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// let req = client.call::<greet>(uri!(greet("Bob"))).body("Hi");
+    /// # });
+    /// ```
+    pub fn body(self, value: &<R::Data as TypedBody>::Value) -> LocalRequest<'c>
+        where R::Data: TypedBody
+    {
+        let (content_type, body) = <R::Data as TypedBody>::encode(value);
+        let mut request = self.request;
+        if let Some(content_type) = content_type {
+            request.add_header(content_type);
+        }
+
+        request.body(body)
+    }
+
+    /// Returns the untyped request, leaving the body unset.
+    pub fn into_inner(self) -> LocalRequest<'c> {
+        self.request
+    }
+
+    /// Dispatches the request, returning the response. Only available for
+    /// routes without a data guard.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    #[doc = $import]
+    ///
+    /// #[get("/<name>")]
+    /// fn hello(name: &str) -> String {
+    ///     format!("Hello, {name}!")
+    /// }
+    ///
+    /// # Client::_test(|client, _, _| {
+    /// let client: &Client = client;
+    /// let response = client.call::<hello>(uri!(hello("Bob"))).dispatch();
+    /// # });
+    /// ```
+    pub $($prefix)? fn dispatch(self) -> LocalResponse<'c>
+        where R: TypedRoute<Data = fn()>
+    {
+        self.request.dispatch()$(.$suffix)?
+    }
+}}
@@ -0,0 +1,342 @@
+//! Offset and cursor pagination for JSON APIs.
+//!
+//! The [`Pagination`] request guard reads pagination parameters from a
+//! request's query string, and the [`Page`] responder returns one page of
+//! items in a consistent JSON envelope along with [`Link`] headers that point
+//! to neighboring pages.
+//!
+//! Two styles of pagination are supported:
+//!
+//!   * **Offset** pagination uses `page` (1-based, default `1`) and
+//!     `per_page` parameters: `/items?page=3&per_page=50`. Respond with
+//!     [`Pagination::page()`], passing the total number of items.
+//!   * **Cursor** pagination uses an opaque `cursor` parameter, absent for the
+//!     first page, and `per_page`: `/items?cursor=abc&per_page=50`. Respond
+//!     with [`Pagination::cursor_page()`], passing the cursor of the next page,
+//!     if there is one.
+//!
+//! All other query parameters are preserved in `Link` header URIs.
+//!
+//! # Configuration
+//!
+//! The default and maximum `per_page` are configured via the `pagination`
+//! configuration parameter. A `per_page` greater than the maximum is capped.
+//!
+//! ```toml
+//! [default.pagination]
+//! default_per_page = 20   # the default
+//! max_per_page = 100      # the default
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::pagination::{Page, Pagination};
+//!
+//! # fn load(offset: u64, limit: u64) -> Vec<String> { vec![] }
+//! #[get("/users")]
+//! fn users(pagination: Pagination) -> Page<String> {
+//!     let users = load(pagination.offset(), pagination.limit());
+//!     pagination.page(users, 1_024)
+//! }
+//! ```
+//!
+//! A request to `/users?page=2&per_page=10` receives a response with the
+//! following body and a `Link` header with `first`, `prev`, `next`, and `last`
+//! relations:
+//!
+//! ```json
+//! {
+//!   "items": [ ... ],
+//!   "pagination": { "page": 2, "per_page": 10, "total": 1024, "total_pages": 103 }
+//! }
+//! ```
+//!
+//! Cursor pages omit `page`, `total`, and `total_pages` and instead include
+//! `next_cursor`, which is `null` on the last page.
+//!
+//! # Database Queries
+//!
+//! [`Pagination::limit()`] and [`Pagination::offset()`] map directly to SQL's
+//! `LIMIT` and `OFFSET`, and [`Pagination::limit_offset()`] returns both as
+//! `i64`s for binding to queries. See the `rocket_db_pools` documentation for
+//! a complete example.
+
+use serde::{Deserialize, Serialize};
+
+use crate::request::{self, Request, FromRequest};
+use crate::response::{self, Responder, Response};
+use crate::http::{Link, RawStr, Status};
+use crate::outcome::Outcome;
+use crate::serde::json::Json;
+
+/// Pagination configuration: the `pagination` configuration parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PaginationConfig {
+    /// The `per_page` to use when a request doesn't specify one.
+    /// **(default: `20`)**
+    #[serde(default = "PaginationConfig::default_per_page")]
+    pub default_per_page: u64,
+    /// The maximum `per_page` a request may ask for. **(default: `100`)**
+    #[serde(default = "PaginationConfig::max_per_page")]
+    pub max_per_page: u64,
+}
+
+impl PaginationConfig {
+    fn default_per_page() -> u64 { 20 }
+
+    fn max_per_page() -> u64 { 100 }
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        PaginationConfig {
+            default_per_page: Self::default_per_page(),
+            max_per_page: Self::max_per_page(),
+        }
+    }
+}
+
+/// A request guard for pagination parameters.
+///
+/// See the [module level docs](self) for details. The guard fails with `400
+/// Bad Request` if `page` or `per_page` are present but not positive
+/// integers, and with `500 Internal Server Error` if the configuration is
+/// invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    page: u64,
+    per_page: u64,
+    cursor: Option<String>,
+}
+
+/// An error retrieving a [`Pagination`].
+#[derive(Debug)]
+pub enum Error {
+    /// The `page` parameter was not a positive integer.
+    InvalidPage,
+    /// The `per_page` parameter was not a positive integer.
+    InvalidPerPage,
+    /// The `pagination` configuration parameter was invalid.
+    Config(Box<figment::Error>),
+}
+
+/// One page of `T`s: a JSON responder with pagination [`Link`] headers.
+///
+/// A `Page` is created with [`Pagination::page()`] or
+/// [`Pagination::cursor_page()`]. See the [module level docs](self).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page<T> {
+    items: Vec<T>,
+    pagination: PageInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct PageInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<u64>,
+    per_page: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_pages: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<Option<String>>,
+}
+
+impl Pagination {
+    /// The requested, 1-based page number. Always `1` for cursor pagination.
+    pub fn page_number(&self) -> u64 {
+        self.page
+    }
+
+    /// The number of items per page, after applying the default and maximum.
+    /// Equivalent to [`Pagination::limit()`].
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+    }
+
+    /// The requested cursor, if any.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// The maximum number of items to load for the page: SQL's `LIMIT`.
+    pub fn limit(&self) -> u64 {
+        self.per_page
+    }
+
+    /// The number of items to skip to reach the page: SQL's `OFFSET`.
+    pub fn offset(&self) -> u64 {
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+
+    /// Returns `(limit, offset)` as `i64`s, saturating at `i64::MAX`. This is
+    /// convenient for binding to database queries, which typically expect
+    /// signed integers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::pagination::Pagination;
+    ///
+    /// #[get("/")]
+    /// fn index(pagination: Pagination) -> String {
+    ///     let (limit, offset) = pagination.limit_offset();
+    ///     format!("SELECT * FROM items LIMIT {} OFFSET {}", limit, offset)
+    /// }
+    /// ```
+    pub fn limit_offset(&self) -> (i64, i64) {
+        let saturate = |n: u64| i64::try_from(n).unwrap_or(i64::MAX);
+        (saturate(self.limit()), saturate(self.offset()))
+    }
+
+    /// Returns an offset-paginated page containing `items` from a collection
+    /// of `total` items.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::pagination::{Page, Pagination};
+    ///
+    /// #[get("/numbers")]
+    /// fn numbers(pagination: Pagination) -> Page<u64> {
+    ///     let (start, end) = (pagination.offset(), pagination.offset() + pagination.limit());
+    ///     let items = (start..end.min(1000)).collect();
+    ///     pagination.page(items, 1000)
+    /// }
+    /// ```
+    pub fn page<T>(self, items: Vec<T>, total: u64) -> Page<T> {
+        let total_pages = total.div_ceil(self.per_page).max(1);
+        let pagination = PageInfo {
+            page: Some(self.page),
+            per_page: self.per_page,
+            total: Some(total),
+            total_pages: Some(total_pages),
+            next_cursor: None,
+        };
+
+        Page { items, pagination }
+    }
+
+    /// Returns a cursor-paginated page containing `items`. `next_cursor` is
+    /// the cursor of the following page or `None` if this is the last page.
+    pub fn cursor_page<T>(self, items: Vec<T>, next_cursor: Option<String>) -> Page<T> {
+        let pagination = PageInfo {
+            page: None,
+            per_page: self.per_page,
+            total: None,
+            total_pages: None,
+            next_cursor: Some(next_cursor),
+        };
+
+        Page { items, pagination }
+    }
+}
+
+impl<T> Page<T> {
+    /// The items in the page.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consumes `self` and returns the items in the page.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Returns the URI of the current request with the pagination parameters
+    /// replaced by `params`.
+    fn uri_with(req: &Request<'_>, params: &[(&str, &str)]) -> String {
+        let mut query = req.uri().query()
+            .map(|q| q.as_str().split('&'))
+            .into_iter()
+            .flatten()
+            .filter(|field| {
+                let name = field.split('=').next().unwrap_or_default();
+                !matches!(name, "page" | "per_page" | "cursor" | "")
+            })
+            .map(|field| field.to_string())
+            .collect::<Vec<_>>();
+
+        for (name, value) in params {
+            query.push(format!("{}={}", name, RawStr::new(value).percent_encode()));
+        }
+
+        format!("{}?{}", req.uri().path(), query.join("&"))
+    }
+
+    fn link(&self, req: &Request<'_>) -> Link {
+        let info = &self.pagination;
+        let per_page = info.per_page.to_string();
+        match (info.page, info.total_pages, &info.next_cursor) {
+            (Some(page), Some(last), _) => Link::new().paginate(page, last, |n| {
+                Self::uri_with(req, &[("page", &n.to_string()), ("per_page", &per_page)])
+            }),
+            (_, _, Some(Some(cursor))) => {
+                let params = [("cursor", cursor.as_str()), ("per_page", &per_page)];
+                Link::new().next(Self::uri_with(req, &params))
+            }
+            _ => Link::new(),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Pagination {
+    type Error = Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let config = match req.rocket().figment().extract_inner::<PaginationConfig>("pagination") {
+            Ok(config) => config,
+            Err(e) if e.missing() => PaginationConfig::default(),
+            Err(e) => {
+                error!("invalid pagination configuration: {}", e);
+                return Outcome::Error((Status::InternalServerError, Error::Config(e.into())));
+            }
+        };
+
+        let page = match req.query_value::<u64>("page") {
+            Some(Ok(page)) if page > 0 => page,
+            None => 1,
+            Some(_) => return Outcome::Error((Status::BadRequest, Error::InvalidPage)),
+        };
+
+        let per_page = match req.query_value::<u64>("per_page") {
+            Some(Ok(per_page)) if per_page > 0 => per_page.min(config.max_per_page),
+            None => config.default_per_page.min(config.max_per_page).max(1),
+            Some(_) => return Outcome::Error((Status::BadRequest, Error::InvalidPerPage)),
+        };
+
+        let cursor = req.query_value::<String>("cursor").and_then(|r| r.ok());
+        let page = if cursor.is_some() { 1 } else { page };
+        Outcome::Success(Pagination { page, per_page, cursor })
+    }
+}
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Page<T> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let link = self.link(req);
+        let mut response = Response::build_from(Json(self).respond_to(req)?);
+        if !link.is_empty() {
+            response.header(link);
+        }
+
+        response.ok()
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidPage => write!(f, "`page` must be a positive integer"),
+            Error::InvalidPerPage => write!(f, "`per_page` must be a positive integer"),
+            Error::Config(e) => write!(f, "invalid pagination configuration: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
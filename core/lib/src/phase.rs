@@ -1,11 +1,15 @@
+use std::sync::Arc;
+
 use state::TypeMap;
 use figment::Figment;
 
-use crate::listener::Endpoint;
+use crate::listener::{Binding, Endpoint};
 use crate::shutdown::Stages;
+use crate::load::Limiter;
 use crate::{Catcher, Config, Rocket, Route};
-use crate::router::{Router, Finalized};
+use crate::router::{Router, Finalized, HotRouter};
 use crate::fairing::Fairings;
+use crate::catcher::ErrorLog;
 
 mod private {
     pub trait Sealed {  }
@@ -91,6 +95,9 @@ phases! {
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) state: TypeMap![Send + Sync],
+        pub(crate) managed: Vec<&'static str>,
+        #[cfg(feature = "tower")]
+        pub(crate) layers: crate::service::Layers,
     }
 
     /// The second launch [`Phase`]: post-build but pre-orbit. See
@@ -105,7 +112,11 @@ phases! {
         pub(crate) figment: Figment,
         pub(crate) config: Config,
         pub(crate) state: TypeMap![Send + Sync],
+        pub(crate) managed: Vec<&'static str>,
         pub(crate) shutdown: Stages,
+        pub(crate) bound: Binding,
+        #[cfg(feature = "tower")]
+        pub(crate) layers: crate::service::Layers,
     }
 
     /// The final launch [`Phase`]. See [Rocket#orbit](`Rocket#orbit`) for
@@ -114,12 +125,19 @@ phases! {
     /// An instance of `Rocket` in this phase is typed as [`Rocket<Orbit>`] and
     /// represents a running application.
     Orbit (#[derive(Debug)] Orbiting) {
-        pub(crate) router: Router<Finalized>,
+        pub(crate) router: HotRouter,
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) config: Config,
         pub(crate) state: TypeMap![Send + Sync],
+        pub(crate) managed: Vec<&'static str>,
         pub(crate) shutdown: Stages,
+        pub(crate) bound: Binding,
         pub(crate) endpoints: Vec<Endpoint>,
+        pub(crate) load: Arc<Limiter>,
+        pub(crate) errors: ErrorLog,
+        pub(crate) tasks: TaskSet,
+        #[cfg(feature = "tower")]
+        pub(crate) layers: crate::service::Layers,
     }
 }
@@ -0,0 +1,222 @@
+//! The C ABI implemented by plugin libraries.
+//!
+//! A plugin library exports a function named [`DECLARE_SYMBOL`] of type
+//! [`DeclareFn`] that returns a pointer to a [`Declaration`]. All types in this
+//! module are `#[repr(C)]` and thus may be produced by any language that can
+//! export C functions. Strings are UTF-8 and, like byte buffers, are passed as
+//! a [`Slice`]: a pointer and a length, without a terminating `NUL`.
+//!
+//! # Version Checks
+//!
+//! A declaration's `abi_version` must equal [`ABI_VERSION`], which is
+//! incremented on every incompatible change to this module. Libraries built
+//! against another version of the ABI are rejected at load time, before any of
+//! their hooks or handlers are called.
+//!
+//! # Threading
+//!
+//! Handlers are called from a blocking thread pool and may be called
+//! concurrently from several threads, all with the same `ctx` pointer. The
+//! lifecycle hooks are called exactly once each, in order, and never
+//! concurrently with one another.
+//!
+//! # Example
+//!
+//! A plugin written in Rust as a `cdylib` depending on `rocket` with the
+//! `plugins` feature enabled might declare itself as follows:
+//!
+//! ```rust
+//! use std::ptr;
+//! use rocket::plugin::abi::*;
+//!
+//! unsafe extern "C" fn hello(
+//!     _: *mut std::ffi::c_void,
+//!     _: *const PluginRequest,
+//!     res: *mut PluginResponse,
+//! ) {
+//!     let res = &mut *res;
+//!     res.status = 200;
+//!     res.content_type = Slice::from_static("text/plain");
+//!     res.body = Slice::from_static("Hello from a plugin!");
+//! }
+//!
+//! static ROUTES: [RouteDeclaration; 1] = [RouteDeclaration {
+//!     method: Slice::from_static("GET"),
+//!     uri: Slice::from_static("/hello"),
+//!     handler: hello,
+//! }];
+//!
+//! struct Declared(Declaration);
+//!
+//! // SAFETY: The declaration's pointers are all to immutable statics.
+//! unsafe impl Sync for Declared {}
+//!
+//! static DECLARATION: Declared = Declared(Declaration {
+//!     abi_version: ABI_VERSION,
+//!     name: Slice::from_static("hello"),
+//!     version: Slice::from_static("1.0.0"),
+//!     routes: ROUTES.as_ptr(),
+//!     routes_len: ROUTES.len(),
+//!     ctx: ptr::null_mut(),
+//!     on_load: None,
+//!     on_liftoff: None,
+//!     on_shutdown: None,
+//! });
+//!
+//! #[no_mangle]
+//! pub extern "C" fn rocket_plugin_declare() -> *const Declaration {
+//!     &DECLARATION.0
+//! }
+//! ```
+
+use std::ffi::c_void;
+
+/// The version of the ABI defined by this module.
+pub const ABI_VERSION: u32 = 1;
+
+/// The name of the function, of type [`DeclareFn`], that every plugin library
+/// must export.
+pub const DECLARE_SYMBOL: &str = "rocket_plugin_declare";
+
+/// The type of the function named [`DECLARE_SYMBOL`].
+///
+/// The function must return a pointer to a [`Declaration`] that remains valid,
+/// along with everything it points to, for as long as the library is loaded.
+pub type DeclareFn = unsafe extern "C" fn() -> *const Declaration;
+
+/// The type of a route handler.
+///
+/// Called with the plugin's `ctx`, the request, and a response initialized to
+/// an empty `500 Internal Server Error`, which the handler fills in. The
+/// request and everything it points to is only valid for the duration of the
+/// call.
+pub type HandlerFn = unsafe extern "C" fn(
+    ctx: *mut c_void,
+    request: *const PluginRequest,
+    response: *mut PluginResponse,
+);
+
+/// The type of the `on_load` hook: returns `0` on success. Any other value
+/// aborts launch.
+pub type LoadFn = unsafe extern "C" fn(ctx: *mut c_void) -> i32;
+
+/// The type of the `on_liftoff` and `on_shutdown` hooks.
+pub type HookFn = unsafe extern "C" fn(ctx: *mut c_void);
+
+/// A borrowed, immutable string or byte buffer.
+///
+/// A `Slice` with a length of `0` is empty regardless of its pointer, which
+/// may be null.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Slice {
+    /// Pointer to the first byte.
+    pub ptr: *const u8,
+    /// The number of bytes.
+    pub len: usize,
+}
+
+/// A plugin's declaration of its metadata, routes, and lifecycle hooks.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Declaration {
+    /// The ABI version the plugin was built against. Must be [`ABI_VERSION`].
+    pub abi_version: u32,
+    /// The plugin's name, for display.
+    pub name: Slice,
+    /// The plugin's version, for display.
+    pub version: Slice,
+    /// Pointer to the first of `routes_len` route declarations.
+    pub routes: *const RouteDeclaration,
+    /// The number of route declarations.
+    pub routes_len: usize,
+    /// An opaque pointer passed to every hook and handler.
+    pub ctx: *mut c_void,
+    /// Called when the plugin is loaded, during ignition, before its routes
+    /// are mounted.
+    pub on_load: Option<LoadFn>,
+    /// Called when Rocket lifts off.
+    pub on_liftoff: Option<HookFn>,
+    /// Called when Rocket begins shutting down.
+    pub on_shutdown: Option<HookFn>,
+}
+
+/// A route to be mounted at `/`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RouteDeclaration {
+    /// The route's method, such as `GET`.
+    pub method: Slice,
+    /// The route's URI. May contain dynamic parameters, which are not parsed.
+    pub uri: Slice,
+    /// The route's handler.
+    pub handler: HandlerFn,
+}
+
+/// A request passed to a [`HandlerFn`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginRequest {
+    /// The request method.
+    pub method: Slice,
+    /// The request's origin URI, including its query, if any.
+    pub uri: Slice,
+    /// The request body, limited to the `plugin` data limit.
+    pub body: Slice,
+}
+
+/// A response filled in by a [`HandlerFn`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginResponse {
+    /// The response status code.
+    pub status: u16,
+    /// The response content type. If empty, no `Content-Type` is set.
+    pub content_type: Slice,
+    /// The response body.
+    pub body: Slice,
+    /// If set, called with the response once Rocket has copied its content
+    /// type and body, allowing the plugin to free them.
+    pub free: Option<unsafe extern "C" fn(response: *mut PluginResponse)>,
+}
+
+// SAFETY: Like `&[u8]`, a `Slice` is an immutable view of shared bytes.
+unsafe impl Send for Slice {}
+unsafe impl Sync for Slice {}
+
+impl Slice {
+    /// An empty slice.
+    pub const EMPTY: Slice = Slice { ptr: std::ptr::null(), len: 0 };
+
+    /// Returns a slice borrowing `string`.
+    pub const fn from_static(string: &'static str) -> Slice {
+        Slice { ptr: string.as_ptr(), len: string.len() }
+    }
+
+    /// Returns a slice borrowing `bytes`.
+    pub const fn from_bytes(bytes: &[u8]) -> Slice {
+        Slice { ptr: bytes.as_ptr(), len: bytes.len() }
+    }
+
+    /// Returns the bytes `self` points to.
+    ///
+    /// # Safety
+    ///
+    /// Unless `self.len` is `0`, `self.ptr` must point to `self.len`
+    /// initialized bytes that remain valid and unmodified for `'a`.
+    pub unsafe fn as_bytes<'a>(&self) -> &'a [u8] {
+        match self.len {
+            0 => &[],
+            len => std::slice::from_raw_parts(self.ptr, len),
+        }
+    }
+
+    /// Returns the string `self` points to or `None` if it is not UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// See [`Slice::as_bytes()`].
+    pub unsafe fn as_str<'a>(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.as_bytes()).ok()
+    }
+}
@@ -0,0 +1,416 @@
+//! Routes and lifecycle hooks loaded from dynamic libraries.
+//!
+//! The [`Plugins`] fairing loads _plugins_: dynamic libraries that implement
+//! the stable C ABI defined in [`abi`]. Plugins allow deployments to ship
+//! site-specific extensions without rebuilding the application binary. Each
+//! plugin declares:
+//!
+//!   * a name and version, which are logged when the plugin is loaded,
+//!   * any number of routes, which are mounted at `/`, and
+//!   * optional `on_load`, `on_liftoff`, and `on_shutdown` lifecycle hooks.
+//!
+//! Plugins are loaded during ignition, in the order they were registered. A
+//! plugin that fails to load, declares an incompatible [ABI
+//! version](abi::ABI_VERSION) or an invalid route, or whose `on_load` hook
+//! fails aborts launch. Routes declared by plugins are checked for collisions
+//! like any other route.
+//!
+//! # Configuration
+//!
+//! In addition to libraries registered via [`Plugins::library()`], a fairing
+//! created with [`Plugins::configured()`] loads the libraries listed in the
+//! `plugins` configuration parameter. Relative paths are resolved against the
+//! directory of the configuration file that sets them:
+//!
+//! ```toml
+//! [release]
+//! plugins = ["plugins/libbilling.so", "/opt/site/libbanner.so"]
+//! ```
+//!
+//! # Handling Requests
+//!
+//! Plugin handlers are synchronous. Rocket reads the request body, up to the
+//! `plugin` data [limit](crate::data::Limits) (1 MiB by default), and then
+//! calls the handler on a blocking thread. A response with an error status and
+//! an empty body invokes the corresponding error catcher. The response content
+//! type, if any, must be a valid media type.
+//!
+//! # Safety
+//!
+//! Loading a dynamic library runs arbitrary code, and Rocket can only trust
+//! that a plugin's declaration is well-formed. Plugins can thus cause
+//! undefined behavior, which is why registering them is `unsafe`. Only load
+//! plugins from trusted sources, built against the same [`abi::ABI_VERSION`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate rocket;
+//! use rocket::plugin::Plugins;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     // SAFETY: Libraries in `/opt/site` are trusted plugins.
+//!     let plugins = unsafe { Plugins::configured().library("/opt/site/libbanner.so") };
+//!     rocket::build().attach(plugins)
+//! }
+//! ```
+
+pub mod abi;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use figment::value::magic::RelativePathBuf;
+use libloading::Library;
+
+use crate::{Build, Data, Orbit, Request, Rocket, Route};
+use crate::data::ToByteUnit;
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{ContentType, Method, Status};
+use crate::route::{Handler, Outcome, RouteUri};
+use crate::trace::Trace;
+
+use self::abi::{Declaration, Slice};
+
+/// A fairing that loads [plugins](self) during ignition.
+///
+/// See the [module level docs](self) for details and an example.
+#[derive(Debug, Default)]
+pub struct Plugins {
+    sources: Vec<Source>,
+    configured: bool,
+    loaded: Mutex<Vec<Arc<Plugin>>>,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Library(PathBuf),
+    Builtin(abi::DeclareFn),
+}
+
+/// A loaded plugin.
+#[derive(Debug)]
+struct Plugin {
+    name: String,
+    version: String,
+    /// Valid for as long as `_library`, if any, is loaded.
+    declaration: &'static Declaration,
+    _library: Option<Library>,
+}
+
+#[derive(Clone)]
+struct PluginHandler {
+    plugin: Arc<Plugin>,
+    handler: abi::HandlerFn,
+}
+
+impl Plugins {
+    /// Creates a fairing that loads no plugins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::plugin::Plugins;
+    ///
+    /// let plugins = Plugins::new();
+    /// ```
+    pub fn new() -> Self {
+        Plugins::default()
+    }
+
+    /// Creates a fairing that loads the libraries listed in the `plugins`
+    /// configuration parameter, if any.
+    ///
+    /// # Safety
+    ///
+    /// Every configured library must be a trusted plugin. See the [module
+    /// level docs](self#safety).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::plugin::Plugins;
+    ///
+    /// // SAFETY: Only trusted plugins are configured.
+    /// let plugins = unsafe { Plugins::configured() };
+    /// ```
+    pub unsafe fn configured() -> Self {
+        Plugins { configured: true, ..Plugins::default() }
+    }
+
+    /// Loads the plugin library at `path`.
+    ///
+    /// # Safety
+    ///
+    /// The library at `path` must be a trusted plugin. See the [module level
+    /// docs](self#safety).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::plugin::Plugins;
+    ///
+    /// // SAFETY: `/opt/site/libbanner.so` is a trusted plugin.
+    /// let plugins = unsafe { Plugins::new().library("/opt/site/libbanner.so") };
+    /// ```
+    pub unsafe fn library<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.sources.push(Source::Library(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Loads the plugin declared by `declare`, a function linked into the
+    /// application, as if it were the [`abi::DECLARE_SYMBOL`] of a library.
+    ///
+    /// # Safety
+    ///
+    /// `declare` must implement the [`abi`] contract.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::plugin::{abi, Plugins};
+    ///
+    /// // Declared by a plugin that is statically linked into the application.
+    /// # unsafe extern "C" fn rocket_plugin_declare() -> *const abi::Declaration {
+    /// #     std::ptr::null()
+    /// # }
+    ///
+    /// // SAFETY: The linked plugin is trusted.
+    /// let plugins = unsafe { Plugins::new().builtin(rocket_plugin_declare) };
+    /// ```
+    pub unsafe fn builtin(mut self, declare: abi::DeclareFn) -> Self {
+        self.sources.push(Source::Builtin(declare));
+        self
+    }
+}
+
+impl Plugin {
+    /// Loads and validates the plugin declared by `source`, then calls its
+    /// `on_load` hook.
+    ///
+    /// # Safety
+    ///
+    /// `source` must implement the [`abi`] contract.
+    unsafe fn load(source: &Source) -> Result<Plugin, String> {
+        let (library, declare) = match source {
+            Source::Library(path) => {
+                let library = Library::new(path).map_err(|e| e.to_string())?;
+                let declare = *library.get::<abi::DeclareFn>(abi::DECLARE_SYMBOL.as_bytes())
+                    .map_err(|e| e.to_string())?;
+
+                (Some(library), declare)
+            }
+            Source::Builtin(declare) => (None, *declare),
+        };
+
+        let declaration = declare().as_ref().ok_or("plugin declaration is null")?;
+        if declaration.abi_version != abi::ABI_VERSION {
+            return Err(format!("plugin ABI version {} is incompatible with version {}",
+                declaration.abi_version, abi::ABI_VERSION));
+        }
+
+        let plugin = Plugin {
+            name: declaration.name.as_str().ok_or("plugin name is not UTF-8")?.into(),
+            version: declaration.version.as_str().ok_or("plugin version is not UTF-8")?.into(),
+            declaration,
+            _library: library,
+        };
+
+        if let Some(on_load) = declaration.on_load {
+            match on_load(declaration.ctx) {
+                0 => {},
+                code => return Err(format!("`on_load` hook failed with code {code}")),
+            }
+        }
+
+        Ok(plugin)
+    }
+
+    fn routes(self: &Arc<Self>) -> Result<Vec<Route>, String> {
+        let declarations = match self.declaration.routes_len {
+            0 => &[][..],
+            // SAFETY: The `abi` contract requires `routes` to be valid.
+            len => unsafe { std::slice::from_raw_parts(self.declaration.routes, len) },
+        };
+
+        declarations.iter()
+            .map(|declaration| {
+                // SAFETY: The `abi` contract requires `method`, `uri` to be valid.
+                let (method, uri) = unsafe {
+                    (declaration.method.as_str(), declaration.uri.as_str())
+                };
+
+                let method = method.and_then(|m| m.parse::<Method>().ok())
+                    .ok_or("route method is invalid")?;
+
+                let uri = uri.ok_or("route uri is not UTF-8")?;
+                RouteUri::try_new("/", uri)
+                    .map_err(|e| format!("route uri `{uri}` is invalid: {e}"))?;
+
+                let handler = PluginHandler { plugin: self.clone(), handler: declaration.handler };
+                let mut route = Route::new(method, uri, handler);
+                route.name = Some(format!("{}: {}", self.name, uri).into());
+                Ok(route)
+            })
+            .collect()
+    }
+
+    fn hook(&self, hook: Option<abi::HookFn>) {
+        if let Some(hook) = hook {
+            // SAFETY: The `abi` contract requires `hook` to be callable.
+            unsafe { hook(self.declaration.ctx) }
+        }
+    }
+}
+
+// SAFETY: The `abi` contract requires the declaration to be immutable and
+// `ctx` to be usable from any thread, concurrently.
+unsafe impl Send for Plugin {}
+unsafe impl Sync for Plugin {}
+
+impl PluginHandler {
+    /// Calls the handler, returning the status, content type, and body.
+    fn call(&self, method: &str, uri: &str, body: &[u8]) -> (u16, Option<ContentType>, Vec<u8>) {
+        let request = abi::PluginRequest {
+            method: Slice::from_bytes(method.as_bytes()),
+            uri: Slice::from_bytes(uri.as_bytes()),
+            body: Slice::from_bytes(body),
+        };
+
+        let mut response = abi::PluginResponse {
+            status: Status::InternalServerError.code,
+            content_type: Slice::EMPTY,
+            body: Slice::EMPTY,
+            free: None,
+        };
+
+        // SAFETY: The `abi` contract requires the handler to be callable and
+        // to fill in a valid `response`, valid until it's `free`d.
+        unsafe {
+            (self.handler)(self.plugin.declaration.ctx, &request, &mut response);
+            let content_type = response.content_type.as_str()
+                .and_then(ContentType::parse_flexible);
+
+            let (status, body) = (response.status, response.body.as_bytes().to_vec());
+            if let Some(free) = response.free {
+                free(&mut response);
+            }
+
+            (status, content_type, body)
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Handler for PluginHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let limit = req.limits().get("plugin").unwrap_or(1.mebibytes());
+        let body = match data.open(limit).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => return Outcome::Error(Status::PayloadTooLarge),
+            Err(e) => {
+                debug!(plugin = %self.plugin.name, "failed to read request body: {e}");
+                return Outcome::Error(Status::BadRequest);
+            }
+        };
+
+        let handler = self.clone();
+        let (method, uri) = (req.method().as_str(), req.uri().to_string());
+        let response = tokio::task::spawn_blocking(move || handler.call(method, &uri, &body));
+        let (status, content_type, body) = match response.await {
+            Ok(response) => response,
+            Err(e) => {
+                error!(plugin = %self.plugin.name, "plugin handler failed: {e}");
+                return Outcome::Error(Status::InternalServerError);
+            }
+        };
+
+        let Some(status) = Status::from_code(status) else {
+            error!(plugin = %self.plugin.name, status, "plugin responded with invalid status");
+            return Outcome::Error(Status::InternalServerError);
+        };
+
+        let class = status.class();
+        if body.is_empty() && (class.is_client_error() || class.is_server_error()) {
+            return Outcome::Error(status);
+        }
+
+        match content_type {
+            Some(content_type) => Outcome::from(req, (status, (content_type, body))),
+            None => Outcome::from(req, (status, body)),
+        }
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Plugins {
+    fn info(&self) -> Info {
+        Info {
+            name: "Plugins",
+            kind: Kind::Ignite | Kind::Liftoff | Kind::Shutdown,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut sources = self.sources.clone();
+        if self.configured {
+            match rocket.figment().extract_inner::<Vec<RelativePathBuf>>("plugins") {
+                Ok(paths) => sources.extend(paths.iter().map(|p| Source::Library(p.relative()))),
+                Err(e) if e.missing() => {},
+                Err(e) => {
+                    e.trace_error();
+                    return Err(rocket);
+                }
+            }
+        }
+
+        let mut routes = vec![];
+        let mut loaded = vec![];
+        for source in &sources {
+            // SAFETY: Sources were registered by `unsafe` methods whose
+            // contracts require them to be valid plugins.
+            let plugin = unsafe { Plugin::load(source) }
+                .map(Arc::new)
+                .and_then(|plugin| Ok((plugin.routes()?, plugin)));
+
+            match plugin {
+                Ok((plugin_routes, plugin)) => {
+                    info!(plugin = %plugin.name, version = %plugin.version, %source,
+                        routes = plugin_routes.len(), "plugin loaded");
+
+                    routes.extend(plugin_routes);
+                    loaded.push(plugin);
+                }
+                Err(e) => {
+                    error!(%source, "failed to load plugin: {e}");
+                    return Err(rocket);
+                }
+            }
+        }
+
+        *self.loaded.lock() = loaded;
+        Ok(rocket.mount("/", routes))
+    }
+
+    async fn on_liftoff(&self, _: &Rocket<Orbit>) {
+        let plugins = self.loaded.lock().clone();
+        plugins.iter().for_each(|plugin| plugin.hook(plugin.declaration.on_liftoff));
+    }
+
+    async fn on_shutdown(&self, _: &Rocket<Orbit>) {
+        let plugins = self.loaded.lock().clone();
+        plugins.iter().for_each(|plugin| plugin.hook(plugin.declaration.on_shutdown));
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Library(path) => path.display().fmt(f),
+            Source::Builtin(_) => "builtin".fmt(f),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! Typed user preferences persisted in a signed cookie.
+//!
+//! The [`Prefs`] request guard retrieves a small preferences structure, such
+//! as a user's theme, locale, or page size, from a cookie, and persists it
+//! back via [`Prefs::save()`]. Preferences are serialized as JSON and signed
+//! with the application's [`SecretKey`](crate::config::SecretKey), so they can
+//! be read but not tampered with by clients. A missing, tampered, or otherwise
+//! invalid cookie yields the type's [`Default`] value.
+//!
+//! Types opt in by implementing [`Preferences`], which names the cookie and
+//! versions the schema. When the schema changes in an incompatible way, bump
+//! [`Preferences::VERSION`] and implement [`Preferences::migrate()`] to
+//! upgrade cookies written with previous versions.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::prefs::{Prefs, Preferences};
+//! use rocket::serde::{Serialize, Deserialize};
+//!
+//! #[derive(Default, Serialize, Deserialize)]
+//! # #[serde(crate = "rocket::serde")]
+//! struct Settings {
+//!     theme: Option<String>,
+//!     page_size: Option<u32>,
+//! }
+//!
+//! impl Preferences for Settings {
+//!     const COOKIE: &'static str = "settings";
+//! }
+//!
+//! #[get("/items")]
+//! fn items(settings: Prefs<'_, Settings>) -> String {
+//!     format!("showing {} items", settings.page_size.unwrap_or(20))
+//! }
+//!
+//! #[post("/theme/<theme>")]
+//! fn theme(theme: &str, mut settings: Prefs<'_, Settings>) {
+//!     settings.theme = Some(theme.into());
+//!     settings.save();
+//! }
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use hmac::{Mac, Hmac};
+use sha2::Sha256;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use time::Duration;
+
+use crate::config::SecretKey;
+use crate::http::{Cookie, CookieJar};
+use crate::request::{FromRequest, Outcome, Request};
+
+/// A type persisted as user preferences by [`Prefs`].
+///
+/// See the [module level docs](self) for details.
+///
+/// # Migrations
+///
+/// Cookies record the [`VERSION`](Self::VERSION) of the schema they were
+/// written with. When a cookie with an older version is read, its JSON value
+/// is passed to [`migrate()`](Self::migrate()), which returns the value in
+/// the current schema, or `None` to discard it. Cookies with a newer version,
+/// written by a newer deployment of the application, are discarded.
+///
+/// ```rust
+/// use rocket::prefs::Preferences;
+/// use rocket::serde::{Serialize, Deserialize, json::{Value, json}};
+///
+/// #[derive(Default, Serialize, Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Settings {
+///     // In version 1, this was `dark: bool`.
+///     theme: String,
+/// }
+///
+/// impl Preferences for Settings {
+///     const VERSION: u32 = 2;
+///
+///     fn migrate(version: u32, value: Value) -> Option<Value> {
+///         match version {
+///             1 => match value["dark"].as_bool()? {
+///                 true => Some(json!({ "theme": "dark" })),
+///                 false => Some(json!({ "theme": "light" })),
+///             },
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait Preferences: Serialize + DeserializeOwned + Default + Send + Sync + 'static {
+    /// The name of the cookie.
+    ///
+    /// **default: `"prefs"`**
+    const COOKIE: &'static str = "prefs";
+
+    /// The version of the current schema.
+    ///
+    /// **default: `1`**
+    const VERSION: u32 = 1;
+
+    /// Migrates `value`, written with schema `version`, to the current schema.
+    /// Returning `None` discards the value in favor of the default.
+    ///
+    /// The default implementation discards all values.
+    fn migrate(version: u32, value: Value) -> Option<Value> {
+        let _ = (version, value);
+        None
+    }
+}
+
+/// Request guard for typed preferences stored in a signed cookie.
+///
+/// Dereferences to the preferences. Changes only persist once
+/// [`Prefs::save()`] is called. The guard never fails: if the cookie is
+/// missing or invalid, the preferences are `T::default()`.
+///
+/// See the [module level docs](self) for details and an example.
+pub struct Prefs<'r, T: Preferences> {
+    value: T,
+    jar: &'r CookieJar<'r>,
+    key: &'r SecretKey,
+}
+
+/// Domain separation for signatures keyed by the secret key.
+const CONTEXT: &[u8] = b"rocket prefs cookie\n";
+
+fn mac(key: &SecretKey, name: &str, content: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.key.signing())
+        .expect("HMAC accepts any key length");
+
+    mac.update(CONTEXT);
+    mac.update(name.as_bytes());
+    mac.update(b"=");
+    mac.update(content.as_bytes());
+    mac
+}
+
+/// Encodes `value` as `{signature}{version}:{json}`, where `signature` is the
+/// hex-encoded HMAC of the cookie's name and the rest of its value.
+fn encode<T: Preferences>(key: &SecretKey, value: &T) -> serde_json::Result<String> {
+    let content = format!("{}:{}", T::VERSION, serde_json::to_string(value)?);
+    let signature = mac(key, T::COOKIE, &content).finalize().into_bytes();
+    let mut hex = [0u8; 64];
+    let hex = binascii::bin2hex(&signature, &mut hex).expect("32 bytes fit in 64 hex digits");
+    let hex = std::str::from_utf8(hex).expect("hex is UTF-8");
+    Ok(format!("{hex}{content}"))
+}
+
+/// Decodes and verifies a cookie value produced by [`encode()`], migrating it
+/// if needed. Returns `None` if it is invalid.
+fn decode<T: Preferences>(key: &SecretKey, cookie: &str) -> Option<T> {
+    let (signature, content) = (cookie.get(..64)?, cookie.get(64..)?);
+    let mut bytes = [0u8; 32];
+    let signature = binascii::hex2bin(signature.as_bytes(), &mut bytes).ok()?;
+    mac(key, T::COOKIE, content).verify_slice(signature).ok()?;
+
+    let (version, json) = content.split_once(':')?;
+    let version = version.parse::<u32>().ok()?;
+    if version == T::VERSION {
+        return serde_json::from_str(json).ok();
+    }
+
+    if version > T::VERSION {
+        debug!(cookie = T::COOKIE, version, "discarding preferences from newer schema");
+        return None;
+    }
+
+    let value = serde_json::from_str(json).ok()?;
+    T::migrate(version, value).and_then(|value| serde_json::from_value(value).ok())
+}
+
+impl<'r, T: Preferences> Prefs<'r, T> {
+    /// Persists the current preferences in the cookie.
+    ///
+    /// The cookie is set for a year and, as with all cookies added via
+    /// [`CookieJar::add()`], defaults to a `path` of `/` and a `SameSite` of
+    /// `Strict`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::serde::{Serialize, Deserialize};
+    /// use rocket::prefs::{Prefs, Preferences};
+    ///
+    /// #[derive(Default, Serialize, Deserialize)]
+    /// # #[serde(crate = "rocket::serde")]
+    /// struct Locale { lang: Option<String> }
+    ///
+    /// impl Preferences for Locale { }
+    ///
+    /// #[post("/lang/<lang>")]
+    /// fn lang(lang: &str, mut locale: Prefs<'_, Locale>) {
+    ///     locale.lang = Some(lang.into());
+    ///     locale.save();
+    /// }
+    /// ```
+    pub fn save(&self) {
+        match encode(self.key, &self.value) {
+            Ok(content) => {
+                let cookie = Cookie::build((T::COOKIE, content))
+                    .max_age(Duration::days(365))
+                    .build();
+
+                self.jar.add(cookie);
+            }
+            Err(e) => error!(cookie = T::COOKIE, "failed to serialize preferences: {e}"),
+        }
+    }
+
+    /// Resets the preferences to their default and removes the cookie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::serde::{Serialize, Deserialize};
+    /// use rocket::prefs::{Prefs, Preferences};
+    ///
+    /// #[derive(Default, Serialize, Deserialize)]
+    /// # #[serde(crate = "rocket::serde")]
+    /// struct Settings { theme: Option<String> }
+    ///
+    /// impl Preferences for Settings { }
+    ///
+    /// #[post("/settings/reset")]
+    /// fn reset(mut settings: Prefs<'_, Settings>) {
+    ///     settings.reset();
+    /// }
+    /// ```
+    pub fn reset(&mut self) {
+        self.value = T::default();
+        self.jar.remove(T::COOKIE);
+    }
+
+    /// Returns the preferences, consuming `self`.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Preferences> Deref for Prefs<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Preferences> DerefMut for Prefs<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Preferences + fmt::Debug> fmt::Debug for Prefs<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Prefs").field(&self.value).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: Preferences> FromRequest<'r> for Prefs<'r, T> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let key = &req.rocket().config().secret_key;
+        let value = req.cookies().get(T::COOKIE)
+            .and_then(|cookie| decode::<T>(key, cookie.value()))
+            .unwrap_or_default();
+
+        Outcome::Success(Prefs { value, jar: req.cookies(), key })
+    }
+}
@@ -0,0 +1,500 @@
+//! Per-route execution profiling for debug builds.
+//!
+//! The [`Profiler`] fairing records, for every route, how long each request
+//! guard and data guard took to resolve, how long the handler ran, and how
+//! long the handler's responder took to produce a response. Samples are
+//! aggregated across requests into a [`Report`] of percentiles, which can be
+//! retrieved via [`Profiler::report()`] or viewed as an HTML page served by
+//! the routes in [`Profiler::routes()`]. This helps find, say, which guard is
+//! slowing down a hot route.
+//!
+//! Profiling is only performed in debug builds: in release builds, the
+//! instrumentation compiles to nothing and reports are empty.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::profile::Profiler;
+//!
+//! #[get("/")]
+//! fn index() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index])
+//!         .mount("/profile", Profiler::routes())
+//!         .attach(Profiler::new())
+//! }
+//! ```
+//!
+//! `GET /profile` then renders a table like:
+//!
+//! | route | stage          | count | mean   | p50    | p90    | p99    | max    |
+//! |-------|----------------|-------|--------|--------|--------|--------|--------|
+//! | index | handler        | 120   | 0.01ms | 0.01ms | 0.02ms | 0.04ms | 0.09ms |
+//! | index | responder      | 120   | 0.00ms | 0.00ms | 0.00ms | 0.01ms | 0.02ms |
+//!
+//! # Sampling
+//!
+//! Each stage of each route retains its most recent samples, 1024 by default,
+//! from which percentiles are computed. The count of samples is not bounded.
+//! The number retained can be changed via [`Profiler::samples()`].
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::{Rocket, Build, Request, Response, Route, Data};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::http::{Method, ContentType, RawStr, Status};
+use crate::route::{Handler, Outcome};
+
+/// A fairing that profiles route execution in debug builds.
+///
+/// See the [module level docs](self) for details.
+#[derive(Clone)]
+pub struct Profiler {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    samples: usize,
+    routes: Mutex<HashMap<RouteKey, RouteSamples>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RouteKey {
+    name: Option<String>,
+    method: Option<Method>,
+    uri: String,
+}
+
+#[derive(Default)]
+struct RouteSamples {
+    guards: Vec<(&'static str, Samples)>,
+    handler: Samples,
+    responder: Samples,
+}
+
+#[derive(Default)]
+struct Samples {
+    count: u64,
+    recent: VecDeque<Duration>,
+}
+
+/// A stage of route execution.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum Stage {
+    Guard(&'static str),
+    Handler,
+    Responder,
+}
+
+/// Samples recorded during a single request, merged on response.
+#[derive(Default)]
+struct Recorded(Mutex<Vec<(RouteKey, Stage, Duration)>>);
+
+/// Aggregated execution times of all profiled routes.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// The profiled routes, ordered by total handler time, descending.
+    pub routes: Vec<RouteReport>,
+}
+
+/// Aggregated execution times of a single route.
+#[derive(Debug, Clone)]
+pub struct RouteReport {
+    /// The route's name, if it has one.
+    pub name: Option<String>,
+    /// The route's method, or `None` if it matches any method.
+    pub method: Option<Method>,
+    /// The route's URI.
+    pub uri: String,
+    /// Request and data guard resolution times, by parameter name, in
+    /// declaration order.
+    pub guards: Vec<(String, Summary)>,
+    /// Handler execution times, if the handler has run.
+    pub handler: Option<Summary>,
+    /// Responder execution times, if a responder has run.
+    pub responder: Option<Summary>,
+}
+
+/// Summary statistics of a set of samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    /// The number of samples ever recorded.
+    pub count: u64,
+    /// The mean of the retained samples.
+    pub mean: Duration,
+    /// The median of the retained samples.
+    pub p50: Duration,
+    /// The 90th percentile of the retained samples.
+    pub p90: Duration,
+    /// The 99th percentile of the retained samples.
+    pub p99: Duration,
+    /// The maximum of the retained samples.
+    pub max: Duration,
+}
+
+impl Profiler {
+    /// The default number of samples retained per stage of each route.
+    pub const SAMPLES: usize = 1024;
+
+    /// Returns a new profiler retaining [`Profiler::SAMPLES`] samples per
+    /// stage of each route.
+    pub fn new() -> Self {
+        Profiler {
+            shared: Arc::new(Shared { samples: Self::SAMPLES, routes: Mutex::default() }),
+        }
+    }
+
+    /// Sets the number of samples retained per stage of each route.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::profile::Profiler;
+    ///
+    /// let profiler = Profiler::new().samples(256);
+    /// ```
+    pub fn samples(self, samples: usize) -> Self {
+        assert!(samples > 0, "profiler must retain at least one sample");
+        Profiler {
+            shared: Arc::new(Shared { samples, routes: Mutex::default() }),
+        }
+    }
+
+    /// Returns a report of the samples recorded so far.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::State;
+    /// use rocket::profile::Profiler;
+    ///
+    /// #[get("/slowest")]
+    /// fn slowest(profiler: &State<Profiler>) -> Option<String> {
+    ///     let report = profiler.report();
+    ///     let route = report.routes.first()?;
+    ///     Some(format!("{}: {:?}", route.uri, route.handler?.p99))
+    /// }
+    /// ```
+    pub fn report(&self) -> Report {
+        let routes = self.shared.routes.lock();
+        let mut report: Vec<_> = routes.iter()
+            .map(|(key, samples)| RouteReport {
+                name: key.name.clone(),
+                method: key.method,
+                uri: key.uri.clone(),
+                guards: samples.guards.iter()
+                    .filter_map(|(name, s)| Some((name.to_string(), s.summary()?)))
+                    .collect(),
+                handler: samples.handler.summary(),
+                responder: samples.responder.summary(),
+            })
+            .collect();
+
+        let total = |r: &RouteReport| {
+            r.handler.map_or(0.0, |s| s.mean.as_secs_f64() * s.count as f64)
+        };
+
+        report.sort_by(|a, b| total(b).total_cmp(&total(a)).then_with(|| a.uri.cmp(&b.uri)));
+        Report { routes: report }
+    }
+
+    /// Discards all samples recorded so far.
+    pub fn reset(&self) {
+        self.shared.routes.lock().clear();
+    }
+
+    /// Returns a route that serves the [`Report`] of the attached profiler
+    /// as an HTML page at `/`, or as plain text if the request prefers it.
+    /// Requests fail with `404 Not Found` if no profiler is attached.
+    ///
+    /// The report reveals the application's routes and performance: only
+    /// mount the route in development.
+    pub fn routes() -> Vec<Route> {
+        let mut route = Route::new(Method::Get, "/", ReportHandler);
+        route.name = Some("Profiler: report".into());
+        vec![route]
+    }
+
+    fn record(&self, key: RouteKey, stage: Stage, duration: Duration) {
+        let mut routes = self.shared.routes.lock();
+        let route = routes.entry(key).or_default();
+        let samples = match stage {
+            Stage::Handler => &mut route.handler,
+            Stage::Responder => &mut route.responder,
+            Stage::Guard(name) => match route.guards.iter().position(|(n, _)| *n == name) {
+                Some(i) => &mut route.guards[i].1,
+                None => {
+                    route.guards.push((name, Samples::default()));
+                    &mut route.guards.last_mut().expect("just pushed").1
+                }
+            },
+        };
+
+        samples.push(duration, self.shared.samples);
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Profiler::new()
+    }
+}
+
+impl fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profiler")
+            .field("samples", &self.shared.samples)
+            .field("routes", &self.shared.routes.lock().len())
+            .finish()
+    }
+}
+
+impl Samples {
+    fn push(&mut self, duration: Duration, capacity: usize) {
+        if self.recent.len() >= capacity {
+            self.recent.pop_front();
+        }
+
+        self.count += 1;
+        self.recent.push_back(duration);
+    }
+
+    fn summary(&self) -> Option<Summary> {
+        if self.recent.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<Duration> = self.recent.iter().copied().collect();
+        sorted.sort_unstable();
+
+        // Nearest-rank percentile.
+        let percentile = |p: usize| sorted[((sorted.len() * p + 99) / 100).max(1) - 1];
+        let sum: Duration = sorted.iter().sum();
+        Some(Summary {
+            count: self.count,
+            mean: sum / sorted.len() as u32,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+impl RouteKey {
+    fn of(route: &Route) -> Self {
+        RouteKey {
+            name: route.name.as_ref().map(|name| name.to_string()),
+            method: route.method,
+            uri: route.uri.to_string(),
+        }
+    }
+}
+
+/// Marks the start of a stage of route execution. Used by code generation.
+#[doc(hidden)]
+pub struct Mark(Option<Instant>);
+
+impl Mark {
+    #[inline(always)]
+    pub fn now() -> Self {
+        Mark(cfg!(debug_assertions).then(Instant::now))
+    }
+
+    /// Records the time elapsed since `self` for `stage` of the route
+    /// currently handling `req`.
+    #[inline(always)]
+    pub fn record(self, req: &Request<'_>, stage: Stage) {
+        #[cfg(debug_assertions)] {
+            let profiler = req.rocket().state::<Profiler>();
+            let (Some(start), Some(_), Some(route)) = (self.0, profiler, req.route()) else {
+                return;
+            };
+
+            let sample = (RouteKey::of(route), stage, start.elapsed());
+            req.local_cache(Recorded::default).0.lock().push(sample);
+        }
+
+        #[cfg(not(debug_assertions))]
+        let _ = (self, req, stage);
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Profiler {
+    fn info(&self) -> Info {
+        Info { name: "Profiler", kind: Kind::Ignite | Kind::Response | Kind::Singleton }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if !cfg!(debug_assertions) {
+            warn!("route profiling is only performed in debug builds");
+        }
+
+        Ok(rocket.manage(self.clone()))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, _: &mut Response<'r>) {
+        let recorded = std::mem::take(&mut *req.local_cache(Recorded::default).0.lock());
+        for (key, stage, duration) in recorded {
+            self.record(key, stage, duration);
+        }
+    }
+}
+
+impl Summary {
+    fn write_row<W: Write>(&self, w: &mut W, cells: &[&str], sep: &str) -> fmt::Result {
+        let ms = |d: Duration| format!("{:.2}ms", d.as_secs_f64() * 1000.0);
+        let stats = [ms(self.mean), ms(self.p50), ms(self.p90), ms(self.p99), ms(self.max)];
+        for cell in cells {
+            write!(w, "{cell}{sep}")?;
+        }
+
+        write!(w, "{}{sep}", self.count)?;
+
+        write!(w, "{}", stats.join(sep))
+    }
+}
+
+impl RouteReport {
+    fn label(&self) -> String {
+        let method = self.method.map_or("*", |m| m.as_str());
+        match &self.name {
+            Some(name) => format!("{name} ({method} {})", self.uri),
+            None => format!("{method} {}", self.uri),
+        }
+    }
+
+    fn stages(&self) -> impl Iterator<Item = (String, Summary)> + '_ {
+        let guards = self.guards.iter().map(|(name, s)| (format!("guard `{name}`"), *s));
+        guards.chain(self.handler.map(|s| ("handler".to_string(), s)))
+            .chain(self.responder.map(|s| ("responder".to_string(), s)))
+    }
+}
+
+const HEADER: [&str; 8] = ["route", "stage", "count", "mean", "p50", "p90", "p99", "max"];
+
+impl Report {
+    /// Renders the report as an HTML page.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n",
+            "    <meta charset=\"utf-8\">\n",
+            "    <meta name=\"color-scheme\" content=\"light dark\">\n",
+            "    <title>Route Profile</title>\n",
+            "    <style>td, th { padding: 0.2em 0.8em; text-align: right; }\n",
+            "    td:nth-child(-n+2), th:nth-child(-n+2) { text-align: left; }</style>\n",
+            "</head>\n<body>\n    <h1>Route Profile</h1>\n",
+        ));
+
+        if self.routes.is_empty() {
+            html.push_str("    <p>No samples have been recorded.</p>\n");
+        } else {
+            html.push_str("    <table>\n        <tr><th>");
+            html.push_str(&HEADER.join("</th><th>"));
+            html.push_str("</th></tr>\n");
+            for route in &self.routes {
+                let label = route.label();
+                for (stage, summary) in route.stages() {
+                    html.push_str("        <tr><td>");
+                    let (label, stage) = (RawStr::new(&label), RawStr::new(&stage));
+                    let cells = [&*label.html_escape(), &*stage.html_escape()];
+                    let _ = summary.write_row(&mut html, &cells, "</td><td>");
+                    html.push_str("</td></tr>\n");
+                }
+            }
+
+            html.push_str("    </table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Renders the report as tab-separated text with a header row.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", HEADER.join("\t"))?;
+        for route in &self.routes {
+            let label = route.label();
+            for (stage, summary) in route.stages() {
+                summary.write_row(f, &[&label, &stage], "\t")?;
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ReportHandler;
+
+#[crate::async_trait]
+impl Handler for ReportHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let Some(profiler) = req.rocket().state::<Profiler>() else {
+            return Outcome::forward(data, Status::NotFound);
+        };
+
+        let report = profiler.report();
+        let text = req.accept().map_or(false, |a| a.preferred().is_plain());
+        match text {
+            true => Outcome::from(req, report.to_string()),
+            false => Outcome::from(req, (ContentType::HTML, report.to_html())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_percentiles() {
+        let mut samples = Samples::default();
+        for ms in (1..=100).rev() {
+            samples.push(Duration::from_millis(ms), 1024);
+        }
+
+        let summary = samples.summary().unwrap();
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p90, Duration::from_millis(90));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.max, Duration::from_millis(100));
+        assert_eq!(summary.mean, Duration::from_micros(50_500));
+    }
+
+    #[test]
+    fn samples_are_bounded() {
+        let mut samples = Samples::default();
+        for ms in 1..=10 {
+            samples.push(Duration::from_millis(ms), 4);
+        }
+
+        let summary = samples.summary().unwrap();
+        assert_eq!(summary.count, 10);
+        assert_eq!(summary.p50, Duration::from_millis(8));
+        assert_eq!(summary.max, Duration::from_millis(10));
+        assert!(Samples::default().summary().is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use std::any::Any;
+use std::cell::UnsafeCell;
+use std::fmt;
+
+use parking_lot::{RawMutex, lock_api::RawMutex as _};
+
+/// A request-local arena.
+///
+/// An `Arena` owns values allocated while handling a request and frees them,
+/// all at once, when the request is dropped. References to allocated values
+/// live as long as the request itself, so guards and handlers can store
+/// borrow-scoped parsing results, such as decoded strings, without leaking
+/// them or threading owned values through their types. The arena for a
+/// request is retrieved via [`Request::arena()`](crate::Request::arena()).
+///
+/// Strings and byte slices are copied into shared, bump-allocated chunks,
+/// avoiding an allocation per value. Other values are individually boxed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest};
+///
+/// /// The lowercased, comma-separated values of the `X-Tags` header.
+/// struct Tags<'r>(Vec<&'r str>);
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for Tags<'r> {
+///     type Error = std::convert::Infallible;
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+///         let raw = req.headers().get_one("X-Tags").unwrap_or_default();
+///         let tags = req.arena().alloc_str(&raw.to_lowercase());
+///         request::Outcome::Success(Tags(tags.split(',').map(|t| t.trim()).collect()))
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index(tags: Tags<'_>) -> String {
+///     tags.0.join(" ")
+/// }
+/// ```
+pub struct Arena {
+    inner: UnsafeCell<Inner>,
+    mutex: RawMutex,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Byte chunks. Chunks are never reallocated: they are only extended
+    /// within their initial capacity, so their contents have stable addresses.
+    chunks: Vec<Vec<u8>>,
+    /// Boxed values. Boxes have stable addresses.
+    values: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl Arena {
+    /// The capacity of the first byte chunk. Each subsequent chunk doubles in
+    /// capacity, up to `MAX_CHUNK`.
+    const MIN_CHUNK: usize = 512;
+
+    /// The maximum capacity of a byte chunk. Larger slices get a chunk of
+    /// their own.
+    const MAX_CHUNK: usize = 64 * 1024;
+
+    /// Creates a new, empty arena. No memory is allocated until a value is.
+    pub(crate) fn new() -> Arena {
+        Arena { inner: UnsafeCell::new(Inner::default()), mutex: RawMutex::INIT }
+    }
+
+    /// Runs `f` with exclusive access to the arena's internals.
+    fn with<R>(&self, f: impl FnOnce(&mut Inner) -> R) -> R {
+        struct Unlock<'a>(&'a RawMutex);
+
+        impl Drop for Unlock<'_> {
+            fn drop(&mut self) {
+                // SAFETY: The mutex was locked by the current context below.
+                unsafe { self.0.unlock() }
+            }
+        }
+
+        self.mutex.lock();
+        let _unlock = Unlock(&self.mutex);
+
+        // SAFETY: The mutex ensures that no other reference to `Inner` exists.
+        // References returned by the arena point into chunk or box contents,
+        // never into `Inner` itself, so they don't alias this one.
+        f(unsafe { &mut *self.inner.get() })
+    }
+
+    /// Moves `value` into the arena, returning a reference to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let arena = request.arena();
+    /// let numbers = arena.alloc(vec![1, 2, 3]);
+    /// let total = arena.alloc(numbers.iter().sum::<u32>());
+    /// assert_eq!(*total, 6);
+    /// ```
+    pub fn alloc<T: Send + Sync + 'static>(&self, value: T) -> &T {
+        let boxed = Box::new(value);
+        let ptr: *const T = &*boxed;
+        self.with(|inner| inner.values.push(boxed));
+
+        // SAFETY: The box is owned by `self` and is never dropped or moved
+        // out of while `self` lives. Moving the `Box` within `values` doesn't
+        // move its heap allocation, so `ptr` remains valid for `&self`.
+        unsafe { &*ptr }
+    }
+
+    /// Copies `bytes` into the arena, returning a reference to the copy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let bytes = request.arena().alloc_bytes(b"hello");
+    /// assert_eq!(bytes, b"hello");
+    /// ```
+    pub fn alloc_bytes(&self, bytes: &[u8]) -> &[u8] {
+        if bytes.is_empty() {
+            return &[];
+        }
+
+        let ptr = self.with(|inner| {
+            let fits = inner.chunks.last()
+                .is_some_and(|chunk| chunk.capacity() - chunk.len() >= bytes.len());
+
+            if !fits {
+                let last = inner.chunks.last().map_or(0, |chunk| chunk.capacity());
+                let capacity = (last * 2).clamp(Self::MIN_CHUNK, Self::MAX_CHUNK);
+                inner.chunks.push(Vec::with_capacity(capacity.max(bytes.len())));
+            }
+
+            let chunk = inner.chunks.last_mut().expect("non-empty chunks");
+            let start = chunk.len();
+            chunk.extend_from_slice(bytes);
+            debug_assert!(chunk.len() <= chunk.capacity());
+            chunk[start..].as_ptr()
+        });
+
+        // SAFETY: `bytes.len()` bytes were just copied to `ptr`. The chunk is
+        // owned by `self`, is never dropped while `self` lives, and is only
+        // extended within its capacity, so it is never reallocated and its
+        // existing contents are never modified: `ptr` remains valid and the
+        // slice immutable for `&self`.
+        unsafe { std::slice::from_raw_parts(ptr, bytes.len()) }
+    }
+
+    /// Copies `string` into the arena, returning a reference to the copy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let name = request.arena().alloc_str(&"BOB".to_lowercase());
+    /// assert_eq!(name, "bob");
+    /// ```
+    pub fn alloc_str(&self, string: &str) -> &str {
+        let bytes = self.alloc_bytes(string.as_bytes());
+
+        // SAFETY: `bytes` is an exact copy of a valid `str`.
+        unsafe { std::str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Returns the number of bytes the arena has reserved for strings and
+    /// byte slices, excluding individually boxed values.
+    pub fn reserved(&self) -> usize {
+        self.with(|inner| inner.chunks.iter().map(|chunk| chunk.capacity()).sum())
+    }
+}
+
+// SAFETY: All access to `inner` is serialized by `mutex`. Values stored in the
+// arena are `Send + Sync`, and byte chunks are plain data.
+unsafe impl Sync for Arena {}
+
+impl fmt::Debug for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|inner| f.debug_struct("Arena")
+            .field("chunks", &inner.chunks.len())
+            .field("values", &inner.values.len())
+            .finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arena;
+
+    #[test]
+    fn test_references_survive_growth() {
+        let arena = Arena::new();
+        let strings: Vec<&str> = (0..10_000).map(|i| arena.alloc_str(&i.to_string())).collect();
+        let values: Vec<&u64> = (0..1_000).map(|i| arena.alloc(i as u64)).collect();
+        let big = arena.alloc_bytes(&vec![7; Arena::MAX_CHUNK * 2]);
+
+        for (i, string) in strings.iter().enumerate() {
+            assert_eq!(*string, i.to_string());
+        }
+
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(**value, i as u64);
+        }
+
+        assert!(big.iter().all(|b| *b == 7));
+        assert_eq!(arena.alloc_str(""), "");
+    }
+
+    #[test]
+    fn test_chunks_are_shared() {
+        let arena = Arena::new();
+        assert_eq!(arena.reserved(), 0);
+
+        arena.alloc_str("a");
+        arena.alloc_str("b");
+        assert_eq!(arena.reserved(), Arena::MIN_CHUNK);
+    }
+}
@@ -0,0 +1,107 @@
+use std::convert::Infallible;
+use std::fmt;
+
+use crate::request::{self, Request, FromRequest, ConnectionMeta, ConnectionState};
+use crate::listener::{Endpoint, TlsInfo};
+use crate::outcome::Outcome;
+
+/// A request guard for details of the connection a request arrived on.
+///
+/// `ConnectionInfo` exposes transport-level information populated by the
+/// listener that accepted the connection: the remote and local endpoints and,
+/// for TLS connections, the negotiated ALPN protocol, TLS version, cipher
+/// suite, and requested server name (SNI). With the `mtls` feature enabled, the
+/// peer's certificate chain is also available.
+///
+/// Any piece of information the listener doesn't provide is `None`. In
+/// particular, requests dispatched via a [local client](crate::local) have no
+/// local endpoint and no TLS session unless one is set with
+/// [`LocalRequest::tls_info()`](crate::local::blocking::LocalRequest::tls_info()).
+///
+/// The guard never fails. Fairings can retrieve the same information via
+/// [`Request::connection_info()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::ConnectionInfo;
+///
+/// #[get("/")]
+/// fn index(conn: ConnectionInfo<'_>) -> String {
+///     match conn.tls() {
+///         Some(tls) => format!("TLS via {:?}", tls.alpn),
+///         None => "plaintext".into(),
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConnectionInfo<'r> {
+    meta: &'r ConnectionMeta,
+}
+
+impl<'r> ConnectionInfo<'r> {
+    pub(crate) fn new(meta: &'r ConnectionMeta) -> Self {
+        ConnectionInfo { meta }
+    }
+
+    /// The remote endpoint of the connection, if known. This is the same
+    /// endpoint as [`Request::remote()`].
+    pub fn remote(&self) -> Option<&'r Endpoint> {
+        self.meta.peer_endpoint.as_ref()
+    }
+
+    /// The local endpoint the connection was accepted on, if known.
+    pub fn local(&self) -> Option<&'r Endpoint> {
+        self.meta.local_endpoint.as_ref()
+    }
+
+    /// Details of the TLS session, if the connection is secured by TLS.
+    pub fn tls(&self) -> Option<&'r TlsInfo> {
+        self.meta.tls.as_deref()
+    }
+
+    /// Whether the connection is secured by TLS.
+    pub fn is_tls(&self) -> bool {
+        self.meta.tls.is_some()
+    }
+
+    /// The state scoped to the connection, shared by all requests on the
+    /// connection. See [`ConnectionState`] for details.
+    pub fn state(&self) -> &'r ConnectionState {
+        &self.meta.state
+    }
+
+    /// The negotiated ALPN protocol, such as `h2`, if any.
+    pub fn alpn(&self) -> Option<&'r str> {
+        self.tls()?.alpn.as_deref()
+    }
+
+    /// The DER-encoded certificate chain presented by the peer, if any, in
+    /// the order it appears in the TLS protocol.
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    pub fn peer_certificates(&self) -> Option<&'r [crate::mtls::CertificateDer<'static>]> {
+        self.meta.peer_certs.as_deref().map(|certs| certs.inner())
+    }
+}
+
+impl fmt::Debug for ConnectionInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionInfo")
+            .field("remote", &self.remote())
+            .field("local", &self.local())
+            .field("tls", &self.tls())
+            .field("peer_certs", &self.meta.peer_certs.is_some())
+            .finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for ConnectionInfo<'r> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Infallible> {
+        Outcome::Success(req.connection_info())
+    }
+}
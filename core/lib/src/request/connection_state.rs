@@ -0,0 +1,153 @@
+use std::fmt;
+
+use state::TypeMap;
+
+/// Typed values scoped to a client connection, keyed by their type.
+///
+/// Every connection accepted by Rocket has its own `ConnectionState`, shared
+/// by all of the requests that arrive on the connection and dropped when the
+/// connection closes. Like [`Extensions`](crate::request::Extensions),
+/// `ConnectionState` stores at most one value of any given type, but its
+/// values outlive any single request. This makes it suitable for state that
+/// spans requests on a keep-alive or HTTP/2 connection: per-connection request
+/// counters for throttling, metrics, or resumable protocol state.
+///
+/// A connection's state is retrieved via [`ConnectionInfo::state()`], making
+/// it available to request guards, handlers, and request and response
+/// fairings, and to [connection fairings](crate::fairing::Fairing#connection)
+/// when a connection opens and closes. Requests dispatched via a [local
+/// client](crate::local) each have their own, new connection state.
+///
+/// [`ConnectionInfo::state()`]: crate::request::ConnectionInfo::state()
+///
+/// # Example
+///
+/// A request guard that fails after 100 requests on the same connection:
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use rocket::request::{self, Request, FromRequest};
+/// use rocket::http::Status;
+///
+/// struct Throttled;
+///
+/// #[derive(Default)]
+/// struct RequestCount(AtomicUsize);
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for Throttled {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         let state = req.connection_info().state();
+///         let count = state.get_or_insert_with(RequestCount::default);
+///         match count.0.fetch_add(1, Ordering::Relaxed) {
+///             0..=99 => request::Outcome::Success(Throttled),
+///             _ => request::Outcome::Error((Status::TooManyRequests, ())),
+///         }
+///     }
+/// }
+/// ```
+pub struct ConnectionState(TypeMap![Send + Sync]);
+
+impl ConnectionState {
+    /// Creates a new, empty connection state.
+    pub(crate) fn new() -> ConnectionState {
+        ConnectionState(<TypeMap![Send + Sync]>::new())
+    }
+
+    /// Inserts `value` if no value of type `T` is present. Returns `true` if
+    /// `value` was inserted and `false` otherwise, in which case the existing
+    /// value is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// #[derive(Debug, PartialEq)]
+    /// struct SessionKey(u64);
+    ///
+    /// let state = request.connection_info().state();
+    /// assert!(state.insert(SessionKey(7)));
+    /// assert!(!state.insert(SessionKey(8)));
+    /// assert_eq!(state.get::<SessionKey>(), Some(&SessionKey(7)));
+    /// ```
+    #[inline]
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> bool {
+        self.0.set(value)
+    }
+
+    /// Returns the value of type `T`, if one was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct SessionKey(u64);
+    ///
+    /// let state = request.connection_info().state();
+    /// assert!(state.get::<SessionKey>().is_none());
+    ///
+    /// state.insert(SessionKey(7));
+    /// assert_eq!(state.get::<SessionKey>().map(|key| key.0), Some(7));
+    /// ```
+    #[inline]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.0.try_get()
+    }
+
+    /// Returns the value of type `T`, first inserting the value returned by
+    /// `f` if there is none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct SessionKey(u64);
+    ///
+    /// let state = request.connection_info().state();
+    /// assert_eq!(state.get_or_insert_with(|| SessionKey(7)).0, 7);
+    /// assert_eq!(state.get_or_insert_with(|| SessionKey(8)).0, 7);
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with<T, F>(&self, f: F) -> &T
+        where T: Send + Sync + 'static, F: FnOnce() -> T
+    {
+        if let Some(value) = self.0.try_get() {
+            return value;
+        }
+
+        self.0.set(f());
+        self.0.get()
+    }
+
+    /// Returns `true` if a value of type `T` was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Authenticated;
+    ///
+    /// let state = request.connection_info().state();
+    /// assert!(!state.contains::<Authenticated>());
+    ///
+    /// state.insert(Authenticated);
+    /// assert!(state.contains::<Authenticated>());
+    /// ```
+    #[inline]
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+}
+
+impl fmt::Debug for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionState").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,233 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::request::{self, Request, FromRequest};
+use crate::http::Status;
+
+/// A request guard for the time budget of a request.
+///
+/// A `Deadline` is the instant by which a request should be handled. It is
+/// derived from the [`request_timeout`](crate::Config::request_timeout)
+/// configuration parameter, measured from when the request was received, and,
+/// if [`timeout_header`](crate::Config::timeout_header) is configured, from the
+/// budget in the request's header of that name, whichever is sooner. If neither
+/// is set, the request has no deadline and never expires.
+///
+/// Handlers, and helpers they call, consult the deadline to abandon expensive
+/// work early: [`Deadline::checked()`] and [`Deadline::run()`] fail with `504
+/// Gateway Timeout` once the deadline has passed, so the response to an expired
+/// request is consistent throughout an application.
+///
+/// The header's value is either a number of seconds, such as `1.5`, or an
+/// integer followed by a `grpc-timeout` unit: one of `H` (hours), `M`
+/// (minutes), `S` (seconds), `m` (milliseconds), `u` (microseconds), or `n`
+/// (nanoseconds), such as `1500m`. Invalid values are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::request::Deadline;
+///
+/// # async fn query(_: &str) -> Vec<String> { vec![] }
+/// # async fn render(_: Vec<String>) -> String { String::new() }
+/// #[get("/report")]
+/// async fn report(deadline: Deadline) -> Result<String, Status> {
+///     let rows = deadline.run(query("SELECT * FROM sales")).await?;
+///     deadline.checked()?;
+///     Ok(render(rows).await)
+/// }
+/// ```
+///
+/// With a `Rocket.toml` of:
+///
+/// ```toml
+/// [default]
+/// request_timeout = 30
+/// timeout_header = "X-Request-Timeout"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline that never expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Deadline;
+    ///
+    /// let deadline = Deadline::none();
+    /// assert!(deadline.remaining().is_none());
+    /// assert!(deadline.checked().is_ok());
+    /// ```
+    pub const fn none() -> Deadline {
+        Deadline { at: None }
+    }
+
+    /// A deadline expiring at `instant`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    /// use rocket::request::Deadline;
+    ///
+    /// let deadline = Deadline::at(Instant::now() + Duration::from_secs(10));
+    /// assert!(deadline.remaining().unwrap() <= Duration::from_secs(10));
+    /// ```
+    pub const fn at(instant: Instant) -> Deadline {
+        Deadline { at: Some(instant) }
+    }
+
+    /// Returns the instant the deadline expires at, if any.
+    pub fn instant(&self) -> Option<Instant> {
+        self.at
+    }
+
+    /// Returns the time remaining until the deadline, saturating at zero, or
+    /// `None` if there is no deadline.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.at.map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns `true` if the deadline has passed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Instant;
+    /// use rocket::request::Deadline;
+    ///
+    /// assert!(Deadline::at(Instant::now()).is_expired());
+    /// assert!(!Deadline::none().is_expired());
+    /// ```
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// Returns `Ok(())` if the deadline has not passed and
+    /// `Err(Status::GatewayTimeout)` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Instant;
+    /// use rocket::http::Status;
+    /// use rocket::request::Deadline;
+    ///
+    /// let deadline = Deadline::at(Instant::now());
+    /// assert_eq!(deadline.checked(), Err(Status::GatewayTimeout));
+    /// ```
+    pub fn checked(&self) -> Result<(), Status> {
+        match self.is_expired() {
+            true => Err(Status::GatewayTimeout),
+            false => Ok(()),
+        }
+    }
+
+    /// Runs `future` to completion unless the deadline passes first, in which
+    /// case `future` is dropped and `Err(Status::GatewayTimeout)` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// use std::time::{Duration, Instant};
+    /// use rocket::http::Status;
+    /// use rocket::request::Deadline;
+    ///
+    /// let deadline = Deadline::at(Instant::now() + Duration::from_millis(10));
+    /// let slow = rocket::tokio::time::sleep(Duration::from_secs(10));
+    /// assert_eq!(deadline.run(slow).await, Err(Status::GatewayTimeout));
+    /// # });
+    /// ```
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, Status> {
+        match self.at {
+            Some(at) => tokio::time::timeout_at(at.into(), future).await
+                .map_err(|_| Status::GatewayTimeout),
+            None => Ok(future.await),
+        }
+    }
+
+    fn compute(req: &Request<'_>) -> Deadline {
+        let config = req.rocket().config();
+        let configured = (config.request_timeout != 0)
+            .then(|| Duration::from_secs(config.request_timeout.into()));
+
+        let inbound = config.timeout_header.as_ref()
+            .and_then(|name| req.headers().get_one(name.as_str()))
+            .and_then(parse_timeout);
+
+        let timeout = match (configured, inbound) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        let received = req.state.received;
+        match timeout.and_then(|timeout| received.checked_add(timeout)) {
+            Some(at) => Deadline::at(at),
+            None => Deadline::none(),
+        }
+    }
+}
+
+/// Parses a time budget of either fractional seconds (`1.5`) or an integer
+/// followed by a `grpc-timeout` unit (`1500m`).
+fn parse_timeout(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let Some(unit) = value.chars().last().filter(|c| c.is_ascii_alphabetic()) else {
+        let seconds: f64 = value.parse().ok()?;
+        return Duration::try_from_secs_f64(seconds).ok();
+    };
+
+    let n: u64 = value[..value.len() - 1].parse().ok()?;
+    match unit {
+        'H' => n.checked_mul(60 * 60).map(Duration::from_secs),
+        'M' => n.checked_mul(60).map(Duration::from_secs),
+        'S' => Some(Duration::from_secs(n)),
+        'm' => Some(Duration::from_millis(n)),
+        'u' => Some(Duration::from_micros(n)),
+        'n' => Some(Duration::from_nanos(n)),
+        _ => None,
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for Deadline {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Infallible> {
+        request::Outcome::Success(*req.local_cache(|| Deadline::compute(req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::parse_timeout;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout(" 2 "), Some(Duration::from_secs(2)));
+        assert_eq!(parse_timeout("1500m"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("2H"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_timeout("3M"), Some(Duration::from_secs(180)));
+        assert_eq!(parse_timeout("4S"), Some(Duration::from_secs(4)));
+        assert_eq!(parse_timeout("5u"), Some(Duration::from_micros(5)));
+        assert_eq!(parse_timeout("6n"), Some(Duration::from_nanos(6)));
+
+        assert_eq!(parse_timeout(""), None);
+        assert_eq!(parse_timeout("-1"), None);
+        assert_eq!(parse_timeout("NaN"), None);
+        assert_eq!(parse_timeout("1.5m"), None);
+        assert_eq!(parse_timeout("10x"), None);
+        assert_eq!(parse_timeout("m"), None);
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use state::TypeMap;
+
+/// Typed, request-local values keyed by their type.
+///
+/// `Extensions` stores at most one value of any given type. A value is
+/// inserted at most once, typically by a [request
+/// fairing](crate::fairing::Fairing#request), and is then available to guards,
+/// handlers, catchers, and response fairings for the remainder of the request.
+/// This makes `Extensions` well suited to passing data computed once per
+/// request, such as an authentication context or a tenant, to request guards.
+/// The extensions for a request are retrieved via
+/// [`Request::extensions()`](crate::Request::extensions()).
+///
+/// Unlike [`Request::local_cache()`](crate::Request::local_cache()), which
+/// computes and caches a value on first access, values in `Extensions` are
+/// only ever present if they were explicitly inserted. Retrieving a value that
+/// was not inserted returns `None`, making it clear whether the code that was
+/// expected to insert the value ran at all.
+///
+/// Like the local cache, extensions are shared by clones of a request.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::{Request, Data};
+/// use rocket::fairing::AdHoc;
+/// use rocket::request::{self, FromRequest};
+/// use rocket::http::Status;
+///
+/// /// The tenant a request is for, as determined by its `Host`.
+/// struct Tenant(String);
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for &'r Tenant {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         match req.extensions().get::<Tenant>() {
+///             Some(tenant) => request::Outcome::Success(tenant),
+///             None => request::Outcome::Error((Status::NotFound, ())),
+///         }
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index(tenant: &Tenant) -> &str {
+///     &tenant.0
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .attach(AdHoc::on_request("Tenant", |req, _| Box::pin(async move {
+///             if let Some(host) = req.host() {
+///                 let tenant = host.domain().as_str().split('.').next().unwrap_or("");
+///                 req.extensions().insert(Tenant(tenant.to_string()));
+///             }
+///         })))
+/// }
+/// ```
+pub struct Extensions(TypeMap![Send + Sync]);
+
+impl Extensions {
+    /// Creates a new, empty set of extensions.
+    pub(crate) fn new() -> Extensions {
+        Extensions(<TypeMap![Send + Sync]>::new())
+    }
+
+    /// Inserts `value` if no value of type `T` is present. Returns `true` if
+    /// `value` was inserted and `false` otherwise, in which case the existing
+    /// value is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// #[derive(Debug, PartialEq)]
+    /// struct UserId(u64);
+    ///
+    /// assert!(request.extensions().insert(UserId(7)));
+    /// assert!(!request.extensions().insert(UserId(8)));
+    /// assert_eq!(request.extensions().get::<UserId>(), Some(&UserId(7)));
+    /// ```
+    #[inline]
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> bool {
+        self.0.set(value)
+    }
+
+    /// Returns the value of type `T`, if one was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct UserId(u64);
+    ///
+    /// assert!(request.extensions().get::<UserId>().is_none());
+    ///
+    /// request.extensions().insert(UserId(7));
+    /// assert_eq!(request.extensions().get::<UserId>().map(|id| id.0), Some(7));
+    /// ```
+    #[inline]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.0.try_get()
+    }
+
+    /// Returns `true` if a value of type `T` was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Authenticated;
+    ///
+    /// assert!(!request.extensions().contains::<Authenticated>());
+    ///
+    /// request.extensions().insert(Authenticated);
+    /// assert!(request.extensions().contains::<Authenticated>());
+    /// ```
+    #[inline]
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").finish_non_exhaustive()
+    }
+}
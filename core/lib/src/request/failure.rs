@@ -0,0 +1,127 @@
+use std::fmt;
+use std::borrow::Cow;
+
+use parking_lot::Mutex;
+
+use crate::http::Status;
+
+/// The kind of route parameter a [`GuardFailure`] is for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GuardKind {
+    /// A dynamic path parameter: a [`FromParam`] or [`FromSegments`] type.
+    ///
+    /// [`FromParam`]: crate::request::FromParam
+    /// [`FromSegments`]: crate::request::FromSegments
+    Path,
+    /// A query parameter: a [`FromForm`](crate::form::FromForm) type.
+    Query,
+    /// A request guard: a [`FromRequest`](crate::request::FromRequest) type.
+    Request,
+    /// A data guard: a [`FromData`](crate::data::FromData) type.
+    Data,
+}
+
+/// An explanation of why a route's guard or parameter failed.
+///
+/// When a request is answered with a `404` or `422` because every matching
+/// route forwarded, the question is usually _which_ guard forwarded and why.
+/// In the debug profile, Rocket records a `GuardFailure` for every guard or
+/// parameter that forwards or fails while a request is routed. Failures are:
+///
+///   * logged, at the `warn` level, when the request is answered by a
+///     catcher after all matching routes forwarded,
+///   * listed in the body of Rocket's default error pages, and
+///   * returned, in the order they occurred, by
+///     [`Request::guard_failures()`], for use in custom catchers.
+///
+/// The `reason` for a failure is the reason the guard gave via
+/// [`Request::explain_failure()`], if it did, or the guard's error otherwise.
+/// Failures are never recorded outside of the debug profile as they may
+/// reveal details of an application's internals.
+///
+/// [`Request::guard_failures()`]: crate::Request::guard_failures()
+/// [`Request::explain_failure()`]: crate::Request::explain_failure()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::local::blocking::Client;
+/// use rocket::request::GuardKind;
+///
+/// #[get("/<id>")]
+/// fn user(id: usize) { /* .. */ }
+///
+/// #[catch(422)]
+/// fn unprocessable(req: &rocket::Request<'_>) -> String {
+///     let failure = &req.guard_failures()[0];
+///     assert_eq!(failure.kind, GuardKind::Path);
+///     assert_eq!(failure.parameter.as_deref(), Some("id"));
+///     format!("bad `{}`: {}", failure.type_name, failure.reason.as_deref().unwrap())
+/// }
+///
+/// let rocket = rocket::build()
+///     .mount("/", routes![user])
+///     .register("/", catchers![unprocessable]);
+///
+/// let client = Client::debug(rocket).unwrap();
+/// let response = client.get("/bob").dispatch();
+/// assert_eq!(response.status(), Status::UnprocessableEntity);
+/// assert_eq!(response.into_string().unwrap(), "bad `usize`: invalid digit found in string");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GuardFailure {
+    /// The name of the route the guard belongs to, if it has one.
+    pub route: Option<Cow<'static, str>>,
+    /// The kind of the guard.
+    pub kind: GuardKind,
+    /// The name of the parameter, or `None` for a guard declared without
+    /// one, like a `guard = Type` route attribute argument.
+    pub parameter: Option<Cow<'static, str>>,
+    /// The guard's type, as written in the route.
+    pub type_name: Cow<'static, str>,
+    /// The status the guard forwarded or failed with.
+    pub status: Status,
+    /// Whether the guard forwarded, as opposed to failed.
+    pub forwarded: bool,
+    /// Why the guard forwarded or failed, if known.
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for GuardFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            GuardKind::Path => "path parameter",
+            GuardKind::Query => "query parameter",
+            GuardKind::Request => "request guard",
+            GuardKind::Data => "data guard",
+        };
+
+        match &self.parameter {
+            Some(name) => write!(f, "{kind} `{name}: {}`", self.type_name)?,
+            None => write!(f, "{kind} `{}`", self.type_name)?,
+        }
+
+        if let Some(route) = &self.route {
+            write!(f, " of route `{route}`")?;
+        }
+
+        let outcome = if self.forwarded { "forwarded" } else { "failed" };
+        write!(f, " {outcome} with {}", self.status)?;
+        if let Some(reason) = &self.reason {
+            write!(f, ": {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The guard failures of a request, stored in its local cache.
+#[derive(Default)]
+pub(crate) struct Failures {
+    /// The reason given by the guard currently resolving, if any.
+    pub explanation: Mutex<Option<String>>,
+    pub failures: Mutex<Vec<GuardFailure>>,
+}
@@ -7,6 +7,8 @@ use crate::outcome::{self, IntoOutcome, Outcome::*};
 
 use crate::http::uri::{Host, Origin};
 use crate::http::{Status, ContentType, Accept, Method, ProxyProto, CookieJar};
+use crate::http::{HeaderError, AcceptLanguage, Authorization};
+use crate::http::{ContentDisposition, Forwarded, Prefer};
 use crate::listener::Endpoint;
 
 /// Type alias for the `Outcome` of a `FromRequest` conversion.
@@ -142,6 +144,20 @@ pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), Status>;
 ///
 ///     _This implementation always returns successfully._
 ///
+///   * **&[`TaskSet`]**
+///
+///     Returns the application's set of supervised tasks, as
+///     [`Rocket::tasks()`](crate::Rocket::tasks()).
+///
+///     _This implementation always returns successfully._
+///
+///   * **[`ResponseGuard`]**
+///
+///     Returns a guard for registering callbacks that run once the response
+///     to the request has been written.
+///
+///     _This implementation always returns successfully._
+///
 ///   * **&ContentType**
 ///
 ///     Extracts the [`ContentType`] header from the incoming request via
@@ -155,6 +171,16 @@ pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), Status>;
 ///     [`Request::accept()`]. If the request didn't specify an `Accept`, the
 ///     request is forwarded with a 500 Internal Server Error status.
 ///
+///   * **AcceptLanguage**, **Authorization**, **ContentDisposition**,
+///     **Forwarded**, **Prefer**
+///
+///     Parses the corresponding header from the incoming request via
+///     [`HeaderMap::get_typed()`]. If the header is malformed, the request
+///     fails with a 400 Bad Request status and a [`HeaderError`]. If it is
+///     missing, the request fails with a 400 Bad Request status, or with a 401
+///     Unauthorized status for `Authorization`. Use `Option<T>` to accept
+///     requests without the header.
+///
 ///   * ***IpAddr**
 ///
 ///     Extracts the client ip address of the incoming request as an [`IpAddr`]
@@ -190,6 +216,10 @@ pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), Status>;
 ///     forwarded with the same status code as the original forward.
 ///
 /// [`Config`]: crate::config::Config
+/// [`TaskSet`]: crate::task::TaskSet
+/// [`ResponseGuard`]: crate::response::ResponseGuard
+/// [`HeaderMap::get_typed()`]: crate::http::HeaderMap::get_typed()
+/// [`HeaderError`]: crate::http::HeaderError
 ///
 /// # Example
 ///
@@ -465,6 +495,31 @@ impl<'r> FromRequest<'r> for &'r ContentType {
     }
 }
 
+macro_rules! impl_typed_header_guards {
+    ($($T:ident => $missing:expr),* $(,)?) => ($(
+        #[crate::async_trait]
+        impl<'r> FromRequest<'r> for $T<'r> {
+            type Error = HeaderError;
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, HeaderError> {
+                match request.headers().get_typed::<$T<'r>>() {
+                    Ok(header) => Success(header),
+                    Err(e@HeaderError::Missing(_)) => Error(($missing, e)),
+                    Err(e) => Error((Status::BadRequest, e)),
+                }
+            }
+        }
+    )*)
+}
+
+impl_typed_header_guards! {
+    AcceptLanguage => Status::BadRequest,
+    Authorization => Status::Unauthorized,
+    ContentDisposition => Status::BadRequest,
+    Forwarded => Status::BadRequest,
+    Prefer => Status::BadRequest,
+}
+
 #[crate::async_trait]
 impl<'r> FromRequest<'r> for IpAddr {
     type Error = Infallible;
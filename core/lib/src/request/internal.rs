@@ -0,0 +1,384 @@
+use crate::{Request, Response, Data};
+use crate::request::ConnectionMeta;
+use crate::response::{self, Responder};
+use crate::http::{Header, HeaderMap, Method, Status, uri::Origin};
+
+/// The maximum number of nested internal dispatches before a request is
+/// answered with `508 Loop Detected`.
+const MAX_DEPTH: usize = 8;
+
+/// Marks a request as dispatched internally and records its nesting depth.
+struct Depth(usize);
+
+/// Marks an internal request whose body was forwarded from its parent.
+struct Forwarded;
+
+/// The body of an [`InternalRequest`].
+enum Body<'a> {
+    Bytes(Vec<u8>),
+    Data(Data<'a>),
+}
+
+/// A request dispatched to the same instance of Rocket without a round trip
+/// through the network.
+///
+/// An `InternalRequest` is created via [`Request::internal()`] and dispatched
+/// with [`InternalRequest::dispatch()`]. The request runs through the complete
+/// request lifecycle, exactly as if it had been received by the server: request
+/// and response fairings run, routing and forwarding occur, and guards and
+/// catchers are invoked as usual. The request inherits the connection
+/// information, such as the remote address and TLS state, of its parent
+/// request, but no headers unless [`InternalRequest::inherit_headers()`] is
+/// called.
+///
+/// The response, including its body, is read into memory and returned as an
+/// [`InternalResponse`], which is itself a [`Responder`].
+///
+/// Internal requests are not subject to load limits: they are accounted for by
+/// their parent request. To prevent unbounded recursion, an internal request
+/// nested more than 8 levels deep is answered with `508 Loop Detected`.
+///
+/// To re-dispatch the current request to a different URI, use
+/// [`Request::forward_to()`] instead.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::http::{Method, uri::Origin};
+/// use rocket::request::InternalResponse;
+///
+/// #[get("/v2/users/<id>")]
+/// fn user(id: usize) -> String {
+///     format!("user #{id}")
+/// }
+///
+/// #[get("/legacy/user?<id>")]
+/// async fn legacy(id: usize, req: &Request<'_>) -> InternalResponse {
+///     req.internal(Method::Get, uri!(user(id)))
+///         .inherit_headers()
+///         .dispatch()
+///         .await
+/// }
+/// ```
+pub struct InternalRequest<'a, 'r> {
+    parent: &'a Request<'r>,
+    method: Method,
+    uri: Origin<'static>,
+    headers: HeaderMap<'static>,
+    body: Body<'a>,
+}
+
+/// The buffered response to an [`InternalRequest`].
+///
+/// As a [`Responder`], an `InternalResponse` responds with the status, headers,
+/// and body of the internally dispatched request.
+#[derive(Debug, Clone)]
+pub struct InternalResponse {
+    status: Status,
+    headers: HeaderMap<'static>,
+    body: Vec<u8>,
+}
+
+impl<'r> Request<'r> {
+    /// Returns a new request with method `method` to `uri` which can be
+    /// dispatched to the same instance of Rocket as `self`. See
+    /// [`InternalRequest`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Method, uri::Origin};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let internal = request.internal(Method::Get, Origin::parse("/status").unwrap());
+    /// ```
+    pub fn internal<'a>(&'a self, method: Method, uri: Origin<'_>) -> InternalRequest<'a, 'r> {
+        InternalRequest {
+            parent: self,
+            method,
+            uri: uri.into_owned(),
+            headers: HeaderMap::new(),
+            body: Body::Bytes(vec![]),
+        }
+    }
+
+    /// Returns a new request to `uri` with the same method and headers as
+    /// `self` which can be dispatched to the same instance of Rocket as `self`.
+    ///
+    /// This is an _internal redirect_: the returned request is handled server
+    /// side, without a `3xx` round trip through the client, and its response
+    /// can be returned directly from a handler. The body of `self` can be
+    /// forwarded with [`InternalRequest::data()`] or rewritten with
+    /// [`InternalRequest::body()`]. Headers can be overridden with
+    /// [`InternalRequest::replace_header()`]. See [`InternalRequest`] for
+    /// details on internal dispatch.
+    ///
+    /// As with any internal request, the response is read into memory in its
+    /// entirety before it is returned, so forwarding to a handler with a large
+    /// or streaming response body, such as a file download or an event stream,
+    /// buffers the whole body. Respond with a redirect in such cases instead.
+    ///
+    /// # Example
+    ///
+    /// Map a legacy URL structure onto new handlers, preserving the body:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::path::PathBuf;
+    ///
+    /// use rocket::{Request, Data};
+    /// use rocket::http::uri::Origin;
+    /// use rocket::request::InternalResponse;
+    ///
+    /// #[post("/api/v2/<path..>", data = "<body>")]
+    /// fn v2(path: PathBuf, body: &str) -> String {
+    ///     format!("{}: {body}", path.display())
+    /// }
+    ///
+    /// #[post("/api/v1/<path..>", data = "<data>")]
+    /// async fn v1(path: PathBuf, req: &Request<'_>, data: Data<'_>) -> InternalResponse {
+    ///     let uri = format!("/api/v2/{}", path.display());
+    ///     req.forward_to(Origin::parse_owned(uri).unwrap())
+    ///         .data(data)
+    ///         .dispatch()
+    ///         .await
+    /// }
+    /// ```
+    pub fn forward_to<'a>(&'a self, uri: Origin<'_>) -> InternalRequest<'a, 'r> {
+        let mut internal = self.internal(self.method(), uri);
+        if let Some(content_type) = self.headers().get_one("Content-Type") {
+            internal = internal.header(Header::new("Content-Type", content_type.to_string()));
+        }
+
+        internal.inherit_headers()
+    }
+
+    /// Returns `true` if `self` was dispatched internally via
+    /// [`InternalRequest::dispatch()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// assert!(!request.is_internal());
+    /// ```
+    pub fn is_internal(&self) -> bool {
+        self.extensions().get::<Depth>().is_some()
+    }
+
+    /// Returns `true` if the body of `self` was forwarded from its parent via
+    /// [`InternalRequest::data()`] and has thus already been processed by data
+    /// fairings.
+    pub(crate) fn has_forwarded_data(&self) -> bool {
+        self.extensions().get::<Forwarded>().is_some()
+    }
+}
+
+impl<'a, 'r> InternalRequest<'a, 'r> {
+    /// Adds the header `header` to the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Method, Header, uri::Origin};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let internal = request.internal(Method::Get, Origin::root().clone())
+    ///     .header(Header::new("X-Batch-Id", "7"));
+    /// ```
+    pub fn header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        self.headers.add(header.into());
+        self
+    }
+
+    /// Sets the header `header` in the request, replacing any existing headers
+    /// with the same name, including those copied from the parent request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{ContentType, uri::Origin};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.post("/").header(ContentType::Text);
+    /// let internal = request.forward_to(Origin::root().clone())
+    ///     .replace_header(ContentType::JSON);
+    /// ```
+    pub fn replace_header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        self.headers.replace(header.into());
+        self
+    }
+
+    /// Copies all of the parent request's headers, except those describing
+    /// the parent's body, into this request. Headers already set on this
+    /// request are retained.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Method, uri::Origin};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let internal = request.internal(Method::Get, Origin::root().clone()).inherit_headers();
+    /// ```
+    pub fn inherit_headers(mut self) -> Self {
+        const BODY: &[&str] = &["Content-Length", "Content-Type", "Transfer-Encoding"];
+
+        for header in self.parent.headers().iter() {
+            let name = header.name();
+            let is_body_header = BODY.iter().any(|h| name.as_str().eq_ignore_ascii_case(h));
+            if is_body_header || self.headers.contains(name.as_str()) {
+                continue;
+            }
+
+            self.headers.add(Header::new(name.to_string(), header.value().to_string()));
+        }
+
+        self
+    }
+
+    /// Sets the body of the request to `body`, replacing any body previously
+    /// set via [`InternalRequest::body()`] or [`InternalRequest::data()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Method, uri::Origin};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let internal = request.internal(Method::Post, Origin::root().clone()).body("hello");
+    /// ```
+    pub fn body<B: AsRef<[u8]>>(mut self, body: B) -> Self {
+        self.body = Body::Bytes(body.as_ref().to_vec());
+        self
+    }
+
+    /// Sets the body of the request to the body of the parent request, `data`.
+    ///
+    /// The body is streamed, not buffered, into the internal request. Because
+    /// data fairings have already processed `data` on behalf of the parent
+    /// request, they are not run again for the internal request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::{Request, Data};
+    /// use rocket::request::InternalResponse;
+    ///
+    /// #[post("/upload", data = "<data>")]
+    /// async fn upload(req: &Request<'_>, data: Data<'_>) -> InternalResponse {
+    ///     req.forward_to(uri!("/v2/upload")).data(data).dispatch().await
+    /// }
+    /// ```
+    pub fn data(mut self, data: Data<'a>) -> Self {
+        self.body = Body::Data(data);
+        self
+    }
+
+    /// Dispatches the request and returns its buffered response.
+    ///
+    /// If reading the response body fails, the response has a status of
+    /// `500 Internal Server Error` and an empty body.
+    pub async fn dispatch(self) -> InternalResponse {
+        let rocket = self.parent.rocket();
+        let depth = self.parent.extensions().get::<Depth>().map_or(0, |d| d.0) + 1;
+
+        let mut request = Request::new(rocket, self.method, self.uri);
+        request.connection = ConnectionMeta::clone(&self.parent.connection);
+        request.extensions().insert(Depth(depth));
+        for header in self.headers.into_iter() {
+            request.add_header(header);
+        }
+
+        if depth > MAX_DEPTH {
+            let uri = rocket.config.redact.uri(request.uri());
+            error!(depth, %uri, "internal request nested too deeply");
+            let response = rocket.dispatch_error(Status::LoopDetected, &request).await;
+            return InternalResponse::read(response).await;
+        }
+
+        let mut data = match self.body {
+            Body::Bytes(bytes) => Data::local(bytes),
+            Body::Data(data) => {
+                request.extensions().insert(Forwarded);
+                data
+            }
+        };
+
+        let token = rocket.preprocess(&mut request, &mut data).await;
+        let response = rocket.dispatch(token, &request, data).await;
+        InternalResponse::read(response).await
+    }
+}
+
+impl InternalResponse {
+    async fn read(mut response: Response<'_>) -> InternalResponse {
+        let headers = response.headers().iter()
+            .map(|h| Header::new(h.name().to_string(), h.value().to_string()))
+            .fold(HeaderMap::new(), |mut map, h| { map.add(h); map });
+
+        match response.body_mut().to_bytes().await {
+            Ok(body) => InternalResponse { status: response.status(), headers, body },
+            Err(e) => {
+                error!("failed to read internal response body: {e}");
+                let status = Status::InternalServerError;
+                InternalResponse { status, headers: HeaderMap::new(), body: vec![] }
+            }
+        }
+    }
+
+    /// The status of the response.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// The headers of the response.
+    pub fn headers(&self) -> &HeaderMap<'static> {
+        &self.headers
+    }
+
+    /// The body of the response.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Consumes `self` and returns the body of the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// Responds with the status, headers, and body of the internal response.
+impl<'r> Responder<'r, 'static> for InternalResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.status(self.status);
+        for header in self.headers.into_iter() {
+            response.header_adjoin(header);
+        }
+
+        response.sized_body(self.body.len(), std::io::Cursor::new(self.body)).ok()
+    }
+}
+
+impl std::fmt::Debug for InternalRequest<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InternalRequest")
+            .field("method", &self.method)
+            .field("uri", &self.uri)
+            .field("headers", &self.headers)
+            .field("body", &match &self.body {
+                Body::Bytes(bytes) => bytes.len().to_string(),
+                Body::Data(_) => "<forwarded>".into(),
+            })
+            .finish()
+    }
+}
@@ -4,6 +4,13 @@ mod request;
 mod from_param;
 mod from_request;
 mod atomic_method;
+mod deadline;
+mod connection_info;
+mod connection_state;
+mod arena;
+mod extensions;
+mod internal;
+mod failure;
 
 #[cfg(test)]
 mod tests;
@@ -11,15 +18,26 @@ mod tests;
 pub use self::request::Request;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
+pub use self::deadline::Deadline;
+pub use self::connection_info::ConnectionInfo;
+pub use self::connection_state::ConnectionState;
+pub use self::arena::Arena;
+pub use self::extensions::Extensions;
+pub use self::internal::{InternalRequest, InternalResponse};
+pub use self::failure::{GuardFailure, GuardKind};
 
 #[doc(hidden)]
 pub use rocket_codegen::FromParam;
 
 #[doc(inline)]
-pub use crate::response::flash::FlashMessage;
+pub use crate::response::flash::{FlashMessage, FlashMessages};
+
+#[doc(inline)]
+pub use crate::response::versioned::IfMatch;
 
 pub(crate) use self::request::ConnectionMeta;
 pub(crate) use self::atomic_method::AtomicMethod;
+pub(crate) use self::failure::Failures;
 
 crate::export! {
     /// Store and immediately retrieve a vector-like value `$v` (`String` or
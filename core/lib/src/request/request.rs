@@ -1,24 +1,41 @@
 use std::{io, fmt};
 use std::ops::RangeFrom;
-use std::sync::{Arc, atomic::Ordering};
+use std::sync::{Arc, atomic::{AtomicU64, Ordering}};
 use std::borrow::Cow;
 use std::str::FromStr;
 use std::future::Future;
 use std::net::IpAddr;
+use std::time::Instant;
 
 use state::{TypeMap, InitCell};
 use futures::future::BoxFuture;
 use ref_swap::OptionRefSwap;
 
-use crate::{Rocket, Route, Orbit};
+use crate::{Rocket, Route, Orbit, Config};
+use crate::catcher::ErrorReference;
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome, AtomicMethod};
+use crate::request::{Arena, ConnectionInfo, ConnectionState, Extensions};
+use crate::request::{Failures, GuardFailure, GuardKind};
 use crate::form::{self, ValueField, FromForm};
-use crate::data::Limits;
+use crate::data::{Limits, BufferPolicy};
+use crate::config::Violation;
+use crate::response::completion::CompletionHooks;
+use crate::router::{Router, Finalized};
 
-use crate::http::ProxyProto;
+use crate::http::{ProxyProto, Status};
 use crate::http::{Method, Header, HeaderMap, ContentType, Accept, MediaType, CookieJar, Cookie};
-use crate::http::uri::{fmt::Path, Origin, Segments, Host, Authority};
-use crate::listener::{Certificates, Endpoint};
+use crate::http::uri::{Origin, Absolute, Segments, Host, Authority};
+use crate::http::uri::fmt::{Path, ValidRoutePrefix};
+use crate::listener::{Certificates, Endpoint, TlsInfo};
+
+/// Marks a `HEAD` request rewritten as a `GET` for automatic handling.
+struct AutoHead;
+
+/// The methods allowed for a request answered with `405 Method Not Allowed`.
+struct AllowedMethods(Vec<Method>);
+
+/// The URI of a request as received, before path normalization.
+struct RawUri(Origin<'static>);
 
 /// The type of an incoming web request.
 ///
@@ -37,11 +54,20 @@ pub struct Request<'r> {
 }
 
 /// Information derived from an incoming connection, if any.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub(crate) struct ConnectionMeta {
     pub peer_endpoint: Option<Endpoint>,
     #[cfg_attr(not(feature = "mtls"), allow(dead_code))]
     pub peer_certs: Option<Arc<Certificates<'static>>>,
+    pub local_endpoint: Option<Endpoint>,
+    pub tls: Option<Arc<TlsInfo>>,
+    pub state: Arc<ConnectionState>,
+}
+
+impl Default for ConnectionMeta {
+    fn default() -> Self {
+        ConnectionMeta::new(Err(io::ErrorKind::NotConnected.into()), None)
+    }
 }
 
 impl ConnectionMeta {
@@ -49,19 +75,45 @@ impl ConnectionMeta {
         ConnectionMeta {
             peer_endpoint: endpoint.ok(),
             peer_certs: certs.map(|c| c.into_owned()).map(Arc::new),
+            local_endpoint: None,
+            tls: None,
+            state: Arc::new(ConnectionState::new()),
         }
     }
+
+    pub fn with_local(mut self, endpoint: io::Result<Endpoint>) -> Self {
+        self.local_endpoint = endpoint.ok();
+        self
+    }
+
+    pub fn with_tls(mut self, tls: Option<TlsInfo>) -> Self {
+        self.tls = tls.map(Arc::new);
+        self
+    }
 }
 
 /// Information derived from the request.
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
     pub route: OptionRefSwap<'r, Route>,
+    pub router: InitCell<Option<Arc<Router<Finalized>>>>,
     pub cookies: CookieJar<'r>,
     pub accept: InitCell<Option<Accept>>,
     pub content_type: InitCell<Option<ContentType>>,
-    pub cache: Arc<TypeMap![Send + Sync]>,
+    pub shared: Arc<SharedState>,
     pub host: Option<Host<'r>>,
+    pub received: Instant,
+    pub arena: Arena,
+    pub error_reference: InitCell<ErrorReference>,
+}
+
+/// Request state shared by a request and its clones, allocated once.
+pub(crate) struct SharedState {
+    pub cache: TypeMap![Send + Sync],
+    pub extensions: Extensions,
+    pub bytes_read: AtomicU64,
+    pub bytes_written: AtomicU64,
+    pub completion: CompletionHooks,
 }
 
 impl Clone for RequestState<'_> {
@@ -69,11 +121,15 @@ impl Clone for RequestState<'_> {
         RequestState {
             rocket: self.rocket,
             route: OptionRefSwap::new(self.route.load(Ordering::Acquire)),
+            router: self.router.clone(),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
-            cache: self.cache.clone(),
+            shared: self.shared.clone(),
             host: self.host.clone(),
+            received: self.received,
+            arena: Arena::new(),
+            error_reference: self.error_reference.clone(),
         }
     }
 }
@@ -95,11 +151,21 @@ impl<'r> Request<'r> {
             state: RequestState {
                 rocket,
                 route: OptionRefSwap::new(None),
+                router: InitCell::new(),
                 cookies: CookieJar::new(None, rocket),
                 accept: InitCell::new(),
                 content_type: InitCell::new(),
-                cache: Arc::new(<TypeMap![Send + Sync]>::new()),
+                shared: Arc::new(SharedState {
+                    cache: <TypeMap![Send + Sync]>::new(),
+                    extensions: Extensions::new(),
+                    bytes_read: AtomicU64::new(0),
+                    bytes_written: AtomicU64::new(0),
+                    completion: CompletionHooks::default(),
+                }),
                 host: None,
+                received: Instant::now(),
+                arena: Arena::new(),
+                error_reference: InitCell::new(),
             }
         }
     }
@@ -142,6 +208,49 @@ impl<'r> Request<'r> {
         self.method.set(method);
     }
 
+    /// Returns `true` if `self` is a `HEAD` request being automatically handled
+    /// by a `GET` route. In this case, [`Request::method()`] returns
+    /// `Method::Get` and the response body is discarded. Handlers and guards
+    /// can use this to skip producing a body that will never be sent.
+    ///
+    /// See [`Handler::handle_head()`](crate::route::Handler::handle_head()) for
+    /// the corresponding hook for custom handlers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.head("/");
+    /// assert!(!request.is_auto_head());
+    /// ```
+    #[inline]
+    pub fn is_auto_head(&self) -> bool {
+        self.extensions().get::<AutoHead>().is_some()
+    }
+
+    /// Returns the methods allowed for the request's URI if the request is
+    /// being answered with `405 Method Not Allowed` because routes match the
+    /// URI but not the request's method. Returns `None` otherwise.
+    ///
+    /// The methods are in canonical order and are exactly those advertised in
+    /// the response's `Allow` header. This is primarily useful in catchers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(405)]
+    /// fn not_allowed(req: &Request<'_>) -> String {
+    ///     let allowed = req.allowed_methods().unwrap_or_default();
+    ///     format!("{} is not allowed here; try one of {allowed:?}", req.method())
+    /// }
+    /// ```
+    pub fn allowed_methods(&self) -> Option<&[Method]> {
+        self.extensions().get::<AllowedMethods>().map(|m| &*m.0)
+    }
+
     /// Borrow the [`Origin`] URI from `self`.
     ///
     /// # Example
@@ -157,6 +266,29 @@ impl<'r> Request<'r> {
         &self.uri
     }
 
+    /// Borrow the [`Origin`] URI of `self` as received, before [path
+    /// normalization]. This is the same as [`Request::uri()`] unless
+    /// normalization changed the path.
+    ///
+    /// [path normalization]: crate::config::NormalizeConfig
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let get = |uri| c.get(uri);
+    /// // Requests are normalized when dispatched; until then, both agree.
+    /// let req = get("/a/./b");
+    /// assert_eq!(req.raw_uri(), req.uri());
+    /// assert_eq!(req.raw_uri(), "/a/./b");
+    /// ```
+    pub fn raw_uri(&self) -> &Origin<'_> {
+        match self.extensions().get::<RawUri>() {
+            Some(raw) => &raw.0,
+            None => self.uri(),
+        }
+    }
+
     /// Set the URI in `self` to `uri`.
     ///
     /// # Example
@@ -357,6 +489,23 @@ impl<'r> Request<'r> {
         self.connection.peer_endpoint = Some(endpoint);
     }
 
+    /// Returns details of the connection the request arrived on. See
+    /// [`ConnectionInfo`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let conn = request.connection_info();
+    /// assert!(conn.local().is_none());
+    /// assert!(!conn.is_tls());
+    /// ```
+    #[inline(always)]
+    pub fn connection_info(&self) -> ConnectionInfo<'_> {
+        ConnectionInfo::new(&self.connection)
+    }
+
     /// Returns the IP address of the configured
     /// [`ip_header`](crate::Config::ip_header) of the request if such a header
     /// is configured, exists and contains a valid IP address.
@@ -466,6 +615,57 @@ impl<'r> Request<'r> {
         self.cookies().state.secure
     }
 
+    /// Returns the absolute URL at which clients reach the application, as
+    /// configured via [`public_url`](crate::Config::public_url), if any.
+    ///
+    /// The request's [`host()`](Self::host()) is intentionally _not_ used as a
+    /// fallback as it is controlled by the client. To derive a URL from the
+    /// host, validate it against a whitelist via [`Host::to_absolute()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// // By default, no `public_url` is configured.
+    /// assert!(request.base_url().is_none());
+    ///
+    /// # let figment = rocket::figment::Figment::from(rocket::Config::debug_default());
+    /// let figment = figment.merge(("public_url", "https://rocket.rs/app"));
+    /// # let c = rocket::local::blocking::Client::debug(rocket::custom(figment)).unwrap();
+    /// # let request = c.get("/");
+    /// assert_eq!(request.base_url().unwrap(), "https://rocket.rs/app");
+    /// ```
+    #[inline]
+    pub fn base_url(&self) -> Option<&Absolute<'static>> {
+        self.rocket().config.public_url.as_ref()
+    }
+
+    /// Returns the absolute URL for `uri`, an origin URI such as one returned
+    /// by [`uri!`](crate::uri!), by joining it with
+    /// [`base_url()`](Self::base_url()). Returns `None` if there is no base
+    /// URL.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// #[get("/hello/<name>")]
+    /// fn hello(name: &str) { /* .. */ }
+    ///
+    /// # let figment = rocket::figment::Figment::from(rocket::Config::debug_default());
+    /// let figment = figment.merge(("public_url", "https://rocket.rs/app"));
+    /// # let c = rocket::local::blocking::Client::debug(rocket::custom(figment)).unwrap();
+    /// # let request = c.get("/");
+    /// let url = request.absolute_uri(uri!(hello("Bob Smith")));
+    /// assert_eq!(url.unwrap(), "https://rocket.rs/app/hello/Bob%20Smith");
+    /// ```
+    pub fn absolute_uri(&self, uri: Origin<'_>) -> Option<Absolute<'static>> {
+        let path = uri.path().as_str().to_owned().into();
+        let query = uri.query().map(|q| q.as_str().to_owned().into());
+        Some(self.base_url()?.clone().append(path, query))
+    }
+
     /// Attempts to return the client's IP address by first inspecting the
     /// [`ip_header`](crate::Config::ip_header) and then using the remote
     /// connection's IP address. Note that the built-in `IpAddr` request guard
@@ -817,6 +1017,9 @@ impl<'r> Request<'r> {
     /// generate a locally anonymous wrapper type, store, and retrieve the
     /// wrapped value from request-local cache.
     ///
+    /// To pass a value computed elsewhere, say in a fairing, to guards, prefer
+    /// [`Request::extensions()`], which never computes a missing value.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -834,10 +1037,10 @@ impl<'r> Request<'r> {
         where F: FnOnce() -> T,
               T: Send + Sync + 'static
     {
-        self.state.cache.try_get()
+        self.state.shared.cache.try_get()
             .unwrap_or_else(|| {
-                self.state.cache.set(f());
-                self.state.cache.get()
+                self.state.shared.cache.set(f());
+                self.state.shared.cache.get()
             })
     }
 
@@ -868,15 +1071,277 @@ impl<'r> Request<'r> {
         where F: Future<Output = T>,
               T: Send + Sync + 'static
     {
-        match self.state.cache.try_get() {
+        match self.state.shared.cache.try_get() {
             Some(s) => s,
             None => {
-                self.state.cache.set(fut.await);
-                self.state.cache.get()
+                self.state.shared.cache.set(fut.await);
+                self.state.shared.cache.get()
             }
         }
     }
 
+    /// Returns the request's typed [`Extensions`].
+    ///
+    /// Values inserted into the extensions, typically by request fairings, are
+    /// available for the remainder of the request. Unlike
+    /// [`local_cache()`](Self::local_cache()), a value is never computed on
+    /// access: it is present only if it was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct TraceId(u128);
+    ///
+    /// request.extensions().insert(TraceId(42));
+    /// assert_eq!(request.extensions().get::<TraceId>().map(|id| id.0), Some(42));
+    /// ```
+    #[inline(always)]
+    pub fn extensions(&self) -> &Extensions {
+        &self.state.shared.extensions
+    }
+
+    /// Returns the request-local [`Arena`], which owns values allocated while
+    /// handling `self` and frees them when `self` is dropped.
+    ///
+    /// Unlike [`local_cache()`](Self::local_cache()), any number of values of
+    /// any type can be allocated. A cloned request receives a new, empty
+    /// arena.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let hello = request.arena().alloc_str("hello");
+    /// let goodbye = request.arena().alloc_str("goodbye");
+    /// assert_eq!((hello, goodbye), ("hello", "goodbye"));
+    /// ```
+    #[inline(always)]
+    pub fn arena(&self) -> &Arena {
+        &self.state.arena
+    }
+
+    /// Returns the [`BufferPolicy`] for bodies of `self` read into a
+    /// [`TempFile`]: the policy set via [`Request::set_buffer_policy()`], if
+    /// any, or the configured policy otherwise.
+    ///
+    /// [`TempFile`]: crate::fs::TempFile
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let policy = request.buffer_policy();
+    /// assert_eq!(policy.memory, 0);
+    /// ```
+    pub fn buffer_policy(&self) -> &BufferPolicy {
+        match self.extensions().get::<BufferPolicy>() {
+            Some(policy) => policy,
+            None => &self.rocket().config().buffer,
+        }
+    }
+
+    /// Sets the [`BufferPolicy`] for bodies of `self` read into a
+    /// [`TempFile`], overriding the configured policy. The policy can be set
+    /// at most once and must be set before the body is read, typically in a
+    /// request fairing or request guard. Returns `false` if a policy was
+    /// already set, in which case it is left unchanged.
+    ///
+    /// [`TempFile`]: crate::fs::TempFile
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{BufferPolicy, ToByteUnit};
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let policy = BufferPolicy { memory: 1.mebibytes(), ..Default::default() };
+    /// assert!(request.set_buffer_policy(policy.clone()));
+    /// assert_eq!(request.buffer_policy(), &policy);
+    ///
+    /// assert!(!request.set_buffer_policy(BufferPolicy::default()));
+    /// assert_eq!(request.buffer_policy(), &policy);
+    /// ```
+    pub fn set_buffer_policy(&self, policy: BufferPolicy) -> bool {
+        self.extensions().insert(policy)
+    }
+
+    /// Returns the [`ErrorReference`] assigned to `self` if it failed with a
+    /// server error, that is, a `5xx` status. Returns `None` otherwise.
+    ///
+    /// References are assigned before error catchers run, so catchers can
+    /// display them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(default)]
+    /// fn default(req: &Request<'_>) -> String {
+    ///     match req.error_reference() {
+    ///         Some(reference) => format!("server error: reference {}", reference),
+    ///         None => "client error".into(),
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn error_reference(&self) -> Option<ErrorReference> {
+        self.state.error_reference.try_get().copied()
+    }
+
+    /// Explains why the guard currently resolving will forward or fail.
+    ///
+    /// `reason` becomes the `reason` of the [`GuardFailure`] recorded when the
+    /// guard forwards or fails, taking precedence over the guard's error, if
+    /// any. It is discarded if the guard succeeds. Call this method right
+    /// before returning a `Forward` or `Error` outcome from a custom guard.
+    ///
+    /// Like guard failures themselves, explanations are only recorded in the
+    /// debug profile. Elsewhere, this method does nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::{self, Request, FromRequest};
+    /// use rocket::outcome::Outcome;
+    /// use rocket::http::Status;
+    ///
+    /// struct ApiKey<'r>(&'r str);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for ApiKey<'r> {
+    ///     type Error = ();
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+    ///         match req.headers().get_one("X-Api-Key") {
+    ///             Some(key) => Outcome::Success(ApiKey(key)),
+    ///             None => {
+    ///                 req.explain_failure("missing `X-Api-Key` header");
+    ///                 Outcome::Forward(Status::Unauthorized)
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn explain_failure<R: fmt::Display>(&self, reason: R) {
+        if self.explains_failures() {
+            let failures = self.local_cache(Failures::default);
+            *failures.explanation.lock() = Some(reason.to_string());
+        }
+    }
+
+    /// Returns the [`GuardFailure`]s recorded while routing `self`, in the
+    /// order they occurred.
+    ///
+    /// Failures are only recorded in the debug profile: elsewhere, the
+    /// returned vector is always empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(404)]
+    /// fn not_found(req: &Request<'_>) -> String {
+    ///     req.guard_failures()
+    ///         .iter()
+    ///         .map(|failure| format!("{failure}\n"))
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn guard_failures(&self) -> Vec<GuardFailure> {
+        match self.explains_failures() {
+            true => self.local_cache(Failures::default).failures.lock().clone(),
+            false => vec![],
+        }
+    }
+
+    /// Whether guard failures are recorded for `self`: only in the debug
+    /// profile.
+    #[inline]
+    fn explains_failures(&self) -> bool {
+        self.rocket().config().profile == Config::DEBUG_PROFILE
+    }
+
+    /// Returns the number of bytes of the request body read so far.
+    ///
+    /// Bytes are counted as they are read from the client, before any
+    /// [data fairings](crate::fairing::Fairing#data) or transforms apply, and
+    /// only once read: a handler that ignores the body reads no bytes. The
+    /// count is thus final in response fairings, making it suitable for access
+    /// logs and metrics. Totals across all requests are reported by
+    /// [`Rocket::load()`](crate::Rocket::load()).
+    ///
+    /// The size of a response body is known ahead of time when the body is
+    /// sized: response fairings can read it via
+    /// [`Body::preset_size()`](crate::response::Body::preset_size()), which
+    /// reflects any compression applied by earlier response fairings. The
+    /// number of bytes actually written is reported by
+    /// [`Request::body_bytes_written()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let access_log = AdHoc::on_response("Access Log", |req, res| Box::pin(async move {
+    ///     let sent = res.body().preset_size();
+    ///     let received = req.body_bytes_read();
+    ///     rocket::info!(uri = %req.uri(), received, sent, "request complete");
+    /// }));
+    /// ```
+    #[inline(always)]
+    pub fn body_bytes_read(&self) -> u64 {
+        self.state.shared.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes of the response body written to the
+    /// connection so far, after any compression.
+    ///
+    /// The response body is written only after response fairings run, so the
+    /// count is `0` in an [`on_response`](crate::fairing::Fairing::on_response())
+    /// callback. The final count is passed to callbacks registered via
+    /// [`ResponseGuard::on_complete_with_bytes()`], which response fairings
+    /// can retrieve via [`Request::guard()`]. Totals across all requests are
+    /// reported by [`Rocket::load()`](crate::Rocket::load()).
+    ///
+    /// [`ResponseGuard::on_complete_with_bytes()`]:
+    ///     crate::response::ResponseGuard::on_complete_with_bytes()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    /// use rocket::response::ResponseGuard;
+    ///
+    /// let access_log = AdHoc::on_response("Access Log", |req, _| Box::pin(async move {
+    ///     assert_eq!(req.body_bytes_written(), 0);
+    ///
+    ///     let (uri, received) = (req.uri().to_string(), req.body_bytes_read());
+    ///     let guard = req.guard::<ResponseGuard<'_>>().await.unwrap();
+    ///     guard.on_complete_with_bytes(move |completion, sent| {
+    ///         rocket::info!(uri = %uri, received, sent, ?completion, "request complete");
+    ///     });
+    /// }));
+    /// ```
+    #[inline(always)]
+    pub fn body_bytes_written(&self) -> u64 {
+        self.state.shared.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Assigns an [`ErrorReference`] to `self`, if it doesn't have one, and
+    /// returns it.
+    pub(crate) fn assign_error_reference(&self) -> ErrorReference {
+        *self.state.error_reference.get_or_init(ErrorReference::generate)
+    }
+
     /// Retrieves and parses into `T` the 0-indexed `n`th non-empty segment from
     /// the _routed_ request, that is, the `n`th segment _after_ the mount
     /// point. If the request has not been routed, then this is simply the `n`th
@@ -1062,6 +1527,58 @@ impl<'r> Request<'r> {
             .flatten()
     }
 
+    /// Records a [`GuardFailure`] for the guard of the currently routed route
+    /// described by the parameters, if failures are recorded. The reason is
+    /// the explanation given via [`Request::explain_failure()`], if any, or
+    /// `error` otherwise. Used by codegen.
+    pub fn record_guard_failure(
+        &self,
+        kind: GuardKind,
+        parameter: Option<&'static str>,
+        type_name: &'static str,
+        status: Status,
+        forwarded: bool,
+        error: Option<fmt::Arguments<'_>>,
+    ) {
+        if !self.explains_failures() {
+            return;
+        }
+
+        let failures = self.local_cache(Failures::default);
+        let explanation = failures.explanation.lock().take();
+        failures.failures.lock().push(GuardFailure {
+            route: self.route().and_then(|route| route.name.clone()),
+            kind,
+            parameter: parameter.map(Cow::Borrowed),
+            type_name: Cow::Borrowed(type_name),
+            status,
+            forwarded,
+            reason: explanation.or_else(|| error.map(|e| e.to_string())),
+        });
+    }
+
+    /// Discards the explanation given via [`Request::explain_failure()`] by a
+    /// guard that then succeeded. Used by codegen.
+    #[inline]
+    pub fn discard_failure_explanation(&self) {
+        if self.explains_failures() {
+            self.local_cache(Failures::default).explanation.lock().take();
+        }
+    }
+
+    /// Returns the router `self` is routed with: the router that was active
+    /// when this method was first called. Holding it keeps the router alive
+    /// even if routes are hot-mounted or unmounted in the meantime. Unless
+    /// routes are hot-mounted, this is the launch router, which is returned
+    /// without locking or reference counting.
+    #[inline]
+    pub(crate) fn router(&self) -> &Router<Finalized> {
+        let router = &self.rocket().router;
+        self.state.router.get_or_init(|| router.hot())
+            .as_deref()
+            .unwrap_or_else(|| router.launch())
+    }
+
     /// Set `self`'s parameters given that the route used to reach this request
     /// was `route`. Use during routing when attempting a given route.
     #[inline(always)]
@@ -1074,6 +1591,51 @@ impl<'r> Request<'r> {
         self.method.store(method)
     }
 
+    /// Rewrites `self`, a `HEAD` request, as a `GET` request for automatic
+    /// `HEAD` handling.
+    pub(crate) fn set_auto_head(&self) {
+        self._set_method(Method::Get);
+        self.extensions().insert(AutoHead);
+    }
+
+    /// Normalizes the path of `self` per the configured [`NormalizeConfig`],
+    /// remembering the URI as received, or rejects `self` if its path can't
+    /// be normalized.
+    ///
+    /// [`NormalizeConfig`]: crate::config::NormalizeConfig
+    pub(crate) fn normalize_uri(&mut self) {
+        match self.rocket().config().normalize.normalize(self.uri()) {
+            Ok(Some(normalized)) => {
+                self.extensions().insert(RawUri(self.uri.clone().into_owned()));
+                self.set_uri(normalized);
+            }
+            Ok(None) => {},
+            Err(violation) => self.reject(violation),
+        }
+    }
+
+    /// Marks `self` as rejected for `violation`, logging the rejection. The
+    /// request is answered by the catcher for the violation's status.
+    pub(crate) fn reject(&mut self, violation: Violation) {
+        warn!(
+            name: "request_rejection",
+            reason = %violation,
+            status = violation.status().code,
+            method = %self.method(),
+            uri = %self.rocket().config().redact.uri(self.uri()),
+            remote = self.remote().map(display),
+            "rejecting request: {violation}"
+        );
+
+        self.errors.push(RequestError::Rejected(violation));
+    }
+
+    /// Records the methods allowed for `self`, which is being answered with a
+    /// `405 Method Not Allowed`.
+    pub(crate) fn set_allowed_methods(&self, methods: Vec<Method>) {
+        self.extensions().insert(AllowedMethods(methods));
+    }
+
     pub(crate) fn cookies_mut(&mut self) -> &mut CookieJar<'r> {
         &mut self.state.cookies
     }
@@ -1136,6 +1698,11 @@ impl<'r> Request<'r> {
         // Set the passed in connection metadata.
         request.connection = connection;
 
+        // Reject ambiguous or oversized requests if strict validation is on.
+        if let Err(violation) = rocket.config().strict.check(hyper) {
+            request.reject(violation);
+        }
+
         // Determine + set host. On HTTP < 2, use the `HOST` header. Otherwise,
         // use the `:authority` pseudo-header which hyper makes part of the URI.
         // TODO: Use an `InitCell` to compute this later.
@@ -1180,6 +1747,22 @@ impl<'r> Request<'r> {
 pub(crate) enum RequestError {
     InvalidUri(hyper::Uri),
     BadMethod(hyper::Method),
+    Rejected(Violation),
+}
+
+impl RequestError {
+    /// The status to respond to the erroneous request with.
+    pub(crate) fn status(&self) -> Status {
+        match self {
+            RequestError::Rejected(violation) => violation.status(),
+            _ => Status::BadRequest,
+        }
+    }
+
+    /// Whether this is a rejection by strict validation or normalization.
+    pub(crate) fn is_rejection(&self) -> bool {
+        matches!(self, RequestError::Rejected(_))
+    }
 }
 
 impl fmt::Display for RequestError {
@@ -1187,6 +1770,7 @@ impl fmt::Display for RequestError {
         match self {
             RequestError::InvalidUri(u) => write!(f, "invalid origin URI: {}", u),
             RequestError::BadMethod(m) => write!(f, "invalid or unrecognized method: {}", m),
+            RequestError::Rejected(v) => write!(f, "rejected by strict validation: {}", v),
         }
     }
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use crate::request::{Request, ConnectionMeta};
 use crate::local::blocking::Client;
+use crate::http::Status;
+use hyper::header::{HeaderName, HeaderValue};
 
 macro_rules! assert_headers {
     ($($key:expr => [$($value:expr),+]),+) => ({
@@ -47,3 +49,51 @@ fn test_multiple_headers_merge_into_one_from_hyp() {
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "friend" => ["carol"]);
     assert_headers!("friend" => ["alice"], "friend" => ["bob"], "enemy" => ["carol"]);
 }
+
+fn strict_violation(headers: &[(&str, &str)]) -> Option<Status> {
+    use crate::config::{Config, StrictConfig};
+
+    let config = Config {
+        strict: StrictConfig { enabled: true, max_headers: 8, ..Default::default() },
+        ..Config::debug_default()
+    };
+
+    let mut req = hyper::Request::post("/test").body(()).unwrap();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).unwrap();
+        req.headers_mut().append(name, HeaderValue::from_str(value).unwrap());
+    }
+
+    let client = Client::debug(crate::custom(config)).unwrap();
+    let hyper = req.into_parts().0;
+    match Request::from_hyp(client.rocket(), &hyper, ConnectionMeta::default()) {
+        Ok(_) => None,
+        Err(req) => Some(req.errors[0].status()),
+    }
+}
+
+#[test]
+fn test_strict_validation() {
+    assert_eq!(strict_violation(&[("content-length", "10")]), None);
+    assert_eq!(strict_violation(&[("transfer-encoding", "Chunked")]), None);
+
+    let ambiguous = [("content-length", "10"), ("transfer-encoding", "chunked")];
+    assert_eq!(strict_violation(&ambiguous), Some(Status::BadRequest));
+
+    let duplicate = [("content-length", "10"), ("content-length", "10")];
+    assert_eq!(strict_violation(&duplicate), Some(Status::BadRequest));
+
+    let chained = [("transfer-encoding", "gzip"), ("transfer-encoding", "chunked")];
+    assert_eq!(strict_violation(&chained), Some(Status::BadRequest));
+
+    assert_eq!(strict_violation(&[("content-length", "+10")]), Some(Status::BadRequest));
+    assert_eq!(strict_violation(&[("content-length", "")]), Some(Status::BadRequest));
+    assert_eq!(strict_violation(&[("transfer-encoding", "gzip")]), Some(Status::BadRequest));
+
+    let many = [("x-a", "a"); 9];
+    assert_eq!(strict_violation(&many), Some(Status::RequestHeaderFieldsTooLarge));
+
+    let large = "a".repeat(64 * 1024);
+    let status = strict_violation(&[("x-a", large.as_str())]);
+    assert_eq!(status, Some(Status::RequestHeaderFieldsTooLarge));
+}
@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::{ContentDisposition, ContentType};
+
+/// Responds with a `Content-Disposition: attachment` header, prompting the
+/// client to download the response as a file.
+///
+/// The filename is encoded as described in
+/// [`ContentDisposition::with_filename()`], so it may contain any characters.
+/// If the wrapped responder does not set a `Content-Type` or sets the generic
+/// `application/octet-stream`, as `Vec<u8>` does, the `Content-Type` is chosen
+/// based on the filename's extension via [`ContentType::from_extension()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::Attachment;
+///
+/// #[get("/report")]
+/// fn report() -> Attachment<Vec<u8>> {
+///     # let csv_bytes = vec![];
+///     Attachment::new("Quarterly Report – Q3.csv", csv_bytes)
+/// }
+/// ```
+///
+/// The response will include the following headers:
+///
+/// ```text
+/// Content-Type: text/csv; charset=utf-8
+/// Content-Disposition: attachment; filename="Quarterly Report _ Q3.csv";
+///     filename*=UTF-8''Quarterly%20Report%20%E2%80%93%20Q3.csv
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment<R> {
+    filename: String,
+    responder: R,
+}
+
+impl<R> Attachment<R> {
+    /// Responds with `responder` as an attachment named `filename`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Attachment;
+    ///
+    /// let attachment = Attachment::new("hello.txt", "Hello, world!");
+    /// ```
+    pub fn new<S: Into<String>>(filename: S, responder: R) -> Self {
+        Attachment { filename: filename.into(), responder }
+    }
+
+    /// The name of the attachment.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Attachment<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.responder.respond_to(req)?;
+        if response.content_type().unwrap_or(ContentType::Binary) == ContentType::Binary {
+            let ext = Path::new(&self.filename).extension().map(|e| e.to_string_lossy());
+            if let Some(ct) = ext.and_then(|ext| ContentType::from_extension(&ext)) {
+                response.set_header(ct);
+            }
+        }
+
+        response.set_header(ContentDisposition::attachment(&self.filename));
+        Ok(response)
+    }
+}
@@ -107,6 +107,15 @@ impl<'r> Body<'r> {
     /// The present value is `4096`.
     pub const DEFAULT_MAX_CHUNK: usize = 4096;
 
+    /// The default max size, in bytes, of chunks for file responses.
+    ///
+    /// Files are read in larger chunks than other bodies to reduce the number
+    /// of reads, each of which is a round-trip to the blocking thread pool,
+    /// and of writes to the network when serving large files.
+    ///
+    /// The present value is `65536`.
+    pub const FILE_MAX_CHUNK: usize = 64 * 1024;
+
     pub(crate) fn unsized_none() -> Self {
         Body {
             size: None,
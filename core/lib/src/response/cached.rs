@@ -0,0 +1,148 @@
+use std::borrow::Cow;
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+
+/// Sets `Cache-Control` and `Vary` headers on the response of a wrapped
+/// responder.
+///
+/// `Cached` is typically used indirectly via the [`#[cache]`](crate::cache)
+/// route attribute, which wraps the value returned by a handler in a `Cached`,
+/// but it can also be used directly.
+///
+/// The headers are only set on responses that are not errors, that is, whose
+/// status is not `4xx` or `5xx`. A `Cache-Control` header already set by the
+/// wrapped responder takes precedence. Header names passed to
+/// [`Cached::vary()`] are merged into any existing `Vary` header.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::Cached;
+///
+/// #[get("/greeting")]
+/// fn greeting() -> Cached<&'static str> {
+///     Cached::new("Hello!").max_age(300).public().vary("Accept-Language")
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached<R> {
+    responder: R,
+    directives: Vec<Cow<'static, str>>,
+    vary: Vec<Cow<'static, str>>,
+}
+
+impl<R> Cached<R> {
+    /// Wraps `responder` without adding any directives.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Cached;
+    ///
+    /// let cached = Cached::new("Hello!");
+    /// ```
+    pub fn new(responder: R) -> Self {
+        Cached { responder, directives: vec![], vary: vec![] }
+    }
+
+    fn directive<D: Into<Cow<'static, str>>>(mut self, directive: D) -> Self {
+        self.directives.push(directive.into());
+        self
+    }
+
+    /// Adds a `max-age` directive: the response is fresh for `seconds`.
+    pub fn max_age(self, seconds: u32) -> Self {
+        self.directive(format!("max-age={seconds}"))
+    }
+
+    /// Adds an `s-maxage` directive: the response is fresh in shared caches
+    /// for `seconds`, overriding `max-age`.
+    pub fn s_maxage(self, seconds: u32) -> Self {
+        self.directive(format!("s-maxage={seconds}"))
+    }
+
+    /// Adds a `public` directive: the response may be stored by shared caches.
+    pub fn public(self) -> Self {
+        self.directive("public")
+    }
+
+    /// Adds a `private` directive: the response may only be stored by the
+    /// client's private cache.
+    pub fn private(self) -> Self {
+        self.directive("private")
+    }
+
+    /// Adds a `no-cache` directive: the response must be revalidated before
+    /// every reuse.
+    pub fn no_cache(self) -> Self {
+        self.directive("no-cache")
+    }
+
+    /// Adds a `no-store` directive: the response must not be stored by any
+    /// cache.
+    pub fn no_store(self) -> Self {
+        self.directive("no-store")
+    }
+
+    /// Adds a `must-revalidate` directive: a stale response must not be reused
+    /// without successful revalidation.
+    pub fn must_revalidate(self) -> Self {
+        self.directive("must-revalidate")
+    }
+
+    /// Adds an `immutable` directive: the response will not change while it is
+    /// fresh.
+    pub fn immutable(self) -> Self {
+        self.directive("immutable")
+    }
+
+    /// Adds `name` to the `Vary` header: the response depends on the value of
+    /// the request header `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Cached;
+    ///
+    /// let cached = Cached::new("Hello!").vary("Accept-Language").vary("Accept");
+    /// ```
+    pub fn vary<N: Into<Cow<'static, str>>>(mut self, name: N) -> Self {
+        self.vary.push(name.into());
+        self
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Cached<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.responder.respond_to(req)?;
+        let status = response.status();
+        if status.class().is_client_error() || status.class().is_server_error() {
+            return Ok(response);
+        }
+
+        if !self.directives.is_empty() && !response.headers().contains("Cache-Control") {
+            response.set_raw_header("Cache-Control", self.directives.join(", "));
+        }
+
+        if !self.vary.is_empty() {
+            let mut names: Vec<String> = response.headers().get("Vary")
+                .flat_map(|value| value.split(','))
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect();
+
+            for name in self.vary {
+                if !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                    names.push(name.into_owned());
+                }
+            }
+
+            response.set_raw_header("Vary", names.join(", "));
+        }
+
+        Ok(response)
+    }
+}
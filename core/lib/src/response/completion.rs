@@ -0,0 +1,207 @@
+use std::fmt;
+use std::io;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::task::Poll;
+
+use parking_lot::Mutex;
+use tokio::io::ReadBuf;
+
+use crate::request::{self, FromRequest, Request};
+use crate::response::Response;
+use crate::http::{Method, Status};
+
+/// How the writing of a response's body ended.
+///
+/// Passed to callbacks registered via [`ResponseGuard::on_complete()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Completion {
+    /// The body was written in its entirety, or the response has no body.
+    Written,
+    /// The connection was closed, or the response discarded, before the body
+    /// was written in its entirety.
+    Aborted,
+    /// Reading the body failed. The client received a truncated body.
+    Failed,
+}
+
+/// A request guard that registers callbacks to run once the response to the
+/// request has been completely written, or has failed to be.
+///
+/// Fairings' [`on_response`](crate::fairing::Fairing::on_response()) callbacks
+/// run _before_ the response body is written. Work that must only happen once
+/// the client has received the body, such as marking a download as delivered
+/// or removing a temporary file the body is streamed from, instead belongs in
+/// an `on_complete()` callback:
+///
+///   * Callbacks run exactly once, after the last byte of the body has been
+///     handed to the connection, the connection has been closed, or reading
+///     the body has failed, whichever happens first.
+///   * Callbacks receive a [`Completion`] indicating which of these happened.
+///     Note that [`Completion::Written`] does not imply that the client has
+///     received the body; the connection may still fail while flushing it.
+///   * Responses without a body, that is, responses to `HEAD` requests and
+///     responses with a `1xx`, `204 No Content`, or `304 Not Modified`
+///     status, including protocol upgrades, complete as
+///     [`Completion::Written`] once they are sent.
+///   * Callbacks run in the order they were registered, regardless of the
+///     response's status, including when a catcher produced the response.
+///   * Callbacks are synchronous and should not block. Asynchronous work can
+///     be spawned via [`TaskSet::spawn()`](crate::task::TaskSet::spawn()). A
+///     callback that panics is logged; the remaining callbacks still run.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::fs::NamedFile;
+/// use rocket::response::{Completion, ResponseGuard};
+///
+/// # fn mark_delivered(_: &str) { }
+/// #[get("/export/<id>")]
+/// async fn export(id: &str, guard: ResponseGuard<'_>) -> Option<NamedFile> {
+///     let path = std::env::temp_dir().join(id);
+///     let file = NamedFile::open(&path).await.ok()?;
+///     let id = id.to_string();
+///     guard.on_complete(move |completion| {
+///         if completion == Completion::Written {
+///             mark_delivered(&id);
+///             let _ = std::fs::remove_file(path);
+///         }
+///     });
+///
+///     Some(file)
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ResponseGuard<'r> {
+    hooks: &'r CompletionHooks,
+}
+
+type Hook = Box<dyn FnOnce(Completion, u64) + Send + 'static>;
+
+/// The completion callbacks registered for a request.
+#[derive(Default)]
+pub(crate) struct CompletionHooks {
+    hooks: Mutex<Vec<Hook>>,
+}
+
+impl ResponseGuard<'_> {
+    /// Registers `f` to be called with the [`Completion`] of the response once
+    /// its body has been written or writing it has ended prematurely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::ResponseGuard;
+    ///
+    /// #[get("/")]
+    /// fn index(guard: ResponseGuard<'_>) -> &'static str {
+    ///     guard.on_complete(|completion| rocket::info!(?completion, "response complete"));
+    ///     "Hello, world!"
+    /// }
+    /// ```
+    pub fn on_complete<F>(&self, f: F)
+        where F: FnOnce(Completion) + Send + 'static
+    {
+        self.on_complete_with_bytes(move |completion, _| f(completion));
+    }
+
+    /// Like [`ResponseGuard::on_complete()`], but `f` is additionally called
+    /// with the number of bytes of the response body written to the
+    /// connection. See [`Request::body_bytes_written()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::ResponseGuard;
+    ///
+    /// #[get("/")]
+    /// fn index(guard: ResponseGuard<'_>) -> &'static str {
+    ///     guard.on_complete_with_bytes(|completion, bytes| {
+    ///         rocket::info!(?completion, bytes, "response complete");
+    ///     });
+    ///
+    ///     "Hello, world!"
+    /// }
+    /// ```
+    pub fn on_complete_with_bytes<F>(&self, f: F)
+        where F: FnOnce(Completion, u64) + Send + 'static
+    {
+        self.hooks.hooks.lock().push(Box::new(f));
+    }
+}
+
+impl CompletionHooks {
+    /// Runs, and removes, every registered callback with `completion` and
+    /// `bytes_written`, the number of response body bytes written.
+    pub(crate) fn run(&self, completion: Completion, bytes_written: u64) {
+        let hooks = std::mem::take(&mut *self.hooks.lock());
+        for hook in hooks {
+            if let Err(panic) = catch_unwind(AssertUnwindSafe(|| hook(completion, bytes_written))) {
+                let panic = crate::task::panic_message(&*panic);
+                error!(panic, ?completion, "response completion callback panicked");
+            }
+        }
+    }
+}
+
+impl Completion {
+    /// Returns [`Completion::Written`] if `response`, the response to
+    /// `request`, has no body to write, either because `request` is a `HEAD`
+    /// request or because `response`'s status precludes a body.
+    pub(crate) fn of_bodiless(request: &Request<'_>, response: &Response<'_>) -> Option<Self> {
+        let is_head = request.method() == Method::Head || request.is_auto_head();
+        let status = response.status();
+        let bodiless = status.class().is_informational()
+            || status == Status::NoContent
+            || status == Status::NotModified;
+
+        (is_head || bodiless).then_some(Completion::Written)
+    }
+
+    /// Returns the completion implied by the `result` of reading a body into
+    /// `buf`, which held `filled` bytes before the read, if it implies one.
+    pub(crate) fn of_read(
+        result: &Poll<io::Result<()>>,
+        filled: usize,
+        buf: &ReadBuf<'_>
+    ) -> Option<Self> {
+        match result {
+            Poll::Ready(Err(_)) => Some(Completion::Failed),
+            Poll::Ready(Ok(())) if buf.filled().len() == filled && buf.remaining() > 0 => {
+                Some(Completion::Written)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the completion implied by the `result` of reading a body in
+    /// its entirety.
+    pub(crate) fn of_result<T>(result: &io::Result<T>) -> Self {
+        match result {
+            Ok(_) => Completion::Written,
+            Err(_) => Completion::Failed,
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for ResponseGuard<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(ResponseGuard {
+            hooks: &req.state.shared.completion,
+        })
+    }
+}
+
+impl fmt::Debug for ResponseGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseGuard")
+            .field("callbacks", &self.hooks.hooks.lock().len())
+            .finish()
+    }
+}
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+
+use time::OffsetDateTime;
+
+use crate::request::Request;
+use crate::response::{self, Responder, Response};
+use crate::http::{Deprecation, Sunset, Link};
+
+/// Marks the response of a wrapped responder as deprecated.
+///
+/// `Deprecated` sets the following headers on the response of the wrapped
+/// responder, regardless of its status:
+///
+///   * `Deprecation`: the date the resource was or will be deprecated, as in
+///     [`Deprecation`].
+///   * `Sunset`: if set with [`Deprecated::sunset()`], the date after which
+///     the resource is expected to stop responding, as in [`Sunset`].
+///   * `Link`: if set with [`Deprecated::successor()`], a link to the
+///     resource's replacement with `rel=successor-version`, adjoined to any
+///     existing `Link` header.
+///
+/// A `Deprecation` header already set by the wrapped responder takes
+/// precedence: no headers are set when one is present.
+///
+/// When the dates are static, prefer the `deprecated` route attribute
+/// argument, which additionally makes the deprecation visible in the route's
+/// [metadata](crate::Route::deprecated) and in Rocket's startup report:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/v1/users", deprecated(
+///     since = "2024-06-01",
+///     sunset = "2025-06-01",
+///     successor = "/v2/users"
+/// ))]
+/// fn users() -> &'static str {
+///     "[]"
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::Deprecated;
+/// use rocket::time::macros::datetime;
+///
+/// #[get("/v1/users/<id>")]
+/// fn user(id: usize) -> Deprecated<String> {
+///     Deprecated::new(datetime!(2024-06-01 0:00 UTC), format!("user {id}"))
+///         .sunset(datetime!(2025-06-01 0:00 UTC))
+///         .successor(format!("/v2/users/{id}"))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecated<R> {
+    /// The date the resource was or will be deprecated.
+    pub since: OffsetDateTime,
+    /// The date after which the resource is expected to stop responding.
+    pub sunset: Option<OffsetDateTime>,
+    /// The URI of the resource's replacement.
+    pub successor: Option<Cow<'static, str>>,
+    /// The wrapped responder.
+    pub responder: R,
+}
+
+impl<R> Deprecated<R> {
+    /// Wraps `responder`, marking it deprecated as of `since`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Deprecated;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let deprecated = Deprecated::new(datetime!(2024-06-01 0:00 UTC), "Hello!");
+    /// ```
+    pub fn new<T: Into<OffsetDateTime>>(since: T, responder: R) -> Self {
+        Deprecated { since: since.into(), sunset: None, successor: None, responder }
+    }
+
+    /// Sets the date after which the resource is expected to stop responding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Deprecated;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let deprecated = Deprecated::new(datetime!(2024-06-01 0:00 UTC), "Hello!")
+    ///     .sunset(datetime!(2025-06-01 0:00 UTC));
+    /// ```
+    pub fn sunset<T: Into<OffsetDateTime>>(mut self, sunset: T) -> Self {
+        self.sunset = Some(sunset.into());
+        self
+    }
+
+    /// Sets the URI of the resource's replacement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Deprecated;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let deprecated = Deprecated::new(datetime!(2024-06-01 0:00 UTC), "Hello!")
+    ///     .successor("/v2/hello");
+    /// ```
+    pub fn successor<U: ToString>(mut self, uri: U) -> Self {
+        self.successor = Some(uri.to_string().into());
+        self
+    }
+
+    /// Returns `true` if the sunset date is set and has passed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Deprecated;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let deprecated = Deprecated::new(datetime!(2014-06-01 0:00 UTC), "Hello!");
+    /// assert!(!deprecated.is_sunset());
+    ///
+    /// let deprecated = deprecated.sunset(datetime!(2015-06-01 0:00 UTC));
+    /// assert!(deprecated.is_sunset());
+    /// ```
+    pub fn is_sunset(&self) -> bool {
+        self.sunset.map_or(false, |sunset| sunset <= OffsetDateTime::now_utc())
+    }
+
+    /// Sets the deprecation headers on `response` unless it already has a
+    /// `Deprecation` header.
+    pub(crate) fn set_headers(&self, response: &mut Response<'_>) {
+        if response.headers().contains("Deprecation") {
+            return;
+        }
+
+        response.set_header(Deprecation(self.since));
+        if let Some(sunset) = self.sunset {
+            response.set_header(Sunset(sunset));
+        }
+
+        if let Some(successor) = &self.successor {
+            response.adjoin_header(Link::new().link(successor, "successor-version"));
+        }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Deprecated<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let Deprecated { since, sunset, successor, responder } = self;
+        let mut response = responder.respond_to(req)?;
+        Deprecated { since, sunset, successor, responder: () }.set_headers(&mut response);
+        Ok(response)
+    }
+}
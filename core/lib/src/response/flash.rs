@@ -1,11 +1,14 @@
+use std::fmt;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use time::Duration;
-use serde::ser::{Serialize, Serializer, SerializeStruct};
+use serde::ser::{Serialize, Serializer, SerializeStruct, SerializeSeq};
 
 use crate::outcome::IntoOutcome;
 use crate::response::{self, Responder};
 use crate::request::{self, Request, FromRequest};
 use crate::http::{Status, Cookie, CookieJar};
-use std::sync::atomic::{AtomicBool, Ordering};
 
 // The name of the actual flash cookie.
 const FLASH_COOKIE_NAME: &str = "_flash";
@@ -13,6 +16,12 @@ const FLASH_COOKIE_NAME: &str = "_flash";
 // Character to use as a delimiter after the cookie's name's length.
 const FLASH_COOKIE_DELIM: char = ':';
 
+// Character prefixing cookies carrying several messages or a payload.
+const FLASH_COOKIE_MULTI: char = '!';
+
+// Character standing in for a missing payload in a multi-message cookie.
+const FLASH_COOKIE_NONE: char = '-';
+
 /// Sets a "flash" cookie that will be removed when it is accessed. The
 /// analogous request type is [`FlashMessage`].
 ///
@@ -34,6 +43,14 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// Messages can be retrieved on the request side via the [`FlashMessage`] type
 /// and the [kind](#method.kind) and [message](#method.message) methods.
 ///
+/// A single `Flash` can carry several messages, added with
+/// [`Flash::and()`], each optionally with a structured payload, attached with
+/// [`Flash::with_payload()`] when the `json` feature is enabled. All messages
+/// are retrieved, typed, via the [`FlashMessages`] request guard. Kinds can be
+/// given as a [`Severity`], and retrieved as one via [`Flashed::severity()`].
+///
+/// [`FlashMessages`]: crate::request::FlashMessages
+///
 /// # Response
 ///
 /// The `Responder` implementation for `Flash` sets the message cookie and then
@@ -75,10 +92,87 @@ const FLASH_COOKIE_DELIM: char = ':';
 /// receive the standard welcome message.
 #[derive(Debug)]
 pub struct Flash<R> {
+    messages: Vec<Flashed>,
+    consumed: AtomicBool,
+    inner: R,
+}
+
+/// The severity of a flash message.
+///
+/// A `Severity` can be used wherever a [`Flash`] kind is expected. It
+/// converts to and from the kinds used by the [`Flash::success()`],
+/// [`Flash::warning()`], and [`Flash::error()`] constructors, with the
+/// addition of `"info"`. On the request side, [`Flashed::severity()`] parses
+/// a message's kind as a `Severity`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::response::{Flash, Redirect, Severity};
+///
+/// # #[allow(unused_variables)]
+/// let flash = Flash::new(Redirect::to("/"), Severity::Info, "Welcome back!");
+/// assert_eq!(Severity::Warning.as_str(), "warning");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// An informational message: `"info"`.
+    Info,
+    /// A success message: `"success"`.
+    Success,
+    /// A warning message: `"warning"`.
+    Warning,
+    /// An error message: `"error"`.
+    Error,
+}
+
+/// A single message retrieved from a flash cookie.
+///
+/// Yielded by the [`FlashMessages`](crate::request::FlashMessages) request
+/// guard. See [`Flash`] for details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flashed {
     kind: String,
     message: String,
+    payload: Option<String>,
+}
+
+/// Request guard retrieving all [`Flash`] messages, typed, in the order they
+/// were added.
+///
+/// # Flash Cookie
+///
+/// The guard always succeeds: if there is no flash cookie, or it is
+/// malformed, no messages are yielded. As with [`FlashMessage`], the flash
+/// cookie is only cleared once the messages are accessed via
+/// [`FlashMessages::iter()`], [`FlashMessages::into_vec()`], or serialization.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::{Flash, Redirect, Severity};
+/// use rocket::request::FlashMessages;
+///
+/// #[post("/save")]
+/// fn save() -> Flash<Redirect> {
+///     Flash::success(Redirect::to(uri!(index)), "Saved.")
+///         .and(Severity::Warning, "Your password expires in 3 days.")
+/// }
+///
+/// #[get("/")]
+/// fn index(flashes: FlashMessages<'_>) -> String {
+///     flashes.iter()
+///         .filter(|flash| flash.severity() >= Some(Severity::Warning))
+///         .map(|flash| flash.message())
+///         .collect::<Vec<_>>()
+///         .join("\n")
+/// }
+/// ```
+pub struct FlashMessages<'r> {
+    messages: Vec<Flashed>,
     consumed: AtomicBool,
-    inner: R,
+    jar: &'r CookieJar<'r>,
 }
 
 /// Type alias to retrieve [`Flash`] messages from a request.
@@ -113,13 +207,65 @@ impl<R> Flash<R> {
     /// ```
     pub fn new<K: Into<String>, M: Into<String>>(res: R, kind: K, message: M) -> Flash<R> {
         Flash {
-            kind: kind.into(),
-            message: message.into(),
+            messages: vec![Flashed::new(kind, message)],
             consumed: AtomicBool::default(),
             inner: res,
         }
     }
 
+    /// Adds another message with the given `kind` and `message`. All messages
+    /// are retrieved by the [`FlashMessages`](crate::request::FlashMessages)
+    /// guard; [`FlashMessage`] only retrieves the first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flash, Severity};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let message = Flash::success(Redirect::to("/"), "Profile updated.")
+    ///     .and(Severity::Info, "Changes may take a minute to appear.");
+    /// ```
+    pub fn and<K: Into<String>, M: Into<String>>(mut self, kind: K, message: M) -> Self {
+        self.messages.push(Flashed::new(kind, message));
+        self
+    }
+
+    /// Attaches `payload`, serialized as JSON, to the most recently added
+    /// message. It can be deserialized on the request side via
+    /// [`Flashed::payload()`]. If `payload` fails to serialize, an error is
+    /// logged and no payload is attached.
+    ///
+    /// Keep payloads small: all messages and payloads must fit in a single
+    /// cookie, which browsers limit to about 4KiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Redirect, Flash};
+    /// use rocket::serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// # #[serde(crate = "rocket::serde")]
+    /// struct Undo { id: u64 }
+    ///
+    /// # #[allow(unused_variables)]
+    /// let message = Flash::success(Redirect::to("/"), "Item deleted.")
+    ///     .with_payload(&Undo { id: 7 });
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn with_payload<T: Serialize + ?Sized>(mut self, payload: &T) -> Self {
+        match serde_json::to_string(payload) {
+            Ok(json) => if let Some(last) = self.messages.last_mut() {
+                last.payload = Some(json);
+            },
+            Err(e) => error!("failed to serialize flash payload: {e}"),
+        }
+
+        self
+    }
+
     /// Constructs a "success" `Flash` message with the given `responder` and
     /// `message`.
     ///
@@ -175,15 +321,201 @@ impl<R> Flash<R> {
     }
 
     fn cookie(&self) -> Cookie<'static> {
-        let content = format!("{}{}{}{}",
-            self.kind.len(), FLASH_COOKIE_DELIM, self.kind, self.message);
-
-        Cookie::build((FLASH_COOKIE_NAME, content))
+        Cookie::build((FLASH_COOKIE_NAME, encode(&self.messages)))
             .max_age(Duration::minutes(5))
             .build()
     }
 }
 
+/// Encodes `messages` as the contents of a flash cookie. A lone message
+/// without a payload uses the original `{len}:{kind}{message}` format. All
+/// others are prefixed with `!` followed by each message's kind, message, and
+/// payload, each as `{len}:{value}` or, for a missing payload, `-`.
+fn encode(messages: &[Flashed]) -> String {
+    if let [Flashed { kind, message, payload: None }] = messages {
+        return format!("{}{}{}{}", kind.len(), FLASH_COOKIE_DELIM, kind, message);
+    }
+
+    let mut content = String::from(FLASH_COOKIE_MULTI);
+    for flashed in messages {
+        let fields = [Some(&*flashed.kind), Some(&*flashed.message), flashed.payload.as_deref()];
+        for field in fields {
+            match field {
+                Some(field) => {
+                    use std::fmt::Write;
+                    let _ = write!(content, "{}{}{}", field.len(), FLASH_COOKIE_DELIM, field);
+                }
+                None => content.push(FLASH_COOKIE_NONE),
+            }
+        }
+    }
+
+    content
+}
+
+/// Decodes the contents of a flash cookie encoded by [`encode()`]. Returns
+/// `None` if the contents are malformed.
+fn decode(content: &str) -> Option<Vec<Flashed>> {
+    fn field(content: &mut &str) -> Option<String> {
+        let (len, rest) = content.split_once(FLASH_COOKIE_DELIM)?;
+        let len = len.parse::<usize>().ok()?;
+        let value = rest.get(..len)?;
+        *content = &rest[len..];
+        Some(value.to_string())
+    }
+
+    let Some(mut rest) = content.strip_prefix(FLASH_COOKIE_MULTI) else {
+        let (len, kv) = content.split_once(FLASH_COOKIE_DELIM)?;
+        let i = len.parse::<usize>().ok()?;
+        let (kind, message) = (kv.get(..i)?, kv.get(i..)?);
+        return Some(vec![Flashed::new(kind, message)]);
+    };
+
+    let mut messages = vec![];
+    while !rest.is_empty() {
+        let (kind, message) = (field(&mut rest)?, field(&mut rest)?);
+        let payload = match rest.strip_prefix(FLASH_COOKIE_NONE) {
+            Some(remaining) => { rest = remaining; None }
+            None => Some(field(&mut rest)?),
+        };
+
+        messages.push(Flashed { kind, message, payload });
+    }
+
+    (!messages.is_empty()).then_some(messages)
+}
+
+impl Severity {
+    /// Returns the flash kind corresponding to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Severity;
+    ///
+    /// assert_eq!(Severity::Info.as_str(), "info");
+    /// assert_eq!(Severity::Error.as_str(), "error");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Success => "success",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    /// Parses a flash kind as a `Severity`, returning `None` for any kind
+    /// other than `info`, `success`, `warning`, and `error`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Severity;
+    ///
+    /// assert_eq!(Severity::from_kind("warning"), Some(Severity::Warning));
+    /// assert_eq!(Severity::from_kind("suggestion"), None);
+    /// ```
+    pub fn from_kind(kind: &str) -> Option<Severity> {
+        match kind {
+            "info" => Some(Severity::Info),
+            "success" => Some(Severity::Success),
+            "warning" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl From<Severity> for String {
+    fn from(severity: Severity) -> Self {
+        severity.as_str().into()
+    }
+}
+
+impl Serialize for Severity {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self.as_str())
+    }
+}
+
+impl Flashed {
+    fn new<K: Into<String>, M: Into<String>>(kind: K, message: M) -> Self {
+        Flashed { kind: kind.into(), message: message.into(), payload: None }
+    }
+
+    /// Returns the `kind` of this message.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns the [`Severity`] of this message, or `None` if its kind is not
+    /// one of the severities.
+    pub fn severity(&self) -> Option<Severity> {
+        Severity::from_kind(&self.kind)
+    }
+
+    /// Returns the `message` contents of this message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Deserializes the payload attached via [`Flash::with_payload()`], if
+    /// any, as a `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::request::FlashMessages;
+    /// use rocket::serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// # #[serde(crate = "rocket::serde")]
+    /// struct Undo { id: u64 }
+    ///
+    /// #[get("/")]
+    /// fn index(flashes: FlashMessages<'_>) -> Option<String> {
+    ///     let undo = flashes.iter().find_map(|flash| flash.payload::<Undo>()?.ok())?;
+    ///     Some(format!("deleted item {}", undo.id))
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(nightly, doc(cfg(feature = "json")))]
+    pub fn payload<T>(&self) -> Option<Result<T, serde_json::Error>>
+        where T: serde::de::DeserializeOwned
+    {
+        self.payload.as_deref().map(serde_json::from_str)
+    }
+}
+
+impl Serialize for Flashed {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut flash = ser.serialize_struct("Flashed", 4)?;
+        flash.serialize_field("kind", self.kind())?;
+        flash.serialize_field("severity", &self.severity())?;
+        flash.serialize_field("message", self.message())?;
+
+        #[cfg(feature = "json")] {
+            let payload = self.payload.as_deref()
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok());
+
+            flash.serialize_field("payload", &payload)?;
+        }
+
+        #[cfg(not(feature = "json"))]
+        flash.serialize_field("payload", &None::<()>)?;
+
+        flash.end()
+    }
+}
+
 /// Sets the message cookie and then uses the wrapped responder to complete the
 /// response. In other words, simply sets a cookie and delegates the rest of the
 /// response handling to the wrapped responder. As a result, the `Outcome` of
@@ -198,15 +530,19 @@ impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Flash<R> {
 impl<'r> FlashMessage<'r> {
     /// Constructs a new message with the given name and message for the given
     /// request.
-    fn named<S: Into<String>>(kind: S, message: S, req: &'r Request<'_>) -> Self {
+    fn named(messages: Vec<Flashed>, req: &'r Request<'_>) -> Self {
         Flash {
-            kind: kind.into(),
-            message: message.into(),
+            messages,
             consumed: AtomicBool::new(false),
             inner: req.cookies(),
         }
     }
 
+    fn first(&self) -> &Flashed {
+        // Decoding never yields an empty list of messages.
+        &self.messages[0]
+    }
+
     // Clears the request cookie if it hasn't already been cleared.
     fn clear_cookie_if_needed(&self) {
         // Remove the cookie if it hasn't already been removed.
@@ -216,21 +552,62 @@ impl<'r> FlashMessage<'r> {
     }
 
     /// Returns a tuple of `(kind, message)`, consuming `self`.
-    pub fn into_inner(self) -> (String, String) {
+    pub fn into_inner(mut self) -> (String, String) {
         self.clear_cookie_if_needed();
-        (self.kind, self.message)
+        let first = self.messages.swap_remove(0);
+        (first.kind, first.message)
     }
 
     /// Returns the `kind` of this message.
     pub fn kind(&self) -> &str {
         self.clear_cookie_if_needed();
-        &self.kind
+        &self.first().kind
     }
 
     /// Returns the `message` contents of this message.
     pub fn message(&self) -> &str {
         self.clear_cookie_if_needed();
-        &self.message
+        &self.first().message
+    }
+}
+
+impl FlashMessages<'_> {
+    // Clears the request cookie if it hasn't already been cleared.
+    fn clear_cookie_if_needed(&self) {
+        if !self.consumed.swap(true, Ordering::Relaxed) && !self.messages.is_empty() {
+            self.jar.remove(FLASH_COOKIE_NAME);
+        }
+    }
+
+    /// Returns the number of messages. Does not clear the flash cookie.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if there are no messages. Does not clear the flash
+    /// cookie.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Returns an iterator over the messages, clearing the flash cookie.
+    pub fn iter(&self) -> impl Iterator<Item = &Flashed> + '_ {
+        self.clear_cookie_if_needed();
+        self.messages.iter()
+    }
+
+    /// Returns the messages, clearing the flash cookie.
+    pub fn into_vec(self) -> Vec<Flashed> {
+        self.clear_cookie_if_needed();
+        self.messages
+    }
+}
+
+impl fmt::Debug for FlashMessages<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlashMessages")
+            .field("messages", &self.messages)
+            .finish()
     }
 }
 
@@ -244,19 +621,29 @@ impl<'r> FromRequest<'r> for FlashMessage<'r> {
     type Error = ();
 
     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
-        req.cookies().get(FLASH_COOKIE_NAME).ok_or(()).and_then(|cookie| {
-            // Parse the flash message.
-            let content = cookie.value();
-            let (len_str, kv) = match content.find(FLASH_COOKIE_DELIM) {
-                Some(i) => (&content[..i], &content[(i + 1)..]),
-                None => return Err(()),
-            };
-
-            match len_str.parse::<usize>() {
-                Ok(i) if i <= kv.len() => Ok(Flash::named(&kv[..i], &kv[i..], req)),
-                _ => Err(())
-            }
-        }).or_error(Status::BadRequest)
+        req.cookies().get(FLASH_COOKIE_NAME)
+            .and_then(|cookie| decode(cookie.value()))
+            .map(|messages| Flash::named(messages, req))
+            .or_error((Status::BadRequest, ()))
+    }
+}
+
+/// Retrieves all flash messages from the flash cookie. Never fails: if there
+/// is no flash cookie, or if it is malformed, there are no messages.
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for FlashMessages<'r> {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let messages = req.cookies().get(FLASH_COOKIE_NAME)
+            .and_then(|cookie| decode(cookie.value()))
+            .unwrap_or_default();
+
+        request::Outcome::Success(FlashMessages {
+            messages,
+            consumed: AtomicBool::new(false),
+            jar: req.cookies(),
+        })
     }
 }
 
@@ -268,3 +655,39 @@ impl Serialize for FlashMessage<'_> {
         flash.end()
     }
 }
+
+impl Serialize for FlashMessages<'_> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut seq = ser.serialize_seq(Some(self.len()))?;
+        for flashed in self.iter() {
+            seq.serialize_element(flashed)?;
+        }
+
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, decode, Flashed};
+
+    #[test]
+    fn encoding_round_trips() {
+        let single = vec![Flashed::new("success", "It worked!")];
+        assert_eq!(encode(&single), "7:successIt worked!");
+        assert_eq!(decode(&encode(&single)).unwrap(), single);
+
+        let mut many = vec![Flashed::new("info", "a:b!"), Flashed::new("", "")];
+        assert_eq!(decode(&encode(&many)).unwrap(), many);
+
+        many[0].payload = Some(r#"{"id":7}"#.into());
+        assert_eq!(decode(&encode(&many)).unwrap(), many);
+    }
+
+    #[test]
+    fn malformed_cookies_are_rejected() {
+        for content in ["", "!", "x:abc", "9:abc", "!4:info", "!4:info3:abc", "!4:info3:abc7"] {
+            assert!(decode(content).is_none(), "{content:?}");
+        }
+    }
+}
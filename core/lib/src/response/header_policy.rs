@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::request::Request;
+use crate::response::Response;
+use crate::http::Header;
+
+type Callback = Arc<dyn for<'r> Fn(&'r Request<'_>, &mut Response<'r>) + Send + Sync>;
+
+/// A named set of headers applied to every response of the routes in a mount.
+///
+/// A `HeaderPolicy` is attached to the routes mounted with
+/// [`Rocket::mount_with()`](crate::Rocket::mount_with()). The policy is
+/// applied to every response to a request routed to one of those routes,
+/// including error responses produced by catchers after one of the routes
+/// fails, just before [response fairings](crate::fairing::Fairing#response)
+/// run. Requests that don't match any route in the mount are unaffected.
+///
+/// A policy consists of static headers, added with [`HeaderPolicy::header()`],
+/// and callbacks, added with [`HeaderPolicy::on_response()`]. Static headers
+/// are only set if the response doesn't already contain a header with the same
+/// name. Callbacks run after static headers are set, in the order they were
+/// added, and can modify the response at will. When routes are mounted with
+/// several policies, the policies are applied in the order they were mounted.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Header;
+/// use rocket::response::HeaderPolicy;
+///
+/// #[get("/users")]
+/// fn users() -> &'static str { "[]" }
+///
+/// #[get("/")]
+/// fn index() -> &'static str { "Hello!" }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let api_policy = HeaderPolicy::json_api()
+///         .header(Header::new("X-Api-Version", "2"))
+///         .on_response(|req, res| {
+///             let id = req.headers().get_one("X-Request-Id").unwrap_or("none").to_owned();
+///             res.set_header(Header::new("X-Request-Id", id));
+///         });
+///
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .mount_with("/api", routes![users], api_policy)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct HeaderPolicy {
+    name: Cow<'static, str>,
+    headers: Vec<Header<'static>>,
+    callbacks: Vec<Callback>,
+}
+
+impl HeaderPolicy {
+    /// Creates a policy named `name` with no headers or callbacks. The name is
+    /// used to identify the policy in logs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// let policy = HeaderPolicy::new("Empty");
+    /// assert_eq!(policy.name(), "Empty");
+    /// ```
+    pub fn new<N: Into<Cow<'static, str>>>(name: N) -> Self {
+        HeaderPolicy { name: name.into(), headers: vec![], callbacks: vec![] }
+    }
+
+    /// A policy named `JSON API` with headers suitable for API responses that
+    /// are not meant to be rendered or cached by browsers:
+    ///
+    /// | Header                    | Value                                        |
+    /// |---------------------------|----------------------------------------------|
+    /// | `Cache-Control`           | `no-store`                                   |
+    /// | `Content-Security-Policy` | `default-src 'none'; frame-ancestors 'none'` |
+    /// | `X-Content-Type-Options`  | `nosniff`                                    |
+    /// | `X-Frame-Options`         | `DENY`                                       |
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// let policy = HeaderPolicy::json_api();
+    /// assert_eq!(policy.name(), "JSON API");
+    /// ```
+    pub fn json_api() -> Self {
+        HeaderPolicy::new("JSON API")
+            .header(Header::new("Cache-Control", "no-store"))
+            .header(Header::new(
+                "Content-Security-Policy",
+                "default-src 'none'; frame-ancestors 'none'"
+            ))
+            .header(Header::new("X-Content-Type-Options", "nosniff"))
+            .header(Header::new("X-Frame-Options", "DENY"))
+    }
+
+    /// Adds `header` to the policy, replacing any previously added header
+    /// with the same name. The header is set on responses that don't already
+    /// contain a header with the same name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::{Header, RetryAfter};
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// let policy = HeaderPolicy::new("Custom")
+    ///     .header(Header::new("X-Api-Version", "2"))
+    ///     .header(RetryAfter::seconds(60));
+    /// ```
+    pub fn header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        let header = header.into();
+        self.headers.retain(|h| h.name() != header.name());
+        self.headers.push(header);
+        self
+    }
+
+    /// Adds a callback that is invoked with every request and response the
+    /// policy applies to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// let policy = HeaderPolicy::new("Echo").on_response(|req, res| {
+    ///     if let Some(id) = req.headers().get_one("X-Request-Id") {
+    ///         res.set_header(Header::new("X-Request-Id", id.to_owned()));
+    ///     }
+    /// });
+    /// ```
+    pub fn on_response<F>(mut self, f: F) -> Self
+        where F: for<'r> Fn(&'r Request<'_>, &mut Response<'r>) + Send + Sync + 'static
+    {
+        self.callbacks.push(Arc::new(f));
+        self
+    }
+
+    /// Returns the name of the policy.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns an iterator over the static headers in the policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// let policy = HeaderPolicy::json_api();
+    /// assert!(policy.headers().any(|h| h.name() == "X-Content-Type-Options"));
+    /// ```
+    pub fn headers(&self) -> impl Iterator<Item = &Header<'static>> {
+        self.headers.iter()
+    }
+
+    /// Applies the policy to `res`, the response to `req`.
+    pub(crate) fn apply<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        for header in &self.headers {
+            if !res.headers().contains(header.name()) {
+                res.set_header(header.clone());
+            }
+        }
+
+        for callback in &self.callbacks {
+            callback(req, res);
+        }
+    }
+}
+
+impl fmt::Debug for HeaderPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderPolicy")
+            .field("name", &self.name)
+            .field("headers", &self.headers)
+            .field("callbacks", &self.callbacks.len())
+            .finish()
+    }
+}
@@ -18,12 +18,21 @@ mod redirect;
 mod response;
 mod debug;
 mod body;
+mod attachment;
+mod with_header;
+mod cached;
+mod deprecated;
+mod validated;
+mod header_policy;
 
 pub(crate) mod flash;
+pub(crate) mod versioned;
+pub(crate) mod completion;
 
 pub mod content;
 pub mod status;
 pub mod stream;
+pub mod multipart;
 
 #[doc(hidden)]
 pub use rocket_codegen::Responder;
@@ -32,8 +41,16 @@ pub use self::response::{Response, Builder};
 pub use self::body::Body;
 pub use self::responder::Responder;
 pub use self::redirect::Redirect;
-pub use self::flash::Flash;
+pub use self::flash::{Flash, Flashed, Severity};
 pub use self::debug::Debug;
+pub use self::versioned::EtagVersioned;
+pub use self::attachment::Attachment;
+pub use self::with_header::WithHeader;
+pub use self::cached::Cached;
+pub use self::deprecated::Deprecated;
+pub use self::validated::Validated;
+pub use self::header_policy::HeaderPolicy;
+pub use self::completion::{ResponseGuard, Completion};
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
 pub type Result<'r> = std::result::Result<Response<'r>, crate::http::Status>;
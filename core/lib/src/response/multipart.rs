@@ -0,0 +1,323 @@
+//! Multipart responses: `multipart/mixed` and `multipart/form-data`.
+//!
+//! See [`MultipartResponse`] for details.
+
+use std::io::Cursor;
+use std::pin::Pin;
+
+use futures::stream;
+use rand::{Rng, distributions::Alphanumeric};
+use tokio::io::AsyncRead;
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder};
+use crate::response::stream::ReaderStream;
+use crate::http::{ContentDisposition, ContentType, Header, Status};
+
+type BoxedBody<'r> = Pin<Box<dyn AsyncRead + Send + 'r>>;
+
+/// A `multipart/mixed` or `multipart/form-data` response.
+///
+/// A `MultipartResponse` is built from a series of [`Part`]s, each with its own
+/// headers and a body that is any [`AsyncRead`], such as `&[u8]` or a
+/// [`File`](tokio::fs::File). Bodies are streamed in order, one after the
+/// other, delimited by the response's boundary. The boundary is randomly
+/// generated unless set via [`MultipartResponse::boundary()`].
+///
+/// # Responder
+///
+/// The response `Content-Type` is `multipart/mixed` or `multipart/form-data`
+/// with a `boundary` parameter. The body is
+/// [unsized](crate::response::Body#unsized). If a custom boundary is not a
+/// valid [RFC 2046] boundary, the responder fails with
+/// `Status::InternalServerError`.
+///
+/// [RFC 2046]: https://datatracker.ietf.org/doc/html/rfc2046#section-5.1.1
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::ContentType;
+/// use rocket::response::multipart::{MultipartResponse, Part};
+/// use rocket::tokio::fs::File;
+///
+/// #[get("/batch")]
+/// async fn batch() -> std::io::Result<MultipartResponse<'static>> {
+///     let report = File::open("report.csv").await?;
+///     Ok(MultipartResponse::mixed()
+///         .part(Part::new(ContentType::JSON, r#"{"status":"ok"}"#.as_bytes()))
+///         .part(Part::new(ContentType::CSV, report).filename("report.csv")))
+/// }
+/// ```
+///
+/// Parts of a `multipart/form-data` response are named with [`Part::name()`]:
+///
+/// ```rust
+/// use rocket::http::ContentType;
+/// use rocket::response::multipart::{MultipartResponse, Part};
+///
+/// let response = MultipartResponse::form_data()
+///     .part(Part::text("Bob").name("username"))
+///     .part(Part::new(ContentType::PNG, &[0u8; 16][..]).name("avatar").filename("bob.png"));
+/// ```
+pub struct MultipartResponse<'r> {
+    subtype: &'static str,
+    boundary: Option<String>,
+    parts: Vec<Part<'r>>,
+}
+
+/// A single part of a [`MultipartResponse`].
+pub struct Part<'r> {
+    headers: Vec<Header<'static>>,
+    disposition: Option<ContentDisposition<'static>>,
+    body: BoxedBody<'r>,
+}
+
+impl<'r> MultipartResponse<'r> {
+    /// Returns an empty `multipart/mixed` response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::MultipartResponse;
+    ///
+    /// let response = MultipartResponse::mixed();
+    /// ```
+    pub fn mixed() -> Self {
+        MultipartResponse { subtype: "mixed", boundary: None, parts: vec![] }
+    }
+
+    /// Returns an empty `multipart/form-data` response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::MultipartResponse;
+    ///
+    /// let response = MultipartResponse::form_data();
+    /// ```
+    pub fn form_data() -> Self {
+        MultipartResponse { subtype: "form-data", boundary: None, parts: vec![] }
+    }
+
+    /// Uses `boundary` as the boundary delimiting parts instead of a randomly
+    /// generated one. The boundary must not appear in the body of any part.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::MultipartResponse;
+    ///
+    /// let response = MultipartResponse::mixed().boundary("simple-boundary");
+    /// ```
+    pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Appends the part `part` to the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::{MultipartResponse, Part};
+    ///
+    /// let response = MultipartResponse::mixed()
+    ///     .part(Part::text("first"))
+    ///     .part(Part::text("second"));
+    /// ```
+    pub fn part(mut self, part: Part<'r>) -> Self {
+        self.parts.push(part);
+        self
+    }
+}
+
+impl<'r> Part<'r> {
+    /// Returns a part with a `Content-Type` of `content_type` and a body of
+    /// `body`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::ContentType;
+    /// use rocket::response::multipart::Part;
+    ///
+    /// let part = Part::new(ContentType::JSON, r#"{"id": 1}"#.as_bytes());
+    /// ```
+    pub fn new<B>(content_type: ContentType, body: B) -> Self
+        where B: AsyncRead + Send + 'r
+    {
+        Part { headers: vec![content_type.into()], disposition: None, body: Box::pin(body) }
+    }
+
+    /// Returns a part with a `Content-Type` of [`ContentType::Text`] and a body
+    /// of `text`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::Part;
+    ///
+    /// let part = Part::text("Hello, world!");
+    /// ```
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        Part::new(ContentType::Text, Cursor::new(text.into()))
+    }
+
+    /// Adds the header `header` to the part, replacing any existing header with
+    /// the same name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Header;
+    /// use rocket::response::multipart::Part;
+    ///
+    /// let part = Part::text("hi").header(Header::new("Content-ID", "<greeting>"));
+    /// ```
+    pub fn header<H: Into<Header<'static>>>(mut self, header: H) -> Self {
+        let header = header.into();
+        self.headers.retain(|h| h.name() != header.name());
+        self.headers.push(header);
+        self
+    }
+
+    /// Sets the part's `Content-Disposition` to `form-data` with a `name` of
+    /// `name`, retaining any filename set by [`Part::filename()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::multipart::Part;
+    ///
+    /// let part = Part::text("Bob").name("username");
+    /// ```
+    pub fn name(mut self, name: &str) -> Self {
+        let mut disposition = ContentDisposition::form_data(name);
+        if let Some(filename) = self.disposition.as_ref().and_then(|d| d.filename()) {
+            disposition = disposition.with_filename(&filename);
+        }
+
+        self.disposition = Some(disposition);
+        self
+    }
+
+    /// Sets the `filename` parameter of the part's `Content-Disposition`. If
+    /// the part has not been named via [`Part::name()`], the disposition is
+    /// `attachment`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::ContentType;
+    /// use rocket::response::multipart::Part;
+    ///
+    /// let part = Part::new(ContentType::CSV, "a,b\n1,2\n".as_bytes()).filename("data.csv");
+    /// ```
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.disposition = Some(match self.disposition.take() {
+            Some(disposition) => disposition.with_filename(filename),
+            None => ContentDisposition::attachment(filename),
+        });
+
+        self
+    }
+
+    fn head(&self, boundary: &str) -> String {
+        let mut head = format!("--{boundary}\r\n");
+        if let Some(disposition) = &self.disposition {
+            head.push_str(&format!("Content-Disposition: {disposition}\r\n"));
+        }
+
+        for header in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", header.name(), header.value()));
+        }
+
+        head.push_str("\r\n");
+        head
+    }
+}
+
+/// Returns `true` if `boundary` is a valid RFC 2046 `boundary`: 1 to 70
+/// `bchars`, not ending in a space.
+fn is_valid_boundary(boundary: &str) -> bool {
+    let is_bchar = |b: u8| b.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&b);
+    (1..=70).contains(&boundary.len())
+        && boundary.bytes().all(is_bchar)
+        && !boundary.ends_with(' ')
+}
+
+impl<'r> Responder<'r, 'r> for MultipartResponse<'r> {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
+        let boundary = match self.boundary {
+            Some(boundary) if is_valid_boundary(&boundary) => boundary,
+            Some(boundary) => {
+                error!(%boundary, "invalid multipart response boundary");
+                return Err(Status::InternalServerError);
+            }
+            None => rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .map(char::from)
+                .collect(),
+        };
+
+        let mut readers: Vec<BoxedBody<'r>> = Vec::with_capacity(self.parts.len() * 2 + 1);
+        for (i, part) in self.parts.into_iter().enumerate() {
+            let head = part.head(&boundary);
+            let head = if i == 0 { head } else { format!("\r\n{head}") };
+            readers.push(Box::pin(Cursor::new(head)));
+            readers.push(part.body);
+        }
+
+        let close = if readers.is_empty() { "" } else { "\r\n" };
+        readers.push(Box::pin(Cursor::new(format!("{close}--{boundary}--\r\n"))));
+
+        let content_type = ContentType::new("multipart", self.subtype)
+            .with_params([("boundary", boundary)]);
+
+        Response::build()
+            .header(content_type)
+            .streamed_body(ReaderStream::from(stream::iter(readers)))
+            .ok()
+    }
+}
+
+impl std::fmt::Debug for MultipartResponse<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultipartResponse")
+            .field("subtype", &self.subtype)
+            .field("boundary", &self.boundary)
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for Part<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Part")
+            .field("headers", &self.headers)
+            .field("disposition", &self.disposition)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_valid_boundary;
+
+    #[test]
+    fn test_boundary_validation() {
+        assert!(is_valid_boundary("simple boundary"));
+        assert!(is_valid_boundary("gc0p4Jq0M2Yt08jU534c0p"));
+        assert!(is_valid_boundary("'()+_,-./:=?"));
+        assert!(is_valid_boundary(&"a".repeat(70)));
+
+        assert!(!is_valid_boundary(""));
+        assert!(!is_valid_boundary("trailing "));
+        assert!(!is_valid_boundary("new\r\nline"));
+        assert!(!is_valid_boundary("quo\"te"));
+        assert!(!is_valid_boundary(&"a".repeat(71)));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::request::Request;
 use crate::response::{self, Response, Responder};
 use crate::http::uri::Reference;
-use crate::http::Status;
+use crate::http::{Status, StatusClass};
 
 /// An empty redirect response to a given URL.
 ///
@@ -150,14 +150,103 @@ impl Redirect {
     {
         Redirect(self.0, self.1.and_then(|p| f(p).try_into().ok()))
     }
+
+    /// Replaces the status of this redirect with `status`, allowing the
+    /// redirect kind to be chosen at runtime. `status` must be a redirection
+    /// (`3xx`) status. Otherwise, responding fails with a status of `500`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Status;
+    /// use rocket::response::Redirect;
+    ///
+    /// # let preserve_method = true;
+    /// let status = match preserve_method {
+    ///     true => Status::TemporaryRedirect,
+    ///     false => Status::SeeOther,
+    /// };
+    ///
+    /// let redirect = Redirect::to(uri!("/login")).with_status(status);
+    /// assert_eq!(redirect.status(), Status::TemporaryRedirect);
+    /// ```
+    pub fn with_status(self, status: Status) -> Redirect {
+        Redirect(status, self.1)
+    }
+
+    /// Appends the query parameter `name` with value `value` to the target
+    /// URI, percent-encoding both. Existing query parameters are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::Redirect;
+    ///
+    /// #[get("/search")]
+    /// fn search() { }
+    ///
+    /// let redirect = Redirect::to(uri!(search))
+    ///     .with_query("q", "web frameworks")
+    ///     .with_query("page", 2);
+    ///
+    /// assert_eq!(redirect.uri().unwrap(), "/search?q=web%20frameworks&page=2");
+    /// ```
+    pub fn with_query<N, V>(mut self, name: N, value: V) -> Redirect
+        where N: Into<String>, V: std::fmt::Display
+    {
+        if let Some(uri) = &mut self.1 {
+            let mut query = uri.query_map();
+            query.append(name, value);
+            uri.set_query_map(&query);
+        }
+
+        self
+    }
+
+    /// Returns the status of this redirect.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::Status;
+    /// use rocket::response::Redirect;
+    ///
+    /// assert_eq!(Redirect::to("/").status(), Status::SeeOther);
+    /// assert_eq!(Redirect::permanent("/").status(), Status::PermanentRedirect);
+    /// ```
+    pub fn status(&self) -> Status {
+        self.0
+    }
+
+    /// Returns the target URI of this redirect, or `None` if the URI it was
+    /// constructed with was invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Redirect;
+    ///
+    /// assert_eq!(Redirect::to("/a?b").uri().unwrap(), "/a?b");
+    /// assert!(Redirect::to("a b").uri().is_none());
+    /// ```
+    pub fn uri(&self) -> Option<&Reference<'static>> {
+        self.1.as_ref()
+    }
 }
 
 /// Constructs a response with the appropriate status code and the given URL in
 /// the `Location` header field. The body of the response is empty. If the URI
-/// value used to create the `Responder` is an invalid URI, an error of
-/// `Status::InternalServerError` is returned.
+/// value used to create the `Responder` is an invalid URI, or the status is not
+/// a redirection status, an error of `Status::InternalServerError` is returned.
 impl<'r> Responder<'r, 'static> for Redirect {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        if self.0.class() != StatusClass::Redirection {
+            error!(status = self.0.code, "Non-redirection status used for redirect.");
+            return Err(Status::InternalServerError);
+        }
+
         if let Some(uri) = self.1 {
             Response::build()
                 .status(self.0)
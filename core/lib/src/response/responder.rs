@@ -444,17 +444,24 @@ impl<'r, 'o: 'r, T: Responder<'r, 'o> + Sized> Responder<'r, 'o> for Box<T> {
     }
 }
 
-/// Returns a response with a sized body for the file. Always returns `Ok`.
+/// Returns a response with a sized body for the file and a max chunk size of
+/// [`Body::FILE_MAX_CHUNK`](crate::response::Body::FILE_MAX_CHUNK). Always
+/// returns `Ok`.
 impl<'r> Responder<'r, 'static> for File {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
         tokio::fs::File::from(self).respond_to(req)
     }
 }
 
-/// Returns a response with a sized body for the file. Always returns `Ok`.
+/// Returns a response with a sized body for the file and a max chunk size of
+/// [`Body::FILE_MAX_CHUNK`](crate::response::Body::FILE_MAX_CHUNK). Always
+/// returns `Ok`.
 impl<'r> Responder<'r, 'static> for tokio::fs::File {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
-        Response::build().sized_body(None, self).ok()
+        Response::build()
+            .sized_body(None, self)
+            .max_chunk_size(crate::response::Body::FILE_MAX_CHUNK)
+            .ok()
     }
 }
 
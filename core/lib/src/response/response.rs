@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use tokio::io::{AsyncRead, AsyncSeek};
 
-use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
+use crate::http::{Header, HeaderMap, Status, ContentType, Cookie, Link};
 use crate::http::uncased::{Uncased, AsUncased};
 use crate::data::IoHandler;
 use crate::response::Body;
@@ -167,6 +167,50 @@ impl<'r> Builder<'r> {
         self
     }
 
+    /// Adds `header` as a trailer of the `Response`, sent after the body.
+    /// See [`Response::adjoin_trailer()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Header;
+    ///
+    /// let response = Response::build()
+    ///     .trailer(Header::new("Digest", "sha-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE="))
+    ///     .finalize();
+    ///
+    /// assert!(response.trailers().contains("Digest"));
+    /// ```
+    #[inline(always)]
+    pub fn trailer<'h: 'r, H>(&mut self, header: H) -> &mut Builder<'r>
+        where H: Into<Header<'h>>
+    {
+        self.response.adjoin_trailer(header);
+        self
+    }
+
+    /// Adds `link`, typically with `preload` relations, to the preload links
+    /// of the `Response`. See [`Response::add_preload_link()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Link;
+    ///
+    /// let response = Response::build()
+    ///     .preload_link(Link::new().preload("/style.css", "style"))
+    ///     .finalize();
+    ///
+    /// assert!(response.preload_links().contains("Link"));
+    /// ```
+    #[inline(always)]
+    pub fn preload_link(&mut self, link: Link) -> &mut Builder<'r> {
+        self.response.add_preload_link(link);
+        self
+    }
+
     /// Adds a custom header to the `Response` with the given name and value,
     /// replacing any header with the same name that already exists in the
     /// response. If multiple headers with the same name exist, they are all
@@ -485,6 +529,8 @@ pub struct Response<'r> {
     headers: HeaderMap<'r>,
     body: Body<'r>,
     upgrade: HashMap<Uncased<'r>, Box<dyn IoHandler + 'r>>,
+    trailers: HeaderMap<'r>,
+    preload_links: HeaderMap<'r>,
 }
 
 impl<'r> Response<'r> {
@@ -770,6 +816,119 @@ impl<'r> Response<'r> {
         self.headers.remove(name);
     }
 
+    /// Returns the trailers of `self`: headers sent after the body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let response = Response::new();
+    /// assert!(response.trailers().is_empty());
+    /// ```
+    #[inline(always)]
+    pub fn trailers(&self) -> &HeaderMap<'r> {
+        &self.trailers
+    }
+
+    /// Sets the trailer `header` in `self`, replacing any existing trailers
+    /// with the same name. See [`Response::adjoin_trailer()`] for details on
+    /// when trailers are sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Header;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_trailer(Header::new("Server-Timing", "db;dur=53"));
+    /// response.set_trailer(Header::new("Server-Timing", "db;dur=12"));
+    /// assert_eq!(response.trailers().get_one("Server-Timing"), Some("db;dur=12"));
+    /// assert_eq!(response.trailers().len(), 1);
+    /// ```
+    #[inline(always)]
+    pub fn set_trailer<'h: 'r, H: Into<Header<'h>>>(&mut self, header: H) -> bool {
+        self.trailers.replace(header)
+    }
+
+    /// Adds the trailer `header` to `self`, keeping any existing trailers with
+    /// the same name.
+    ///
+    /// Trailers are headers sent _after_ the response body, typically with
+    /// values that are only known once the body has been produced, such as
+    /// checksums or timing information. A response with trailers is never
+    /// sent with a `Content-Length`; instead, the body is chunked over HTTP/1.1
+    /// and Rocket announces the trailer names in a `Trailer` header. Clients
+    /// may ignore trailers: HTTP/1.1 clients only receive them if they send
+    /// `TE: trailers`. As such, trailers should never carry information
+    /// critical to interpreting the response, nor should they include fields
+    /// like `Content-Length`, `Content-Type`, or `Transfer-Encoding`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Header;
+    ///
+    /// let mut response = Response::new();
+    /// response.adjoin_trailer(Header::new("Server-Timing", "db;dur=53"));
+    /// response.adjoin_trailer(Header::new("Server-Timing", "app;dur=47.2"));
+    /// assert_eq!(response.trailers().get("Server-Timing").count(), 2);
+    /// ```
+    #[inline(always)]
+    pub fn adjoin_trailer<'h: 'r, H: Into<Header<'h>>>(&mut self, header: H) {
+        self.trailers.add(header)
+    }
+
+    /// Returns the preload links of `self` as `Link` headers. See
+    /// [`Response::add_preload_link()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let response = Response::new();
+    /// assert!(response.preload_links().is_empty());
+    /// ```
+    #[inline(always)]
+    pub fn preload_links(&self) -> &HeaderMap<'r> {
+        &self.preload_links
+    }
+
+    /// Adds `link`, typically with `preload` relations, to the preload links
+    /// of `self`.
+    ///
+    /// Preload links tell a client which resources, like stylesheets and
+    /// scripts, it will need so that it can begin fetching them sooner. They
+    /// are kept apart from the response's headers so that they can be added
+    /// at any point, including by response fairings, and are sent as `Link`
+    /// headers in the final response after response fairings have run.
+    ///
+    /// **Note:** Rocket does not send a `103 Early Hints` ([RFC 8297])
+    /// interim response: Rocket's HTTP server implementation cannot write
+    /// interim (`1xx`) responses. Preload links are thus only seen by the
+    /// client once the final response's headers arrive.
+    ///
+    /// [RFC 8297]: https://datatracker.ietf.org/doc/html/rfc8297
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Link;
+    ///
+    /// let mut response = Response::new();
+    /// response.add_preload_link(Link::new().preload("/app.js", "script"));
+    /// let link = response.preload_links().get_one("Link");
+    /// assert_eq!(link, Some("</app.js>; rel=preload; as=script"));
+    /// ```
+    #[inline(always)]
+    pub fn add_preload_link(&mut self, link: Link) {
+        self.preload_links.add(link)
+    }
+
     /// Returns an immutable borrow of the body of `self`, if there is one.
     ///
     /// # Example
@@ -939,6 +1098,81 @@ impl<'r> Response<'r> {
         self.body = Body::with_unsized(body);
     }
 
+    /// Wraps or replaces the body of `self` with the body returned by `f`,
+    /// which is passed the current body. Returns `true` if the body was
+    /// transformed and `false` otherwise.
+    ///
+    /// This method is intended for use in [response
+    /// fairings](crate::fairing::Fairing#response) that transform response
+    /// bodies, for instance, to rewrite HTML or to append a watermark. The
+    /// following rules apply:
+    ///
+    ///   * **Unset bodies are not transformed.** If the body [is
+    ///     none](Body::is_none()), `f` is not called.
+    ///
+    ///   * **Encoded and partial bodies are not transformed.** If the response
+    ///     has a `Content-Encoding` header other than `identity`, or a
+    ///     `Content-Range` header, `f` is not called. Transforming these bodies
+    ///     would corrupt them. As a result, a compressing fairing should be
+    ///     attached _after_ any transforming fairings: it then sees the
+    ///     transformed body, while transforming fairings attached after it see
+    ///     an encoded body and leave it untouched.
+    ///
+    ///   * **Transformed bodies are streamed.** The body returned by `f` is set
+    ///     as a [streamed body](Response::set_streamed_body()), and any
+    ///     `Content-Length` header is removed, as the size of the transformed
+    ///     body is generally unknown. The maximum chunk size is preserved. A
+    ///     transformer that knows the size of its output, for instance, because
+    ///     it buffers it, can use [`Response::set_sized_body()`] instead.
+    ///
+    /// Response fairings run in the order in which they were attached, so
+    /// transformations compose in `attach()` order: each `f` is passed the
+    /// body returned by the previous transformation. Validators such as `ETag`
+    /// are left untouched; a transformation that changes the body in a way
+    /// that is not a function of the original body should remove them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Cursor};
+    /// use rocket::Response;
+    /// use rocket::http::Header;
+    /// use rocket::tokio::io::AsyncReadExt;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let mut response = Response::new();
+    /// response.set_sized_body(5, Cursor::new("Hello"));
+    /// assert!(response.map_body(|body| body.chain(Cursor::new(", world!"))));
+    /// assert_eq!(response.body().preset_size(), None);
+    /// assert_eq!(response.body_mut().to_string().await?, "Hello, world!");
+    ///
+    /// // Encoded bodies are not transformed.
+    /// response.set_sized_body(5, Cursor::new("Hello"));
+    /// response.set_header(Header::new("Content-Encoding", "gzip"));
+    /// assert!(!response.map_body(|body| body.chain(Cursor::new(", world!"))));
+    /// assert_eq!(response.body_mut().to_string().await?, "Hello");
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub fn map_body<F, B>(&mut self, f: F) -> bool
+        where F: FnOnce(Body<'r>) -> B, B: AsyncRead + Send + 'r
+    {
+        let encoded = self.headers.get("Content-Encoding")
+            .any(|encoding| !encoding.trim().eq_ignore_ascii_case("identity"));
+
+        if self.body.is_none() || encoded || self.headers.contains("Content-Range") {
+            return false;
+        }
+
+        let max_chunk = self.body.max_chunk_size();
+        let body = f(self.body.take());
+        self.set_streamed_body(body);
+        self.set_max_chunk_size(max_chunk);
+        self.remove_header("Content-Length");
+        true
+    }
+
     /// Registers `handler` as the I/O handler for upgrade protocol `protocol`.
     ///
     /// Responses registering I/O handlers for upgraded protocols **should not**
@@ -1061,6 +1295,14 @@ impl<'r> Response<'r> {
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
+
+        for (name, values) in other.trailers.into_iter_raw() {
+            self.trailers.replace_all(name.into_cow(), values);
+        }
+
+        for (name, values) in other.preload_links.into_iter_raw() {
+            self.preload_links.replace_all(name.into_cow(), values);
+        }
     }
 
     /// Sets `self`'s status and body to that of `other` if they are not already
@@ -1106,6 +1348,14 @@ impl<'r> Response<'r> {
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
+
+        for (name, mut values) in other.trailers.into_iter_raw() {
+            self.trailers.add_all(name.into_cow(), &mut values);
+        }
+
+        for (name, mut values) in other.preload_links.into_iter_raw() {
+            self.preload_links.add_all(name.into_cow(), &mut values);
+        }
     }
 }
 
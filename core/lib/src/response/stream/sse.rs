@@ -326,6 +326,47 @@ impl Event {
         self
     }
 
+    /// Parses the raw, serialized `lines` of a single event, without line
+    /// terminators, as a client would. Returns `None` if `lines` is empty.
+    ///
+    /// Unknown fields and invalid `retry` values are ignored. Multiple `data`
+    /// and comment lines are joined with `\n`.
+    pub(crate) fn parse<'a, I>(lines: I) -> Option<Self>
+        where I: IntoIterator<Item = &'a str>
+    {
+        fn append(field: &mut Option<Cow<'static, str>>, value: &str) {
+            match field {
+                Some(ref mut existing) => {
+                    let existing = existing.to_mut();
+                    existing.push('\n');
+                    existing.push_str(value);
+                }
+                None => *field = Some(value.to_string().into()),
+            }
+        }
+
+        let mut lines = lines.into_iter().peekable();
+        lines.peek()?;
+
+        let mut event = Event::new();
+        for line in lines {
+            let (name, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match name {
+                "" => append(&mut event.comment, value),
+                "data" => append(&mut event.data, value),
+                "event" => event.event = Some(value.to_string().into()),
+                "id" => event.id = Some(value.to_string().into()),
+                "retry" => if let Ok(ms) = value.parse() {
+                    event.retry = Some(Duration::from_millis(ms));
+                },
+                _ => { /* unknown fields are ignored */ }
+            }
+        }
+
+        Some(event)
+    }
+
     fn into_stream(self) -> impl Stream<Item = RawLinedEvent> {
         let events = [
             self.comment.map(|v| RawLinedEvent::many("", v)),
@@ -808,4 +849,29 @@ mod sse_tests {
         assert!(string.contains("data:a\n\n"), "string = {:?}", string);
         assert!(string.contains("data:b\n\n"), "string = {:?}", string);
     }
+
+    #[test]
+    fn test_event_parse_roundtrip() {
+        fn roundtrip(event: Event) -> Option<Event> {
+            let string = event.into_string();
+            Event::parse(string.strip_suffix("\n\n").unwrap().split('\n'))
+        }
+
+        let events = [
+            Event::data("a\nb"),
+            Event::data("cats make me happy!").event("cat").id("1"),
+            Event::comment("silly boy"),
+            Event::retry(Duration::from_millis(1500)).with_data("hi"),
+            Event::data("x").with_comment("a\nb"),
+            Event::empty(),
+        ];
+
+        for event in events {
+            assert_eq!(roundtrip(event.clone()), Some(event));
+        }
+
+        assert_eq!(Event::parse(["data: hi", "retry:bad", "foo:bar"]), Some(Event::data("hi")));
+        assert_eq!(Event::parse([":", "event"]), Some(Event::comment("").event("")));
+        assert_eq!(Event::parse([]), None);
+    }
 }
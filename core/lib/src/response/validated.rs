@@ -0,0 +1,171 @@
+use std::fmt;
+
+use time::OffsetDateTime;
+
+use crate::request::Request;
+use crate::response::{self, Responder, Response};
+use crate::response::versioned::{etag, entity_tags};
+use crate::http::{Header, LastModified, Method, Status};
+
+/// A responder that answers conditional requests using the last modification
+/// date and/or version of a dynamic resource.
+///
+/// `Validated` brings conditional `GET` handling to resources that aren't
+/// files, such as rows loaded from a database: the handler supplies the
+/// resource's last modification date via [`Validated::last_modified()`],
+/// typically an `updated_at` column, and/or its version via
+/// [`Validated::version()`], typically a revision counter or content hash.
+///
+/// For `GET` and `HEAD` requests, the response is `304 Not Modified`, with no
+/// body, and the wrapped responder is not called if either:
+///
+///   * the request has an `If-None-Match` header listing `*` or the `ETag` for
+///     the version, or
+///   * the request has no `If-None-Match` header but has an
+///     `If-Modified-Since` header with a date no earlier than the last
+///     modification date, compared in whole seconds.
+///
+/// Otherwise the wrapped responder is called. If its response is successful,
+/// that is, has a `2xx` status, the `Last-Modified` and `ETag` headers are set
+/// on it, replacing any set by the responder. The same headers are set on
+/// `304` responses. Versions are converted to entity tags as in
+/// [`EtagVersioned`](crate::response::EtagVersioned), which additionally pairs
+/// with the [`IfMatch`](crate::request::IfMatch) guard for conditional updates.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::Validated;
+/// use rocket::time::OffsetDateTime;
+///
+/// struct Article {
+///     revision: u64,
+///     updated_at: OffsetDateTime,
+///     body: String,
+/// }
+///
+/// # fn load(_: usize) -> Article {
+/// #     Article { revision: 1, updated_at: OffsetDateTime::UNIX_EPOCH, body: "".into() }
+/// # }
+/// #[get("/articles/<id>")]
+/// fn article(id: usize) -> Validated<String> {
+///     let article = load(id);
+///     Validated::new(article.body)
+///         .last_modified(article.updated_at)
+///         .version(article.revision)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validated<R> {
+    last_modified: Option<OffsetDateTime>,
+    etag: Option<String>,
+    responder: R,
+}
+
+impl<R> Validated<R> {
+    /// Wraps `responder` without any validators. Without validators, only
+    /// requests with an `If-None-Match: *` header are answered with `304`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Validated;
+    ///
+    /// let validated = Validated::new("Hello, world!");
+    /// assert!(validated.etag().is_none());
+    /// ```
+    pub fn new(responder: R) -> Self {
+        Validated { last_modified: None, etag: None, responder }
+    }
+
+    /// Sets the date the resource was last modified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    ///
+    /// use rocket::response::Validated;
+    /// use rocket::time::macros::datetime;
+    ///
+    /// let validated = Validated::new("Hello!").last_modified(SystemTime::now());
+    /// let validated = Validated::new("Hello!").last_modified(datetime!(2024-06-01 12:00 UTC));
+    /// ```
+    pub fn last_modified<T: Into<OffsetDateTime>>(mut self, date: T) -> Self {
+        self.last_modified = Some(date.into());
+        self
+    }
+
+    /// Sets the version of the resource, sent as the strong entity tag
+    /// `"{version}"`. Versions containing characters not allowed in entity
+    /// tags are hashed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Validated;
+    ///
+    /// let validated = Validated::new("Hello!").version(7);
+    /// assert_eq!(validated.etag(), Some("\"7\""));
+    /// ```
+    pub fn version<V: fmt::Display>(mut self, version: V) -> Self {
+        self.etag = Some(etag(&version.to_string()));
+        self
+    }
+
+    /// Returns the `ETag` for the resource's version, including quotes, if a
+    /// version was set.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        self.responder
+    }
+
+    /// Returns `true` if `req` is a conditional `GET` or `HEAD` request whose
+    /// cached response is still valid.
+    fn is_not_modified(&self, req: &Request<'_>) -> bool {
+        if !matches!(req.method(), Method::Get | Method::Head) {
+            return false;
+        }
+
+        // `If-Modified-Since` is ignored when `If-None-Match` is present.
+        let mut if_none_match = req.headers().get("If-None-Match").flat_map(entity_tags).peekable();
+        if if_none_match.peek().is_some() {
+            let matches = |tag: &str| Some(tag.trim_start_matches("W/")) == self.etag.as_deref();
+            return if_none_match.any(|tag| tag == "*" || matches(tag));
+        }
+
+        let since = req.headers().get_one("If-Modified-Since").and_then(LastModified::parse_date);
+        match (self.last_modified, since) {
+            (Some(modified), Some(since)) => modified.unix_timestamp() <= since.unix_timestamp(),
+            _ => false,
+        }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Validated<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let not_modified = self.is_not_modified(req);
+        let Validated { last_modified, etag, responder } = self;
+        let validators = last_modified.map(|date| Header::from(LastModified(date)))
+            .into_iter()
+            .chain(etag.map(|etag| Header::new("ETag", etag)));
+
+        let mut response = match not_modified {
+            true => Response::build().status(Status::NotModified).finalize(),
+            false => responder.respond_to(req)?,
+        };
+
+        if not_modified || response.status().class().is_success() {
+            for header in validators {
+                response.set_header(header);
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+
+use crate::request::{self, Request, FromRequest};
+use crate::response::{self, Responder, Response};
+use crate::http::{Method, Status};
+
+/// A responder that tags a response with an `ETag` derived from the version
+/// of the resource it represents.
+///
+/// Paired with the [`IfMatch`](crate::request::IfMatch) request guard,
+/// `EtagVersioned` provides optimistic concurrency control for REST APIs: a
+/// client reads a resource along with its `ETag`, then sends its modification
+/// with an `If-Match` header containing the `ETag`. The modification is applied
+/// only if the resource's version is unchanged, otherwise the request fails
+/// with `412 Precondition Failed`, so concurrent modifications are never lost.
+///
+/// The version is any value implementing `Display`, typically a revision
+/// counter or a last-modified timestamp, and is sent as the strong entity tag
+/// `"{version}"`. Versions containing characters not allowed in entity tags
+/// are hashed.
+///
+/// If the request is a `GET` or `HEAD` request with an `If-None-Match` header
+/// matching the `ETag`, the response is `304 Not Modified` and the wrapped
+/// responder is not called.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Status;
+/// use rocket::request::IfMatch;
+/// use rocket::response::EtagVersioned;
+///
+/// struct Document {
+///     revision: u64,
+///     text: String,
+/// }
+///
+/// # fn load(_: usize) -> Document { Document { revision: 1, text: "".into() } }
+/// # fn save(_: usize, _: Document) { }
+/// #[get("/docs/<id>")]
+/// fn read(id: usize) -> EtagVersioned<String> {
+///     let doc = load(id);
+///     EtagVersioned::new(doc.revision, doc.text)
+/// }
+///
+/// #[put("/docs/<id>", data = "<text>")]
+/// fn update(id: usize, text: String, if_match: IfMatch<'_>) -> Result<(), Status> {
+///     let doc = load(id);
+///     if_match.check(doc.revision)?;
+///     save(id, Document { revision: doc.revision + 1, text });
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EtagVersioned<R> {
+    etag: String,
+    responder: R,
+}
+
+/// A request guard for the `If-Match` header of a request.
+///
+/// The guard fails with `428 Precondition Required` if the request has no
+/// `If-Match` header, forcing clients to send the version they intend to
+/// modify. Use `Option<IfMatch>` to make the precondition optional.
+///
+/// Call [`IfMatch::check()`] with the current version of the resource before
+/// modifying it. See [`EtagVersioned`](crate::response::EtagVersioned) for an
+/// example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfMatch<'r>(&'r str);
+
+/// Returns the strong entity tag for `version`.
+pub(crate) fn etag(version: &str) -> String {
+    let valid = !version.is_empty() && version.bytes().all(|b| b.is_ascii_graphic() && b != b'"');
+    if valid {
+        return format!("\"{version}\"");
+    }
+
+    let mut hasher = DefaultHasher::new();
+    version.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Returns an iterator over the entity tags in an `If-Match` or
+/// `If-None-Match` header value.
+pub(crate) fn entity_tags(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(|tag| tag.trim()).filter(|tag| !tag.is_empty())
+}
+
+impl<R> EtagVersioned<R> {
+    /// Wraps `responder`, tagging its response with an `ETag` for `version`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::EtagVersioned;
+    ///
+    /// let response = EtagVersioned::new(7, "Hello, world!");
+    /// assert_eq!(response.etag(), "\"7\"");
+    /// ```
+    pub fn new<V: fmt::Display>(version: V, responder: R) -> Self {
+        EtagVersioned { etag: etag(&version.to_string()), responder }
+    }
+
+    /// Returns the `ETag` the response is tagged with, including quotes.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// Returns the wrapped responder.
+    pub fn into_inner(self) -> R {
+        self.responder
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for EtagVersioned<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let is_safe = matches!(req.method(), Method::Get | Method::Head);
+        let not_modified = req.headers().get("If-None-Match")
+            .flat_map(entity_tags)
+            .any(|tag| tag == "*" || tag.trim_start_matches("W/") == self.etag);
+
+        if is_safe && not_modified {
+            return Response::build()
+                .status(Status::NotModified)
+                .raw_header("ETag", self.etag)
+                .ok();
+        }
+
+        let mut response = self.responder.respond_to(req)?;
+        response.set_raw_header("ETag", self.etag);
+        Ok(response)
+    }
+}
+
+impl<'r> IfMatch<'r> {
+    /// Returns `true` if the `If-Match` header matches the current `version`
+    /// of the resource: that is, if it is `*` or lists the strong entity tag
+    /// for `version`. Weak entity tags never match.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::http::Header;
+    /// # use rocket::local::blocking::Client;
+    /// use rocket::request::{FromRequest, IfMatch};
+    ///
+    /// # let client = Client::debug_with(vec![]).unwrap();
+    /// # let req = client.put("/").header(Header::new("If-Match", "\"1\", \"2\""));
+    /// # let if_match = rocket::async_test(IfMatch::from_request(&req)).unwrap();
+    /// // The request has a header of `If-Match: "1", "2"`.
+    /// assert!(if_match.matches(1));
+    /// assert!(if_match.matches("2"));
+    /// assert!(!if_match.matches(3));
+    /// ```
+    pub fn matches<V: fmt::Display>(&self, version: V) -> bool {
+        let etag = etag(&version.to_string());
+        entity_tags(self.0).any(|tag| tag == "*" || tag == etag)
+    }
+
+    /// Returns `Ok(())` if the header [matches](IfMatch::matches()) `version`
+    /// and `Err(Status::PreconditionFailed)` otherwise.
+    pub fn check<V: fmt::Display>(&self, version: V) -> Result<(), Status> {
+        match self.matches(version) {
+            true => Ok(()),
+            false => Err(Status::PreconditionFailed),
+        }
+    }
+
+    /// Returns the raw value of the `If-Match` header.
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for IfMatch<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("If-Match") {
+            Some(value) => request::Outcome::Success(IfMatch(value)),
+            None => request::Outcome::Error((Status::PreconditionRequired, ())),
+        }
+    }
+}
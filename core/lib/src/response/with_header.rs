@@ -0,0 +1,41 @@
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::Header;
+
+/// Sets a header on the response of a wrapped responder.
+///
+/// `WithHeader(header, responder)` responds with the response of `responder`
+/// after setting `header`, replacing any existing header of the same name.
+/// `header` can be a [`Header`] or any typed header that implements
+/// `Into<Header>`, such as [`Link`], [`RetryAfter`], or
+/// [`ContentDisposition`], so handlers needn't format header values by hand.
+///
+/// [`Link`]: crate::http::Link
+/// [`RetryAfter`]: crate::http::RetryAfter
+/// [`ContentDisposition`]: crate::http::ContentDisposition
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::Link;
+/// use rocket::response::WithHeader;
+///
+/// #[get("/items?<page>")]
+/// fn items(page: u64) -> WithHeader<Link, String> {
+///     let link = Link::new().paginate(page, 10, |p| uri!(items(p)));
+///     WithHeader(link, format!("page {} of 10", page))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithHeader<H, R>(pub H, pub R);
+
+impl<'r, 'o: 'r, H, R> Responder<'r, 'o> for WithHeader<H, R>
+    where H: Into<Header<'static>>, R: Responder<'r, 'o>
+{
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.1.respond_to(req)?;
+        response.set_header(self.0);
+        Ok(response)
+    }
+}
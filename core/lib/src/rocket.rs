@@ -11,11 +11,21 @@ use figment::{Figment, Provider};
 use futures::TryFutureExt;
 
 use crate::shutdown::{Stages, Shutdown};
+use crate::load::{Limiter, LoadStats};
+use crate::runtime::RuntimeStats;
+use crate::task::TaskSet;
+use crate::catcher::{ErrorLog, ErrorSummary};
 use crate::trace::{Trace, TraceAll};
 use crate::{sentinel, shield::Shield, Catcher, Config, Route};
-use crate::listener::{Bind, DefaultListener, Endpoint, Listener};
-use crate::router::Router;
+use crate::listener::{Bind, Binding, BoundAddresses, DefaultListener, Endpoint, Listener};
+use crate::router::{Router, HotRouter};
+use crate::route::HotMount;
+use crate::state::Override;
+use crate::service::RocketService;
+#[cfg(feature = "tower")]
+use crate::service::{BoxService, RequestBody};
 use crate::fairing::{Fairing, Fairings};
+use crate::response::HeaderPolicy;
 use crate::phase::{Phase, Build, Building, Ignite, Igniting, Orbit, Orbiting};
 use crate::phase::{Stateful, StateRef, StateRefMut, State};
 use crate::http::uri::Origin;
@@ -247,19 +257,7 @@ impl Rocket<Build> {
               F: Fn(&mut Self, T),
               T: Clone + Trace,
     {
-        let mut base = match base.clone().try_into() {
-            Ok(origin) => origin.into_owned(),
-            Err(e) => {
-                error!(%base, location = %Location::caller(), "invalid {kind} base uri: {e}");
-                panic!("aborting due to {} base error", kind);
-            }
-        };
-
-        if base.query().is_some() {
-            warn!(%base, location = %Location::caller(), "query in {kind} base is ignored");
-            base.clear_query();
-        }
-
+        let base = parse_base(kind, base);
         for unmounted_item in items {
             f(&mut self, m(&base, unmounted_item.clone()))
         }
@@ -269,6 +267,8 @@ impl Rocket<Build> {
 
     /// Mounts all of the `routes` at the given `base` mount point.
     ///
+    /// Routes may also be mounted after launch with [`Rocket::hot_mount()`].
+    ///
     /// A route _mounted_ at `base` has an effective URI of `base/route`, where
     /// `route` is the route URI. In other words, `base` is added as a prefix to
     /// the route's URI. The URI resulting from joining the `base` URI and the
@@ -368,6 +368,51 @@ impl Rocket<Build> {
             |r, route| r.0.routes.push(route))
     }
 
+    /// Mounts all of the `routes` at the given `base` mount point, like
+    /// [`Rocket::mount()`], and applies the header `policy` to every response
+    /// of the mounted routes.
+    ///
+    /// This is equivalent to, but cheaper and less error-prone than, attaching
+    /// a response fairing that sets headers on responses for requests under
+    /// `base`. See [`HeaderPolicy`] for details on how and when the policy is
+    /// applied. Routes mounted with several calls to `mount_with()` apply each
+    /// call's policy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` or any route URI is invalid, as in [`Rocket::mount()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::response::HeaderPolicy;
+    ///
+    /// #[get("/users")]
+    /// fn users() -> &'static str { "[]" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().mount_with("/api", routes![users], HeaderPolicy::json_api())
+    /// }
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn mount_with<'a, B, R>(self, base: B, routes: R, policy: HeaderPolicy) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let policy = Arc::new(policy);
+        self.load("route", base, routes.into(),
+            |base, route| {
+                let mut route = route.rebase(base.clone());
+                route.header_policies.push(policy.clone());
+                route
+            },
+            |r, route| r.0.routes.push(route))
+    }
+
     /// Registers all of the catchers in the supplied vector, scoped to `base`.
     ///
     /// # Panics
@@ -449,7 +494,7 @@ impl Rocket<Build> {
     /// }
     /// ```
     #[must_use]
-    pub fn manage<T>(self, state: T) -> Self
+    pub fn manage<T>(mut self, state: T) -> Self
         where T: Send + Sync + 'static
     {
         let type_name = std::any::type_name::<T>();
@@ -458,6 +503,32 @@ impl Rocket<Build> {
             panic!("aborting due to duplicated managed state");
         }
 
+        self.managed.push(type_name);
+        self
+    }
+
+    /// Overrides managed state of type `T` with `state`, whether `T` is
+    /// managed before or after this call. Used by local client builders.
+    pub(crate) fn override_state<T>(mut self, state: T) -> Self
+        where T: Send + Sync + 'static
+    {
+        let type_name = std::any::type_name::<T>();
+        if !self.state.set(Override(state)) {
+            error!("state for type '{}' is already overridden", type_name);
+            panic!("aborting due to duplicated state override");
+        }
+
+        if !self.managed.contains(&type_name) {
+            self.managed.push(type_name);
+        }
+
+        self
+    }
+
+    /// Removes all fairings named `name`, including any attached later.
+    /// Used by local client builders.
+    pub(crate) fn disable_fairing(mut self, name: String) -> Self {
+        self.fairings.disable(name);
         self
     }
 
@@ -491,6 +562,60 @@ impl Rocket<Build> {
         self
     }
 
+    /// Wraps Rocket's request pipeline in the [`tower::Layer`] `layer`.
+    ///
+    /// Layers wrap all request processing, including request and response
+    /// fairings. They are applied in order: the last layer applied is the
+    /// outermost and sees requests first and responses last. Layers apply to
+    /// requests received by Rocket's HTTP/1 and HTTP/2 server and to requests
+    /// dispatched via a [`RocketService`], but not to requests dispatched by a
+    /// local [`Client`](crate::local).
+    ///
+    /// A layer may change the type of the response body but not that of the
+    /// request body. If the layered service fails, the error is logged and an
+    /// empty `500 Internal Server Error` response is returned.
+    ///
+    /// [`tower::Layer`]: https://docs.rs/tower/0.5/tower/trait.Layer.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::service::RequestBody;
+    /// use tower::util::MapRequestLayer;
+    ///
+    /// #[get("/")]
+    /// fn index() -> &'static str {
+    ///     "Hello, world!"
+    /// }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount("/", routes![index])
+    ///         .layer(MapRequestLayer::new(|mut req: http::Request<RequestBody>| {
+    ///             req.headers_mut().insert("x-layered", "yes".parse().unwrap());
+    ///             req
+    ///         }))
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(feature = "tower")]
+    #[cfg_attr(nightly, doc(cfg(feature = "tower")))]
+    pub fn layer<L, B>(mut self, layer: L) -> Self
+        where L: tower::Layer<BoxService> + Send + Sync + 'static,
+              L::Service: tower::Service<http::Request<RequestBody>, Response = http::Response<B>>,
+              L::Service: Clone + Send + 'static,
+              <L::Service as tower::Service<http::Request<RequestBody>>>::Future: Send + 'static,
+              <L::Service as tower::Service<http::Request<RequestBody>>>::Error:
+                  Into<Box<dyn std::error::Error + Send + Sync>>,
+              B: hyper::body::Body + Send + 'static,
+              B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.layers.push(layer);
+        self
+    }
+
     /// Returns a `Future` that transitions this instance of `Rocket` into the
     /// _ignite_ phase.
     ///
@@ -565,8 +690,7 @@ impl Rocket<Build> {
         // Finally, freeze managed state for faster access later.
         self.state.freeze();
 
-        // Log everything we know: config, routes, catchers, fairings.
-        // TODO: Store/print managed state type names?
+        // Log everything we know: config, routes, catchers, fairings, state.
         let fairings = self.fairings.unique_set();
         span_info!("config", profile = %self.figment().profile() => {
             config.trace_info();
@@ -574,15 +698,43 @@ impl Rocket<Build> {
         });
 
         span_info!("routes", count = self.routes.len() => self.routes().trace_all_info());
+        let deprecated: Vec<_> = self.routes()
+            .filter_map(|route| Some((route, route.deprecated.as_ref()?)))
+            .collect();
+
+        if !deprecated.is_empty() {
+            span_warn!("deprecated", count = deprecated.len() => for (route, notice) in deprecated {
+                warn!(
+                    name: "deprecated_route",
+                    route = route.name.as_deref(),
+                    uri = %route.uri,
+                    since = %notice.since.date(),
+                    sunset = notice.sunset.map(|date| display(date.date())),
+                    successor = notice.successor.as_deref(),
+                    "{}", match notice.is_sunset() {
+                        true => "route is past its sunset date",
+                        false => "route is deprecated",
+                    }
+                );
+            });
+        }
+
         span_info!("catchers", count = self.catchers.len() => self.catchers().trace_all_info());
         span_info!("fairings", count = fairings.len() => fairings.trace_all_info());
+        span_debug!("state", count = self.managed.len() => for name in &self.managed {
+            debug!(type_name = %name);
+        });
 
         // Ignite the rocket.
         let rocket: Rocket<Ignite> = Rocket(Igniting {
             shutdown: Stages::new(),
+            bound: Binding::new(),
             figment: self.0.figment,
             fairings: self.0.fairings,
             state: self.0.state,
+            managed: self.0.managed,
+            #[cfg(feature = "tower")]
+            layers: self.0.layers,
             router, config,
         });
 
@@ -648,15 +800,35 @@ impl Rocket<Ignite> {
         self.shutdown.start.clone()
     }
 
+    /// Returns a handle that resolves to the endpoints Rocket binds to once
+    /// it is launched. When a port of `0` is configured, the endpoints
+    /// contain the port actually assigned by the operating system.
+    ///
+    /// See [`BoundAddresses`] for details and an example.
+    pub fn bound_addresses(&self) -> BoundAddresses {
+        self.bound.handle()
+    }
+
     pub(crate) fn into_orbit(self, endpoints: Vec<Endpoint>) -> Rocket<Orbit> {
+        let load = Arc::new(Limiter::new(&self.0.config.load));
+        self.0.shutdown.track(load.clone());
+        self.0.bound.set(&endpoints);
+        let tasks = TaskSet::new(self.0.shutdown.grace.clone());
         Rocket(Orbiting {
+            load,
+            errors: ErrorLog::default(),
+            tasks,
             endpoints,
-            router: self.0.router,
+            router: HotRouter::new(self.0.router),
             fairings: self.0.fairings,
             figment: self.0.figment,
             config: self.0.config,
             state: self.0.state,
+            managed: self.0.managed,
             shutdown: self.0.shutdown,
+            bound: self.0.bound,
+            #[cfg(feature = "tower")]
+            layers: self.0.layers,
         })
     }
 
@@ -732,12 +904,16 @@ impl Rocket<Orbit> {
 
     pub(crate) fn deorbit(self) -> Rocket<Ignite> {
         Rocket(Igniting {
-            router: self.0.router,
+            router: self.0.router.into_inner(),
             fairings: self.0.fairings,
             figment: self.0.figment,
             config: self.0.config,
             state: self.0.state,
+            managed: self.0.managed,
             shutdown: self.0.shutdown,
+            bound: self.0.bound,
+            #[cfg(feature = "tower")]
+            layers: self.0.layers,
         })
     }
 
@@ -753,7 +929,8 @@ impl Rocket<Orbit> {
             );
         }
 
-        tracing::info!(name: "liftoff", endpoint = %rocket.endpoints[0]);
+        let endpoints = rocket.endpoints.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        tracing::info!(name: "liftoff", endpoint = %endpoints.join(", "));
     }
 
     /// Returns the finalized, active configuration. This is guaranteed to
@@ -778,10 +955,202 @@ impl Rocket<Orbit> {
         &self.config
     }
 
+    /// Returns the endpoints Rocket is bound to and serving requests on.
+    ///
+    /// When a port of `0` is configured, the endpoints contain the port
+    /// actually assigned by the operating system. To retrieve the endpoints
+    /// from outside of a fairing or handler, use
+    /// [`Rocket::bound_addresses()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .attach(AdHoc::on_liftoff("Register", |rocket| Box::pin(async move {
+    ///             for endpoint in rocket.endpoints() {
+    ///                 println!("registering service at {endpoint}");
+    ///             }
+    ///         })))
+    /// }
+    /// ```
     pub fn endpoints(&self) -> impl Iterator<Item = &Endpoint> {
         self.endpoints.iter()
     }
 
+    /// Returns a handle that resolves to the endpoints Rocket is bound to.
+    /// Because `self` is in orbit, the handle resolves immediately to the
+    /// same endpoints as [`Rocket::endpoints()`].
+    ///
+    /// See [`BoundAddresses`] for details.
+    pub fn bound_addresses(&self) -> BoundAddresses {
+        self.bound.handle()
+    }
+
+    /// Mounts all of the `routes` at the given `base` mount point while Rocket
+    /// is running, returning a [`HotMount`] handle that unmounts them.
+    ///
+    /// `base` is interpreted as in [`Rocket::mount()`]. The active router is
+    /// rebuilt with the new routes and replaced atomically: requests routed
+    /// before this method returns are routed without them, and all requests
+    /// routed afterwards see them. Routes mounted at launch cannot be
+    /// unmounted.
+    ///
+    /// Unlike routes mounted before launch, the [sentinels](crate::Sentinel)
+    /// of hot-mounted routes are not queried. A replaced router is retained
+    /// until no in-flight request routed with it remains. Hot-mounted routes
+    /// are not returned by [`Rocket::routes()`]; see
+    /// [`Rocket::active_routes()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::Collisions`](crate::error::ErrorKind) error,
+    /// leaving the active router unchanged, if any of `routes` collide with
+    /// each other or with a mounted route.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::local::blocking::Client;
+    /// # use rocket::http::Status;
+    /// #[get("/hello")]
+    /// fn hello() -> &'static str { "Hello, world!" }
+    ///
+    /// let client = Client::debug_with(vec![]).unwrap();
+    /// assert_eq!(client.get("/plugin/hello").dispatch().status(), Status::NotFound);
+    ///
+    /// let mount = client.rocket().hot_mount("/plugin", routes![hello]).unwrap();
+    /// let response = client.get("/plugin/hello").dispatch();
+    /// assert_eq!(response.into_string().unwrap(), "Hello, world!");
+    ///
+    /// // Routes can't be mounted twice.
+    /// assert!(client.rocket().hot_mount("/plugin", routes![hello]).is_err());
+    /// # assert!(mount.unmount(client.rocket()));
+    /// ```
+    #[track_caller]
+    pub fn hot_mount<'a, B, R>(&self, base: B, routes: R) -> Result<HotMount, Error>
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let base = parse_base("route", base);
+        let routes: Vec<Route> = routes.into().into_iter()
+            .map(|route| route.rebase(base.clone()))
+            .collect();
+
+        let id = self.router.mount(routes.clone())
+            .map_err(|(r, c)| ErrorKind::Collisions { routes: r, catchers: c, })?;
+
+        span_info!("hot_mount", id, count = routes.len() => routes.iter().trace_all_info());
+        Ok(HotMount::new(id))
+    }
+
+    /// Returns all of the routes currently mounted on this instance of Rocket,
+    /// including those [hot-mounted](Rocket::hot_mount()). The order is
+    /// unspecified.
+    ///
+    /// Unlike [`Rocket::routes()`], which borrows the routes mounted at
+    /// launch, this method returns a copy of the active routes, which may
+    /// change as routes are mounted and unmounted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::local::blocking::Client;
+    /// #[get("/hello")]
+    /// fn hello() -> &'static str { "Hello, world!" }
+    ///
+    /// let client = Client::debug_with(routes![hello]).unwrap();
+    /// let mount = client.rocket().hot_mount("/plugin", routes![hello]).unwrap();
+    /// assert_eq!(client.rocket().routes().count(), 1);
+    /// assert_eq!(client.rocket().active_routes().len(), 2);
+    /// # assert!(mount.unmount(client.rocket()));
+    /// ```
+    pub fn active_routes(&self) -> Vec<Route> {
+        self.router.get().routes.iter().cloned().collect()
+    }
+
+    /// Returns a snapshot of the server's current load: open connections,
+    /// in-flight and queued requests, and the number of requests shed so far.
+    /// See [`load`](crate::load) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::blocking::Client;
+    ///
+    /// let client = Client::debug_with(vec![]).unwrap();
+    /// let load = client.rocket().load();
+    /// assert_eq!(load.requests, 0);
+    /// assert_eq!(load.shed, 0);
+    /// ```
+    pub fn load(&self) -> LoadStats {
+        self.load.stats()
+    }
+
+    /// Returns summaries of the most recent server errors, oldest first.
+    ///
+    /// Rocket retains a summary of each of the last 64 requests that failed
+    /// with a `5xx` status and were thus assigned an
+    /// [`ErrorReference`](crate::catcher::ErrorReference). Errors from before
+    /// the most recent liftoff are not retained. See
+    /// [`ErrorSummary`](crate::catcher::ErrorSummary) for details and an
+    /// example.
+    pub fn recent_errors(&self) -> Vec<ErrorSummary> {
+        self.errors.recent()
+    }
+
+    /// Returns statistics about the async runtime the caller is running on,
+    /// or `None` if the caller isn't running on an async runtime. See
+    /// [`runtime`](crate::runtime) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::asynchronous::Client;
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::tracked(rocket::build()).await.unwrap();
+    /// let stats = client.rocket().runtime_stats().unwrap();
+    /// assert!(stats.workers > 0);
+    /// # });
+    /// ```
+    pub fn runtime_stats(&self) -> Option<RuntimeStats> {
+        crate::runtime::stats()
+    }
+
+    /// Returns the set of supervised tasks spawned by the application. Tasks
+    /// spawned via the set are tracked, cancelled when the shutdown grace
+    /// period elapses, and logged if they panic. See [`task`](crate::task) for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::asynchronous::Client;
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::tracked(rocket::build()).await.unwrap();
+    /// let tasks = client.rocket().tasks();
+    /// let handle = tasks.spawn("cache refresh", async { /* ... */ });
+    /// assert_eq!(handle.await.unwrap(), Some(()));
+    /// assert!(tasks.is_empty());
+    /// # });
+    /// ```
+    pub fn tasks(&self) -> &TaskSet {
+        &self.tasks
+    }
+
     /// Returns a handle which can be used to trigger a shutdown and detect a
     /// triggered shutdown.
     ///
@@ -814,10 +1183,36 @@ impl Rocket<Orbit> {
     }
 }
 
+/// Parses a mount or registration `base`, panicking if it is invalid and
+/// dropping its query, if any, with a warning.
+#[track_caller]
+pub(crate) fn parse_base<'a, B>(kind: &str, base: B) -> Origin<'static>
+    where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+          B::Error: fmt::Display,
+{
+    let mut base = match base.clone().try_into() {
+        Ok(origin) => origin.into_owned(),
+        Err(e) => {
+            error!(%base, location = %Location::caller(), "invalid {kind} base uri: {e}");
+            panic!("aborting due to {} base error", kind);
+        }
+    };
+
+    if base.query().is_some() {
+        warn!(%base, location = %Location::caller(), "query in {kind} base is ignored");
+        base.clear_query();
+    }
+
+    base
+}
+
 impl<P: Phase> Rocket<P> {
     /// Returns an iterator over all of the routes mounted on this instance of
     /// Rocket. The order is unspecified.
     ///
+    /// Routes [hot-mounted](Rocket::hot_mount()) on a running instance are not
+    /// included. Use [`Rocket::active_routes()`] to retrieve them.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -842,7 +1237,7 @@ impl<P: Phase> Rocket<P> {
         match self.0.as_ref() {
             StateRef::Build(p) => Either::Left(p.routes.iter()),
             StateRef::Ignite(p) => Either::Right(p.router.routes.iter()),
-            StateRef::Orbit(p) => Either::Right(p.router.routes.iter()),
+            StateRef::Orbit(p) => Either::Right(p.router.launch().routes.iter()),
         }
     }
 
@@ -873,7 +1268,7 @@ impl<P: Phase> Rocket<P> {
         match self.0.as_ref() {
             StateRef::Build(p) => Either::Left(p.catchers.iter()),
             StateRef::Ignite(p) => Either::Right(p.router.catchers.iter()),
-            StateRef::Orbit(p) => Either::Right(p.router.catchers.iter()),
+            StateRef::Orbit(p) => Either::Right(p.router.launch().catchers.iter()),
         }
     }
 
@@ -890,10 +1285,41 @@ impl<P: Phase> Rocket<P> {
     /// assert_eq!(rocket.state::<MyState>().unwrap(), &MyState("hello!"));
     /// ```
     pub fn state<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        let state = match self.0.as_ref() {
+            StateRef::Build(p) => &p.state,
+            StateRef::Ignite(p) => &p.state,
+            StateRef::Orbit(p) => &p.state,
+        };
+
+        state.try_get::<Override<T>>()
+            .map(|o| &o.0)
+            .or_else(|| state.try_get())
+    }
+
+    /// Returns an iterator over the type names, as reported by
+    /// [`std::any::type_name()`], of the values in managed state, in the order
+    /// they were [managed](Rocket::manage()). Type names are informational:
+    /// their exact contents are not guaranteed to be stable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// struct MyState(&'static str);
+    ///
+    /// let rocket = rocket::build()
+    ///     .manage(MyState("hello!"))
+    ///     .manage(10usize);
+    ///
+    /// let names: Vec<_> = rocket.managed_types().collect();
+    /// assert_eq!(names.len(), 2);
+    /// assert!(names[0].ends_with("MyState"));
+    /// assert_eq!(names[1], "usize");
+    /// ```
+    pub fn managed_types(&self) -> impl Iterator<Item = &'static str> + '_ {
         match self.0.as_ref() {
-            StateRef::Build(p) => p.state.try_get(),
-            StateRef::Ignite(p) => p.state.try_get(),
-            StateRef::Orbit(p) => p.state.try_get(),
+            StateRef::Build(p) => p.managed.iter().copied(),
+            StateRef::Ignite(p) => p.managed.iter().copied(),
+            StateRef::Orbit(p) => p.managed.iter().copied(),
         }
     }
 
@@ -1012,6 +1438,32 @@ impl<P: Phase> Rocket<P> {
         }
     }
 
+    /// Returns the [`Info`](crate::fairing::Info) of every attached fairing
+    /// that will run, in the order the fairings were attached. Singletons that
+    /// were replaced by a later fairing are not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let rocket = rocket::build()
+    ///     .attach(AdHoc::on_liftoff("Liftoff Printer", |_| Box::pin(async {})))
+    ///     .attach(AdHoc::on_response("Response Printer", |_, _| Box::pin(async {})));
+    ///
+    /// let names: Vec<_> = rocket.fairing_info().map(|info| info.name).collect();
+    /// assert_eq!(names, ["Liftoff Printer", "Response Printer"]);
+    /// ```
+    pub fn fairing_info(&self) -> impl Iterator<Item = crate::fairing::Info> + '_ {
+        let fairings = match self.0.as_ref() {
+            StateRef::Build(p) => &p.fairings,
+            StateRef::Ignite(p) => &p.fairings,
+            StateRef::Orbit(p) => &p.fairings,
+        };
+
+        fairings.unique_set().into_iter().map(|fairing| fairing.info())
+    }
+
     /// Returns a mutable reference to the first fairing of type `F` if it is
     /// attached. Otherwise, returns `None`.
     ///
@@ -1137,6 +1589,32 @@ impl<P: Phase> Rocket<P> {
         Ok(self.into_ignite().await?._local_launch(e).await)
     }
 
+    /// Ignites `self` and lifts it off without binding to any interface,
+    /// returning a [`RocketService`] through which requests received by an
+    /// external server or runtime can be dispatched.
+    ///
+    /// Liftoff fairings run before the returned future resolves. Shutdown
+    /// signals are not listened for, and shutdown fairings run only when
+    /// [`RocketService::terminate()`] is called. See the
+    /// [`service`](crate::service) module docs for details.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if igniting `self` fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let service = rocket::build().into_service().await;
+    /// assert!(service.is_ok());
+    /// # });
+    /// ```
+    pub async fn into_service(self) -> Result<RocketService, Error> {
+        let rocket = self.local_launch(Endpoint::new("service")).await?;
+        Ok(RocketService::new(rocket))
+    }
+
     /// Returns a `Future` that transitions this instance of `Rocket` from any
     /// phase into the _orbit_ phase. When `await`ed, the future drives the
     /// server forward, listening for and dispatching requests to mounted routes
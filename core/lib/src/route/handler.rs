@@ -146,6 +146,40 @@ pub trait Handler: Cloneable + Send + Sync + 'static {
     /// the next matching route is attempted. If there are no other matching
     /// routes, the `404` error catcher is invoked.
     async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r>;
+
+    /// Called by Rocket instead of [`Handler::handle()`] when a `HEAD` request
+    /// is automatically handled by this handler's `GET` route, that is, when no
+    /// `HEAD` route handled the request.
+    ///
+    /// The response body is discarded. Handlers that can cheaply compute a
+    /// response's metadata without its body should override this method. The
+    /// default implementation calls [`Handler::handle()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::{Request, Data};
+    /// use rocket::route::{Handler, Outcome};
+    /// use rocket::http::Status;
+    ///
+    /// #[derive(Clone)]
+    /// struct Report;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Handler for Report {
+    ///     async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+    ///         # let expensive_report = || String::new();
+    ///         Outcome::from(req, expensive_report())
+    ///     }
+    ///
+    ///     async fn handle_head<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+    ///         Outcome::from(req, Status::Ok)
+    ///     }
+    /// }
+    /// ```
+    async fn handle_head<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        self.handle(request, data).await
+    }
 }
 
 // We write this manually to avoid double-boxing.
@@ -8,5 +8,6 @@ mod segment;
 pub use route::*;
 pub use handler::*;
 pub use uri::*;
+pub use crate::router::HotMount;
 
 pub(crate) use segment::Segment;
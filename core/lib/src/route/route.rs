@@ -1,7 +1,11 @@
 use std::fmt;
 use std::borrow::Cow;
+use std::sync::Arc;
+
+use time::OffsetDateTime;
 
 use crate::http::{uri, Method, MediaType};
+use crate::response::{Deprecated, HeaderPolicy};
 use crate::route::{Handler, RouteUri, BoxFuture};
 use crate::sentinel::Sentry;
 
@@ -174,10 +178,20 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The route's deprecation, if it is deprecated.
+    ///
+    /// The deprecation headers are set on every successful response of a
+    /// deprecated route, as if its handler's responder were wrapped in a
+    /// [`Deprecated`], and deprecated routes are reported at launch.
+    pub deprecated: Option<Deprecated<()>>,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
     /// The file, line, and column where the route was defined, if known.
     pub(crate) location: Option<(&'static str, u32, u32)>,
+    /// The names and types of the dynamic path and query parameters, if known.
+    pub(crate) params: Vec<(&'static str, &'static str)>,
+    /// The header policies of the mounts the route was mounted with.
+    pub(crate) header_policies: Vec<Arc<HeaderPolicy>>,
 }
 
 impl Route {
@@ -255,6 +269,9 @@ impl Route {
             sentinels: Vec::new(),
             handler: Box::new(handler),
             location: None,
+            params: Vec::new(),
+            header_policies: Vec::new(),
+            deprecated: None,
             method: method.into(),
             rank,
             uri,
@@ -356,6 +373,8 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("deprecated", &self.deprecated)
+            .field("header_policies", &self.header_policies)
             .finish()
     }
 }
@@ -380,6 +399,11 @@ pub struct StaticInfo {
     pub sentinels: Vec<Sentry>,
     /// The file, line, and column where the route was defined.
     pub location: (&'static str, u32, u32),
+    /// The names and types of the route's dynamic path and query parameters.
+    pub params: &'static [(&'static str, &'static str)],
+    /// The route's deprecation date, sunset date, and successor URI, if it is
+    /// deprecated. Dates are in seconds since the Unix epoch.
+    pub deprecated: Option<(i64, Option<i64>, Option<&'static str>)>,
 }
 
 #[doc(hidden)]
@@ -396,6 +420,17 @@ impl From<StaticInfo> for Route {
             format: info.format,
             sentinels: info.sentinels.into_iter().collect(),
             location: Some(info.location),
+            params: info.params.to_vec(),
+            header_policies: Vec::new(),
+            deprecated: info.deprecated.map(|(since, sunset, successor)| {
+                let date = |secs| OffsetDateTime::from_unix_timestamp(secs)
+                    .expect("codegen emits valid dates");
+
+                let mut deprecated = Deprecated::new(date(since), ());
+                deprecated.sunset = sunset.map(date);
+                deprecated.successor = successor.map(Cow::Borrowed);
+                deprecated
+            }),
             uri,
         }
     }
@@ -1,3 +1,5 @@
+use crate::http::uri::Glob;
+
 #[derive(Debug, Clone)]
 pub struct Segment {
     /// The name of the parameter or just the static string.
@@ -6,17 +8,31 @@ pub struct Segment {
     pub dynamic: bool,
     /// This is a `<a..>`.
     pub dynamic_trail: bool,
+    /// The constraint in a `<a: glob>` or `<a..: glob>`.
+    pub constraint: Option<Glob>,
 }
 
 impl Segment {
+    /// Panics if the segment is a parameter with an invalid constraint, which
+    /// codegen rejects at compile-time.
+    #[track_caller]
     pub fn from(segment: &crate::http::RawStr) -> Self {
-        let mut value = segment;
+        let mut value = segment.as_str();
         let mut dynamic = false;
         let mut dynamic_trail = false;
+        let mut constraint = None;
 
         if segment.starts_with('<') && segment.ends_with('>') {
             dynamic = true;
-            value = &segment[1..(segment.len() - 1)];
+            value = &value[1..(value.len() - 1)];
+
+            if let Some((name, pattern)) = value.split_once(':') {
+                value = name.trim_end();
+                constraint = match Glob::parse(pattern.trim()) {
+                    Ok(glob) => Some(glob),
+                    Err(e) => panic!("invalid constraint in route segment {segment}: {e}"),
+                };
+            }
 
             if value.ends_with("..") {
                 dynamic_trail = true;
@@ -24,6 +40,6 @@ impl Segment {
             }
         }
 
-        Segment { value: value.to_string(), dynamic, dynamic_trail }
+        Segment { value: value.to_string(), dynamic, dynamic_trail, constraint }
     }
 }
@@ -179,7 +179,16 @@ impl Collide for RouteUri<'_> {
 
 impl Collide for Segment {
     fn collides_with(&self, other: &Self) -> bool {
-        self.dynamic || other.dynamic || self.value == other.value
+        // A constrained parameter only matches static segments it accepts.
+        let accepts = |dynamic: &Segment, fixed: &Segment| dynamic.dynamic_trail
+            || dynamic.constraint.as_ref().map_or(true, |glob| glob.matches(&fixed.value));
+
+        match (self.dynamic, other.dynamic) {
+            (true, true) => true,
+            (true, false) => accepts(self, other),
+            (false, true) => accepts(other, self),
+            (false, false) => self.value == other.value,
+        }
     }
 }
 
@@ -399,6 +408,20 @@ mod tests {
         assert_collision!("/<a..>", "/");
     }
 
+    #[test]
+    fn constrained_collisions() {
+        assert_no_collision!("/<a: [0-9]+>", "/me");
+        assert_no_collision!("/<a: [0-9]+>", "/");
+        assert_no_collision!("/user/<a: *.json>", "/user/me.html");
+        assert_no_collision!("/<a: v[0-9]>/<b>", "/v10/<b>");
+
+        assert_collision!("/<a: [0-9]+>", "/123");
+        assert_collision!("/<a: [0-9]+>", "/<b>");
+        assert_collision!("/<a: [0-9]+>", "/<b: [a-z]+>");
+        assert_collision!("/user/<a: *.json>", "/user/me.json");
+        assert_collision!("/<a..: *.md>", "/a/b/c.txt");
+    }
+
     fn mt_mt_collide(mt1: &str, mt2: &str) -> bool {
         let mt_a = MediaType::from_str(mt1).expect(mt1);
         let mt_b = MediaType::from_str(mt2).expect(mt2);
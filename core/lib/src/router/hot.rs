@@ -0,0 +1,205 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use parking_lot::{Mutex, RwLock};
+
+use crate::{Orbit, Rocket, Route};
+use crate::router::{Collisions, Finalized, Router};
+
+/// A handle to routes mounted while Rocket is running.
+///
+/// A `HotMount` is returned by [`Rocket::hot_mount()`]. The routes it refers
+/// to remain mounted until [`HotMount::unmount()`] is called. Dropping a
+/// `HotMount` does _not_ unmount its routes.
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[must_use = "dropping a `HotMount` makes its routes impossible to unmount"]
+pub struct HotMount {
+    id: u64,
+}
+
+/// The router of a running Rocket instance, which may be atomically replaced
+/// to add or remove routes.
+///
+/// Each request holds the router that was active when it was routed, via
+/// `Request::router()`, so a replaced router is dropped once no request uses
+/// it. Until routes are hot-mounted, requests use the launch router directly.
+pub(crate) struct HotRouter {
+    /// The router at launch, which is never replaced.
+    launch: Arc<Router<Finalized>>,
+    /// Whether `current` may contain hot-mounted routes.
+    hot: AtomicBool,
+    /// The active router: `launch` with all hot-mounted routes, if any.
+    current: RwLock<Arc<Router<Finalized>>>,
+    /// Routes mounted via [`HotRouter::mount()`], by mount ID.
+    mounts: Mutex<Vec<(u64, Vec<Route>)>>,
+}
+
+impl HotRouter {
+    pub fn new(router: Router<Finalized>) -> Self {
+        let launch = Arc::new(router);
+        HotRouter {
+            current: RwLock::new(launch.clone()),
+            hot: AtomicBool::new(false),
+            launch,
+            mounts: Mutex::new(vec![]),
+        }
+    }
+
+    /// Returns the router at launch, without any hot-mounted routes.
+    #[inline]
+    pub fn launch(&self) -> &Router<Finalized> {
+        &self.launch
+    }
+
+    /// Returns the active router.
+    #[inline]
+    pub fn get(&self) -> Arc<Router<Finalized>> {
+        self.current.read().clone()
+    }
+
+    /// Returns the active router if routes are hot-mounted and `None` if the
+    /// active router is the launch router. Only locks in the former case.
+    #[inline]
+    pub fn hot(&self) -> Option<Arc<Router<Finalized>>> {
+        self.hot.load(Ordering::Acquire).then(|| self.get())
+    }
+
+    /// Adds `routes` to the active router, returning an ID for the mount.
+    pub fn mount(&self, routes: Vec<Route>) -> Result<u64, Collisions> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        let mut mounts = self.mounts.lock();
+        let mut new_mounts = mounts.clone();
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        new_mounts.push((id, routes));
+
+        let router = self.build(&new_mounts)?;
+        *mounts = new_mounts;
+        *self.current.write() = Arc::new(router);
+        self.hot.store(true, Ordering::Release);
+        Ok(id)
+    }
+
+    /// Removes the routes mounted with `id` from the active router. Returns
+    /// `false` if there are no such routes.
+    pub fn unmount(&self, id: u64) -> bool {
+        let mut mounts = self.mounts.lock();
+        let Some(i) = mounts.iter().position(|(mount, _)| *mount == id) else {
+            return false;
+        };
+
+        mounts.remove(i);
+        if mounts.is_empty() {
+            *self.current.write() = self.launch.clone();
+            self.hot.store(false, Ordering::Release);
+            return true;
+        }
+
+        let router = self.build(&mounts).expect("removing routes can't cause collisions");
+        *self.current.write() = Arc::new(router);
+        true
+    }
+
+    /// Builds a router from the launch router's routes and catchers and the
+    /// hot-mounted `mounts`.
+    fn build(&self, mounts: &[(u64, Vec<Route>)]) -> Result<Router<Finalized>, Collisions> {
+        let mut router = Router::new();
+        router.routes.extend(self.launch.routes.iter().cloned());
+        router.routes.extend(mounts.iter().flat_map(|(_, routes)| routes.iter().cloned()));
+        router.catchers.extend(self.launch.catchers.iter().cloned());
+        router.finalize()
+    }
+
+    /// Returns the active router.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a request still holds a router. Since requests borrow the
+    /// Rocket instance that owns `self`, this cannot happen.
+    pub fn into_inner(self) -> Router<Finalized> {
+        let HotRouter { launch, current, .. } = self;
+        drop(launch);
+        Arc::try_unwrap(current.into_inner())
+            .unwrap_or_else(|_| panic!("active router is held by a request"))
+    }
+}
+
+impl fmt::Debug for HotRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl HotMount {
+    pub(crate) fn new(id: u64) -> Self {
+        HotMount { id }
+    }
+
+    /// Unmounts the routes mounted by the [`Rocket::hot_mount()`] call that
+    /// returned `self` from `rocket`. Returns `false`, doing nothing, if the
+    /// routes weren't mounted on `rocket`.
+    ///
+    /// Requests routed before this method returns may still be handled by the
+    /// unmounted routes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # use rocket::local::blocking::Client;
+    /// # use rocket::http::Status;
+    /// #[get("/promo")]
+    /// fn promo() -> &'static str { "50% off!" }
+    ///
+    /// let client = Client::debug_with(vec![]).unwrap();
+    /// let mount = client.rocket().hot_mount("/", routes![promo]).unwrap();
+    /// assert_eq!(client.get("/promo").dispatch().status(), Status::Ok);
+    ///
+    /// assert!(mount.unmount(client.rocket()));
+    /// assert_eq!(client.get("/promo").dispatch().status(), Status::NotFound);
+    /// ```
+    pub fn unmount(self, rocket: &Rocket<Orbit>) -> bool {
+        let unmounted = rocket.router.unmount(self.id);
+        if unmounted {
+            info!(id = self.id, "hot mounted routes unmounted");
+        }
+
+        unmounted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::HotRouter;
+    use crate::Route;
+    use crate::route::dummy_handler;
+    use crate::router::Router;
+    use crate::http::Method::Get;
+
+    #[test]
+    fn replaced_routers_are_dropped() {
+        let hot = HotRouter::new(Router::new().finalize().unwrap());
+        let launch = hot.get();
+        assert!(hot.hot().is_none());
+
+        let id = hot.mount(vec![Route::new(Get, "/", dummy_handler)]).unwrap();
+        let mounted = hot.hot().unwrap();
+        assert_eq!(mounted.routes.len(), 1);
+        assert_eq!(Arc::strong_count(&mounted), 2);
+
+        assert!(hot.unmount(id));
+        assert!(!hot.unmount(id));
+        assert!(hot.hot().is_none());
+        assert_eq!(Arc::strong_count(&mounted), 1);
+        assert!(hot.get().routes.is_empty());
+
+        // With no hot-mounted routes, the launch router is active again.
+        drop(mounted);
+        assert_eq!(Arc::strong_count(&launch), 3);
+        drop(launch);
+        assert!(hot.into_inner().routes.is_empty());
+    }
+}
@@ -18,6 +18,8 @@ impl Route {
     ///       route's format.
     ///   * All static segments in the route's URI match the corresponding
     ///     components in the same position in the incoming request URI.
+    ///   * The values of all constrained dynamic segments in the route's URI,
+    ///     like `<file: *.md>`, match their [`Glob`](crate::http::uri::Glob).
     ///   * The route URI has no query part _or_ all static segments in the
     ///     route's query string are in the request query string, though in any
     ///     position.
@@ -72,6 +74,12 @@ impl Route {
             && queries_match(self, request)
             && formats_match(self, request)
     }
+
+    /// Returns `true` if `self` matches `request` without regard to the
+    /// request's method or format.
+    pub(crate) fn matches_resource(&self, request: &Request<'_>) -> bool {
+        paths_match(self, request) && queries_match(self, request)
+    }
 }
 
 impl Catcher {
@@ -145,7 +153,8 @@ fn methods_match(route: &Route, req: &Request<'_>) -> bool {
 }
 
 fn paths_match(route: &Route, req: &Request<'_>) -> bool {
-    trace!(route.uri = %route.uri, request.uri = %req.uri());
+    let uri = req.rocket().config().redact.uri(req.uri());
+    trace!(route.uri = %route.uri, request.uri = %uri);
     let route_segments = &route.uri.metadata.uri_segments;
     let req_segments = req.uri().path().segments();
 
@@ -161,14 +170,25 @@ fn paths_match(route: &Route, req: &Request<'_>) -> bool {
     }
 
     // We've checked everything beyond the zip of their lengths already.
-    for (route_seg, req_seg) in route_segments.iter().zip(req_segments.clone()) {
+    let segments = route_segments.iter().zip(req_segments.clone());
+    for (i, (route_seg, req_seg)) in segments.enumerate() {
         if route_seg.dynamic_trail {
-            return true;
+            return route_seg.constraint.as_ref().map_or(true, |glob| {
+                let trail = req_segments.skip(i).collect::<Vec<_>>().join("/");
+                glob.matches(&trail)
+            });
         }
 
         if !route_seg.dynamic && route_seg.value != req_seg {
             return false;
         }
+
+        if let Some(glob) = &route_seg.constraint {
+            if !glob.matches(req_seg) {
+                debug!(param = route_seg.value, constraint = %glob, "unsatisfied constraint");
+                return false;
+            }
+        }
     }
 
     true
@@ -3,6 +3,11 @@
 mod router;
 mod collider;
 mod matcher;
+mod trie;
+mod hot;
 
 pub(crate) use router::*;
 pub(crate) use collider::*;
+pub(crate) use trie::*;
+pub(crate) use hot::HotRouter;
+pub use hot::HotMount;
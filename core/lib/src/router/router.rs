@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use crate::request::Request;
 use crate::http::{Method, Status};
 use crate::{Route, Catcher};
-use crate::router::Collide;
+use crate::router::{Collide, Trie};
 
 #[derive(Debug)]
 pub(crate) struct Router<T>(T);
@@ -19,7 +19,7 @@ pub struct Pending {
 pub struct Finalized {
     pub routes: Vec<Route>,
     pub catchers: Vec<Catcher>,
-    route_map: HashMap<Method, Vec<usize>>,
+    route_map: HashMap<Method, Trie>,
     catcher_map: HashMap<Option<u16>, Vec<usize>>,
 }
 
@@ -55,13 +55,13 @@ impl Router<Pending> {
             return Err((route_collisions, catcher_collisions))
         }
 
-        // create the route map
-        let mut route_map: HashMap<Method, Vec<usize>> = HashMap::new();
+        // create the route map: a path trie for each method
+        let mut route_map: HashMap<Method, Trie> = HashMap::new();
         for (i, route) in self.routes.iter().enumerate() {
             match route.method {
-                Some(method) => route_map.entry(method).or_default().push(i),
+                Some(method) => route_map.entry(method).or_default().insert(i, route),
                 None => for method in Method::ALL_VARIANTS {
-                    route_map.entry(*method).or_default().push(i);
+                    route_map.entry(*method).or_default().insert(i, route);
                 }
             }
         }
@@ -72,11 +72,6 @@ impl Router<Pending> {
             catcher_map.entry(catcher.code).or_default().push(i);
         }
 
-        // sort routes by rank
-        for routes in route_map.values_mut() {
-            routes.sort_by_key(|&i| &self.routes[i].rank);
-        }
-
         // sort catchers by rank
         for catchers in catcher_map.values_mut() {
             catchers.sort_by_key(|&i| &self.catchers[i].rank);
@@ -96,14 +91,51 @@ impl Router<Finalized> {
         &'a self,
         req: &'r Request<'r>
     ) -> impl Iterator<Item = &'a Route> + 'r {
-        // Note that routes are presorted by ascending rank on each `add` and
-        // that all routes with `None` methods have been cloned into all methods.
-        self.route_map.get(&req.method())
-            .into_iter()
-            .flat_map(move |routes| routes.iter().map(move |&i| &self.routes[i]))
+        // Note that all routes with `None` methods have been inserted into the
+        // tries for all methods. Candidates are ordered by ascending rank, with
+        // ties broken by the order in which routes were added.
+        let mut candidates = vec![];
+        if let Some(trie) = self.route_map.get(&req.method()) {
+            trie.candidates(req.uri().path().segments(), &mut candidates);
+            candidates.sort_unstable_by_key(|&i| (self.routes[i].rank, i));
+        }
+
+        candidates.into_iter()
+            .map(move |i| &self.routes[i])
             .filter(move |r| r.matches(req))
     }
 
+    /// Returns the methods, in canonical order, of all routes that match `req`
+    /// without regard to its method or format. If any route matches, `HEAD`
+    /// is included when `GET` is and `OPTIONS` is always included, as Rocket
+    /// handles both automatically.
+    pub fn allowed_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        let segments = req.uri().path().segments();
+        let mut allowed: Vec<Method> = Method::ALL_VARIANTS.iter()
+            .filter(|method| self.route_map.get(method).map_or(false, |trie| {
+                let mut candidates = vec![];
+                trie.candidates(segments.clone(), &mut candidates);
+                candidates.iter().any(|&i| self.routes[i].matches_resource(req))
+            }))
+            .copied()
+            .collect();
+
+        if allowed.is_empty() {
+            return allowed;
+        }
+
+        if allowed.contains(&Method::Get) && !allowed.contains(&Method::Head) {
+            allowed.push(Method::Head);
+        }
+
+        if !allowed.contains(&Method::Options) {
+            allowed.push(Method::Options);
+        }
+
+        allowed.sort_by_key(|m| Method::ALL_VARIANTS.iter().position(|v| v == m));
+        allowed
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     #[track_caller]
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
@@ -592,6 +624,34 @@ mod test {
         r.catch(status, &request)
     }
 
+    #[test]
+    fn test_many_routes_routing() {
+        let mut router = Router::new();
+        for i in 0..1000 {
+            let uri = format!("/r{i}/<id>/edit");
+            router.routes.push(Route::new(Get, &uri, dummy_handler));
+            router.routes.push(Route::new(Post, &format!("/r{i}/<id>"), dummy_handler));
+        }
+
+        router.routes.push(Route::ranked(20, Get, "/r7/<a..>", dummy_handler));
+        router.routes.push(Route::ranked(10, None, "/<a>/<b>/<c>", dummy_handler));
+        router.routes.push(Route::ranked(30, None, "/<a..>", dummy_handler));
+        let router = router.finalize().unwrap();
+
+        let uris = |method, uri| -> Vec<String> {
+            matches(&router, method, uri).iter().map(|r| r.uri.to_string()).collect()
+        };
+
+        let expected = ["/r7/<id>/edit", "/<a>/<b>/<c>", "/r7/<a..>", "/<a..>"];
+        assert_eq!(uris(Get, "/r7/1/edit"), expected);
+        assert_eq!(uris(Get, "/r8/1/edit"), ["/r8/<id>/edit", "/<a>/<b>/<c>", "/<a..>"]);
+        assert_eq!(uris(Put, "/r8/1/edit"), ["/<a>/<b>/<c>", "/<a..>"]);
+        assert_eq!(uris(Post, "/r999/1"), ["/r999/<id>", "/<a..>"]);
+        assert_eq!(uris(Post, "/r1000/1"), ["/<a..>"]);
+        assert_eq!(uris(Get, "/r7"), ["/<a..>"]);
+        assert_eq!(uris(Get, "/"), ["/<a..>"]);
+    }
+
     macro_rules! assert_catcher_routing {
         (
             catch: [$(($code:expr, $uri:expr)),+],
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::Route;
+
+/// A trie over route path segments, mapping request paths to the indices of
+/// routes whose paths _may_ match them.
+///
+/// Static segments are stored in a map, so finding candidates for a request
+/// costs time proportional to its number of segments and the number of
+/// dynamic branches along the way, not to the total number of routes.
+/// Candidates are a superset of matching routes: query strings and formats
+/// are ignored, and a route with a dynamic trail is a candidate for any
+/// request that reaches it. Callers must still check [`Route::matches()`].
+#[derive(Debug, Default)]
+pub(crate) struct Trie {
+    /// Routes whose path ends at this node.
+    routes: Vec<usize>,
+    /// Routes whose path ends in a dynamic trail (`<a..>`) at this node.
+    trails: Vec<usize>,
+    /// Children for static segments, keyed by segment.
+    statics: HashMap<String, Trie>,
+    /// The child for dynamic segments (`<a>`), if any.
+    dynamic: Option<Box<Trie>>,
+}
+
+impl Trie {
+    /// Inserts the route at index `i` in the router, `route`, into the trie.
+    pub fn insert(&mut self, i: usize, route: &Route) {
+        let mut node = self;
+        for segment in &route.uri.metadata.uri_segments {
+            if segment.dynamic_trail {
+                node.trails.push(i);
+                return;
+            }
+
+            node = match segment.dynamic {
+                true => node.dynamic.get_or_insert_with(Default::default),
+                false => node.statics.entry(segment.value.clone()).or_default(),
+            };
+        }
+
+        node.routes.push(i);
+    }
+
+    /// Pushes into `candidates` the indices of all routes that may match the
+    /// request path with segments `segments`, in no particular order.
+    pub fn candidates<'s, I>(&self, mut segments: I, candidates: &mut Vec<usize>)
+        where I: Iterator<Item = &'s str> + Clone
+    {
+        candidates.extend_from_slice(&self.trails);
+        match segments.next() {
+            None => candidates.extend_from_slice(&self.routes),
+            Some(segment) => {
+                if let Some(child) = self.statics.get(segment) {
+                    child.candidates(segments.clone(), candidates);
+                }
+
+                if let Some(child) = &self.dynamic {
+                    child.candidates(segments, candidates);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Async runtime introspection.
+//!
+//! The async runtime Rocket creates for [`#[launch]`](crate::launch),
+//! [`#[main]`](crate::main), and [`execute()`](crate::execute()) is
+//! configured via the `workers`, `max_blocking`, `thread_name`, and
+//! `thread_stack_size` [configuration parameters](crate::Config). Rocket only
+//! reads these values from sources in the [default
+//! provider](crate::Config::figment()), and they cannot be changed once the
+//! runtime has started.
+//!
+//! [`Rocket::runtime_stats()`](crate::Rocket::runtime_stats()) reports the
+//! state of the runtime, which helps to diagnose thread-pool starvation: a
+//! persistently deep [global queue](RuntimeStats::global_queue_depth) with no
+//! [idle workers](RuntimeStats::idle_workers) indicates that futures are
+//! blocking worker threads, while a [saturated blocking
+//! pool](RuntimeStats::blocking_saturation()) indicates that too much blocking
+//! work is being offloaded to too few threads.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::fairing::AdHoc;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let stats = AdHoc::on_liftoff("Runtime Stats", |rocket| Box::pin(async move {
+//!         if let Some(stats) = rocket.runtime_stats() {
+//!             println!("{} workers, {} tasks", stats.workers, stats.alive_tasks);
+//!         }
+//!     }));
+//!
+//!     rocket::build().attach(stats)
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::runtime::{Builder, Handle};
+
+/// A snapshot of the state of the async runtime.
+///
+/// Returned by [`Rocket::runtime_stats()`](crate::Rocket::runtime_stats()).
+/// Statistics that are only tracked for runtimes created by Rocket are `None`
+/// for other runtimes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// The number of worker threads executing futures.
+    pub workers: usize,
+    /// The number of tasks that have been spawned and not yet completed.
+    pub alive_tasks: usize,
+    /// The number of tasks waiting in the runtime's global queue.
+    pub global_queue_depth: usize,
+    /// The number of worker threads that are idle.
+    pub idle_workers: Option<usize>,
+    /// The number of threads in the blocking pool.
+    pub blocking_threads: Option<usize>,
+    /// The maximum number of threads in the blocking pool.
+    pub max_blocking_threads: Option<usize>,
+}
+
+impl RuntimeStats {
+    /// The fraction, between `0` and `1`, of the blocking pool that is in use,
+    /// if known. A value of `1` means that new blocking tasks must wait for a
+    /// thread to become available.
+    pub fn blocking_saturation(&self) -> Option<f64> {
+        match (self.blocking_threads, self.max_blocking_threads) {
+            (Some(_), Some(0)) => Some(1.0),
+            (Some(n), Some(max)) => Some(n as f64 / max as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Thread counters for a runtime created by Rocket.
+#[derive(Debug, Default)]
+struct Counters {
+    workers: usize,
+    max_blocking: usize,
+    threads: AtomicUsize,
+    parked: AtomicUsize,
+}
+
+thread_local! {
+    static COUNTERS: RefCell<Option<Arc<Counters>>> = const { RefCell::new(None) };
+}
+
+/// Configures `builder` to track the statistics of the runtime it builds.
+pub(crate) fn instrument(builder: &mut Builder, workers: usize, max_blocking: usize) {
+    let counters = Arc::new(Counters { workers, max_blocking, ..Default::default() });
+    let (start, stop) = (counters.clone(), counters.clone());
+    let (park, unpark) = (counters.clone(), counters);
+    builder
+        .on_thread_start(move || {
+            start.threads.fetch_add(1, Ordering::AcqRel);
+            COUNTERS.with(|c| *c.borrow_mut() = Some(start.clone()));
+        })
+        .on_thread_stop(move || { stop.threads.fetch_sub(1, Ordering::AcqRel); })
+        .on_thread_park(move || { park.parked.fetch_add(1, Ordering::AcqRel); })
+        .on_thread_unpark(move || { unpark.parked.fetch_sub(1, Ordering::AcqRel); });
+}
+
+/// Whether the current thread belongs to a runtime created by Rocket.
+pub(crate) fn is_rocket_thread() -> bool {
+    COUNTERS.with(|c| c.borrow().is_some())
+}
+
+/// Returns statistics for the current runtime or `None` if there is none.
+pub(crate) fn stats() -> Option<RuntimeStats> {
+    let metrics = Handle::try_current().ok()?.metrics();
+    let mut stats = RuntimeStats {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+        idle_workers: None,
+        blocking_threads: None,
+        max_blocking_threads: None,
+    };
+
+    if let Some(counters) = COUNTERS.with(|c| c.borrow().clone()) {
+        let threads = counters.threads.load(Ordering::Acquire);
+        let parked = counters.parked.load(Ordering::Acquire);
+        stats.idle_workers = Some(parked.min(counters.workers));
+        stats.blocking_threads = Some(threads.saturating_sub(counters.workers));
+        stats.max_blocking_threads = Some(counters.max_blocking);
+    }
+
+    Some(stats)
+}
@@ -1,6 +1,7 @@
 //! Automatic JSON (de)serialization support.
 //!
-//! See [`Json`] for details.
+//! See [`Json`] for details. For streams of newline-delimited JSON documents,
+//! see [`NdJson`].
 //!
 //! # Enabling
 //!
@@ -39,6 +40,10 @@ use serde::{Serialize, Deserialize};
 #[doc(hidden)]
 pub use serde_json;
 
+mod ndjson;
+
+pub use ndjson::{NdJson, Lines};
+
 /// The JSON guard: easily consume and return JSON.
 ///
 /// ## Sending JSON
@@ -0,0 +1,211 @@
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{Stream, StreamExt};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::request::Request;
+use crate::data::{Limits, Data, DataStream, FromData, Outcome};
+use crate::response::{self, Response, Responder};
+use crate::response::stream::ReaderStream;
+use crate::http::ContentType;
+
+/// A stream of newline-delimited JSON (NDJSON) documents.
+///
+/// `NdJson` is both a responder, when wrapping a [`Stream`] of serializable
+/// items, and a data guard, in the form of `NdJson<Lines<'r, T>>`, yielding
+/// deserialized items as they arrive in the request body. Either way, each
+/// item corresponds to exactly one JSON document on a line of its own.
+///
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+///
+/// ## Sending NDJSON
+///
+/// Each item yielded by the wrapped stream is serialized and sent as a line as
+/// soon as it is available. To reduce the number of small writes for streams
+/// that produce items in bursts, [`NdJson::batched()`] coalesces up to `n`
+/// items that are immediately ready into a single write. Items that fail to
+/// serialize are logged and skipped.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::{Serialize, json::NdJson};
+/// use rocket::futures::stream::{self, Stream};
+///
+/// #[derive(Serialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Event { id: usize }
+///
+/// #[get("/events")]
+/// fn events() -> NdJson<impl Stream<Item = Event>> {
+///     NdJson::from(stream::iter((0..100).map(|id| Event { id }))).batched(16)
+/// }
+/// ```
+///
+/// The response `Content-Type` is [`NDJSON`](ContentType::NDJSON), and the
+/// body is [unsized](crate::response::Body#unsized).
+///
+/// ## Receiving NDJSON
+///
+/// `NdJson<Lines<'r, T>>` is a data guard that never fails. It yields a
+/// `Result<T, serde_json::Error>` for every non-empty line in the body, in
+/// order, without first buffering the entire body. A line that fails to parse
+/// yields an `Err` but does not end the stream; an I/O error does.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::serde::{Deserialize, json::{NdJson, Lines}};
+/// use rocket::futures::StreamExt;
+///
+/// #[derive(Deserialize)]
+/// # #[serde(crate = "rocket::serde")]
+/// struct Record { level: String, message: String }
+///
+/// #[post("/ingest", data = "<logs>")]
+/// async fn ingest(mut logs: NdJson<Lines<'_, Record>>) -> String {
+///     let (mut ok, mut bad) = (0, 0);
+///     while let Some(record) = logs.next().await {
+///         match record {
+///             Ok(_) => ok += 1,
+///             Err(_) => bad += 1,
+///         }
+///     }
+///
+///     format!("{ok} ingested, {bad} rejected")
+/// }
+/// ```
+///
+/// The body is read up to the `ndjson` data limit, which defaults to
+/// [`Limits::JSON`] when unset. A body that exceeds the limit ends the stream
+/// early; the final, truncated line is yielded as a parse error.
+#[derive(Debug, Clone)]
+pub struct NdJson<S> {
+    stream: S,
+    batch: usize,
+}
+
+/// A stream of items deserialized from a newline-delimited JSON request body.
+///
+/// See [`NdJson`] for details.
+pub struct Lines<'r, T> {
+    lines: tokio::io::Lines<BufReader<DataStream<'r>>>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<S> NdJson<S> {
+    /// Coalesces up to `n` items that are immediately ready into a single
+    /// write. By default, and when `n` is `0`, each item is written on its own.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::json::NdJson;
+    /// use rocket::futures::stream;
+    ///
+    /// let ndjson = NdJson::from(stream::iter(vec![1, 2, 3])).batched(64);
+    /// ```
+    pub fn batched(mut self, n: usize) -> Self {
+        self.batch = n.max(1);
+        self
+    }
+
+    /// Consumes `self` and returns the wrapped stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::serde::json::NdJson;
+    /// use rocket::futures::stream;
+    ///
+    /// let ndjson = NdJson::from(stream::iter(vec![1, 2, 3]));
+    /// let inner = ndjson.into_inner();
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> From<S> for NdJson<S> {
+    fn from(stream: S) -> Self {
+        NdJson { stream, batch: 1 }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for NdJson<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for Lines<'_, T> {
+    type Item = Result<T, serde_json::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let line = match Pin::new(&mut self.lines).poll_next_line(cx) {
+                Poll::Ready(Ok(Some(line))) => line,
+                Poll::Ready(Ok(None)) => return Poll::Ready(None),
+                Poll::Ready(Err(e)) => {
+                    return Poll::Ready(Some(Err(serde_json::Error::io(e))));
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if !line.trim().is_empty() {
+                return Poll::Ready(Some(serde_json::from_str(&line)));
+            }
+        }
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: DeserializeOwned> FromData<'r> for NdJson<Lines<'r, T>> {
+    type Error = std::convert::Infallible;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let limit = req.limits().get("ndjson").unwrap_or(Limits::JSON);
+        let lines = BufReader::new(data.open(limit)).lines();
+        Outcome::Success(NdJson::from(Lines { lines, _item: PhantomData }))
+    }
+}
+
+/// Serializes each item into a line of JSON. Returns a response with
+/// Content-Type NDJSON and an unsized body that yields lines as the wrapped
+/// stream yields items.
+impl<'r, S: Stream> Responder<'r, 'r> for NdJson<S>
+    where S: Send + 'r, S::Item: Serialize + Send + 'r
+{
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
+        let lines = self.stream.ready_chunks(self.batch).map(|items| {
+            let mut buf = Vec::new();
+            for item in items {
+                match serde_json::to_vec(&item) {
+                    Ok(line) => { buf.extend(line); buf.push(b'\n'); }
+                    Err(e) => error!("NDJSON serialize failure: {}", e),
+                }
+            }
+
+            Cursor::new(buf)
+        });
+
+        Response::build()
+            .header(ContentType::NDJSON)
+            .streamed_body(ReaderStream::from(lines))
+            .ok()
+    }
+}
+
+impl<T> std::fmt::Debug for Lines<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lines").finish_non_exhaustive()
+    }
+}
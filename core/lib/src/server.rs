@@ -10,7 +10,7 @@ use futures::{Future, TryFutureExt};
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::{Ignite, Orbit, Request, Rocket};
-use crate::request::ConnectionMeta;
+use crate::request::{ConnectionMeta, ConnectionInfo};
 use crate::erased::{ErasedRequest, ErasedResponse, ErasedIoHandler};
 use crate::listener::{Listener, Connection, BouncedExt, CancellableExt};
 use crate::error::log_server_error;
@@ -18,16 +18,17 @@ use crate::data::{IoStream, RawStream};
 use crate::util::{spawn_inspect, FutureExt, ReaderStream};
 use crate::http::Status;
 use crate::trace::{Trace, TraceAll};
+use crate::service::{RequestBody, ResponseBody, Stack};
 
 type Result<T, E = crate::Error> = std::result::Result<T, E>;
 
 impl Rocket<Orbit> {
     #[tracing::instrument("request", skip_all, fields(
         method = %parts.method,
-        uri = %parts.uri,
+        uri = %self.config.redact.uri(&parts.uri),
         autohandled
     ))]
-    async fn service<T: for<'a> Into<RawStream<'a>>>(
+    pub(crate) async fn service<T: for<'a> Into<RawStream<'a>>>(
         self: Arc<Self>,
         parts: http::request::Parts,
         stream: T,
@@ -35,17 +36,29 @@ impl Rocket<Orbit> {
         connection: ConnectionMeta,
     ) -> Result<hyper::Response<ReaderStream<ErasedResponse>>, http::Error> {
         connection.trace_debug();
+        let http1 = parts.version <= http::Version::HTTP_11;
+        let shutdown = self.shutdown.clone();
+        let rocket = self.clone();
         let request = ErasedRequest::new(self, parts, |rocket, parts| {
             Request::from_hyp(rocket, parts, connection).unwrap_or_else(|e| e)
         });
 
-        span_debug!("request headers" => request.inner().headers().iter().trace_all_debug());
+        span_debug!("request headers" => request.inner().headers().iter()
+            .map(|h| rocket.config.redact.header(h))
+            .trace_all_debug());
         let mut response = request.into_response(
             stream,
             |rocket, request, data| Box::pin(rocket.preprocess(request, data)),
-            |token, rocket, request, data| Box::pin(async move {
-                if !request.errors.is_empty() {
-                    return rocket.dispatch_error(Status::BadRequest, request).await;
+            move |token, rocket, request, data| Box::pin(async move {
+                if let Some(error) = request.errors.first() {
+                    // Don't reuse an HTTP/1 connection after rejecting a
+                    // request whose framing or path can't be trusted.
+                    let mut response = rocket.dispatch_error(error.status(), request).await;
+                    if http1 && request.errors.iter().any(|e| e.is_rejection()) {
+                        response.set_raw_header("Connection", "close");
+                    }
+
+                    return response;
                 }
 
                 rocket.dispatch(token, request, data).await
@@ -54,7 +67,9 @@ impl Rocket<Orbit> {
 
         // TODO: Should upgrades be handled in dispatch?
         response.inner().trace_info();
-        span_debug!("response headers" => response.inner().headers().iter().trace_all_debug());
+        span_debug!("response headers" => response.inner().headers().iter()
+            .map(|h| rocket.config.redact.header(h))
+            .trace_all_debug());
         let io_handler = response.make_io_handler(Rocket::extract_io_handler);
         if let (Some((proto, handler)), Some(upgrade)) = (io_handler, upgrade) {
             let upgrade = upgrade.map_ok(IoStream::from).map_err(io::Error::other);
@@ -67,8 +82,47 @@ impl Rocket<Orbit> {
             builder = builder.header(header.name().as_str(), header.value());
         }
 
+        // While shutting down, ask HTTP/1 clients not to reuse the connection
+        // so that they reconnect, likely to another server, for new requests.
+        let status = response.inner().status();
+        let draining = shutdown.drain.notified() || shutdown.start.notified();
+        let closing = response.inner().headers().contains("Connection");
+        if draining && http1 && !closing && status != Status::SwitchingProtocols {
+            builder = builder.header(http::header::CONNECTION, "close");
+        }
+
+        let mut trailers = http::HeaderMap::new();
+        for trailer in response.inner().trailers().iter() {
+            let name = http::HeaderName::from_bytes(trailer.name().as_str().as_bytes());
+            let value = http::HeaderValue::from_str(trailer.value());
+            match (name, value) {
+                (Ok(name), Ok(value)) => { trailers.append(name, value); },
+                _ => warn!(name = %trailer.name(), "ignoring invalid response trailer"),
+            }
+        }
+
         let chunk_size = response.inner().body().max_chunk_size();
-        builder.body(ReaderStream::with_capacity(response, chunk_size))
+        let body = ReaderStream::with_capacity(response, chunk_size).with_trailers(trailers);
+        builder.body(body)
+    }
+
+    /// Dispatches an HTTP/1 or HTTP/2 `request` through the layered `stack`,
+    /// if there is one, or directly to Rocket otherwise.
+    async fn service12(
+        self: Arc<Self>,
+        mut request: hyper::Request<hyper::body::Incoming>,
+        connection: ConnectionMeta,
+        stack: Option<Arc<Stack>>,
+    ) -> Result<hyper::Response<ResponseBody>, http::Error> {
+        if let Some(stack) = stack {
+            request.extensions_mut().insert(connection);
+            return Ok(stack.call(request.map(RequestBody::from)).await);
+        }
+
+        let upgrade = hyper::upgrade::on(&mut request);
+        let (parts, incoming) = request.into_parts();
+        let response = self.service(parts, incoming, Some(upgrade), connection).await?;
+        Ok(response.map(ResponseBody::from))
     }
 
     pub(crate) fn alt_svc(&self) -> Option<&'static str> {
@@ -122,6 +176,7 @@ impl Rocket<Ignite> {
 
             let rocket = self.into_orbit(vec![h3listener.endpoint()?, endpoint]);
             let rocket = orbit_callback(rocket).await?;
+            #[cfg(unix)] crate::listener::handoff::ready();
 
             let http12 = tokio::task::spawn(rocket.clone().serve12(listener));
             let http3 = tokio::task::spawn(rocket.clone().serve3(h3listener));
@@ -138,6 +193,7 @@ impl Rocket<Ignite> {
 
         let rocket = self.into_orbit(vec![endpoint]);
         let rocket = orbit_callback(rocket).await?;
+        #[cfg(unix)] crate::listener::handoff::ready();
         rocket.clone().serve12(listener).await?;
         Ok(rocket)
     }
@@ -167,27 +223,36 @@ impl Rocket<Orbit> {
             }
         }
 
+        let stack = Stack::new(&self);
         let (listener, server) = (Arc::new(listener.bounced()), Arc::new(builder));
-        while let Some(accept) = listener.accept().race(self.shutdown()).await.left().transpose()? {
+        while let Some(permit) = self.load.connection().race(self.shutdown()).await.left() {
+            let accept = listener.accept().race(self.shutdown()).await.left().transpose()?;
+            let Some(accept) = accept else { break };
             let (listener, rocket, server) = (listener.clone(), self.clone(), server.clone());
+            let stack = stack.clone();
             spawn_inspect(|e| log_server_error(&**e), async move {
+                let _permit = permit;
                 let conn = listener.connect(accept).race_io(rocket.shutdown()).await?;
-                let meta = ConnectionMeta::new(conn.endpoint(), conn.certificates());
-                let service = service_fn(|mut req| {
-                    let upgrade = hyper::upgrade::on(&mut req);
-                    let (parts, incoming) = req.into_parts();
-                    rocket.clone().service(parts, incoming, Some(upgrade), meta.clone())
+                let meta = ConnectionMeta::new(conn.endpoint(), conn.certificates())
+                    .with_local(conn.local_endpoint())
+                    .with_tls(conn.tls_info());
+                rocket.fairings.handle_connect(ConnectionInfo::new(&meta)).await;
+                let service = service_fn(|req| {
+                    rocket.clone().service12(req, meta.clone(), stack.clone())
                 });
 
                 let io = TokioIo::new(conn.cancellable(rocket.shutdown.clone()));
                 let mut server = pin!(server.serve_connection_with_upgrades(io, service));
-                match server.as_mut().race(rocket.shutdown()).await.left() {
+                let result = match server.as_mut().race(rocket.shutdown()).await.left() {
                     Some(result) => result,
                     None => {
                         server.as_mut().graceful_shutdown();
                         server.await
                     },
-                }
+                };
+
+                rocket.fairings.handle_disconnect(ConnectionInfo::new(&meta)).await;
+                result
             });
         }
 
@@ -198,33 +263,68 @@ impl Rocket<Orbit> {
     async fn serve3(self: Arc<Self>, listener: crate::listener::quic::QuicListener) -> Result<()> {
         let rocket = self.clone();
         let listener = Arc::new(listener);
-        while let Some(Some(accept)) = listener.accept().race(rocket.shutdown()).await.left() {
+        while let Some(permit) = rocket.load.connection().race(rocket.shutdown()).await.left() {
+            let Some(Some(accept)) = listener.accept().race(rocket.shutdown()).await.left() else {
+                break;
+            };
+
             let (listener, rocket) = (listener.clone(), rocket.clone());
             spawn_inspect(|e: &io::Error| log_server_error(e), async move {
+                let _permit = permit;
                 let mut stream = listener.connect(accept).race_io(rocket.shutdown()).await?;
-                while let Some(mut conn) = stream.accept().race_io(rocket.shutdown()).await? {
-                    let rocket = rocket.clone();
-                    spawn_inspect(|e: &io::Error| log_server_error(e), async move {
-                        let meta = ConnectionMeta::new(conn.endpoint(), None);
-                        let rx = conn.rx.cancellable(rocket.shutdown.clone());
-                        let response = rocket.clone()
-                            .service(conn.parts, rx, None, meta)
-                            .map_err(io::Error::other)
-                            .race_io(rocket.shutdown.mercy.clone())
-                            .await?;
-
-                        let grace = rocket.shutdown.grace.clone();
-                        match conn.tx.send_response(response).race(grace).await.left() {
-                            Some(result) => result,
-                            None => Ok(conn.tx.cancel()),
-                        }
-                    });
-                }
+                let meta = ConnectionMeta::new(stream.endpoint(), None)
+                    .with_local(stream.local_endpoint())
+                    .with_tls(Some(stream.tls_info()));
 
-                Ok(())
+                rocket.fairings.handle_connect(ConnectionInfo::new(&meta)).await;
+                let result = rocket.clone().serve3_connection(&mut stream, &meta).await;
+                rocket.fairings.handle_disconnect(ConnectionInfo::new(&meta)).await;
+                result
             });
         }
 
         Ok(())
     }
+
+    /// Serves every request stream on the HTTP/3 connection `stream`,
+    /// returning once the connection has closed and all of its in-flight
+    /// responses have been written or abandoned.
+    #[cfg(feature = "http3-preview")]
+    async fn serve3_connection(
+        self: Arc<Self>,
+        stream: &mut crate::listener::quic::H3Stream,
+        meta: &ConnectionMeta,
+    ) -> io::Result<()> {
+        let mut streams = tokio::task::JoinSet::new();
+        let result = loop {
+            let mut conn = match stream.accept().race_io(self.shutdown()).await {
+                Ok(Some(conn)) => conn,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            };
+
+            let (rocket, meta) = (self.clone(), meta.clone());
+            let task = async move {
+                let rx = conn.rx.cancellable(rocket.shutdown.clone());
+                let mut response = rocket.clone()
+                    .service(conn.parts, rx, None, meta)
+                    .map_err(io::Error::other)
+                    .race_io(rocket.shutdown.mercy.clone())
+                    .await?;
+
+                let grace = rocket.shutdown.grace.clone();
+                let trailers = response.body_mut().take_trailers();
+                let send = conn.tx.send_response(response, trailers);
+                match send.race(grace).await.left() {
+                    Some(result) => result,
+                    None => Ok(conn.tx.cancel()),
+                }
+            };
+
+            streams.spawn(task.inspect_err(|e: &io::Error| log_server_error(e)));
+        };
+
+        while streams.join_next().await.is_some() { }
+        result
+    }
 }
@@ -0,0 +1,247 @@
+use std::any::Any;
+use std::error::Error;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::body::{Body, Buf, Bytes, Frame, Incoming, SizeHint};
+
+use crate::data::{BoxedBody, RawStream};
+use crate::erased::ErasedResponse;
+use crate::util::ReaderStream;
+
+/// The body of a request dispatched to a [`RocketService`].
+///
+/// Implements [`hyper::body::Body`]. A `RequestBody` can be created from any
+/// `Body` with [`RequestBody::new()`].
+///
+/// [`RocketService`]: crate::service::RocketService
+pub struct RequestBody(RequestKind);
+
+enum RequestKind {
+    Empty,
+    Incoming(Incoming),
+    Boxed(BoxedBody),
+}
+
+/// The streaming body of a response produced by a [`RocketService`].
+///
+/// Implements [`hyper::body::Body`]. Trailers set by the application, if any,
+/// are emitted after the last data frame.
+///
+/// [`RocketService`]: crate::service::RocketService
+pub struct ResponseBody(ResponseKind);
+
+enum ResponseKind {
+    Empty,
+    Rocket(ReaderStream<ErasedResponse>),
+    Boxed(BoxedBody),
+}
+
+/// Adapts an arbitrary body into Rocket's boxed body type.
+struct MapBody<B>(Pin<Box<B>>);
+
+/// Returns `body` as a `T` if it is one.
+fn downcast<T: 'static, B: 'static>(body: B) -> Result<T, B> {
+    let mut body = Some(body);
+    match (&mut body as &mut dyn Any).downcast_mut::<Option<T>>() {
+        Some(value) => Ok(value.take().expect("value present")),
+        None => Err(body.expect("value present")),
+    }
+}
+
+fn boxed<B>(body: B) -> BoxedBody
+    where B: Body + Send + 'static, B::Error: Into<Box<dyn Error + Send + Sync>>
+{
+    Box::pin(MapBody(Box::pin(body)))
+}
+
+impl RequestBody {
+    /// Wraps `body` in a `RequestBody`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::service::RequestBody;
+    ///
+    /// let body = RequestBody::new(String::from("hello"));
+    /// ```
+    pub fn new<B>(body: B) -> Self
+        where B: Body + Send + 'static,
+              B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        downcast(body)
+            .or_else(|body| downcast(body).map(|body| RequestBody(RequestKind::Incoming(body))))
+            .unwrap_or_else(|body| RequestBody(RequestKind::Boxed(boxed(body))))
+    }
+
+    /// Returns an empty `RequestBody`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::service::RequestBody;
+    ///
+    /// let body = RequestBody::empty();
+    /// ```
+    pub fn empty() -> Self {
+        RequestBody(RequestKind::Empty)
+    }
+}
+
+impl ResponseBody {
+    /// Wraps `body` in a `ResponseBody`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::service::ResponseBody;
+    ///
+    /// let body = ResponseBody::new(String::from("hello"));
+    /// ```
+    pub fn new<B>(body: B) -> Self
+        where B: Body + Send + 'static,
+              B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        downcast(body).unwrap_or_else(|body| ResponseBody(ResponseKind::Boxed(boxed(body))))
+    }
+
+    /// Returns an empty `ResponseBody`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::service::ResponseBody;
+    ///
+    /// let body = ResponseBody::empty();
+    /// ```
+    pub fn empty() -> Self {
+        ResponseBody(ResponseKind::Empty)
+    }
+}
+
+impl From<Incoming> for RequestBody {
+    fn from(body: Incoming) -> Self {
+        RequestBody(RequestKind::Incoming(body))
+    }
+}
+
+impl<'r> From<RequestBody> for RawStream<'r> {
+    fn from(body: RequestBody) -> Self {
+        match body.0 {
+            RequestKind::Empty => RawStream::Empty,
+            RequestKind::Incoming(body) => RawStream::Body(body),
+            RequestKind::Boxed(body) => RawStream::Boxed(body),
+        }
+    }
+}
+
+impl From<ReaderStream<ErasedResponse>> for ResponseBody {
+    fn from(body: ReaderStream<ErasedResponse>) -> Self {
+        ResponseBody(ResponseKind::Rocket(body))
+    }
+}
+
+impl Body for RequestBody {
+    type Data = Bytes;
+
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.get_mut().0 {
+            RequestKind::Empty => Poll::Ready(None),
+            RequestKind::Incoming(body) => Pin::new(body).poll_frame(cx).map_err(io::Error::other),
+            RequestKind::Boxed(body) => body.as_mut().poll_frame(cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.0 {
+            RequestKind::Empty => true,
+            RequestKind::Incoming(body) => body.is_end_stream(),
+            RequestKind::Boxed(body) => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.0 {
+            RequestKind::Empty => SizeHint::with_exact(0),
+            RequestKind::Incoming(body) => body.size_hint(),
+            RequestKind::Boxed(body) => body.size_hint(),
+        }
+    }
+}
+
+impl Body for ResponseBody {
+    type Data = Bytes;
+
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.get_mut().0 {
+            ResponseKind::Empty => Poll::Ready(None),
+            ResponseKind::Rocket(body) => Pin::new(body).poll_frame(cx),
+            ResponseKind::Boxed(body) => body.as_mut().poll_frame(cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.0 {
+            ResponseKind::Empty => true,
+            ResponseKind::Rocket(body) => body.is_end_stream(),
+            ResponseKind::Boxed(body) => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.0 {
+            ResponseKind::Empty => SizeHint::with_exact(0),
+            ResponseKind::Rocket(body) => body.size_hint(),
+            ResponseKind::Boxed(body) => body.size_hint(),
+        }
+    }
+}
+
+impl<B> Body for MapBody<B>
+    where B: Body, B::Error: Into<Box<dyn Error + Send + Sync>>
+{
+    type Data = Bytes;
+
+    type Error = io::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.0.as_mut().poll_frame(cx).map(|frame| frame.map(|frame| match frame {
+            Ok(frame) => Ok(frame.map_data(|mut data| data.copy_to_bytes(data.remaining()))),
+            Err(e) => Err(io::Error::other(e)),
+        }))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
+}
+
+impl std::fmt::Debug for RequestBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RequestBody").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for ResponseBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ResponseBody").finish_non_exhaustive()
+    }
+}
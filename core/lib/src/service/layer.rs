@@ -0,0 +1,115 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use hyper::body::Body;
+use hyper::upgrade::OnUpgrade;
+use parking_lot::Mutex;
+use tower::util::BoxCloneService;
+use tower::{Layer, Service, ServiceExt};
+
+use crate::{Orbit, Rocket};
+use crate::request::ConnectionMeta;
+use crate::service::{error_response, RequestBody, ResponseBody};
+
+/// The type of the service wrapped by layers applied with
+/// [`Rocket::layer()`].
+///
+/// The innermost service dispatches requests to Rocket. Each layer wraps the
+/// service produced by the previous layer, boxed as a `BoxService`.
+pub type BoxService = BoxCloneService<
+    http::Request<RequestBody>,
+    http::Response<ResponseBody>,
+    Infallible,
+>;
+
+type Wrap = dyn Fn(BoxService) -> BoxService + Send + Sync;
+
+/// The layers applied to the request pipeline, innermost first.
+#[derive(Default)]
+pub(crate) struct Layers(Vec<Arc<Wrap>>);
+
+/// The request pipeline wrapped in all of Rocket's layers.
+pub(crate) struct Stack(Mutex<BoxService>);
+
+/// The innermost service: dispatches requests to Rocket.
+#[derive(Clone)]
+struct Dispatch(Arc<Rocket<Orbit>>);
+
+impl Layers {
+    pub fn push<L, B>(&mut self, layer: L)
+        where L: Layer<BoxService> + Send + Sync + 'static,
+              L::Service: Service<http::Request<RequestBody>, Response = http::Response<B>>,
+              L::Service: Clone + Send + 'static,
+              <L::Service as Service<http::Request<RequestBody>>>::Future: Send + 'static,
+              <L::Service as Service<http::Request<RequestBody>>>::Error:
+                  Into<Box<dyn Error + Send + Sync>>,
+              B: Body + Send + 'static,
+              B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.0.push(Arc::new(move |inner| {
+            let service = layer.layer(inner).map_result(|result| match result {
+                Ok(response) => Ok::<_, Infallible>(response.map(ResponseBody::new)),
+                Err(e) => Ok(error_response(e.into())),
+            });
+
+            BoxCloneService::new(service)
+        }));
+    }
+}
+
+impl Stack {
+    /// Returns the layered pipeline for `rocket` or `None` if there are no
+    /// layers to apply.
+    pub fn new(rocket: &Arc<Rocket<Orbit>>) -> Option<Arc<Stack>> {
+        if rocket.layers.0.is_empty() {
+            return None;
+        }
+
+        let inner = BoxCloneService::new(Dispatch(rocket.clone()));
+        let service = rocket.layers.0.iter().fold(inner, |service, wrap| wrap(service));
+        Some(Arc::new(Stack(Mutex::new(service))))
+    }
+
+    /// Dispatches `request` through the pipeline. Connection metadata, if
+    /// any, is expected in the request's extensions.
+    pub async fn call(&self, req: http::Request<RequestBody>) -> http::Response<ResponseBody> {
+        let service = self.0.lock().clone();
+        match service.oneshot(req).await {
+            Ok(response) => response,
+            Err(e) => match e {},
+        }
+    }
+}
+
+impl Service<http::Request<RequestBody>> for Dispatch {
+    type Response = http::Response<ResponseBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<RequestBody>) -> Self::Future {
+        let rocket = self.0.clone();
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let connection = parts.extensions.remove::<ConnectionMeta>().unwrap_or_default();
+            let upgrade = parts.extensions.remove::<OnUpgrade>();
+            Ok(match rocket.service(parts, body, upgrade, connection).await {
+                Ok(response) => response.map(ResponseBody::from),
+                Err(e) => error_response(e.into()),
+            })
+        })
+    }
+}
+
+impl fmt::Debug for Layers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layers").field("len", &self.0.len()).finish()
+    }
+}
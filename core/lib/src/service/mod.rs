@@ -0,0 +1,436 @@
+//! Embedding Rocket in other servers and runtimes.
+//!
+//! [`Rocket::into_service()`] ignites an instance and lifts it off _without_
+//! binding to any network interface, returning a [`RocketService`]. The
+//! service dispatches [`http::Request`]s to the application and resolves to
+//! [`http::Response`]s with a streaming [`ResponseBody`]. This allows Rocket
+//! applications to be driven by servers and runtimes other than Rocket's own,
+//! such as serverless platforms or an existing `hyper` or `axum` server.
+//!
+//! Requests are processed exactly as if they were received by Rocket's server:
+//! request and response fairings run, routes and catchers are dispatched, and
+//! managed state is available. Rocket does not, however, listen for shutdown
+//! signals or apply connection-level configuration such as TLS or connection
+//! limits; these are the responsibility of the embedding runtime.
+//!
+//! # Embedding in `hyper` and `axum`
+//!
+//! `RocketService` implements `hyper`'s [`Service`](hyper::service::Service)
+//! trait and can be passed directly to `hyper`'s connection builders. With the
+//! `tower` feature enabled, it also implements [`tower::Service`] and can be
+//! nested in an `axum` router, for instance, with `Router::nest_service()` or
+//! `Router::fallback_service()`, allowing an application to be migrated to or
+//! from Rocket incrementally.
+//!
+//! When the embedding server passes requests to Rocket with their full path,
+//! use [`RocketService::with_prefix()`] to strip a path prefix before the
+//! request is routed. Note that `axum`'s `nest_service()` strips the prefix
+//! itself. In either case, Rocket is unaware of the prefix: URIs generated by
+//! the application, for instance via [`uri!`](crate::uri!), do not include it.
+//!
+//! To share state between the embedding server and Rocket, [`manage`] an
+//! `Arc<T>` in Rocket and keep a clone of it. Managed state can also be
+//! retrieved directly from the service via [`RocketService::state()`].
+//!
+//! [`manage`]: crate::Rocket::manage()
+//!
+//! # Shutdown
+//!
+//! [`RocketService::shutdown()`] returns Rocket's [`Shutdown`] handle. The
+//! handle resolves when shutdown is requested, by the application, for
+//! instance via the `Shutdown` request guard, or by the embedding server via
+//! [`Shutdown::notify()`]. The embedding server should use it as a signal to
+//! stop accepting requests and, after draining in-flight requests, call
+//! [`RocketService::terminate()`] to run Rocket's shutdown fairings.
+//!
+//! The example below serves a `RocketService` with `hyper` until shutdown is
+//! requested:
+//!
+//! ```rust,no_run
+//! use hyper_util::rt::{TokioExecutor, TokioIo};
+//! use hyper_util::server::conn::auto::Builder;
+//! use rocket::tokio::{self, net::TcpListener};
+//!
+//! #[rocket::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//!     let service = rocket::build().into_service().await?;
+//!     let listener = TcpListener::bind("127.0.0.1:8000").await?;
+//!     let shutdown = service.shutdown();
+//!     loop {
+//!         let (stream, _) = tokio::select! {
+//!             accept = listener.accept() => accept?,
+//!             _ = shutdown.clone() => break,
+//!         };
+//!
+//!         let service = service.clone();
+//!         tokio::spawn(async move {
+//!             let io = TokioIo::new(stream);
+//!             let builder = Builder::new(TokioExecutor::new());
+//!             builder.serve_connection(io, service).await
+//!         });
+//!     }
+//!
+//!     service.terminate().await;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Tower
+//!
+//! With the `tower` feature enabled, `RocketService` implements
+//! [`tower::Service`], and the request pipeline can be wrapped in
+//! [`tower::Layer`]s with [`Rocket::layer()`]. Layers apply to requests
+//! received by Rocket's HTTP/1 and HTTP/2 server and to requests dispatched
+//! via a `RocketService`, but not to requests dispatched by a local
+//! [`Client`](crate::local) or, currently, to HTTP/3 requests.
+//!
+//! # Remote Address
+//!
+//! The service has no connection from which to determine the client's address.
+//! If the request's extensions contain a [`SocketAddr`], it is used as the
+//! address of the remote peer, as returned by [`Request::remote()`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::http::Status;
+//! use http_body_util::BodyExt;
+//!
+//! #[get("/")]
+//! fn hello() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! # rocket::async_test(async {
+//! let service = rocket::build()
+//!     .mount("/", routes![hello])
+//!     .into_service()
+//!     .await
+//!     .expect("valid rocket");
+//!
+//! let request = http::Request::get("/").body(String::new()).unwrap();
+//! let response = service.handle(request).await;
+//! assert_eq!(response.status(), Status::Ok.code);
+//!
+//! let body = response.into_body().collect().await.unwrap().to_bytes();
+//! assert_eq!(body, "Hello, world!");
+//!
+//! let request = http::Request::post("/").body("data".to_string()).unwrap();
+//! let response = service.handle(request).await;
+//! assert_eq!(response.status(), Status::NotFound.code);
+//! # });
+//! ```
+//!
+//! [`Request::remote()`]: crate::Request::remote()
+//! [`Shutdown`]: crate::Shutdown
+//! [`Shutdown::notify()`]: crate::Shutdown::notify()
+//! [`tower::Service`]: https://docs.rs/tower/0.5/tower/trait.Service.html
+//! [`tower::Layer`]: https://docs.rs/tower/0.5/tower/trait.Layer.html
+
+mod body;
+#[cfg(feature = "tower")]
+mod layer;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use hyper::body::Body;
+use tokio::sync::OnceCell;
+
+use crate::{Orbit, Rocket, Shutdown};
+use crate::http::uri::Origin;
+use crate::listener::Endpoint;
+use crate::request::ConnectionMeta;
+
+pub use body::{RequestBody, ResponseBody};
+
+#[cfg(feature = "tower")]
+#[cfg_attr(nightly, doc(cfg(feature = "tower")))]
+pub use layer::BoxService;
+
+#[cfg(feature = "tower")]
+pub(crate) use layer::{Layers, Stack};
+
+/// Without `tower`, there are never any layers to apply.
+#[cfg(not(feature = "tower"))]
+pub(crate) enum Stack { }
+
+/// A Rocket application in orbit, ready to handle requests from an external
+/// server or runtime.
+///
+/// A `RocketService` is created by [`Rocket::into_service()`]. It is cheap to
+/// clone: clones refer to the same application. See the [module level
+/// docs](self) for details and an example.
+#[derive(Clone)]
+pub struct RocketService {
+    rocket: Arc<Rocket<Orbit>>,
+    stack: Option<Arc<Stack>>,
+    prefix: Option<Arc<str>>,
+    terminated: Arc<OnceCell<()>>,
+}
+
+impl RocketService {
+    pub(crate) fn new(rocket: Rocket<Orbit>) -> Self {
+        let rocket = Arc::new(rocket);
+        RocketService {
+            stack: Stack::new(&rocket),
+            prefix: None,
+            terminated: Arc::new(OnceCell::new()),
+            rocket,
+        }
+    }
+
+    /// Strips the path `prefix` from requests before they are routed.
+    ///
+    /// Requests whose path does not begin with `prefix`, with `prefix` ending
+    /// at a segment boundary, are answered with an empty `404 Not Found`
+    /// response. This is useful when an embedding server passes requests
+    /// under a prefix to Rocket without stripping the prefix itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` is not a valid origin URI.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::Status;
+    ///
+    /// #[get("/hello")]
+    /// fn hello() -> &'static str {
+    ///     "Hello, world!"
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let service = rocket::build()
+    ///     .mount("/", routes![hello])
+    ///     .into_service()
+    ///     .await
+    ///     .unwrap()
+    ///     .with_prefix("/legacy");
+    ///
+    /// let request = http::Request::get("/legacy/hello").body(String::new()).unwrap();
+    /// assert_eq!(service.handle(request).await.status(), Status::Ok.code);
+    ///
+    /// let request = http::Request::get("/hello").body(String::new()).unwrap();
+    /// assert_eq!(service.handle(request).await.status(), Status::NotFound.code);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn with_prefix<'a, P>(mut self, prefix: P) -> Self
+        where P: TryInto<Origin<'a>> + Clone + fmt::Display,
+              P::Error: fmt::Display,
+    {
+        let prefix = crate::rkt::parse_base("service", prefix);
+        let path = prefix.path().as_str().trim_end_matches('/');
+        self.prefix = (!path.is_empty()).then(|| Arc::from(path));
+        self
+    }
+
+    /// Returns the instance of `Rocket` handling requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let service = rocket::build().into_service().await.unwrap();
+    /// let config = service.rocket().config();
+    /// # });
+    /// ```
+    pub fn rocket(&self) -> &Rocket<Orbit> {
+        &self.rocket
+    }
+
+    /// Returns the managed state value for the type `T`, if it is being
+    /// managed. Otherwise, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// # rocket::async_test(async {
+    /// let hits = Arc::new(AtomicUsize::new(0));
+    /// let service = rocket::build()
+    ///     .manage(hits.clone())
+    ///     .into_service()
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// hits.fetch_add(1, Ordering::Relaxed);
+    /// let state = service.state::<Arc<AtomicUsize>>().unwrap();
+    /// assert_eq!(state.load(Ordering::Relaxed), 1);
+    /// # });
+    /// ```
+    pub fn state<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.rocket.state()
+    }
+
+    /// Returns a handle to Rocket's [`Shutdown`], which resolves when shutdown
+    /// is requested. See the [module level docs](self#shutdown).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let service = rocket::build().into_service().await.unwrap();
+    /// let shutdown = service.shutdown();
+    /// shutdown.clone().notify();
+    /// shutdown.await;
+    /// # });
+    /// ```
+    pub fn shutdown(&self) -> Shutdown {
+        self.rocket.shutdown()
+    }
+
+    /// Requests shutdown, if it hasn't been requested already, and runs
+    /// Rocket's shutdown fairings.
+    ///
+    /// Shutdown fairings are run at most once, no matter how many times, or
+    /// from which clones, `terminate()` is called. Concurrent calls wait for
+    /// the fairings to complete. Requests can continue to be dispatched, but
+    /// resources released by shutdown fairings may no longer be available.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # rocket::async_test(async {
+    /// let service = rocket::build().into_service().await.unwrap();
+    /// service.terminate().await;
+    /// # });
+    /// ```
+    pub async fn terminate(&self) {
+        self.terminated.get_or_init(|| async {
+            self.rocket.shutdown().notify();
+            self.rocket.fairings.handle_shutdown(&self.rocket).await;
+        }).await;
+    }
+
+    /// Dispatches `request` to the application and returns its response.
+    ///
+    /// The response body is streamed: it is produced as it is read. If the
+    /// application's response cannot be represented as an [`http::Response`],
+    /// an empty `500 Internal Server Error` response is returned instead.
+    pub async fn handle<B>(&self, request: http::Request<B>) -> http::Response<ResponseBody>
+        where B: Body + Send + 'static,
+              B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let (mut parts, body) = request.into_parts();
+        if let Some(prefix) = &self.prefix {
+            match strip_prefix(&parts.uri, prefix) {
+                Some(uri) => parts.uri = uri,
+                None => return empty_response(http::StatusCode::NOT_FOUND),
+            }
+        }
+
+        let peer = parts.extensions.get::<SocketAddr>()
+            .map(|addr| Endpoint::Tcp(*addr))
+            .ok_or_else(|| io::Error::other("remote address unknown"));
+
+        let connection = ConnectionMeta::new(peer, None);
+        if let Some(stack) = &self.stack {
+            parts.extensions.insert(connection);
+            let request = http::Request::from_parts(parts, RequestBody::new(body));
+            return stack.call(request).await;
+        }
+
+        let body = RequestBody::new(body);
+        match self.rocket.clone().service(parts, body, None, connection).await {
+            Ok(response) => response.map(ResponseBody::from),
+            Err(e) => error_response(e.into()),
+        }
+    }
+}
+
+#[cfg(not(feature = "tower"))]
+impl Stack {
+    pub fn new(_: &Arc<Rocket<Orbit>>) -> Option<Arc<Stack>> {
+        None
+    }
+
+    pub async fn call(&self, _: http::Request<RequestBody>) -> http::Response<ResponseBody> {
+        match *self { }
+    }
+}
+
+impl<B> hyper::service::Service<http::Request<B>> for RocketService
+    where B: Body + Send + 'static,
+          B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Response = http::Response<ResponseBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn call(&self, request: http::Request<B>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { Ok(service.handle(request).await) })
+    }
+}
+
+#[cfg(feature = "tower")]
+#[cfg_attr(nightly, doc(cfg(feature = "tower")))]
+impl<B> tower::Service<http::Request<B>> for RocketService
+    where B: Body + Send + 'static,
+          B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    type Response = http::Response<ResponseBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move { Ok(service.handle(request).await) })
+    }
+}
+
+/// Returns `uri` with `prefix` stripped from its path or `None` if its path
+/// doesn't begin with the path segments in `prefix`.
+fn strip_prefix(uri: &http::Uri, prefix: &str) -> Option<http::Uri> {
+    let rest = uri.path().strip_prefix(prefix)?;
+    let path = match rest {
+        "" => "/",
+        rest if rest.starts_with('/') => rest,
+        _ => return None,
+    };
+
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_owned(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query.parse().ok()?);
+    http::Uri::from_parts(parts).ok()
+}
+
+fn empty_response(status: http::StatusCode) -> http::Response<ResponseBody> {
+    let mut response = http::Response::new(ResponseBody::empty());
+    *response.status_mut() = status;
+    response
+}
+
+/// Logs `error` and returns an empty `500 Internal Server Error` response.
+pub(crate) fn error_response(error: Box<dyn Error + Send + Sync>) -> http::Response<ResponseBody> {
+    error!("failed to produce response: {error}");
+    empty_response(http::StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+impl fmt::Debug for RocketService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocketService").finish_non_exhaustive()
+    }
+}
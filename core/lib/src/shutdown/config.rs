@@ -12,8 +12,8 @@ use crate::shutdown::Sig;
 /// # Summary
 ///
 /// This structure configures when and how graceful shutdown occurs. The `ctrlc`
-/// and `signals` properties control _when_ and the `grace` and `mercy`
-/// properties control _how_.
+/// and `signals` properties control _when_ and the `drain`, `grace`, and
+/// `mercy` properties control _how_.
 ///
 /// When a shutdown is triggered by an externally or internally initiated
 /// [`Shutdown::notify()`], Rocket allows application I/O to make progress for
@@ -36,6 +36,36 @@ use crate::shutdown::Sig;
 ///
 /// [`Shutdown::notify()`]: crate::Shutdown::notify()
 ///
+/// # Drain Period
+///
+/// When a shutdown is triggered by a signal and `drain` is non-zero, Rocket
+/// first enters a _drain_ period of `drain` seconds during which it continues
+/// to accept and serve requests as usual but reports itself as not ready to
+/// the readiness probes provided by the `health` module. This gives load
+/// balancers and orchestrators time to observe the failing probe and stop
+/// routing new traffic to the server before it stops accepting connections.
+/// Shutdowns initiated directly via [`Shutdown::notify()`] skip the drain
+/// period.
+///
+/// # Upgrades
+///
+/// On Unix, when the `upgrade` signal is received, Rocket performs a
+/// zero-downtime upgrade: it executes the current binary anew with the same
+/// arguments, handing it the server's listening sockets. Once the new process
+/// has bound to the sockets and lifted off, this process begins a graceful
+/// shutdown, skipping the drain period. Because the sockets are shared, no
+/// connection is refused in between; deployments can thus replace the binary
+/// on disk and signal the running server to upgrade. If the new process fails
+/// to become ready within `upgrade_timeout` seconds, it is killed and this
+/// process continues serving as usual.
+///
+/// The `upgrade` signal must not also trigger a shutdown: a configuration in
+/// which it is one of `signals`, or is `int` while `ctrlc` is `true`, is
+/// rejected.
+///
+/// Listening sockets passed via systemd socket activation are used in the
+/// same manner, in place of binding new sockets.
+///
 /// # Grace Period
 ///
 /// Once a shutdown is triggered, Rocket stops accepting new connections and
@@ -93,6 +123,8 @@ use crate::shutdown::Sig;
 /// [default.shutdown]
 /// ctrlc = false
 /// signals = ["term", "hup"]
+/// upgrade = "usr2"
+/// upgrade_timeout = 60
 /// grace = 10
 /// mercy = 5
 /// # force = false
@@ -111,6 +143,8 @@ use crate::shutdown::Sig;
 /// assert_eq!(config.shutdown.signals.len(), 2);
 /// assert!(config.shutdown.signals.contains(&Sig::Term));
 /// assert!(config.shutdown.signals.contains(&Sig::Hup));
+/// assert_eq!(config.shutdown.upgrade, Some(Sig::Usr2));
+/// assert_eq!(config.shutdown.upgrade_timeout, 60);
 /// # }
 /// ```
 ///
@@ -166,6 +200,29 @@ pub struct ShutdownConfig {
     #[cfg(unix)]
     #[cfg_attr(nightly, doc(cfg(unix)))]
     pub signals: HashSet<Sig>,
+    /// On Unix, a signal which triggers a zero-downtime upgrade. See
+    /// [upgrades](#upgrades) for details. On non-Unix, this option is
+    /// unavailable and silently ignored.
+    ///
+    /// **default: `None`**
+    #[cfg(unix)]
+    #[cfg_attr(nightly, doc(cfg(unix)))]
+    #[serde(default)]
+    pub upgrade: Option<Sig>,
+    /// On Unix, the number of seconds to wait for the new process to become
+    /// ready during an [upgrade](#upgrades) before killing it. On non-Unix,
+    /// this option is unavailable and silently ignored.
+    ///
+    /// **default: `30`**
+    #[cfg(unix)]
+    #[cfg_attr(nightly, doc(cfg(unix)))]
+    pub upgrade_timeout: u32,
+    /// The drain period: number of seconds to continue to accept and serve
+    /// requests after a shutdown signal is received while reporting the
+    /// server as not ready.
+    ///
+    /// **default: `0`**
+    pub drain: u32,
     /// The grace period: number of seconds to continue to try to finish
     /// outstanding _server_ I/O for before forcibly terminating it.
     ///
@@ -213,6 +270,11 @@ impl Default for ShutdownConfig {
             ctrlc: true,
             #[cfg(unix)]
             signals: { let mut set = HashSet::new(); set.insert(Sig::Term); set },
+            #[cfg(unix)]
+            upgrade: None,
+            #[cfg(unix)]
+            upgrade_timeout: 30,
+            drain: 0,
             grace: 2,
             mercy: 3,
             force: true,
@@ -222,6 +284,10 @@ impl Default for ShutdownConfig {
 }
 
 impl ShutdownConfig {
+    pub(crate) fn drain(&self) -> Duration {
+        Duration::from_secs(self.drain as u64)
+    }
+
     pub(crate) fn grace(&self) -> Duration {
         Duration::from_secs(self.grace as u64)
     }
@@ -230,10 +296,30 @@ impl ShutdownConfig {
         Duration::from_secs(self.mercy as u64)
     }
 
+    #[cfg(unix)]
+    pub(crate) fn upgrade_timeout(&self) -> Duration {
+        Duration::from_secs(self.upgrade_timeout as u64)
+    }
+
+    /// Returns an error if the `upgrade` signal also triggers a shutdown.
+    pub(crate) fn validate(&self) -> figment::error::Result<()> {
+        #[cfg(unix)]
+        if let Some(sig) = self.upgrade {
+            if self.signals.contains(&sig) || (self.ctrlc && sig == Sig::Int) {
+                let msg = format!("upgrade signal `{sig}` is also a shutdown signal");
+                let mut error = figment::Error::from(msg);
+                error.path = vec!["shutdown".into(), "upgrade".into()];
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
     #[cfg(unix)]
     pub(crate) fn signal_stream(&self) -> Option<impl Stream<Item = Sig>> {
         use tokio_stream::{StreamExt, StreamMap, wrappers::SignalStream};
-        use tokio::signal::unix::{signal, SignalKind};
+        use tokio::signal::unix::signal;
 
         if !self.ctrlc && self.signals.is_empty() {
             return None;
@@ -246,20 +332,7 @@ impl ShutdownConfig {
 
         let mut map = StreamMap::new();
         for sig in signals {
-            let sigkind = match sig {
-                Sig::Alrm => SignalKind::alarm(),
-                Sig::Chld => SignalKind::child(),
-                Sig::Hup => SignalKind::hangup(),
-                Sig::Int => SignalKind::interrupt(),
-                Sig::Io => SignalKind::io(),
-                Sig::Pipe => SignalKind::pipe(),
-                Sig::Quit => SignalKind::quit(),
-                Sig::Term => SignalKind::terminate(),
-                Sig::Usr1 => SignalKind::user_defined1(),
-                Sig::Usr2 => SignalKind::user_defined2()
-            };
-
-            match signal(sigkind) {
+            match signal(sig.kind()) {
                 Ok(signal) => { map.insert(sig, SignalStream::new(signal)); },
                 Err(e) => warn!("Failed to enable `{}` shutdown signal: {}", sig, e),
             }
@@ -268,6 +341,20 @@ impl ShutdownConfig {
         Some(map.map(|(k, _)| k))
     }
 
+    #[cfg(unix)]
+    pub(crate) fn upgrade_stream(&self) -> Option<impl Stream<Item = Sig>> {
+        use tokio_stream::{StreamExt, wrappers::SignalStream};
+
+        let sig = self.upgrade?;
+        match tokio::signal::unix::signal(sig.kind()) {
+            Ok(signal) => Some(SignalStream::new(signal).map(move |_| sig)),
+            Err(e) => {
+                warn!("Failed to enable `{}` upgrade signal: {}", sig, e);
+                None
+            }
+        }
+    }
+
     #[cfg(not(unix))]
     pub(crate) fn signal_stream(&self) -> Option<impl Stream<Item = Sig>> {
         use tokio_stream::StreamExt;
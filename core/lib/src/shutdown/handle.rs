@@ -1,10 +1,13 @@
 use std::future::Future;
 use std::task::{Context, Poll};
 use std::pin::Pin;
+use std::sync::Arc;
 
 use futures::{FutureExt, StreamExt};
 
-use crate::shutdown::{ShutdownConfig, TripWire};
+use crate::load::Limiter;
+use crate::shutdown::{ShutdownConfig, ShutdownStage, ShutdownStatus, TripWire};
+use crate::shutdown::status::Progress;
 use crate::request::{FromRequest, Outcome, Request};
 
 /// A request guard and future for graceful shutdown.
@@ -67,19 +70,24 @@ use crate::request::{FromRequest, Outcome, Request};
 #[must_use = "`Shutdown` does nothing unless polled or `notify`ed"]
 pub struct Shutdown {
     wire: TripWire,
+    stage: ShutdownStage,
+    progress: Arc<Progress>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Stages {
+    pub drain: Shutdown,
     pub start: Shutdown,
     pub grace: Shutdown,
     pub mercy: Shutdown,
 }
 
 impl Shutdown {
-    fn new() -> Self {
+    fn new(stage: ShutdownStage, progress: &Arc<Progress>) -> Self {
         Shutdown {
             wire: TripWire::new(),
+            stage,
+            progress: progress.clone(),
         }
     }
 
@@ -102,6 +110,7 @@ impl Shutdown {
     /// ```
     #[inline(always)]
     pub fn notify(&self) {
+        self.progress.advance(self.stage);
         self.wire.trip();
     }
 
@@ -124,6 +133,33 @@ impl Shutdown {
     pub fn notified(&self) -> bool {
         self.wire.tripped()
     }
+
+    /// Returns the progress of the shutdown, if any: its stage, how long ago
+    /// it was requested, and the number of requests still being dispatched and
+    /// connections still open.
+    ///
+    /// During a long drain, this allows an application, or an orchestrator
+    /// via an endpoint like [`Health`](crate::health::Health)'s, to observe
+    /// how much work remains.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shutdown::ShutdownStage;
+    /// use rocket::local::blocking::Client;
+    ///
+    /// let client = Client::debug_with(vec![]).unwrap();
+    /// let shutdown = client.rocket().shutdown();
+    /// assert_eq!(shutdown.status().stage, ShutdownStage::Running);
+    /// assert!(shutdown.status().elapsed.is_none());
+    ///
+    /// shutdown.notify();
+    /// assert_eq!(shutdown.status().stage, ShutdownStage::Stopping);
+    /// assert!(shutdown.status().elapsed.is_some());
+    /// ```
+    pub fn status(&self) -> ShutdownStatus {
+        self.progress.status()
+    }
 }
 
 impl Future for Shutdown {
@@ -146,13 +182,20 @@ impl<'r> FromRequest<'r> for Shutdown {
 
 impl Stages {
     pub fn new() -> Self {
+        let progress = Arc::new(Progress::default());
         Stages {
-            start: Shutdown::new(),
-            grace: Shutdown::new(),
-            mercy: Shutdown::new(),
+            drain: Shutdown::new(ShutdownStage::Draining, &progress),
+            start: Shutdown::new(ShutdownStage::Stopping, &progress),
+            grace: Shutdown::new(ShutdownStage::Terminating, &progress),
+            mercy: Shutdown::new(ShutdownStage::Terminating, &progress),
         }
     }
 
+    /// Tracks in-flight requests and open connections via `load`.
+    pub(crate) fn track(&self, load: Arc<Limiter>) {
+        self.start.progress.track(load);
+    }
+
     pub(crate) fn spawn_listener(&self, config: &ShutdownConfig) {
         use futures::stream;
         use futures::future::{select, Either};
@@ -162,15 +205,49 @@ impl Stages {
             None => Either::Right(stream::pending()),
         };
 
+        #[cfg(unix)]
+        if let Some(mut upgrade) = config.upgrade_stream() {
+            let start = self.start.clone();
+            let timeout = config.upgrade_timeout();
+            tokio::spawn(async move {
+                while let Some(sig) = upgrade.next().await {
+                    if start.notified() {
+                        break;
+                    }
+
+                    warn!("Received {}. Upgrading.", sig);
+                    match crate::listener::handoff::upgrade(timeout).await {
+                        Ok(pid) => {
+                            warn!("Sockets handed off to process {}. Shutdown started.", pid);
+                            start.notify();
+                            break;
+                        }
+                        Err(e) => error!("Upgrade failed: {}. Continuing to serve.", e),
+                    }
+                }
+            });
+        }
+
         let start  = self.start.clone();
+        let (drain, drain_duration)  = (self.drain.clone(), config.drain());
         let (grace, grace_duration)  = (self.grace.clone(), config.grace());
         let (mercy, mercy_duration)  = (self.mercy.clone(), config.mercy());
         tokio::spawn(async move {
             if let Either::Left((sig, start)) = select(signal.next(), start).await {
-                warn!("Received {}. Shutdown started.", sig.unwrap());
+                if !drain_duration.is_zero() {
+                    warn!("Received {}. Draining before shutdown.", sig.unwrap());
+                    drain.notify();
+                    tokio::time::sleep(drain_duration).await;
+                    warn!("Drain period elapsed. Shutdown started.");
+                } else {
+                    warn!("Received {}. Shutdown started.", sig.unwrap());
+                }
+
                 start.notify();
             }
 
+            drain.notify();
+
             tokio::time::sleep(grace_duration).await;
             warn!("Shutdown grace period elapsed. Shutting down I/O.");
             grace.notify();
@@ -4,10 +4,12 @@ mod tripwire;
 mod handle;
 mod sig;
 mod config;
+mod status;
 
 pub(crate) use tripwire::TripWire;
 pub(crate) use handle::Stages;
 
 pub use config::ShutdownConfig;
 pub use handle::Shutdown;
+pub use status::{ShutdownStage, ShutdownStatus};
 pub use sig::Sig;
@@ -55,6 +55,26 @@ impl Sig {
     }
 }
 
+#[cfg(unix)]
+impl Sig {
+    pub(crate) fn kind(self) -> tokio::signal::unix::SignalKind {
+        use tokio::signal::unix::SignalKind;
+
+        match self {
+            Sig::Alrm => SignalKind::alarm(),
+            Sig::Chld => SignalKind::child(),
+            Sig::Hup => SignalKind::hangup(),
+            Sig::Int => SignalKind::interrupt(),
+            Sig::Io => SignalKind::io(),
+            Sig::Pipe => SignalKind::pipe(),
+            Sig::Quit => SignalKind::quit(),
+            Sig::Term => SignalKind::terminate(),
+            Sig::Usr1 => SignalKind::user_defined1(),
+            Sig::Usr2 => SignalKind::user_defined2()
+        }
+    }
+}
+
 impl fmt::Display for Sig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.as_str())
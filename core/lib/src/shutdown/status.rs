@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::load::Limiter;
+
+/// The stage of a graceful shutdown.
+///
+/// Stages only advance, in the order of the variants below. See
+/// [`ShutdownConfig`](crate::config::ShutdownConfig) for details on each
+/// stage's duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShutdownStage {
+    /// No shutdown has been requested.
+    Running,
+    /// A shutdown was requested and the
+    /// [`drain`](crate::config::ShutdownConfig::drain) period is underway:
+    /// readiness probes fail and HTTP/1 responses close their connection, but
+    /// new connections are still accepted.
+    Draining,
+    /// New connections are no longer accepted. Pending requests are finishing
+    /// within the [`grace`](crate::config::ShutdownConfig::grace) period.
+    Stopping,
+    /// The grace period has elapsed. Outstanding I/O is being shut down and,
+    /// once the [`mercy`](crate::config::ShutdownConfig::mercy) period
+    /// elapses, terminated.
+    Terminating,
+}
+
+/// A snapshot of the progress of a graceful shutdown.
+///
+/// Returned by [`Shutdown::status()`](crate::Shutdown::status()).
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Shutdown;
+/// use rocket::shutdown::ShutdownStage;
+///
+/// #[get("/drain")]
+/// fn drain(shutdown: Shutdown) -> String {
+///     let status = shutdown.status();
+///     match status.stage {
+///         ShutdownStage::Running => "running".into(),
+///         stage => format!("{}: {} requests in flight after {:?}",
+///             stage, status.requests, status.elapsed.unwrap_or_default()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShutdownStatus {
+    /// The current stage.
+    pub stage: ShutdownStage,
+    /// The time since the shutdown was requested, or `None` if it hasn't been.
+    pub elapsed: Option<Duration>,
+    /// The number of requests being dispatched.
+    pub requests: usize,
+    /// The number of open connections.
+    pub connections: usize,
+}
+
+/// Shutdown progress shared by the handles of every stage.
+#[derive(Debug, Default)]
+pub(crate) struct Progress {
+    stage: AtomicU8,
+    began: OnceLock<Instant>,
+    load: Mutex<Option<Arc<Limiter>>>,
+}
+
+impl ShutdownStage {
+    /// Returns the stage's name in lowercase, as displayed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::shutdown::ShutdownStage;
+    ///
+    /// assert_eq!(ShutdownStage::Draining.as_str(), "draining");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShutdownStage::Running => "running",
+            ShutdownStage::Draining => "draining",
+            ShutdownStage::Stopping => "stopping",
+            ShutdownStage::Terminating => "terminating",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => ShutdownStage::Running,
+            1 => ShutdownStage::Draining,
+            2 => ShutdownStage::Stopping,
+            _ => ShutdownStage::Terminating,
+        }
+    }
+}
+
+impl std::fmt::Display for ShutdownStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl Progress {
+    /// Records that `stage` was reached, if it's later than the current one.
+    pub fn advance(&self, stage: ShutdownStage) {
+        if stage > ShutdownStage::Running {
+            self.began.get_or_init(Instant::now);
+        }
+
+        self.stage.fetch_max(stage as u8, Ordering::AcqRel);
+    }
+
+    /// Sets the limiter in-flight requests and connections are read from.
+    pub fn track(&self, load: Arc<Limiter>) {
+        *self.load.lock().expect("progress lock") = Some(load);
+    }
+
+    pub fn status(&self) -> ShutdownStatus {
+        let load = self.load.lock().expect("progress lock").as_ref().map(|l| l.stats());
+        ShutdownStatus {
+            stage: ShutdownStage::from_u8(self.stage.load(Ordering::Acquire)),
+            elapsed: self.began.get().map(|began| began.elapsed()),
+            requests: load.map_or(0, |load| load.requests),
+            connections: load.map_or(0, |load| load.connections),
+        }
+    }
+}
@@ -238,3 +238,15 @@ impl<T: Send + Sync + 'static> Deref for State<T> {
         &self.0
     }
 }
+
+/// Implemented by [`#[launch]`](crate::launch) for each type it finds managed
+/// in the launch function, with `Registry` a marker type it generates. Checked
+/// by [`state!`](crate::state!) to prove at compile-time that a type is managed.
+#[doc(hidden)]
+pub trait Managed<Registry> {
+    /// Always `Self`.
+    type Type: Send + Sync + 'static;
+}
+
+/// Managed state that takes precedence over managed state of type `T`.
+pub(crate) struct Override<T>(pub T);
@@ -0,0 +1,233 @@
+//! Supervised background tasks.
+//!
+//! Work that outlives a request, such as sending a notification or refreshing
+//! a cache, is often started with `tokio::spawn()` and forgotten. Such tasks
+//! are invisible to operators, keep running during and after shutdown, and
+//! panic silently. A [`TaskSet`], retrieved via
+//! [`Rocket::tasks()`](crate::Rocket::tasks()) or as a request guard, spawns
+//! _supervised_ tasks instead. Supervised tasks are:
+//!
+//!   * **Tracked.** Every running task is listed by [`TaskSet::running()`],
+//!     with its name and spawn time, and by the `/tasks` [admin] endpoint.
+//!
+//!   * **Cancelled on shutdown.** Once [shutdown is triggered], tasks may run
+//!     until the grace period elapses, the same deadline requests are held
+//!     to. Tasks still running then are cancelled: their future is dropped at
+//!     its next `.await` point. Tasks that need to clean up can watch for the
+//!     start of shutdown with [`Rocket::shutdown()`](crate::Rocket::shutdown()).
+//!
+//!   * **Logged.** A task runs in a `task` span with its name, nested in the
+//!     span active when it was spawned, such as that of a request. A task that
+//!     panics or is cancelled is logged with its name and the panic message.
+//!
+//! [admin]: crate::admin
+//! [shutdown is triggered]: crate::config::ShutdownConfig#triggers
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::task::TaskSet;
+//!
+//! # async fn send_welcome_email(_: &str) { }
+//! #[post("/signup/<email>")]
+//! fn signup(email: String, tasks: &TaskSet) -> &'static str {
+//!     tasks.spawn("welcome email", async move {
+//!         send_welcome_email(&email).await;
+//!     });
+//!
+//!     "Welcome aboard!"
+//! }
+//! ```
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use either::Either;
+use futures::FutureExt as _;
+use parking_lot::Mutex;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+use crate::request::{self, FromRequest, Request};
+use crate::shutdown::Shutdown;
+use crate::util::FutureExt;
+
+/// A set of supervised tasks tied to the lifecycle of a Rocket application.
+///
+/// See the [module level docs](self) for details and an example.
+#[derive(Clone)]
+pub struct TaskSet {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    next_id: AtomicU64,
+    running: Mutex<BTreeMap<u64, TaskInfo>>,
+    deadline: Shutdown,
+}
+
+/// Information about a running supervised task.
+///
+/// Returned by [`TaskSet::running()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskInfo {
+    /// A unique, increasing identifier for the task.
+    pub id: u64,
+    /// The name the task was spawned with.
+    pub name: Cow<'static, str>,
+    /// When the task was spawned.
+    pub spawned: SystemTime,
+}
+
+/// Removes a task from the running set when dropped, even if the task is
+/// aborted via its `JoinHandle`.
+struct Registration {
+    inner: Arc<Inner>,
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.inner.running.lock().remove(&self.id);
+    }
+}
+
+/// Returns the message of a panic, if it has one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload.downcast_ref::<&str>().copied()
+        .or_else(|| payload.downcast_ref::<String>().map(|s| s.as_str()))
+        .unwrap_or("[non-string payload]")
+}
+
+impl TaskSet {
+    /// Creates an empty task set whose tasks are cancelled when `deadline`
+    /// resolves.
+    pub(crate) fn new(deadline: Shutdown) -> Self {
+        TaskSet {
+            inner: Arc::new(Inner {
+                next_id: AtomicU64::new(0),
+                running: Mutex::new(BTreeMap::new()),
+                deadline,
+            })
+        }
+    }
+
+    /// Spawns `future` as a supervised task named `name` on the current async
+    /// runtime.
+    ///
+    /// The returned handle resolves to `Some` of the future's output if the
+    /// future completes and `None` if it panics or is cancelled at shutdown.
+    /// The handle need not be awaited; dropping it detaches the task, which
+    /// nevertheless remains supervised. Aborting the task via the handle
+    /// removes it from the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of an async runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::asynchronous::Client;
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::tracked(rocket::build()).await.unwrap();
+    /// let tasks = client.rocket().tasks();
+    ///
+    /// let handle = tasks.spawn("answer", async { 42 });
+    /// assert_eq!(handle.await.unwrap(), Some(42));
+    ///
+    /// let handle = tasks.spawn("bug", async { panic!("oh no") });
+    /// assert_eq!(handle.await.unwrap(), None::<()>);
+    /// # });
+    /// ```
+    pub fn spawn<N, F>(&self, name: N, future: F) -> JoinHandle<Option<F::Output>>
+        where N: Into<Cow<'static, str>>,
+              F: Future + Send + 'static,
+              F::Output: Send + 'static,
+    {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        let name = name.into();
+        let span = tracing::info_span!("task", name = %name, id);
+        let info = TaskInfo { id, name, spawned: SystemTime::now() };
+        self.inner.running.lock().insert(id, info);
+
+        let registration = Registration { inner: self.inner.clone(), id };
+        let deadline = self.inner.deadline.clone();
+        let task = async move {
+            let _registration = registration;
+            match AssertUnwindSafe(future).catch_unwind().race(deadline).await {
+                Either::Left(Ok(output)) => Some(output),
+                Either::Left(Err(panic)) => {
+                    error!(panic = panic_message(&*panic), "supervised task panicked");
+                    None
+                }
+                Either::Right(()) => {
+                    warn!("supervised task cancelled: shutdown grace period elapsed");
+                    None
+                }
+            }
+        };
+
+        tokio::spawn(task.instrument(span))
+    }
+
+    /// Returns information about every running task, in spawn order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::tokio::sync::oneshot;
+    ///
+    /// # rocket::async_test(async {
+    /// let client = Client::tracked(rocket::build()).await.unwrap();
+    /// let tasks = client.rocket().tasks();
+    ///
+    /// let (tx, rx) = oneshot::channel::<()>();
+    /// let handle = tasks.spawn("waiter", rx);
+    /// assert_eq!(tasks.running()[0].name, "waiter");
+    ///
+    /// tx.send(()).unwrap();
+    /// handle.await.unwrap();
+    /// assert!(tasks.is_empty());
+    /// # });
+    /// ```
+    pub fn running(&self) -> Vec<TaskInfo> {
+        self.inner.running.lock().values().cloned().collect()
+    }
+
+    /// Returns the number of running tasks.
+    pub fn len(&self) -> usize {
+        self.inner.running.lock().len()
+    }
+
+    /// Returns `true` if no tasks are running.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r TaskSet {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(req.rocket().tasks())
+    }
+}
+
+impl std::fmt::Debug for TaskSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskSet")
+            .field("running", &self.running())
+            .finish()
+    }
+}
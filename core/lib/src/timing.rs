@@ -0,0 +1,374 @@
+//! Server-Timing instrumentation.
+//!
+//! [`ServerTiming`] is a request-local collector of named timing metrics.
+//! Handlers and guards retrieve it as a request guard, `&ServerTiming`, and
+//! record metrics with [`ServerTiming::record()`], [`ServerTiming::start()`],
+//! or [`ServerTiming::time()`]. Values that outlive the borrow, such as
+//! pooled database connections, record metrics via an owned [`Recorder`].
+//! When the fairing returned by
+//! [`ServerTiming::fairing()`] is attached, every recorded metric, along with
+//! a `total` metric for the entire request, is emitted in a [`Server-Timing`]
+//! response header and logged at the debug level. Browser developer tools
+//! display the header as a breakdown of the time spent on the server.
+//!
+//! [`Server-Timing`]: https://www.w3.org/TR/server-timing/
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use std::time::Duration;
+//! use rocket::timing::ServerTiming;
+//!
+//! # async fn query_db() -> Vec<String> { vec![] }
+//! #[get("/")]
+//! async fn index(timing: &ServerTiming) -> String {
+//!     let users = timing.time("db", query_db()).await;
+//!
+//!     let render = timing.start("render");
+//!     let page = users.join("\n");
+//!     render.stop();
+//!
+//!     timing.record("cache", Duration::from_micros(250));
+//!     page
+//! }
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .mount("/", routes![index])
+//!         .attach(ServerTiming::fairing())
+//! }
+//! ```
+//!
+//! A response to `GET /` then includes a header like:
+//!
+//! ```text
+//! Server-Timing: db;dur=12.5, render;dur=0.042, cache;dur=0.25, total;dur=13.1
+//! ```
+//!
+//! # Privacy
+//!
+//! Timing information can reveal details about the server to clients, such as
+//! whether a cache was hit. Consider only attaching the fairing in debug
+//! builds or profiles when such information is sensitive.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::Future;
+use parking_lot::Mutex;
+
+use crate::{Request, Response, Data};
+use crate::fairing::{Fairing, Info, Kind};
+use crate::http::Header;
+use crate::request::{FromRequest, Outcome};
+
+/// A request-local collector of `Server-Timing` metrics.
+///
+/// See the [module level docs](self) for details.
+pub struct ServerTiming {
+    start: Instant,
+    metrics: Arc<Mutex<Vec<Metric>>>,
+}
+
+/// An owned handle that records metrics into a request's [`ServerTiming`].
+///
+/// Returned by [`ServerTiming::recorder()`]. Metrics recorded after the
+/// response's headers have been written are ignored.
+#[derive(Clone)]
+pub struct Recorder {
+    metrics: Arc<Mutex<Vec<Metric>>>,
+}
+
+/// A single `Server-Timing` metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    name: Cow<'static, str>,
+    duration: Option<Duration>,
+    description: Option<Cow<'static, str>>,
+}
+
+/// Measures the time from its creation in [`ServerTiming::start()`] until it
+/// is stopped or dropped and records it as a metric.
+#[must_use = "the metric is recorded when the timer is stopped or dropped"]
+pub struct Timer<'a> {
+    timing: &'a ServerTiming,
+    start: Instant,
+    name: Option<Cow<'static, str>>,
+}
+
+impl ServerTiming {
+    fn new() -> Self {
+        ServerTiming { start: Instant::now(), metrics: Arc::default() }
+    }
+
+    /// Returns a fairing that emits the metrics recorded during each request
+    /// in a `Server-Timing` response header. A `total` metric with the time
+    /// elapsed since the request was received is always included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::timing::ServerTiming;
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(ServerTiming::fairing())
+    /// }
+    /// ```
+    pub fn fairing() -> impl Fairing {
+        ServerTimingFairing
+    }
+
+    /// Records a metric named `name` with a duration of `duration`.
+    ///
+    /// Metric names must be valid HTTP tokens: metrics with invalid names are
+    /// logged and otherwise ignored.
+    pub fn record<N>(&self, name: N, duration: Duration)
+        where N: Into<Cow<'static, str>>
+    {
+        self.push(Metric::new(name).with_duration(duration));
+    }
+
+    /// Records `metric`, which may include a description or omit a duration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::timing::{ServerTiming, Metric};
+    ///
+    /// #[get("/")]
+    /// fn index(timing: &ServerTiming) {
+    ///     timing.push(Metric::new("miss").with_description("cache miss"));
+    /// }
+    /// ```
+    pub fn push(&self, metric: Metric) {
+        self.metrics.lock().push(metric);
+    }
+
+    /// Starts a timer that, when stopped or dropped, records a metric named
+    /// `name` with the elapsed time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::timing::ServerTiming;
+    ///
+    /// #[get("/")]
+    /// fn index(timing: &ServerTiming) -> String {
+    ///     let _timer = timing.start("render");
+    ///     "Hello, world!".to_string()
+    /// }
+    /// ```
+    pub fn start<N>(&self, name: N) -> Timer<'_>
+        where N: Into<Cow<'static, str>>
+    {
+        Timer { timing: self, start: Instant::now(), name: Some(name.into()) }
+    }
+
+    /// Awaits `future` and records a metric named `name` with the time it
+    /// took to complete, returning the future's output.
+    pub async fn time<N, F>(&self, name: N, future: F) -> F::Output
+        where N: Into<Cow<'static, str>>, F: Future
+    {
+        let timer = self.start(name);
+        let output = future.await;
+        timer.stop();
+        output
+    }
+
+    /// Returns an owned handle that records metrics into `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Instant;
+    /// use rocket::timing::ServerTiming;
+    ///
+    /// #[get("/")]
+    /// fn index(timing: &ServerTiming) -> &'static str {
+    ///     let (recorder, start) = (timing.recorder(), Instant::now());
+    ///     std::thread::spawn(move || recorder.record("thread", start.elapsed()));
+    ///     "Hello, world!"
+    /// }
+    /// ```
+    pub fn recorder(&self) -> Recorder {
+        Recorder { metrics: self.metrics.clone() }
+    }
+
+    /// Returns the time elapsed since the request was received.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns a copy of the metrics recorded so far, in recording order.
+    pub fn metrics(&self) -> Vec<Metric> {
+        self.metrics.lock().clone()
+    }
+}
+
+impl Recorder {
+    /// Records a metric named `name` with a duration of `duration`.
+    ///
+    /// See [`ServerTiming::record()`].
+    pub fn record<N>(&self, name: N, duration: Duration)
+        where N: Into<Cow<'static, str>>
+    {
+        self.push(Metric::new(name).with_duration(duration));
+    }
+
+    /// Records `metric`.
+    ///
+    /// See [`ServerTiming::push()`].
+    pub fn push(&self, metric: Metric) {
+        self.metrics.lock().push(metric);
+    }
+}
+
+impl Metric {
+    /// Returns a new metric named `name` with no duration or description.
+    pub fn new<N: Into<Cow<'static, str>>>(name: N) -> Self {
+        Metric { name: name.into(), duration: None, description: None }
+    }
+
+    /// Sets the duration of the metric.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets the human-readable description of the metric.
+    pub fn with_description<D: Into<Cow<'static, str>>>(mut self, description: D) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// The name of the metric.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The duration of the metric, if any.
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
+    /// The description of the metric, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(duration) = self.duration {
+            // Milliseconds, with up to three fractional digits.
+            let millis = format!("{:.3}", duration.as_secs_f64() * 1000.0);
+            match millis.trim_end_matches('0').trim_end_matches('.') {
+                "" => f.write_str(";dur=0")?,
+                millis => write!(f, ";dur={}", millis)?,
+            }
+        }
+
+        if let Some(description) = &self.description {
+            let escaped = description.replace('\\', "\\\\").replace('"', "\\\"");
+            write!(f, ";desc=\"{}\"", escaped)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Timer<'_> {
+    /// Stops the timer and records its metric.
+    pub fn stop(mut self) {
+        self.record();
+    }
+
+    fn record(&mut self) {
+        if let Some(name) = self.name.take() {
+            self.timing.record(name, self.start.elapsed());
+        }
+    }
+}
+
+impl Drop for Timer<'_> {
+    fn drop(&mut self) {
+        self.record();
+    }
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for ServerTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerTiming")
+            .field("elapsed", &self.elapsed())
+            .field("metrics", &*self.metrics.lock())
+            .finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r> FromRequest<'r> for &'r ServerTiming {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(req.local_cache(ServerTiming::new))
+    }
+}
+
+struct ServerTimingFairing;
+
+#[crate::async_trait]
+impl Fairing for ServerTimingFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Server-Timing",
+            kind: Kind::Request | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        // Start the clock for the `total` metric.
+        req.local_cache(ServerTiming::new);
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let timing = req.local_cache(ServerTiming::new);
+        let mut metrics = timing.metrics();
+        metrics.push(Metric::new("total").with_duration(timing.elapsed()));
+
+        metrics.retain(|metric| {
+            let valid = Header::is_valid_name(metric.name());
+            if !valid {
+                warn!(name = metric.name(), "ignoring server timing metric with invalid name");
+            }
+
+            valid
+        });
+
+        span_debug!("server timing" => for metric in &metrics {
+            debug!(
+                name = metric.name(),
+                duration_ms = metric.duration().map(|d| d.as_secs_f64() * 1000.0),
+                description = metric.description(),
+            );
+        });
+
+        let values = metrics.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        res.adjoin_header(Header::new("Server-Timing", values.join(", ")));
+    }
+}
@@ -7,7 +7,7 @@ use tokio_rustls::LazyConfigAcceptor;
 use rustls::server::{Acceptor, ServerConfig};
 
 use crate::{Ignite, Rocket};
-use crate::listener::{Bind, Certificates, Connection, Endpoint, Listener};
+use crate::listener::{Bind, Certificates, Connection, Endpoint, Listener, TlsInfo};
 use crate::tls::{TlsConfig, Result, Error};
 use super::resolver::DynResolver;
 
@@ -96,4 +96,20 @@ impl<C: Connection> Connection for TlsStream<C> {
         #[cfg(not(feature = "mtls"))]
         None
     }
+
+    fn local_endpoint(&self) -> io::Result<Endpoint> {
+        Ok(self.get_ref().0.local_endpoint()?.assume_tls())
+    }
+
+    fn tls_info(&self) -> Option<TlsInfo> {
+        let conn = self.get_ref().1;
+        Some(TlsInfo {
+            alpn: conn.alpn_protocol().map(|p| String::from_utf8_lossy(p).into_owned()),
+            version: conn.protocol_version().and_then(|v| v.as_str()).map(Into::into),
+            cipher_suite: conn.negotiated_cipher_suite()
+                .and_then(|s| s.suite().as_str())
+                .map(Into::into),
+            server_name: conn.server_name().map(|s| s.to_string()),
+        })
+    }
 }
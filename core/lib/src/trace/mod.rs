@@ -21,12 +21,18 @@ pub use tracing::{Level, level_filters::LevelFilter};
 #[serde(crate = "rocket::serde")]
 #[non_exhaustive]
 pub enum TraceFormat {
+    /// Human-readable, indented output, suited to development.
     #[serde(rename = "pretty")]
     #[serde(alias = "PRETTY")]
     Pretty,
+    /// Human-readable, timestamped, one-line-per-request output.
     #[serde(rename = "compact")]
     #[serde(alias = "COMPACT")]
-    Compact
+    Compact,
+    /// One JSON object per event or span, suited to log aggregators.
+    #[serde(rename = "json")]
+    #[serde(alias = "JSON")]
+    Json,
 }
 
 #[cfg_attr(nightly, doc(cfg(feature = "trace")))]
@@ -18,6 +18,7 @@ mod private {
 
     impl FmtKind for crate::trace::subscriber::Pretty { }
     impl FmtKind for crate::trace::subscriber::Compact { }
+    impl FmtKind for crate::trace::subscriber::Json { }
 }
 
 #[derive(Default)]
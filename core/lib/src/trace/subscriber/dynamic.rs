@@ -10,31 +10,44 @@ use tracing_subscriber::layer::{Context, Layer, Layered, SubscriberExt};
 use tracing_subscriber::util::SubscriberInitExt;
 
 use crate::config::Config;
-use crate::trace::subscriber::{Compact, Pretty, RequestId, RequestIdLayer, RocketFmt};
+use crate::trace::subscriber::{Compact, Json, Pretty, RequestId, RequestIdLayer, RocketFmt};
 use crate::trace::TraceFormat;
 
-/// A subscriber that is either a [`Pretty`] or [`Compact`] [`RocketFmt`].
+/// A subscriber that is either a [`Pretty`], [`Compact`], or [`Json`]
+/// [`RocketFmt`].
 pub struct RocketDynFmt {
-    inner: either::Either<RocketFmt<Compact>, RocketFmt<Pretty>>,
+    inner: Inner,
+}
+
+enum Inner {
+    Compact(RocketFmt<Compact>),
+    Pretty(RocketFmt<Pretty>),
+    Json(RocketFmt<Json>),
 }
 
 impl From<RocketFmt<Compact>> for RocketDynFmt {
     fn from(value: RocketFmt<Compact>) -> Self {
-        RocketDynFmt { inner: either::Either::Left(value) }
+        RocketDynFmt { inner: Inner::Compact(value) }
     }
 }
 
 impl From<RocketFmt<Pretty>> for RocketDynFmt {
     fn from(value: RocketFmt<Pretty>) -> Self {
-        RocketDynFmt { inner: either::Either::Right(value) }
+        RocketDynFmt { inner: Inner::Pretty(value) }
+    }
+}
+
+impl From<RocketFmt<Json>> for RocketDynFmt {
+    fn from(value: RocketFmt<Json>) -> Self {
+        RocketDynFmt { inner: Inner::Json(value) }
     }
 }
 
 impl RocketDynFmt {
     /// Creates a new `RocketDynFmt` subscriber given a `Config`.
     ///
-    /// [`Config::log_format`] determines which `RocketFmt` subscriber (either
-    /// [`Pretty`] or [`Compact`]) is used.
+    /// [`Config::log_format`] determines which `RocketFmt` subscriber
+    /// ([`Pretty`], [`Compact`], or [`Json`]) is used.
     ///
     /// If `config` is `None`, [`Config::debug_default()`] is used, which uses
     /// the [`Pretty`] subscriber by default.
@@ -48,6 +61,7 @@ impl RocketDynFmt {
         match format {
             TraceFormat::Pretty => Self::from(RocketFmt::<Pretty>::new(workers, colors, level)),
             TraceFormat::Compact => Self::from(RocketFmt::<Compact>::new(workers, colors, level)),
+            TraceFormat::Json => Self::from(RocketFmt::<Json>::new(workers, colors, level)),
         }
     }
 
@@ -83,8 +97,9 @@ macro_rules! forward {
         #[inline(always)]
         fn $method(& $($r)? self $(, $p : $t)*) $(-> $R)? {
             match & $($r)* self.inner {
-                either::Either::Left(layer) => Layer::<$T>::$method(layer, $($p),*),
-                either::Either::Right(layer) => Layer::<$T>::$method(layer, $($p),*),
+                Inner::Compact(layer) => Layer::<$T>::$method(layer, $($p),*),
+                Inner::Pretty(layer) => Layer::<$T>::$method(layer, $($p),*),
+                Inner::Json(layer) => Layer::<$T>::$method(layer, $($p),*),
             }
         }
     };
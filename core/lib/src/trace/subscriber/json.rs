@@ -0,0 +1,178 @@
+use std::fmt::{self, Write};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Metadata, Subscriber};
+use tracing::span::{Attributes, Id, Record};
+use tracing_subscriber::layer::{Layer, Context};
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+use tracing_subscriber::field::RecordFields;
+
+use time::OffsetDateTime;
+
+use crate::trace::subscriber::{RequestId, RocketFmt};
+use crate::util::Formatter;
+
+/// Formats each event and span as a single-line JSON object.
+///
+/// Every object has `timestamp`, `level`, `target`, `name`, `kind`, and
+/// `fields` keys. The `kind` is `"event"` for events, `"span"` for new spans,
+/// and `"record"` for values later recorded to a span. `fields` is an object
+/// with one key per recorded field. Objects emitted within a span additionally
+/// carry a `spans` key, listing the names of enclosing spans from outermost to
+/// innermost, and objects emitted while handling a request carry a
+/// `request_id` key.
+///
+/// Startup output is emitted as regular events, so each configuration value,
+/// route, catcher, and fairing reported at launch becomes an object of its own.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Json;
+
+impl RocketFmt<Json> {
+    fn print_json<S, F>(
+        &self,
+        kind: &str,
+        meta: &Metadata<'_>,
+        data: F,
+        span: Option<SpanRef<'_, S>>,
+    ) where S: for<'a> LookupSpan<'a>, F: RecordFields {
+        let mut line = String::with_capacity(256);
+        let now = OffsetDateTime::now_utc();
+        let (date, time) = (now.date(), now.time());
+        let (year, month, day) = (date.year(), date.month() as u8, date.day());
+        let (h, m, s, l) = (time.hour(), time.minute(), time.second(), time.millisecond());
+        let _ = write!(line, "{{\"timestamp\":\"{year:04}-{month:02}-{day:02}T\
+            {h:02}:{m:02}:{s:02}.{l:03}Z\"");
+
+        let name = meta.name()
+            .starts_with("event ")
+            .then_some(meta.target())
+            .unwrap_or(meta.name());
+
+        let _ = write!(line, ",\"level\":\"{}\"", meta.level());
+        let _ = write!(line, ",\"target\":{}", json_str(meta.target()));
+        let _ = write!(line, ",\"name\":{}", json_str(name));
+        let _ = write!(line, ",\"kind\":\"{kind}\"");
+
+        if let Some(span) = span {
+            line.push_str(",\"spans\":[");
+            for (i, span) in span.scope().from_root().enumerate() {
+                if i != 0 { line.push(','); }
+                let _ = write!(line, "{}", json_str(span.name()));
+            }
+
+            line.push(']');
+        }
+
+        if let Some(id) = RequestId::current() {
+            let _ = write!(line, ",\"request_id\":\"{id:x}\"");
+        }
+
+        line.push_str(",\"fields\":{");
+        data.record(&mut JsonFields { out: &mut line, empty: true });
+        line.push_str("}}");
+        println!("{line}");
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for RocketFmt<Json> {
+    fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, S>) -> bool {
+        self.filter.would_enable(metadata.target(), metadata.level())
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctxt: Context<'_, S>) {
+        self.print_json("event", event.metadata(), event, ctxt.event_span(event));
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctxt: Context<'_, S>) {
+        let span = ctxt.span(id).expect("new_span: span does not exist");
+        let parent = span.parent();
+        self.print_json("span", span.metadata(), attrs, parent);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctxt: Context<'_, S>) {
+        let span = ctxt.span(id).expect("record: span does not exist");
+        let metadata = span.metadata();
+        self.print_json("record", metadata, values, Some(span));
+    }
+}
+
+/// Appends each visited field to a JSON object as `"name":value`.
+struct JsonFields<'a> {
+    out: &'a mut String,
+    empty: bool,
+}
+
+impl JsonFields<'_> {
+    fn key(&mut self, field: &Field) -> &mut String {
+        if !std::mem::take(&mut self.empty) {
+            self.out.push(',');
+        }
+
+        let _ = write!(self.out, "{}:", json_str(field.name()));
+        self.out
+    }
+}
+
+impl Visit for JsonFields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let value = Formatter(|f| value.fmt(f)).to_string();
+        let _ = write!(self.key(field), "{}", json_str(&value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let _ = write!(self.key(field), "{}", json_str(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        let _ = write!(self.key(field), "{value}");
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        let _ = write!(self.key(field), "{value}");
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        let _ = write!(self.key(field), "{value}");
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if value.is_finite() {
+            let _ = write!(self.key(field), "{value}");
+        } else {
+            let _ = write!(self.key(field), "\"{value}\"");
+        }
+    }
+}
+
+/// Formats `string` as a quoted, escaped JSON string.
+fn json_str(string: &str) -> impl fmt::Display + '_ {
+    Formatter(move |f| {
+        f.write_char('"')?;
+        for c in string.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+
+        f.write_char('"')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_str;
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_str("plain").to_string(), r#""plain""#);
+        assert_eq!(json_str("a \"b\"\\c").to_string(), r#""a \"b\"\\c""#);
+        assert_eq!(json_str("1\n2\t\u{1}").to_string(), r#""1\n2\t\u0001""#);
+        assert_eq!(json_str("ü").to_string(), r#""ü""#);
+    }
+}
@@ -1,12 +1,14 @@
 mod visit;
 mod pretty;
 mod compact;
+mod json;
 mod dynamic;
 mod common;
 mod request_id;
 
 pub use pretty::Pretty;
 pub use compact::Compact;
+pub use json::Json;
 pub use common::RocketFmt;
 pub use request_id::{RequestId, RequestIdLayer};
 pub use dynamic::RocketDynFmt;
@@ -84,14 +84,21 @@ impl Trace for Config {
             cli_colors = %self.cli_colors,
             workers = self.workers,
             max_blocking = self.max_blocking,
+            thread_name = %self.thread_name,
+            thread_stack_size = (self.thread_stack_size != 0).then_some(self.thread_stack_size),
             ident = %self.ident,
             ip_header = self.ip_header.as_ref().map(|s| s.as_str()),
             proxy_proto_header = self.proxy_proto_header.as_ref().map(|s| s.as_str()),
+            public_url = self.public_url.as_ref().map(display),
             limits = %Formatter(|f| f.debug_map()
                 .entries(self.limits.limits.iter().map(|(k, v)| (k.as_str(), display(v))))
                 .finish()),
             temp_dir = %self.temp_dir.relative().display(),
             keep_alive = (self.keep_alive != 0).then_some(self.keep_alive),
+            request_timeout = (self.request_timeout != 0).then_some(self.request_timeout),
+            timeout_header = self.timeout_header.as_ref().map(|s| s.as_str()),
+            method_not_allowed = self.method_not_allowed,
+            auto_options = self.auto_options,
             shutdown.ctrlc = self.shutdown.ctrlc,
             shutdown.signals = %{
                 #[cfg(not(unix))] {
@@ -104,9 +111,20 @@ impl Trace for Config {
                         .finish())
                 }
             },
+                shutdown.drain = self.shutdown.drain,
                 shutdown.grace = self.shutdown.grace,
                 shutdown.mercy = self.shutdown.mercy,
                 shutdown.force = self.shutdown.force,
+                load.max_connections = (self.load.max_connections != 0)
+                    .then_some(self.load.max_connections),
+                load.max_requests = (self.load.max_requests != 0).then_some(self.load.max_requests),
+                load.queue = self.load.queue,
+                strict.enabled = self.strict.enabled,
+                strict.max_headers = self.strict.max_headers,
+                strict.max_header_size = %self.strict.max_header_size,
+                normalize.enabled = self.normalize.enabled,
+                redact.headers = ?self.redact.headers,
+                redact.query = ?self.redact.query,
         }
 
         #[cfg(feature = "secrets")] {
@@ -150,6 +168,9 @@ impl Trace for Route {
             uri.base = %self.uri.base(),
             uri.unmounted = %self.uri.unmounted(),
             format = self.format.as_ref().map(display),
+            headers = (!self.header_policies.is_empty()).then(|| Formatter(|f| {
+                f.debug_list().entries(self.header_policies.iter().map(|p| p.name())).finish()
+            })).map(display),
             location = self.location.as_ref()
                 .map(|(file, line, _)| Formatter(move |f| write!(f, "{file}:{line}")))
                 .map(display),
@@ -278,7 +299,8 @@ impl Trace for Sentry {
 
 impl Trace for Request<'_> {
     fn trace(&self, level: Level) {
-        event!(level, "request", method = %self.method(), uri = %self.uri())
+        let uri = self.rocket().config().redact.uri(self.uri());
+        event!(level, "request", method = %self.method(), %uri)
     }
 }
 
@@ -286,7 +308,10 @@ impl Trace for ConnectionMeta {
     fn trace(&self, level: Level) {
         event!(level, "connection",
             endpoint = self.peer_endpoint.as_ref().map(display),
+            local = self.local_endpoint.as_ref().map(display),
             certs = self.peer_certs.is_some(),
+            alpn = self.tls.as_ref().and_then(|tls| tls.alpn.as_deref()),
+            tls_version = self.tls.as_ref().and_then(|tls| tls.version.as_deref()),
         )
     }
 }
@@ -1,5 +1,5 @@
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
 use bytes::{Bytes, BytesMut};
 use futures::stream::Stream;
@@ -51,6 +51,8 @@ pin_project! {
         buf: BytesMut,
         capacity: usize,
         done: bool,
+        // Trailers to send, as a final frame, after the body.
+        trailers: Option<http::HeaderMap>,
     }
 }
 
@@ -67,8 +69,20 @@ impl<R: AsyncRead> ReaderStream<R> {
             buf: BytesMut::with_capacity(capacity),
             capacity,
             done: false,
+            trailers: None,
         }
     }
+
+    /// Sets the trailers to emit after the last chunk of the body.
+    pub fn with_trailers(mut self, trailers: http::HeaderMap) -> Self {
+        self.trailers = (!trailers.is_empty()).then_some(trailers);
+        self
+    }
+
+    /// Takes the trailers, if any, so that they can be sent manually.
+    pub fn take_trailers(&mut self) -> Option<http::HeaderMap> {
+        self.trailers.take()
+    }
 }
 
 impl<R: AsyncRead> Stream for ReaderStream<R> {
@@ -116,9 +130,14 @@ impl<R: AsyncRead> hyper::body::Body for ReaderStream<R> {
     type Error = std::io::Error;
 
     fn poll_frame(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
-        self.poll_next(cx).map_ok(hyper::body::Frame::data)
+        use hyper::body::Frame;
+
+        match ready!(self.as_mut().poll_next(cx)) {
+            Some(result) => Poll::Ready(Some(result.map(Frame::data))),
+            None => Poll::Ready(self.project().trailers.take().map(|t| Ok(Frame::trailers(t)))),
+        }
     }
 }
@@ -0,0 +1,395 @@
+//! Signature verification for webhook payloads.
+//!
+//! Webhook providers sign request bodies with a shared secret so that
+//! receivers can verify that a request originates from the provider and was
+//! not tampered with. Verification must happen against the _raw_ body, exactly
+//! as it was received, before it is parsed. The [`SignedPayload<T, S>`] data
+//! guard does just that: it reads the body, verifies the signature in the
+//! request's headers according to the [`Scheme`] `S`, and only then parses the
+//! body as the data guard `T`.
+//!
+//! Three schemes are provided:
+//!
+//!   * [`GitHub`]: HMAC-SHA256 in `X-Hub-Signature-256: sha256=<hex>`.
+//!   * [`Stripe`]: timestamped HMAC-SHA256 in `Stripe-Signature: t=..,v1=..`.
+//!   * [`Hmac`]: HMAC-SHA256 in `X-Signature: <hex>`, optionally prefixed with
+//!     `sha256=`.
+//!
+//! Signatures are compared in constant time. Other schemes can be supported by
+//! implementing [`Scheme`], typically in terms of [`verify_hmac_sha256()`].
+//!
+//! # Configuration
+//!
+//! The secret for a scheme is read from the `webhooks.{name}` configuration
+//! parameter, where `{name}` is the scheme's [`Scheme::NAME`]. For example, in
+//! `Rocket.toml`:
+//!
+//! ```toml
+//! [default.webhooks]
+//! github = "my-github-secret"
+//! stripe = "whsec_..."
+//! ```
+//!
+//! Or, via environment variables: `ROCKET_WEBHOOKS={github="my-github-secret"}`.
+//! Requests are rejected with `500 Internal Server Error` if no secret is
+//! configured.
+//!
+//! The body is read up to the `webhook` [limit](crate::data::Limits), falling
+//! back to the limit for the scheme's name and finally to 1MiB.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[cfg(feature = "json")] mod example {
+//! use rocket::serde::{Deserialize, json::Json};
+//! use rocket::webhook::{SignedPayload, GitHub, Stripe};
+//!
+//! #[derive(Deserialize)]
+//! #[serde(crate = "rocket::serde")]
+//! struct Push {
+//!     after: String,
+//! }
+//!
+//! #[post("/hooks/github", data = "<push>")]
+//! fn github(push: SignedPayload<Json<Push>, GitHub>) -> String {
+//!     format!("pushed {}", push.after)
+//! }
+//!
+//! #[post("/hooks/stripe", data = "<event>")]
+//! fn stripe(event: SignedPayload<String, Stripe>) {
+//!     let event: String = event.into_inner();
+//!     /* ... */
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use hmac::{Mac, Hmac as HmacImpl};
+use sha2::Sha256;
+
+use crate::Request;
+use crate::data::{Data, FromData, Limits, Outcome};
+use crate::http::Status;
+
+/// A data guard that verifies the signature of the request body before
+/// parsing it as `T`.
+///
+/// The body is verified according to the [`Scheme`] `S`, which defaults to
+/// [`GitHub`]. If verification succeeds, `T` is parsed from the verified body
+/// via its [`FromData`] implementation. See the [module docs](self) for
+/// configuration details and an example.
+///
+/// # Outcome
+///
+/// The guard fails with:
+///
+///   * `500 Internal Server Error` if no secret is configured for `S`.
+///   * `413 Payload Too Large` if the body exceeds the configured limit.
+///   * `401 Unauthorized` if the signature is missing, malformed, or invalid.
+///
+/// Otherwise, the guard succeeds, fails, or forwards as `T` does.
+pub struct SignedPayload<T, S = GitHub> {
+    value: T,
+    _scheme: PhantomData<fn() -> S>,
+}
+
+/// A webhook signature scheme.
+///
+/// A scheme determines the name of the configuration parameter that holds the
+/// secret and how a request's signature is verified.
+///
+/// # Example
+///
+/// A scheme for signatures sent in an `X-Acme-Signature` header:
+///
+/// ```rust
+/// use rocket::Request;
+/// use rocket::webhook::{Scheme, SignatureError, verify_hmac_sha256};
+///
+/// struct Acme;
+///
+/// impl Scheme for Acme {
+///     const NAME: &'static str = "acme";
+///
+///     fn verify(req: &Request<'_>, body: &[u8], secret: &[u8]) -> Result<(), SignatureError> {
+///         let signature = req.headers().get_one("X-Acme-Signature")
+///             .ok_or(SignatureError::Missing("X-Acme-Signature"))?;
+///
+///         verify_hmac_sha256(secret, &[body], signature)
+///     }
+/// }
+/// ```
+pub trait Scheme: Send + Sync + 'static {
+    /// The name of the scheme. The secret is read from the `webhooks.{NAME}`
+    /// configuration parameter.
+    const NAME: &'static str;
+
+    /// Verifies the signature of `req` with `body` using `secret`.
+    fn verify(req: &Request<'_>, body: &[u8], secret: &[u8]) -> Result<(), SignatureError>;
+}
+
+/// GitHub's signature scheme, configured via `webhooks.github`.
+///
+/// The signature is the hex-encoded HMAC-SHA256 of the body in an
+/// `X-Hub-Signature-256` header prefixed with `sha256=`.
+#[derive(Debug, Clone, Copy)]
+pub struct GitHub;
+
+/// Stripe's signature scheme, configured via `webhooks.stripe`.
+///
+/// The signature is carried in a `Stripe-Signature` header of the form
+/// `t=<timestamp>,v1=<signature>[,v1=<signature>..]`, where each `v1`
+/// signature is the hex-encoded HMAC-SHA256 of `<timestamp>.<body>`. A request
+/// is accepted if any `v1` signature is valid and the timestamp is no more than
+/// [`Stripe::TOLERANCE`] from the current time, preventing replays.
+#[derive(Debug, Clone, Copy)]
+pub struct Stripe;
+
+/// A generic HMAC-SHA256 scheme, configured via `webhooks.hmac`.
+///
+/// The signature is the hex-encoded HMAC-SHA256 of the body in an `X-Signature`
+/// header, optionally prefixed with `sha256=`.
+#[derive(Debug, Clone, Copy)]
+pub struct Hmac;
+
+/// An error verifying a signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureError {
+    /// The header with the given name, containing the signature, is missing.
+    Missing(&'static str),
+    /// The header with the given name is malformed.
+    Malformed(&'static str),
+    /// The signature's timestamp is outside of the accepted tolerance.
+    Expired,
+    /// The signature does not match the body.
+    Mismatch,
+}
+
+/// An error returned by the [`SignedPayload`] guard.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// No secret is configured for the scheme.
+    Config(Box<figment::Error>),
+    /// An I/O error occurred while reading the body.
+    Io(io::Error),
+    /// The body exceeded the configured limit.
+    TooLarge,
+    /// The signature could not be verified.
+    Signature(SignatureError),
+    /// The body was verified but the inner guard failed.
+    Payload(E),
+}
+
+impl<T, S> SignedPayload<T, S> {
+    /// Consumes `self` and returns the verified value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::webhook::SignedPayload;
+    ///
+    /// #[post("/hook", data = "<payload>")]
+    /// fn hook(payload: SignedPayload<String>) -> String {
+    ///     payload.into_inner()
+    /// }
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, S> Deref for SignedPayload<T, S> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, S> DerefMut for SignedPayload<T, S> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug, S> fmt::Debug for SignedPayload<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SignedPayload").field(&self.value).finish()
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: FromData<'r>, S: Scheme> FromData<'r> for SignedPayload<T, S> {
+    type Error = Error<T::Error>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let param = format!("webhooks.{}", S::NAME);
+        let secret = match req.rocket().figment().extract_inner::<String>(&param) {
+            Ok(secret) => secret,
+            Err(e) => {
+                error!(scheme = S::NAME, "missing webhook secret: {e}");
+                return Outcome::Error((Status::InternalServerError, Error::Config(e.into())));
+            }
+        };
+
+        let limit = req.limits().find(["webhook", S::NAME]).unwrap_or(Limits::JSON);
+        let body = match data.open(limit).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => return Outcome::Error((Status::PayloadTooLarge, Error::TooLarge)),
+            Err(e) => return Outcome::Error((Status::BadRequest, Error::Io(e))),
+        };
+
+        if let Err(e) = S::verify(req, &body, secret.as_bytes()) {
+            debug!(scheme = S::NAME, "webhook signature rejected: {e}");
+            return Outcome::Error((Status::Unauthorized, Error::Signature(e)));
+        }
+
+        match T::from_data(req, Data::local(body)).await {
+            Outcome::Success(value) => Outcome::Success(SignedPayload {
+                value,
+                _scheme: PhantomData,
+            }),
+            Outcome::Error((status, e)) => Outcome::Error((status, Error::Payload(e))),
+            Outcome::Forward(f) => Outcome::Forward(f),
+        }
+    }
+}
+
+impl Scheme for GitHub {
+    const NAME: &'static str = "github";
+
+    fn verify(req: &Request<'_>, body: &[u8], secret: &[u8]) -> Result<(), SignatureError> {
+        const HEADER: &str = "X-Hub-Signature-256";
+
+        let signature = req.headers().get_one(HEADER).ok_or(SignatureError::Missing(HEADER))?;
+        let hex = signature.strip_prefix("sha256=").ok_or(SignatureError::Malformed(HEADER))?;
+        verify_hmac_sha256(secret, &[body], hex)
+    }
+}
+
+impl Stripe {
+    /// The maximum difference between a signature's timestamp and the current
+    /// time: five minutes.
+    pub const TOLERANCE: Duration = Duration::from_secs(5 * 60);
+}
+
+impl Scheme for Stripe {
+    const NAME: &'static str = "stripe";
+
+    fn verify(req: &Request<'_>, body: &[u8], secret: &[u8]) -> Result<(), SignatureError> {
+        const HEADER: &str = "Stripe-Signature";
+
+        let header = req.headers().get_one(HEADER).ok_or(SignatureError::Missing(HEADER))?;
+        let mut timestamp = None;
+        let mut signatures = vec![];
+        for (key, value) in header.split(',').filter_map(|kv| kv.trim().split_once('=')) {
+            match key {
+                "t" => timestamp = Some(value),
+                "v1" => signatures.push(value),
+                _ => {}
+            }
+        }
+
+        let timestamp = timestamp.ok_or(SignatureError::Malformed(HEADER))?;
+        let seconds: i64 = timestamp.parse().map_err(|_| SignatureError::Malformed(HEADER))?;
+        if signatures.is_empty() {
+            return Err(SignatureError::Malformed(HEADER));
+        }
+
+        let now = time::OffsetDateTime::now_utc().unix_timestamp();
+        if now.abs_diff(seconds) > Stripe::TOLERANCE.as_secs() {
+            return Err(SignatureError::Expired);
+        }
+
+        let payload: [&[u8]; 3] = [timestamp.as_bytes(), b".", body];
+        signatures.into_iter()
+            .map(|signature| verify_hmac_sha256(secret, &payload, signature))
+            .find(|result| result.is_ok())
+            .unwrap_or(Err(SignatureError::Mismatch))
+    }
+}
+
+impl Scheme for Hmac {
+    const NAME: &'static str = "hmac";
+
+    fn verify(req: &Request<'_>, body: &[u8], secret: &[u8]) -> Result<(), SignatureError> {
+        const HEADER: &str = "X-Signature";
+
+        let signature = req.headers().get_one(HEADER).ok_or(SignatureError::Missing(HEADER))?;
+        let hex = signature.strip_prefix("sha256=").unwrap_or(signature);
+        verify_hmac_sha256(secret, &[body], hex)
+    }
+}
+
+/// Verifies that `hex` is the hex-encoded HMAC-SHA256, keyed by `secret`, of
+/// the concatenation of `parts`. The comparison is constant-time.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::webhook::{verify_hmac_sha256, SignatureError};
+///
+/// let hex = "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+/// let parts: [&[u8]; 2] = [b"The quick brown fox ", b"jumps over the lazy dog"];
+/// assert!(verify_hmac_sha256(b"key", &parts, hex).is_ok());
+///
+/// let result = verify_hmac_sha256(b"key", &[b"tampered"], hex);
+/// assert_eq!(result, Err(SignatureError::Mismatch));
+/// ```
+pub fn verify_hmac_sha256(
+    secret: &[u8],
+    parts: &[&[u8]],
+    hex: &str
+) -> Result<(), SignatureError> {
+    let signature = decode_hex(hex.trim()).ok_or(SignatureError::Mismatch)?;
+    let mut mac = HmacImpl::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    parts.iter().for_each(|part| mac.update(part));
+    mac.verify_slice(&signature).map_err(|_| SignatureError::Mismatch)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    let nibble = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Some((nibble(pair[0])? << 4) | nibble(pair[1])?))
+        .collect()
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::Missing(header) => write!(f, "missing `{header}` header"),
+            SignatureError::Malformed(header) => write!(f, "malformed `{header}` header"),
+            SignatureError::Expired => write!(f, "signature timestamp outside of tolerance"),
+            SignatureError::Mismatch => write!(f, "signature does not match payload"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError { }
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(e) => write!(f, "missing webhook secret: {e}"),
+            Error::Io(e) => write!(f, "i/o error: {e}"),
+            Error::TooLarge => write!(f, "payload exceeds limit"),
+            Error::Signature(e) => write!(f, "invalid signature: {e}"),
+            Error::Payload(e) => write!(f, "invalid payload: {e}"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for Error<E> { }
@@ -0,0 +1,95 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use rocket::{Request, Data};
+use rocket::data::ToByteUnit;
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::response::{Completion, ResponseGuard};
+use rocket::tokio::io::AsyncReadExt;
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[post("/ignore")]
+fn ignore() -> &'static str {
+    "ignored"
+}
+
+struct Prefix;
+
+#[rocket::async_trait]
+impl Fairing for Prefix {
+    fn info(&self) -> Info {
+        Info { name: "Prefix", kind: Kind::Data }
+    }
+
+    async fn on_data<'r>(&self, _: &Request<'_>, data: Data<'r>) -> Data<'r> {
+        let prefix = Cursor::new(b"prefix: ".to_vec());
+        Data::from_reader(prefix.chain(data.open(1.mebibytes())))
+    }
+}
+
+type Written = Arc<Mutex<Vec<(Completion, u64)>>>;
+
+fn client_with_log() -> (Client, Written) {
+    let written = Written::default();
+    let log = written.clone();
+    let rocket = rocket::build()
+        .mount("/", routes![echo, ignore])
+        .attach(Prefix)
+        .attach(AdHoc::on_response("Size", move |req, res| {
+            let log = log.clone();
+            Box::pin(async move {
+                res.set_header(Header::new("X-Read", req.body_bytes_read().to_string()));
+                res.set_header(Header::new("X-Written", req.body_bytes_written().to_string()));
+
+                let guard = req.guard::<ResponseGuard<'_>>().await.unwrap();
+                guard.on_complete_with_bytes(move |c, n| log.lock().unwrap().push((c, n)));
+            })
+        }));
+
+    (Client::debug(rocket).unwrap(), written)
+}
+
+fn client() -> Client {
+    client_with_log().0
+}
+
+#[test]
+fn body_bytes_read_counts_raw_bytes() {
+    let client = client();
+    let response = client.post("/echo").body("hello, world").dispatch();
+    assert_eq!(response.headers().get_one("X-Read"), Some("12"));
+    assert_eq!(response.into_string().unwrap(), "prefix: hello, world");
+
+    let response = client.post("/echo").dispatch();
+    assert_eq!(response.headers().get_one("X-Read"), Some("0"));
+}
+
+#[test]
+fn unread_bodies_are_not_counted() {
+    let client = client();
+    let response = client.post("/ignore").body("hello, world").dispatch();
+    assert_eq!(response.headers().get_one("X-Read"), Some("0"));
+}
+
+#[test]
+fn body_bytes_written_are_reported_on_completion() {
+    let (client, written) = client_with_log();
+    let response = client.post("/echo").body("hello, world").dispatch();
+    assert_eq!(response.headers().get_one("X-Written"), Some("0"));
+    assert!(written.lock().unwrap().is_empty());
+
+    assert_eq!(response.into_string().unwrap(), "prefix: hello, world");
+    assert_eq!(*written.lock().unwrap(), [(Completion::Written, 20)]);
+
+    let response = client.post("/ignore").dispatch();
+    drop(response);
+    assert_eq!(written.lock().unwrap()[1], (Completion::Aborted, 0));
+}
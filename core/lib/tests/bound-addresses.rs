@@ -0,0 +1,58 @@
+use std::net::{SocketAddr, Ipv4Addr};
+
+use rocket::config::Config;
+use rocket::listener::tcp::TcpListener;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[rocket::get("/")]
+fn index() -> &'static str {
+    "bound"
+}
+
+#[rocket::async_test]
+async fn bound_addresses_resolve_after_launch() {
+    let rocket = rocket::custom(Config::debug_default())
+        .mount("/", rocket::routes![index])
+        .ignite().await
+        .unwrap();
+
+    let bound = rocket.bound_addresses();
+    assert!(bound.get().is_none());
+
+    let shutdown = rocket.shutdown();
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let server = rocket::tokio::spawn(rocket.try_launch_on(TcpListener::bind(addr)));
+
+    let endpoints = bound.clone().await;
+    assert_eq!(endpoints.len(), 1);
+    let addr = endpoints[0].tcp().unwrap();
+    assert_ne!(addr.port(), 0);
+    assert_eq!(bound.get().unwrap(), &endpoints[..]);
+
+    let mut stream = rocket::tokio::net::TcpStream::connect(addr).await.unwrap();
+    let request = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    stream.write_all(request).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.ends_with("bound"));
+
+    shutdown.notify();
+    let rocket = server.await.unwrap().unwrap();
+    assert_eq!(rocket.bound_addresses().await, endpoints);
+}
+
+#[rocket::async_test]
+async fn bound_addresses_resolve_empty_if_never_launched() {
+    let rocket = rocket::custom(Config::debug_default()).ignite().await.unwrap();
+    let bound = rocket.bound_addresses();
+    drop(rocket);
+    assert!(bound.await.is_empty());
+}
+
+#[test]
+fn local_client_bound_addresses() {
+    let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    let endpoints = client.rocket().bound_addresses().get().unwrap().to_vec();
+    let listed: Vec<_> = client.rocket().endpoints().cloned().collect();
+    assert_eq!(endpoints, listed);
+}
@@ -0,0 +1,79 @@
+#[macro_use] extern crate rocket;
+
+use std::path::Path;
+
+use rocket::config::Config;
+use rocket::data::{BufferPolicy, ToByteUnit};
+use rocket::fairing::AdHoc;
+use rocket::fs::TempFile;
+use rocket::local::blocking::Client;
+use rocket::tokio::io::AsyncReadExt;
+
+#[post("/<_>", data = "<file>")]
+async fn upload(mut file: TempFile<'_>) -> String {
+    let mut contents = String::new();
+    file.open().await.unwrap().read_to_string(&mut contents).await.unwrap();
+    let path = file.path().map(|p| p.display().to_string());
+
+    let dir = tempfile::tempdir().unwrap();
+    file.persist_to(dir.path().join("file")).await.unwrap();
+    assert_eq!(std::fs::read_to_string(dir.path().join("file")).unwrap(), contents);
+
+    format!("{} {}", contents.len(), path.unwrap_or_else(|| "-".into()))
+}
+
+fn client(policy: BufferPolicy) -> Client {
+    let config = Config { buffer: policy, ..Config::debug_default() };
+    let rocket = rocket::custom(config)
+        .mount("/", routes![upload])
+        .attach(AdHoc::on_request("Memory", |req, _| Box::pin(async move {
+            if req.uri().path() == "/memory" {
+                req.set_buffer_policy(BufferPolicy { memory: 1.kibibytes(), ..Default::default() });
+            }
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+fn upload_to(client: &Client, uri: &str, len: usize) -> (usize, Option<String>) {
+    let response = client.post(uri).body("a".repeat(len)).dispatch().into_string().unwrap();
+    let (len, path) = response.split_once(' ').unwrap();
+    (len.parse().unwrap(), (path != "-").then(|| path.to_string()))
+}
+
+#[test]
+fn default_policy_writes_named_files() {
+    let client = client(BufferPolicy::default());
+    let (len, path) = upload_to(&client, "/file", 10);
+    assert_eq!(len, 10);
+    assert!(path.is_some());
+}
+
+#[test]
+fn memory_policy_buffers_small_bodies() {
+    let client = client(BufferPolicy { memory: 1.kibibytes(), ..Default::default() });
+    assert_eq!(upload_to(&client, "/file", 0), (0, None));
+    assert_eq!(upload_to(&client, "/file", 1024), (1024, None));
+
+    let (len, path) = upload_to(&client, "/file", 1025);
+    assert_eq!(len, 1025);
+    assert!(path.is_some());
+}
+
+#[test]
+fn per_request_policy_overrides_config() {
+    let client = client(BufferPolicy::default());
+    assert_eq!(upload_to(&client, "/memory", 100), (100, None));
+    assert!(upload_to(&client, "/file", 100).1.is_some());
+}
+
+#[test]
+fn temp_dir_and_unnamed_policies() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = client(BufferPolicy { temp_dir: Some(dir.path().into()), ..Default::default() });
+    let (_, path) = upload_to(&client, "/file", 100);
+    assert!(Path::new(&path.unwrap()).starts_with(dir.path()));
+
+    let client = client(BufferPolicy { unnamed: true, ..Default::default() });
+    assert_eq!(upload_to(&client, "/file", 4096), (4096, None));
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "multipart")]
+
 #[macro_use] extern crate rocket;
 
 use std::str::from_utf8;
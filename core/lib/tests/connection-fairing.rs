@@ -0,0 +1,103 @@
+use std::net::{SocketAddr, Ipv4Addr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rocket::config::Config;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::listener::tcp::TcpListener;
+use rocket::request::ConnectionInfo;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[derive(Default)]
+struct Counts {
+    connects: AtomicUsize,
+    disconnects: AtomicUsize,
+}
+
+struct Opened;
+
+#[derive(Default)]
+struct Served(AtomicUsize);
+
+#[rocket::async_trait]
+impl Fairing for Counts {
+    fn info(&self) -> Info {
+        Info { name: "Connection Counter", kind: Kind::Connection }
+    }
+
+    async fn on_connect(&self, conn: ConnectionInfo<'_>) {
+        assert!(conn.remote().is_some());
+        conn.state().insert(Opened);
+        self.connects.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn on_disconnect(&self, conn: ConnectionInfo<'_>) {
+        assert!(conn.state().contains::<Opened>());
+        self.disconnects.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[rocket::get("/")]
+fn index(conn: ConnectionInfo<'_>) -> String {
+    let state = conn.state();
+    let served = state.get_or_insert_with(Served::default);
+    let n = served.0.fetch_add(1, Ordering::SeqCst) + 1;
+    format!("[{}:{n}]", state.contains::<Opened>())
+}
+
+#[test]
+fn local_requests_have_fresh_connection_state() {
+    let client = rocket::local::blocking::Client::debug_with(rocket::routes![index]).unwrap();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "[false:1]");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "[false:1]");
+}
+
+#[rocket::async_test]
+async fn connection_callbacks_and_state() {
+    let counts = Arc::new(Counts::default());
+    let rocket = rocket::custom(Config::debug_default())
+        .mount("/", rocket::routes![index])
+        .attach(counts.clone())
+        .ignite().await
+        .unwrap();
+
+    let bound = rocket.bound_addresses();
+    let shutdown = rocket.shutdown();
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let server = rocket::tokio::spawn(rocket.try_launch_on(TcpListener::bind(addr)));
+    let addr = bound.await[0].tcp().unwrap();
+
+    // Both requests arrive on the same connection and share its state.
+    let mut stream = rocket::tokio::net::TcpStream::connect(addr).await.unwrap();
+    let requests = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    stream.write_all(requests).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.contains("[true:1]"));
+    assert!(response.ends_with("[true:2]"));
+    assert_eq!(counts.connects.load(Ordering::SeqCst), 1);
+
+    // A new connection has new state.
+    let mut stream = rocket::tokio::net::TcpStream::connect(addr).await.unwrap();
+    let request = b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    stream.write_all(request).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.ends_with("[true:1]"));
+    assert_eq!(counts.connects.load(Ordering::SeqCst), 2);
+
+    // The close callback runs after the client sees the connection close.
+    for _ in 0..100 {
+        if counts.disconnects.load(Ordering::SeqCst) == 2 {
+            break;
+        }
+
+        rocket::tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(counts.disconnects.load(Ordering::SeqCst), 2);
+    shutdown.notify();
+    server.await.unwrap().unwrap();
+}
@@ -0,0 +1,39 @@
+#[macro_use] extern crate rocket;
+
+use rocket::listener::TlsInfo;
+use rocket::local::blocking::Client;
+use rocket::request::ConnectionInfo;
+
+#[get("/")]
+fn index(conn: ConnectionInfo<'_>) -> String {
+    let remote = conn.remote().and_then(|e| e.socket_addr());
+    let tls = conn.tls().map(|tls| (tls.version.as_deref(), tls.server_name.as_deref()));
+    format!("{:?} {:?} {} {:?} {:?}", remote, conn.local(), conn.is_tls(), conn.alpn(), tls)
+}
+
+#[test]
+fn connection_info_defaults() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "None None false None None");
+}
+
+#[test]
+fn connection_info_from_local_request() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let info = TlsInfo {
+        alpn: Some("h2".into()),
+        version: Some("TLSv1_3".into()),
+        cipher_suite: Some("TLS13_AES_128_GCM_SHA256".into()),
+        server_name: Some("rocket.rs".into()),
+    };
+
+    let response = client.get("/")
+        .remote("tcp:127.0.0.1:8000")
+        .tls_info(info)
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(),
+        "Some(127.0.0.1:8000) None true Some(\"h2\") \
+        Some((Some(\"TLSv1_3\"), Some(\"rocket.rs\")))");
+}
@@ -0,0 +1,91 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::{Request, Data};
+use rocket::data::ToByteUnit;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::tokio::io::AsyncReadExt;
+
+/// Prefixes the body with `self.0`.
+struct Prefix(&'static str);
+
+/// Truncates the body to the number of bytes in the `X-Truncate` header,
+/// which is set by its request callback.
+struct Truncate;
+
+#[rocket::async_trait]
+impl Fairing for Prefix {
+    fn info(&self) -> Info {
+        Info { name: "Prefix", kind: Kind::Data }
+    }
+
+    async fn on_data<'r>(&self, _: &Request<'_>, data: Data<'r>) -> Data<'r> {
+        let prefix = Cursor::new(self.0.as_bytes());
+        Data::from_reader(prefix.chain(data.open(1.kibibytes())))
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Truncate {
+    fn info(&self) -> Info {
+        Info { name: "Truncate", kind: Kind::Request | Kind::Data }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        req.add_header(Header::new("X-Truncate", "5"));
+    }
+
+    async fn on_data<'r>(&self, req: &Request<'_>, data: Data<'r>) -> Data<'r> {
+        let limit = req.headers().get_one("X-Truncate").unwrap().parse::<u64>().unwrap();
+        Data::from_reader(data.open(limit.bytes()))
+    }
+}
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[post("/short", data = "<data>")]
+async fn short(data: Data<'_>) -> String {
+    let body = data.open(4.bytes()).into_string().await.unwrap();
+    format!("{}:{}", body.value, body.is_complete())
+}
+
+#[test]
+fn data_fairings_run_in_attach_order() {
+    let rocket = rocket::build()
+        .mount("/", routes![echo])
+        .attach(Prefix("a:"))
+        .attach(Prefix("b:"));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/").body("hello").dispatch();
+    assert_eq!(response.into_string().unwrap(), "b:a:hello");
+}
+
+#[test]
+fn data_fairings_see_request_fairing_changes() {
+    let rocket = rocket::build()
+        .mount("/", routes![echo])
+        .attach(Truncate);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/").body("hello world").dispatch();
+    assert_eq!(response.into_string().unwrap(), "hello");
+}
+
+#[test]
+fn guard_limits_apply_to_transformed_data() {
+    let rocket = rocket::build()
+        .mount("/", routes![short])
+        .attach(Truncate)
+        .attach(Prefix("xy"));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/short").body("hello world").dispatch();
+    assert_eq!(response.into_string().unwrap(), "xyhe:false");
+}
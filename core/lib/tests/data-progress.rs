@@ -0,0 +1,51 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::State;
+use rocket::data::{Data, ToByteUnit, Progress, ProgressTracker};
+use rocket::local::blocking::Client;
+
+#[post("/progress", data = "<data>")]
+async fn progress(mut data: Data<'_>) -> String {
+    let updates: Arc<Mutex<Vec<Progress>>> = Arc::default();
+    let sink = updates.clone();
+    data.chain_progress(Some(11), move |p| sink.lock().unwrap().push(*p));
+    let body = data.open(1.kibibytes()).into_string().await.unwrap();
+    assert_eq!(body.value, "hello world");
+
+    let updates = updates.lock().unwrap();
+    let last = updates.last().unwrap();
+    assert!(last.is_complete());
+    assert_eq!(updates.iter().filter(|p| p.is_complete()).count(), 1);
+    assert!(updates.windows(2).all(|w| w[0].read() <= w[1].read()));
+    format!("{} {:?} {:?}", last.read(), last.total(), last.fraction())
+}
+
+#[post("/tracked/<id>", data = "<data>")]
+async fn tracked(id: &str, mut data: Data<'_>, tracker: &State<ProgressTracker>) -> String {
+    tracker.track(id, &mut data, None);
+    let mut updates = tracker.subscribe(id).unwrap();
+    assert_eq!(tracker.get(id).unwrap().read(), 0);
+
+    let _ = data.open(1.kibibytes()).into_bytes().await.unwrap();
+    let latest = *updates.borrow_and_update();
+    assert!(latest.is_complete());
+    assert!(tracker.subscribe(id).is_none());
+    assert!(updates.changed().await.is_err());
+    latest.read().to_string()
+}
+
+#[test]
+fn progress_is_reported() {
+    let rocket = rocket::build()
+        .manage(ProgressTracker::new())
+        .mount("/", routes![progress, tracked]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/progress").body("hello world").dispatch();
+    assert_eq!(response.into_string().unwrap(), "11 Some(11) Some(1.0)");
+
+    let response = client.post("/tracked/abc").body("hello world!").dispatch();
+    assert_eq!(response.into_string().unwrap(), "12");
+}
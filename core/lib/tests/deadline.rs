@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Config;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::Deadline;
+
+#[get("/remaining")]
+fn remaining(deadline: Deadline) -> String {
+    match deadline.remaining() {
+        Some(remaining) => remaining.as_secs_f64().ceil().to_string(),
+        None => "none".into(),
+    }
+}
+
+#[get("/checked")]
+fn checked(deadline: Deadline) -> Result<&'static str, Status> {
+    deadline.checked()?;
+    Ok("in time")
+}
+
+#[get("/slow")]
+async fn slow(deadline: Deadline) -> Result<&'static str, Status> {
+    deadline.run(rocket::tokio::time::sleep(Duration::from_secs(10))).await?;
+    Ok("finished")
+}
+
+fn client(request_timeout: u32) -> Client {
+    let config = Config {
+        request_timeout,
+        timeout_header: Some("X-Request-Timeout".into()),
+        ..Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config).mount("/", routes![remaining, checked, slow]);
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn deadline_from_config_and_header() {
+    let client = client(0);
+    let response = client.get("/remaining").dispatch();
+    assert_eq!(response.into_string().unwrap(), "none");
+
+    let response = client.get("/remaining").header(Header::new("X-Request-Timeout", "5S"));
+    assert_eq!(response.dispatch().into_string().unwrap(), "5");
+
+    let client = client(30);
+    let response = client.get("/remaining").dispatch();
+    assert_eq!(response.into_string().unwrap(), "30");
+
+    let response = client.get("/remaining").header(Header::new("X-Request-Timeout", "2.5"));
+    assert_eq!(response.dispatch().into_string().unwrap(), "3");
+
+    let response = client.get("/remaining").header(Header::new("X-Request-Timeout", "1H"));
+    assert_eq!(response.dispatch().into_string().unwrap(), "30");
+
+    let response = client.get("/remaining").header(Header::new("X-Request-Timeout", "bad"));
+    assert_eq!(response.dispatch().into_string().unwrap(), "30");
+}
+
+#[test]
+fn expired_deadline_is_gateway_timeout() {
+    let client = client(30);
+    let response = client.get("/checked").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/checked").header(Header::new("X-Request-Timeout", "0"));
+    assert_eq!(response.dispatch().status(), Status::GatewayTimeout);
+
+    let response = client.get("/slow").header(Header::new("X-Request-Timeout", "10m"));
+    assert_eq!(response.dispatch().status(), Status::GatewayTimeout);
+}
@@ -0,0 +1,62 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::catcher::ErrorReference;
+use rocket::http::{Accept, Status};
+use rocket::local::blocking::Client;
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::InternalServerError
+}
+
+#[get("/panic")]
+fn panic() -> &'static str {
+    panic!("oh no")
+}
+
+#[get("/unavailable")]
+fn unavailable_route() -> Status {
+    Status::ServiceUnavailable
+}
+
+#[catch(503)]
+fn unavailable(req: &Request<'_>) -> String {
+    req.error_reference().unwrap().to_string()
+}
+
+#[test]
+fn default_catcher_includes_reference() {
+    let client = Client::debug_with(routes![fail, panic]).unwrap();
+    for uri in ["/fail", "/panic"] {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+        let reference = response.headers().get_one(ErrorReference::HEADER).unwrap().to_string();
+        assert_eq!(reference.len(), 8);
+
+        let html = response.into_string().unwrap();
+        assert!(html.contains(&format!("<code>{reference}</code>")));
+    }
+
+    let response = client.get("/fail").header(Accept::JSON).dispatch();
+    let reference = response.headers().get_one(ErrorReference::HEADER).unwrap().to_string();
+    let json = response.into_string().unwrap();
+    assert!(json.contains(&format!("\"reference\": \"{reference}\"")));
+}
+
+#[test]
+fn custom_catchers_see_reference() {
+    let rocket = rocket::build()
+        .mount("/", routes![unavailable_route])
+        .register("/", catchers![unavailable]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/unavailable").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    let reference = response.headers().get_one(ErrorReference::HEADER).unwrap().to_string();
+    assert_eq!(response.into_string().unwrap(), reference);
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one(ErrorReference::HEADER).is_none());
+}
@@ -0,0 +1,68 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::IfMatch;
+use rocket::response::EtagVersioned;
+
+#[get("/")]
+fn read(revision: &State<AtomicU64>) -> EtagVersioned<String> {
+    let revision = revision.load(Ordering::SeqCst);
+    EtagVersioned::new(revision, format!("revision {revision}"))
+}
+
+#[put("/")]
+fn update(revision: &State<AtomicU64>, if_match: IfMatch<'_>) -> Result<(), Status> {
+    if_match.check(revision.load(Ordering::SeqCst))?;
+    revision.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(AtomicU64::new(1))
+        .mount("/", routes![read, update]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn optimistic_concurrency() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    let etag = response.headers().get_one("ETag").unwrap().to_owned();
+    assert_eq!(etag, "\"1\"");
+    assert_eq!(response.into_string().unwrap(), "revision 1");
+
+    let response = client.put("/").header(Header::new("If-Match", etag.clone())).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.put("/").header(Header::new("If-Match", etag.clone())).dispatch();
+    assert_eq!(response.status(), Status::PreconditionFailed);
+
+    let response = client.put("/").header(Header::new("If-Match", "W/\"2\"")).dispatch();
+    assert_eq!(response.status(), Status::PreconditionFailed);
+
+    let response = client.put("/").header(Header::new("If-Match", "\"1\", \"2\"")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.put("/").dispatch();
+    assert_eq!(response.status(), Status::PreconditionRequired);
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("ETag"), Some("\"3\""));
+}
+
+#[test]
+fn if_none_match_is_not_modified() {
+    let client = client();
+    let response = client.get("/").header(Header::new("If-None-Match", "\"1\"")).dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some("\"1\""));
+
+    let response = client.get("/").header(Header::new("If-None-Match", "\"0\"")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
@@ -0,0 +1,57 @@
+use rocket::figment::Figment;
+use rocket::fs::{Favicon, TextFile};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::Config;
+
+const DAY: &str = "public, max-age=86400";
+
+#[test]
+fn favicon_from_bytes_and_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let svg = dir.path().join("icon.svg");
+    std::fs::write(&svg, "<svg/>").unwrap();
+
+    let rocket = rocket::build()
+        .mount("/", Favicon::bytes([0u8, 0, 1, 0]))
+        .mount("/svg", Favicon::file(&svg).max_age(60))
+        .mount("/missing", Favicon::file(dir.path().join("missing.ico")));
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/favicon.ico").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Icon));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(DAY));
+    assert_eq!(response.into_bytes().unwrap(), [0, 0, 1, 0]);
+
+    let response = client.get("/svg/favicon.ico").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::SVG));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("public, max-age=60"));
+    assert_eq!(response.into_string().unwrap(), "<svg/>");
+
+    let response = client.get("/missing/favicon.ico").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn text_files_from_strings_and_config() {
+    let figment = Figment::from(Config::debug_default())
+        .merge(("security_txt", "Contact: mailto:security@example.com\n"));
+
+    let rocket = rocket::custom(figment)
+        .mount("/", TextFile::robots("User-agent: *\nDisallow:\n"))
+        .mount("/", TextFile::security_from_config())
+        .mount("/other", TextFile::robots_from_config());
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/robots.txt").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some(DAY));
+    assert_eq!(response.into_string().unwrap(), "User-agent: *\nDisallow:\n");
+
+    let response = client.get("/.well-known/security.txt").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.into_string().unwrap(), "Contact: mailto:security@example.com\n");
+
+    let response = client.get("/other/robots.txt").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
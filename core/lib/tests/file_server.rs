@@ -293,3 +293,15 @@ fn test_panic_on_missing_dir() {
 fn test_panic_on_file_not_dir() {
     let _ = Prefix::checked(static_root().join("index.html"));
 }
+
+#[rocket::async_test]
+async fn test_file_chunk_size() {
+    use rocket::fs::NamedFile;
+    use rocket::response::{Body, Responder};
+
+    let client = rocket::local::asynchronous::Client::debug_with(vec![]).await.unwrap();
+    let request = client.get("/");
+    let file = NamedFile::open(static_root().join("index.html")).await.unwrap();
+    let response = file.respond_to(request.inner()).unwrap();
+    assert_eq!(response.body().max_chunk_size(), Body::FILE_MAX_CHUNK);
+}
@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::request::{FlashMessage, FlashMessages};
+use rocket::response::{Flash, Redirect, Severity};
+
+#[post("/")]
+fn set() -> Flash<Redirect> {
+    Flash::success(Redirect::to(uri!(all)).with_status(Status::TemporaryRedirect), "Saved.")
+        .and(Severity::Warning, "Quota almost reached.")
+        .and("tip", "Try the new editor!")
+}
+
+#[get("/all")]
+fn all(flashes: FlashMessages<'_>) -> String {
+    flashes.iter()
+        .map(|f| format!("{:?}/{}/{}", f.severity(), f.kind(), f.message()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[get("/first")]
+fn first(flash: Option<FlashMessage<'_>>) -> Option<String> {
+    flash.map(|f| f.message().into())
+}
+
+#[get("/search")]
+fn search() -> Redirect {
+    Redirect::to(uri!(all)).with_query("q", "a&b c").with_query("page", 2)
+}
+
+#[get("/broken")]
+fn broken() -> Redirect {
+    Redirect::to("/").with_status(Status::Ok)
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![set, all, first, search, broken]).unwrap()
+}
+
+#[test]
+fn multiple_typed_messages() {
+    let client = client();
+    let response = client.post("/").dispatch();
+    assert_eq!(response.status(), Status::TemporaryRedirect);
+
+    let response = client.get("/all").dispatch();
+    assert_eq!(response.into_string().unwrap(),
+        "Some(Success)/success/Saved.,\
+        Some(Warning)/warning/Quota almost reached.,\
+        None/tip/Try the new editor!");
+
+    // Accessing the messages cleared them.
+    assert_eq!(client.get("/all").dispatch().into_string().unwrap(), "");
+}
+
+#[test]
+fn single_message_guard_yields_first() {
+    let client = client();
+    client.post("/").dispatch();
+    assert_eq!(client.get("/first").dispatch().into_string().unwrap(), "Saved.");
+    assert_eq!(client.get("/first").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn redirect_query_and_status() {
+    let client = client();
+    let response = client.get("/search").dispatch();
+    assert_eq!(response.status(), Status::SeeOther);
+    assert_eq!(response.headers().get_one("Location"), Some("/all?q=a%26b%20c&page=2"));
+
+    let response = client.get("/broken").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+}
@@ -0,0 +1,106 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::{self, Form, Contextual};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::request::Request;
+
+struct Taken(Vec<&'static str>);
+
+async fn available<'v>(name: &str, req: &Request<'_>) -> form::Result<'v, ()> {
+    let taken = req.rocket().state::<Taken>().expect("managed state");
+    if taken.0.contains(&name) {
+        Err(form::Error::validation("name is taken"))?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromForm)]
+struct Team<'r> {
+    #[field(validate = available().await)]
+    name: &'r str,
+}
+
+#[derive(FromForm)]
+struct Signup<'r> {
+    #[field(validate = len(3..))]
+    #[field(validate = available().await)]
+    username: &'r str,
+    team: Option<Team<'r>>,
+    invite: Team<'r>,
+}
+
+#[post("/", data = "<form>")]
+fn signup(form: Form<Signup<'_>>) -> String {
+    format!("{} {}", form.username, form.invite.name)
+}
+
+#[post("/contextual", data = "<form>")]
+fn contextual(form: Form<Contextual<'_, Signup<'_>>>) -> String {
+    let errors = form.context.errors()
+        .map(|e| e.name.as_ref().map(|n| n.to_string()).unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    format!("{} {:?}", form.value.is_some(), errors)
+}
+
+#[get("/?<team>")]
+fn query(team: Team<'_>) -> &str {
+    team.name
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(Taken(vec!["bob", "rockets"]))
+        .mount("/", routes![signup, contextual, query]);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn post(client: &Client, uri: &str, body: &str) -> (Status, Option<String>) {
+    let response = client.post(uri).header(ContentType::Form).body(body).dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn async_validators_run() {
+    let client = client();
+    let (status, body) = post(&client, "/", "username=alice&invite.name=crabs");
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body.unwrap(), "alice crabs");
+
+    let (status, _) = post(&client, "/", "username=bob&invite.name=crabs");
+    assert_eq!(status, Status::UnprocessableEntity);
+
+    let (status, _) = post(&client, "/", "username=alice&invite.name=rockets");
+    assert_eq!(status, Status::UnprocessableEntity);
+}
+
+#[test]
+fn async_validators_run_after_sync_validators() {
+    let client = client();
+    let (_, body) = post(&client, "/contextual", "username=al&invite.name=crabs");
+    assert_eq!(body.unwrap(), r#"false ["username"]"#);
+}
+
+#[test]
+fn async_errors_are_attributed() {
+    let client = client();
+    let (_, body) = post(&client, "/contextual", "username=bob&invite.name=rockets");
+    assert_eq!(body.unwrap(), r#"false ["invite.name", "username"]"#);
+
+    // Optional nested forms that fail validation are `None`.
+    let (_, body) = post(&client, "/contextual", "username=alice&team.name=bob&invite.name=a");
+    assert_eq!(body.unwrap(), "true []");
+}
+
+#[test]
+fn async_validators_run_for_query_guards() {
+    let client = client();
+    let response = client.get("/?team.name=crabs").dispatch();
+    assert_eq!(response.into_string().unwrap(), "crabs");
+
+    let response = client.get("/?team.name=rockets").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
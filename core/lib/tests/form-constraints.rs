@@ -0,0 +1,87 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::{FormConstraints, FieldConstraints};
+use rocket::fs::TempFile;
+use rocket::http::ContentType;
+use rocket::data::ToByteUnit;
+
+#[derive(FromForm)]
+struct Profile<'r> {
+    #[field(validate = len(3..=16).or_else(msg!("bad name")))]
+    name: &'r str,
+    #[field(name = "years", validate = range(18..130))]
+    age: u8,
+    #[field(validate = len(..10))]
+    bio: Option<String>,
+    #[field(default = 5)]
+    rating: u8,
+    #[field(validate = ext(ContentType::PNG))]
+    #[field(validate = len(..=2.mebibytes()))]
+    avatar: TempFile<'r>,
+    #[field(validate = eq(true))]
+    accept: bool,
+}
+
+#[derive(FromForm)]
+struct Generic<T> {
+    value: T,
+    #[field(validate = range(1..))]
+    count: usize,
+}
+
+#[test]
+fn test_derived_constraints() {
+    let constraints = Profile::constraints();
+    let names = constraints.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "years", "bio", "rating", "avatar", "accept"]);
+
+    assert_eq!(constraints.get("name").unwrap(), &FieldConstraints {
+        required: true,
+        min_length: Some(3),
+        max_length: Some(16),
+        ..Default::default()
+    });
+
+    assert_eq!(constraints.get("years").unwrap(), &FieldConstraints {
+        required: true,
+        min: Some(18),
+        max: Some(129),
+        ..Default::default()
+    });
+
+    assert_eq!(constraints.get("bio").unwrap(), &FieldConstraints {
+        max_length: Some(9),
+        ..Default::default()
+    });
+
+    assert_eq!(constraints.get("rating").unwrap(), &FieldConstraints::default());
+    assert_eq!(constraints.get("avatar").unwrap(), &FieldConstraints {
+        required: true,
+        max_length: Some(2 * 1024 * 1024),
+        accept: Some("image/png".into()),
+        ..Default::default()
+    });
+
+    assert_eq!(constraints.get("accept").unwrap(), &FieldConstraints::default());
+}
+
+#[test]
+fn test_generic_constraints() {
+    // Generic fields are never marked required.
+    let constraints = Generic::<usize>::constraints();
+    assert_eq!(constraints.get("value").unwrap(), &FieldConstraints::default());
+    assert_eq!(constraints.get("count").unwrap(), &FieldConstraints {
+        required: true,
+        min: Some(1),
+        ..Default::default()
+    });
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_constraints_serialize() {
+    let value = rocket::serde::json::to_value(Profile::constraints()).unwrap();
+    assert_eq!(value["name"]["min_length"], 3);
+    assert_eq!(value["years"]["required"], true);
+    assert_eq!(value["bio"]["min_length"], rocket::serde::json::Value::Null);
+}
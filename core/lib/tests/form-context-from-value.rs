@@ -0,0 +1,100 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::BTreeMap;
+
+use rocket::form::{Context, Contextual, Form, FromFormField};
+use rocket::serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize, FromFormField)]
+#[serde(crate = "rocket::serde")]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Debug, PartialEq, Serialize, FromForm)]
+#[serde(crate = "rocket::serde")]
+struct Address {
+    street: String,
+    zip: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, FromForm)]
+#[serde(crate = "rocket::serde")]
+struct User {
+    name: String,
+    nickname: Option<String>,
+    admin: bool,
+    role: Role,
+    tags: Vec<String>,
+    addresses: Vec<Address>,
+    scores: BTreeMap<String, u8>,
+}
+
+fn user() -> User {
+    User {
+        name: "Bob".into(),
+        nickname: None,
+        admin: true,
+        role: Role::Member,
+        tags: vec!["a".into(), "b".into()],
+        addresses: vec![
+            Address { street: "Main St".into(), zip: 12345 },
+            Address { street: "Elm St".into(), zip: 54321 },
+        ],
+        scores: [("math".to_string(), 9)].into_iter().collect(),
+    }
+}
+
+#[test]
+fn from_value_flattens_fields() {
+    let context = Context::from_value(&user()).unwrap();
+    assert_eq!(context.field_value("name"), Some("Bob"));
+    assert_eq!(context.field_value("nickname"), None);
+    assert_eq!(context.field_value("admin"), Some("true"));
+    assert_eq!(context.field_value("role"), Some("Member"));
+    assert_eq!(context.field_values("tags").collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(context.field_value("addresses[0].street"), Some("Main St"));
+    assert_eq!(context.field_value("addresses.1.zip"), Some("54321"));
+    assert_eq!(context.field_value("scores.math"), Some("9"));
+    assert_eq!(context.errors().count(), 0);
+}
+
+#[test]
+fn from_value_round_trips() {
+    let context = Context::from_value(&user()).unwrap();
+    let form = context.fields()
+        .flat_map(|name| context.field_values(name).map(move |v| format!("{}={}", name, v)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    assert_eq!(Form::<User>::parse(&form).unwrap(), user());
+}
+
+#[test]
+fn from_value_serializes_like_submitted_context() {
+    let submitted = "name=Bob&tags=a&tags=b";
+    let submitted = Form::<Contextual<'_, User>>::parse(submitted).unwrap();
+    let from_value = Context::from_value(&user()).unwrap();
+
+    let values = |context: &Context<'_>| context.field_values("tags")
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(values(&submitted.context), values(&from_value));
+}
+
+#[test]
+fn from_value_rejects_non_forms() {
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    enum Shape { Circle(u8) }
+
+    #[derive(Serialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Drawing { shape: Shape }
+
+    assert!(Context::from_value(&"just a string").is_err());
+    assert!(Context::from_value(&[1, 2, 3]).is_err());
+    assert!(Context::from_value(&Drawing { shape: Shape::Circle(1) }).is_err());
+}
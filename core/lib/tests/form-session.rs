@@ -0,0 +1,85 @@
+#![cfg(feature = "secrets")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::form::{FormSession, Steps};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[derive(Debug, FromForm)]
+struct Checkout {
+    #[field(step = 1, validate = contains('@'))]
+    email: String,
+    #[field(step = 2, validate = len(1..))]
+    address: String,
+    #[field(step = 3, validate = eq(true))]
+    accept: bool,
+}
+
+#[post("/", data = "<session>")]
+fn submit(session: FormSession<'_, Checkout>) -> String {
+    match session.next_step() {
+        Some(next) => format!("next: {}", next),
+        None => {
+            let checkout = session.finish().expect("complete form");
+            format!("done: {} {} {}", checkout.email, checkout.address, checkout.accept)
+        }
+    }
+}
+
+#[get("/")]
+fn state(session: FormSession<'_, Checkout>) -> String {
+    let email = session.value("email").map(|v| v.into_owned()).unwrap_or_default();
+    format!("{} {}", session.completed(), email)
+}
+
+fn client() -> Client {
+    Client::tracked(rocket::build().mount("/", routes![submit, state])).unwrap()
+}
+
+fn post(client: &Client, body: &str) -> (Status, String) {
+    let response = client.post("/").header(ContentType::Form).body(body).dispatch();
+    (response.status(), response.into_string().unwrap_or_default())
+}
+
+#[test]
+fn test_derived_steps() {
+    assert_eq!(Checkout::NAME, "Checkout");
+    assert_eq!(Checkout::LAST, 3);
+    assert_eq!(Checkout::step("email"), Some(1));
+    assert_eq!(Checkout::step("accept"), Some(3));
+    assert_eq!(Checkout::step("unknown"), None);
+}
+
+#[test]
+fn test_steps_accumulate() {
+    let client = client();
+    assert_eq!(post(&client, "_step=1&email=bob%40example.com").1, "next: 2");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "1 bob@example.com");
+
+    assert_eq!(post(&client, "_step=2&address=Main+St").1, "next: 3");
+    assert_eq!(post(&client, "_step=3&accept=on").1, "done: bob@example.com Main St true");
+
+    // Finishing the form removes the session.
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "0 ");
+}
+
+#[test]
+fn test_step_validation() {
+    let client = client();
+    assert_eq!(post(&client, "_step=1&email=bob").0, Status::UnprocessableEntity);
+    assert_eq!(post(&client, "email=bob%40example.com").0, Status::UnprocessableEntity);
+    assert_eq!(post(&client, "_step=4&email=bob%40example.com").0, Status::UnprocessableEntity);
+
+    // Later steps can't be completed before earlier ones.
+    assert_eq!(post(&client, "_step=2&address=Main+St").0, Status::UnprocessableEntity);
+
+    // Fields of other steps are ignored.
+    assert_eq!(post(&client, "_step=1&email=a%40b.c&address=Elm+St").1, "next: 2");
+    assert_eq!(post(&client, "_step=2&address=").0, Status::UnprocessableEntity);
+    assert_eq!(post(&client, "_step=2&address=Main+St").1, "next: 3");
+
+    // Resubmitting a step replaces its fields.
+    assert_eq!(post(&client, "_step=1&email=c%40d.e").1, "next: 3");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "2 c@d.e");
+}
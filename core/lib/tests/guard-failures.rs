@@ -0,0 +1,139 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Config, Catcher};
+use rocket::figment::{Figment, Profile};
+use rocket::http::{Accept, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct ApiKey;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ApiKey {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("X-Api-Key") {
+            Some(_) => request::Outcome::Success(ApiKey),
+            None => {
+                req.explain_failure("missing `X-Api-Key` header");
+                request::Outcome::Forward(Status::Unauthorized)
+            }
+        }
+    }
+}
+
+/// Explains a failure but then succeeds anyway.
+struct Fickle;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Fickle {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.explain_failure("never mind");
+        request::Outcome::Success(Fickle)
+    }
+}
+
+/// Always forwards without an explanation.
+struct Never;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Never {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Forward(Status::NotFound)
+    }
+}
+
+#[get("/user/<id>")]
+fn user(id: usize) -> String {
+    id.to_string()
+}
+
+#[get("/key")]
+fn key(_key: ApiKey) { }
+
+#[get("/search?<page>")]
+fn search(page: u8) -> String {
+    page.to_string()
+}
+
+#[get("/fickle")]
+fn fickle(_fickle: Fickle, _never: Never) { }
+
+#[catch(default)]
+fn failures(req: &Request<'_>) -> String {
+    req.guard_failures().iter().map(|f| format!("{f}\n")).collect()
+}
+
+fn client(profile: Profile, catchers: Vec<Catcher>) -> Client {
+    // A secret key is required outside of debug with the `secrets` feature.
+    let figment = Figment::from(Config { profile, ..Config::debug_default() })
+        .merge(("secret_key", "99Wii7x0JHEwBLaqMHxS2P5geR0He5o5UIiUt7jREKM="));
+
+    let rocket = rocket::custom(figment)
+        .mount("/", routes![user, key, search, fickle])
+        .register("/", catchers);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn records_guard_failures() {
+    let client = client(Config::DEBUG_PROFILE, catchers![failures]);
+    let response = client.get("/user/bob").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.into_string().unwrap(), "path parameter `id: usize` of route `user` \
+        forwarded with 422 Unprocessable Entity: invalid digit found in string\n");
+
+    let response = client.get("/search?page=1000").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert!(response.into_string().unwrap()
+        .starts_with("query parameter `page: u8` of route `search` forwarded with 422"));
+}
+
+#[test]
+fn custom_guards_explain_failures() {
+    let client = client(Config::DEBUG_PROFILE, catchers![failures]);
+    let response = client.get("/key").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().unwrap(), "request guard `_key: ApiKey` of route `key` \
+        forwarded with 401 Unauthorized: missing `X-Api-Key` header\n");
+
+    let response = client.get("/key").header(Header::new("X-Api-Key", "k")).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // The explanation of a guard that succeeds is discarded.
+    let response = client.get("/fickle").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.into_string().unwrap(), "request guard `_never: Never` of route \
+        `fickle` forwarded with 404 Not Found\n");
+}
+
+#[test]
+fn default_catcher_lists_failures() {
+    let client = client(Config::DEBUG_PROFILE, vec![]);
+    let html = client.get("/user/bob").dispatch().into_string().unwrap();
+    assert!(html.contains("<li>path parameter"));
+    assert!(html.contains("invalid digit found in string</li>"));
+
+    let json = client.get("/key").header(Accept::JSON).dispatch().into_string().unwrap();
+    assert!(json.contains(r#""failures": ["#));
+    assert!(json.contains(r#""kind": "request", "parameter": "_key", "type": "ApiKey""#));
+    assert!(json.contains(r#""reason": "missing `X-Api-Key` header""#));
+}
+
+#[test]
+fn failures_are_only_recorded_in_debug() {
+    let client = client(Config::RELEASE_PROFILE, catchers![failures]);
+    let response = client.get("/key").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().unwrap(), "");
+
+    let client = client(Config::RELEASE_PROFILE, vec![]);
+    let html = client.get("/user/bob").dispatch().into_string().unwrap();
+    assert!(!html.contains("<li>"));
+}
@@ -1,7 +1,9 @@
 #[macro_use] extern crate rocket;
 
-use rocket::http::Status;
+use rocket::{Request, Data};
+use rocket::http::{Header, Status};
 use rocket::response::content::RawJson;
+use rocket::route::{Handler, Outcome};
 
 #[get("/empty")]
 fn empty() -> Status {
@@ -18,15 +20,39 @@ fn other() -> RawJson<&'static str> {
     RawJson("{ 'hi': 'hello' }")
 }
 
+#[get("/lazy")]
+fn lazy(req: &Request<'_>) -> String {
+    match req.is_auto_head() {
+        true => String::new(),
+        false => "expensive".into(),
+    }
+}
+
+#[derive(Clone)]
+struct Report;
+
+#[rocket::async_trait]
+impl Handler for Report {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, "full report")
+    }
+
+    async fn handle_head<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        Outcome::from(req, (Header::new("X-Cheap", "1"), "full report"))
+    }
+}
+
 mod head_handling_tests {
     use super::*;
 
     use rocket::Route;
     use rocket::local::blocking::Client;
-    use rocket::http::ContentType;
+    use rocket::http::{ContentType, Method};
 
     fn routes() -> Vec<Route> {
-        routes![index, empty, other]
+        let mut routes = routes![index, empty, other, lazy];
+        routes.push(Route::new(Method::Get, "/report", Report));
+        routes
     }
 
     #[test]
@@ -56,4 +82,22 @@ mod head_handling_tests {
         assert_eq!(response.body().preset_size(), Some(17));
         assert!(response.into_bytes().unwrap().is_empty());
     }
+
+    #[test]
+    fn auto_head_hooks() {
+        let client = Client::debug_with(routes()).unwrap();
+        let response = client.head("/lazy").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body().preset_size(), Some(0));
+
+        let response = client.get("/lazy").dispatch();
+        assert_eq!(response.into_string().unwrap(), "expensive");
+
+        let response = client.head("/report").dispatch();
+        assert_eq!(response.headers().get_one("X-Cheap"), Some("1"));
+
+        let response = client.get("/report").dispatch();
+        assert!(response.headers().get_one("X-Cheap").is_none());
+        assert_eq!(response.into_string().unwrap(), "full report");
+    }
 }
@@ -0,0 +1,109 @@
+#![cfg(feature = "json")]
+
+use std::time::Duration;
+
+use rocket::http::Status;
+use rocket::health::Health;
+use rocket::local::blocking::Client;
+use rocket::serde::json::Value;
+
+struct Flaky;
+
+fn client() -> Client {
+    let health = Health::new()
+        .liveness("alive", Duration::from_secs(1), |_| Box::pin(async {
+            Ok::<_, String>(())
+        }))
+        .readiness("flaky", Duration::from_secs(1), |rocket| Box::pin(async move {
+            match rocket.state::<Flaky>() {
+                Some(_) => Err("flaky is flaking"),
+                None => Ok(()),
+            }
+        }))
+        .readiness("slow", Duration::from_millis(10), |_| Box::pin(async {
+            rocket::tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok::<_, String>(())
+        }));
+
+    let rocket = rocket::build()
+        .manage(Flaky)
+        .mount("/", health);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn liveness_runs_only_liveness_checks() {
+    let client = client();
+    let response = client.get("/healthz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let value = response.into_json::<Value>().unwrap();
+    assert_eq!(value["status"], "up");
+    assert_eq!(value["checks"].as_array().unwrap().len(), 1);
+    assert_eq!(value["checks"][0]["name"], "alive");
+    assert_eq!(value["checks"][0]["status"], "up");
+    assert!(value["checks"][0]["latency_ms"].is_f64());
+}
+
+#[test]
+fn readiness_reports_failures_and_timeouts() {
+    let client = client();
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+
+    let value = response.into_json::<Value>().unwrap();
+    assert_eq!(value["status"], "down");
+
+    let checks = value["checks"].as_array().unwrap();
+    let names: Vec<_> = checks.iter().map(|c| c["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["alive", "flaky", "slow"]);
+    assert_eq!(checks[0]["status"], "up");
+    assert_eq!(checks[1]["status"], "down");
+    assert_eq!(checks[1]["error"], "flaky is flaking");
+    assert_eq!(checks[2]["status"], "down");
+    assert!(checks[2]["error"].as_str().unwrap().contains("timed out"));
+}
+
+#[test]
+fn readiness_fails_during_shutdown() {
+    let rocket = rocket::build().mount("/", Health::new());
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/readyz").dispatch().status(), Status::Ok);
+
+    client.rocket().shutdown().notify();
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.into_json::<Value>().unwrap()["status"], "draining");
+    assert_eq!(client.get("/healthz").dispatch().status(), Status::Ok);
+}
+
+#[test]
+fn drain_endpoint_reports_shutdown_progress() {
+    let rocket = rocket::build().mount("/", Health::new().drain_endpoint());
+    let client = Client::debug(rocket).unwrap();
+
+    let value = client.get("/drainz").dispatch().into_json::<Value>().unwrap();
+    assert_eq!(value["stage"], "running");
+    assert!(value["elapsed_ms"].is_null());
+
+    client.rocket().shutdown().notify();
+    let response = client.get("/drainz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let value = response.into_json::<Value>().unwrap();
+    assert_eq!(value["stage"], "stopping");
+    assert!(value["elapsed_ms"].is_f64());
+    assert!(value["requests"].is_u64());
+    assert!(value["connections"].is_u64());
+
+    let value = client.get("/readyz").dispatch().into_json::<Value>().unwrap();
+    assert_eq!(value["shutdown"]["stage"], "stopping");
+}
+
+#[test]
+fn drain_endpoint_is_opt_in() {
+    let rocket = rocket::build().mount("/", Health::new());
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/drainz").dispatch().status(), Status::NotFound);
+}
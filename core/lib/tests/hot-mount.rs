@@ -0,0 +1,62 @@
+#[macro_use] extern crate rocket;
+
+use rocket::local::blocking::Client;
+use rocket::http::Status;
+use rocket::error::ErrorKind;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/hello")]
+fn hello() -> &'static str { "hello" }
+
+#[get("/<name>", rank = 1)]
+fn name(name: &str) -> String { format!("name: {name}") }
+
+#[test]
+fn hot_mounted_routes_are_routed() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let status = |uri: &str| client.get(uri.to_string()).dispatch().status();
+    assert_eq!(status("/a/hello"), Status::NotFound);
+
+    let a = client.rocket().hot_mount("/a", routes![hello]).unwrap();
+    let b = client.rocket().hot_mount("/b?ignored", routes![hello]).unwrap();
+    assert_eq!(client.get("/a/hello").dispatch().into_string().unwrap(), "hello");
+    assert_eq!(client.get("/b/hello").dispatch().into_string().unwrap(), "hello");
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "index");
+    assert_eq!(client.rocket().active_routes().len(), 3);
+    assert_eq!(client.rocket().routes().count(), 1);
+
+    assert!(a.unmount(client.rocket()));
+    assert_eq!(status("/a/hello"), Status::NotFound);
+    assert_eq!(status("/b/hello"), Status::Ok);
+    assert_eq!(client.rocket().active_routes().len(), 2);
+
+    assert!(b.unmount(client.rocket()));
+    assert_eq!(status("/b/hello"), Status::NotFound);
+    assert_eq!(status("/"), Status::Ok);
+}
+
+#[test]
+fn hot_mount_collisions_are_rejected() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let error = client.rocket().hot_mount("/", routes![index]).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Collisions { .. }));
+
+    let mount = client.rocket().hot_mount("/", routes![name]).unwrap();
+    assert!(client.rocket().hot_mount("/", routes![name]).is_err());
+    assert_eq!(client.get("/bob").dispatch().into_string().unwrap(), "name: bob");
+
+    assert!(mount.unmount(client.rocket()));
+    assert_eq!(client.get("/bob").dispatch().status(), Status::NotFound);
+    assert!(client.rocket().hot_mount("/", routes![name]).is_ok());
+}
+
+#[test]
+fn hot_mounts_are_per_instance() {
+    let one = Client::debug_with(vec![]).unwrap();
+    let two = Client::debug_with(vec![]).unwrap();
+    let mount = one.rocket().hot_mount("/", routes![hello]).unwrap();
+    assert_eq!(two.get("/hello").dispatch().status(), Status::NotFound);
+    assert!(!mount.unmount(two.rocket()));
+}
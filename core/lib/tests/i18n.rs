@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::form::{Form, Contextual};
+use rocket::http::{Accept, ContentType, Header, Status};
+use rocket::i18n::{Catalog, Locale, Translations};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Signup<'r> {
+    #[field(validate = len(3..))]
+    #[allow(dead_code)]
+    name: &'r str,
+}
+
+#[post("/", data = "<form>")]
+fn signup(form: Form<Contextual<'_, Signup<'_>>>, locale: Locale<'_>) -> String {
+    form.context.errors()
+        .map(|e| locale.form_error(e))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[get("/locale")]
+fn locale(locale: Locale<'_>) -> String {
+    locale.tag().into()
+}
+
+fn rocket() -> Rocket<Build> {
+    let german = Catalog::new("de")
+        .status(404, "Nicht gefunden", "Die <Ressource> existiert nicht.")
+        .message("status.unknown.reason", "Unbekannter Fehler")
+        .message("form.length.min", "mindestens {min} Zeichen erwartet");
+
+    rocket::build()
+        .mount("/", routes![signup, locale])
+        .attach(Translations::new().add(german))
+}
+
+#[test]
+fn catcher_pages_are_translated() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/missing")
+        .header(Header::new("Accept-Language", "de-DE, en;q=0.5"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotFound);
+    let body = response.into_string().unwrap();
+    assert!(body.contains(r#"<html lang="de">"#));
+    assert!(body.contains("404: Nicht gefunden"));
+    assert!(body.contains("Die &lt;Ressource&gt; existiert nicht."));
+
+    let response = client.get("/missing")
+        .header(Header::new("Accept-Language", "de"))
+        .header(Accept::JSON)
+        .dispatch();
+
+    let body = response.into_string().unwrap();
+    assert!(body.contains(r#""reason": "Nicht gefunden""#));
+}
+
+#[test]
+fn untranslated_messages_fall_back_to_english() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/missing")
+        .header(Header::new("Accept-Language", "fr, en;q=0.5"))
+        .dispatch();
+
+    let body = response.into_string().unwrap();
+    assert!(body.contains(r#"<html lang="en">"#));
+    assert!(body.contains("404: Not Found"));
+
+    let response = client.post("/")
+        .header(ContentType::Form)
+        .body("name=x")
+        .header(Header::new("Accept-Language", "de"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "mindestens 3 Zeichen erwartet");
+
+    let response = client.post("/").header(ContentType::Form).body("name=x").dispatch();
+    assert_eq!(response.into_string().unwrap(), "expected at least 3");
+}
+
+#[test]
+fn default_locale_is_configurable() {
+    let client = Client::debug(rocket()).unwrap();
+    assert_eq!(client.get("/locale").dispatch().into_string().unwrap(), "en");
+
+    let rocket = rocket().reconfigure(rocket::Config::figment().merge(("locale", "de")));
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/locale").dispatch().into_string().unwrap(), "de");
+
+    let response = client.get("/missing").dispatch();
+    assert!(response.into_string().unwrap().contains("404: Nicht gefunden"));
+
+    let response = client.get("/locale")
+        .header(Header::new("Accept-Language", "en"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "en");
+}
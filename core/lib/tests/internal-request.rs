@@ -0,0 +1,160 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Config, Data};
+use rocket::config::LoadConfig;
+use rocket::fairing::AdHoc;
+use rocket::http::{ContentType, Header, Method, Status, uri::Origin};
+use rocket::local::blocking::Client;
+use rocket::request::{FromRequest, InternalResponse, Outcome};
+
+struct Token<'r>(&'r str);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Token<'r> {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, ()> {
+        match req.headers().get_one("Authorization") {
+            Some(token) => Outcome::Success(Token(token)),
+            None => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[get("/secret")]
+fn secret(token: Token<'_>, req: &Request<'_>) -> String {
+    format!("{} internal={}", token.0, req.is_internal())
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: &str) -> String {
+    body.to_string()
+}
+
+#[get("/proxy?<inherit>&<uri>")]
+async fn proxy(inherit: bool, uri: &str, req: &Request<'_>) -> InternalResponse {
+    let internal = req.internal(Method::Get, Origin::parse(uri).unwrap());
+    match inherit {
+        true => internal.inherit_headers().dispatch().await,
+        false => internal.dispatch().await,
+    }
+}
+
+#[post("/proxy/echo", data = "<body>")]
+async fn proxy_echo(body: String, req: &Request<'_>) -> InternalResponse {
+    req.internal(Method::Post, uri!(echo))
+        .header(ContentType::Text)
+        .body(body.to_uppercase())
+        .dispatch()
+        .await
+}
+
+#[put("/v2/<name>", format = "json", data = "<body>")]
+fn v2(name: &str, body: &str) -> String {
+    format!("v2 {name}: {body}")
+}
+
+#[put("/v1/<name>", data = "<data>")]
+async fn v1(name: &str, req: &Request<'_>, data: Data<'_>) -> InternalResponse {
+    req.forward_to(uri!(v2(name))).data(data).dispatch().await
+}
+
+#[put("/v1/<name>/shout", data = "<body>")]
+async fn v1_shout(name: &str, body: &str, req: &Request<'_>) -> InternalResponse {
+    req.forward_to(uri!(v2(name))).body(body.to_uppercase()).dispatch().await
+}
+
+#[put("/v1/<name>/text", data = "<data>")]
+async fn v1_text(name: &str, req: &Request<'_>, data: Data<'_>) -> InternalResponse {
+    req.forward_to(uri!(v2(name))).replace_header(ContentType::JSON).data(data).dispatch().await
+}
+
+#[get("/loop")]
+async fn looping(req: &Request<'_>) -> InternalResponse {
+    let response = req.internal(Method::Get, uri!(looping)).dispatch().await;
+    assert!(response.status() == Status::LoopDetected || response.status() == Status::Ok);
+    response
+}
+
+fn client(config: Config) -> Client {
+    let rocket = rocket::custom(config)
+        .mount("/", routes![secret, echo, proxy, proxy_echo, v1, v1_shout, v1_text, v2, looping])
+        .attach(AdHoc::on_response("Internal", |req, res| Box::pin(async move {
+            if req.is_internal() {
+                res.set_header(Header::new("X-Internal", "1"));
+            }
+        })));
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn internal_requests_respect_guards_and_fairings() {
+    let client = client(Config::debug_default());
+    let response = client.get("/proxy?inherit=true&uri=/secret")
+        .header(Header::new("Authorization", "abc"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("X-Internal"), Some("1"));
+    assert_eq!(response.into_string().unwrap(), "abc internal=true");
+
+    let response = client.get("/proxy?inherit=false&uri=/secret")
+        .header(Header::new("Authorization", "abc"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.get("/proxy?inherit=false&uri=/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/secret").header(Header::new("Authorization", "x")).dispatch();
+    assert!(response.headers().get_one("X-Internal").is_none());
+    assert_eq!(response.into_string().unwrap(), "x internal=false");
+}
+
+#[test]
+fn internal_requests_carry_bodies() {
+    let client = client(Config::debug_default());
+    let response = client.post("/proxy/echo").body("hello").dispatch();
+    assert_eq!(response.into_string().unwrap(), "HELLO");
+}
+
+#[test]
+fn recursive_internal_requests_are_bounded() {
+    let client = client(Config::debug_default());
+    let response = client.get("/loop").dispatch();
+    assert_eq!(response.status(), Status::LoopDetected);
+}
+
+#[test]
+fn internal_requests_bypass_load_limits() {
+    let config = Config {
+        load: LoadConfig { max_requests: 1, queue: 0, ..Default::default() },
+        ..Config::debug_default()
+    };
+
+    let client = client(config);
+    let response = client.post("/proxy/echo").body("limited").dispatch();
+    assert_eq!(response.into_string().unwrap(), "LIMITED");
+}
+
+#[test]
+fn forward_to_preserves_method_headers_and_data() {
+    let client = client(Config::debug_default());
+    let response = client.put("/v1/bob").header(ContentType::JSON).body("{}").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("X-Internal"), Some("1"));
+    assert_eq!(response.into_string().unwrap(), "v2 bob: {}");
+
+    let response = client.put("/v1/bob/shout").header(ContentType::JSON).body("hi").dispatch();
+    assert_eq!(response.into_string().unwrap(), "v2 bob: HI");
+
+    // The `Content-Type` is forwarded, so the format mismatch forwards to a 404.
+    let response = client.put("/v1/bob").header(ContentType::Text).body("{}").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Unless it's replaced.
+    let response = client.put("/v1/bob/text").header(ContentType::Text).body("{}").dispatch();
+    assert_eq!(response.into_string().unwrap(), "v2 bob: {}");
+}
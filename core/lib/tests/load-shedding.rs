@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::config::{Config, LoadConfig};
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use rocket::tokio::{join, task::yield_now, time::sleep};
+
+#[get("/sleep/<ms>")]
+async fn slow(ms: u64) -> &'static str {
+    sleep(Duration::from_millis(ms)).await;
+    "done"
+}
+
+#[get("/fast")]
+fn fast() -> &'static str {
+    "done"
+}
+
+async fn client(load: LoadConfig) -> Client {
+    let config = Config { load, ..Config::debug_default() };
+    let rocket = rocket::custom(config).mount("/", routes![slow, fast]);
+    Client::untracked(rocket).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn requests_over_limit_are_shed() {
+    let client = client(LoadConfig { max_requests: 1, ..Default::default() }).await;
+    let first = client.get("/sleep/100").dispatch();
+    let second = async {
+        yield_now().await;
+        client.get("/fast").dispatch().await
+    };
+
+    let (first, second) = join!(first, second);
+    assert_eq!(first.status(), Status::Ok);
+    assert_eq!(second.status(), Status::ServiceUnavailable);
+    assert_eq!(second.headers().get_one("Retry-After"), Some("1"));
+
+    let load = client.rocket().load();
+    assert_eq!((load.requests, load.queued, load.shed), (0, 0, 1));
+
+    // With capacity available again, requests are dispatched.
+    let response = client.get("/fast").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[rocket::async_test]
+async fn requests_over_limit_are_queued() {
+    let load = LoadConfig { max_requests: 1, queue: 1, ..Default::default() };
+    let client = client(load).await;
+    let first = client.get("/sleep/100").dispatch();
+    let second = async {
+        yield_now().await;
+        client.get("/fast").dispatch().await
+    };
+
+    let third = async {
+        yield_now().await;
+        yield_now().await;
+        client.get("/fast").dispatch().await
+    };
+
+    let (first, second, third) = join!(first, second, third);
+    assert_eq!(first.status(), Status::Ok);
+    assert_eq!(second.status(), Status::Ok);
+    assert_eq!(third.status(), Status::ServiceUnavailable);
+    assert_eq!(client.rocket().load().shed, 1);
+}
+
+#[rocket::async_test]
+async fn queued_requests_time_out() {
+    let load = LoadConfig {
+        max_requests: 1,
+        queue: 1,
+        queue_timeout: 1,
+        retry_after: 0,
+        ..Default::default()
+    };
+
+    let client = client(load).await;
+    let first = client.get("/sleep/1500").dispatch();
+    let second = async {
+        yield_now().await;
+        client.get("/fast").dispatch().await
+    };
+
+    let (first, second) = join!(first, second);
+    assert_eq!(first.status(), Status::Ok);
+    assert_eq!(second.status(), Status::ServiceUnavailable);
+    assert!(second.headers().get_one("Retry-After").is_none());
+}
+
+#[rocket::async_test]
+async fn limits_are_disabled_by_default() {
+    let client = client(LoadConfig::default()).await;
+    let requests = (0..8).map(|_| client.get("/sleep/100").dispatch());
+    for response in rocket::futures::future::join_all(requests).await {
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    assert_eq!(client.rocket().load().shed, 0);
+}
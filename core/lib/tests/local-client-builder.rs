@@ -0,0 +1,105 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, State};
+use rocket::fairing::AdHoc;
+use rocket::local::blocking::Client;
+
+struct Greeting(&'static str);
+
+struct Counter(usize);
+
+#[get("/")]
+fn index(greeting: &State<Greeting>) -> &'static str {
+    greeting.0
+}
+
+#[get("/count")]
+fn count(counter: &State<Counter>) -> String {
+    counter.0.to_string()
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![index, count])
+        .manage(Greeting("hello"))
+        .attach(AdHoc::on_ignite("Counter", |rocket| async {
+            rocket.manage(Counter(1))
+        }))
+        .attach(AdHoc::on_response("Stamp", |_, res| Box::pin(async move {
+            res.set_raw_header("X-Stamp", "1");
+        })))
+        .attach(AdHoc::on_ignite("Nested", |rocket| async {
+            rocket.attach(AdHoc::on_response("Nested Stamp", |_, res| Box::pin(async move {
+                res.set_raw_header("X-Nested", "1");
+            })))
+        }))
+}
+
+#[test]
+fn builder_without_overrides_matches_tracked() {
+    let client = Client::builder(rocket()).build().unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("X-Stamp"), Some("1"));
+    assert_eq!(response.headers().get_one("X-Nested"), Some("1"));
+    assert_eq!(response.into_string().unwrap(), "hello");
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "1");
+}
+
+#[test]
+fn override_state() {
+    let client = Client::builder(rocket())
+        .override_state(Greeting("mocked"))
+        .override_state(Counter(7))
+        .override_state(Counter(42))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "mocked");
+    assert_eq!(client.get("/count").dispatch().into_string().unwrap(), "42");
+    assert_eq!(client.rocket().state::<Greeting>().unwrap().0, "mocked");
+}
+
+#[test]
+fn override_unmanaged_state() {
+    let rocket = rocket::build().mount("/", routes![index]);
+    let client = Client::builder(rocket)
+        .override_state(Greeting("only mock"))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "only mock");
+}
+
+#[test]
+fn without_fairing() {
+    let client = Client::builder(rocket())
+        .without_fairing("Stamp")
+        .without_fairing("Nested Stamp")
+        .build()
+        .unwrap();
+
+    let response = client.get("/").dispatch();
+    assert!(response.headers().get_one("X-Stamp").is_none());
+    assert!(response.headers().get_one("X-Nested").is_none());
+
+    let names: Vec<_> = client.rocket().fairing_info().map(|info| info.name).collect();
+    assert!(names.contains(&"Counter"));
+    assert!(!names.contains(&"Stamp"));
+}
+
+#[test]
+fn async_builder() {
+    use rocket::local::asynchronous::Client;
+
+    rocket::async_test(async {
+        let client = Client::builder(rocket())
+            .override_state(Greeting("async"))
+            .tracked(false)
+            .build()
+            .await
+            .unwrap();
+
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "async");
+    });
+}
@@ -0,0 +1,134 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Data;
+use rocket::data::ToByteUnit;
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+
+#[get("/<name>")]
+fn hello(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+#[post("/<name>", data = "<greeting>")]
+fn greet(name: &str, greeting: &str) -> String {
+    format!("{greeting}, {name}!")
+}
+
+#[put("/raw", data = "<data>")]
+async fn raw(data: Data<'_>) -> std::io::Result<String> {
+    data.open(64.bytes()).into_string().await.map(|s| s.value)
+}
+
+#[test]
+fn typed_call_sets_method_and_body() {
+    let client = Client::debug_with(routes![hello, greet, raw]).unwrap();
+
+    let response = client.call::<hello>(uri!(hello("Bob"))).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Hello, Bob!");
+
+    let request = client.call::<greet>(uri!(greet("Bob"))).body("Hi");
+    assert_eq!(request.inner().method(), rocket::http::Method::Post);
+    assert_eq!(request.content_type(), Some(&ContentType::Text));
+    assert_eq!(request.dispatch().into_string().unwrap(), "Hi, Bob!");
+
+    let response = client.call::<raw>(uri!(raw)).body(b"bytes").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "bytes");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn typed_call_serializes_json() {
+    use rocket::serde::{Serialize, Deserialize, json::Json};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Task {
+        complete: bool,
+    }
+
+    #[put("/<id>", data = "<task>")]
+    fn update(id: usize, task: Json<Task>) -> String {
+        format!("{id}: {}", task.complete)
+    }
+
+    let client = Client::debug_with(routes![update]).unwrap();
+    let request = client.call::<update>(uri!(update(10))).body(&Task { complete: true });
+    assert_eq!(request.content_type(), Some(&ContentType::JSON));
+    assert_eq!(request.dispatch().into_string().unwrap(), "10: true");
+}
+
+#[test]
+fn typed_call_encodes_forms() {
+    use rocket::form::Form;
+
+    #[derive(FromForm, UriDisplayQuery)]
+    struct Login<'r> {
+        user: &'r str,
+        remember: bool,
+    }
+
+    #[post("/login", data = "<login>")]
+    fn login(login: Form<Login<'_>>) -> String {
+        format!("{}: {}", login.user, login.remember)
+    }
+
+    let client = Client::debug_with(routes![login]).unwrap();
+    let login = Login { user: "Bob Smith", remember: true };
+    let request = client.call::<login>(uri!(login)).body(&login);
+    assert_eq!(request.content_type(), Some(&ContentType::Form));
+    assert_eq!(request.dispatch().into_string().unwrap(), "Bob Smith: true");
+}
+
+#[test]
+fn typed_call_supports_custom_data_guards() {
+    use rocket::data::{self, FromData};
+    use rocket::local::TypedBody;
+    use rocket::request::Request;
+
+    struct Shout(String);
+
+    #[rocket::async_trait]
+    impl<'r> FromData<'r> for Shout {
+        type Error = std::io::Error;
+
+        async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+            String::from_data(req, data).await.map(|s| Shout(s.to_uppercase()))
+        }
+    }
+
+    impl TypedBody for Shout {
+        type Value = str;
+
+        fn encode(value: &str) -> (Option<ContentType>, Vec<u8>) {
+            (Some(ContentType::Plain), value.as_bytes().to_vec())
+        }
+    }
+
+    #[post("/shout", data = "<shout>")]
+    fn shout(shout: Shout) -> String {
+        shout.0
+    }
+
+    let client = Client::debug_with(routes![shout]).unwrap();
+    let response = client.call::<shout>(uri!(shout)).body("hi").dispatch();
+    assert_eq!(response.into_string().unwrap(), "HI");
+}
+
+#[cfg(feature = "json")]
+#[test]
+#[should_panic(expected = "failed to serialize typed JSON request body")]
+fn typed_call_panics_on_serialization_errors() {
+    use std::collections::HashMap;
+    use rocket::serde::json::Json;
+
+    #[post("/", data = "<map>")]
+    fn map(map: Json<HashMap<(u8, u8), u8>>) -> String {
+        map.len().to_string()
+    }
+
+    let client = Client::debug_with(routes![map]).unwrap();
+    let value = HashMap::from([((1, 2), 3)]);
+    let _ = client.call::<map>(uri!(map)).body(&value);
+}
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::io::{Read, Write};
+
+use rocket::{Request, Response};
+use rocket::http::Status;
+use rocket::data::{IoHandler, IoStream};
+use rocket::response::{self, Responder};
+use rocket::response::stream::{Event, EventStream};
+use rocket::local::blocking::Client;
+use rocket::tokio::io;
+
+struct Echo;
+
+#[rocket::async_trait]
+impl IoHandler for Echo {
+    async fn io(self: Box<Self>, io: IoStream) -> io::Result<()> {
+        let (mut reader, mut writer) = io::split(io);
+        io::copy(&mut reader, &mut writer).await?;
+        Ok(())
+    }
+}
+
+impl<'r> Responder<'r, 'r> for Echo {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'r> {
+        Response::build()
+            .upgrade("echo", self)
+            .sized_body(None, std::io::Cursor::new("not upgraded"))
+            .ok()
+    }
+}
+
+#[get("/echo")]
+fn echo() -> Echo {
+    Echo
+}
+
+#[get("/events")]
+fn events() -> EventStream![] {
+    EventStream! {
+        yield Event::data("one");
+        yield Event::data("two\nlines").event("multi").id("2");
+        yield Event::comment("bye");
+    }
+}
+
+#[test]
+fn local_upgrade_echoes() {
+    let client = Client::debug_with(routes![echo]).unwrap();
+    let mut upgraded = client.get(uri!(echo)).dispatch_upgrade("echo").unwrap();
+    assert_eq!(upgraded.protocol(), "echo");
+    assert_eq!(upgraded.status(), Status::SwitchingProtocols);
+    assert_eq!(upgraded.headers().get_one("Upgrade"), Some("echo"));
+
+    upgraded.write_all(b"hello, world").unwrap();
+    let mut buf = [0; 12];
+    upgraded.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello, world");
+}
+
+#[test]
+fn local_upgrade_refused() {
+    let client = Client::debug_with(routes![echo]).unwrap();
+    let response = client.get(uri!(echo)).dispatch_upgrade("websocket").unwrap_err();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "not upgraded");
+
+    let response = client.get(uri!(echo)).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "not upgraded");
+}
+
+#[test]
+fn local_event_stream() {
+    let client = Client::debug_with(routes![events]).unwrap();
+    let mut response = client.get(uri!(events)).dispatch();
+    let events: Vec<_> = response.events().collect();
+    assert_eq!(events, [
+        Event::data("one"),
+        Event::data("two\nlines").event("multi").id("2"),
+        Event::comment("bye"),
+    ]);
+}
@@ -0,0 +1,109 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::local::fuzz::Fuzzer;
+use rocket::request::FromParam;
+
+struct UserId(String);
+
+impl<'a> FromParam<'a> for UserId {
+    type Error = ();
+
+    fn from_param(param: &'a str) -> Result<Self, ()> {
+        Ok(UserId(param.to_string()))
+    }
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/search?<q>&<page>&lang=en")]
+fn search(q: &str, page: Option<u8>) -> String {
+    format!("{q} {page:?}")
+}
+
+#[get("/files/<path..>")]
+fn files(path: PathBuf) -> String {
+    path.display().to_string()
+}
+
+#[post("/item/<n>", format = "json", data = "<body>")]
+fn item(n: i64, body: &str) -> String {
+    format!("{n} {body}")
+}
+
+#[get("/page/<n>")]
+fn page(n: u8) -> &'static str {
+    ["one", "two", "three"][n as usize]
+}
+
+#[get("/user/<id>")]
+fn user(id: UserId) -> String {
+    assert!(id.0.starts_with("user-"), "bad user id");
+    id.0
+}
+
+#[test]
+fn well_behaved_routes_pass() {
+    let client = Client::debug_with(routes![index, search, files, item]).unwrap();
+    let report = Fuzzer::new().iterations(20).run_blocking(&client);
+    report.assert_ok();
+    assert_eq!(report.requests, 4 * 20);
+}
+
+#[test]
+fn panics_are_reported() {
+    let client = Client::debug_with(routes![index, page]).unwrap();
+    let report = Fuzzer::new().run_blocking(&client);
+    assert!(!report.is_ok());
+    assert_eq!(report.requests, 2 * Fuzzer::ITERATIONS);
+    for failure in &report.failures {
+        assert_eq!(failure.route.as_deref(), Some("page"));
+        assert_eq!(failure.status, Status::InternalServerError);
+        assert!(failure.uri.starts_with("/page/"));
+
+        let n: u8 = failure.uri["/page/".len()..].parse().unwrap();
+        assert!(n > 2);
+    }
+
+    assert!(report.to_string().contains("(route `page`)"));
+}
+
+#[test]
+fn custom_generators_are_used() {
+    let client = Client::debug_with(routes![user]).unwrap();
+    assert!(!Fuzzer::new().run_blocking(&client).is_ok());
+
+    let report = Fuzzer::new()
+        .generator("UserId", |g| format!("user-{}", g.below(100)))
+        .run_blocking(&client);
+
+    report.assert_ok();
+}
+
+#[test]
+fn runs_are_deterministic() {
+    let client = Client::debug_with(routes![page, user]).unwrap();
+    let uris = |seed| Fuzzer::new().seed(seed).run_blocking(&client).failures.into_iter()
+        .map(|failure| failure.uri)
+        .collect::<Vec<_>>();
+
+    assert_eq!(uris(1), uris(1));
+    assert_ne!(uris(1), uris(2));
+}
+
+#[test]
+fn routes_can_be_filtered() {
+    let client = Client::debug_with(routes![index, page]).unwrap();
+    let report = Fuzzer::new()
+        .filter(|route| route.name.as_deref() != Some("page"))
+        .run_blocking(&client);
+
+    report.assert_ok();
+    assert_eq!(report.requests, Fuzzer::ITERATIONS);
+}
@@ -0,0 +1,110 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+
+#[derive(Responder)]
+#[response(content_type = "text")]
+struct Tagged {
+    body: &'static str,
+    tag: Header<'static>,
+    other: Header<'static>,
+}
+
+#[get("/")]
+fn index() -> Tagged {
+    Tagged {
+        body: "Hello, world!",
+        tag: Header::new("X-Tag", "one"),
+        other: Header::new("A-Header", "first"),
+    }
+}
+
+#[get("/binary")]
+fn binary() -> Vec<u8> {
+    vec![0xff, 0xfe, 0x00]
+}
+
+#[test]
+fn assertions_pass() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    client.get(uri!(index)).dispatch()
+        .assert_status(Status::Ok)
+        .assert_header("x-tag", "one")
+        .assert_content_type(ContentType::Text);
+}
+
+#[test]
+#[should_panic(expected = "expected header `X-Tag: two`")]
+fn assert_header_fails() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    client.get(uri!(index)).dispatch().assert_header("X-Tag", "two");
+}
+
+#[test]
+#[should_panic(expected = "unexpected response status")]
+fn assert_status_fails() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    client.get("/unknown").dispatch().assert_status(Status::Ok);
+}
+
+#[test]
+fn snapshot_is_canonical() {
+    let client = Client::debug_with(routes![index, binary]).unwrap();
+    let snapshot = client.get(uri!(index)).dispatch().into_snapshot();
+    assert!(snapshot.starts_with("200 OK\n\
+        a-header: first\n\
+        content-length: 13\n\
+        content-type: text/plain; charset=utf-8\n"));
+
+    assert!(snapshot.contains("\nserver: Rocket\n"));
+    assert!(snapshot.ends_with("\nx-tag: one\n\nHello, world!"));
+
+    let snapshot = client.get(uri!(binary)).dispatch().into_snapshot();
+    assert!(snapshot.ends_with("\n\n<3 bytes of binary data>"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn snapshot_pretty_prints_json() {
+    use rocket::serde::json::{Value, json};
+
+    #[get("/json")]
+    fn json() -> Value {
+        json!({ "a": null, "b": [1, 2] })
+    }
+
+    let client = Client::debug_with(routes![json]).unwrap();
+    let snapshot = client.get(uri!(json)).dispatch().into_snapshot();
+    assert!(snapshot.ends_with("\n\n{\n  \"a\": null,\n  \"b\": [\n    1,\n    2\n  ]\n}"));
+}
+
+#[cfg(feature = "html")]
+mod html {
+    use rocket::response::content::RawHtml;
+    use rocket::local::blocking::Client;
+
+    #[get("/")]
+    fn page() -> RawHtml<&'static str> {
+        RawHtml(r#"<ul id="todos"><li>Buy <b>milk</b></li><li class="done">Walk</li></ul>"#)
+    }
+
+    #[test]
+    fn html_select_returns_matching_text() {
+        let client = Client::debug_with(routes![page]).unwrap();
+        let items = client.get("/").dispatch().html_select("#todos > li");
+        assert_eq!(items, ["Buy milk", "Walk"]);
+
+        let done = client.get("/").dispatch().html_select("li.done");
+        assert_eq!(done, ["Walk"]);
+
+        assert!(client.get("/").dispatch().html_select("table").is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid CSS selector")]
+    fn html_select_panics_on_invalid_selectors() {
+        let client = Client::debug_with(routes![page]).unwrap();
+        client.get("/").dispatch().html_select("li[");
+    }
+}
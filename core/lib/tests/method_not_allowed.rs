@@ -0,0 +1,74 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Config};
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+
+#[get("/item")]
+fn get_item() { }
+
+#[patch("/item")]
+fn patch_item() { }
+
+#[post("/form")]
+fn post_form() { }
+
+#[put("/guarded")]
+fn guarded() -> Status {
+    Status::Unauthorized
+}
+
+#[catch(405)]
+fn not_allowed(req: &Request<'_>) -> String {
+    let allowed = req.allowed_methods().unwrap_or_default();
+    let allowed: Vec<_> = allowed.iter().map(|m| m.as_str()).collect();
+    allowed.join("|")
+}
+
+#[catch(404)]
+fn not_found(req: &Request<'_>) -> String {
+    format!("404 {}", req.allowed_methods().is_none())
+}
+
+fn client(method_not_allowed: bool) -> Client {
+    let rocket = rocket::custom(Config { method_not_allowed, ..Config::debug_default() })
+        .mount("/", routes![get_item, patch_item, post_form, guarded])
+        .register("/", catchers![not_allowed, not_found]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn unmatched_methods_are_not_allowed() {
+    let client = client(true);
+    let response = client.delete("/item").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, OPTIONS, PATCH"));
+    assert_eq!(response.into_string().unwrap(), "GET|HEAD|OPTIONS|PATCH");
+
+    let response = client.head("/form").dispatch();
+    assert_eq!(response.status(), Status::MethodNotAllowed);
+    assert_eq!(response.headers().get_one("Allow"), Some("POST, OPTIONS"));
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("Allow").is_none());
+    assert_eq!(response.into_string().unwrap(), "404 true");
+}
+
+#[test]
+fn matched_methods_are_unaffected() {
+    let client = client(true);
+    assert_eq!(client.get("/item").dispatch().status(), Status::Ok);
+    assert_eq!(client.head("/item").dispatch().status(), Status::Ok);
+    assert_eq!(client.put("/guarded").dispatch().status(), Status::Unauthorized);
+}
+
+#[test]
+fn method_not_allowed_can_be_disabled() {
+    let client = client(false);
+    let response = client.delete("/item").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("Allow").is_none());
+    assert_eq!(response.into_string().unwrap(), "404 true");
+}
@@ -0,0 +1,81 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::{HeaderPolicy, WithHeader};
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/cached")]
+fn cached() -> WithHeader<Header<'static>, &'static str> {
+    WithHeader(Header::new("Cache-Control", "max-age=60"), "cached")
+}
+
+#[get("/fail")]
+fn fail() -> Status {
+    Status::Unauthorized
+}
+
+fn client() -> Client {
+    let echo = HeaderPolicy::new("Echo")
+        .header(Header::new("X-Api", "1"))
+        .on_response(|req, res| {
+            let path = req.uri().path().to_string();
+            res.set_header(Header::new("X-Path", path));
+        });
+
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .mount_with("/api", routes![index, cached, fail], HeaderPolicy::json_api())
+        .mount_with("/v2", routes![index], echo.clone())
+        .mount_with("/v2", routes![cached], echo)
+        .mount_with("/both", routes![index], HeaderPolicy::new("A").header(Header::new("X-A", "a")));
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn policies_apply_to_mounted_routes() {
+    let client = client();
+    let response = client.get("/api").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("no-store"));
+    assert_eq!(response.headers().get_one("X-Content-Type-Options"), Some("nosniff"));
+    assert_eq!(response.headers().get_one("X-Frame-Options"), Some("DENY"));
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.get("/").dispatch();
+    assert!(response.headers().get_one("Cache-Control").is_none());
+    assert!(response.headers().get_one("X-Frame-Options").is_none());
+
+    let response = client.get("/v2/cached").dispatch();
+    assert_eq!(response.headers().get_one("X-Api"), Some("1"));
+    assert_eq!(response.headers().get_one("X-Path"), Some("/v2/cached"));
+    assert!(response.headers().get_one("X-Frame-Options").is_none());
+
+    let response = client.get("/both").dispatch();
+    assert_eq!(response.headers().get_one("X-A"), Some("a"));
+}
+
+#[test]
+fn existing_headers_take_precedence() {
+    let client = client();
+    let response = client.get("/api/cached").dispatch();
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("max-age=60"));
+    assert_eq!(response.headers().get_one("X-Frame-Options"), Some("DENY"));
+}
+
+#[test]
+fn policies_apply_to_error_responses() {
+    let client = client();
+    let response = client.get("/api/fail").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.headers().get_one("X-Frame-Options"), Some("DENY"));
+
+    // Requests that don't match a route in the mount are unaffected.
+    let response = client.get("/api/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("X-Frame-Options").is_none());
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "multipart")]
+
 #[macro_use] extern crate rocket;
 
 use rocket::{Config, Build, Rocket};
@@ -0,0 +1,93 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+use rocket::response::multipart::{MultipartResponse, Part};
+
+#[get("/mixed")]
+fn mixed() -> MultipartResponse<'static> {
+    MultipartResponse::mixed()
+        .boundary("b0und4ry")
+        .part(Part::new(ContentType::JSON, "{\"id\":1}".as_bytes()))
+        .part(Part::text("hi").header(Header::new("Content-ID", "<greeting>")))
+        .part(Part::new(ContentType::CSV, "a,b\n".as_bytes()).filename("data.csv"))
+}
+
+#[get("/form")]
+fn form() -> MultipartResponse<'static> {
+    MultipartResponse::form_data()
+        .part(Part::text("Bob").name("username"))
+        .part(Part::new(ContentType::Binary, &[1u8, 2, 3][..]).filename("a.bin").name("file"))
+}
+
+#[get("/empty")]
+fn empty() -> MultipartResponse<'static> {
+    MultipartResponse::mixed().boundary("x")
+}
+
+#[get("/invalid")]
+fn invalid() -> MultipartResponse<'static> {
+    MultipartResponse::mixed().boundary("no\r\ncrlf").part(Part::text("hi"))
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![mixed, form, empty, invalid]).unwrap()
+}
+
+#[test]
+fn mixed_response() {
+    let client = client();
+    let response = client.get("/mixed").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Content-Type"),
+        Some("multipart/mixed; boundary=b0und4ry"));
+
+    assert_eq!(response.into_string().unwrap(),
+        "--b0und4ry\r\n\
+         Content-Type: application/json\r\n\
+         \r\n\
+         {\"id\":1}\r\n\
+         --b0und4ry\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-ID: <greeting>\r\n\
+         \r\n\
+         hi\r\n\
+         --b0und4ry\r\n\
+         Content-Disposition: attachment; filename=data.csv\r\n\
+         Content-Type: text/csv; charset=utf-8\r\n\
+         \r\n\
+         a,b\n\r\n\
+         --b0und4ry--\r\n");
+}
+
+#[test]
+fn form_data_response_has_random_boundary() {
+    let client = client();
+    let response = client.get("/form").dispatch();
+    let content_type = response.content_type().unwrap();
+    assert_eq!(content_type.media_type().sub(), "form-data");
+
+    let boundary = content_type.param("boundary").unwrap().to_string();
+    assert_eq!(boundary.len(), 32);
+
+    let body = response.into_bytes().unwrap();
+    let expected_head = format!("--{boundary}\r\n\
+        Content-Disposition: form-data; name=username\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\r\nBob\r\n\
+        --{boundary}\r\n\
+        Content-Disposition: form-data; name=file; filename=a.bin\r\n\
+        Content-Type: application/octet-stream\r\n\r\n");
+
+    assert!(body.starts_with(expected_head.as_bytes()));
+    assert!(body.ends_with(format!("\x01\x02\x03\r\n--{boundary}--\r\n").as_bytes()));
+
+    let other = client.get("/form").dispatch();
+    assert_ne!(other.content_type().unwrap().param("boundary").unwrap(), boundary);
+}
+
+#[test]
+fn empty_and_invalid_responses() {
+    let client = client();
+    assert_eq!(client.get("/empty").dispatch().into_string().unwrap(), "--x--\r\n");
+    assert_eq!(client.get("/invalid").dispatch().status(), Status::InternalServerError);
+}
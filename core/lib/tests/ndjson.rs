@@ -0,0 +1,91 @@
+#![cfg(feature = "json")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::futures::stream::{self, Stream, StreamExt};
+use rocket::http::{ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::serde::{Serialize, Deserialize};
+use rocket::serde::json::{NdJson, Lines};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Record {
+    id: usize,
+    level: String,
+}
+
+#[get("/records?<n>&<batch>")]
+fn records(n: usize, batch: Option<usize>) -> NdJson<impl Stream<Item = Record>> {
+    let records = stream::iter(0..n).map(|id| Record { id, level: "info".into() });
+    NdJson::from(records).batched(batch.unwrap_or(0))
+}
+
+#[post("/ingest", data = "<records>")]
+async fn ingest(mut records: NdJson<Lines<'_, Record>>) -> String {
+    let (mut ids, mut bad) = (vec![], 0);
+    while let Some(record) = records.next().await {
+        match record {
+            Ok(record) => ids.push(record.id.to_string()),
+            Err(_) => bad += 1,
+        }
+    }
+
+    format!("{} ({} bad)", ids.join(","), bad)
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![records, ingest]).unwrap()
+}
+
+#[test]
+fn responds_with_one_document_per_line() {
+    let client = client();
+    for uri in ["/records?n=3", "/records?n=3&batch=2"] {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::NDJSON));
+        assert_eq!(response.into_string().unwrap(),
+            "{\"id\":0,\"level\":\"info\"}\n\
+             {\"id\":1,\"level\":\"info\"}\n\
+             {\"id\":2,\"level\":\"info\"}\n");
+    }
+
+    let response = client.get("/records?n=0").dispatch();
+    assert_eq!(response.into_string().unwrap(), "");
+}
+
+#[test]
+fn round_trips_records() {
+    let client = client();
+    let body = client.get("/records?n=5").dispatch().into_bytes().unwrap();
+    let response = client.post("/ingest").header(ContentType::NDJSON).body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), "0,1,2,3,4 (0 bad)");
+}
+
+#[test]
+fn yields_errors_for_bad_lines_and_skips_blank_ones() {
+    let body = "{\"id\":7,\"level\":\"warn\"}\n\
+        \n\
+        not json\n\
+        {\"id\":8}\n\
+        {\"id\":9,\"level\":\"error\"}";
+
+    let client = client();
+    let response = client.post("/ingest").body(body).dispatch();
+    assert_eq!(response.into_string().unwrap(), "7,9 (2 bad)");
+}
+
+#[test]
+fn respects_the_ndjson_limit() {
+    let line = "{\"id\":1,\"level\":\"info\"}\n";
+    let config = rocket::Config {
+        limits: rocket::data::Limits::default().limit("ndjson", rocket::data::ByteUnit::from(30)),
+        ..rocket::Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config).mount("/", routes![ingest]);
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/ingest").body(line.repeat(2)).dispatch();
+    assert_eq!(response.into_string().unwrap(), "1 (1 bad)");
+}
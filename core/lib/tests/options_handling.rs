@@ -0,0 +1,68 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Config;
+use rocket::http::{Method, Status};
+use rocket::local::blocking::Client;
+
+#[get("/item/<_id>")]
+fn get_item(_id: usize) { }
+
+#[put("/item/<_id>")]
+fn put_item(_id: usize) { }
+
+#[delete("/item/<_id>?force=true")]
+fn delete_item(_id: usize) { }
+
+#[post("/custom")]
+fn post_custom() { }
+
+#[options("/custom")]
+fn options_custom() -> &'static str {
+    "custom options"
+}
+
+fn client_with(auto_options: bool) -> Client {
+    let routes = routes![get_item, put_item, delete_item, post_custom, options_custom];
+    let rocket = rocket::custom(Config { auto_options, ..Config::debug_default() })
+        .mount("/", routes);
+
+    Client::debug(rocket).unwrap()
+}
+
+fn client() -> Client {
+    client_with(true)
+}
+
+#[test]
+fn auto_options_advertises_allowed_methods() {
+    let client = client();
+    let response = client.req(Method::Options, "/item/1").dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, PUT, OPTIONS"));
+
+    let response = client.req(Method::Options, "/item/1?force=true").dispatch();
+    assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, PUT, DELETE, OPTIONS"));
+}
+
+#[test]
+fn auto_options_defers_to_user_routes_and_404s() {
+    let client = client();
+    let response = client.req(Method::Options, "/custom").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "custom options");
+
+    let response = client.req(Method::Options, "/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("Allow").is_none());
+}
+
+#[test]
+fn auto_options_can_be_disabled() {
+    let client = client_with(false);
+    let response = client.req(Method::Options, "/item/1").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("Allow").is_none());
+
+    let response = client.req(Method::Options, "/custom").dispatch();
+    assert_eq!(response.into_string().unwrap(), "custom options");
+}
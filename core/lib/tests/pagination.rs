@@ -0,0 +1,87 @@
+#![cfg(feature = "json")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::pagination::{Page, Pagination};
+use rocket::serde::json::{Value, json};
+
+#[get("/numbers")]
+fn numbers(pagination: Pagination) -> Page<u64> {
+    let end = (pagination.offset() + pagination.limit()).min(95);
+    pagination.page((pagination.offset()..end).collect(), 95)
+}
+
+#[get("/feed")]
+fn feed(pagination: Pagination) -> Page<&'static str> {
+    match pagination.cursor() {
+        None => pagination.cursor_page(vec!["a", "b"], Some("c d".into())),
+        Some(_) => pagination.cursor_page(vec!["c"], None),
+    }
+}
+
+fn client() -> Client {
+    let figment = rocket::Config::figment()
+        .merge(("pagination.default_per_page", 10))
+        .merge(("pagination.max_per_page", 50));
+
+    Client::debug(rocket::custom(figment).mount("/", routes![numbers, feed])).unwrap()
+}
+
+#[test]
+fn offset_pagination() {
+    let client = client();
+    let response = client.get("/numbers?filter=odd&page=2").dispatch();
+    assert_eq!(response.headers().get_one("Link").unwrap(), concat!(
+        "</numbers?filter=odd&page=1&per_page=10>; rel=first, ",
+        "</numbers?filter=odd&page=1&per_page=10>; rel=prev, ",
+        "</numbers?filter=odd&page=3&per_page=10>; rel=next, ",
+        "</numbers?filter=odd&page=10&per_page=10>; rel=last"
+    ));
+
+    let body = response.into_json::<Value>().unwrap();
+    assert_eq!(body["items"], json!([10, 11, 12, 13, 14, 15, 16, 17, 18, 19]));
+    assert_eq!(body["pagination"], json!({
+        "page": 2, "per_page": 10, "total": 95, "total_pages": 10
+    }));
+}
+
+#[test]
+fn per_page_is_capped() {
+    let client = client();
+    let response = client.get("/numbers?per_page=1000&page=2").dispatch();
+    let link = response.headers().get_one("Link").unwrap().to_string();
+    assert!(!link.contains("rel=next"));
+
+    let body = response.into_json::<Value>().unwrap();
+    assert_eq!(body["pagination"]["per_page"], 50);
+    assert_eq!(body["items"].as_array().unwrap().len(), 45);
+}
+
+#[test]
+fn invalid_parameters() {
+    let client = client();
+    for uri in ["/numbers?page=0", "/numbers?page=x", "/numbers?per_page=0"] {
+        assert_eq!(client.get(uri).dispatch().status(), Status::BadRequest);
+    }
+}
+
+#[test]
+fn cursor_pagination() {
+    let client = client();
+    let response = client.get("/feed?per_page=2").dispatch();
+    assert_eq!(response.headers().get_one("Link"),
+        Some("</feed?cursor=c%20d&per_page=2>; rel=next"));
+
+    let body = response.into_json::<Value>().unwrap();
+    assert_eq!(body, json!({
+        "items": ["a", "b"],
+        "pagination": { "per_page": 2, "next_cursor": "c d" }
+    }));
+
+    let response = client.get("/feed?cursor=c%20d&per_page=2").dispatch();
+    assert!(response.headers().get_one("Link").is_none());
+    let body = response.into_json::<Value>().unwrap();
+    assert_eq!(body["pagination"]["next_cursor"], Value::Null);
+}
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::convert::Infallible;
+
+use rocket::config::{Config, NormalizeConfig};
+use rocket::http::Status;
+use rocket::http::uri::Origin;
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest, Request};
+
+struct RawUri(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RawUri {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Infallible> {
+        request::Outcome::Success(RawUri(req.raw_uri().to_string()))
+    }
+}
+
+#[get("/<_..>")]
+fn echo(uri: &Origin<'_>, raw: RawUri) -> String {
+    format!("{uri} {}", raw.0)
+}
+
+fn client(normalize: NormalizeConfig) -> Client {
+    let config = Config { normalize, ..Config::debug_default() };
+    Client::debug(rocket::custom(config).mount("/", routes![echo])).unwrap()
+}
+
+fn enabled() -> NormalizeConfig {
+    NormalizeConfig { enabled: true, ..Default::default() }
+}
+
+#[test]
+fn normalizes_paths() {
+    let client = client(enabled());
+    let echo = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+    assert_eq!(echo("/a/b"), "/a/b /a/b");
+    assert_eq!(echo("/a/./b/../%63"), "/a/c /a/./b/../%63");
+    assert_eq!(echo("/%61/b/"), "/a/b/ /%61/b/");
+    assert_eq!(echo("/a/b/.."), "/a/ /a/b/..");
+    assert_eq!(echo("/../../etc/passwd"), "/etc/passwd /../../etc/passwd");
+    assert_eq!(echo("/a/%2e%2E/b"), "/b /a/%2e%2E/b");
+    assert_eq!(echo("/a/../b?x=1&y"), "/b?x=1&y /a/../b?x=1&y");
+    assert_eq!(echo("/a%2Fb"), "/a%2Fb /a%2Fb");
+
+    #[cfg(feature = "nfc")]
+    assert_eq!(echo("/caf%65%CC%81"), "/caf%C3%A9 /caf%65%CC%81");
+
+    #[cfg(not(feature = "nfc"))]
+    assert_eq!(echo("/caf%65%CC%81"), "/cafe%CC%81 /caf%65%CC%81");
+}
+
+#[test]
+fn respects_policy() {
+    #[allow(unused_mut)]
+    let mut policy = NormalizeConfig { dot_segments: false, ..enabled() };
+    #[cfg(feature = "nfc")] {
+        policy.nfc = false;
+    }
+
+    let client = client(policy);
+    let echo = |uri: &str| client.get(uri.to_string()).dispatch().into_string().unwrap();
+
+    assert_eq!(echo("/%61/./b/.."), "/a/./b/.. /%61/./b/..");
+    assert_eq!(echo("/caf%65%CC%81"), "/cafe%CC%81 /caf%65%CC%81");
+
+    let client = client(NormalizeConfig::default());
+    let response = client.get("/%61/./b").dispatch();
+    assert_eq!(response.into_string().unwrap(), "/%61/./b /%61/./b");
+}
+
+#[test]
+fn rejects_ambiguous_paths() {
+    let client = client(enabled());
+    for uri in ["/a%00b", "/%252e%252e/etc", "/%FF", "/a/%2561"] {
+        let response = client.get(uri).dispatch();
+        assert_eq!(response.status(), Status::BadRequest, "{uri}");
+    }
+}
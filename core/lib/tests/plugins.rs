@@ -0,0 +1,117 @@
+#![cfg(feature = "plugins")]
+
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::plugin::Plugins;
+use rocket::plugin::abi::*;
+
+static LOADS: AtomicUsize = AtomicUsize::new(0);
+static LIFTOFFS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn echo(_: *mut c_void, req: *const PluginRequest, res: *mut PluginResponse) {
+    let (req, res) = (&*req, &mut *res);
+    let body = format!("{} {}: {}",
+        req.method.as_str().unwrap(),
+        req.uri.as_str().unwrap(),
+        req.body.as_str().unwrap());
+
+    let body = Box::leak(body.into_boxed_str());
+    res.status = 200;
+    res.content_type = Slice::from_static("text/plain");
+    res.body = Slice::from_bytes(body.as_bytes());
+    res.free = Some(free);
+}
+
+unsafe extern "C" fn free(res: *mut PluginResponse) {
+    let body = (*res).body;
+    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(body.ptr as *mut u8, body.len)));
+}
+
+unsafe extern "C" fn teapot(_: *mut c_void, _: *const PluginRequest, res: *mut PluginResponse) {
+    (*res).status = 418;
+}
+
+unsafe extern "C" fn on_load(_: *mut c_void) -> i32 {
+    LOADS.fetch_add(1, Ordering::SeqCst);
+    0
+}
+
+unsafe extern "C" fn on_liftoff(_: *mut c_void) {
+    LIFTOFFS.fetch_add(1, Ordering::SeqCst);
+}
+
+unsafe extern "C" fn failing_load(_: *mut c_void) -> i32 {
+    7
+}
+
+static ROUTES: [RouteDeclaration; 2] = [
+    RouteDeclaration {
+        method: Slice::from_static("POST"),
+        uri: Slice::from_static("/echo/<_..>"),
+        handler: echo,
+    },
+    RouteDeclaration {
+        method: Slice::from_static("GET"),
+        uri: Slice::from_static("/teapot"),
+        handler: teapot,
+    },
+];
+
+struct Declared(Declaration);
+
+unsafe impl Sync for Declared {}
+
+const fn declaration(abi_version: u32, on_load: LoadFn) -> Declared {
+    Declared(Declaration {
+        abi_version,
+        name: Slice::from_static("test"),
+        version: Slice::from_static("1.2.3"),
+        routes: ROUTES.as_ptr(),
+        routes_len: ROUTES.len(),
+        ctx: ptr::null_mut(),
+        on_load: Some(on_load),
+        on_liftoff: Some(on_liftoff),
+        on_shutdown: None,
+    })
+}
+
+static VALID: Declared = declaration(ABI_VERSION, on_load);
+static OUTDATED: Declared = declaration(ABI_VERSION + 1, on_load);
+static FAILING: Declared = declaration(ABI_VERSION, failing_load);
+
+unsafe extern "C" fn valid() -> *const Declaration { &VALID.0 }
+unsafe extern "C" fn outdated() -> *const Declaration { &OUTDATED.0 }
+unsafe extern "C" fn failing() -> *const Declaration { &FAILING.0 }
+unsafe extern "C" fn null() -> *const Declaration { ptr::null() }
+
+#[test]
+fn builtin_plugin_routes_and_hooks() {
+    let plugins = unsafe { Plugins::new().builtin(valid) };
+    let client = Client::debug(rocket::build().attach(plugins)).unwrap();
+    assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+    assert_eq!(LIFTOFFS.load(Ordering::SeqCst), 1);
+
+    let response = client.post("/echo/a/b?c=d").body("hi").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.content_type().unwrap().is_plain());
+    assert_eq!(response.into_string().unwrap(), "POST /echo/a/b?c=d: hi");
+
+    let response = client.get("/teapot").dispatch();
+    assert_eq!(response.status(), Status::ImATeapot);
+    assert!(response.into_string().unwrap().contains("418"));
+}
+
+#[test]
+fn invalid_plugins_abort_launch() {
+    for declare in [outdated as DeclareFn, failing, null] {
+        let plugins = unsafe { Plugins::new().builtin(declare) };
+        assert!(Client::debug(rocket::build().attach(plugins)).is_err());
+    }
+
+    let plugins = unsafe { Plugins::new().library("/nonexistent/libplugin.so") };
+    assert!(Client::debug(rocket::build().attach(plugins)).is_err());
+}
@@ -0,0 +1,113 @@
+#![cfg(all(feature = "secrets", feature = "json"))]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Cookie, Status};
+use rocket::local::blocking::Client;
+use rocket::prefs::{Prefs, Preferences};
+use rocket::serde::{Serialize, Deserialize, json::{Value, json}};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Settings {
+    theme: String,
+    page_size: u32,
+}
+
+impl Preferences for Settings {
+    const COOKIE: &'static str = "settings";
+    const VERSION: u32 = 2;
+
+    fn migrate(version: u32, value: Value) -> Option<Value> {
+        match version {
+            1 => Some(json!({
+                "theme": if value["dark"].as_bool()? { "dark" } else { "light" },
+                "page_size": 20,
+            })),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct SettingsV1 {
+    dark: bool,
+}
+
+impl Preferences for SettingsV1 {
+    const COOKIE: &'static str = "settings";
+}
+
+#[get("/")]
+fn get(settings: Prefs<'_, Settings>) -> String {
+    format!("{}:{}", settings.theme, settings.page_size)
+}
+
+#[post("/<theme>")]
+fn set(theme: &str, mut settings: Prefs<'_, Settings>) {
+    settings.theme = theme.into();
+    settings.page_size = 50;
+    settings.save();
+}
+
+#[post("/v1/dark")]
+fn set_v1(mut settings: Prefs<'_, SettingsV1>) {
+    settings.dark = true;
+    settings.save();
+}
+
+#[post("/reset")]
+fn reset(mut settings: Prefs<'_, Settings>) {
+    settings.reset();
+}
+
+fn rocket() -> rocket::Rocket<rocket::Build> {
+    let key = "hPRYyVRiMyxpw5sBB1XeCMN1kFsDCqKvBi2QJxBVHQk=";
+    rocket::custom(rocket::Config::figment().merge(("secret_key", key)))
+        .mount("/", routes![get, set, set_v1, reset])
+}
+
+fn client() -> Client {
+    Client::tracked(rocket()).unwrap()
+}
+
+#[test]
+fn defaults_save_and_reset() {
+    let client = client();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), ":0");
+
+    assert_eq!(client.post("/solarized").dispatch().status(), Status::Ok);
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "solarized:50");
+
+    client.post("/reset").dispatch();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), ":0");
+}
+
+#[test]
+fn tampered_cookies_are_ignored() {
+    let client = client();
+    client.post("/solarized").dispatch();
+
+    let cookie = client.cookies().get("settings").unwrap().value().to_string();
+    let untracked = Client::untracked(rocket()).unwrap();
+    let response = untracked.get("/")
+        .cookie(Cookie::new("settings", cookie.clone()))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "solarized:50");
+
+    let tampered = cookie.replace("solarized", "hacked!!!");
+    let response = untracked.get("/")
+        .cookie(Cookie::new("settings", tampered))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), ":0");
+}
+
+#[test]
+fn older_schemas_are_migrated() {
+    let client = client();
+    client.post("/v1/dark").dispatch();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "dark:20");
+}
@@ -0,0 +1,53 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::http::{Header, Link};
+use rocket::response::{self, Responder};
+use rocket::local::blocking::Client;
+
+struct Page(&'static str);
+
+impl<'r> Responder<'r, 'static> for Page {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        Response::build_from(self.0.respond_to(req)?)
+            .preload_link(Link::new().preload("/style.css", "style"))
+            .trailer(Header::new("Server-Timing", "render;dur=12"))
+            .trailer(Header::new("X-Checksum", "abc"))
+            .ok()
+    }
+}
+
+#[get("/")]
+fn index() -> Page {
+    Page("Hello, world!")
+}
+
+#[get("/plain")]
+fn plain() -> &'static str {
+    "Hello, world!"
+}
+
+#[test]
+fn preload_links_are_in_final_response() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let response = client.get("/").dispatch();
+    let link = "</style.css>; rel=preload; as=style";
+    assert_eq!(response.preload_links().get_one("Link"), Some(link));
+    assert_eq!(response.headers().get_one("Link"), Some(link));
+}
+
+#[test]
+fn trailers_are_announced_and_unsized() {
+    let client = Client::debug_with(routes![index, plain]).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("Trailer"), Some("Server-Timing, X-Checksum"));
+    assert!(response.headers().get_one("Content-Length").is_none());
+    assert_eq!(response.trailers().get_one("X-Checksum"), Some("abc"));
+    assert_eq!(response.into_string().unwrap(), "Hello, world!");
+
+    let response = client.get("/plain").dispatch();
+    assert!(response.trailers().is_empty());
+    assert!(response.preload_links().is_empty());
+    assert!(response.headers().get_one("Trailer").is_none());
+    assert_eq!(response.headers().get_one("Content-Length"), Some("13"));
+}
@@ -0,0 +1,100 @@
+#![cfg(debug_assertions)]
+
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::{Request, State};
+use rocket::http::{Accept, ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::profile::Profiler;
+use rocket::request::{FromRequest, Outcome};
+
+struct Slow;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Slow {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> Outcome<Self, ()> {
+        rocket::tokio::time::sleep(Duration::from_millis(5)).await;
+        Outcome::Success(Slow)
+    }
+}
+
+#[get("/slow")]
+fn slow(_slow: Slow) -> &'static str {
+    "slow"
+}
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[get("/report")]
+fn report(profiler: &State<Profiler>) -> String {
+    profiler.report().to_string()
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![slow, echo, report])
+        .mount("/profile", Profiler::routes())
+        .attach(Profiler::new());
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn records_guards_handler_and_responder() {
+    let client = client();
+    for _ in 0..3 {
+        assert_eq!(client.get("/slow").dispatch().into_string().unwrap(), "slow");
+    }
+
+    client.post("/echo").body("hi").dispatch();
+
+    let profiler = client.rocket().state::<Profiler>().unwrap();
+    let report = profiler.report();
+    let slow = report.routes.iter().find(|r| r.uri == "/slow").unwrap();
+    assert_eq!(slow.name.as_deref(), Some("slow"));
+    assert_eq!(slow.guards.len(), 1);
+    assert_eq!(slow.guards[0].0, "_slow");
+    assert_eq!(slow.guards[0].1.count, 3);
+    assert!(slow.guards[0].1.p50 >= Duration::from_millis(5));
+    assert_eq!(slow.handler.unwrap().count, 3);
+    assert_eq!(slow.responder.unwrap().count, 3);
+
+    let echo = report.routes.iter().find(|r| r.uri == "/echo").unwrap();
+    assert_eq!(echo.guards[0].0, "body");
+    assert_eq!(echo.handler.unwrap().count, 1);
+
+    let text = client.get("/report").dispatch().into_string().unwrap();
+    assert!(text.starts_with("route\tstage\tcount"));
+    assert!(text.contains("guard `_slow`"));
+
+    profiler.reset();
+    assert!(profiler.report().routes.is_empty());
+}
+
+#[test]
+fn serves_html_and_text_reports() {
+    let client = client();
+    client.get("/slow").dispatch();
+
+    let response = client.get("/profile").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    let html = response.into_string().unwrap();
+    let row = "<td>slow (GET &#x2F;slow)</td><td>guard &#96;_slow&#96;</td>";
+    assert!(html.contains(row));
+
+    let response = client.get("/profile").header(Accept::Text).dispatch();
+    assert!(response.into_string().unwrap().contains("slow (GET /slow)\thandler\t1\t"));
+}
+
+#[test]
+fn report_route_requires_profiler() {
+    let client = Client::debug_with(Profiler::routes()).unwrap();
+    assert_eq!(client.get("/").dispatch().status(), Status::NotFound);
+}
@@ -0,0 +1,61 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::fairing::AdHoc;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct User(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.extensions().get::<User>() {
+            Some(user) => request::Outcome::Success(user),
+            None => request::Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+#[get("/")]
+fn index(user: &User) -> &str {
+    &user.0
+}
+
+fn rocket() -> rocket::Rocket<rocket::Build> {
+    rocket::build()
+        .mount("/", routes![index])
+        .attach(AdHoc::on_request("Authenticate", |req, _| Box::pin(async move {
+            if let Some(name) = req.headers().get_one("X-User") {
+                req.extensions().insert(User(name.to_string()));
+            }
+        })))
+        .attach(AdHoc::on_request("Guest", |req, _| Box::pin(async move {
+            // The first insert wins: this is ignored if a user was inserted above.
+            req.extensions().insert(User("guest".into()));
+        })))
+}
+
+#[test]
+fn fairing_extensions_reach_guards() {
+    let client = Client::debug(rocket()).unwrap();
+    let response = client.get("/").header(Header::new("X-User", "alice")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "alice");
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "guest");
+}
+
+#[test]
+fn missing_extensions_are_none() {
+    let client = Client::debug(rocket::build()).unwrap();
+    let request = client.get("/");
+    assert!(request.extensions().get::<User>().is_none());
+    assert!(!request.extensions().contains::<User>());
+    assert!(request.extensions().insert(User("bob".into())));
+    assert!(!request.extensions().insert(User("eve".into())));
+    assert_eq!(request.extensions().get::<User>().unwrap().0, "bob");
+}
@@ -0,0 +1,63 @@
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::response::content::RawHtml;
+use rocket::tokio::io::AsyncReadExt;
+
+#[get("/")]
+fn index() -> RawHtml<&'static str> {
+    RawHtml("<p>hi</p>")
+}
+
+#[derive(Responder)]
+struct Encoded(&'static str, Header<'static>);
+
+#[get("/gzip")]
+fn gzip() -> Encoded {
+    Encoded("compressed", Header::new("Content-Encoding", "gzip"))
+}
+
+#[get("/empty")]
+fn empty() {}
+
+fn watermark(mark: &'static str) -> AdHoc {
+    AdHoc::on_response(mark, move |_, res| Box::pin(async move {
+        res.map_body(move |body| body.chain(Cursor::new(mark)));
+    }))
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/", routes![index, gzip, empty])
+        .attach(watermark("<!-- a -->"))
+        .attach(watermark("<!-- b -->"));
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn transforms_compose_in_attach_order() {
+    let client = client();
+    let response = client.get("/").dispatch();
+    assert!(response.headers().get_one("Content-Length").is_none());
+    assert_eq!(response.into_string().unwrap(), "<p>hi</p><!-- a --><!-- b -->");
+}
+
+#[test]
+fn encoded_bodies_are_untouched() {
+    let client = client();
+    let response = client.get("/gzip").dispatch();
+    assert_eq!(response.headers().get_one("Content-Length"), Some("10"));
+    assert_eq!(response.into_string().unwrap(), "compressed");
+}
+
+#[test]
+fn unset_bodies_are_untouched() {
+    let client = client();
+    let response = client.get("/empty").dispatch();
+    assert!(response.into_string().unwrap_or_default().is_empty());
+}
@@ -0,0 +1,151 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use rocket::{Config, State};
+use rocket::http::Status;
+use rocket::listener::tcp::TcpListener;
+use rocket::local::asynchronous::Client;
+use rocket::response::{Completion, ResponseGuard, stream::ReaderStream};
+use rocket::tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, ReadBuf};
+use rocket::tokio::net::TcpStream;
+
+type Log = Arc<Mutex<Vec<(&'static str, Completion)>>>;
+
+struct Broken;
+
+impl AsyncRead for Broken {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Poll::Ready(Err(io::Error::other("broken body")))
+    }
+}
+
+#[get("/")]
+fn index(guard: ResponseGuard<'_>, log: &State<Log>) -> &'static str {
+    let (first, second) = (log.inner().clone(), log.inner().clone());
+    guard.on_complete(move |c| first.lock().unwrap().push(("first", c)));
+    guard.on_complete(move |_| panic!("callback panic"));
+    guard.on_complete(move |c| second.lock().unwrap().push(("second", c)));
+    "Hello, world!"
+}
+
+#[get("/broken")]
+fn broken(guard: ResponseGuard<'_>, log: &State<Log>) -> ReaderStream![Broken] {
+    let log = log.inner().clone();
+    guard.on_complete(move |c| log.lock().unwrap().push(("broken", c)));
+    ReaderStream::one(Broken)
+}
+
+#[get("/empty")]
+fn empty(guard: ResponseGuard<'_>, log: &State<Log>) -> Status {
+    let log = log.inner().clone();
+    guard.on_complete(move |c| log.lock().unwrap().push(("empty", c)));
+    Status::NoContent
+}
+
+fn rocket(log: &Log) -> rocket::Rocket<rocket::Build> {
+    rocket::custom(Config::debug_default())
+        .mount("/", routes![index, broken, empty])
+        .manage(log.clone())
+}
+
+async fn client() -> (Client, Log) {
+    let log = Log::default();
+    let rocket = rocket(&log);
+
+    (Client::debug(rocket).await.unwrap(), log)
+}
+
+#[async_test]
+async fn callbacks_run_once_body_is_written() {
+    let (client, log) = client().await;
+    let response = client.get("/").dispatch().await;
+    assert!(log.lock().unwrap().is_empty());
+
+    assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+    let expected = [("first", Completion::Written), ("second", Completion::Written)];
+    assert_eq!(*log.lock().unwrap(), expected);
+}
+
+#[async_test]
+async fn callbacks_observe_aborted_responses() {
+    let (client, log) = client().await;
+    let response = client.get("/").dispatch().await;
+    drop(response);
+
+    let expected = [("first", Completion::Aborted), ("second", Completion::Aborted)];
+    assert_eq!(*log.lock().unwrap(), expected);
+}
+
+#[async_test]
+async fn callbacks_observe_failed_bodies() {
+    let (client, log) = client().await;
+    let response = client.get("/broken").dispatch().await;
+    assert!(response.into_bytes().await.is_none());
+    assert_eq!(*log.lock().unwrap(), [("broken", Completion::Failed)]);
+}
+
+#[async_test]
+async fn bodiless_responses_are_written() {
+    let (client, log) = client().await;
+    drop(client.head("/").dispatch().await);
+    drop(client.get("/empty").dispatch().await);
+
+    let expected = [
+        ("first", Completion::Written),
+        ("second", Completion::Written),
+        ("empty", Completion::Written),
+    ];
+
+    assert_eq!(*log.lock().unwrap(), expected);
+}
+
+#[async_test]
+async fn callbacks_run_for_served_responses() {
+    let log = Log::default();
+    let rocket = rocket(&log).ignite().await.unwrap();
+    let bound = rocket.bound_addresses();
+    let shutdown = rocket.shutdown();
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let server = rocket::tokio::spawn(rocket.try_launch_on(TcpListener::bind(addr)));
+    let addr = bound.await[0].tcp().unwrap();
+
+    // Callbacks run once the connection drops the response, which may be
+    // after the client has read it, so wait for each before continuing.
+    for (request, completions) in [("GET /", 2), ("HEAD /", 4), ("GET /empty", 5)] {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!("{request} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+        stream.read_to_end(&mut vec![]).await.unwrap();
+
+        let completed = rocket::tokio::time::timeout(Duration::from_secs(5), async {
+            while log.lock().unwrap().len() < completions {
+                rocket::tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        completed.await.expect("callbacks run");
+    }
+
+    let expected = [
+        ("first", Completion::Written),
+        ("second", Completion::Written),
+        ("first", Completion::Written),
+        ("second", Completion::Written),
+        ("empty", Completion::Written),
+    ];
+
+    assert_eq!(*log.lock().unwrap(), expected);
+
+    shutdown.notify();
+    server.await.unwrap().unwrap();
+}
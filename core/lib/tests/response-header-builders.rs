@@ -0,0 +1,67 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{ContentType, Link, RetryAfter, Status};
+use rocket::response::{status, Attachment, WithHeader};
+use rocket::local::blocking::Client;
+
+#[get("/items?<page>")]
+fn items(page: u64) -> WithHeader<Link, String> {
+    let link = Link::new().paginate(page, 3, |p| uri!(items(p)));
+    WithHeader(link, format!("page {}", page))
+}
+
+#[get("/busy")]
+fn busy() -> WithHeader<RetryAfter, status::Custom<&'static str>> {
+    WithHeader(RetryAfter::seconds(120), status::Custom(Status::ServiceUnavailable, "busy"))
+}
+
+#[get("/download")]
+fn download() -> Attachment<Vec<u8>> {
+    Attachment::new("résumé.pdf", b"%PDF".to_vec())
+}
+
+#[get("/download/text")]
+fn download_text() -> Attachment<&'static str> {
+    Attachment::new("notes.csv", "a,b")
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![items, busy, download, download_text]).unwrap()
+}
+
+#[test]
+fn link_pagination() {
+    let client = client();
+    let response = client.get(uri!(items(2))).dispatch();
+    assert_eq!(response.headers().get_one("Link").unwrap(), concat!(
+        "</items?page=1>; rel=first, </items?page=1>; rel=prev, ",
+        "</items?page=3>; rel=next, </items?page=3>; rel=last"
+    ));
+
+    let response = client.get(uri!(items(3))).dispatch();
+    assert_eq!(response.headers().get("Link").count(), 1);
+    assert!(!response.headers().get_one("Link").unwrap().contains("rel=next"));
+}
+
+#[test]
+fn retry_after() {
+    let client = client();
+    let response = client.get(uri!(busy)).dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.headers().get_one("Retry-After"), Some("120"));
+}
+
+#[test]
+fn attachment() {
+    let client = client();
+    let response = client.get(uri!(download)).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::PDF));
+    assert_eq!(response.headers().get_one("Content-Disposition").unwrap(),
+        "attachment; filename=r_sum_.pdf; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf");
+
+    // Non-generic content types set by the responder are kept.
+    let response = client.get(uri!(download_text)).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::Plain));
+    assert_eq!(response.headers().get_one("Content-Disposition"),
+        Some("attachment; filename=notes.csv"));
+}
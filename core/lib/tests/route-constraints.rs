@@ -0,0 +1,54 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+#[get("/docs/<path..: *.md>")]
+fn markdown(path: PathBuf) -> String {
+    format!("markdown: {}", path.display())
+}
+
+#[get("/docs/<path..>", rank = 2)]
+fn other(path: PathBuf) -> String {
+    format!("other: {}", path.display())
+}
+
+#[get("/user/<id: [0-9]+>")]
+fn user(id: u64) -> String {
+    format!("user: {}", id)
+}
+
+#[get("/user/me")]
+fn me() -> &'static str {
+    "me"
+}
+
+#[get("/user/<name: [!0-9]*>", rank = 2)]
+fn named(name: &str) -> String {
+    format!("named: {}", name)
+}
+
+mod tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    #[test]
+    fn constrained_params_route() {
+        let rocket = rocket::build().mount("/", routes![markdown, other, user, me, named]);
+        let client = Client::debug(rocket).unwrap();
+
+        let get = |uri: &str| client.get(uri.to_string()).dispatch().into_string();
+        assert_eq!(get("/docs/README.md").unwrap(), "markdown: README.md");
+        assert_eq!(get("/docs/guide/intro.md").unwrap(), "markdown: guide/intro.md");
+        assert_eq!(get("/docs/guide/intro.txt").unwrap(), "other: guide/intro.txt");
+        assert_eq!(get("/docs/md").unwrap(), "other: md");
+
+        assert_eq!(get("/user/123").unwrap(), "user: 123");
+        assert_eq!(get("/user/me").unwrap(), "me");
+        assert_eq!(get("/user/bob").unwrap(), "named: bob");
+        assert_eq!(get("/user/b%20b").unwrap(), "named: b b");
+
+        let response = client.get("/user/1bob").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
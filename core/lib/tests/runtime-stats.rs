@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use rocket::local::asynchronous::Client;
+
+#[rocket::async_test]
+async fn runtime_stats_are_reported() {
+    let client = Arc::new(Client::tracked(rocket::build()).await.unwrap());
+
+    // The test's future runs outside of the runtime's threads, so only the
+    // runtime's own metrics are available.
+    let stats = client.rocket().runtime_stats().unwrap();
+    assert_eq!(stats.workers, 1);
+    assert!(stats.blocking_threads.is_none());
+    assert!(stats.blocking_saturation().is_none());
+
+    let task_client = client.clone();
+    let stats = rocket::tokio::spawn(async move {
+        let blocking = rocket::tokio::task::spawn_blocking(|| ());
+        blocking.await.unwrap();
+        task_client.rocket().runtime_stats().unwrap()
+    }).await.unwrap();
+
+    assert_eq!(stats.workers, 1);
+    assert!(stats.alive_tasks >= 1);
+    assert!(stats.idle_workers.unwrap() <= 1);
+    assert_eq!(stats.max_blocking_threads, Some(32));
+    assert!(stats.blocking_threads.unwrap() <= 32);
+    assert!(stats.blocking_saturation().unwrap() <= 1.0);
+}
+
+#[test]
+fn runtime_stats_are_none_outside_of_a_runtime() {
+    let client = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    assert!(client.rocket().runtime_stats().is_none());
+}
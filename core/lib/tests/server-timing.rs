@@ -0,0 +1,64 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::local::blocking::Client;
+use rocket::timing::{Metric, ServerTiming};
+
+#[get("/")]
+async fn index(timing: &ServerTiming) -> &'static str {
+    timing.record("db", Duration::from_micros(12_500));
+    timing.record("zero", Duration::ZERO);
+    timing.push(Metric::new("miss").with_description("cache \"miss\""));
+    timing.push(Metric::new("bad name"));
+    timing.time("work", async { }).await;
+    "ok"
+}
+
+#[test]
+fn server_timing_header() {
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .attach(ServerTiming::fairing());
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/").dispatch();
+    let header = response.headers().get_one("Server-Timing").unwrap();
+    let metrics: Vec<_> = header.split(", ").collect();
+    assert_eq!(metrics.len(), 5);
+    assert_eq!(metrics[0], "db;dur=12.5");
+    assert_eq!(metrics[1], "zero;dur=0");
+    assert_eq!(metrics[2], r#"miss;desc="cache \"miss\"""#);
+    assert!(metrics[3].starts_with("work;dur="));
+    assert!(metrics[4].starts_with("total;dur="));
+}
+
+#[test]
+fn no_header_without_fairing() {
+    let client = Client::debug_with(routes![index]).unwrap();
+    let response = client.get("/").dispatch();
+    assert!(response.headers().get_one("Server-Timing").is_none());
+    assert_eq!(response.into_string().unwrap(), "ok");
+}
+
+#[get("/later")]
+fn later(timing: &ServerTiming) -> &'static str {
+    let recorder = timing.recorder();
+    std::thread::spawn(move || recorder.record("thread", Duration::from_millis(2)))
+        .join()
+        .unwrap();
+
+    "ok"
+}
+
+#[test]
+fn recorder_records_into_request_timing() {
+    let rocket = rocket::build()
+        .mount("/", routes![later])
+        .attach(ServerTiming::fairing());
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/later").dispatch();
+    let header = response.headers().get_one("Server-Timing").unwrap();
+    assert!(header.starts_with("thread;dur=2, total;dur="));
+}
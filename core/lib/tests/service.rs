@@ -0,0 +1,107 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use http_body_util::BodyExt;
+use rocket::http::Status;
+
+#[post("/echo", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[get("/remote")]
+fn remote(remote: Option<SocketAddr>) -> String {
+    remote.map(|addr| addr.to_string()).unwrap_or_else(|| "unknown".into())
+}
+
+async fn body_of(response: http::Response<rocket::service::ResponseBody>) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[rocket::async_test]
+async fn service_dispatches_requests() {
+    let service = rocket::build()
+        .mount("/", routes![echo, remote])
+        .into_service()
+        .await
+        .unwrap();
+
+    let request = http::Request::post("/echo").body(String::from("hello")).unwrap();
+    let response = service.handle(request).await;
+    assert_eq!(response.status(), Status::Ok.code);
+    assert_eq!(body_of(response).await, "hello");
+
+    let request = http::Request::get("/remote").body(String::new()).unwrap();
+    assert_eq!(body_of(service.handle(request).await).await, "unknown");
+
+    let addr: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+    let mut request = http::Request::get("/remote").body(String::new()).unwrap();
+    request.extensions_mut().insert(addr);
+    assert_eq!(body_of(service.handle(request).await).await, "10.0.0.1:4000");
+
+    let request = http::Request::get("/missing").body(String::new()).unwrap();
+    let response = service.clone().handle(request).await;
+    assert_eq!(response.status(), Status::NotFound.code);
+}
+
+#[rocket::async_test]
+async fn service_strips_prefix() {
+    let service = rocket::build()
+        .mount("/", routes![echo, remote])
+        .into_service()
+        .await
+        .unwrap()
+        .with_prefix("/legacy/");
+
+    let request = http::Request::post("/legacy/echo?a=b").body(String::from("hi")).unwrap();
+    assert_eq!(body_of(service.handle(request).await).await, "hi");
+
+    for uri in ["/echo", "/legacyecho", "/other/legacy/echo"] {
+        let request = http::Request::post(uri).body(String::from("hi")).unwrap();
+        assert_eq!(service.handle(request).await.status(), Status::NotFound.code, "{uri}");
+    }
+}
+
+#[rocket::async_test]
+async fn service_shares_state_and_terminates_once() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use hyper::service::Service;
+    use rocket::fairing::{Fairing, Info, Kind};
+    use rocket::{Orbit, Rocket};
+
+    struct Count;
+
+    #[rocket::async_trait]
+    impl Fairing for Count {
+        fn info(&self) -> Info {
+            Info { name: "Count", kind: Kind::Shutdown }
+        }
+
+        async fn on_shutdown(&self, rocket: &Rocket<Orbit>) {
+            rocket.state::<Arc<AtomicUsize>>().unwrap().fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let shutdowns = Arc::new(AtomicUsize::new(0));
+    let service = rocket::build()
+        .mount("/", routes![echo])
+        .manage(shutdowns.clone())
+        .attach(Count)
+        .into_service()
+        .await
+        .unwrap();
+
+    let request = http::Request::post("/echo").body(String::from("hyper")).unwrap();
+    let response = Service::call(&service, request).await.unwrap();
+    assert_eq!(body_of(response).await, "hyper");
+
+    assert!(Arc::ptr_eq(service.state::<Arc<AtomicUsize>>().unwrap(), &shutdowns));
+    service.clone().terminate().await;
+    service.terminate().await;
+    service.shutdown().await;
+    assert_eq!(shutdowns.load(Ordering::SeqCst), 1);
+}
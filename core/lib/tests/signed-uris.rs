@@ -0,0 +1,55 @@
+#![cfg(feature = "secrets")]
+
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::config::SecretKey;
+use rocket::http::{Signed, ValidSignature, Status};
+
+#[get("/share/<file>")]
+fn share(file: &str, key: &SecretKey) -> String {
+    Signed::sign(key, uri!(download(file, Some(2))), Duration::from_secs(60)).to_string()
+}
+
+#[get("/download/<file>?<v>")]
+fn download(file: &str, v: Option<usize>, _signature: ValidSignature) -> String {
+    format!("{file} v{}", v.unwrap_or(1))
+}
+
+mod tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn signed_uris_grant_access() {
+        let client = Client::debug_with(routes![share, download]).unwrap();
+        let uri = client.get("/share/a%20b.zip").dispatch().into_string().unwrap();
+        assert!(uri.starts_with("/download/a%20b.zip?v=2&expires="));
+
+        let response = client.get(uri.clone()).dispatch();
+        assert_eq!(response.into_string().unwrap(), "a b.zip v2");
+
+        let unsigned = client.get("/download/a%20b.zip?v=2").dispatch();
+        assert_eq!(unsigned.status(), Status::Forbidden);
+
+        let tampered = client.get(uri.replace("v=2", "v=3")).dispatch();
+        assert_eq!(tampered.status(), Status::Forbidden);
+
+        let appended = client.get(format!("{uri}&v=3")).dispatch();
+        assert_eq!(appended.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn signed_uris_expire() {
+        let client = Client::debug_with(routes![download]).unwrap();
+        let key = &client.rocket().config().secret_key;
+
+        let past = rocket::time::macros::datetime!(2000-01-01 0:00 UTC);
+        let expired = Signed::sign_until(key, uri!("/download/a"), past);
+        assert_eq!(client.get(expired).dispatch().status(), Status::Forbidden);
+
+        let valid = Signed::sign(key, uri!("/download/a"), Duration::from_secs(60));
+        assert_eq!(client.get(valid).dispatch().into_string().unwrap(), "a v1");
+    }
+}
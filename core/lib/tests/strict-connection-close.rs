@@ -0,0 +1,82 @@
+use std::net::{SocketAddr, Ipv4Addr};
+use std::time::Duration;
+
+use rocket::config::{Config, StrictConfig, NormalizeConfig};
+use rocket::listener::tcp::TcpListener;
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rocket::tokio::net::TcpStream;
+use rocket::tokio::time::timeout;
+
+#[rocket::post("/", data = "<body>")]
+fn echo(body: &str) -> &str {
+    body
+}
+
+#[rocket::get("/<path..>")]
+fn any(path: std::path::PathBuf) -> String {
+    path.display().to_string()
+}
+
+/// Writes `request` to a new connection to `addr` and returns everything the
+/// server sends until it closes the connection.
+async fn exchange(addr: SocketAddr, request: &[u8]) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(request).await.unwrap();
+    let mut response = String::new();
+    timeout(Duration::from_secs(5), stream.read_to_string(&mut response))
+        .await
+        .expect("server closes the connection")
+        .unwrap();
+
+    response
+}
+
+#[rocket::async_test]
+async fn rejected_requests_close_the_connection() {
+    let config = Config {
+        strict: StrictConfig { enabled: true, ..Default::default() },
+        normalize: NormalizeConfig { enabled: true, ..Default::default() },
+        ..Config::debug_default()
+    };
+
+    let rocket = rocket::custom(config)
+        .mount("/", rocket::routes![echo, any])
+        .ignite().await
+        .unwrap();
+
+    let bound = rocket.bound_addresses();
+    let shutdown = rocket.shutdown();
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let server = rocket::tokio::spawn(rocket.try_launch_on(TcpListener::bind(addr)));
+    let addr = bound.await[0].tcp().unwrap();
+
+    // The smuggled `GET /smuggled` must never be read as a second request.
+    let response = exchange(addr, b"POST / HTTP/1.1\r\nHost: localhost\r\n\
+        Content-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n\
+        0\r\n\r\nGET /smuggled HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(response.to_ascii_lowercase().contains("\r\nconnection: close\r\n"));
+    assert!(!response.contains("HTTP/1.1 200"));
+    assert!(!response.contains("smuggled"));
+
+    // Normalization rejections close the connection, too.
+    let response = exchange(addr, b"GET /a%2500b HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /next HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(response.to_ascii_lowercase().contains("\r\nconnection: close\r\n"));
+    assert!(!response.contains("next"));
+
+    // Accepted requests keep the connection alive.
+    let response = exchange(addr, b"POST / HTTP/1.1\r\nHost: localhost\r\n\
+        Content-Length: 5\r\n\r\nhello\
+        GET /next HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").await;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("hello"));
+    assert!(response.ends_with("next"));
+
+    shutdown.notify();
+    server.await.unwrap().unwrap();
+}
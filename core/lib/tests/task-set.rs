@@ -0,0 +1,94 @@
+#[macro_use] extern crate rocket;
+
+use std::net::{SocketAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+
+use rocket::config::Config;
+use rocket::fairing::AdHoc;
+use rocket::listener::tcp::TcpListener;
+use rocket::local::asynchronous::Client;
+use rocket::task::TaskSet;
+use rocket::tokio::sync::oneshot;
+use rocket::tokio::task::JoinHandle;
+
+#[get("/spawn")]
+async fn spawn(tasks: &TaskSet) -> String {
+    let handle = tasks.spawn("from handler", async { 7 });
+    format!("{:?}", handle.await.unwrap())
+}
+
+#[async_test]
+async fn tasks_are_tracked_until_complete() {
+    let client = Client::debug_with(routes![spawn]).await.unwrap();
+    let tasks = client.rocket().tasks();
+    assert!(tasks.is_empty());
+
+    let (tx1, rx1) = oneshot::channel::<()>();
+    let (tx2, rx2) = oneshot::channel::<()>();
+    let first = tasks.spawn("first", rx1);
+    let second = tasks.spawn(String::from("second"), rx2);
+
+    let running = tasks.running();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(running[0].name, "first");
+    assert_eq!(running[1].name, "second");
+    assert!(running[0].id < running[1].id);
+
+    tx2.send(()).unwrap();
+    assert_eq!(second.await.unwrap(), Some(Ok(())));
+    assert_eq!(tasks.running().len(), 1);
+    assert_eq!(tasks.running()[0].name, "first");
+
+    first.abort();
+    assert!(first.await.unwrap_err().is_cancelled());
+    assert!(tasks.is_empty());
+    drop(tx1);
+
+    let response = client.get("/spawn").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Some(7)");
+    assert!(tasks.is_empty());
+}
+
+#[async_test]
+async fn panicking_tasks_are_contained() {
+    let client = Client::debug_with(vec![]).await.unwrap();
+    let tasks = client.rocket().tasks();
+
+    let handle = tasks.spawn("panics", async { panic!("boom") });
+    assert_eq!(handle.await.unwrap(), None::<()>);
+
+    let handle = tasks.spawn("panics", async { std::panic::panic_any(42) });
+    assert_eq!(handle.await.unwrap(), None::<()>);
+
+    assert!(tasks.is_empty());
+    assert_eq!(tasks.spawn("ok", async { 1 }).await.unwrap(), Some(1));
+}
+
+#[async_test]
+async fn tasks_are_cancelled_after_grace_period() {
+    let (tx, rx) = oneshot::channel::<JoinHandle<Option<()>>>();
+    let tx = Arc::new(Mutex::new(Some(tx)));
+    let figment = Config::figment()
+        .merge(("shutdown.grace", 0))
+        .merge(("shutdown.mercy", 0));
+
+    let rocket = rocket::custom(figment)
+        .attach(AdHoc::on_liftoff("Spawn", move |rocket| {
+            let tx = tx.clone();
+            Box::pin(async move {
+                let handle = rocket.tasks().spawn("forever", std::future::pending());
+                tx.lock().unwrap().take().unwrap().send(handle).unwrap();
+            })
+        }))
+        .ignite().await
+        .unwrap();
+
+    let shutdown = rocket.shutdown();
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let server = rocket::tokio::spawn(rocket.try_launch_on(TcpListener::bind(addr)));
+
+    let handle = rx.await.unwrap();
+    shutdown.notify();
+    assert_eq!(handle.await.unwrap(), None);
+    server.await.unwrap().unwrap();
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "tower")]
+
+#[macro_use] extern crate rocket;
+
+use std::convert::Infallible;
+
+use http_body_util::BodyExt;
+use rocket::http::Status;
+use rocket::service::{RequestBody, ResponseBody};
+use tower::util::{MapRequestLayer, MapResponseLayer, MapResultLayer};
+use tower::ServiceExt;
+
+#[get("/")]
+fn index(request: &rocket::Request<'_>) -> String {
+    request.headers().get("x-trail").collect::<Vec<_>>().join(",")
+}
+
+fn inner(mut req: http::Request<RequestBody>) -> http::Request<RequestBody> {
+    req.headers_mut().append("x-trail", "inner".parse().unwrap());
+    req
+}
+
+fn outer(mut req: http::Request<RequestBody>) -> http::Request<RequestBody> {
+    req.headers_mut().append("x-trail", "outer".parse().unwrap());
+    req
+}
+
+async fn body_of(response: http::Response<ResponseBody>) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[rocket::async_test]
+async fn layers_wrap_pipeline_in_order() {
+    let service = rocket::build()
+        .mount("/", routes![index])
+        .layer(MapRequestLayer::new(inner))
+        .layer(MapRequestLayer::new(outer))
+        .layer(MapResponseLayer::new(|mut res: http::Response<ResponseBody>| {
+            res.headers_mut().insert("x-layered", "yes".parse().unwrap());
+            res
+        }))
+        .into_service()
+        .await
+        .unwrap();
+
+    let request = http::Request::get("/").body(String::new()).unwrap();
+    let response = service.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), Status::Ok.code);
+    assert_eq!(response.headers()["x-layered"], "yes");
+    assert_eq!(body_of(response).await, "outer,inner");
+}
+
+#[rocket::async_test]
+async fn layer_errors_are_internal_server_errors() {
+    type Result = std::result::Result<http::Response<ResponseBody>, Infallible>;
+
+    let service = rocket::build()
+        .mount("/", routes![index])
+        .layer(MapResultLayer::new(|_: Result| Err::<http::Response<ResponseBody>, _>("nope")))
+        .into_service()
+        .await
+        .unwrap();
+
+    let request = http::Request::get("/").body(String::new()).unwrap();
+    let response = service.handle(request).await;
+    assert_eq!(response.status(), Status::InternalServerError.code);
+}
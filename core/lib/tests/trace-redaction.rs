@@ -0,0 +1,72 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::config::{Config, RedactConfig};
+use rocket::fairing::AdHoc;
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+
+#[get("/")]
+fn index() -> &'static str {
+    "Hello!"
+}
+
+#[test]
+fn redact_config_is_read_from_figment() {
+    let figment = Config::figment()
+        .merge(("redact.headers", ["X-Session"]))
+        .merge(("redact.query", ["sid"]));
+
+    let config = Config::from(figment);
+    assert_eq!(config.redact.headers, ["X-Session"]);
+    assert_eq!(config.redact.query, ["sid"]);
+
+    let header = config.redact.header(Header::new("x-session", "abc"));
+    assert_eq!(header.value(), "[redacted]");
+
+    let header = config.redact.header(Header::new("Authorization", "Bearer abc"));
+    assert_eq!(header.value(), "Bearer abc");
+
+    let uri = config.redact.uri("/?SID=1&token=2&sid");
+    assert_eq!(uri.to_string(), "/?SID=[redacted]&token=2&sid");
+}
+
+#[test]
+fn empty_redact_config_redacts_nothing() {
+    let redact = RedactConfig { headers: vec![], query: vec![], ..Default::default() };
+    let header = redact.header(Header::new("Cookie", "session=abc"));
+    assert_eq!(header.value(), "session=abc");
+    assert_eq!(redact.uri("/?token=abc").to_string(), "/?token=abc");
+}
+
+#[test]
+fn access_log_can_apply_redaction() {
+    let log = Arc::new(Mutex::new(vec![]));
+    let access_log = log.clone();
+    let rocket = rocket::build()
+        .mount("/", routes![index])
+        .attach(AdHoc::on_response("Access Log", move |req, _| {
+            let log = access_log.clone();
+            Box::pin(async move {
+                let redact = &req.rocket().config().redact;
+                let mut lines = log.lock().unwrap();
+                lines.push(redact.uri(req.uri()).to_string());
+                for header in req.headers().iter() {
+                    lines.push(redact.header(header).to_string());
+                }
+            })
+        }));
+
+    let client = Client::debug(rocket).unwrap();
+    client.get("/?q=rocket&access_token=abc")
+        .header(Header::new("Authorization", "Bearer abc"))
+        .header(Header::new("Cookie", "session=abc"))
+        .dispatch();
+
+    let log = log.lock().unwrap();
+    assert_eq!(log[0], "/?q=rocket&access_token=[redacted]");
+    assert!(log.contains(&"Authorization: [redacted]".to_string()));
+    assert!(log.contains(&"Cookie: session=[redacted]".to_string()));
+    assert!(!log.iter().any(|line| line.contains("abc")));
+}
@@ -0,0 +1,127 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{AcceptLanguage, Authorization, ContentDisposition, Forwarded, Prefer};
+use rocket::http::{Header, HeaderError, Status};
+use rocket::local::blocking::Client;
+
+#[get("/lang")]
+fn lang(languages: AcceptLanguage<'_>) -> &'static str {
+    languages.negotiate(&["en", "de"]).unwrap_or("none")
+}
+
+#[get("/auth")]
+fn auth(auth: Authorization<'_>) -> String {
+    match auth.basic() {
+        Some((user, _)) => format!("basic {}", user),
+        None => auth.scheme().to_string(),
+    }
+}
+
+#[get("/auth/error")]
+fn auth_error(auth: Result<Authorization<'_>, HeaderError>) -> String {
+    match auth {
+        Ok(_) => "ok".into(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[put("/upload")]
+fn upload(disposition: ContentDisposition<'_>) -> String {
+    disposition.filename().unwrap_or_default().into_owned()
+}
+
+#[get("/forwarded")]
+fn forwarded(forwarded: Forwarded<'_>) -> String {
+    let ips = forwarded.iter().filter_map(|e| e.for_ip()).map(|ip| ip.to_string());
+    ips.collect::<Vec<_>>().join(" ")
+}
+
+#[get("/prefer")]
+fn prefer(prefer: Option<Prefer<'_>>) -> String {
+    match prefer {
+        Some(prefer) => format!("{:?} {:?}", prefer.returns(), prefer.wait()),
+        None => "none".into(),
+    }
+}
+
+fn client() -> Client {
+    let routes = routes![lang, auth, auth_error, upload, forwarded, prefer];
+    Client::debug_with(routes).unwrap()
+}
+
+fn get(client: &Client, uri: &'static str, headers: &[(&str, &str)]) -> (Status, String) {
+    let mut request = client.get(uri);
+    for (name, value) in headers {
+        request.add_header(Header::new(name.to_string(), value.to_string()));
+    }
+
+    let response = request.dispatch();
+    (response.status(), response.into_string().unwrap_or_default())
+}
+
+#[test]
+fn accept_language_guard() {
+    let client = client();
+    let (status, body) = get(&client, "/lang", &[("Accept-Language", "fr, de-AT;q=0.8")]);
+    assert_eq!((status, body.as_str()), (Status::Ok, "de"));
+
+    let (status, _) = get(&client, "/lang", &[("Accept-Language", "fr;q=nope")]);
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, _) = get(&client, "/lang", &[]);
+    assert_eq!(status, Status::BadRequest);
+}
+
+#[test]
+fn authorization_guard() {
+    let client = client();
+    let (_, body) = get(&client, "/auth", &[("Authorization", "Basic Ym9iOmh1bnRlcjI=")]);
+    assert_eq!(body, "basic bob");
+
+    let (_, body) = get(&client, "/auth", &[("Authorization", "Digest realm=\"x\"")]);
+    assert_eq!(body, "Digest");
+
+    let (status, _) = get(&client, "/auth", &[]);
+    assert_eq!(status, Status::Unauthorized);
+
+    let (status, _) = get(&client, "/auth", &[("Authorization", "Basic !!")]);
+    assert_eq!(status, Status::BadRequest);
+
+    let (_, body) = get(&client, "/auth/error", &[("Authorization", "Bearer a b")]);
+    assert_eq!(body, "malformed `Authorization` header: invalid bearer token");
+
+    let (_, body) = get(&client, "/auth/error", &[]);
+    assert_eq!(body, "missing `Authorization` header");
+}
+
+#[test]
+fn content_disposition_guard() {
+    let client = client();
+    let response = client.put("/upload")
+        .header(Header::new("Content-Disposition", "attachment; filename=\"a b.txt\""))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "a b.txt");
+}
+
+#[test]
+fn forwarded_guard() {
+    let client = client();
+    let headers = [
+        ("Forwarded", "for=192.0.2.60;proto=http"),
+        ("Forwarded", "for=\"[2001:db8::1]:80\", for=unknown"),
+    ];
+
+    let (_, body) = get(&client, "/forwarded", &headers);
+    assert_eq!(body, "192.0.2.60 2001:db8::1");
+}
+
+#[test]
+fn prefer_guard() {
+    let client = client();
+    let (_, body) = get(&client, "/prefer", &[("Prefer", "return=minimal, wait=5")]);
+    assert_eq!(body, "Some(\"minimal\") Some(5)");
+
+    let (_, body) = get(&client, "/prefer", &[]);
+    assert_eq!(body, "none");
+}
@@ -0,0 +1,96 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket::response::Validated;
+use rocket::time::macros::datetime;
+
+const MODIFIED: &str = "Sat, 01 Jun 2024 12:00:00 GMT";
+
+#[get("/article")]
+fn article() -> Validated<&'static str> {
+    Validated::new("article")
+        .last_modified(datetime!(2024-06-01 12:00:00.5 UTC))
+        .version(3)
+}
+
+#[post("/article")]
+fn update() -> Validated<&'static str> {
+    Validated::new("updated").last_modified(datetime!(2024-06-01 12:00 UTC))
+}
+
+#[get("/missing")]
+fn missing() -> Validated<Option<&'static str>> {
+    Validated::new(None).version(1)
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![article, update, missing]).unwrap()
+}
+
+fn get<'c>(client: &'c Client, headers: &[(&'static str, &'static str)]) -> LocalResponse<'c> {
+    let mut request = client.get("/article");
+    for (name, value) in headers {
+        request.add_header(Header::new(*name, *value));
+    }
+
+    request.dispatch()
+}
+
+#[test]
+fn validators_are_set() {
+    let client = client();
+    let response = get(&client, &[]);
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Last-Modified"), Some(MODIFIED));
+    assert_eq!(response.headers().get_one("ETag"), Some("\"3\""));
+    assert_eq!(response.into_string().unwrap(), "article");
+
+    let response = client.get("/missing").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert!(response.headers().get_one("ETag").is_none());
+}
+
+#[test]
+fn if_modified_since() {
+    let client = client();
+    let response = get(&client, &[("If-Modified-Since", MODIFIED)]);
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("Last-Modified"), Some(MODIFIED));
+    assert_eq!(response.headers().get_one("ETag"), Some("\"3\""));
+    assert!(response.into_string().is_none());
+
+    let response = get(&client, &[("If-Modified-Since", "Sun, 02 Jun 2024 00:00:00 GMT")]);
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = get(&client, &[("If-Modified-Since", "Sat, 01 Jun 2024 11:59:59 GMT")]);
+    assert_eq!(response.status(), Status::Ok);
+
+    // Invalid dates are ignored.
+    let response = get(&client, &[("If-Modified-Since", "yesterday")]);
+    assert_eq!(response.status(), Status::Ok);
+
+    // Only `GET` and `HEAD` requests are conditional.
+    let response = client.head("/article").header(Header::new("If-Modified-Since", MODIFIED));
+    assert_eq!(response.dispatch().status(), Status::NotModified);
+
+    let response = client.post("/article").header(Header::new("If-Modified-Since", MODIFIED));
+    assert_eq!(response.dispatch().status(), Status::Ok);
+}
+
+#[test]
+fn if_none_match() {
+    let client = client();
+    let response = get(&client, &[("If-None-Match", "\"2\", W/\"3\"")]);
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = get(&client, &[("If-None-Match", "*")]);
+    assert_eq!(response.status(), Status::NotModified);
+
+    let response = get(&client, &[("If-None-Match", "\"2\"")]);
+    assert_eq!(response.status(), Status::Ok);
+
+    // `If-Modified-Since` is ignored when `If-None-Match` is present.
+    let headers = [("If-None-Match", "\"2\""), ("If-Modified-Since", MODIFIED)];
+    assert_eq!(get(&client, &headers).status(), Status::Ok);
+}
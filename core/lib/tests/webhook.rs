@@ -0,0 +1,94 @@
+#![cfg(feature = "webhook")]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::webhook::{SignedPayload, GitHub, Stripe, Hmac};
+
+const BODY: &str = "The quick brown fox jumps over the lazy dog";
+
+// HMAC-SHA256 of `BODY` with the key `key`.
+const SIGNATURE: &str = "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+
+#[post("/github", data = "<body>")]
+fn github(body: SignedPayload<String, GitHub>) -> String {
+    body.into_inner()
+}
+
+#[post("/stripe", data = "<body>")]
+fn stripe(body: SignedPayload<String, Stripe>) -> String {
+    body.into_inner()
+}
+
+#[post("/hmac", data = "<body>")]
+fn hmac(body: SignedPayload<String, Hmac>) -> String {
+    body.into_inner()
+}
+
+fn client() -> Client {
+    let figment = rocket::Config::figment()
+        .merge(("webhooks.github", "key"))
+        .merge(("webhooks.stripe", "key"));
+
+    Client::debug(rocket::custom(figment).mount("/", routes![github, stripe, hmac])).unwrap()
+}
+
+#[test]
+fn github_signature_is_verified() {
+    let client = client();
+    let response = client.post("/github")
+        .header(Header::new("X-Hub-Signature-256", format!("sha256={SIGNATURE}")))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), BODY);
+
+    let response = client.post("/github")
+        .header(Header::new("X-Hub-Signature-256", format!("sha256={SIGNATURE}")))
+        .body("The quick brown fox jumps over the lazy cat")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.post("/github")
+        .header(Header::new("X-Hub-Signature-256", SIGNATURE))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.post("/github").body(BODY).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn stripe_signature_timestamp_is_checked() {
+    let client = client();
+    let header = format!("t=1492774577,v1={SIGNATURE}");
+    let response = client.post("/stripe")
+        .header(Header::new("Stripe-Signature", header))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client.post("/stripe")
+        .header(Header::new("Stripe-Signature", format!("v1={SIGNATURE}")))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[test]
+fn missing_secret_is_an_error() {
+    let client = client();
+    let response = client.post("/hmac")
+        .header(Header::new("X-Signature", SIGNATURE))
+        .body(BODY)
+        .dispatch();
+
+    assert_eq!(response.status(), Status::InternalServerError);
+}
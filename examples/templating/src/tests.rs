@@ -2,7 +2,6 @@ use super::rocket;
 
 use rocket::http::{RawStr, Status, Method::*};
 use rocket::local::blocking::Client;
-use rocket_dyn_templates::{Template, context};
 
 fn test_root(kind: &str) {
     // Check that the redirect works.
@@ -16,15 +15,16 @@ fn test_root(kind: &str) {
         assert_eq!(location, format!("/{}/hello/Your%20Name", kind));
     }
 
-    // Check that other request methods are not accepted (and instead caught).
-    for method in &[Post, Put, Delete, Options, Trace, Connect, Patch] {
-        let context = context! { uri: format!("/{}", kind) };
-        let expected = Template::show(client.rocket(), format!("{}/error/404", kind), &context);
-
+    // Check that other request methods are not allowed.
+    for method in &[Post, Put, Delete, Trace, Connect, Patch] {
         let response = client.req(*method, format!("/{}", kind)).dispatch();
-        assert_eq!(response.status(), Status::NotFound);
-        assert_eq!(response.into_string(), expected);
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, OPTIONS"));
     }
+
+    let response = client.req(Options, format!("/{}", kind)).dispatch();
+    assert_eq!(response.status(), Status::NoContent);
+    assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, OPTIONS"));
 }
 
 fn test_name(base: &str) {
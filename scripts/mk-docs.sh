@@ -30,5 +30,24 @@ pushd "${PROJECT_ROOT}" > /dev/null 2>&1
         -p rocket_db_pools \
         -p rocket_sync_db_pools \
         -p rocket_dyn_templates \
-        -p rocket_ws
+        -p rocket_ws \
+        -p rocket_tasks \
+        -p rocket_cache \
+        -p rocket_idempotency \
+        -p rocket_object_store \
+        -p rocket_mail \
+        -p rocket_events \
+        -p rocket_coalesce \
+        -p rocket_wasm \
+        -p rocket_lambda \
+        -p rocket_flags \
+        -p rocket_resilience \
+        -p rocket_client \
+        -p rocket_live_reload \
+        -p rocket_apikey \
+        -p rocket_sanitize \
+        -p rocket_batch \
+        -p rocket_spa \
+        -p rocket_assets \
+        -p rocket_well_known
 popd > /dev/null 2>&1
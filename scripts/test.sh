@@ -123,12 +123,70 @@ function test_contrib() {
     echo ":: Building and testing ws [$feature]..."
     $CARGO test -p rocket_ws --no-default-features --features $feature $@
   done
+
+  echo ":: Building and testing tasks..."
+  $CARGO test -p rocket_tasks $@
+
+  echo ":: Building and testing cache..."
+  $CARGO test -p rocket_cache $@
+
+  echo ":: Building and testing idempotency..."
+  $CARGO test -p rocket_idempotency $@
+
+  echo ":: Building and testing object_store..."
+  $CARGO test -p rocket_object_store $@
+
+  echo ":: Building and testing mail..."
+  $CARGO test -p rocket_mail $@
+
+  echo ":: Building and testing events..."
+  $CARGO test -p rocket_events --all-features $@
+
+  echo ":: Building and testing coalesce..."
+  $CARGO test -p rocket_coalesce $@
+
+  echo ":: Building and testing wasm..."
+  $CARGO test -p rocket_wasm $@
+
+  echo ":: Building and testing lambda..."
+  $CARGO test -p rocket_lambda $@
+
+  echo ":: Building and testing flags..."
+  $CARGO test -p rocket_flags $@
+
+  echo ":: Building and testing resilience..."
+  $CARGO test -p rocket_resilience $@
+
+  echo ":: Building and testing client..."
+  $CARGO test -p rocket_client $@
+
+  echo ":: Building and testing live_reload..."
+  $CARGO test -p rocket_live_reload $@
+
+  echo ":: Building and testing apikey..."
+  $CARGO test -p rocket_apikey $@
+
+  echo ":: Building and testing sanitize..."
+  $CARGO test -p rocket_sanitize --all-features $@
+
+  echo ":: Building and testing batch..."
+  $CARGO test -p rocket_batch $@
+
+  echo ":: Building and testing spa..."
+  $CARGO test -p rocket_spa --all-features $@
+
+  echo ":: Building and testing assets..."
+  $CARGO test -p rocket_assets --all-features $@
+
+  echo ":: Building and testing well_known..."
+  $CARGO test -p rocket_well_known $@
 }
 
 function test_core() {
   FEATURES=(
     tokio-macros
     http2
+    multipart
     http3-preview
     secrets
     tls
@@ -137,6 +195,11 @@ function test_core() {
     msgpack
     uuid
     trace
+    webhook
+    plugins
+    tower
+    html
+    nfc
   )
 
   echo ":: Building and checking core [no features]..."